@@ -55,6 +55,9 @@ import { shouldUseDesktopDaemon } from "@/desktop/daemon/desktop-daemon";
 import { listenToDesktopEvent } from "@/desktop/electron/events";
 import { updateDesktopWindowControls } from "@/desktop/electron/window";
 import { getDesktopHost } from "@/desktop/host";
+import { useAutomationActions } from "@/desktop/hooks/use-automation-actions";
+import { useQuickActions } from "@/desktop/hooks/use-quick-actions";
+import { applyDesktopInitialState } from "@/desktop/initial-state";
 import { RosettaCalloutSource } from "@/desktop/updates/rosetta-callout-source";
 import { UpdateCalloutSource } from "@/desktop/updates/update-callout-source";
 import { useActiveWorktreeNewAction } from "@/hooks/use-active-worktree-new-action";
@@ -102,6 +105,7 @@ import { resolveWorkspaceIdByExecutionDirectory } from "@/utils/workspace-execut
 import { prepareWorkspaceTab } from "@/utils/workspace-navigation";
 
 polyfillCrypto();
+applyDesktopInitialState();
 
 export interface HostRuntimeBootstrapState {
   splashError: string | null;
@@ -449,6 +453,7 @@ function AppContainer({
   });
 
   useActiveWorktreeNewAction();
+  useQuickActions();
 
   const containerStyle = useMemo(
     () => ({ flex: 1 as const, backgroundColor: theme.colors.surface0 }),
@@ -791,6 +796,8 @@ function AppWithSidebar({ children }: { children: ReactNode }) {
     return match ? `${match.serverId}:${match.agentId}` : undefined;
   }, [params.open, pathname]);
 
+  useAutomationActions(selectedAgentKey);
+
   return (
     <AppContainer
       selectedAgentId={shouldShowAppChrome ? selectedAgentKey : undefined}
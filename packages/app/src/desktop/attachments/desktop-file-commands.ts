@@ -41,12 +41,20 @@ export async function copyDesktopAttachmentFile(input: {
   });
 }
 
-export async function deleteDesktopAttachmentFile(input: { path: string }): Promise<boolean> {
+export async function deleteDesktopAttachmentFile(input: {
+  path: string;
+  permanent?: boolean;
+}): Promise<boolean> {
   return await invokeDesktopCommand<boolean>("delete_attachment_file", {
     path: input.path,
+    permanent: input.permanent ?? false,
   });
 }
 
+export async function moveDesktopPathToTrash(input: { path: string }): Promise<boolean> {
+  return await invokeDesktopCommand<boolean>("move_to_trash", { path: input.path });
+}
+
 export async function garbageCollectDesktopAttachmentFiles(input: {
   referencedIds: readonly string[];
 }): Promise<number> {
@@ -37,6 +37,7 @@ const { alertMock, confirmDialogMock, settingsState, daemonStatusState } = vi.ho
         home: "/tmp/paseo",
         version: "1.2.3",
         desktopManaged: true,
+        tls: false,
         error: null,
       },
       logs: {
@@ -0,0 +1,124 @@
+import { useCallback, useMemo, useState } from "react";
+import { Text, View } from "react-native";
+import { useFocusEffect } from "@react-navigation/native";
+import { StyleSheet } from "react-native-unistyles";
+import { Button } from "@/components/ui/button";
+import { settingsStyles } from "@/styles/settings";
+import { SettingsSection } from "@/screens/settings/settings-section";
+import {
+  listWorkspaceTrustDecisions,
+  revokeWorkspaceTrust,
+  type WorkspaceTrustDecision,
+} from "@/desktop/settings/workspace-trust";
+
+const ROW_WITH_BORDER_STYLE = [settingsStyles.row, settingsStyles.rowBorder];
+
+export function WorkspaceTrustSection() {
+  const [decisions, setDecisions] = useState<WorkspaceTrustDecision[] | null>(null);
+  const [revokingPath, setRevokingPath] = useState<string | null>(null);
+
+  const loadDecisions = useCallback(() => {
+    void listWorkspaceTrustDecisions()
+      .then(setDecisions)
+      .catch((error) => {
+        console.error("[WorkspaceTrust] Failed to load decisions", error);
+      });
+  }, []);
+
+  useFocusEffect(
+    useCallback(() => {
+      loadDecisions();
+      return undefined;
+    }, [loadDecisions]),
+  );
+
+  const handleRevoke = useCallback(
+    (path: string) => {
+      if (revokingPath) return;
+      setRevokingPath(path);
+      void revokeWorkspaceTrust(path)
+        .catch((error) => {
+          console.error("[WorkspaceTrust] Failed to revoke decision", error);
+        })
+        .finally(() => {
+          setRevokingPath(null);
+          loadDecisions();
+        });
+    },
+    [loadDecisions, revokingPath],
+  );
+
+  return (
+    <SettingsSection title="Workspace trust">
+      <View style={settingsStyles.card}>
+        {decisions && decisions.length > 0 ? (
+          decisions.map((decision, index) => (
+            <WorkspaceTrustRow
+              key={decision.path}
+              decision={decision}
+              showBorder={index > 0}
+              isRevoking={revokingPath === decision.path}
+              onRevoke={handleRevoke}
+            />
+          ))
+        ) : (
+          <View style={settingsStyles.row}>
+            <Text style={styles.emptyText}>
+              {decisions
+                ? "No folders yet. Paseo asks before running anything in a new folder."
+                : "Loading..."}
+            </Text>
+          </View>
+        )}
+      </View>
+    </SettingsSection>
+  );
+}
+
+function WorkspaceTrustRow({
+  decision,
+  showBorder,
+  isRevoking,
+  onRevoke,
+}: {
+  decision: WorkspaceTrustDecision;
+  showBorder: boolean;
+  isRevoking: boolean;
+  onRevoke: (path: string) => void;
+}) {
+  const handlePress = useCallback(() => onRevoke(decision.path), [decision.path, onRevoke]);
+  const hint = useMemo(() => {
+    const verdict = decision.trusted ? "Trusted" : "Not trusted";
+    const decidedAt = decision.decidedAt ? new Date(decision.decidedAt) : null;
+    return decidedAt && !Number.isNaN(decidedAt.getTime())
+      ? `${verdict} on ${decidedAt.toLocaleDateString()}`
+      : verdict;
+  }, [decision.decidedAt, decision.trusted]);
+
+  return (
+    <View style={showBorder ? ROW_WITH_BORDER_STYLE : settingsStyles.row}>
+      <View style={settingsStyles.rowContent}>
+        <Text style={settingsStyles.rowTitle} numberOfLines={1}>
+          {decision.path}
+        </Text>
+        <Text style={settingsStyles.rowHint}>{hint}</Text>
+      </View>
+      <Button
+        variant="outline"
+        size="sm"
+        onPress={handlePress}
+        disabled={isRevoking}
+        accessibilityLabel={`Forget the trust decision for ${decision.path}`}
+      >
+        {isRevoking ? "Forgetting..." : "Forget"}
+      </Button>
+    </View>
+  );
+}
+
+const styles = StyleSheet.create((theme) => ({
+  emptyText: {
+    color: theme.colors.foregroundMuted,
+    fontSize: theme.fontSize.sm,
+  },
+}));
@@ -0,0 +1,22 @@
+import { describe, expect, it } from "vitest";
+import { parseDesktopDaemonAuthState } from "./desktop-daemon-auth";
+
+describe("parseDesktopDaemonAuthState", () => {
+  it("keeps the fields the renderer acts on", () => {
+    expect(
+      parseDesktopDaemonAuthState({
+        serverId: "srv",
+        status: "expired",
+        expiresAt: "2026-01-01T00:00:00.000Z",
+        updatedAt: "2025-12-31T00:00:00.000Z",
+        persisted: true,
+      }),
+    ).toEqual({ serverId: "srv", status: "expired", expiresAt: "2026-01-01T00:00:00.000Z" });
+  });
+
+  it("rejects unknown statuses and missing server ids", () => {
+    expect(parseDesktopDaemonAuthState({ serverId: "srv", status: "refreshing" })).toBeNull();
+    expect(parseDesktopDaemonAuthState({ status: "authenticated" })).toBeNull();
+    expect(parseDesktopDaemonAuthState(null)).toBeNull();
+  });
+});
@@ -0,0 +1,96 @@
+import type { DaemonClientConfig } from "@server/client/daemon-client";
+import { isElectronRuntime } from "@/desktop/host";
+import { invokeDesktopCommand } from "@/desktop/electron/invoke";
+import { listenToDesktopEvent, type DesktopEventUnlisten } from "@/desktop/electron/events";
+
+export type DesktopDaemonAuthStatus = "authenticated" | "expired" | "signed_out";
+
+/** Daemon bearer token state, as the desktop main process keeps it. */
+export interface DesktopDaemonAuthState {
+  serverId: string;
+  status: DesktopDaemonAuthStatus;
+  expiresAt: string | null;
+}
+
+function isRecord(value: unknown): value is Record<string, unknown> {
+  return typeof value === "object" && value !== null;
+}
+
+export function parseDesktopDaemonAuthState(value: unknown): DesktopDaemonAuthState | null {
+  if (!isRecord(value) || typeof value.serverId !== "string") {
+    return null;
+  }
+  const status = value.status;
+  if (status !== "authenticated" && status !== "expired" && status !== "signed_out") {
+    return null;
+  }
+  return {
+    serverId: value.serverId,
+    status,
+    expiresAt: typeof value.expiresAt === "string" ? value.expiresAt : null,
+  };
+}
+
+export async function getDesktopDaemonAuthState(
+  serverId: string,
+): Promise<DesktopDaemonAuthState> {
+  const state = parseDesktopDaemonAuthState(
+    await invokeDesktopCommand<unknown>("get_auth_state", { serverId }),
+  );
+  if (!state) {
+    throw new Error("Unexpected daemon auth state response.");
+  }
+  return state;
+}
+
+/**
+ * Hands a daemon password to the main process, which keeps it in the OS
+ * keychain and only sends it to `endpoints`, the daemon URLs it is for.
+ */
+export async function setDesktopDaemonAuthToken(input: {
+  serverId: string;
+  token: string;
+  endpoints: string[];
+}): Promise<void> {
+  await invokeDesktopCommand("set_daemon_auth_token", input);
+}
+
+/** The password the main process holds for `url`, or `null` when it has none for it. */
+export async function getDesktopDaemonAuthToken(input: {
+  serverId: string;
+  url: string;
+}): Promise<string | null> {
+  const token = await invokeDesktopCommand<unknown>("get_daemon_auth_token", input);
+  return typeof token === "string" ? token : null;
+}
+
+/**
+ * Password options for a direct connection to `url`. On desktop the password
+ * is pulled from the main process before every attempt; one still saved on the
+ * connection only covers the time until it has been handed over.
+ */
+export function resolveDirectConnectionAuth(input: {
+  serverId?: string;
+  url: string;
+  password?: string;
+}): Pick<DaemonClientConfig, "password" | "resolvePassword"> {
+  const { serverId, url, password } = input;
+  if (!serverId || !isElectronRuntime()) {
+    return password ? { password } : {};
+  }
+  return {
+    resolvePassword: async () =>
+      (await getDesktopDaemonAuthToken({ serverId, url }).catch(() => null)) ?? password ?? null,
+  };
+}
+
+export function listenToDesktopDaemonAuthState(
+  handler: (state: DesktopDaemonAuthState) => void,
+): Promise<DesktopEventUnlisten> {
+  return listenToDesktopEvent<unknown>("auth-state-changed", (payload) => {
+    const state = parseDesktopDaemonAuthState(payload);
+    if (state) {
+      handler(state);
+    }
+  });
+}
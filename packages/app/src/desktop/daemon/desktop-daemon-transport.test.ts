@@ -132,4 +132,22 @@ describe("desktop-daemon-transport", () => {
     expect(desktopDaemonMock.closeLocalTransportSession).toHaveBeenCalledWith("local-session-2");
     expect(cleanup).toHaveBeenCalledTimes(1);
   });
+
+  it("carries the server id to the main process so it can attach the daemon password", async () => {
+    desktopDaemonMock.openLocalTransportSession.mockResolvedValue("local-session-3");
+
+    const mod = await import("./desktop-daemon-transport");
+    const url = mod.buildLocalDaemonTransportUrl({
+      transportType: "socket",
+      transportPath: "/tmp/paseo.sock",
+      serverId: "srv-1",
+    });
+    mod.createDesktopLocalDaemonTransportFactory()!({ url });
+
+    expect(desktopDaemonMock.openLocalTransportSession).toHaveBeenCalledWith({
+      transportType: "socket",
+      transportPath: "/tmp/paseo.sock",
+      serverId: "srv-1",
+    });
+  });
 });
@@ -30,6 +30,9 @@ function decodeBase64ToBytes(base64: string): Uint8Array {
 export function buildLocalDaemonTransportUrl(target: LocalTransportTarget): string {
   const url = new URL(`${LOCAL_TRANSPORT_SCHEME}//${target.transportType}`);
   url.searchParams.set("path", target.transportPath);
+  if (target.serverId) {
+    url.searchParams.set("serverId", target.serverId);
+  }
   return url.toString();
 }
 
@@ -43,9 +46,11 @@ function parseLocalDaemonTransportUrl(url: string): LocalTransportTarget {
   if ((transportType !== "socket" && transportType !== "pipe") || !transportPath) {
     throw new Error(`Invalid local transport target: ${url}`);
   }
+  const serverId = parsed.searchParams.get("serverId")?.trim();
   return {
     transportType,
     transportPath,
+    ...(serverId ? { serverId } : {}),
   };
 }
 
@@ -12,6 +12,8 @@ export interface DesktopDaemonStatus {
   home: string;
   version: string | null;
   desktopManaged: boolean;
+  /** The daemon serves WSS on its TCP listen address, with a certificate the app trusts. */
+  tls: boolean;
   error: string | null;
 }
 
@@ -30,6 +32,8 @@ export interface LocalTransportTarget {
   [key: string]: unknown;
   transportType: "socket" | "pipe";
   transportPath: string;
+  /** Lets the main process attach the daemon password it keeps for this server. */
+  serverId?: string;
 }
 
 interface LocalTransportEventPayload {
@@ -85,6 +89,7 @@ function parseDesktopDaemonStatus(raw: unknown): DesktopDaemonStatus {
     home: toStringOrNull(raw.home) ?? "",
     version: toStringOrNull(raw.version),
     desktopManaged: raw.desktopManaged === true,
+    tls: raw.tls === true,
     error: toStringOrNull(raw.error),
   };
 }
@@ -156,7 +161,7 @@ export async function listenToLocalTransportEvents(
   if (typeof listen !== "function") {
     throw new Error("Desktop events API is unavailable.");
   }
-  const unlisten = await listen("local-daemon-transport-event", (payload: unknown) => {
+  const handlePayload = (payload: unknown) => {
     if (!isRecord(payload)) {
       return;
     }
@@ -169,8 +174,22 @@ export async function listenToLocalTransportEvents(
       reason: toStringOrNull(payload.reason),
       error: toStringOrNull(payload.error),
     });
+  };
+  const unlisten = await listen("local-daemon-transport-event", handlePayload);
+  // With event batching on, consecutive messages of one session arrive together.
+  const unlistenBatch = await listen("local-daemon-transport-batch", (payload: unknown) => {
+    if (isRecord(payload) && Array.isArray(payload.events)) {
+      payload.events.forEach(handlePayload);
+    }
   });
-  return typeof unlisten === "function" ? unlisten : () => {};
+  return () => {
+    if (typeof unlisten === "function") {
+      unlisten();
+    }
+    if (typeof unlistenBatch === "function") {
+      unlistenBatch();
+    }
+  };
 }
 
 export async function openLocalTransportSession(target: LocalTransportTarget): Promise<string> {
@@ -42,3 +42,21 @@ export async function updateDesktopWindowControls(
 
   await win.updateWindowControls(update);
 }
+
+/** Lets the desktop app clear unread counts for the agent this window shows. */
+export async function setDesktopViewedAgent(agentId: string | null): Promise<void> {
+  const win = getDesktopWindow();
+  if (!win || typeof win.setViewedAgent !== "function") {
+    return;
+  }
+  await win.setViewedAgent(agentId);
+}
+
+/** Counts what the desktop app cannot see itself: hosts reached without the local transport. */
+export async function reportDesktopUnreadCount(count: number): Promise<void> {
+  const win = getDesktopWindow();
+  if (!win || typeof win.reportUnreadCount !== "function") {
+    return;
+  }
+  await win.reportUnreadCount(count);
+}
@@ -0,0 +1,107 @@
+import { useEffect, useRef } from "react";
+import { router } from "expo-router";
+import { getDesktopDaemonStatus, shouldUseDesktopDaemon } from "@/desktop/daemon/desktop-daemon";
+import { listenToDesktopEvent } from "@/desktop/electron/events";
+import { getHostRuntimeStore } from "@/runtime/host-runtime";
+import { buildHostAgentDetailRoute } from "@/utils/host-routes";
+
+interface AutomationOpenAgentPayload {
+  agentId?: unknown;
+}
+
+interface AutomationSendPromptPayload {
+  agentId?: unknown;
+  text?: unknown;
+}
+
+function toAgentId(value: unknown): string | null {
+  return typeof value === "string" && value.trim() ? value.trim() : null;
+}
+
+async function getLocalServerId(): Promise<string | null> {
+  const { serverId } = await getDesktopDaemonStatus();
+  return serverId || null;
+}
+
+async function openAgent(agentId: string): Promise<void> {
+  const serverId = await getLocalServerId();
+  if (serverId) {
+    router.navigate(buildHostAgentDetailRoute(serverId, agentId) as never);
+  }
+}
+
+async function sendPrompt(input: {
+  agentId: string | null;
+  text: string;
+  selectedAgentKey: string | undefined;
+}): Promise<void> {
+  const serverId = await getLocalServerId();
+  if (!serverId) {
+    return;
+  }
+  const selectedPrefix = `${serverId}:`;
+  const agentId =
+    input.agentId ??
+    (input.selectedAgentKey?.startsWith(selectedPrefix)
+      ? input.selectedAgentKey.slice(selectedPrefix.length)
+      : null);
+  const client = getHostRuntimeStore().getClient(serverId);
+  if (!agentId || !client) {
+    return;
+  }
+  await client.sendAgentMessage(agentId, input.text);
+}
+
+/**
+ * Carries out the control API's `open-agent` and `send-prompt` requests, which
+ * the main process hands over because it has no daemon client of its own. A
+ * prompt without an agent goes to the agent on screen, if it belongs to the
+ * local daemon.
+ */
+export function useAutomationActions(selectedAgentKey: string | undefined): void {
+  const selectedAgentKeyRef = useRef(selectedAgentKey);
+  selectedAgentKeyRef.current = selectedAgentKey;
+
+  useEffect(() => {
+    if (!shouldUseDesktopDaemon()) {
+      return;
+    }
+    let disposed = false;
+    const unlisteners: Array<() => void> = [];
+    const keep = (dispose: () => void) => {
+      if (disposed) {
+        dispose();
+        return;
+      }
+      unlisteners.push(dispose);
+    };
+
+    void listenToDesktopEvent<AutomationOpenAgentPayload>("automation-open-agent", (payload) => {
+      const agentId = toAgentId(payload?.agentId);
+      if (!disposed && agentId) {
+        void openAgent(agentId).catch(() => undefined);
+      }
+    })
+      .then(keep)
+      .catch(() => undefined);
+
+    void listenToDesktopEvent<AutomationSendPromptPayload>("automation-send-prompt", (payload) => {
+      const text = typeof payload?.text === "string" ? payload.text : "";
+      if (disposed || !text.trim()) {
+        return;
+      }
+      void sendPrompt({
+        agentId: toAgentId(payload?.agentId),
+        text,
+        selectedAgentKey: selectedAgentKeyRef.current,
+      }).catch(() => undefined);
+    })
+      .then(keep)
+      .catch(() => undefined);
+
+    return () => {
+      disposed = true;
+      unlisteners.forEach((dispose) => dispose());
+    };
+  }, []);
+}
@@ -0,0 +1,94 @@
+import { useEffect } from "react";
+import { router } from "expo-router";
+import { getDesktopDaemonStatus, shouldUseDesktopDaemon } from "@/desktop/daemon/desktop-daemon";
+import { invokeDesktopCommand } from "@/desktop/electron/invoke";
+import { listenToDesktopEvent } from "@/desktop/electron/events";
+import { buildNewWorkspaceDraftKey } from "@/screens/new-workspace-screen";
+import { useDraftStore } from "@/stores/draft-store";
+import { buildHostNewWorkspaceRoute } from "@/utils/host-routes";
+
+/** A `paseo://new-agent` link the user already confirmed in the main process. */
+export interface NewAgentQuickAction {
+  kind: "new-agent";
+  cwd: string;
+  prompt: string | null;
+}
+
+export function parseNewAgentQuickAction(value: unknown): NewAgentQuickAction | null {
+  if (typeof value !== "object" || value === null) {
+    return null;
+  }
+  const record = value as Record<string, unknown>;
+  if (record.kind !== "new-agent" || typeof record.cwd !== "string" || !record.cwd.trim()) {
+    return null;
+  }
+  return {
+    kind: "new-agent",
+    cwd: record.cwd,
+    prompt: typeof record.prompt === "string" && record.prompt.trim() ? record.prompt : null,
+  };
+}
+
+async function runNewAgentQuickAction(action: NewAgentQuickAction): Promise<void> {
+  const { serverId } = await getDesktopDaemonStatus();
+  if (!serverId) {
+    return;
+  }
+  if (action.prompt) {
+    useDraftStore.getState().saveDraftInput({
+      draftKey: buildNewWorkspaceDraftKey(serverId, action.cwd),
+      draft: { text: action.prompt, attachments: [], cwd: action.cwd },
+    });
+  }
+  router.navigate(buildHostNewWorkspaceRoute(serverId, action.cwd) as never);
+}
+
+function runQuickAction(value: unknown): void {
+  const action = parseNewAgentQuickAction(value);
+  if (action) {
+    void runNewAgentQuickAction(action).catch(() => undefined);
+  }
+}
+
+/**
+ * Opens the new-workspace screen for confirmed `paseo://new-agent` links,
+ * including ones that arrived before the window loaded.
+ */
+export function useQuickActions(): void {
+  useEffect(() => {
+    if (!shouldUseDesktopDaemon()) {
+      return;
+    }
+    let disposed = false;
+    let unlisten: (() => void) | null = null;
+
+    void invokeDesktopCommand<unknown>("take_pending_quick_actions")
+      .then((pending) => {
+        if (!disposed && Array.isArray(pending)) {
+          pending.forEach(runQuickAction);
+        }
+        return;
+      })
+      .catch(() => undefined);
+
+    void listenToDesktopEvent<unknown>("quick-action", (payload) => {
+      if (!disposed) {
+        runQuickAction(payload);
+      }
+    })
+      .then((dispose) => {
+        if (disposed) {
+          dispose();
+          return;
+        }
+        unlisten = dispose;
+        return;
+      })
+      .catch(() => undefined);
+
+    return () => {
+      disposed = true;
+      unlisten?.();
+    };
+  }, []);
+}
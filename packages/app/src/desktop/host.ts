@@ -55,7 +55,8 @@ export interface DesktopWindowBridge {
   onResized?: <TEvent = unknown>(
     handler: (event: TEvent) => void,
   ) => Promise<() => void> | (() => void);
-  setBadgeCount?: (count?: number) => Promise<void>;
+  setViewedAgent?: (agentId: string | null) => Promise<void>;
+  reportUnreadCount?: (count: number) => Promise<void>;
   onDragDropEvent?: <TEvent = unknown>(
     handler: (event: TEvent) => void,
   ) => Promise<() => void> | (() => void);
@@ -82,8 +83,24 @@ export interface DesktopInvokeBridge {
   invoke?: (command: string, args?: Record<string, unknown>) => Promise<unknown>;
 }
 
+/** Values the desktop app hands the renderer before it runs; each key may be missing. */
+export interface DesktopInitialState {
+  zoom?: { userFactor: number; systemTextScale: number; effectiveFactor: number };
+  theme?: "light" | "dark";
+  locale?: {
+    locale: string;
+    preferredLanguages: string[];
+    clockFormat: "12h" | "24h";
+    firstDayOfWeek: number;
+  };
+  profile?: { packaged: boolean; devWorktreeName: string | null };
+  featureFlags?: Record<string, boolean>;
+  [key: string]: unknown;
+}
+
 export interface DesktopHostBridge {
   platform?: string;
+  initialState?: DesktopInitialState;
   invoke?: DesktopInvokeBridge["invoke"];
   getPendingOpenProject?: () => Promise<string | null>;
   events?: DesktopEventsBridge;
@@ -108,6 +125,10 @@ export function getDesktopHost(): DesktopHostBridge | null {
   return getElectronHost();
 }
 
+export function getDesktopInitialState(): DesktopInitialState {
+  return getDesktopHost()?.initialState ?? {};
+}
+
 export function isElectronRuntime(): boolean {
   return getDesktopHost() !== null;
 }
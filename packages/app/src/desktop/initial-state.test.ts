@@ -0,0 +1,30 @@
+import { describe, expect, it } from "vitest";
+import { applyDesktopInitialState } from "./initial-state";
+
+describe("applyDesktopInitialState", () => {
+  it("sets the document language from the system locale", () => {
+    const root = { lang: "" };
+
+    applyDesktopInitialState(
+      {
+        locale: {
+          locale: "de-DE",
+          preferredLanguages: ["de-DE", "en-US"],
+          clockFormat: "24h",
+          firstDayOfWeek: 1,
+        },
+      },
+      root,
+    );
+
+    expect(root.lang).toBe("de-DE");
+  });
+
+  it("leaves the document alone when the desktop app sent no locale", () => {
+    const root = { lang: "en" };
+
+    applyDesktopInitialState({}, root);
+
+    expect(root.lang).toBe("en");
+  });
+});
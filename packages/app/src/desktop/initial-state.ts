@@ -0,0 +1,25 @@
+import { getDesktopInitialState, type DesktopInitialState } from "@/desktop/host";
+
+/**
+ * Applies what the desktop app knew at window creation before React renders.
+ * The preload already set the zoom; here the document takes the system
+ * locale, so text shaping, hyphenation and spellcheck match from first paint.
+ */
+export function applyDesktopInitialState(
+  state: DesktopInitialState = getDesktopInitialState(),
+  root: { lang: string } | null = typeof document === "undefined" ? null : document.documentElement,
+): void {
+  if (root && state.locale?.locale) {
+    root.lang = state.locale.locale;
+  }
+}
+
+/** The OS color scheme at window creation, until the renderer can read it itself. */
+export function getDesktopInitialColorScheme(): "light" | "dark" | null {
+  return getDesktopInitialState().theme ?? null;
+}
+
+/** The system locale the desktop app reported, or `null` outside it. */
+export function getDesktopLocale(): string | null {
+  return getDesktopInitialState().locale?.locale ?? null;
+}
@@ -0,0 +1,23 @@
+import { invokeDesktopCommand } from "@/desktop/electron/invoke";
+
+/**
+ * Asks the desktop app to confirm a high-risk approval the daemon refused.
+ * Resolves the signature to resend the allow with, or `null` when declined.
+ */
+export async function requestNativeApprovalSignature(input: {
+  agentId: string;
+  requestId: string;
+}): Promise<string | null> {
+  try {
+    const result = await invokeDesktopCommand<{ approved?: unknown; signature?: unknown }>(
+      "request_native_approval",
+      input,
+    );
+    return result?.approved === true && typeof result.signature === "string"
+      ? result.signature
+      : null;
+  } catch (error) {
+    console.warn("[NativeApproval] Confirmation failed", error);
+    return null;
+  }
+}
@@ -0,0 +1,25 @@
+import { describe, expect, it, vi } from "vitest";
+import { parseWorkspaceTrustDecisions } from "./workspace-trust";
+
+vi.mock("react-native", () => ({ Platform: { OS: "web" } }));
+
+describe("parseWorkspaceTrustDecisions", () => {
+  it("keeps well-formed decisions sorted by path", () => {
+    expect(
+      parseWorkspaceTrustDecisions([
+        { path: "/work/web", trusted: false, decidedAt: "2026-10-01T00:00:00.000Z" },
+        { path: "/work/api", trusted: true },
+        { path: 42, trusted: true },
+        "junk",
+      ]),
+    ).toEqual([
+      { path: "/work/api", trusted: true, decidedAt: "" },
+      { path: "/work/web", trusted: false, decidedAt: "2026-10-01T00:00:00.000Z" },
+    ]);
+  });
+
+  it("treats anything but a list as empty", () => {
+    expect(parseWorkspaceTrustDecisions(null)).toEqual([]);
+    expect(parseWorkspaceTrustDecisions({ decisions: [] })).toEqual([]);
+  });
+});
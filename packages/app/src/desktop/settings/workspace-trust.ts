@@ -0,0 +1,56 @@
+import { getIsElectron } from "@/constants/platform";
+import { invokeDesktopCommand } from "@/desktop/electron/invoke";
+
+export interface WorkspaceTrustDecision {
+  path: string;
+  trusted: boolean;
+  decidedAt: string;
+}
+
+export async function listWorkspaceTrustDecisions(): Promise<WorkspaceTrustDecision[]> {
+  if (!getIsElectron()) {
+    return [];
+  }
+  return parseWorkspaceTrustDecisions(
+    await invokeDesktopCommand<unknown>("list_trusted_workspaces"),
+  );
+}
+
+/** Forgets the decision for `path`, so the next request there asks again. */
+export async function revokeWorkspaceTrust(path: string): Promise<boolean> {
+  return (await invokeDesktopCommand<unknown>("revoke_workspace_trust", { path })) === true;
+}
+
+/**
+ * Lets the desktop app ask about `path` after the daemon refused to run
+ * something there. Resolves whether the folder is trusted now.
+ */
+export async function ensureWorkspaceTrusted(path: string): Promise<boolean> {
+  return await invokeDesktopCommand("ensure_workspace_trusted", { path }).then(
+    () => true,
+    () => false,
+  );
+}
+
+export function parseWorkspaceTrustDecisions(raw: unknown): WorkspaceTrustDecision[] {
+  if (!Array.isArray(raw)) {
+    return [];
+  }
+  return raw
+    .flatMap((entry: unknown): WorkspaceTrustDecision[] =>
+      isRecord(entry) && typeof entry.path === "string" && typeof entry.trusted === "boolean"
+        ? [
+            {
+              path: entry.path,
+              trusted: entry.trusted,
+              decidedAt: typeof entry.decidedAt === "string" ? entry.decidedAt : "",
+            },
+          ]
+        : [],
+    )
+    .sort((left, right) => left.path.localeCompare(right.path));
+}
+
+function isRecord(value: unknown): value is Record<string, unknown> {
+  return typeof value === "object" && value !== null && !Array.isArray(value);
+}
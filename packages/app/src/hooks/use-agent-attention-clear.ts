@@ -7,6 +7,7 @@ import {
 } from "@/utils/agent-attention";
 import { getIsAppActivelyVisible } from "@/utils/app-visibility";
 import { isWeb } from "@/constants/platform";
+import { setDesktopViewedAgent } from "@/desktop/electron/window";
 
 type AttentionReason = "finished" | "error" | "permission" | null | undefined;
 
@@ -89,6 +90,18 @@ export function useAgentAttentionClear({
     };
   }, []);
 
+  // The desktop app keeps unread counts; tell it which agent this window is showing.
+  useEffect(() => {
+    const viewedAgentId = isScreenFocused ? agentId?.trim() : null;
+    if (!viewedAgentId) {
+      return;
+    }
+    void setDesktopViewedAgent(viewedAgentId).catch(() => {});
+    return () => {
+      void setDesktopViewedAgent(null).catch(() => {});
+    };
+  }, [agentId, isScreenFocused]);
+
   useEffect(() => {
     if (!requiresAttention) {
       deferredFocusEntryClearRef.current = false;
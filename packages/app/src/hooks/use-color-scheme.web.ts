@@ -1,8 +1,10 @@
 import { useEffect, useState } from "react";
 import { useColorScheme as useRNColorScheme } from "react-native";
+import { getDesktopInitialColorScheme } from "@/desktop/initial-state";
 
 /**
- * To support static rendering, this value needs to be re-calculated on the client side for web
+ * To support static rendering, this value needs to be re-calculated on the client side for web.
+ * Until then the desktop app's system theme stands in, so dark systems do not flash light.
  */
 export function useColorScheme() {
   const [hasHydrated, setHasHydrated] = useState(false);
@@ -17,5 +19,5 @@ export function useColorScheme() {
     return colorScheme;
   }
 
-  return "light";
+  return getDesktopInitialColorScheme() ?? "light";
 }
@@ -1,10 +1,10 @@
-import { useEffect, useRef, useState } from "react";
-import { getIsElectronRuntimeMac } from "@/constants/layout";
+import { useEffect, useMemo, useRef, useState, useSyncExternalStore } from "react";
 import { useAggregatedAgents } from "./use-aggregated-agents";
-import { getDesktopHost } from "@/desktop/host";
+import { getIsElectron, isNative } from "@/constants/platform";
+import { reportDesktopUnreadCount } from "@/desktop/electron/window";
+import { getHostRuntimeStore, useHosts } from "@/runtime/host-runtime";
 import { useWorkspaceStatusesForBadges } from "@/stores/session-store-hooks";
 import { deriveMacDockBadgeCountFromWorkspaceStatuses } from "@/utils/desktop-badge-state";
-import { isNative } from "@/constants/platform";
 
 type FaviconStatus = "none" | "running" | "attention";
 type ColorScheme = "dark" | "light";
@@ -78,26 +78,33 @@ function getSystemColorScheme(): ColorScheme {
   return window.matchMedia("(prefers-color-scheme: dark)").matches ? "dark" : "light";
 }
 
-async function updateMacDockBadge(count?: number) {
-  if (isNative || !getIsElectronRuntimeMac()) return;
-
-  const desktopWindow = getDesktopHost()?.window?.getCurrentWindow?.();
-  if (!desktopWindow || typeof desktopWindow.setBadgeCount !== "function") {
-    return;
-  }
-
-  try {
-    await desktopWindow.setBadgeCount(count);
-  } catch (error) {
-    console.warn("[useFaviconStatus] Failed to update macOS dock badge", error);
-  }
+// Frames on these connections pass through the desktop app, which counts them itself.
+const LOCAL_TRANSPORT_CONNECTION_TYPES = new Set(["directSocket", "directPipe"]);
+
+function useHostsOutsideLocalTransport(): string[] {
+  const hosts = useHosts();
+  const runtime = getHostRuntimeStore();
+  const readKey = () =>
+    hosts
+      .map((host) => host.serverId)
+      .filter((serverId) => {
+        const type = runtime.getSnapshot(serverId)?.activeConnection?.type;
+        return !type || !LOCAL_TRANSPORT_CONNECTION_TYPES.has(type);
+      })
+      .join("\n");
+  const key = useSyncExternalStore(
+    (onStoreChange) => runtime.subscribeAll(onStoreChange),
+    readKey,
+    readKey,
+  );
+  return useMemo(() => (key ? key.split("\n") : []), [key]);
 }
 
 export function useFaviconStatus() {
   const { agents } = useAggregatedAgents();
-  const workspaceStatuses = useWorkspaceStatusesForBadges();
   const [colorScheme, setColorScheme] = useState<ColorScheme>(getSystemColorScheme);
-  const lastDockBadgeCountRef = useRef<number | undefined>(undefined);
+  const unobservedStatuses = useWorkspaceStatusesForBadges(useHostsOutsideLocalTransport());
+  const lastReportedCountRef = useRef<number | null>(null);
 
   // Listen for system color scheme changes
   useEffect(() => {
@@ -118,11 +125,18 @@ export function useFaviconStatus() {
 
     const status = deriveFaviconStatus(agents);
     updateFavicon(status, colorScheme);
+  }, [agents, colorScheme]);
 
-    const dockBadgeCount = deriveMacDockBadgeCountFromWorkspaceStatuses(workspaceStatuses);
-    if (dockBadgeCount !== lastDockBadgeCountRef.current) {
-      lastDockBadgeCountRef.current = dockBadgeCount;
-      void updateMacDockBadge(dockBadgeCount);
-    }
-  }, [agents, colorScheme, workspaceStatuses]);
+  // The desktop badge, tray and Window menu are drawn in the main process, which
+  // only sees the local transport; workspaces on other hosts are reported here.
+  useEffect(() => {
+    if (!getIsElectron()) return;
+
+    const count = deriveMacDockBadgeCountFromWorkspaceStatuses(unobservedStatuses) ?? 0;
+    if (count === lastReportedCountRef.current) return;
+    lastReportedCountRef.current = count;
+    void reportDesktopUnreadCount(count).catch((error) => {
+      console.warn("[useFaviconStatus] Failed to report unread count", error);
+    });
+  }, [unobservedStatuses]);
 }
@@ -7,6 +7,7 @@ interface RecordedUpsert {
   listenAddress: string;
   serverId: string;
   hostname: string | null;
+  useTls?: boolean;
 }
 
 function createFakeStore(): {
@@ -33,6 +34,7 @@ function makeStatus(overrides: Partial<DesktopDaemonStatus> = {}): DesktopDaemon
     home: "/home",
     version: "0.0.0",
     desktopManaged: true,
+    tls: false,
     error: null,
     ...overrides,
   };
@@ -56,6 +58,25 @@ describe("DaemonStartService", () => {
     expect(service.isRunning()).toBe(false);
   });
 
+  it("asks for a TLS route when the daemon serves TLS", async () => {
+    const fake = createFakeStore();
+    const service = new DaemonStartService({
+      store: fake.store,
+      startDesktopDaemon: async () => makeStatus({ tls: true }),
+    });
+
+    await service.start();
+
+    expect(fake.upserts).toEqual([
+      {
+        listenAddress: "127.0.0.1:6767",
+        serverId: "srv_desktop",
+        hostname: "desktop",
+        useTls: true,
+      },
+    ]);
+  });
+
   it("reports lastError after a missing listen address and clears running state when done", async () => {
     const fake = createFakeStore();
     const service = new DaemonStartService({
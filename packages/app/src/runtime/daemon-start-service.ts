@@ -33,13 +33,15 @@ export class DaemonStartService {
       if (!serverId) {
         return this.fail("Desktop daemon did not return a server id.");
       }
-      if (!connectionFromListen(listenAddress)) {
+      const useTls = daemon.tls;
+      if (!connectionFromListen(listenAddress, { useTls })) {
         return this.fail(`Desktop daemon returned an unsupported listen address: ${listenAddress}`);
       }
       await this.store.upsertConnectionFromListen({
         listenAddress,
         serverId,
         hostname: daemon.hostname,
+        ...(useTls ? { useTls } : {}),
       });
       return { ok: true };
     } catch (error) {
@@ -11,6 +11,7 @@ import { useSessionStore, type Agent } from "@/stores/session-store";
 import {
   HostRuntimeController,
   HostRuntimeStore,
+  type HostRouteChangedEvent,
   type HostRuntimeControllerDeps,
   type HostRuntimeSnapshot,
 } from "./host-runtime";
@@ -558,6 +559,57 @@ describe("HostRuntimeController", () => {
     expect((initialClient as unknown as FakeDaemonClient | null)?.closeCalls).toBe(1);
   });
 
+  it("returns from a relay fallback once the direct route answers again", async () => {
+    const host = makeHost({ preferredConnectionId: "direct:lan:6767" });
+    const clients: FakeDaemonClient[] = [];
+    const latencies: Record<string, number | Error> = {
+      "direct:lan:6767": 15,
+      "relay:relay.paseo.sh:443": 55,
+    };
+    const controller = new HostRuntimeController({
+      host,
+      deps: makeDeps(latencies, clients),
+    });
+    const routes: HostRouteChangedEvent[] = [];
+    controller.subscribeRouteChanges((event) => routes.push(event));
+
+    await controller.start({ autoProbe: false });
+    const initialClient = controller.getSnapshot().client as unknown as FakeDaemonClient;
+    initialClient.ping = async () => {
+      throw new Error("active ping failed");
+    };
+    latencies["direct:lan:6767"] = new Error("direct unavailable");
+    clearProbeBackoff(controller);
+    await controller.runProbeCycleNow();
+    expect(controller.getSnapshot().activeConnectionId).toBe("relay:relay.paseo.sh:443");
+
+    latencies["direct:lan:6767"] = 90;
+    latencies["relay:relay.paseo.sh:443"] = 20;
+    clearProbeBackoff(controller);
+    await controller.runProbeCycleNow();
+
+    expect(controller.getSnapshot().activeConnectionId).toBe("direct:lan:6767");
+    expect(
+      routes.map(({ previousConnectionId, connectionId, reason }) => ({
+        previousConnectionId,
+        connectionId,
+        reason,
+      })),
+    ).toEqual([
+      { previousConnectionId: null, connectionId: "direct:lan:6767", reason: "initial" },
+      {
+        previousConnectionId: "direct:lan:6767",
+        connectionId: "relay:relay.paseo.sh:443",
+        reason: "fallback",
+      },
+      {
+        previousConnectionId: "relay:relay.paseo.sh:443",
+        connectionId: "direct:lan:6767",
+        reason: "direct_restored",
+      },
+    ]);
+  });
+
   it("switches only after the faster alternative wins consecutive probes", async () => {
     const host = makeHost({ preferredConnectionId: "direct:lan:6767" });
     const clients: FakeDaemonClient[] = [];
@@ -1595,6 +1647,33 @@ describe("HostRuntimeStore", () => {
     store.syncHosts([]);
   });
 
+  it("keeps the last route change of each host until the host is removed", async () => {
+    const host = makeHost({ serverId: "srv_routes" });
+    const store = new HostRuntimeStore({
+      deps: makeDeps({ "direct:lan:6767": 15, "relay:relay.paseo.sh:443": 55 }, []),
+    });
+    const notified = vi.fn();
+    const unsubscribe = store.subscribe(host.serverId, notified);
+
+    store.syncHosts([host]);
+    const timeoutAt = Date.now() + 200;
+    while (!store.getLastRouteChange(host.serverId) && Date.now() < timeoutAt) {
+      await new Promise((resolve) => setTimeout(resolve, 0));
+    }
+
+    expect(store.getLastRouteChange(host.serverId)).toMatchObject({
+      serverId: "srv_routes",
+      previousConnectionId: null,
+      connectionId: "direct:lan:6767",
+      reason: "initial",
+    });
+    expect(notified).toHaveBeenCalled();
+
+    store.syncHosts([]);
+    unsubscribe();
+    expect(store.getLastRouteChange(host.serverId)).toBeNull();
+  });
+
   it("uses the advertised hostname when adding a relay host from a pairing offer", async () => {
     const store = new HostRuntimeStore({
       deps: {
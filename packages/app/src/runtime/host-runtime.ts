@@ -26,10 +26,17 @@ import {
 import { resolveAppVersion } from "@/utils/app-version";
 import { ConnectionOfferSchema, type ConnectionOffer } from "@server/shared/connection-offer";
 import { shouldUseDesktopDaemon } from "@/desktop/daemon/desktop-daemon";
+import {
+  getDesktopDaemonAuthState,
+  listenToDesktopDaemonAuthState,
+  resolveDirectConnectionAuth,
+  setDesktopDaemonAuthToken,
+} from "@/desktop/daemon/desktop-daemon-auth";
 import { connectToDaemon } from "@/utils/test-daemon-connection";
 import { getOrCreateClientId } from "@/utils/client-id";
 import {
   selectBestConnection,
+  selectBestDirectConnection,
   type ConnectionCandidate,
   type ConnectionProbeState,
 } from "@/utils/connection-selection";
@@ -37,7 +44,9 @@ import {
   buildLocalDaemonTransportUrl,
   createDesktopLocalDaemonTransportFactory,
 } from "@/desktop/daemon/desktop-daemon-transport";
-import { isDev } from "@/constants/platform";
+import { getIsElectron, isDev } from "@/constants/platform";
+import { ensureWorkspaceTrusted } from "@/desktop/settings/workspace-trust";
+import { requestNativeApprovalSignature } from "@/desktop/permissions/native-approval";
 import { replaceFetchedAgentDirectory } from "@/utils/agent-directory-sync";
 import { useSessionStore } from "@/stores/session-store";
 
@@ -49,6 +58,21 @@ export type ActiveConnection =
   | { type: "directPipe"; endpoint: string; display: "pipe" }
   | { type: "relay"; endpoint: string; display: "relay" };
 
+export type HostRouteChangeReason =
+  | "initial"
+  | "fallback"
+  | "direct_restored"
+  | "latency"
+  | "manual";
+
+export interface HostRouteChangedEvent {
+  serverId: string;
+  previousConnectionId: string | null;
+  connectionId: string;
+  connection: ActiveConnection;
+  reason: HostRouteChangeReason;
+}
+
 export type HostRuntimeAgentDirectoryStatus =
   | "idle"
   | "initial_loading"
@@ -454,6 +478,12 @@ function createDefaultDeps(): HostRuntimeControllerDeps {
         appVersion: resolveAppVersion() ?? undefined,
         runtimeGeneration,
         ...(isDev ? { runtimeMetricsIntervalMs: 10_000 } : {}),
+        ...(getIsElectron()
+          ? {
+              onUntrustedWorkspace: ensureWorkspaceTrusted,
+              onNativeApprovalRequired: requestNativeApprovalSignature,
+            }
+          : {}),
       };
       if (connection.type === "directSocket" || connection.type === "directPipe") {
         return new DaemonClient({
@@ -462,16 +492,22 @@ function createDefaultDeps(): HostRuntimeControllerDeps {
           url: buildLocalDaemonTransportUrl({
             transportType: connection.type === "directSocket" ? "socket" : "pipe",
             transportPath: connection.path,
+            serverId: host.serverId,
           }),
         });
       }
       if (connection.type === "directTcp") {
+        const url = buildDaemonWebSocketUrl(connection.endpoint, {
+          useTls: connection.useTls ?? false,
+        });
         return new DaemonClient({
           ...base,
-          url: buildDaemonWebSocketUrl(connection.endpoint, {
-            useTls: connection.useTls ?? false,
+          url,
+          ...resolveDirectConnectionAuth({
+            serverId: host.serverId,
+            url,
+            password: connection.password,
           }),
-          ...(connection.password ? { password: connection.password } : {}),
         });
       }
       return new DaemonClient({
@@ -500,6 +536,10 @@ export class HostRuntimeController {
   private connectionMachineState: HostRuntimeConnectionMachineState;
   private snapshot: HostRuntimeSnapshot;
   private listeners = new Set<() => void>();
+  private routeListeners = new Set<(event: HostRouteChangedEvent) => void>();
+  // Set while an automatic switch has parked us on the relay; cleared once a
+  // direct route is restored or the user/latency logic picks the route instead.
+  private relayFallbackActive = false;
   private activeClient: DaemonClient | null = null;
   private unsubscribeClientStatus: (() => void) | null = null;
   private probeIntervalHandle: ReturnType<typeof setInterval> | null = null;
@@ -552,6 +592,13 @@ export class HostRuntimeController {
     };
   }
 
+  subscribeRouteChanges(listener: (event: HostRouteChangedEvent) => void): () => void {
+    this.routeListeners.add(listener);
+    return () => {
+      this.routeListeners.delete(listener);
+    };
+  }
+
   async start(options?: HostRuntimeStartOptions): Promise<void> {
     if (this.started) {
       return;
@@ -562,6 +609,7 @@ export class HostRuntimeController {
       await this.switchToConnection({
         connectionId: options.initialConnection.connectionId,
         existingClient: options.initialConnection.existingClient,
+        reason: "initial",
       });
     }
     await this.runProbeCycleNow();
@@ -736,6 +784,7 @@ export class HostRuntimeController {
             connectionId,
             expectedProbeVersion: requestVersion,
             existingClient: client,
+            reason: "initial",
           }).finally(() => {
             activationLock = null;
           });
@@ -766,6 +815,7 @@ export class HostRuntimeController {
           await this.switchToConnection({
             connectionId: nextConnectionId,
             expectedProbeVersion: requestVersion,
+            reason: currentActiveConnectionId ? "fallback" : "initial",
           });
         }
         return;
@@ -780,6 +830,7 @@ export class HostRuntimeController {
           await this.switchToConnection({
             connectionId: nextConnectionId,
             expectedProbeVersion: requestVersion,
+            reason: "fallback",
           });
         }
         this.switchCandidateConnectionId = null;
@@ -791,6 +842,23 @@ export class HostRuntimeController {
         return;
       }
 
+      if (this.relayFallbackActive) {
+        const directConnectionId = selectBestDirectConnection({
+          candidates: buildConnectionCandidates(this.host),
+          probeByConnectionId,
+        });
+        if (directConnectionId) {
+          this.switchCandidateConnectionId = null;
+          this.switchCandidateHitCount = 0;
+          await this.switchToConnection({
+            connectionId: directConnectionId,
+            expectedProbeVersion: requestVersion,
+            reason: "direct_restored",
+          });
+          return;
+        }
+      }
+
       const available = Array.from(probeByConnectionId.entries())
         .filter(
           (entry): entry is [string, Extract<ConnectionProbeState, { status: "available" }>] =>
@@ -829,6 +897,7 @@ export class HostRuntimeController {
         await this.switchToConnection({
           connectionId: fastest.connectionId,
           expectedProbeVersion: requestVersion,
+          reason: "latency",
         });
       }
     };
@@ -1054,8 +1123,11 @@ export class HostRuntimeController {
     connectionId: string;
     expectedProbeVersion?: number;
     existingClient?: DaemonClient;
+    reason?: HostRouteChangeReason;
   }): Promise<void> {
     const { connectionId, expectedProbeVersion, existingClient } = input;
+    const reason = input.reason ?? "manual";
+    const previousConnectionId = this.snapshot.activeConnectionId;
     if (!this.canProceedForProbe(expectedProbeVersion)) {
       await this.abortSwitchWithClient(existingClient);
       return;
@@ -1116,6 +1188,17 @@ export class HostRuntimeController {
     for (const listener of this.listeners) {
       listener();
     }
+    this.relayFallbackActive =
+      connection.type === "relay" && reason !== "latency" && reason !== "manual";
+    if (previousConnectionId !== connection.id) {
+      this.emitRouteChanged({
+        serverId: this.host.serverId,
+        previousConnectionId,
+        connectionId: connection.id,
+        connection: toActiveConnection(connection),
+        reason,
+      });
+    }
 
     this.unsubscribeClientStatus = client.subscribeConnectionStatus((state) => {
       if (!this.isCurrentSwitchRequest(requestVersion) || this.activeClient !== client) {
@@ -1152,6 +1235,12 @@ export class HostRuntimeController {
     }
   }
 
+  private emitRouteChanged(event: HostRouteChangedEvent): void {
+    for (const listener of this.routeListeners) {
+      listener(event);
+    }
+  }
+
   adoptReconciledServerId(newServerId: string): void {
     this.host = { ...this.host, serverId: newServerId };
     this.snapshot = { ...this.snapshot, serverId: newServerId };
@@ -1176,6 +1265,15 @@ const LOCALHOST_FALLBACK_ENDPOINT = "localhost:6767";
 const DEFAULT_LOCALHOST_BOOTSTRAP_TIMEOUT_MS = 2500;
 const E2E_STORAGE_KEY = "@paseo:e2e";
 
+/** The daemon URLs a host's password is used against. */
+function directEndpointUrls(connections: HostConnection[]): string[] {
+  return connections.flatMap((connection) =>
+    connection.type === "directTcp"
+      ? [buildDaemonWebSocketUrl(connection.endpoint, { useTls: connection.useTls ?? false })]
+      : [],
+  );
+}
+
 function readConfiguredLocalDaemonOverride(): string | null {
   const value = process.env.EXPO_PUBLIC_LOCAL_DAEMON?.trim();
   return value && value.length > 0 ? value : null;
@@ -1208,6 +1306,7 @@ export class HostRuntimeStore {
   private hosts: HostProfile[] = [];
   private deps: HostRuntimeControllerDeps;
   private lastConnectionStatusByServer = new Map<string, HostRuntimeConnectionStatus>();
+  private lastRouteChangeByServer = new Map<string, HostRouteChangedEvent>();
   private agentDirectoryBootstrapInFlight = new Map<string, Promise<void>>();
   private bootStarted = false;
 
@@ -1242,6 +1341,9 @@ export class HostRuntimeStore {
 
   private async runBoot(): Promise<void> {
     await this.loadFromStorage();
+    if (shouldUseDesktopDaemon()) {
+      void this.syncDesktopDaemonAuth();
+    }
 
     const override = readConfiguredLocalDaemonOverride();
     if (override) {
@@ -1290,6 +1392,64 @@ export class HostRuntimeStore {
     }
   }
 
+  /**
+   * The desktop main process owns daemon passwords. Ones still saved in a
+   * profile are handed to it and then dropped from the profile; connections
+   * pull them from the main process instead.
+   */
+  private async syncDesktopDaemonAuth(): Promise<void> {
+    try {
+      await listenToDesktopDaemonAuthState((state) => {
+        if (state.status !== "authenticated") {
+          this.dropDirectPasswords(state.serverId);
+        }
+      });
+      for (const host of this.hosts) {
+        const password = host.connections.find(
+          (connection) => connection.type === "directTcp" && connection.password,
+        );
+        if (password?.type !== "directTcp" || !password.password) {
+          continue;
+        }
+        const { status } = await getDesktopDaemonAuthState(host.serverId);
+        // An expired password stays expired; any other is (re)sent with its endpoints.
+        if (status !== "expired") {
+          await setDesktopDaemonAuthToken({
+            serverId: host.serverId,
+            token: password.password,
+            endpoints: directEndpointUrls(host.connections),
+          });
+        }
+        this.dropDirectPasswords(host.serverId);
+      }
+    } catch (error) {
+      console.error("[HostRuntime] Failed to sync daemon passwords with the desktop", error);
+    }
+  }
+
+  private dropDirectPasswords(serverId: string): void {
+    let changed = false;
+    const next = this.hosts.map((host) => {
+      if (host.serverId !== serverId) {
+        return host;
+      }
+      const connections = host.connections.map((connection) => {
+        if (connection.type !== "directTcp" || !connection.password) {
+          return connection;
+        }
+        changed = true;
+        const { password: _removed, ...rest } = connection;
+        return rest;
+      });
+      return { ...host, connections };
+    });
+    if (!changed) {
+      return;
+    }
+    this.setHostsAndSync(next);
+    void this.persistHosts();
+  }
+
   private async bootstrapDefaultLocalhost(): Promise<void> {
     const connection = connectionFromListen(LOCALHOST_FALLBACK_ENDPOINT);
     if (!connection || registryHasConnection(this.hosts, connection)) {
@@ -1367,6 +1527,7 @@ export class HostRuntimeStore {
     controller.adoptReconciledServerId(newServerId);
 
     rekeyMap(this.lastConnectionStatusByServer, oldServerId, newServerId);
+    rekeyMap(this.lastRouteChangeByServer, oldServerId, newServerId);
     rekeyMap(this.agentDirectoryBootstrapInFlight, oldServerId, newServerId);
 
     const listeners = this.serverListeners.get(oldServerId);
@@ -1399,16 +1560,27 @@ export class HostRuntimeStore {
   }): Promise<HostProfile> {
     const endpoint = normalizeHostPort(input.endpoint);
     const password = input.password?.trim();
+    const handOver = Boolean(password) && shouldUseDesktopDaemon();
+    const connection: HostConnection = {
+      id: `direct:${endpoint}`,
+      type: "directTcp",
+      endpoint,
+      useTls: input.useTls ?? false,
+      // Handed to the desktop main process instead of being saved in the profile.
+      ...(password && !handOver ? { password } : {}),
+    };
+    if (password && handOver) {
+      const existing = this.hosts.find((host) => host.serverId === input.serverId);
+      await setDesktopDaemonAuthToken({
+        serverId: input.serverId,
+        token: password,
+        endpoints: directEndpointUrls([...(existing?.connections ?? []), connection]),
+      });
+    }
     return this.upsertHostConnection({
       serverId: input.serverId,
       label: input.label,
-      connection: {
-        id: `direct:${endpoint}`,
-        type: "directTcp",
-        endpoint,
-        useTls: input.useTls ?? false,
-        ...(password ? { password } : {}),
-      },
+      connection,
       existingClient: input.existingClient,
     });
   }
@@ -1467,10 +1639,11 @@ export class HostRuntimeStore {
     listenAddress: string;
     serverId: string;
     hostname: string | null;
+    useTls?: boolean;
   }): Promise<HostProfile> {
     const normalizedListenAddress = input.listenAddress.trim();
     const serverId = input.serverId.trim();
-    const connection = connectionFromListen(normalizedListenAddress);
+    const connection = connectionFromListen(normalizedListenAddress, { useTls: input.useTls });
     if (!connection) {
       throw new Error(`Unsupported listen address: ${input.listenAddress}`);
     }
@@ -1599,6 +1772,7 @@ export class HostRuntimeStore {
       }
       this.controllers.delete(serverId);
       this.lastConnectionStatusByServer.delete(serverId);
+      this.lastRouteChangeByServer.delete(serverId);
       this.agentDirectoryBootstrapInFlight.delete(serverId);
       void controller.stop();
       this.emit(serverId);
@@ -1630,6 +1804,10 @@ export class HostRuntimeStore {
         this.maybeAutoBootstrapAgentDirectory(host.serverId);
         this.emit(host.serverId);
       });
+      controller.subscribeRouteChanges((event) => {
+        this.lastRouteChangeByServer.set(event.serverId, event);
+        this.emit(event.serverId);
+      });
       void controller
         .start(
           initialConnection
@@ -1703,6 +1881,11 @@ export class HostRuntimeStore {
     return this.controllers.get(serverId)?.getSnapshot() ?? null;
   }
 
+  /** The most recent switch between a host's routes, such as a fallback to its relay. */
+  getLastRouteChange(serverId: string): HostRouteChangedEvent | null {
+    return this.lastRouteChangeByServer.get(serverId) ?? null;
+  }
+
   getVersion(): number {
     return this.version;
   }
@@ -1945,6 +2128,15 @@ export function useHostRuntimeLastError(serverId: string): string | null {
   );
 }
 
+export function useHostRouteChange(serverId: string): HostRouteChangedEvent | null {
+  const store = getHostRuntimeStore();
+  return useSyncExternalStore(
+    (onStoreChange) => store.subscribe(serverId, onStoreChange),
+    () => store.getLastRouteChange(serverId),
+    () => store.getLastRouteChange(serverId),
+  );
+}
+
 export function useHostRuntimeAgentDirectoryStatus(
   serverId: string,
 ): HostRuntimeAgentDirectoryStatus {
@@ -174,6 +174,10 @@ function PickerOptionItem({
   );
 }
 
+export function buildNewWorkspaceDraftKey(serverId: string, sourceDirectory: string): string {
+  return `new-workspace:${serverId}:${sourceDirectory}`;
+}
+
 function branchOptionId(name: string): string {
   return `${BRANCH_OPTION_PREFIX}${name}`;
 }
@@ -447,7 +451,7 @@ export function NewWorkspaceScreen({
   const isPending = pendingAction !== null;
   const client = useHostRuntimeClient(serverId);
   const isConnected = useHostRuntimeIsConnected(serverId);
-  const draftKey = `new-workspace:${serverId}:${sourceDirectory}`;
+  const draftKey = buildNewWorkspaceDraftKey(serverId, sourceDirectory);
   const chatDraft = useAgentInputDraft({
     draftKey,
     initialCwd: sourceDirectory,
@@ -756,7 +760,7 @@ export function NewWorkspaceScreen({
         <TitlebarDragRegion />
         <View style={styles.centered}>
           <Composer
-            agentId={`new-workspace:${serverId}:${sourceDirectory}`}
+            agentId={draftKey}
             serverId={serverId}
             isPaneFocused={true}
             onSubmitMessage={handleCreateChatAgent}
@@ -63,6 +63,7 @@ import {
 } from "@/components/ui/dropdown-menu";
 import { DesktopPermissionsSection } from "@/desktop/components/desktop-permissions-section";
 import { IntegrationsSection } from "@/desktop/components/integrations-section";
+import { WorkspaceTrustSection } from "@/desktop/components/workspace-trust-section";
 import { isElectronRuntime } from "@/desktop/host";
 import { useDesktopAppUpdater } from "@/desktop/updates/use-desktop-app-updater";
 import { formatVersionWithPrefix } from "@/desktop/updates/desktop-updates";
@@ -958,7 +959,12 @@ export default function SettingsScreen({ view }: SettingsScreenProps) {
         case "integrations":
           return isDesktopApp ? <IntegrationsSection /> : null;
         case "permissions":
-          return isDesktopApp ? <DesktopPermissionsSection /> : null;
+          return isDesktopApp ? (
+            <>
+              <DesktopPermissionsSection />
+              <WorkspaceTrustSection />
+            </>
+          ) : null;
         case "diagnostics":
           return (
             <DiagnosticsSection
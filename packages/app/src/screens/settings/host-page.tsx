@@ -5,7 +5,9 @@ import { ChevronRight, Globe, Monitor, Pencil, RotateCw, Trash2 } from "lucide-r
 import type { HostConnection, HostProfile } from "@/types/host-connection";
 import {
   getHostRuntimeStore,
+  type HostRouteChangedEvent,
   isHostRuntimeConnected,
+  useHostRouteChange,
   useHostRuntimeClient,
   useHostRuntimeIsConnected,
   useHostRuntimeSnapshot,
@@ -62,6 +64,20 @@ function formatActiveConnectionBadge(
   };
 }
 
+function formatRouteChangeNotice(
+  routeChange: HostRouteChangedEvent | null,
+  activeConnectionId: string | null,
+): string | null {
+  if (!routeChange || routeChange.connectionId !== activeConnectionId) return null;
+  if (routeChange.reason === "fallback") {
+    return "Direct address unreachable, connected through the relay.";
+  }
+  if (routeChange.reason === "direct_restored") {
+    return "Direct address reachable again, switched back from the relay.";
+  }
+  return null;
+}
+
 function formatDaemonVersionBadge(version: string | null): string | null {
   const trimmed = version?.trim();
   if (!trimmed) return null;
@@ -79,6 +95,7 @@ export function HostPage({ serverId, onHostRemoved }: HostPageProps) {
   const { theme } = useUnistyles();
   const snapshot = useHostRuntimeSnapshot(serverId);
   const isLocalDaemon = useIsLocalDaemon(serverId);
+  const routeChange = useHostRouteChange(serverId);
 
   const daemonVersion = useSessionStore(
     (state) => state.sessions[serverId]?.serverInfo?.version ?? null,
@@ -110,6 +127,7 @@ export function HostPage({ serverId, onHostRemoved }: HostPageProps) {
     statusPillBg = "rgba(161, 161, 170, 0.1)";
   }
   const connectionBadge = formatActiveConnectionBadge(activeConnection, theme);
+  const routeNotice = formatRouteChangeNotice(routeChange, snapshot?.activeConnectionId ?? null);
   const versionBadgeText = formatDaemonVersionBadge(daemonVersion);
   const connectionError =
     typeof lastError === "string" && lastError.trim().length > 0 ? lastError.trim() : null;
@@ -158,6 +176,11 @@ export function HostPage({ serverId, onHostRemoved }: HostPageProps) {
         ) : null}
       </View>
       {connectionError ? <Text style={styles.errorText}>{connectionError}</Text> : null}
+      {routeNotice ? (
+        <Text style={styles.noticeText} testID="host-page-route-notice">
+          {routeNotice}
+        </Text>
+      ) : null}
 
       <ConnectionsSection host={host} />
 
@@ -810,6 +833,11 @@ const styles = StyleSheet.create((theme) => ({
     fontSize: theme.fontSize.xs,
     marginBottom: theme.spacing[2],
   },
+  noticeText: {
+    color: theme.colors.foregroundMuted,
+    fontSize: theme.fontSize.xs,
+    marginBottom: theme.spacing[2],
+  },
   connectionLatency: {
     fontSize: theme.fontSize.sm,
     marginRight: theme.spacing[2],
@@ -363,4 +363,14 @@ describe("useWorkspaceStatusesForBadges", () => {
     expect(result.current).not.toBe(before);
     expect(result.current).toEqual(["failed", "attention"]);
   });
+
+  it("only reads the requested hosts when given", () => {
+    initializeWorkspaces([createWorkspace({ id: "workspace-a", status: "attention" })]);
+
+    const { result: matching } = renderHook(() => useWorkspaceStatusesForBadges([SERVER_ID]));
+    const { result: other } = renderHook(() => useWorkspaceStatusesForBadges(["other-server"]));
+
+    expect(matching.current).toEqual(["attention"]);
+    expect(other.current).toEqual([]);
+  });
 });
@@ -366,12 +366,18 @@ export function useResolveWorkspaceIdByCwd(
   );
 }
 
-export function useWorkspaceStatusesForBadges(): DesktopBadgeWorkspaceStatus[] {
+/** Statuses of every workspace, or only those on `serverIds` when given. */
+export function useWorkspaceStatusesForBadges(
+  serverIds?: readonly string[],
+): DesktopBadgeWorkspaceStatus[] {
   return useStoreWithEqualityFn(
     useSessionStore,
     (state) => {
       const statuses: DesktopBadgeWorkspaceStatus[] = [];
-      for (const session of Object.values(state.sessions)) {
+      for (const [serverId, session] of Object.entries(state.sessions)) {
+        if (serverIds && !serverIds.includes(serverId)) {
+          continue;
+        }
         for (const workspace of session.workspaces.values()) {
           statuses.push(workspace.status);
         }
@@ -1,5 +1,10 @@
 import { describe, expect, it } from "vitest";
-import { normalizeStoredHostProfile } from "./host-connection";
+import {
+  connectionFromListen,
+  normalizeStoredHostProfile,
+  upsertHostConnectionInProfiles,
+  type HostProfile,
+} from "./host-connection";
 
 describe("normalizeStoredHostProfile", () => {
   it("loads direct TCP connections stored before TLS and password fields existed", () => {
@@ -28,3 +33,27 @@ describe("normalizeStoredHostProfile", () => {
     expect(profile?.connections[0]).not.toHaveProperty("password");
   });
 });
+
+describe("upsertHostConnectionInProfiles", () => {
+  it("replaces a stored route when the daemon starts serving TLS on it", () => {
+    const plain = connectionFromListen("127.0.0.1:6767")!;
+    const profiles: HostProfile[] = upsertHostConnectionInProfiles({
+      profiles: [],
+      serverId: "srv_desktop",
+      connection: plain,
+      now: "2026-01-01T00:00:00.000Z",
+    });
+
+    const next = upsertHostConnectionInProfiles({
+      profiles,
+      serverId: "srv_desktop",
+      connection: connectionFromListen("127.0.0.1:6767", { useTls: true })!,
+      now: "2026-01-02T00:00:00.000Z",
+    });
+
+    expect(next).toHaveLength(1);
+    expect(next[0]?.connections).toEqual([
+      { id: "direct:localhost:6767", type: "directTcp", endpoint: "localhost:6767", useTls: true },
+    ]);
+  });
+});
@@ -80,6 +80,21 @@ function hostConnectionEquals(left: HostConnection, right: HostConnection): bool
   return false;
 }
 
+/**
+ * An upserted connection supersedes a stored one with the same id, so a daemon
+ * that starts serving TLS does not leave its plain route behind. A stored
+ * password survives when the update carries none.
+ */
+function replaceSameIdConnection(stored: HostConnection, next: HostConnection): HostConnection {
+  if (stored.id !== next.id) {
+    return stored;
+  }
+  if (stored.type === "directTcp" && next.type === "directTcp" && !next.password) {
+    return stored.password ? { ...next, password: stored.password } : next;
+  }
+  return next;
+}
+
 function hostLifecycleEquals(left: HostLifecycle, right: HostLifecycle): boolean {
   return JSON.stringify(left) === JSON.stringify(right);
 }
@@ -138,7 +153,9 @@ export function upsertHostConnectionInProfiles(input: {
   const prev =
     matchedProfiles.find((daemon) => daemon.serverId === serverId) ?? matchedProfiles[0]!;
   const nextConnections = dedupeHostConnections([
-    ...matchedProfiles.flatMap((daemon) => daemon.connections),
+    ...matchedProfiles
+      .flatMap((daemon) => daemon.connections)
+      .map((connection) => replaceSameIdConnection(connection, input.connection)),
     input.connection,
   ]);
   const nextLifecycle = prev.lifecycle;
@@ -187,7 +204,10 @@ export function upsertHostConnectionInProfiles(input: {
   return next;
 }
 
-export function connectionFromListen(listen: string): HostConnection | null {
+export function connectionFromListen(
+  listen: string,
+  options?: { useTls?: boolean },
+): HostConnection | null {
   const normalizedListen = listen.trim();
   if (!normalizedListen) {
     return null;
@@ -225,6 +245,7 @@ export function connectionFromListen(listen: string): HostConnection | null {
       id: `direct:${endpoint}`,
       type: "directTcp",
       endpoint,
+      ...(options?.useTls ? { useTls: true } : {}),
     };
   } catch {
     return null;
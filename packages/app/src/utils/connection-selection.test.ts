@@ -2,6 +2,7 @@ import { describe, expect, it } from "vitest";
 import type { HostConnection } from "@/types/host-connection";
 import {
   selectBestConnection,
+  selectBestDirectConnection,
   type ConnectionCandidate,
   type ConnectionProbeState,
 } from "./connection-selection";
@@ -123,3 +124,45 @@ describe("selectBestConnection", () => {
     expect(selected).toBeNull();
   });
 });
+
+describe("selectBestDirectConnection", () => {
+  it("prefers an available direct connection even when the relay is faster", () => {
+    const candidates: ConnectionCandidate[] = [
+      { connectionId: "direct:a", connection: makeDirect("direct:a", "a:6767") },
+      {
+        connectionId: "relay:b",
+        connection: makeRelay("relay:b", "relay.example:443"),
+      },
+    ];
+
+    const selected = selectBestDirectConnection({
+      candidates,
+      probeByConnectionId: probes({
+        "direct:a": { status: "available", latencyMs: 90 },
+        "relay:b": { status: "available", latencyMs: 20 },
+      }),
+    });
+
+    expect(selected).toBe("direct:a");
+  });
+
+  it("returns null when only the relay is available", () => {
+    const candidates: ConnectionCandidate[] = [
+      { connectionId: "direct:a", connection: makeDirect("direct:a", "a:6767") },
+      {
+        connectionId: "relay:b",
+        connection: makeRelay("relay:b", "relay.example:443"),
+      },
+    ];
+
+    const selected = selectBestDirectConnection({
+      candidates,
+      probeByConnectionId: probes({
+        "direct:a": { status: "unavailable", latencyMs: null },
+        "relay:b": { status: "available", latencyMs: 20 },
+      }),
+    });
+
+    expect(selected).toBeNull();
+  });
+});
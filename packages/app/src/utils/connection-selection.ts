@@ -48,3 +48,14 @@ export function selectBestConnection(input: SelectBestConnectionInput): string |
 
   return bestConnectionId;
 }
+
+/**
+ * Best available non-relay candidate. Used to leave a relay fallback as soon as
+ * a direct route answers again, regardless of relative latency.
+ */
+export function selectBestDirectConnection(input: SelectBestConnectionInput): string | null {
+  return selectBestConnection({
+    candidates: input.candidates.filter((candidate) => candidate.connection.type !== "relay"),
+    probeByConnectionId: input.probeByConnectionId,
+  });
+}
@@ -12,6 +12,7 @@ import {
   buildLocalDaemonTransportUrl,
   createDesktopLocalDaemonTransportFactory,
 } from "@/desktop/daemon/desktop-daemon-transport";
+import { resolveDirectConnectionAuth } from "@/desktop/daemon/desktop-daemon-auth";
 import { isDev } from "@/constants/platform";
 
 function normalizeNonEmptyString(value: unknown): string | null {
@@ -43,7 +44,7 @@ function isIncorrectPasswordFailure(input: {
   reason: string | null;
   lastError: string | null;
 }): boolean {
-  if (!input.config.password) {
+  if (!input.config.password && !input.config.resolvePassword) {
     return false;
   }
   const details = [input.reason, input.lastError].filter(Boolean).join("\n").toLowerCase();
@@ -97,10 +98,13 @@ export async function buildClientConfig(
   }
 
   if (connection.type === "directTcp") {
+    const url = buildDaemonWebSocketUrl(connection.endpoint, {
+      useTls: connection.useTls ?? false,
+    });
     return {
       ...base,
-      url: buildDaemonWebSocketUrl(connection.endpoint, { useTls: connection.useTls ?? false }),
-      ...(connection.password ? { password: connection.password } : {}),
+      url,
+      ...resolveDirectConnectionAuth({ serverId, url, password: connection.password }),
     };
   }
 
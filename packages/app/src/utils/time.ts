@@ -1,3 +1,5 @@
+import { getDesktopLocale } from "@/desktop/initial-state";
+
 /**
  * Format a date as a human-friendly relative time string
  * Examples: "just now", "5m ago", "2h ago", "3d ago", "Jan 15"
@@ -31,7 +33,7 @@ export function formatTimeAgo(date: Date): string {
   }
 
   // For older dates, show abbreviated month and day
-  const month = date.toLocaleDateString("en-US", { month: "short" });
+  const month = date.toLocaleDateString(getDesktopLocale() ?? "en-US", { month: "short" });
   const day = date.getDate();
   return `${month} ${day}`;
 }
@@ -143,10 +143,15 @@ function resolveConfiguredIpcDaemonHost(env: NodeJS.ProcessEnv, paseoHome: strin
 }
 
 function resolveConfiguredTcpDaemonHost(env: NodeJS.ProcessEnv, paseoHome: string): string | null {
-  const configuredHost = normalizeDaemonHost(loadConfig(paseoHome, { env }).listen);
+  const config = loadConfig(paseoHome, { env });
+  const configuredHost = normalizeDaemonHost(config.listen);
   if (!isTcpDaemonHost(configuredHost)) {
     return null;
   }
+  // A daemon serving TLS only speaks WSS, on the default address as well.
+  if (config.tls && !configuredHost.startsWith("tcp://")) {
+    return `tcp://${normalizeHostPort(configuredHost)}?ssl=true`;
+  }
   return configuredHost === "127.0.0.1:6767" ? null : configuredHost;
 }
 
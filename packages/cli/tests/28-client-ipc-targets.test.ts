@@ -153,4 +153,24 @@ console.log("=== CLI IPC Target Helpers ===\n");
   console.log("✓ daemon password resolution uses only the TCP URI query\n");
 }
 
+{
+  console.log("Test 11: a configured TLS daemon is reached over wss");
+  const paseoHome = mkdtempSync(path.join(os.tmpdir(), "paseo-client-targets-tls-"));
+  try {
+    const hosts = resolveDefaultDaemonHosts({
+      PASEO_HOME: paseoHome,
+      PASEO_TLS_CERT: "/tmp/paseo-tls/server.crt",
+      PASEO_TLS_KEY: "/tmp/paseo-tls/server.key",
+    });
+    assert.deepStrictEqual(hosts, ["tcp://127.0.0.1:6767?ssl=true", "localhost:6767"]);
+    assert.deepStrictEqual(resolveDaemonTarget(hosts[0]!), {
+      type: "tcp",
+      url: "wss://127.0.0.1:6767/ws",
+    });
+  } finally {
+    rmSync(paseoHome, { recursive: true, force: true });
+  }
+  console.log("✓ a configured TLS daemon is reached over wss\n");
+}
+
 console.log("=== All CLI IPC target tests passed ===");
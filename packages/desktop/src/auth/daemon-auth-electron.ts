@@ -0,0 +1,31 @@
+import { app, safeStorage } from "electron";
+import { emitDesktopEvent } from "../window/desktop-events.js";
+import {
+  createDaemonAuthStore,
+  type DaemonAuthState,
+  type DaemonAuthStore,
+} from "./daemon-auth-store.js";
+
+let daemonAuthStore: DaemonAuthStore | null = null;
+const stateListeners = new Set<(state: DaemonAuthState) => void>();
+
+export function getDaemonAuthStore(): DaemonAuthStore {
+  daemonAuthStore ??= createDaemonAuthStore({
+    userDataPath: app.getPath("userData"),
+    cipher: safeStorage,
+    onStateChanged: (state) => {
+      emitDesktopEvent("auth-state-changed", state);
+      for (const listener of stateListeners) {
+        listener(state);
+      }
+    },
+  });
+  return daemonAuthStore;
+}
+
+export function onDaemonAuthStateChanged(listener: (state: DaemonAuthState) => void): () => void {
+  stateListeners.add(listener);
+  return () => {
+    stateListeners.delete(listener);
+  };
+}
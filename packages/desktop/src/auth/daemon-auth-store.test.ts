@@ -0,0 +1,133 @@
+import { mkdtemp, readFile, rm } from "node:fs/promises";
+import { tmpdir } from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import { createDaemonAuthStore, type DaemonAuthState, type TokenCipher } from "./daemon-auth-store";
+
+function createReversingCipher(available = true): TokenCipher {
+  return {
+    isEncryptionAvailable: () => available,
+    encryptString: (plainText) => Buffer.from([...plainText].reverse().join(""), "utf8"),
+    decryptString: (encrypted) => [...encrypted.toString("utf8")].reverse().join(""),
+  };
+}
+
+describe("createDaemonAuthStore", () => {
+  let userDataPath: string;
+
+  beforeEach(async () => {
+    userDataPath = await mkdtemp(path.join(tmpdir(), "paseo-daemon-auth-"));
+  });
+
+  afterEach(async () => {
+    vi.useRealTimers();
+    await rm(userDataPath, { recursive: true, force: true });
+  });
+
+  it("persists tokens encrypted and reloads them", async () => {
+    const store = createDaemonAuthStore({ userDataPath, cipher: createReversingCipher() });
+
+    await store.setToken({ serverId: "srv", token: "secret" });
+
+    const raw = await readFile(path.join(userDataPath, "daemon-auth.json"), "utf8");
+    expect(raw).not.toContain("secret");
+
+    const reloaded = createDaemonAuthStore({ userDataPath, cipher: createReversingCipher() });
+    expect(await reloaded.getToken("srv")).toBe("secret");
+    expect(await reloaded.getState("srv")).toMatchObject({
+      status: "authenticated",
+      persisted: true,
+    });
+  });
+
+  it("keeps the endpoints a token was handed over for", async () => {
+    const store = createDaemonAuthStore({ userDataPath, cipher: createReversingCipher() });
+
+    await store.setToken({ serverId: "srv", token: "secret", endpoints: ["wss://a:6767/ws"] });
+    await store.setToken({ serverId: "srv", token: "rotated" });
+
+    const reloaded = createDaemonAuthStore({ userDataPath, cipher: createReversingCipher() });
+    expect(await reloaded.getEndpoints("srv")).toEqual(["wss://a:6767/ws"]);
+    expect(await reloaded.getEndpoints("other")).toEqual([]);
+  });
+
+  it("keeps tokens in memory only when the keychain is unavailable", async () => {
+    const store = createDaemonAuthStore({ userDataPath, cipher: createReversingCipher(false) });
+
+    await store.setToken({ serverId: "srv", token: "secret" });
+
+    expect(await store.getToken("srv")).toBe("secret");
+    const raw = await readFile(path.join(userDataPath, "daemon-auth.json"), "utf8");
+    expect(JSON.parse(raw).tokens).toEqual({});
+  });
+
+  it("reports expiry and stops handing out the token", async () => {
+    vi.useFakeTimers();
+    vi.setSystemTime(new Date("2026-01-01T00:00:00.000Z"));
+    const changes: DaemonAuthState[] = [];
+    const store = createDaemonAuthStore({
+      userDataPath,
+      cipher: createReversingCipher(),
+      onStateChanged: (state) => changes.push(state),
+    });
+
+    await store.setToken({
+      serverId: "srv",
+      token: "secret",
+      expiresAt: "2026-01-01T00:01:00.000Z",
+    });
+    vi.advanceTimersByTime(60_000);
+
+    expect(changes.map((state) => state.status)).toEqual(["authenticated", "expired"]);
+    expect(await store.getToken("srv")).toBeNull();
+    store.dispose();
+  });
+
+  it("reports tokens that expired while the machine slept on revalidate", async () => {
+    let nowMs = Date.parse("2026-01-01T00:00:00.000Z");
+    const changes: DaemonAuthState[] = [];
+    const store = createDaemonAuthStore({
+      userDataPath,
+      cipher: createReversingCipher(),
+      onStateChanged: (state) => changes.push(state),
+      now: () => nowMs,
+    });
+    await store.setToken({
+      serverId: "srv",
+      token: "secret",
+      expiresAt: "2026-01-01T00:01:00.000Z",
+    });
+    await store.setToken({ serverId: "other", token: "secret" });
+
+    nowMs += 8 * 60 * 60_000;
+    const states = await store.revalidate();
+
+    expect(states.map(({ serverId, status }) => [serverId, status])).toEqual([
+      ["srv", "expired"],
+      ["other", "authenticated"],
+    ]);
+    expect(changes.map(({ serverId, status }) => [serverId, status])).toEqual([
+      ["srv", "authenticated"],
+      ["other", "authenticated"],
+      ["srv", "expired"],
+    ]);
+    expect(await store.revalidate()).toHaveLength(2);
+    expect(changes).toHaveLength(3);
+    store.dispose();
+  });
+
+  it("signs out on clear", async () => {
+    const changes: DaemonAuthState[] = [];
+    const store = createDaemonAuthStore({
+      userDataPath,
+      cipher: createReversingCipher(),
+      onStateChanged: (state) => changes.push(state),
+    });
+    await store.setToken({ serverId: "srv", token: "secret" });
+
+    await store.clearToken("srv");
+
+    expect(await store.getToken("srv")).toBeNull();
+    expect(changes.at(-1)).toMatchObject({ serverId: "srv", status: "signed_out" });
+  });
+});
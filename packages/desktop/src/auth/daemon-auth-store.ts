@@ -0,0 +1,301 @@
+import { readFile } from "node:fs/promises";
+import path from "node:path";
+import { updateJsonConfigFile } from "../settings/config-file.js";
+import { isRecord } from "../utils/is-record.js";
+
+const DAEMON_AUTH_FILENAME = "daemon-auth.json";
+// setTimeout overflows past ~24.8 days; longer expiries are re-armed on the next set/load.
+const MAX_EXPIRY_TIMER_MS = 2_147_483_647;
+
+export type DaemonAuthStatus = "authenticated" | "expired" | "signed_out";
+
+export interface DaemonAuthState {
+  serverId: string;
+  status: DaemonAuthStatus;
+  expiresAt: string | null;
+  updatedAt: string | null;
+  /** False when the OS keychain is unavailable and the token only lives in memory. */
+  persisted: boolean;
+}
+
+/** Keychain-backed encryption, normally Electron's `safeStorage`. */
+export interface TokenCipher {
+  isEncryptionAvailable(): boolean;
+  encryptString(plainText: string): Buffer;
+  decryptString(encrypted: Buffer): string;
+}
+
+interface StoredToken {
+  token: string;
+  expiresAt: string | null;
+  updatedAt: string;
+  persisted: boolean;
+  /** Daemon URLs the token was handed over for; it is never sent anywhere else. */
+  endpoints: string[];
+}
+
+interface PersistedDaemonAuthDocument {
+  version: 1;
+  tokens: Record<
+    string,
+    { encryptedToken: string; expiresAt: string | null; updatedAt: string; endpoints: string[] }
+  >;
+}
+
+/**
+ * Daemon passwords, one per server, with optional expiry. This is the only
+ * copy: renderer profiles drop a password once it is handed over, and each
+ * connection asks for the current one before it connects.
+ *
+ * Refreshing is deliberately out of scope. A daemon password is a shared
+ * secret set on the daemon, with no refresh endpoint or refresh token to renew
+ * it with. An expired password is withheld and reported through
+ * `onStateChanged` until the user enters a new one, which every connection
+ * then picks up on its next attempt.
+ */
+export interface DaemonAuthStore {
+  /** `endpoints` replaces the known URLs for the server; omitted, the current ones are kept. */
+  setToken(input: {
+    serverId: string;
+    token: string;
+    expiresAt?: string | null;
+    endpoints?: string[];
+  }): Promise<DaemonAuthState>;
+  clearToken(serverId: string): Promise<DaemonAuthState>;
+  /** Current bearer token, or `null` when signed out or past expiry. */
+  getToken(serverId: string): Promise<string | null>;
+  getState(serverId: string): Promise<DaemonAuthState>;
+  /** URLs the server's token may be sent to. */
+  getEndpoints(serverId: string): Promise<string[]>;
+  listStates(): Promise<DaemonAuthState[]>;
+  /**
+   * Re-checks expiries against the wall clock. Timers do not count time spent
+   * asleep, so a token that lapsed overnight is only reported after this runs.
+   */
+  revalidate(): Promise<DaemonAuthState[]>;
+  dispose(): void;
+}
+
+function coerceExpiresAt(value: unknown): string | null {
+  if (typeof value !== "string") {
+    return null;
+  }
+  const parsed = Date.parse(value);
+  return Number.isFinite(parsed) ? new Date(parsed).toISOString() : null;
+}
+
+function coerceEndpoints(value: unknown): string[] {
+  if (!Array.isArray(value)) {
+    return [];
+  }
+  const endpoints = value.flatMap((entry: unknown) =>
+    typeof entry === "string" && entry.trim() ? [entry.trim()] : [],
+  );
+  return [...new Set(endpoints)];
+}
+
+export function createDaemonAuthStore(input: {
+  userDataPath: string;
+  cipher: TokenCipher;
+  onStateChanged?: (state: DaemonAuthState) => void;
+  now?: () => number;
+}): DaemonAuthStore {
+  const filePath = path.join(input.userDataPath, DAEMON_AUTH_FILENAME);
+  const now = input.now ?? (() => Date.now());
+  const tokens = new Map<string, StoredToken>();
+  const expiryTimers = new Map<string, NodeJS.Timeout>();
+  let loaded: Promise<void> | null = null;
+
+  function toState(serverId: string): DaemonAuthState {
+    const stored = tokens.get(serverId);
+    if (!stored) {
+      return { serverId, status: "signed_out", expiresAt: null, updatedAt: null, persisted: false };
+    }
+    const expired = stored.expiresAt !== null && Date.parse(stored.expiresAt) <= now();
+    return {
+      serverId,
+      status: expired ? "expired" : "authenticated",
+      expiresAt: stored.expiresAt,
+      updatedAt: stored.updatedAt,
+      persisted: stored.persisted,
+    };
+  }
+
+  function armExpiryTimer(serverId: string): void {
+    const existing = expiryTimers.get(serverId);
+    if (existing) {
+      clearTimeout(existing);
+      expiryTimers.delete(serverId);
+    }
+    const expiresAt = tokens.get(serverId)?.expiresAt;
+    if (!expiresAt) {
+      return;
+    }
+    const remainingMs = Date.parse(expiresAt) - now();
+    if (remainingMs <= 0 || remainingMs > MAX_EXPIRY_TIMER_MS) {
+      return;
+    }
+    const timer = setTimeout(() => {
+      expiryTimers.delete(serverId);
+      input.onStateChanged?.(toState(serverId));
+    }, remainingMs);
+    timer.unref?.();
+    expiryTimers.set(serverId, timer);
+  }
+
+  async function load(): Promise<void> {
+    let raw: string;
+    try {
+      raw = await readFile(filePath, "utf8");
+    } catch {
+      return;
+    }
+    if (!input.cipher.isEncryptionAvailable()) {
+      return;
+    }
+    try {
+      const document = JSON.parse(raw) as unknown;
+      if (!isRecord(document) || !isRecord(document.tokens)) {
+        return;
+      }
+      for (const [serverId, entry] of Object.entries(document.tokens)) {
+        if (!isRecord(entry) || typeof entry.encryptedToken !== "string") {
+          continue;
+        }
+        tokens.set(serverId, {
+          token: input.cipher.decryptString(Buffer.from(entry.encryptedToken, "base64")),
+          expiresAt: coerceExpiresAt(entry.expiresAt),
+          updatedAt:
+            typeof entry.updatedAt === "string" ? entry.updatedAt : new Date(now()).toISOString(),
+          persisted: true,
+          endpoints: coerceEndpoints(entry.endpoints),
+        });
+        armExpiryTimer(serverId);
+      }
+    } catch {
+      // A keychain reset makes old ciphertext unreadable; start signed out.
+      tokens.clear();
+    }
+  }
+
+  function ensureLoaded(): Promise<void> {
+    loaded ??= load();
+    return loaded;
+  }
+
+  /**
+   * Writes one server's entry. The others are kept as they are on disk, where
+   * another instance may have changed them since this one loaded.
+   */
+  async function persist(serverId: string): Promise<void> {
+    await updateJsonConfigFile(
+      filePath,
+      (current): PersistedDaemonAuthDocument => {
+        const persistedTokens: PersistedDaemonAuthDocument["tokens"] = {};
+        if (isRecord(current) && isRecord(current.tokens)) {
+          for (const [id, entry] of Object.entries(current.tokens)) {
+            if (isRecord(entry) && typeof entry.encryptedToken === "string") {
+              persistedTokens[id] = {
+                encryptedToken: entry.encryptedToken,
+                expiresAt: coerceExpiresAt(entry.expiresAt),
+                updatedAt:
+                  typeof entry.updatedAt === "string"
+                    ? entry.updatedAt
+                    : new Date(now()).toISOString(),
+                endpoints: coerceEndpoints(entry.endpoints),
+              };
+            }
+          }
+        }
+        delete persistedTokens[serverId];
+        const stored = tokens.get(serverId);
+        if (stored?.persisted) {
+          persistedTokens[serverId] = {
+            encryptedToken: input.cipher.encryptString(stored.token).toString("base64"),
+            expiresAt: stored.expiresAt,
+            updatedAt: stored.updatedAt,
+            endpoints: stored.endpoints,
+          };
+        }
+        return { version: 1, tokens: persistedTokens };
+      },
+      { mode: 0o600 },
+    );
+  }
+
+  return {
+    async setToken({ serverId, token, expiresAt, endpoints }) {
+      await ensureLoaded();
+      const trimmed = token.trim();
+      if (!serverId || !trimmed) {
+        throw new Error("serverId and token are required");
+      }
+      tokens.set(serverId, {
+        token: trimmed,
+        expiresAt: coerceExpiresAt(expiresAt),
+        updatedAt: new Date(now()).toISOString(),
+        persisted: input.cipher.isEncryptionAvailable(),
+        endpoints: endpoints ? coerceEndpoints(endpoints) : (tokens.get(serverId)?.endpoints ?? []),
+      });
+      await persist(serverId);
+      armExpiryTimer(serverId);
+      const state = toState(serverId);
+      input.onStateChanged?.(state);
+      return state;
+    },
+
+    async clearToken(serverId) {
+      await ensureLoaded();
+      const existed = tokens.delete(serverId);
+      armExpiryTimer(serverId);
+      const state = toState(serverId);
+      if (existed) {
+        await persist(serverId);
+        input.onStateChanged?.(state);
+      }
+      return state;
+    },
+
+    async getToken(serverId) {
+      await ensureLoaded();
+      return toState(serverId).status === "authenticated"
+        ? (tokens.get(serverId)?.token ?? null)
+        : null;
+    },
+
+    async getState(serverId) {
+      await ensureLoaded();
+      return toState(serverId);
+    },
+
+    async getEndpoints(serverId) {
+      await ensureLoaded();
+      return tokens.get(serverId)?.endpoints ?? [];
+    },
+
+    async listStates() {
+      await ensureLoaded();
+      return [...tokens.keys()].map((serverId) => toState(serverId));
+    },
+
+    async revalidate() {
+      await ensureLoaded();
+      return [...tokens.keys()].map((serverId) => {
+        const pending = expiryTimers.has(serverId);
+        armExpiryTimer(serverId);
+        const state = toState(serverId);
+        if (pending && state.status === "expired") {
+          input.onStateChanged?.(state);
+        }
+        return state;
+      });
+    },
+
+    dispose() {
+      for (const timer of expiryTimers.values()) {
+        clearTimeout(timer);
+      }
+      expiryTimers.clear();
+    },
+  };
+}
@@ -0,0 +1,96 @@
+import { randomUUID } from "node:crypto";
+import {
+  buildCancelAgentFrame,
+  createActiveAgentTracker,
+  parseCancelAgentReply,
+  type ActiveAgentTracker,
+} from "./agent-interrupts.js";
+import {
+  listOpenLocalTransportSessions,
+  observeLocalTransport,
+  sendLocalDaemonRequest,
+} from "./local-transport.js";
+import type { LocalTransportSessionInfo } from "./transport-observers.js";
+
+export interface InterruptAgentResult {
+  agentId: string;
+  /** Socket or pipe path of the daemon that runs the agent. */
+  daemon: string;
+  status: "interrupted" | "failed";
+  error?: string;
+}
+
+// Keyed by session id; a session keeps its tracker across reconnects.
+const trackers = new Map<string, { daemon: string; agents: ActiveAgentTracker }>();
+
+/** Follows which agents are running on each connected daemon. */
+export function startActiveAgentTracking(): void {
+  observeLocalTransport({
+    inbound: (text, { sessionId, daemon }) => {
+      let tracker = trackers.get(sessionId);
+      if (!tracker) {
+        tracker = { daemon, agents: createActiveAgentTracker() };
+        trackers.set(sessionId, tracker);
+      }
+      tracker.agents.observeInbound(text);
+    },
+    closed: ({ sessionId }) => {
+      trackers.delete(sessionId);
+    },
+  });
+}
+
+async function cancelAgent(
+  session: LocalTransportSessionInfo,
+  agentId: string,
+): Promise<InterruptAgentResult> {
+  const requestId = `desktop-cancel-${randomUUID()}`;
+  const { daemon } = session;
+  try {
+    const reply = await sendLocalDaemonRequest(session.sessionId, {
+      requestId,
+      frame: buildCancelAgentFrame({ agentId, requestId }),
+      parseReply: parseCancelAgentReply,
+    });
+    return reply.ok
+      ? { agentId, daemon, status: "interrupted" }
+      : { agentId, daemon, status: "failed", error: reply.error };
+  } catch (error) {
+    const message = error instanceof Error ? error.message : String(error);
+    return { agentId, daemon, status: "failed", error: message };
+  }
+}
+
+/**
+ * Sends a cancel to every agent that is running on any connected daemon. Several
+ * windows can share a daemon, so each agent is interrupted once, through one socket.
+ */
+export async function interruptAllLocalAgents(): Promise<InterruptAgentResult[]> {
+  const byDaemon = new Map<
+    string,
+    { session: LocalTransportSessionInfo; agentIds: Set<string> }
+  >();
+  for (const session of listOpenLocalTransportSessions()) {
+    const entry = byDaemon.get(session.daemon) ?? { session, agentIds: new Set<string>() };
+    trackers
+      .get(session.sessionId)
+      ?.agents.list()
+      .forEach((agentId) => entry.agentIds.add(agentId));
+    byDaemon.set(session.daemon, entry);
+  }
+  const pending = [...byDaemon.values()].flatMap(({ session, agentIds }) =>
+    [...agentIds].map((agentId) => cancelAgent(session, agentId)),
+  );
+  return await Promise.all(pending);
+}
+
+/** Agents running on any connected daemon, each counted once however many windows watch it. */
+export function countRunningLocalAgents(): number {
+  const agents = new Set<string>();
+  for (const { daemon, agents: tracker } of trackers.values()) {
+    for (const agentId of tracker.list()) {
+      agents.add(`${daemon}\0${agentId}`);
+    }
+  }
+  return agents.size;
+}
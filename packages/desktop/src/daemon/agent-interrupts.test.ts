@@ -0,0 +1,65 @@
+import { describe, expect, it } from "vitest";
+import {
+  buildCancelAgentFrame,
+  createActiveAgentTracker,
+  parseCancelAgentReply,
+} from "./agent-interrupts";
+
+function session(message: Record<string, unknown>): string {
+  return JSON.stringify({ type: "session", message });
+}
+
+function upsert(id: string, status: string): string {
+  return session({ type: "agent_update", payload: { kind: "upsert", agent: { id, status } } });
+}
+
+describe("createActiveAgentTracker", () => {
+  it("follows running agents through updates, directory pages and removals", () => {
+    const tracker = createActiveAgentTracker();
+    tracker.observeInbound(
+      session({
+        type: "fetch_agents_response",
+        payload: {
+          entries: [
+            { agent: { id: "a1", status: "running" } },
+            { agent: { id: "a2", status: "idle" } },
+          ],
+        },
+      }),
+    );
+    tracker.observeInbound(upsert("a3", "initializing"));
+    tracker.observeInbound(upsert("a2", "running"));
+    tracker.observeInbound(upsert("a1", "idle"));
+    tracker.observeInbound(
+      session({ type: "agent_update", payload: { kind: "remove", agentId: "a3" } }),
+    );
+
+    expect(tracker.list()).toEqual(["a2"]);
+  });
+});
+
+describe("parseCancelAgentReply", () => {
+  it("matches cancel responses and cancel rpc errors by request id", () => {
+    const frame = JSON.parse(buildCancelAgentFrame({ agentId: "a1", requestId: "r1" }));
+    expect(frame.message).toEqual({ type: "cancel_agent_request", agentId: "a1", requestId: "r1" });
+
+    expect(
+      parseCancelAgentReply(
+        session({ type: "cancel_agent_response", payload: { requestId: "r1", agentId: "a1" } }),
+      ),
+    ).toEqual({ requestId: "r1", ok: true });
+    expect(
+      parseCancelAgentReply(
+        session({
+          type: "rpc_error",
+          payload: { requestId: "r2", requestType: "cancel_agent_request", error: "gone" },
+        }),
+      ),
+    ).toEqual({ requestId: "r2", ok: false, error: "gone" });
+    expect(
+      parseCancelAgentReply(
+        session({ type: "rpc_error", payload: { requestId: "r3", requestType: "other" } }),
+      ),
+    ).toBeNull();
+  });
+});
@@ -0,0 +1,94 @@
+import { isRecord } from "../utils/is-record.js";
+
+const ACTIVE_AGENT_STATUSES = new Set(["initializing", "running"]);
+
+export interface ActiveAgentTracker {
+  /** Follows agent status from `agent_update` and `fetch_agents_response` frames. */
+  observeInbound(text: string): void;
+  list(): string[];
+}
+
+export type CancelAgentReply =
+  | { requestId: string; ok: true }
+  | { requestId: string; ok: false; error: string };
+
+function parseSessionMessage(text: string): Record<string, unknown> | null {
+  try {
+    const parsed: unknown = JSON.parse(text);
+    if (isRecord(parsed) && parsed.type === "session" && isRecord(parsed.message)) {
+      return parsed.message;
+    }
+  } catch {
+    // not JSON
+  }
+  return null;
+}
+
+export function createActiveAgentTracker(): ActiveAgentTracker {
+  const active = new Set<string>();
+
+  function applySnapshot(agent: unknown): void {
+    if (!isRecord(agent) || typeof agent.id !== "string") {
+      return;
+    }
+    if (ACTIVE_AGENT_STATUSES.has(String(agent.status))) {
+      active.add(agent.id);
+    } else {
+      active.delete(agent.id);
+    }
+  }
+
+  return {
+    observeInbound(text) {
+      const message = parseSessionMessage(text);
+      const payload = message && isRecord(message.payload) ? message.payload : null;
+      if (!payload) {
+        return;
+      }
+      if (message?.type === "agent_update") {
+        if (payload.kind === "remove" && typeof payload.agentId === "string") {
+          active.delete(payload.agentId);
+        } else if (payload.kind === "upsert") {
+          applySnapshot(payload.agent);
+        }
+        return;
+      }
+      if (message?.type === "fetch_agents_response" && Array.isArray(payload.entries)) {
+        for (const entry of payload.entries) {
+          applySnapshot(isRecord(entry) ? entry.agent : null);
+        }
+      }
+    },
+
+    list() {
+      return [...active].sort();
+    },
+  };
+}
+
+export function buildCancelAgentFrame(input: { agentId: string; requestId: string }): string {
+  return JSON.stringify({
+    type: "session",
+    message: { type: "cancel_agent_request", agentId: input.agentId, requestId: input.requestId },
+  });
+}
+
+/** The daemon's answer to a cancel request: a `cancel_agent_response` or an `rpc_error`. */
+export function parseCancelAgentReply(text: string): CancelAgentReply | null {
+  const message = parseSessionMessage(text);
+  const payload = message && isRecord(message.payload) ? message.payload : null;
+  if (!payload || typeof payload.requestId !== "string") {
+    return null;
+  }
+  if (message?.type === "cancel_agent_response") {
+    return { requestId: payload.requestId, ok: true };
+  }
+  if (message?.type === "rpc_error" && payload.requestType === "cancel_agent_request") {
+    return {
+      requestId: payload.requestId,
+      ok: false,
+      error: typeof payload.error === "string" ? payload.error : "Cancel failed.",
+    };
+  }
+  return null;
+}
@@ -0,0 +1,147 @@
+import { randomBytes } from "node:crypto";
+import { readFileSync, writeFileSync } from "node:fs";
+import path from "node:path";
+import { signNativeApproval, type HighRiskPermission } from "@getpaseo/server";
+import { app } from "electron";
+import { promptNativeApproval, type NativeApprovalResult } from "../features/native-approval.js";
+import { getDesktopSettingsStore } from "../settings/desktop-settings-electron.js";
+import { createApprovalGate, type NativeApprovalDetails } from "./approval-gate.js";
+import {
+  findOpenLocalTransportSession,
+  observeLocalTransport,
+  sendLocalDaemonFrame,
+} from "./local-transport.js";
+
+const NATIVE_APPROVAL_KEY_FILENAME = "native-approval.key";
+
+const approvalGate = createApprovalGate();
+let nativeApprovalKey: string | null = null;
+
+/** Shared with the daemon this app spawns, which only accepts high-risk allows signed with it. */
+export function getNativeApprovalKeyFilePath(): string {
+  return path.join(app.getPath("userData"), NATIVE_APPROVAL_KEY_FILENAME);
+}
+
+function loadNativeApprovalKey(): string {
+  if (nativeApprovalKey) {
+    return nativeApprovalKey;
+  }
+  const filePath = getNativeApprovalKeyFilePath();
+  try {
+    writeFileSync(filePath, randomBytes(32).toString("hex"), { flag: "wx", mode: 0o600 });
+  } catch (error) {
+    if ((error as NodeJS.ErrnoException).code !== "EEXIST") {
+      throw error;
+    }
+  }
+  nativeApprovalKey = readFileSync(filePath, "utf8").trim();
+  return nativeApprovalKey;
+}
+
+export interface NativeApprovalResponse extends NativeApprovalResult {
+  /** Sent with the allow so the daemon accepts it; set when approved. */
+  signature?: string;
+}
+
+async function confirmNativeApproval(
+  details: NativeApprovalDetails,
+): Promise<NativeApprovalResult> {
+  const { approvals } = await getDesktopSettingsStore().get();
+  const result = await promptNativeApproval({
+    details,
+    requireTouchId: approvals.requireTouchId,
+  });
+  if (result.approved) {
+    approvalGate.grant(details);
+  }
+  return result;
+}
+
+/**
+ * Holds the renderer's allow for a high-risk request until it is confirmed
+ * natively. Has to run before any window can connect to the daemon.
+ */
+export function startNativeApprovalGate(): void {
+  loadNativeApprovalKey();
+  observeLocalTransport({
+    inbound: (text, { sessionId }) => approvalGate.observeInbound(text, sessionId),
+    screenOutbound: async (frame) => {
+      const approval = approvalGate.pendingNativeApproval(frame);
+      if (approval && !(await confirmNativeApproval(approval)).approved) {
+        throw new Error("Approval was declined in the native confirmation.");
+      }
+    },
+  });
+}
+
+/**
+ * The modal shows what the daemon reported, either on the local transport or
+ * when asked through `fetchPermission`, so the renderer cannot choose what is
+ * approved. A request already confirmed on the local transport is signed
+ * without asking twice.
+ */
+export async function requestNativeApproval(
+  args: Record<string, unknown> | undefined,
+  options: {
+    fetchPermission?: (key: {
+      agentId: string;
+      requestId: string;
+    }) => Promise<HighRiskPermission | null>;
+  } = {},
+): Promise<NativeApprovalResponse> {
+  const agentId = typeof args?.agentId === "string" ? args.agentId : "";
+  const requestId = typeof args?.requestId === "string" ? args.requestId : "";
+  if (!agentId || !requestId) {
+    throw new Error("agentId and requestId are required.");
+  }
+  const key = { agentId, requestId };
+  const details = approvalGate.getDetails(key) ?? (await options.fetchPermission?.(key)) ?? null;
+  if (!details) {
+    throw new Error(`No pending high-risk request ${requestId} from agent ${agentId}.`);
+  }
+  const result = approvalGate.isGranted(key)
+    ? { approved: true, method: "dialog" as const }
+    : await confirmNativeApproval(details);
+  return result.approved
+    ? { ...result, signature: signNativeApproval(loadNativeApprovalKey(), key) }
+    : result;
+}
+
+/**
+ * Approves from a `paseo://approve` link: after the native confirmation the
+ * allow goes straight to the daemon on the session the request came from, as
+ * no renderer is involved. Without an agent id only requests the gate is
+ * holding can be found.
+ */
+export async function approveFromLink(input: {
+  requestId: string;
+  agentId: string | null;
+}): Promise<{ agentId: string; requestId: string; approved: boolean }> {
+  const agentId = input.agentId ?? approvalGate.findByRequestId(input.requestId)?.agentId;
+  if (!agentId) {
+    throw new Error(`No pending approval ${input.requestId}; add ?agentId= to the link.`);
+  }
+  const key = { agentId, requestId: input.requestId };
+  const result = await requestNativeApproval(key);
+  if (result.approved) {
+    const sessionId = approvalGate.sessionIdFor(key);
+    const session = sessionId ? findOpenLocalTransportSession(sessionId) : null;
+    if (!session) {
+      throw new Error("The daemon that asked for this approval is no longer connected.");
+    }
+    await sendLocalDaemonFrame(
+      session.sessionId,
+      JSON.stringify({
+        type: "session",
+        message: {
+          type: "agent_permission_response",
+          agentId,
+          requestId: input.requestId,
+          response: { behavior: "allow" },
+          nativeApproval: result.signature,
+        },
+      }),
+    );
+  }
+  return { ...key, approved: result.approved };
+}
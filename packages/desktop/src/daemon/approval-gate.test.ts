@@ -0,0 +1,98 @@
+import { describe, expect, it } from "vitest";
+import { createApprovalGate } from "./approval-gate";
+
+function permissionRequest(command: string): string {
+  return JSON.stringify({
+    type: "session",
+    message: {
+      type: "agent_permission_request",
+      payload: {
+        agentId: "agent-1",
+        request: {
+          id: "perm-1",
+          provider: "claude",
+          name: "Bash",
+          kind: "tool",
+          detail: { type: "shell", command, cwd: "/work/repo" },
+        },
+      },
+    },
+  });
+}
+
+function permissionResponse(behavior: "allow" | "deny"): string {
+  return JSON.stringify({
+    type: "session",
+    message: {
+      type: "agent_permission_response",
+      agentId: "agent-1",
+      requestId: "perm-1",
+      response: { behavior },
+    },
+  });
+}
+
+describe("createApprovalGate", () => {
+  it("holds allow responses for high-risk requests until granted", () => {
+    const gate = createApprovalGate();
+    gate.observeInbound(permissionRequest("rm -rf build"), "s1");
+
+    expect(gate.pendingNativeApproval(permissionResponse("allow"))).toMatchObject({
+      agentId: "agent-1",
+      requestId: "perm-1",
+      command: "rm -rf build",
+      cwd: "/work/repo",
+      riskCategory: "destructive",
+    });
+    expect(gate.pendingNativeApproval(permissionResponse("deny"))).toBeNull();
+
+    expect(gate.isGranted({ agentId: "agent-1", requestId: "perm-1" })).toBe(false);
+    gate.grant({ agentId: "agent-1", requestId: "perm-1" });
+    expect(gate.pendingNativeApproval(permissionResponse("allow"))).toBeNull();
+    expect(gate.isGranted({ agentId: "agent-1", requestId: "perm-1" })).toBe(true);
+  });
+
+  it("holds escaped and binary allow responses too", () => {
+    const gate = createApprovalGate();
+    gate.observeInbound(permissionRequest("rm -rf build"), "s1");
+    const escaped = permissionResponse("allow").replace(
+      "agent_permission_response",
+      "agent\\u005fpermission_response",
+    );
+
+    expect(escaped).not.toContain("agent_permission_response");
+    expect(gate.pendingNativeApproval(escaped)).toMatchObject({ requestId: "perm-1" });
+    expect(
+      gate.pendingNativeApproval(Buffer.from(permissionResponse("allow"))),
+    ).toMatchObject({ requestId: "perm-1" });
+  });
+
+  it("finds a held request by its id alone", () => {
+    const gate = createApprovalGate();
+    gate.observeInbound(permissionRequest("sudo reboot"), "s1");
+
+    expect(gate.findByRequestId("perm-1")).toMatchObject({ agentId: "agent-1" });
+    expect(gate.findByRequestId("perm-2")).toBeNull();
+    expect(gate.sessionIdFor({ agentId: "agent-1", requestId: "perm-1" })).toBe("s1");
+  });
+
+  it("ignores low-risk requests and forgets resolved ones", () => {
+    const gate = createApprovalGate();
+    gate.observeInbound(permissionRequest("npm test"), "s1");
+    expect(gate.pendingNativeApproval(permissionResponse("allow"))).toBeNull();
+
+    gate.observeInbound(permissionRequest("sudo reboot"), "s1");
+    gate.observeInbound(
+      JSON.stringify({
+        type: "session",
+        message: {
+          type: "agent_permission_resolved",
+          payload: { agentId: "agent-1", requestId: "perm-1", resolution: { behavior: "deny" } },
+        },
+      }),
+      "s1",
+    );
+    expect(gate.pendingNativeApproval(permissionResponse("allow"))).toBeNull();
+    expect(gate.sessionIdFor({ agentId: "agent-1", requestId: "perm-1" })).toBeNull();
+  });
+});
@@ -0,0 +1,146 @@
+import {
+  classifyApprovalRisk,
+  extractPermissionCommand,
+  type ApprovalRiskCategory,
+} from "@getpaseo/server";
+import { isRecord } from "../utils/is-record.js";
+
+export interface NativeApprovalDetails {
+  agentId: string;
+  requestId: string;
+  title: string;
+  command: string | null;
+  cwd: string | null;
+  riskCategory: ApprovalRiskCategory;
+}
+
+export interface ApprovalGate {
+  /** Records high-risk permission requests as they arrive on session `sessionId`. */
+  observeInbound(text: string, sessionId: string): void;
+  /**
+   * Details of the request when `frame` is an allow response that has not
+   * been confirmed natively yet; `null` when it may be forwarded as-is. Binary
+   * frames count too: the daemon parses any non-terminal binary frame as JSON.
+   */
+  pendingNativeApproval(frame: string | Buffer): NativeApprovalDetails | null;
+  getDetails(input: { agentId: string; requestId: string }): NativeApprovalDetails | null;
+  /** For callers that only know the request id, such as a `paseo://approve` link. */
+  findByRequestId(requestId: string): NativeApprovalDetails | null;
+  grant(input: { agentId: string; requestId: string }): void;
+  isGranted(input: { agentId: string; requestId: string }): boolean;
+  /** The session a held request arrived on, where its response has to go. */
+  sessionIdFor(input: { agentId: string; requestId: string }): string | null;
+}
+
+function parseSessionMessage(text: string): Record<string, unknown> | null {
+  try {
+    const parsed: unknown = JSON.parse(text);
+    if (isRecord(parsed) && parsed.type === "session" && isRecord(parsed.message)) {
+      return parsed.message;
+    }
+  } catch {
+    // not JSON
+  }
+  return null;
+}
+
+function toKey(input: { agentId: string; requestId: string }): string {
+  return `${input.agentId}:${input.requestId}`;
+}
+
+/** Pulls the shell command out of an `agent_permission_request` payload, if it has one. */
+export function extractApprovalDetails(payload: unknown): NativeApprovalDetails | null {
+  if (!isRecord(payload) || typeof payload.agentId !== "string" || !isRecord(payload.request)) {
+    return null;
+  }
+  const request = payload.request;
+  if (typeof request.id !== "string") {
+    return null;
+  }
+
+  // Classified the same way the daemon does when it checks the signed allow.
+  const shell = extractPermissionCommand(request);
+  const riskCategory = shell ? classifyApprovalRisk(shell.command) : null;
+  if (!shell || !riskCategory) {
+    return null;
+  }
+  return {
+    agentId: payload.agentId,
+    requestId: request.id,
+    title: typeof request.title === "string" ? request.title : String(request.name ?? "Command"),
+    command: shell.command,
+    cwd: shell.cwd,
+    riskCategory,
+  };
+}
+
+export function createApprovalGate(): ApprovalGate {
+  const pending = new Map<string, NativeApprovalDetails>();
+  const sessionIds = new Map<string, string>();
+  const granted = new Set<string>();
+
+  return {
+    observeInbound(text, sessionId) {
+      const message = parseSessionMessage(text);
+      if (message?.type === "agent_permission_request") {
+        const details = extractApprovalDetails(message.payload);
+        if (details) {
+          pending.set(toKey(details), details);
+          sessionIds.set(toKey(details), sessionId);
+        }
+        return;
+      }
+      if (message?.type === "agent_permission_resolved" && isRecord(message.payload)) {
+        const { agentId, requestId } = message.payload;
+        if (typeof agentId === "string" && typeof requestId === "string") {
+          pending.delete(toKey({ agentId, requestId }));
+          sessionIds.delete(toKey({ agentId, requestId }));
+          granted.delete(toKey({ agentId, requestId }));
+        }
+      }
+    },
+
+    pendingNativeApproval(frame) {
+      if (pending.size === 0) {
+        return null;
+      }
+      // Parsed in full: a substring check misses escapes such as `agent\u005fpermission`.
+      const message = parseSessionMessage(typeof frame === "string" ? frame : frame.toString());
+      if (
+        message?.type !== "agent_permission_response" ||
+        typeof message.agentId !== "string" ||
+        typeof message.requestId !== "string" ||
+        !isRecord(message.response) ||
+        message.response.behavior !== "allow"
+      ) {
+        return null;
+      }
+      const key = toKey({ agentId: message.agentId, requestId: message.requestId });
+      const details = pending.get(key);
+      if (!details || granted.has(key)) {
+        return null;
+      }
+      return details;
+    },
+
+    getDetails(input) {
+      return pending.get(toKey(input)) ?? null;
+    },
+
+    findByRequestId(requestId) {
+      return [...pending.values()].find((details) => details.requestId === requestId) ?? null;
+    },
+
+    grant(input) {
+      granted.add(toKey(input));
+    },
+
+    isGranted(input) {
+      return granted.has(toKey(input));
+    },
+
+    sessionIdFor(input) {
+      return sessionIds.get(toKey(input)) ?? null;
+    },
+  };
+}
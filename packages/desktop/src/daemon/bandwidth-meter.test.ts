@@ -0,0 +1,61 @@
+import { describe, expect, it } from "vitest";
+import { createBandwidthMeter } from "./bandwidth-meter";
+
+describe("createBandwidthMeter", () => {
+  it("separates rolling window usage from lifetime totals", () => {
+    const meter = createBandwidthMeter();
+    meter.open("a", 0);
+    meter.recordReceived("a", 1_000, 0);
+    meter.recordSent("a", 200, 500);
+    meter.recordReceived("a", 3_000, 30_000);
+
+    const stats = meter.getStats({ windowMs: 10_000, nowMs: 30_500 });
+
+    expect(stats).toEqual({
+      windowMs: 10_000,
+      bytesSent: 0,
+      bytesReceived: 3_000,
+      connections: [
+        {
+          connectionId: "a",
+          open: true,
+          openedAt: new Date(0).toISOString(),
+          bytesSent: 0,
+          bytesReceived: 3_000,
+          totalBytesSent: 200,
+          totalBytesReceived: 4_000,
+        },
+      ],
+    });
+  });
+
+  it("orders connections by usage within the window", () => {
+    const meter = createBandwidthMeter();
+    meter.recordReceived("quiet", 10, 1_000);
+    meter.recordReceived("busy", 10_000, 1_000);
+
+    const stats = meter.getStats({ windowMs: 60_000, nowMs: 2_000 });
+
+    expect(stats.connections.map((entry) => entry.connectionId)).toEqual(["busy", "quiet"]);
+    expect(stats.bytesReceived).toBe(10_010);
+  });
+
+  it("forgets closed connections after the retention period", () => {
+    const meter = createBandwidthMeter({ retentionMs: 60_000 });
+    meter.open("a", 0);
+    meter.recordSent("a", 50, 0);
+    meter.close("a", 1_000);
+
+    expect(meter.hasOpenConnections()).toBe(false);
+    expect(meter.getStats({ windowMs: 60_000, nowMs: 30_000 }).connections).toHaveLength(1);
+    expect(meter.getStats({ windowMs: 60_000, nowMs: 120_000 }).connections).toEqual([]);
+  });
+
+  it("ignores empty and invalid byte counts", () => {
+    const meter = createBandwidthMeter();
+    meter.recordSent("a", 0, 0);
+    meter.recordSent("a", Number.NaN, 0);
+
+    expect(meter.getStats({ windowMs: 1_000, nowMs: 0 }).connections).toEqual([]);
+  });
+});
@@ -0,0 +1,165 @@
+const BUCKET_MS = 1000;
+const DEFAULT_RETENTION_MS = 15 * 60_000;
+
+interface BandwidthBucket {
+  startMs: number;
+  sent: number;
+  received: number;
+}
+
+interface ConnectionUsage {
+  connectionId: string;
+  openedAtMs: number;
+  closedAtMs: number | null;
+  totalSent: number;
+  totalReceived: number;
+  buckets: BandwidthBucket[];
+}
+
+export interface ConnectionBandwidthStats {
+  connectionId: string;
+  open: boolean;
+  openedAt: string;
+  bytesSent: number;
+  bytesReceived: number;
+  totalBytesSent: number;
+  totalBytesReceived: number;
+}
+
+export interface BandwidthStats {
+  windowMs: number;
+  bytesSent: number;
+  bytesReceived: number;
+  connections: ConnectionBandwidthStats[];
+}
+
+export interface BandwidthMeter {
+  open(connectionId: string, nowMs: number): void;
+  close(connectionId: string, nowMs: number): void;
+  recordSent(connectionId: string, bytes: number, nowMs: number): void;
+  recordReceived(connectionId: string, bytes: number, nowMs: number): void;
+  getStats(input: { windowMs: number; nowMs: number }): BandwidthStats;
+  hasOpenConnections(): boolean;
+}
+
+export function createBandwidthMeter(options?: { retentionMs?: number }): BandwidthMeter {
+  const retentionMs = options?.retentionMs ?? DEFAULT_RETENTION_MS;
+  const usageByConnection = new Map<string, ConnectionUsage>();
+
+  function prune(nowMs: number): void {
+    const cutoff = nowMs - retentionMs;
+    for (const [connectionId, usage] of usageByConnection) {
+      if (usage.closedAtMs !== null && usage.closedAtMs < cutoff) {
+        usageByConnection.delete(connectionId);
+        continue;
+      }
+      const firstRetained = usage.buckets.findIndex((bucket) => bucket.startMs >= cutoff);
+      usage.buckets.splice(0, firstRetained === -1 ? usage.buckets.length : firstRetained);
+    }
+  }
+
+  function ensureUsage(connectionId: string, nowMs: number): ConnectionUsage {
+    let usage = usageByConnection.get(connectionId);
+    if (!usage) {
+      usage = {
+        connectionId,
+        openedAtMs: nowMs,
+        closedAtMs: null,
+        totalSent: 0,
+        totalReceived: 0,
+        buckets: [],
+      };
+      usageByConnection.set(connectionId, usage);
+    }
+    return usage;
+  }
+
+  function currentBucket(usage: ConnectionUsage, nowMs: number): BandwidthBucket {
+    const startMs = nowMs - (nowMs % BUCKET_MS);
+    const last = usage.buckets.at(-1);
+    if (last && last.startMs === startMs) {
+      return last;
+    }
+    const bucket = { startMs, sent: 0, received: 0 };
+    usage.buckets.push(bucket);
+    return bucket;
+  }
+
+  function record(input: {
+    connectionId: string;
+    bytes: number;
+    nowMs: number;
+    direction: "sent" | "received";
+  }): void {
+    if (!Number.isFinite(input.bytes) || input.bytes <= 0) {
+      return;
+    }
+    const usage = ensureUsage(input.connectionId, input.nowMs);
+    const bucket = currentBucket(usage, input.nowMs);
+    if (input.direction === "sent") {
+      usage.totalSent += input.bytes;
+      bucket.sent += input.bytes;
+    } else {
+      usage.totalReceived += input.bytes;
+      bucket.received += input.bytes;
+    }
+    prune(input.nowMs);
+  }
+
+  return {
+    open(connectionId, nowMs) {
+      ensureUsage(connectionId, nowMs);
+    },
+
+    close(connectionId, nowMs) {
+      const usage = usageByConnection.get(connectionId);
+      if (usage && usage.closedAtMs === null) {
+        usage.closedAtMs = nowMs;
+      }
+    },
+
+    recordSent(connectionId, bytes, nowMs) {
+      record({ connectionId, bytes, nowMs, direction: "sent" });
+    },
+
+    recordReceived(connectionId, bytes, nowMs) {
+      record({ connectionId, bytes, nowMs, direction: "received" });
+    },
+
+    getStats({ windowMs, nowMs }) {
+      prune(nowMs);
+      const effectiveWindowMs = Math.min(Math.max(windowMs, BUCKET_MS), retentionMs);
+      const cutoff = nowMs - effectiveWindowMs;
+      const connections: ConnectionBandwidthStats[] = [];
+
+      for (const usage of usageByConnection.values()) {
+        const inWindow = usage.buckets.filter((bucket) => bucket.startMs + BUCKET_MS > cutoff);
+        connections.push({
+          connectionId: usage.connectionId,
+          open: usage.closedAtMs === null,
+          openedAt: new Date(usage.openedAtMs).toISOString(),
+          bytesSent: inWindow.reduce((sum, bucket) => sum + bucket.sent, 0),
+          bytesReceived: inWindow.reduce((sum, bucket) => sum + bucket.received, 0),
+          totalBytesSent: usage.totalSent,
+          totalBytesReceived: usage.totalReceived,
+        });
+      }
+
+      connections.sort((a, b) => b.bytesSent + b.bytesReceived - (a.bytesSent + a.bytesReceived));
+
+      return {
+        windowMs: effectiveWindowMs,
+        bytesSent: connections.reduce((sum, entry) => sum + entry.bytesSent, 0),
+        bytesReceived: connections.reduce((sum, entry) => sum + entry.bytesReceived, 0),
+        connections,
+      };
+    },
+
+    hasOpenConnections() {
+      for (const usage of usageByConnection.values()) {
+        if (usage.closedAtMs === null) return true;
+      }
+      return false;
+    },
+  };
+}
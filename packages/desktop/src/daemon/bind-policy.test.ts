@@ -0,0 +1,57 @@
+import { describe, expect, it } from "vitest";
+import {
+  isListenApproved,
+  isLoopbackHost,
+  parseMacFirewallState,
+  parseWindowsFirewallState,
+} from "./bind-policy";
+
+describe("isLoopbackHost", () => {
+  it("accepts loopback names and addresses", () => {
+    expect(isLoopbackHost("127.0.0.1")).toBe(true);
+    expect(isLoopbackHost("127.0.1.1")).toBe(true);
+    expect(isLoopbackHost("localhost")).toBe(true);
+    expect(isLoopbackHost("[::1]")).toBe(true);
+  });
+
+  it("rejects wildcard and LAN addresses", () => {
+    expect(isLoopbackHost("0.0.0.0")).toBe(false);
+    expect(isLoopbackHost("::")).toBe(false);
+    expect(isLoopbackHost("192.168.1.20")).toBe(false);
+    expect(isLoopbackHost("paseo.local")).toBe(false);
+  });
+});
+
+describe("isListenApproved", () => {
+  it("only lets the approved non-loopback address through", () => {
+    expect(isListenApproved("127.0.0.1:6767", null)).toBe(true);
+    expect(isListenApproved("/tmp/paseo.sock", null)).toBe(true);
+    expect(isListenApproved("0.0.0.0:6767", null)).toBe(false);
+    expect(isListenApproved("0.0.0.0:6767", "0.0.0.0:6767")).toBe(true);
+    expect(isListenApproved("0.0.0.0:6768", "0.0.0.0:6767")).toBe(false);
+    expect(isListenApproved("192.168.1.20:6767", "0.0.0.0:6767")).toBe(false);
+  });
+});
+
+describe("firewall state parsers", () => {
+  it("reads the macOS application firewall state", () => {
+    expect(parseMacFirewallState("Firewall is enabled. (State = 1)\n")).toBe(true);
+    expect(parseMacFirewallState("Firewall is disabled. (State = 0)\n")).toBe(false);
+  });
+
+  it("treats any enabled Windows profile as prompting", () => {
+    const stdout = [
+      "Domain Profile Settings:",
+      "----------------------------------------------------------------------",
+      "State                                 OFF",
+      "",
+      "Private Profile Settings:",
+      "----------------------------------------------------------------------",
+      "State                                 ON",
+      "Ok.",
+    ].join("\r\n");
+
+    expect(parseWindowsFirewallState(stdout)).toBe(true);
+    expect(parseWindowsFirewallState(stdout.replace(/ON/g, "OFF"))).toBe(false);
+  });
+});
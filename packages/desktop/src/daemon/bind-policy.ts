@@ -0,0 +1,72 @@
+import { execFile } from "node:child_process";
+import { isIP } from "node:net";
+import { promisify } from "node:util";
+import { parseTcpListenAddress } from "./port-selection.js";
+
+const execFileAsync = promisify(execFile);
+
+const FIREWALL_QUERY_TIMEOUT_MS = 5_000;
+const MAC_SOCKETFILTERFW = "/usr/libexec/ApplicationFirewall/socketfilterfw";
+
+export function isLoopbackHost(host: string): boolean {
+  const normalized = host.trim().toLowerCase().replace(/^\[|\]$/g, "");
+  if (normalized === "localhost" || normalized === "::1") {
+    return true;
+  }
+  return isIP(normalized) === 4 && normalized.startsWith("127.");
+}
+
+/**
+ * Whether `listen` may be used without asking: a socket or loopback address,
+ * or the same host and port as the non-loopback address the user approved.
+ */
+export function isListenApproved(listen: string | null, approved: string | null): boolean {
+  const address = parseTcpListenAddress(listen);
+  if (!address || isLoopbackHost(address.host)) {
+    return true;
+  }
+  const approvedAddress = parseTcpListenAddress(approved);
+  return (
+    approvedAddress !== null &&
+    approvedAddress.host.toLowerCase() === address.host.toLowerCase() &&
+    approvedAddress.port === address.port
+  );
+}
+
+/** `socketfilterfw --getglobalstate`: `Firewall is enabled. (State = 1)`. */
+export function parseMacFirewallState(stdout: string): boolean {
+  return /State = [12]/.test(stdout) || /firewall is enabled/i.test(stdout);
+}
+
+/** `netsh advfirewall show allprofiles state`: one `State  ON|OFF` line per profile. */
+export function parseWindowsFirewallState(stdout: string): boolean {
+  return /^\s*State\s+ON\s*$/im.test(stdout);
+}
+
+/**
+ * Whether binding a non-loopback address will make the OS ask the user to
+ * allow incoming connections. Linux firewalls drop traffic silently instead.
+ */
+export async function willFirewallPromptForLanBind(platform: NodeJS.Platform): Promise<boolean> {
+  const run = async (file: string, args: string[]) => {
+    const { stdout } = await execFileAsync(file, args, {
+      timeout: FIREWALL_QUERY_TIMEOUT_MS,
+      windowsHide: true,
+    });
+    return stdout;
+  };
+  try {
+    if (platform === "darwin") {
+      return parseMacFirewallState(await run(MAC_SOCKETFILTERFW, ["--getglobalstate"]));
+    }
+    if (platform === "win32") {
+      return parseWindowsFirewallState(
+        await run("netsh", ["advfirewall", "show", "allprofiles", "state"]),
+      );
+    }
+  } catch {
+    // Unknown state: assume the usual default, which prompts on both platforms.
+    return true;
+  }
+  return false;
+}
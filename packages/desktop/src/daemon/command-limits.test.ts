@@ -0,0 +1,69 @@
+import { describe, expect, it } from "vitest";
+import { createCommandLimiter, limitCommandHandlers } from "./command-limits";
+
+function deferred() {
+  let resolve: () => void = () => undefined;
+  const promise = new Promise<void>((done) => {
+    resolve = done;
+  });
+  return { promise, resolve };
+}
+
+describe("command limits", () => {
+  it("queues calls past the category limit and reports queue depth", async () => {
+    let clock = 0;
+    const limiter = createCommandLimiter({
+      limits: { attachments: 1, shell: 2, filesystem: 1 },
+      now: () => clock,
+    });
+    const first = deferred();
+    const started: string[] = [];
+
+    const running = limiter.run("attachments", async () => {
+      started.push("first");
+      await first.promise;
+    });
+    const queued = limiter.run("attachments", () => {
+      started.push("second");
+    });
+    const other = limiter.run("shell", () => {
+      started.push("shell");
+    });
+    await other;
+
+    expect(started).toEqual(["first", "shell"]);
+    expect(limiter.metrics().attachments).toMatchObject({ active: 1, queued: 1, peakQueued: 1 });
+
+    clock = 40;
+    first.resolve();
+    await Promise.all([running, queued]);
+
+    expect(started).toEqual(["first", "shell", "second"]);
+    expect(limiter.metrics().attachments).toEqual({
+      limit: 1,
+      active: 0,
+      queued: 0,
+      peakQueued: 1,
+      completed: 2,
+      maxWaitMs: 40,
+    });
+  });
+
+  it("releases the slot when a command fails", async () => {
+    const limiter = createCommandLimiter({ limits: { attachments: 1, shell: 1, filesystem: 1 } });
+    const handlers = limitCommandHandlers(
+      {
+        read_file_base64: () => {
+          throw new Error("too large");
+        },
+        get_about_info: () => "about",
+      },
+      limiter,
+    );
+
+    await expect(handlers.read_file_base64?.({})).rejects.toThrow("too large");
+    await expect(handlers.read_file_base64?.({})).rejects.toThrow("too large");
+    expect(handlers.get_about_info?.()).toBe("about");
+    expect(limiter.metrics().attachments).toMatchObject({ active: 0, completed: 2 });
+  });
+});
@@ -0,0 +1,146 @@
+import type { DesktopCommandHandler } from "../settings/desktop-settings-commands.js";
+
+/**
+ * Commands that lean on libuv's thread pool (fs, zlib, crypto) or spawn
+ * processes. The pool has four threads shared with `dns.lookup`, so a burst
+ * of renderer calls left unbounded also stalls update checks and daemon
+ * requests.
+ */
+export type CommandCategory = "attachments" | "shell" | "filesystem";
+
+export const DEFAULT_COMMAND_LIMITS: Record<CommandCategory, number> = {
+  attachments: 4,
+  shell: 4,
+  // A directory walk keeps many reads in flight by itself.
+  filesystem: 2,
+};
+
+export const COMMAND_CATEGORIES: Record<string, CommandCategory> = {
+  write_attachment_base64: "attachments",
+  write_attachment_bytes: "attachments",
+  copy_attachment_file: "attachments",
+  read_file_base64: "attachments",
+  delete_attachment_file: "attachments",
+  garbage_collect_attachment_files: "attachments",
+  set_attachment_storage_location: "attachments",
+  sign_commit_available: "shell",
+  create_worktree: "shell",
+  list_worktrees: "shell",
+  remove_worktree: "shell",
+  clone_repository: "shell",
+  apply_patch: "shell",
+  create_scratch_workspace: "shell",
+  estimate_directory_size: "filesystem",
+  move_to_trash: "filesystem",
+  delete_scratch_workspace: "filesystem",
+};
+
+export interface CommandCategoryMetrics {
+  limit: number;
+  active: number;
+  /** Calls waiting for a slot right now. */
+  queued: number;
+  /** Deepest the queue has been since launch. */
+  peakQueued: number;
+  completed: number;
+  /** Longest a call has waited for a slot, in milliseconds. */
+  maxWaitMs: number;
+}
+
+export interface CommandLimiter {
+  run<T>(category: CommandCategory, task: () => Promise<T> | T): Promise<T>;
+  metrics(): Record<CommandCategory, CommandCategoryMetrics>;
+}
+
+export function createCommandLimiter(input: {
+  limits: Record<CommandCategory, number>;
+  now?: () => number;
+}): CommandLimiter {
+  const now = input.now ?? Date.now;
+  const state = new Map<
+    CommandCategory,
+    { metrics: CommandCategoryMetrics; waiting: Array<() => void> }
+  >();
+  for (const category of Object.keys(input.limits) as CommandCategory[]) {
+    state.set(category, {
+      metrics: {
+        limit: input.limits[category],
+        active: 0,
+        queued: 0,
+        peakQueued: 0,
+        completed: 0,
+        maxWaitMs: 0,
+      },
+      waiting: [],
+    });
+  }
+
+  function stateFor(category: CommandCategory) {
+    const entry = state.get(category);
+    if (!entry) {
+      throw new Error(`Unknown command category: ${category}`);
+    }
+    return entry;
+  }
+
+  async function acquire(category: CommandCategory): Promise<void> {
+    const entry = stateFor(category);
+    const { metrics } = entry;
+    if (metrics.active < metrics.limit) {
+      metrics.active += 1;
+      return;
+    }
+    const queuedAt = now();
+    metrics.queued += 1;
+    metrics.peakQueued = Math.max(metrics.peakQueued, metrics.queued);
+    // The releasing call hands its slot over, so `active` stays at the limit.
+    await new Promise<void>((resolve) => entry.waiting.push(resolve));
+    metrics.queued -= 1;
+    metrics.maxWaitMs = Math.max(metrics.maxWaitMs, now() - queuedAt);
+  }
+
+  function release(category: CommandCategory): void {
+    const entry = stateFor(category);
+    entry.metrics.completed += 1;
+    const next = entry.waiting.shift();
+    if (next) {
+      next();
+    } else {
+      entry.metrics.active -= 1;
+    }
+  }
+
+  return {
+    async run(category, task) {
+      await acquire(category);
+      try {
+        return await task();
+      } finally {
+        release(category);
+      }
+    },
+    metrics() {
+      return Object.fromEntries(
+        [...state].map(([category, entry]) => [category, { ...entry.metrics }]),
+      ) as Record<CommandCategory, CommandCategoryMetrics>;
+    },
+  };
+}
+
+/** Wraps the handlers named in `categories`; everything else runs unbounded. */
+export function limitCommandHandlers(
+  handlers: Record<string, DesktopCommandHandler>,
+  limiter: CommandLimiter,
+  categories: Record<string, CommandCategory> = COMMAND_CATEGORIES,
+): Record<string, DesktopCommandHandler> {
+  return Object.fromEntries(
+    Object.entries(handlers).map(([name, handler]) => {
+      const category = categories[name];
+      if (!category) {
+        return [name, handler];
+      }
+      const limited: DesktopCommandHandler = (args) => limiter.run(category, () => handler(args));
+      return [name, limited];
+    }),
+  );
+}
@@ -0,0 +1,127 @@
+import { createHash } from "node:crypto";
+import http, { type Server } from "node:http";
+import type { AddressInfo } from "node:net";
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+import {
+  categorizeConnectionError,
+  parseDiagnosticsTarget,
+  runConnectionDiagnostics,
+} from "./connection-diagnostics";
+
+const WEBSOCKET_GUID = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+describe("parseDiagnosticsTarget", () => {
+  it("defaults ports and the WebSocket path by scheme", () => {
+    expect(parseDiagnosticsTarget("wss://paseo.example.com")).toEqual({
+      secure: true,
+      hostname: "paseo.example.com",
+      port: 443,
+      path: "/ws",
+    });
+    expect(parseDiagnosticsTarget("127.0.0.1:6767")).toEqual({
+      secure: false,
+      hostname: "127.0.0.1",
+      port: 6767,
+      path: "/ws",
+    });
+    expect(parseDiagnosticsTarget("ws://[::1]:6767/custom")).toMatchObject({
+      hostname: "::1",
+      path: "/custom",
+    });
+  });
+
+  it("rejects other schemes", () => {
+    expect(() => parseDiagnosticsTarget("ftp://example.com")).toThrow("Unsupported daemon URL");
+    expect(() => parseDiagnosticsTarget("")).toThrow("Invalid daemon URL");
+  });
+});
+
+describe("categorizeConnectionError", () => {
+  it("maps socket and certificate error codes", () => {
+    const withCode = (code: string) => Object.assign(new Error(code), { code });
+    expect(categorizeConnectionError(withCode("ENOTFOUND"))).toBe("dns_not_found");
+    expect(categorizeConnectionError(withCode("ECONNREFUSED"))).toBe("connection_refused");
+    expect(categorizeConnectionError(withCode("CERT_HAS_EXPIRED"))).toBe("certificate_expired");
+    expect(categorizeConnectionError(withCode("DEPTH_ZERO_SELF_SIGNED_CERT"))).toBe(
+      "certificate_untrusted",
+    );
+    expect(categorizeConnectionError(new Error("boom"))).toBe("unknown");
+  });
+});
+
+describe("runConnectionDiagnostics", () => {
+  let server: Server;
+  let port: number;
+
+  beforeEach(async () => {
+    server = http.createServer((req, res) => {
+      const authorized = req.headers.authorization === "Bearer secret";
+      res.writeHead(req.url === "/api/status" && authorized ? 200 : 401).end();
+    });
+    server.on("upgrade", (req, socket) => {
+      if (req.url !== "/ws") {
+        socket.end("HTTP/1.1 404 Not Found\r\n\r\n");
+        return;
+      }
+      const accept = createHash("sha1")
+        .update(`${String(req.headers["sec-websocket-key"])}${WEBSOCKET_GUID}`)
+        .digest("base64");
+      socket.end(
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n" +
+          `Sec-WebSocket-Accept: ${accept}\r\n\r\n`,
+      );
+    });
+    await new Promise<void>((resolve) => server.listen(0, "127.0.0.1", resolve));
+    port = (server.address() as AddressInfo).port;
+  });
+
+  afterEach(async () => {
+    await new Promise((resolve) => server.close(resolve));
+  });
+
+  it("passes every step against a reachable daemon", async () => {
+    const report = await runConnectionDiagnostics({
+      url: `ws://127.0.0.1:${port}`,
+      authToken: "secret",
+    });
+
+    expect(report.ok).toBe(true);
+    expect(report.steps.map((step) => [step.step, step.status])).toEqual([
+      ["dns", "ok"],
+      ["tcp", "ok"],
+      ["tls", "skipped"],
+      ["websocket", "ok"],
+      ["auth", "ok"],
+    ]);
+    expect(report.steps[0]?.durationMs).toEqual(expect.any(Number));
+  });
+
+  it("categorizes a rejected token", async () => {
+    const report = await runConnectionDiagnostics({
+      url: `ws://127.0.0.1:${port}`,
+      authToken: "wrong",
+    });
+
+    expect(report.ok).toBe(false);
+    expect(report.steps.at(-1)).toMatchObject({ step: "auth", category: "unauthorized" });
+  });
+
+  it("skips the remaining steps after a refused connection", async () => {
+    await new Promise((resolve) => server.close(resolve));
+    server = http.createServer();
+    await new Promise<void>((resolve) => server.listen(0, "127.0.0.1", resolve));
+
+    const report = await runConnectionDiagnostics({
+      url: `ws://127.0.0.1:${port}`,
+      authToken: null,
+    });
+
+    expect(report.steps.map((step) => [step.step, step.status, step.category])).toEqual([
+      ["dns", "ok", null],
+      ["tcp", "failed", "connection_refused"],
+      ["tls", "skipped", null],
+      ["websocket", "skipped", null],
+      ["auth", "skipped", null],
+    ]);
+  });
+});
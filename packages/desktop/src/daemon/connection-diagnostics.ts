@@ -0,0 +1,319 @@
+import { randomBytes } from "node:crypto";
+import { lookup } from "node:dns/promises";
+import http from "node:http";
+import https from "node:https";
+import net from "node:net";
+import tls from "node:tls";
+
+const STEP_TIMEOUT_MS = 5_000;
+const DEFAULT_WEBSOCKET_PATH = "/ws";
+const AUTH_CHECK_PATH = "/api/status";
+
+export const DIAGNOSTIC_STEPS = ["dns", "tcp", "tls", "websocket", "auth"] as const;
+
+export type DiagnosticStepName = (typeof DIAGNOSTIC_STEPS)[number];
+
+export type DiagnosticFailureCategory =
+  | "dns_not_found"
+  | "connection_refused"
+  | "unreachable"
+  | "timeout"
+  | "certificate_untrusted"
+  | "certificate_expired"
+  | "certificate_hostname_mismatch"
+  | "tls_handshake_failed"
+  | "origin_rejected"
+  | "upgrade_rejected"
+  | "auth_required"
+  | "unauthorized"
+  | "unknown";
+
+export interface DiagnosticStepResult {
+  step: DiagnosticStepName;
+  status: "ok" | "failed" | "skipped";
+  durationMs: number | null;
+  category: DiagnosticFailureCategory | null;
+  message: string | null;
+}
+
+export interface ConnectionDiagnosticsReport {
+  url: string;
+  ok: boolean;
+  steps: DiagnosticStepResult[];
+}
+
+export interface DiagnosticsTarget {
+  secure: boolean;
+  hostname: string;
+  port: number;
+  path: string;
+}
+
+/** `ws://`, `wss://`, `http://`, `https://` URLs, or a bare `host:port` daemon address. */
+export function parseDiagnosticsTarget(value: unknown): DiagnosticsTarget {
+  const raw = typeof value === "string" ? value.trim() : "";
+  const withScheme = /^[a-z]+:\/\//i.test(raw) ? raw : `ws://${raw}`;
+  let url: URL;
+  try {
+    url = new URL(withScheme);
+  } catch {
+    throw new Error(`Invalid daemon URL: ${String(value)}`);
+  }
+  if (!["ws:", "wss:", "http:", "https:"].includes(url.protocol) || !url.hostname) {
+    throw new Error(`Unsupported daemon URL: ${String(value)}`);
+  }
+  const secure = url.protocol === "wss:" || url.protocol === "https:";
+  return {
+    secure,
+    hostname: url.hostname.replace(/^\[|\]$/g, ""),
+    port: url.port ? Number(url.port) : secure ? 443 : 80,
+    path: url.pathname === "/" ? DEFAULT_WEBSOCKET_PATH : url.pathname,
+  };
+}
+
+export function categorizeConnectionError(error: unknown): DiagnosticFailureCategory {
+  const code =
+    typeof error === "object" && error !== null && "code" in error
+      ? String((error as { code: unknown }).code)
+      : "";
+  switch (code) {
+    case "ENOTFOUND":
+    case "EAI_AGAIN":
+    case "EAI_NONAME":
+      return "dns_not_found";
+    case "ECONNREFUSED":
+      return "connection_refused";
+    case "EHOSTUNREACH":
+    case "ENETUNREACH":
+    case "EADDRNOTAVAIL":
+      return "unreachable";
+    case "ETIMEDOUT":
+    case "ESTEPTIMEOUT":
+      return "timeout";
+    case "CERT_HAS_EXPIRED":
+    case "CERT_NOT_YET_VALID":
+      return "certificate_expired";
+    case "ERR_TLS_CERT_ALTNAME_INVALID":
+      return "certificate_hostname_mismatch";
+    case "DEPTH_ZERO_SELF_SIGNED_CERT":
+    case "SELF_SIGNED_CERT_IN_CHAIN":
+    case "UNABLE_TO_VERIFY_LEAF_SIGNATURE":
+    case "UNABLE_TO_GET_ISSUER_CERT_LOCALLY":
+      return "certificate_untrusted";
+    default:
+      return code.startsWith("ERR_SSL") || code.startsWith("ERR_TLS")
+        ? "tls_handshake_failed"
+        : "unknown";
+  }
+}
+
+function stepTimeoutError(step: DiagnosticStepName): Error {
+  return Object.assign(new Error(`${step} step timed out after ${STEP_TIMEOUT_MS}ms`), {
+    code: "ESTEPTIMEOUT",
+  });
+}
+
+function connectTcp(address: string, port: number): Promise<void> {
+  return new Promise((resolve, reject) => {
+    const socket = net.connect({ host: address, port });
+    socket.setTimeout(STEP_TIMEOUT_MS, () => socket.destroy(stepTimeoutError("tcp")));
+    socket.once("connect", () => {
+      socket.destroy();
+      resolve();
+    });
+    socket.once("error", reject);
+  });
+}
+
+function handshakeTls(address: string, target: DiagnosticsTarget): Promise<void> {
+  return new Promise((resolve, reject) => {
+    const socket = tls.connect({
+      host: address,
+      port: target.port,
+      servername: net.isIP(target.hostname) ? undefined : target.hostname,
+    });
+    socket.setTimeout(STEP_TIMEOUT_MS, () => socket.destroy(stepTimeoutError("tls")));
+    socket.once("secureConnect", () => {
+      // Node only fails the connection on a bad certificate; the hostname is checked here.
+      const error = tls.checkServerIdentity(target.hostname, socket.getPeerCertificate());
+      socket.destroy();
+      if (error) {
+        reject(error);
+        return;
+      }
+      resolve();
+    });
+    socket.once("error", reject);
+  });
+}
+
+function requestOptions(
+  address: string,
+  target: DiagnosticsTarget,
+  input: { path: string; headers: Record<string, string> },
+): https.RequestOptions {
+  const hostHeader = net.isIP(target.hostname) === 6 ? `[${target.hostname}]` : target.hostname;
+  return {
+    host: address,
+    port: target.port,
+    path: input.path,
+    servername: target.secure && !net.isIP(target.hostname) ? target.hostname : undefined,
+    headers: { host: `${hostHeader}:${target.port}`, ...input.headers },
+    timeout: STEP_TIMEOUT_MS,
+  };
+}
+
+class StepFailure extends Error {
+  constructor(
+    message: string,
+    readonly category: DiagnosticFailureCategory,
+  ) {
+    super(message);
+  }
+}
+
+function upgradeWebSocket(address: string, target: DiagnosticsTarget): Promise<void> {
+  const client = target.secure ? https : http;
+  return new Promise((resolve, reject) => {
+    const req = client.request(
+      requestOptions(address, target, {
+        path: target.path,
+        headers: {
+          connection: "Upgrade",
+          upgrade: "websocket",
+          "sec-websocket-version": "13",
+          "sec-websocket-key": randomBytes(16).toString("base64"),
+        },
+      }),
+    );
+    req.once("upgrade", (_res, socket) => {
+      socket.destroy();
+      resolve();
+    });
+    req.once("response", (res) => {
+      res.resume();
+      const status = res.statusCode ?? 0;
+      reject(
+        new StepFailure(
+          `WebSocket upgrade was answered with HTTP ${status}`,
+          status === 403 ? "origin_rejected" : "upgrade_rejected",
+        ),
+      );
+    });
+    req.once("timeout", () => req.destroy(stepTimeoutError("websocket")));
+    req.once("error", reject);
+    req.end();
+  });
+}
+
+function checkAuth(
+  address: string,
+  target: DiagnosticsTarget,
+  authToken: string | null,
+): Promise<string> {
+  const client = target.secure ? https : http;
+  return new Promise((resolve, reject) => {
+    const req = client.request(
+      requestOptions(address, target, {
+        path: AUTH_CHECK_PATH,
+        headers: authToken ? { authorization: `Bearer ${authToken}` } : {},
+      }),
+      (res) => {
+        res.resume();
+        const status = res.statusCode ?? 0;
+        if (status === 401) {
+          reject(
+            authToken
+              ? new StepFailure("The daemon rejected the saved password", "unauthorized")
+              : new StepFailure("The daemon requires a password", "auth_required"),
+          );
+          return;
+        }
+        if (status < 200 || status >= 300) {
+          reject(new StepFailure(`Status check was answered with HTTP ${status}`, "unknown"));
+          return;
+        }
+        resolve(authToken ? "Token accepted" : "No password required");
+      },
+    );
+    req.once("timeout", () => req.destroy(stepTimeoutError("auth")));
+    req.once("error", reject);
+    req.end();
+  });
+}
+
+/**
+ * Walks DNS → TCP → TLS → WebSocket upgrade → auth, one step at a time, so
+ * a "can't connect" report says where it broke. Steps after the first
+ * failure are skipped; TLS is skipped for plain `ws://` targets.
+ */
+export async function runConnectionDiagnostics(input: {
+  url: unknown;
+  authToken: string | null;
+}): Promise<ConnectionDiagnosticsReport> {
+  const target = parseDiagnosticsTarget(input.url);
+  const steps: DiagnosticStepResult[] = [];
+  let address = target.hostname;
+  let failed = false;
+
+  const run = async (
+    step: DiagnosticStepName,
+    action: () => Promise<string | null>,
+  ): Promise<void> => {
+    if (failed) {
+      steps.push({ step, status: "skipped", durationMs: null, category: null, message: null });
+      return;
+    }
+    const startedAt = performance.now();
+    try {
+      const message = await action();
+      const durationMs = Math.round(performance.now() - startedAt);
+      steps.push({ step, status: "ok", durationMs, category: null, message });
+    } catch (error) {
+      failed = true;
+      steps.push({
+        step,
+        status: "failed",
+        durationMs: Math.round(performance.now() - startedAt),
+        category: error instanceof StepFailure ? error.category : categorizeConnectionError(error),
+        message: error instanceof Error ? error.message : String(error),
+      });
+    }
+  };
+
+  await run("dns", async () => {
+    const result = await Promise.race([
+      lookup(target.hostname),
+      new Promise<never>((_, reject) =>
+        setTimeout(() => reject(stepTimeoutError("dns")), STEP_TIMEOUT_MS).unref(),
+      ),
+    ]);
+    address = result.address;
+    return address;
+  });
+  await run("tcp", async () => {
+    await connectTcp(address, target.port);
+    return null;
+  });
+  if (target.secure) {
+    await run("tls", async () => {
+      await handshakeTls(address, target);
+      return null;
+    });
+  } else {
+    steps.push({
+      step: "tls",
+      status: "skipped",
+      durationMs: null,
+      category: null,
+      message: "Plain WebSocket",
+    });
+  }
+  await run("websocket", async () => {
+    await upgradeWebSocket(address, target);
+    return null;
+  });
+  await run("auth", () => checkAuth(address, target, input.authToken));
+
+  return { url: String(input.url), ok: !failed, steps };
+}
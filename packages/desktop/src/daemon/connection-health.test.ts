@@ -0,0 +1,67 @@
+import { describe, expect, it } from "vitest";
+import { classifyConnectionHealth, createConnectionHealthTracker } from "./connection-health";
+
+describe("createConnectionHealthTracker", () => {
+  it("reports unknown health before any ping settles", () => {
+    const tracker = createConnectionHealthTracker({ connectionId: "local-session-1" });
+    tracker.recordPing(0);
+
+    expect(tracker.snapshot()).toEqual({
+      connectionId: "local-session-1",
+      status: "unknown",
+      rttMs: null,
+      jitterMs: null,
+      loss: 0,
+      samples: 0,
+      lastPongAt: null,
+    });
+  });
+
+  it("computes rolling rtt, jitter, and loss from settled pings", () => {
+    const tracker = createConnectionHealthTracker({ connectionId: "a" });
+    const first = tracker.recordPing(0);
+    tracker.recordPong(first, 10);
+    const second = tracker.recordPing(5_000);
+    tracker.recordPong(second, 5_030);
+    tracker.recordPing(10_000);
+    tracker.expirePings(15_000, 5_000);
+
+    expect(tracker.snapshot()).toMatchObject({
+      status: "red",
+      rttMs: 20,
+      jitterMs: 20,
+      loss: 0.333,
+      samples: 3,
+      lastPongAt: new Date(5_030).toISOString(),
+    });
+  });
+
+  it("ignores pongs for pings that already timed out", () => {
+    const tracker = createConnectionHealthTracker({ connectionId: "a" });
+    const seq = tracker.recordPing(0);
+    tracker.expirePings(6_000, 5_000);
+    tracker.recordPong(seq, 6_500);
+
+    expect(tracker.snapshot()).toMatchObject({ rttMs: null, loss: 1, status: "red" });
+  });
+
+  it("only keeps the configured number of samples", () => {
+    const tracker = createConnectionHealthTracker({ connectionId: "a", sampleWindow: 2 });
+    tracker.recordPing(0);
+    tracker.expirePings(5_000, 5_000);
+    tracker.recordPong(tracker.recordPing(5_000), 5_005);
+    tracker.recordPong(tracker.recordPing(10_000), 10_005);
+
+    expect(tracker.snapshot()).toMatchObject({ status: "green", loss: 0, samples: 2 });
+  });
+});
+
+describe("classifyConnectionHealth", () => {
+  it("maps latency and loss onto traffic-light thresholds", () => {
+    expect(classifyConnectionHealth({ rttMs: 5, loss: 0, samples: 4 })).toBe("green");
+    expect(classifyConnectionHealth({ rttMs: 300, loss: 0, samples: 4 })).toBe("yellow");
+    expect(classifyConnectionHealth({ rttMs: 5, loss: 0.1, samples: 10 })).toBe("yellow");
+    expect(classifyConnectionHealth({ rttMs: 1_500, loss: 0, samples: 4 })).toBe("red");
+    expect(classifyConnectionHealth({ rttMs: null, loss: 0, samples: 0 })).toBe("unknown");
+  });
+});
@@ -0,0 +1,116 @@
+const DEFAULT_SAMPLE_WINDOW = 20;
+const YELLOW_RTT_MS = 250;
+const RED_RTT_MS = 1_000;
+const YELLOW_LOSS = 0.1;
+const RED_LOSS = 0.3;
+
+export type ConnectionHealthStatus = "green" | "yellow" | "red" | "unknown";
+
+export interface ConnectionHealth {
+  connectionId: string;
+  status: ConnectionHealthStatus;
+  rttMs: number | null;
+  jitterMs: number | null;
+  /** Fraction of pings in the window that never got a pong, 0–1. */
+  loss: number;
+  samples: number;
+  lastPongAt: string | null;
+}
+
+interface PingSample {
+  seq: number;
+  sentAtMs: number;
+  rttMs: number | null;
+  lost: boolean;
+}
+
+export interface ConnectionHealthTracker {
+  recordPing(nowMs: number): number;
+  recordPong(seq: number, nowMs: number): void;
+  /** Marks every outstanding ping older than `timeoutMs` as lost. */
+  expirePings(nowMs: number, timeoutMs: number): void;
+  snapshot(): ConnectionHealth;
+}
+
+export function classifyConnectionHealth(input: {
+  rttMs: number | null;
+  loss: number;
+  samples: number;
+}): ConnectionHealthStatus {
+  if (input.samples === 0) {
+    return "unknown";
+  }
+  if (input.loss >= RED_LOSS || (input.rttMs !== null && input.rttMs >= RED_RTT_MS)) {
+    return "red";
+  }
+  if (input.loss >= YELLOW_LOSS || (input.rttMs !== null && input.rttMs >= YELLOW_RTT_MS)) {
+    return "yellow";
+  }
+  return "green";
+}
+
+function average(values: number[]): number | null {
+  if (values.length === 0) {
+    return null;
+  }
+  return values.reduce((sum, value) => sum + value, 0) / values.length;
+}
+
+export function createConnectionHealthTracker(input: {
+  connectionId: string;
+  sampleWindow?: number;
+}): ConnectionHealthTracker {
+  const sampleWindow = input.sampleWindow ?? DEFAULT_SAMPLE_WINDOW;
+  const samples: PingSample[] = [];
+  let nextSeq = 0;
+  let lastPongAtMs: number | null = null;
+
+  return {
+    recordPing(nowMs) {
+      const seq = nextSeq++;
+      samples.push({ seq, sentAtMs: nowMs, rttMs: null, lost: false });
+      if (samples.length > sampleWindow) {
+        samples.splice(0, samples.length - sampleWindow);
+      }
+      return seq;
+    },
+
+    recordPong(seq, nowMs) {
+      const sample = samples.find((entry) => entry.seq === seq);
+      if (!sample || sample.lost || sample.rttMs !== null) {
+        return;
+      }
+      sample.rttMs = Math.max(0, nowMs - sample.sentAtMs);
+      lastPongAtMs = nowMs;
+    },
+
+    expirePings(nowMs, timeoutMs) {
+      for (const sample of samples) {
+        if (sample.rttMs === null && !sample.lost && nowMs - sample.sentAtMs >= timeoutMs) {
+          sample.lost = true;
+        }
+      }
+    },
+
+    snapshot() {
+      const settled = samples.filter((sample) => sample.lost || sample.rttMs !== null);
+      const rtts = settled
+        .map((sample) => sample.rttMs)
+        .filter((rttMs): rttMs is number => rttMs !== null);
+      const lost = settled.filter((sample) => sample.lost).length;
+      const loss = settled.length > 0 ? lost / settled.length : 0;
+      const rttMs = average(rtts);
+      const jitterMs = average(rtts.slice(1).map((rtt, index) => Math.abs(rtt - rtts[index])));
+
+      return {
+        connectionId: input.connectionId,
+        status: classifyConnectionHealth({ rttMs, loss, samples: settled.length }),
+        rttMs: rttMs === null ? null : Math.round(rttMs * 10) / 10,
+        jitterMs: jitterMs === null ? null : Math.round(jitterMs * 10) / 10,
+        loss: Math.round(loss * 1000) / 1000,
+        samples: settled.length,
+        lastPongAt: lastPongAtMs === null ? null : new Date(lastPongAtMs).toISOString(),
+      };
+    },
+  };
+}
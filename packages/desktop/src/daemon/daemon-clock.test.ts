@@ -0,0 +1,42 @@
+import { describe, expect, it } from "vitest";
+import { buildPingFrame, computeClockSkew, parsePongReply } from "./daemon-clock";
+
+describe("computeClockSkew", () => {
+  it("splits the network delay evenly and excludes daemon processing time", () => {
+    expect(
+      computeClockSkew({
+        clientSentAt: 1_000,
+        serverReceivedAt: 6_010,
+        serverSentAt: 6_030,
+        clientReceivedAt: 1_050,
+      }),
+    ).toEqual({ skewMs: 4_995, roundTripMs: 30 });
+  });
+});
+
+describe("ping frames", () => {
+  it("wraps the ping in a session frame", () => {
+    expect(JSON.parse(buildPingFrame({ requestId: "r1", clientSentAt: 5 }))).toEqual({
+      type: "session",
+      message: { type: "ping", requestId: "r1", clientSentAt: 5 },
+    });
+  });
+
+  it("reads daemon timestamps from a pong and rejects anything else", () => {
+    const pong = {
+      type: "session",
+      message: {
+        type: "pong",
+        payload: { requestId: "r1", serverReceivedAt: 10, serverSentAt: 12 },
+      },
+    };
+    expect(parsePongReply(JSON.stringify(pong))).toEqual({
+      serverReceivedAt: 10,
+      serverSentAt: 12,
+    });
+    expect(parsePongReply(JSON.stringify({ type: "session", message: { type: "pong" } }))).toBe(
+      null,
+    );
+    expect(parsePongReply("{")).toBeNull();
+  });
+});
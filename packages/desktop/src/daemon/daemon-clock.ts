@@ -0,0 +1,57 @@
+import { isRecord } from "../utils/is-record.js";
+
+export interface DaemonClockSample {
+  /** Daemon clock minus local clock; positive when the daemon runs ahead. */
+  skewMs: number;
+  roundTripMs: number;
+}
+
+interface PongReply {
+  serverReceivedAt: number;
+  serverSentAt: number;
+}
+
+export function buildPingFrame(input: { requestId: string; clientSentAt: number }): string {
+  return JSON.stringify({
+    type: "session",
+    message: { type: "ping", requestId: input.requestId, clientSentAt: input.clientSentAt },
+  });
+}
+
+export function parsePongReply(text: string): PongReply | null {
+  try {
+    const parsed: unknown = JSON.parse(text);
+    if (!isRecord(parsed) || !isRecord(parsed.message) || parsed.message.type !== "pong") {
+      return null;
+    }
+    const payload = parsed.message.payload;
+    if (
+      !isRecord(payload) ||
+      typeof payload.serverReceivedAt !== "number" ||
+      typeof payload.serverSentAt !== "number"
+    ) {
+      return null;
+    }
+    return { serverReceivedAt: payload.serverReceivedAt, serverSentAt: payload.serverSentAt };
+  } catch {
+    return null;
+  }
+}
+
+/** NTP-style estimate: assumes the request and the reply spent equal time in flight. */
+export function computeClockSkew(input: {
+  clientSentAt: number;
+  serverReceivedAt: number;
+  serverSentAt: number;
+  clientReceivedAt: number;
+}): DaemonClockSample {
+  const skewMs = Math.round(
+    (input.serverReceivedAt - input.clientSentAt + (input.serverSentAt - input.clientReceivedAt)) /
+      2,
+  );
+  const roundTripMs = Math.max(
+    0,
+    input.clientReceivedAt - input.clientSentAt - (input.serverSentAt - input.serverReceivedAt),
+  );
+  return { skewMs, roundTripMs };
+}
@@ -0,0 +1,105 @@
+import { BrowserWindow, Notification, powerMonitor } from "electron";
+import log from "electron-log/main";
+import { emitDesktopEvent } from "../window/desktop-events.js";
+import {
+  createDaemonWatchdog,
+  type DaemonHealthIssue,
+  type DaemonHealthSample,
+} from "./daemon-watchdog.js";
+
+const ISSUE_MESSAGES: Record<DaemonHealthIssue, { title: string; body: string }> = {
+  crashed: {
+    title: "Paseo daemon stopped",
+    body: "Agents are not running while the daemon is down.",
+  },
+  port_lost: {
+    title: "Paseo daemon is unreachable",
+    body: "The daemon is running but no longer accepting connections.",
+  },
+  disk_full: {
+    title: "Disk almost full",
+    body: "The Paseo daemon may fail to save sessions until space is freed.",
+  },
+};
+
+const activeNotifications = new Set<Notification>();
+
+function isRunningInBackground(): boolean {
+  return BrowserWindow.getAllWindows().every((win) => win.isDestroyed() || !win.isVisible());
+}
+
+function showIssueNotification(issue: DaemonHealthIssue, restart: () => Promise<unknown>): void {
+  const message = ISSUE_MESSAGES[issue];
+  // Only macOS renders notification action buttons; elsewhere the whole toast restarts.
+  const hasActions = process.platform === "darwin";
+  const notification = new Notification({
+    title: message.title,
+    body: hasActions ? message.body : `${message.body} Click to restart the daemon.`,
+    actions: hasActions ? [{ type: "button", text: "Restart daemon" }] : [],
+  });
+  const restartFromNotification = () => {
+    activeNotifications.delete(notification);
+    void restart().catch((error) => {
+      log.error("[daemon-health] restart from notification failed", error);
+    });
+  };
+  notification.on("action", restartFromNotification);
+  if (!hasActions) {
+    notification.on("click", restartFromNotification);
+  }
+  notification.on("close", () => activeNotifications.delete(notification));
+  activeNotifications.add(notification);
+  notification.show();
+}
+
+/**
+ * Tells the user an updated daemon kept crashing after it started. `to` is the
+ * version now running in its place, or `null` when there was none to fall back to.
+ */
+export function notifyDaemonRolledBack(input: { version: string; to: string | null }): void {
+  emitDesktopEvent("daemon-rolled-back", input);
+  if (!Notification.isSupported()) {
+    return;
+  }
+  const notification = new Notification(
+    input.to
+      ? {
+          title: "Paseo daemon rolled back",
+          body: `Daemon ${input.version} kept crashing, so Paseo is running ${input.to} again.`,
+        }
+      : {
+          title: "Paseo daemon keeps crashing",
+          body: `Daemon ${input.version} crashed repeatedly and no earlier version is kept.`,
+        },
+  );
+  notification.on("close", () => activeNotifications.delete(notification));
+  activeNotifications.add(notification);
+  notification.show();
+}
+
+/**
+ * Watches the supervised daemon while the app keeps running without a
+ * visible window (tray or dock only), where a crash would otherwise go
+ * unnoticed. With a window open the renderer gets the event instead.
+ */
+export function startDaemonHealthNotifications(input: {
+  sample: () => Promise<DaemonHealthSample>;
+  restart: () => Promise<unknown>;
+}): void {
+  const watchdog = createDaemonWatchdog({
+    sample: input.sample,
+    onIssue: (issue) => {
+      log.warn("[daemon-health] detected issue", issue);
+      emitDesktopEvent("daemon-health-issue", { issue });
+      if (isRunningInBackground() && Notification.isSupported()) {
+        showIssueNotification(issue, input.restart);
+      }
+    },
+  });
+  watchdog.start();
+  powerMonitor.on("resume", () => {
+    void watchdog.check().catch((error) => {
+      log.warn("[daemon-health] check after resume failed", error);
+    });
+  });
+}
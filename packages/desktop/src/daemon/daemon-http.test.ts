@@ -0,0 +1,69 @@
+import { describe, expect, it } from "vitest";
+import { buildDaemonHttpRequestOptions, coerceDaemonHttpRequest } from "./daemon-http";
+
+describe("coerceDaemonHttpRequest", () => {
+  it("normalizes method, headers, and the auth token", () => {
+    expect(
+      coerceDaemonHttpRequest({
+        method: "post",
+        path: "/api/status",
+        body: "{}",
+        headers: { "X-Trace": "1", Host: "evil.example", Count: 2 },
+        stream: true,
+        authToken: " secret ",
+      }),
+    ).toEqual({
+      method: "POST",
+      path: "/api/status",
+      body: "{}",
+      headers: { "x-trace": "1" },
+      stream: true,
+      authToken: "secret",
+    });
+  });
+
+  it("rejects paths that are not origin-relative", () => {
+    expect(() => coerceDaemonHttpRequest({ path: "https://example.com/" })).toThrow();
+    expect(() => coerceDaemonHttpRequest({ path: "//example.com/" })).toThrow();
+    expect(() => coerceDaemonHttpRequest({ method: "TRACE", path: "/" })).toThrow();
+  });
+});
+
+describe("buildDaemonHttpRequestOptions", () => {
+  it("targets loopback for wildcard TCP binds and adds the bearer token", () => {
+    const request = coerceDaemonHttpRequest({
+      method: "POST",
+      path: "/api/files/download?token=t",
+      body: '{"a":1}',
+      authToken: "secret",
+    });
+
+    expect(buildDaemonHttpRequestOptions({ listen: "0.0.0.0:6767", request })).toEqual({
+      method: "POST",
+      path: "/api/files/download?token=t",
+      host: "127.0.0.1",
+      port: 6767,
+      headers: {
+        authorization: "Bearer secret",
+        "content-type": "application/json",
+        "content-length": "7",
+      },
+    });
+  });
+
+  it("speaks HTTPS with the daemon's CA when it serves TLS", () => {
+    const request = coerceDaemonHttpRequest({ path: "/api/health" });
+
+    expect(
+      buildDaemonHttpRequestOptions({ listen: "127.0.0.1:6767", request, ca: "CA PEM" }),
+    ).toMatchObject({ host: "127.0.0.1", port: 6767, protocol: "https:", ca: "CA PEM" });
+  });
+
+  it("connects through the daemon's unix socket", () => {
+    const request = coerceDaemonHttpRequest({ path: "/api/health" });
+
+    expect(
+      buildDaemonHttpRequestOptions({ listen: "/tmp/paseo/paseo.sock", request }),
+    ).toMatchObject({ method: "GET", socketPath: "/tmp/paseo/paseo.sock", headers: {} });
+  });
+});
@@ -0,0 +1,225 @@
+import http, { type IncomingHttpHeaders, type IncomingMessage } from "node:http";
+import https from "node:https";
+import { randomUUID } from "node:crypto";
+import { parseListenString } from "@getpaseo/server";
+import { emitDesktopEvent } from "../window/desktop-events.js";
+import { isRecord } from "../utils/is-record.js";
+
+const DAEMON_HTTP_METHODS = ["GET", "HEAD", "POST", "PUT", "PATCH", "DELETE"] as const;
+// Anything bigger should be requested with `stream: true` instead of crossing IPC in one piece.
+const MAX_BUFFERED_RESPONSE_BYTES = 16 * 1024 * 1024;
+const DAEMON_HTTP_TIMEOUT_MS = 30_000;
+const TEXT_CONTENT_TYPE_PATTERN = /^(text\/|application\/(json|[a-z0-9.+-]+\+json)\b)/i;
+
+export type DaemonHttpMethod = (typeof DAEMON_HTTP_METHODS)[number];
+
+export interface DaemonHttpRequest {
+  method: DaemonHttpMethod;
+  path: string;
+  body: string | null;
+  headers: Record<string, string>;
+  stream: boolean;
+  authToken: string | null;
+}
+
+export interface DaemonHttpResponse {
+  requestId: string;
+  status: number;
+  headers: Record<string, string>;
+  /** Set for text and JSON bodies of buffered requests. */
+  text: string | null;
+  /** Set for binary bodies of buffered requests. */
+  bodyBase64: string | null;
+  streaming: boolean;
+}
+
+interface DaemonHttpStreamEvent {
+  requestId: string;
+  kind: "data" | "end" | "error";
+  dataBase64?: string;
+  error?: string;
+}
+
+const activeStreams = new Map<string, http.ClientRequest>();
+
+function isDaemonHttpMethod(value: string): value is DaemonHttpMethod {
+  return (DAEMON_HTTP_METHODS as readonly string[]).includes(value);
+}
+
+export function coerceDaemonHttpRequest(
+  args: Record<string, unknown> | undefined,
+): DaemonHttpRequest {
+  const method = typeof args?.method === "string" ? args.method.toUpperCase() : "GET";
+  if (!isDaemonHttpMethod(method)) {
+    throw new Error(`Unsupported daemon HTTP method: ${method}`);
+  }
+
+  const requestPath = typeof args?.path === "string" ? args.path.trim() : "";
+  // Only origin-relative paths; anything else could redirect the request off the daemon.
+  if (!requestPath.startsWith("/") || requestPath.startsWith("//")) {
+    throw new Error("Daemon HTTP path must start with a single '/'.");
+  }
+
+  const headers: Record<string, string> = {};
+  if (isRecord(args?.headers)) {
+    for (const [name, value] of Object.entries(args.headers)) {
+      if (typeof value === "string" && name.toLowerCase() !== "host") {
+        headers[name.toLowerCase()] = value;
+      }
+    }
+  }
+
+  return {
+    method,
+    path: requestPath,
+    body: typeof args?.body === "string" ? args.body : null,
+    headers,
+    stream: args?.stream === true,
+    authToken:
+      typeof args?.authToken === "string" && args.authToken.trim().length > 0
+        ? args.authToken.trim()
+        : null,
+  };
+}
+
+/** `ca` is the daemon's TLS authority when it serves HTTPS on its TCP address. */
+export function buildDaemonHttpRequestOptions(input: {
+  listen: string;
+  request: DaemonHttpRequest;
+  ca?: string | null;
+}): https.RequestOptions {
+  const target = parseListenString(input.listen);
+  const headers: Record<string, string> = { ...input.request.headers };
+  if (input.request.authToken) {
+    headers.authorization = `Bearer ${input.request.authToken}`;
+  }
+  if (input.request.body !== null) {
+    headers["content-type"] ??= "application/json";
+    headers["content-length"] = String(Buffer.byteLength(input.request.body));
+  }
+
+  const base = { method: input.request.method, path: input.request.path, headers };
+  if (target.type === "tcp") {
+    // A wildcard bind is reachable on loopback, which the daemon's Host allowlist always accepts.
+    const host = target.host === "0.0.0.0" || target.host === "::" ? "127.0.0.1" : target.host;
+    return input.ca
+      ? { ...base, host, port: target.port, protocol: "https:", ca: input.ca }
+      : { ...base, host, port: target.port };
+  }
+  return { ...base, socketPath: target.path };
+}
+
+function flattenHeaders(headers: IncomingHttpHeaders): Record<string, string> {
+  const result: Record<string, string> = {};
+  for (const [name, value] of Object.entries(headers)) {
+    if (value !== undefined) {
+      result[name] = Array.isArray(value) ? value.join(", ") : value;
+    }
+  }
+  return result;
+}
+
+function emitStreamEvent(payload: DaemonHttpStreamEvent): void {
+  emitDesktopEvent("daemon-http-stream", payload);
+}
+
+function sendDaemonHttpRequest(
+  options: https.RequestOptions,
+  onResponse: (res: IncomingMessage) => void,
+): http.ClientRequest {
+  return options.protocol === "https:"
+    ? https.request(options, onResponse)
+    : http.request(options, onResponse);
+}
+
+export function performDaemonHttpRequest(input: {
+  listen: string | null;
+  request: DaemonHttpRequest;
+  ca?: string | null;
+}): Promise<DaemonHttpResponse> {
+  if (!input.listen) {
+    return Promise.reject(new Error("Local daemon is not running."));
+  }
+  const { request } = input;
+  const options = buildDaemonHttpRequestOptions({ listen: input.listen, request, ca: input.ca });
+  const requestId = randomUUID();
+
+  return new Promise((resolve, reject) => {
+    const req = sendDaemonHttpRequest(options, (res) => {
+      const status = res.statusCode ?? 0;
+      const headers = flattenHeaders(res.headers);
+
+      if (request.stream) {
+        activeStreams.set(requestId, req);
+        resolve({ requestId, status, headers, text: null, bodyBase64: null, streaming: true });
+        res.on("data", (chunk: Buffer) => {
+          emitStreamEvent({ requestId, kind: "data", dataBase64: chunk.toString("base64") });
+        });
+        res.on("end", () => {
+          activeStreams.delete(requestId);
+          emitStreamEvent({ requestId, kind: "end" });
+        });
+        res.on("error", (error) => {
+          activeStreams.delete(requestId);
+          emitStreamEvent({ requestId, kind: "error", error: error.message });
+        });
+        return;
+      }
+
+      const chunks: Buffer[] = [];
+      let received = 0;
+      res.on("data", (chunk: Buffer) => {
+        received += chunk.byteLength;
+        if (received > MAX_BUFFERED_RESPONSE_BYTES) {
+          req.destroy(
+            new Error("Daemon HTTP response is too large to buffer; retry with stream enabled."),
+          );
+          return;
+        }
+        chunks.push(chunk);
+      });
+      res.on("end", () => {
+        const body = Buffer.concat(chunks);
+        const isText = TEXT_CONTENT_TYPE_PATTERN.test(headers["content-type"] ?? "");
+        resolve({
+          requestId,
+          status,
+          headers,
+          text: isText ? body.toString("utf8") : null,
+          bodyBase64: isText ? null : body.toString("base64"),
+          streaming: false,
+        });
+      });
+      res.on("error", reject);
+    });
+
+    req.setTimeout(DAEMON_HTTP_TIMEOUT_MS, () => {
+      req.destroy(new Error(`Daemon HTTP request timed out: ${request.method} ${request.path}`));
+    });
+    req.on("error", (error) => {
+      if (activeStreams.delete(requestId)) {
+        emitStreamEvent({ requestId, kind: "error", error: error.message });
+        return;
+      }
+      reject(new Error(`Daemon HTTP request failed: ${error.message}`));
+    });
+    if (request.body !== null) {
+      req.write(request.body);
+    }
+    req.end();
+  });
+}
+
+export function cancelDaemonHttpRequest(requestId: unknown): boolean {
+  if (typeof requestId !== "string") {
+    return false;
+  }
+  const req = activeStreams.get(requestId);
+  if (!req) {
+    return false;
+  }
+  activeStreams.delete(requestId);
+  req.destroy();
+  emitStreamEvent({ requestId, kind: "error", error: "Cancelled" });
+  return true;
+}
@@ -1,10 +1,13 @@
 import { type ChildProcess } from "node:child_process";
 import { readFileSync } from "node:fs";
+import { statfs } from "node:fs/promises";
+import os from "node:os";
 import path from "node:path";
-import { app, ipcMain, powerMonitor } from "electron";
+import { app, BrowserWindow, ipcMain, powerMonitor, session } from "electron";
 import log from "electron-log/main";
-import { resolvePaseoHome, spawnProcess } from "@getpaseo/server";
+import { resolvePaseoHome, spawnProcess, type HighRiskPermission } from "@getpaseo/server";
 import {
+  attachmentsDirPath,
   copyAttachmentFileToManagedStorage,
   deleteManagedAttachmentFile,
   garbageCollectManagedAttachmentFiles,
@@ -12,6 +15,41 @@ import {
   writeAttachmentBase64,
   writeAttachmentBytes,
 } from "../features/attachments.js";
+import { setAttachmentStorageLocation } from "../features/attachment-storage-electron.js";
+import { moveToTrash, trashPath } from "../features/trash.js";
+import {
+  DESKTOP_SETTINGS_CONFIG_TARGET,
+  listRecentOperations,
+  recordDesktopSettingsOverwrite,
+  removeAttachmentWithJournal,
+  removeGarbageAttachmentsWithJournal,
+  undoOperation,
+} from "../features/operation-journal-electron.js";
+import { takePendingPaseoBundles } from "../features/paseo-bundle-electron.js";
+import { takePendingQuickActions } from "../features/quick-actions-electron.js";
+import { captureWindowOfApp } from "../features/window-capture-electron.js";
+import { listOpenWindows } from "../system/window-list-electron.js";
+import { getUsageStats } from "../features/usage-stats-electron.js";
+import { generateDailyDigest, runScheduledDailyDigest } from "../features/daily-digest-electron.js";
+import { collectTaskArtifactsCommand } from "../features/task-artifacts-electron.js";
+import { getUnreadCounts } from "../features/unread-indicators.js";
+import { setAgentIdentities } from "../features/agent-icons.js";
+import { getCachedAgentTimeline, prefetchAgent } from "../features/agent-prefetch.js";
+import { applyPauseAllShortcut, pauseAllAgents } from "../features/pause-all-agents.js";
+import { reloadWindowPreservingState } from "../window/window-reload.js";
+import {
+  applyWindowLayout,
+  deleteWindowLayout,
+  listWindowLayouts,
+  saveWindowLayout,
+} from "../window/window-layout.js";
+import { getFeatureFlags, setFeatureFlag } from "../features/feature-flags-electron.js";
+import {
+  buildAboutInfo,
+  buildAboutPanelOptions,
+  resolveBuildCommit,
+  type AboutInfo,
+} from "../features/about-info.js";
 import {
   checkForAppUpdate,
   downloadAndInstallUpdate,
@@ -27,19 +65,180 @@ import {
   openLocalTransportSession,
   sendLocalTransportMessage,
   closeLocalTransportSession,
+  getLocalTransportBandwidthStats,
+  getLocalTransportCompressionStats,
+  getLocalTransportConnectionHealth,
+  getLocalTransportEventBatching,
+  getLocalTransportPolicy,
+  getLocalTransportStreamSubscriptions,
+  isLocalTransportLowBandwidthMode,
+  setLocalTransportEventBatching,
+  setLocalTransportLowBandwidthMode,
+  setLocalTransportPolicy,
+  setLocalTransportStreamSubscriptions,
 } from "./local-transport.js";
+import { getNativeApprovalKeyFilePath, requestNativeApproval } from "./approval-gate-electron.js";
+import { buildPerMessageDeflateOptions } from "./transport-compression.js";
+import { forwardPort, listPortForwards, stopPortForward } from "./port-forward-electron.js";
+import { coerceEventBatchingConfig } from "./event-batching.js";
+import {
+  createCommandLimiter,
+  DEFAULT_COMMAND_LIMITS,
+  limitCommandHandlers,
+} from "./command-limits.js";
+import {
+  cancelDaemonHttpRequest,
+  coerceDaemonHttpRequest,
+  performDaemonHttpRequest,
+  type DaemonHttpRequest,
+  type DaemonHttpResponse,
+} from "./daemon-http.js";
 import {
   createNodeEntrypointInvocation,
-  resolveDaemonRunnerEntrypoint,
   runCliJsonCommand,
   runCliTextCommand,
+  setCliEnvOverlay,
 } from "./runtime-paths.js";
 import {
   createDesktopSettingsCommandHandlers,
   type DesktopCommandHandler,
 } from "../settings/desktop-settings-commands.js";
+import {
+  omitMainProcessOnlySettings,
+  type DesktopSettings,
+} from "../settings/desktop-settings.js";
 import { getDesktopSettingsStore } from "../settings/desktop-settings-electron.js";
+import { getFileReadMaxBytes } from "../features/file-read-guard-electron.js";
+import { getDaemonAuthStore } from "../auth/daemon-auth-electron.js";
+import {
+  getWorkspaceTrustFilePath,
+  getWorkspaceTrustRegistry,
+} from "../features/workspace-trust-electron.js";
+import { setApprovalTouchIdRequired } from "../features/native-approval.js";
+import {
+  createScratchWorkspace,
+  deleteScratchWorkspace,
+  listScratchWorkspaces,
+} from "../features/scratch-workspaces-electron.js";
+import { getPermissionBroker } from "../features/permission-policy-electron.js";
+import {
+  configureCommitSigning,
+  getCommitSigningAvailability,
+  getCommitSigningEnv,
+} from "../features/commit-signing-electron.js";
+import {
+  applyPatch,
+  cancelCloneRepository,
+  cloneRepository,
+  createWorktree,
+  listWorktrees,
+  removeWorktree,
+} from "../features/git-electron.js";
+import {
+  listWatchFolders,
+  setWatchFolderEnabled,
+  unwatchAttachFolder,
+  watchAttachFolder,
+} from "../features/watch-folders-electron.js";
+import {
+  applyClipboardHistorySettings,
+  clearClipboardHistory,
+  getClipboardHistoryEntries,
+} from "../features/clipboard-history-electron.js";
+import {
+  applyAutomationSettings,
+  getAutomationEndpoint,
+} from "../features/automation-api-electron.js";
+import { copyToClipboard } from "../features/clipboard.js";
+import { setNotificationCoalesceWindow } from "../features/notifications.js";
+import { completeOnboardingStep, getOnboardingStore } from "../features/onboarding-electron.js";
+import {
+  assertPresentationCommandAllowed,
+  openPresentationWindow,
+} from "../features/presentation-window-electron.js";
+import { openPreviewWindow } from "../features/preview-window-electron.js";
+import {
+  applyStatusPopoverSettings,
+  setDockIconVisible,
+  toggleStatusPopover,
+} from "../features/status-popover.js";
+import {
+  setWindowBackground,
+  setWindowVisibleOnAllWorkspaces,
+  toggleWindowFullscreen,
+} from "../window/window-manager.js";
+import { resolveWindowByLabel } from "../window/window-labels.js";
+import { getWindowZoomState, setWindowZoomFactor } from "../window/window-zoom.js";
+import { getScrollProfile } from "../window/scroll-events.js";
 import { isRunningUnderARM64Translation } from "../system/arm64-translation.js";
+import { getSystemLocaleInfo } from "../system/system-locale.js";
+import { getTimeInfo } from "../system/time-info.js";
+import { getAccessibilityPreferences } from "../system/accessibility.js";
+import { getKeyboardLayout } from "../system/keyboard-layout.js";
+import { applyProxySettings, getProxyConfig } from "../system/proxy-config.js";
+import { pickColor } from "../system/color-picker.js";
+import { listSystemFonts } from "../system/system-fonts.js";
+import { getDisplayInfo } from "../system/display-info.js";
+import { getPowerStatus } from "../system/power-status.js";
+import { getPresentationMode, setPresentationMode } from "../system/presentation-mode.js";
+import { playFeedback } from "../system/feedback.js";
+import {
+  type DiskUsageLocations,
+  getDiskUsageReport,
+  registerLowDiskSpaceEvents,
+} from "../system/disk-usage.js";
+import {
+  cancelDirectorySizeEstimate,
+  runDirectorySizeEstimate,
+} from "../system/directory-size.js";
+import { shareItems } from "../features/share.js";
+import { previewFile } from "../features/file-preview.js";
+import { buildExportFilenameCommand } from "../features/export-naming.js";
+import { createReminder } from "../features/reminders.js";
+import { getWebAuthnAssertion } from "../features/webauthn.js";
+import { annotateImage } from "../features/image-annotation-electron.js";
+import {
+  applyRetention,
+  type RetentionReport,
+  type RetentionTarget,
+} from "../features/retention.js";
+import {
+  getLocalTaskScheduler,
+  startLocalTaskScheduler,
+} from "../features/local-tasks-electron.js";
+import type { LocalTaskAction } from "../features/local-tasks.js";
+import { emitDesktopEvent } from "../window/desktop-events.js";
+import { rotateLogFile } from "./log-rotation.js";
+import {
+  coerceTlsHostnames,
+  DAEMON_TLS_FILES,
+  type DaemonTlsMaterial,
+  generateDaemonTlsMaterial,
+  resolveDaemonTlsEnv,
+} from "./daemon-tls.js";
+import { restoreDaemonCertificateTrust, trustDaemonCertificate } from "./daemon-tls-trust.js";
+import { isListenApproved, willFirewallPromptForLanBind } from "./bind-policy.js";
+import {
+  runConnectionDiagnostics,
+  type ConnectionDiagnosticsReport,
+} from "./connection-diagnostics.js";
+import { startDaemonHealthNotifications } from "./daemon-health-notifications.js";
+import {
+  isDaemonVersionHeldBack,
+  resolveSupervisedDaemonRunner,
+  startSupervisedDaemon,
+} from "./daemon-rollback-electron.js";
+import type { DaemonHealthSample } from "./daemon-watchdog.js";
+import { confirmLanBind } from "./lan-bind-consent.js";
+import { skipQuitConfirmation } from "./quit-confirmation.js";
+import {
+  coercePort,
+  findAvailablePort,
+  formatTcpListenAddress,
+  isPortAvailable,
+  parseTcpListenAddress,
+} from "./port-selection.js";
+import { isRecord } from "../utils/is-record.js";
 
 const DAEMON_LOG_FILENAME = "daemon.log";
 const PID_POLL_INTERVAL_MS = 100;
@@ -48,6 +247,12 @@ const STARTUP_POLL_MAX_ATTEMPTS = 150;
 const STOP_TIMEOUT_MS = 15_000;
 const KILL_TIMEOUT_MS = 3_000;
 const DETACHED_STARTUP_GRACE_MS = 1200;
+const DAEMON_LOG_ROTATE_BYTES = 10 * 1024 * 1024;
+const DAEMON_LOG_ARCHIVES = 3;
+const MAINTENANCE_STARTUP_DELAY_MS = 5 * 60_000;
+const MAINTENANCE_INTERVAL_MS = 24 * 60 * 60_000;
+
+const commandLimiter = createCommandLimiter({ limits: DEFAULT_COMMAND_LIMITS });
 
 type DesktopDaemonState = "starting" | "running" | "stopped" | "errored";
 
@@ -60,6 +265,8 @@ export interface DesktopDaemonStatus {
   home: string;
   version: string | null;
   desktopManaged: boolean;
+  /** Whether the daemon serves HTTPS and WSS on its TCP listen address. */
+  tls: boolean;
   error: string | null;
 }
 
@@ -205,10 +412,6 @@ function toTrimmedString(value: unknown): string | null {
   return trimmed.length > 0 ? trimmed : null;
 }
 
-function isRecord(value: unknown): value is Record<string, unknown> {
-  return typeof value === "object" && value !== null && !Array.isArray(value);
-}
-
 function resolveDesktopAppVersion(): string {
   if (app.isPackaged) {
     return app.getVersion();
@@ -243,16 +446,23 @@ export async function resolveDesktopDaemonStatus(): Promise<DesktopDaemonStatus>
     >;
     const localDaemon = typeof payload.localDaemon === "string" ? payload.localDaemon : "stopped";
     const running = localDaemon === "running";
+    const listen = typeof payload.listen === "string" ? payload.listen : null;
+    const desktopManaged = payload.desktopManaged === true;
 
     return {
       serverId: typeof payload.serverId === "string" ? payload.serverId : "",
       status: running ? "running" : "stopped",
-      listen: typeof payload.listen === "string" ? payload.listen : null,
+      listen,
       hostname: running && typeof payload.hostname === "string" ? payload.hostname : null,
       pid: running && typeof payload.pid === "number" ? payload.pid : null,
       home,
       version: typeof payload.daemonVersion === "string" ? payload.daemonVersion : null,
-      desktopManaged: payload.desktopManaged === true,
+      desktopManaged,
+      // Only a daemon this app started was handed the material; others serve what they configure.
+      tls:
+        desktopManaged &&
+        parseTcpListenAddress(listen) !== null &&
+        (await resolveDaemonTlsEnv(daemonTlsDir())) !== null,
       error: null,
     };
   } catch (error) {
@@ -267,6 +477,7 @@ export async function resolveDesktopDaemonStatus(): Promise<DesktopDaemonStatus>
       home,
       version: null,
       desktopManaged: false,
+      tls: false,
       error: errorMessage,
     };
   }
@@ -278,11 +489,18 @@ function normalizeVersion(version: string | null): string | null {
   return trimmed.replace(/^v/i, "");
 }
 
-function shouldRestartForVersion(current: DesktopDaemonStatus): boolean {
+async function shouldRestartForVersion(current: DesktopDaemonStatus): Promise<boolean> {
   if (!current.desktopManaged) return false;
   const appVersion = normalizeVersion(resolveDesktopAppVersion());
   const daemonVersion = normalizeVersion(current.version);
-  return Boolean(appVersion && daemonVersion && appVersion !== daemonVersion);
+  if (!appVersion || !daemonVersion || appVersion === daemonVersion) return false;
+  // A daemon rolled back after crash-looping stays on its version until the app updates.
+  const heldBack = await isDaemonVersionHeldBack({
+    paseoHome: getPaseoHome(),
+    appVersion,
+    daemonVersion,
+  });
+  return !heldBack;
 }
 
 function buildStartupFailureError(
@@ -322,6 +540,15 @@ async function pollForRunningDaemon(): Promise<DesktopDaemonStatus> {
 }
 
 async function startDaemon(): Promise<DesktopDaemonStatus> {
+  return await startSupervisedDaemon({
+    paseoHome: getPaseoHome(),
+    appVersion: normalizeVersion(resolveDesktopAppVersion()) ?? "unknown",
+    launch: launchDaemon,
+    isRunning: async () => (await resolveDesktopDaemonStatus()).status === "running",
+  });
+}
+
+async function launchDaemon(): Promise<DesktopDaemonStatus> {
   const current = await resolveDesktopDaemonStatus();
   logDesktopDaemonLifecycle("initial status check before start", {
     status: current.status,
@@ -331,19 +558,33 @@ async function startDaemon(): Promise<DesktopDaemonStatus> {
     error: current.error,
     desktopManaged: current.desktopManaged,
   });
+  const { lanBindListen } = (await getDesktopSettingsStore().get()).daemon;
   if (current.status === "running") {
-    if (shouldRestartForVersion(current)) {
+    if (await shouldRestartForVersion(current)) {
       logDesktopDaemonLifecycle("daemon version mismatch, restarting", {
         appVersion: normalizeVersion(resolveDesktopAppVersion()),
         daemonVersion: normalizeVersion(current.version),
       });
       await stopDesktopDaemon();
+    } else if (current.desktopManaged && !isListenApproved(current.listen, lanBindListen)) {
+      logDesktopDaemonLifecycle("daemon is listening beyond loopback without consent, restarting", {
+        listen: current.listen,
+      });
+      await stopDesktopDaemon();
     } else {
       return current;
     }
   }
 
-  const daemonRunner = resolveDaemonRunnerEntrypoint();
+  const portOverride = await selectDaemonListenAddress(current.listen);
+  const listenOverride =
+    (await enforceLoopbackBind(portOverride ?? current.listen)) ?? portOverride;
+  const daemonRunner = await resolveSupervisedDaemonRunner({
+    paseoHome: getPaseoHome(),
+    appVersion: normalizeVersion(resolveDesktopAppVersion()) ?? "unknown",
+  });
+  const signingEnv = await getCommitSigningEnv();
+  const tlsEnv = await resolveDaemonTlsEnv(daemonTlsDir());
   const invocation = createNodeEntrypointInvocation({
     entrypoint: daemonRunner,
     argvMode: "node-script",
@@ -370,7 +611,14 @@ async function startDaemon(): Promise<DesktopDaemonStatus> {
     detached: true,
     envMode: "internal",
     env: invocation.env,
-    envOverlay: { PASEO_DESKTOP_MANAGED: "1" },
+    envOverlay: {
+      ...signingEnv,
+      ...tlsEnv,
+      PASEO_DESKTOP_MANAGED: "1",
+      PASEO_WORKSPACE_TRUST_FILE: getWorkspaceTrustFilePath(),
+      PASEO_NATIVE_APPROVAL_KEY_FILE: getNativeApprovalKeyFilePath(),
+      ...(listenOverride ? { PASEO_LISTEN: listenOverride } : {}),
+    },
     stdio: ["ignore", "pipe", "pipe"],
   });
 
@@ -433,7 +681,84 @@ async function startDaemon(): Promise<DesktopDaemonStatus> {
     throw buildStartupFailureError(result, stdout, stderr);
   }
 
-  return pollForRunningDaemon();
+  const status = await pollForRunningDaemon();
+  const approved = (await getDesktopSettingsStore().get()).daemon.lanBindListen;
+  if (!isListenApproved(status.listen, approved)) {
+    // The daemon's own config can still widen the bind; it does not get to stay up that way.
+    log.warn("[desktop daemon] daemon is listening beyond loopback without consent, stopping", {
+      listen: status.listen,
+    });
+    await stopDesktopDaemon();
+    throw new Error(
+      `Daemon bound ${status.listen} beyond loopback without consent and was stopped.`,
+    );
+  }
+  return status;
+}
+
+/**
+ * Moves the daemon to a nearby free port when the configured one is taken,
+ * remembering the choice so later launches keep the same address. The
+ * renderer re-saves its connection from the status `listen` after start.
+ * Returns the listen address to force, or `null` to use the configuration.
+ */
+async function selectDaemonListenAddress(configuredListen: string | null): Promise<string | null> {
+  const configured = parseTcpListenAddress(configuredListen);
+  if (!configured || process.env.PASEO_LISTEN) {
+    return null;
+  }
+  const settingsStore = getDesktopSettingsStore();
+  const remembered = (await settingsStore.get()).daemon.port;
+  const port =
+    (await findAvailablePort({
+      preferred: remembered ?? configured.port,
+      host: configured.host,
+    })) ??
+    (remembered !== null
+      ? await findAvailablePort({ preferred: configured.port, host: configured.host })
+      : null);
+  if (port === null) {
+    logDesktopDaemonLifecycle("no free port near the configured listen address", {
+      listen: configuredListen,
+    });
+    return null;
+  }
+
+  const nextRemembered = port === configured.port ? null : port;
+  if (nextRemembered !== remembered) {
+    await settingsStore.patch({ daemon: { port: nextRemembered } });
+    emitDesktopEvent("daemon-port-changed", { configuredPort: configured.port, port });
+  }
+  if (port === configured.port) {
+    return null;
+  }
+  logDesktopDaemonLifecycle("configured daemon port is in use, moving", {
+    configuredPort: configured.port,
+    port,
+  });
+  return formatTcpListenAddress({ host: configured.host, port });
+}
+
+/**
+ * Keeps the daemon on loopback unless the user has agreed to this LAN bind.
+ * Returns a loopback listen address to force, or `null` to leave it alone.
+ */
+async function enforceLoopbackBind(listen: string | null): Promise<string | null> {
+  const settingsStore = getDesktopSettingsStore();
+  const address = parseTcpListenAddress(listen);
+  if (!address || isListenApproved(listen, (await settingsStore.get()).daemon.lanBindListen)) {
+    return null;
+  }
+  const allowed = await confirmLanBind({
+    listen: listen ?? "",
+    firewallPrompts: await willFirewallPromptForLanBind(process.platform),
+  });
+  if (allowed) {
+    await settingsStore.patch({ daemon: { lanBindListen: formatTcpListenAddress(address) } });
+    return null;
+  }
+  logDesktopDaemonLifecycle("non-loopback bind declined, keeping the daemon local", { listen });
+  return formatTcpListenAddress({ host: "127.0.0.1", port: address.port });
 }
 
 export async function stopDesktopDaemon(): Promise<DesktopDaemonStatus> {
@@ -514,6 +839,263 @@ async function getLocalDaemonVersion(): Promise<{ version: string | null; error:
   };
 }
 
+let buildCommit: string | null | undefined;
+
+export async function getAboutInfo(): Promise<AboutInfo> {
+  buildCommit ??= resolveBuildCommit({ isPackaged: app.isPackaged, appPath: app.getAppPath() });
+  const [daemon, settings] = await Promise.all([
+    resolveDesktopDaemonStatus(),
+    getDesktopSettingsStore().get(),
+  ]);
+  const info = buildAboutInfo({
+    appName: app.getName(),
+    appVersion: resolveDesktopAppVersion(),
+    commit: buildCommit,
+    releaseChannel: settings.releaseChannel,
+    daemonVersion: daemon.version,
+    daemonStatus: daemon.status,
+    electronVersion: process.versions.electron,
+    webviewVersion: process.versions.chrome,
+    nodeVersion: process.versions.node,
+    os: {
+      platform: process.platform,
+      arch: os.arch(),
+      release: os.release(),
+      version: os.version(),
+    },
+  });
+  // Keep the native macOS panel in sync with whatever the renderer last showed.
+  app.setAboutPanelOptions(buildAboutPanelOptions(info));
+  return info;
+}
+
+async function ensureOnboardingDaemon(): Promise<string> {
+  const { daemon } = await getDesktopSettingsStore().get();
+  const status = daemon.manageBuiltInDaemon
+    ? await startDaemon()
+    : await resolveDesktopDaemonStatus();
+  if (status.status !== "running") {
+    throw new Error(status.error ?? "Local daemon is not running.");
+  }
+  return status.version ?? "running";
+}
+
+async function runLocalTaskAction(action: LocalTaskAction): Promise<unknown> {
+  switch (action) {
+    case "attachment_gc":
+      // Only the renderer knows which attachments are still referenced, so it runs the GC.
+      emitDesktopEvent("attachment-gc-requested", {});
+      return { requested: true };
+    case "diagnostics_rotation":
+      return {
+        rotated: await rotateLogFile({
+          filePath: logFilePath(),
+          maxBytes: DAEMON_LOG_ROTATE_BYTES,
+          keep: DAEMON_LOG_ARCHIVES,
+        }),
+      };
+    case "update_check":
+      return await checkForAppUpdate({
+        currentVersion: resolveDesktopAppVersion(),
+        releaseChannel: (await getDesktopSettingsStore().get()).releaseChannel,
+      });
+    case "daemon_health_probe":
+      return await resolveDesktopDaemonStatus();
+    case "daily_digest":
+      return await runScheduledDailyDigest();
+  }
+}
+
+export function startDesktopLocalTasks(): Promise<void> {
+  return startLocalTaskScheduler({ runAction: runLocalTaskAction });
+}
+
+async function sampleDaemonHealth(): Promise<DaemonHealthSample> {
+  const freeBytes = await statfs(getPaseoHome())
+    .then((stats) => stats.bavail * stats.bsize)
+    .catch(() => null);
+  const { daemon } = await getDesktopSettingsStore().get();
+  const status = await resolveDesktopDaemonStatus();
+  // Only the daemon this app supervises can crash on our watch.
+  const running =
+    daemon.manageBuiltInDaemon && status.desktopManaged && status.status === "running";
+  const address = parseTcpListenAddress(status.listen);
+  // If the port can be bound, nothing is listening on it any more.
+  const listening = running && address ? !(await isPortAvailable(address)) : null;
+  return { running, listening, freeBytes };
+}
+
+export function startDaemonHealthWatchdog(): void {
+  startDaemonHealthNotifications({ sample: sampleDaemonHealth, restart: restartDaemon });
+}
+
+function resolveDiskUsageLocations(): DiskUsageLocations {
+  const home = getPaseoHome();
+  const sessionData = app.getPath("sessionData");
+  return {
+    attachments: [attachmentsDirPath()],
+    logs: [app.getPath("logs"), logFilePath()],
+    caches: ["Cache", "Code Cache", "GPUCache"].map((name) => path.join(sessionData, name)),
+    // The daemon keeps agent records and their timelines here.
+    timeline: [path.join(home, "agents")],
+  };
+}
+
+function resolveRetentionTargets(): RetentionTarget[] {
+  const logsDir = app.getPath("logs");
+  return [
+    {
+      name: "daemon_logs",
+      dir: getPaseoHome(),
+      include: (name) => /^daemon\.log\.\d+$/.test(name),
+    },
+    {
+      name: "app_logs",
+      dir: logsDir,
+      include: (name) => name.endsWith(".log"),
+      keep: [log.transports.file.getFile().path],
+      recursive: true,
+    },
+    {
+      name: "crash_reports",
+      dir: app.getPath("crashDumps"),
+      include: (name) => name.endsWith(".dmp"),
+      recursive: true,
+    },
+  ];
+}
+
+/**
+ * Applies the retention settings to logs and crash reports, then trims the
+ * HTTP cache. Chromium owns its cache files while running, so the cache is
+ * cleared through the session once it outgrows the size limit.
+ */
+async function runMaintenance(): Promise<RetentionReport> {
+  const { retention } = await getDesktopSettingsStore().get();
+  const report = await applyRetention({ targets: resolveRetentionTargets(), policy: retention });
+  const cacheBytes = await session.defaultSession.getCacheSize();
+  if (cacheBytes > retention.maxSizeMb * 1024 * 1024) {
+    await session.defaultSession.clearCache();
+    report.deleted.push({
+      target: "http_cache",
+      path: path.join(app.getPath("sessionData"), "Cache"),
+      bytes: cacheBytes,
+      reason: "size",
+    });
+    report.freedBytes += cacheBytes;
+  }
+  log.info("[maintenance] retention applied", {
+    deleted: report.deleted.length,
+    freedBytes: report.freedBytes,
+    errors: report.errors.length,
+  });
+  return report;
+}
+
+export function startDesktopMaintenance(): void {
+  const run = () => {
+    void runMaintenance().catch((error) => {
+      log.warn("[maintenance] retention run failed", error);
+    });
+  };
+  setTimeout(run, MAINTENANCE_STARTUP_DELAY_MS).unref();
+  setInterval(run, MAINTENANCE_INTERVAL_MS).unref();
+}
+
+export function registerDesktopDiskSpaceEvents(): void {
+  registerLowDiskSpaceEvents({ getLocations: resolveDiskUsageLocations });
+}
+
+function daemonTlsDir(): string {
+  return path.join(getPaseoHome(), "tls");
+}
+
+async function requestLocalDaemonHttp(request: DaemonHttpRequest): Promise<DaemonHttpResponse> {
+  const { listen, serverId, tls } = await resolveDesktopDaemonStatus();
+  const authToken =
+    request.authToken ?? (serverId ? await getDaemonAuthStore().getToken(serverId) : null);
+  const ca = tls ? readFileSync(path.join(daemonTlsDir(), DAEMON_TLS_FILES.caCert), "utf8") : null;
+  return await performDaemonHttpRequest({ listen, request: { ...request, authToken }, ca });
+}
+
+/** A connection's token, only for a URL the token was handed over for. */
+async function getDaemonAuthTokenForEndpoint(
+  args: Record<string, unknown> | undefined,
+): Promise<string | null> {
+  const serverId = typeof args?.serverId === "string" ? args.serverId : "";
+  const url = typeof args?.url === "string" ? args.url : "";
+  const store = getDaemonAuthStore();
+  if (!serverId || !(await store.getEndpoints(serverId)).includes(url)) {
+    return null;
+  }
+  return await store.getToken(serverId);
+}
+
+/**
+ * With a `serverId` the stored token is only sent to the endpoint it was
+ * handed over for, and `url` is ignored, so a renderer cannot point the token
+ * at another host. Without one, only a token passed in is used.
+ */
+async function runDaemonConnectionDiagnostics(
+  args: Record<string, unknown> | undefined,
+): Promise<ConnectionDiagnosticsReport> {
+  if (typeof args?.serverId !== "string") {
+    return await runConnectionDiagnostics({
+      url: args?.url,
+      authToken: typeof args?.authToken === "string" ? args.authToken : null,
+    });
+  }
+  const store = getDaemonAuthStore();
+  const [url] = await store.getEndpoints(args.serverId);
+  if (!url) {
+    throw new Error(`No known endpoint for server ${args.serverId}.`);
+  }
+  return await runConnectionDiagnostics({ url, authToken: await store.getToken(args.serverId) });
+}
+
+/** Asks the local daemon itself what a pending permission would run. */
+async function fetchHighRiskPermission(key: {
+  agentId: string;
+  requestId: string;
+}): Promise<HighRiskPermission | null> {
+  const query = new URLSearchParams(key).toString();
+  const response = await requestLocalDaemonHttp({
+    method: "GET",
+    path: `/api/permissions/high-risk?${query}`,
+    body: null,
+    headers: {},
+    stream: false,
+    authToken: null,
+  });
+  return response.status === 200 && response.text
+    ? (JSON.parse(response.text) as HighRiskPermission)
+    : null;
+}
+
+async function generateDaemonTls(
+  args: Record<string, unknown> | undefined,
+): Promise<DaemonTlsMaterial> {
+  const material = await generateDaemonTlsMaterial({
+    dir: daemonTlsDir(),
+    hostnames: coerceTlsHostnames(args?.hostnames),
+  });
+  trustDaemonCertificate(material);
+  setCliEnvOverlay((await resolveDaemonTlsEnv(daemonTlsDir())) ?? {});
+  log.info("[daemon-tls] generated certificate", material.fingerprint256);
+  // A running daemon keeps the certificate it loaded at start; restart it onto the new one.
+  const status = await resolveDesktopDaemonStatus();
+  if (status.status === "running" && status.desktopManaged) {
+    await restartDaemon();
+  }
+  return material;
+}
+
+/** Re-applies earlier material: trust for the app's connections and TLS for CLI runs. */
+export async function restoreDaemonTlsTrust(): Promise<void> {
+  setCliEnvOverlay((await resolveDaemonTlsEnv(daemonTlsDir())) ?? {});
+  await restoreDaemonCertificateTrust(daemonTlsDir());
+}
+
 async function resolveRequestedReleaseChannel(
   args: Record<string, unknown> | undefined,
 ): Promise<AppReleaseChannel> {
@@ -524,13 +1106,47 @@ async function resolveRequestedReleaseChannel(
 // IPC registration
 // ---------------------------------------------------------------------------
 
+function applyDesktopSettings(settings: DesktopSettings): void {
+  void applyProxySettings(settings.proxy).catch((error) => {
+    log.warn("[proxy] failed to apply proxy settings", error);
+  });
+  void applyClipboardHistorySettings(settings.clipboardHistory).catch((error) => {
+    log.warn("[clipboard-history] failed to apply settings", error);
+  });
+  applyStatusPopoverSettings(settings.statusPopover);
+  setNotificationCoalesceWindow(settings.notifications.coalesceWindowSeconds);
+  applyPauseAllShortcut(settings.shortcuts.pauseAllAgents);
+  void applyAutomationSettings(settings.automation).catch((error) => {
+    log.warn("[automation] failed to apply settings", error);
+  });
+}
+
+/** `undo_operation` restorer for journaled config overwrites. */
+async function restoreJournaledConfig(target: string, previous: unknown): Promise<void> {
+  if (target !== DESKTOP_SETTINGS_CONFIG_TARGET) {
+    throw new Error(`Unknown config: ${target}`);
+  }
+  // Undo goes through the same gate as renderer patches: it cannot flip a main-only safeguard.
+  const patch = omitMainProcessOnlySettings(previous);
+  applyDesktopSettings(await getDesktopSettingsStore().patch(patch));
+}
+
 export function createDaemonCommandHandlers(): Record<string, DesktopCommandHandler> {
   return {
-    ...createDesktopSettingsCommandHandlers({ settingsStore: getDesktopSettingsStore() }),
+    ...createDesktopSettingsCommandHandlers({
+      settingsStore: getDesktopSettingsStore(),
+      onSettingsChanged: applyDesktopSettings,
+      onSettingsOverwritten: (previous) =>
+        recordDesktopSettingsOverwrite({ previous, summary: "Changed desktop settings" }),
+    }),
     desktop_get_runtime_info: () => ({
       appVersion: resolveDesktopAppVersion(),
       runningUnderARM64Translation: isRunningUnderARM64Translation(),
     }),
+    get_about_info: () => getAboutInfo(),
+    get_onboarding_state: () => getOnboardingStore().getState(),
+    complete_onboarding_step: (args) =>
+      completeOnboardingStep({ args, ensureDaemon: ensureOnboardingDaemon }),
     desktop_daemon_status: () => resolveDesktopDaemonStatus(),
     start_desktop_daemon: () => startDaemon(),
     stop_desktop_daemon: () => stopDesktopDaemon(),
@@ -538,16 +1154,46 @@ export function createDaemonCommandHandlers(): Record<string, DesktopCommandHand
     desktop_daemon_logs: () => getDaemonLogs(),
     desktop_daemon_pairing: () => getDaemonPairing(),
     desktop_get_system_idle_time: () => powerMonitor.getSystemIdleTime() * 1000,
+    get_system_locale_info: () => getSystemLocaleInfo(),
+    get_time_info: () => getTimeInfo(),
+    get_accessibility_preferences: () => getAccessibilityPreferences(),
+    get_keyboard_layout: () => getKeyboardLayout(),
+    get_proxy_config: async (args) =>
+      getProxyConfig({
+        proxy: (await getDesktopSettingsStore().get()).proxy,
+        url: args?.url,
+      }),
     cli_daemon_status: () => getCliDaemonStatus(),
     write_attachment_base64: (args) => writeAttachmentBase64(args ?? {}),
     write_attachment_bytes: (args) => writeAttachmentBytes(args ?? {}),
     copy_attachment_file: (args) => copyAttachmentFileToManagedStorage(args ?? {}),
-    read_file_base64: (args) => readManagedFileBase64(args ?? {}),
-    delete_attachment_file: (args) => deleteManagedAttachmentFile(args ?? {}),
-    garbage_collect_attachment_files: (args) => garbageCollectManagedAttachmentFiles(args ?? {}),
+    annotate_image: (args) =>
+      annotateImage({ args, parent: resolveWindowByLabel(args?.windowLabel) }),
+    read_file_base64: async (args) =>
+      readManagedFileBase64(args ?? {}, { maxBytes: await getFileReadMaxBytes() }),
+    delete_attachment_file: (args) =>
+      deleteManagedAttachmentFile(args ?? {}, trashPath, removeAttachmentWithJournal),
+    move_to_trash: (args) => moveToTrash(args ?? {}),
+    garbage_collect_attachment_files: (args) =>
+      garbageCollectManagedAttachmentFiles(args ?? {}, removeGarbageAttachmentsWithJournal),
+    list_recent_operations: (args) => listRecentOperations(args),
+    undo_operation: (args) => undoOperation({ args, restoreConfig: restoreJournaledConfig }),
+    set_attachment_storage_location: (args) => setAttachmentStorageLocation(args?.path),
     open_local_daemon_transport: async (args) => {
-      const target = args as { transportType: "socket" | "pipe"; transportPath: string };
-      return await openLocalTransportSession(target);
+      const { transportType, transportPath, serverId } = args as {
+        transportType: "socket" | "pipe";
+        transportPath: string;
+        serverId?: string;
+      };
+      const { transport } = await getDesktopSettingsStore().get();
+      return await openLocalTransportSession(
+        { transportType, transportPath },
+        {
+          perMessageDeflate: buildPerMessageDeflateOptions(transport),
+          serverId: serverId ?? null,
+          authToken: serverId ? await getDaemonAuthStore().getToken(serverId) : null,
+        },
+      );
     },
     send_local_daemon_transport_message: async (args) => {
       await sendLocalTransportMessage(
@@ -561,6 +1207,147 @@ export function createDaemonCommandHandlers(): Record<string, DesktopCommandHand
           : "";
       if (sessionId) closeLocalTransportSession(sessionId);
     },
+    daemon_http_request: (args) => requestLocalDaemonHttp(coerceDaemonHttpRequest(args)),
+    cancel_daemon_http_request: (args) => cancelDaemonHttpRequest(args?.requestId),
+    forward_port: (args) => forwardPort(args),
+    list_port_forwards: () => listPortForwards(),
+    stop_port_forward: (args) => stopPortForward(args),
+    ensure_workspace_trusted: (args) =>
+      getWorkspaceTrustRegistry().ensureTrusted(typeof args?.path === "string" ? args.path : ""),
+    list_trusted_workspaces: () => getWorkspaceTrustRegistry().list(),
+    revoke_workspace_trust: (args) =>
+      getWorkspaceTrustRegistry().revoke(typeof args?.path === "string" ? args.path : ""),
+    create_scratch_workspace: (args) => createScratchWorkspace(args),
+    list_scratch_workspaces: () => listScratchWorkspaces(),
+    delete_scratch_workspace: (args) => deleteScratchWorkspace(args),
+    get_permission_policy: () => getPermissionBroker().load(),
+    set_permission_policy: (args) => getPermissionBroker().setPolicy(args),
+    sign_commit_available: () => getCommitSigningAvailability(),
+    configure_signing: (args) => configureCommitSigning(args),
+    create_worktree: (args) => createWorktree(args),
+    list_worktrees: (args) => listWorktrees(args),
+    remove_worktree: (args) => removeWorktree(args),
+    clone_repository: (args) => cloneRepository(args),
+    cancel_clone_repository: (args) => cancelCloneRepository(args?.requestId),
+    apply_patch: (args) => applyPatch(args),
+    watch_attach_folder: (args) => watchAttachFolder(args),
+    set_watch_folder_enabled: (args) => setWatchFolderEnabled(args),
+    unwatch_attach_folder: (args) => unwatchAttachFolder(args),
+    list_watch_folders: () => listWatchFolders(),
+    get_auth_state: (args) =>
+      typeof args?.serverId === "string"
+        ? getDaemonAuthStore().getState(args.serverId)
+        : getDaemonAuthStore().listStates(),
+    set_daemon_auth_token: (args) =>
+      getDaemonAuthStore().setToken({
+        serverId: typeof args?.serverId === "string" ? args.serverId : "",
+        token: typeof args?.token === "string" ? args.token : "",
+        expiresAt: typeof args?.expiresAt === "string" ? args.expiresAt : null,
+        ...(Array.isArray(args?.endpoints)
+          ? { endpoints: args.endpoints.filter((entry) => typeof entry === "string") }
+          : {}),
+      }),
+    get_daemon_auth_token: (args) => getDaemonAuthTokenForEndpoint(args),
+    clear_daemon_auth_token: (args) =>
+      getDaemonAuthStore().clearToken(typeof args?.serverId === "string" ? args.serverId : ""),
+    generate_daemon_tls_material: (args) => generateDaemonTls(args),
+    run_maintenance_now: () => runMaintenance(),
+    get_disk_usage_report: (args) =>
+      getDiskUsageReport({
+        locations: resolveDiskUsageLocations(),
+        lowSpaceBytes:
+          typeof args?.lowSpaceBytes === "number" && args.lowSpaceBytes >= 0
+            ? args.lowSpaceBytes
+            : undefined,
+      }),
+    estimate_directory_size: (args) => runDirectorySizeEstimate(args),
+    cancel_directory_size_estimate: (args) => cancelDirectorySizeEstimate(args?.requestId),
+    run_connection_diagnostics: (args) => runDaemonConnectionDiagnostics(args),
+    find_available_port: (args) =>
+      findAvailablePort({
+        preferred: coercePort(args?.preferred),
+        range: typeof args?.range === "number" ? args.range : undefined,
+      }),
+    webauthn_get_assertion: (args) =>
+      getWebAuthnAssertion({ args, parent: BrowserWindow.getFocusedWindow() }),
+    set_visible_on_all_workspaces: (args) =>
+      setWindowVisibleOnAllWorkspaces({
+        win: resolveWindowByLabel(args?.windowLabel),
+        enabled: args?.enabled === true,
+      }),
+    set_window_background: (args) =>
+      setWindowBackground({ win: resolveWindowByLabel(args?.windowLabel), color: args?.color }),
+    set_zoom_factor: (args) =>
+      setWindowZoomFactor({ win: resolveWindowByLabel(args?.windowLabel), factor: args?.factor }),
+    get_scroll_profile: (args) => getScrollProfile(resolveWindowByLabel(args?.windowLabel)),
+    get_zoom_factor: (args) => getWindowZoomState(resolveWindowByLabel(args?.windowLabel)),
+    toggle_fullscreen: (args) => toggleWindowFullscreen(resolveWindowByLabel(args?.windowLabel)),
+    toggle_status_popover: () => toggleStatusPopover(),
+    set_dock_icon_visible: (args) => setDockIconVisible(args),
+    open_presentation_window: (args) => openPresentationWindow(args),
+    open_preview_window: (args) => openPreviewWindow(args),
+    get_automation_endpoint: () => getAutomationEndpoint(),
+    pick_color: (args) => pickColor({ initial: args?.initial }),
+    play_feedback: (args) => playFeedback(args),
+    get_display_info: () => getDisplayInfo(),
+    get_power_status: () => getPowerStatus(),
+    set_presentation_mode: (args) => setPresentationMode(args),
+    get_presentation_mode: () => getPresentationMode(),
+    create_reminder: (args) => createReminder(args),
+    schedule_local_task: (args) =>
+      getLocalTaskScheduler().schedule({ cron: args?.cron, action: args?.action }),
+    unschedule_local_task: (args) =>
+      getLocalTaskScheduler().unschedule(typeof args?.taskId === "string" ? args.taskId : ""),
+    list_local_tasks: () => getLocalTaskScheduler().list(),
+    take_pending_paseo_bundles: () => takePendingPaseoBundles(),
+    take_pending_quick_actions: () => takePendingQuickActions(),
+    capture_window_of_app: (args) => captureWindowOfApp(args),
+    list_open_windows: () => listOpenWindows(),
+    get_usage_stats: (args) => getUsageStats(args),
+    get_unread_counts: () => getUnreadCounts(),
+    set_agent_identities: (args) => setAgentIdentities(args),
+    prefetch_agent: (args) => prefetchAgent(args),
+    get_cached_agent_timeline: (args) => getCachedAgentTimeline(args),
+    pause_all_agents: () => pauseAllAgents(),
+    get_feature_flags: () => getFeatureFlags(),
+    set_feature_flag: (args) => setFeatureFlag(args),
+    generate_daily_digest: (args) =>
+      generateDailyDigest({ args, win: resolveWindowByLabel(args?.windowLabel) }),
+    collect_task_artifacts: (args) =>
+      collectTaskArtifactsCommand({ args, win: resolveWindowByLabel(args?.windowLabel) }),
+    apply_window_layout: (args) =>
+      applyWindowLayout({ win: resolveWindowByLabel(args?.windowLabel), args }),
+    save_window_layout: (args) => saveWindowLayout(args),
+    list_window_layouts: () => listWindowLayouts(),
+    delete_window_layout: (args) => deleteWindowLayout(args),
+    reload_window: (args) =>
+      reloadWindowPreservingState({
+        win: resolveWindowByLabel(args?.windowLabel),
+        ignoreCache: args?.ignoreCache === true,
+      }),
+    build_export_filename: (args) => buildExportFilenameCommand(args),
+    preview_file: (args) =>
+      previewFile({ path: args?.path, win: resolveWindowByLabel(args?.windowLabel) }),
+    share_items: (args) => shareItems({ args, win: resolveWindowByLabel(args?.windowLabel) }),
+    list_system_fonts: (args) => listSystemFonts({ monospaceOnly: args?.monospaceOnly === true }),
+    copy_to_clipboard: (args) => copyToClipboard(args),
+    get_clipboard_history: () => getClipboardHistoryEntries(),
+    clear_clipboard_history: () => clearClipboardHistory(),
+    request_native_approval: (args) =>
+      requestNativeApproval(args, { fetchPermission: fetchHighRiskPermission }),
+    set_approval_touch_id: (args) => setApprovalTouchIdRequired(args),
+    get_bandwidth_stats: (args) => getLocalTransportBandwidthStats(args),
+    get_compression_stats: () => getLocalTransportCompressionStats(),
+    get_connection_health: (args) => getLocalTransportConnectionHealth(args),
+    get_low_bandwidth_mode: () => isLocalTransportLowBandwidthMode(),
+    set_low_bandwidth_mode: (args) => setLocalTransportLowBandwidthMode(args?.enabled === true),
+    get_command_queue_metrics: () => commandLimiter.metrics(),
+    get_transport_policy: (args) => getLocalTransportPolicy(args),
+    set_transport_policy: (args) => setLocalTransportPolicy(args),
+    get_stream_subscriptions: (args) => getLocalTransportStreamSubscriptions(args),
+    set_stream_subscriptions: (args) => setLocalTransportStreamSubscriptions(args),
+    get_event_batching: () => getLocalTransportEventBatching(),
+    set_event_batching: (args) => setLocalTransportEventBatching(coerceEventBatchingConfig(args)),
     check_app_update: async (args) => {
       const currentVersion = resolveDesktopAppVersion();
       return checkForAppUpdate({
@@ -573,6 +1360,7 @@ export function createDaemonCommandHandlers(): Record<string, DesktopCommandHand
       return downloadAndInstallUpdate(
         { currentVersion, releaseChannel: await resolveRequestedReleaseChannel(args) },
         async () => {
+          skipQuitConfirmation();
           await stopDesktopDaemon();
         },
       );
@@ -586,11 +1374,12 @@ export function createDaemonCommandHandlers(): Record<string, DesktopCommandHand
 }
 
 export function registerDaemonManager(): void {
-  const handlers = createDaemonCommandHandlers();
+  const handlers = limitCommandHandlers(createDaemonCommandHandlers(), commandLimiter);
 
   ipcMain.handle(
     "paseo:invoke",
-    async (_event, command: string, args?: Record<string, unknown>) => {
+    async (event, command: string, args?: Record<string, unknown>) => {
+      assertPresentationCommandAllowed(event.sender, command);
       const handler = handlers[command];
       if (!handler) {
         throw new Error(`Unknown desktop command: ${command}`);
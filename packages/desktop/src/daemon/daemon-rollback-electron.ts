@@ -0,0 +1,220 @@
+import { cp, readFile, readdir, rename, rm } from "node:fs/promises";
+import path from "node:path";
+import { app } from "electron";
+import log from "electron-log/main";
+import { updateJsonConfigFile } from "../settings/config-file.js";
+import { notifyDaemonRolledBack } from "./daemon-health-notifications.js";
+import {
+  coerceDaemonRollbackState,
+  createDaemonHealthCheck,
+  EMPTY_DAEMON_ROLLBACK_STATE,
+  needsDaemonHealthCheck,
+  resolveRollbackRuntime,
+  type DaemonHealthCheck,
+  type DaemonRollbackState,
+} from "./daemon-rollback.js";
+import type { NodeEntrypointSpec } from "./node-entrypoint-launcher.js";
+import {
+  resolveDaemonRunnerEntrypoint,
+  resolvePackagedDaemonRuntimeFiles,
+} from "./runtime-paths.js";
+
+const RUNTIMES_DIRNAME = "daemon-runtimes";
+const STATE_FILENAME = "state.json";
+const SAMPLE_INTERVAL_MS = 2_000;
+
+let supervising = false;
+
+function runtimesDir(paseoHome: string): string {
+  return path.join(paseoHome, RUNTIMES_DIRNAME);
+}
+
+function statePath(paseoHome: string): string {
+  return path.join(runtimesDir(paseoHome), STATE_FILENAME);
+}
+
+function delay(ms: number): Promise<void> {
+  return new Promise((resolve) => setTimeout(resolve, ms));
+}
+
+export async function readDaemonRollbackState(paseoHome: string): Promise<DaemonRollbackState> {
+  return await readFile(statePath(paseoHome), "utf8")
+    .then((raw) => coerceDaemonRollbackState(JSON.parse(raw)))
+    .catch(() => EMPTY_DAEMON_ROLLBACK_STATE);
+}
+
+async function updateDaemonRollbackState(
+  paseoHome: string,
+  mutate: (current: DaemonRollbackState) => DaemonRollbackState,
+): Promise<DaemonRollbackState> {
+  return await updateJsonConfigFile(statePath(paseoHome), (raw) =>
+    mutate(coerceDaemonRollbackState(raw)),
+  );
+}
+
+/** The runner to spawn: the kept previous version while this one is held back. */
+export async function resolveSupervisedDaemonRunner(input: {
+  paseoHome: string;
+  appVersion: string;
+}): Promise<NodeEntrypointSpec> {
+  const state = await readDaemonRollbackState(input.paseoHome);
+  const runtime = resolveRollbackRuntime(state, input.appVersion);
+  if (runtime) {
+    try {
+      return resolveDaemonRunnerEntrypoint(runtime.dir);
+    } catch (error) {
+      log.warn("[daemon-rollback] kept runtime is unusable, running the bundled one", error);
+    }
+  }
+  return resolveDaemonRunnerEntrypoint();
+}
+
+/** Whether a daemon reporting `daemonVersion` is the one deliberately rolled back to. */
+export async function isDaemonVersionHeldBack(input: {
+  paseoHome: string;
+  appVersion: string;
+  daemonVersion: string;
+}): Promise<boolean> {
+  const state = await readDaemonRollbackState(input.paseoHome);
+  return resolveRollbackRuntime(state, input.appVersion)?.version === input.daemonVersion;
+}
+
+/**
+ * Copies the packaged runtime of a version that passed its health check, then
+ * drops the copy it replaces. Only one version is kept: after an update it is
+ * the previous one, which is all a rollback needs.
+ */
+async function keepDaemonRuntime(paseoHome: string, version: string): Promise<void> {
+  const root = runtimesDir(paseoHome);
+  const dir = path.join(root, version);
+  const staging = `${dir}.partial`;
+  await rm(staging, { recursive: true, force: true });
+  // Copy app.asar as a file rather than letting Electron's fs read into it.
+  const noAsar = process.noAsar;
+  process.noAsar = true;
+  try {
+    for (const source of resolvePackagedDaemonRuntimeFiles()) {
+      await cp(source, path.join(staging, path.basename(source)), { recursive: true });
+    }
+  } finally {
+    process.noAsar = noAsar;
+  }
+  await rm(dir, { recursive: true, force: true });
+  await rename(staging, dir);
+  await updateDaemonRollbackState(paseoHome, () => ({
+    knownGood: { version, dir },
+    rolledBack: null,
+  }));
+  for (const entry of await readdir(root)) {
+    const isState = entry === STATE_FILENAME || entry.startsWith(`${STATE_FILENAME}.`);
+    if (entry !== version && !isState) {
+      await rm(path.join(root, entry), { recursive: true, force: true });
+    }
+  }
+  log.info("[daemon-rollback] kept daemon runtime", { version, dir });
+}
+
+async function rollBack<T>(input: {
+  paseoHome: string;
+  appVersion: string;
+  launch: () => Promise<T>;
+}): Promise<T | null> {
+  const { knownGood } = await readDaemonRollbackState(input.paseoHome);
+  if (!knownGood) {
+    log.error("[daemon-rollback] daemon is crash-looping with no version to roll back to", {
+      version: input.appVersion,
+    });
+    notifyDaemonRolledBack({ version: input.appVersion, to: null });
+    return null;
+  }
+  const at = new Date().toISOString();
+  await updateDaemonRollbackState(input.paseoHome, (current) => ({
+    ...current,
+    rolledBack: { version: input.appVersion, to: knownGood.version, at },
+  }));
+  log.warn("[daemon-rollback] daemon is crash-looping, rolling back", {
+    version: input.appVersion,
+    to: knownGood.version,
+  });
+  const status = await input.launch();
+  notifyDaemonRolledBack({ version: input.appVersion, to: knownGood.version });
+  return status;
+}
+
+async function watchStartedDaemon<T>(input: {
+  paseoHome: string;
+  appVersion: string;
+  check: DaemonHealthCheck;
+  launch: () => Promise<T>;
+  isRunning: () => Promise<boolean>;
+}): Promise<void> {
+  for (;;) {
+    await delay(SAMPLE_INTERVAL_MS);
+    if (await input.isRunning()) {
+      if (input.check.running(Date.now()) === "healthy") {
+        await keepDaemonRuntime(input.paseoHome, input.appVersion);
+        return;
+      }
+      continue;
+    }
+    if (input.check.crashed() === "crash_loop") {
+      await rollBack(input);
+      return;
+    }
+    log.warn("[daemon-rollback] daemon exited during its health check, restarting", {
+      version: input.appVersion,
+    });
+    await input.launch().catch((error: unknown) => {
+      log.warn("[daemon-rollback] restart during health check failed", error);
+    });
+  }
+}
+
+/**
+ * Starts the daemon through `launch`. A version that has not yet survived a
+ * minute of supervised runtime is watched: it is restarted when it exits, kept
+ * once healthy, and replaced by the previous version (with a notification)
+ * if it crash-loops. Failed starts count as crashes.
+ */
+export async function startSupervisedDaemon<T>(input: {
+  paseoHome: string;
+  appVersion: string;
+  launch: () => Promise<T>;
+  isRunning: () => Promise<boolean>;
+}): Promise<T> {
+  const state = await readDaemonRollbackState(input.paseoHome);
+  if (!app.isPackaged || supervising || !needsDaemonHealthCheck(state, input.appVersion)) {
+    return await input.launch();
+  }
+  supervising = true;
+  let watching = false;
+  try {
+    const check = createDaemonHealthCheck();
+    for (;;) {
+      let status: T;
+      try {
+        status = await input.launch();
+      } catch (error) {
+        if (check.crashed() !== "crash_loop") {
+          continue;
+        }
+        const rolledBack = await rollBack(input);
+        if (rolledBack === null) {
+          throw error;
+        }
+        return rolledBack;
+      }
+      watching = true;
+      void watchStartedDaemon({ ...input, check })
+        .catch((error: unknown) => log.error("[daemon-rollback] health check failed", error))
+        .finally(() => {
+          supervising = false;
+        });
+      return status;
+    }
+  } finally {
+    if (!watching) {
+      supervising = false;
+    }
+  }
+}
@@ -0,0 +1,62 @@
+import { describe, expect, it } from "vitest";
+import {
+  coerceDaemonRollbackState,
+  createDaemonHealthCheck,
+  needsDaemonHealthCheck,
+  resolveRollbackRuntime,
+} from "./daemon-rollback";
+
+describe("createDaemonHealthCheck", () => {
+  it("passes after a minute of supervised runtime", () => {
+    const check = createDaemonHealthCheck();
+    expect(check.running(0)).toBe("pending");
+    expect(check.running(30_000)).toBe("pending");
+    expect(check.running(60_000)).toBe("healthy");
+    expect(check.crashed()).toBe("healthy");
+  });
+
+  it("does not count time the daemon spent down", () => {
+    const check = createDaemonHealthCheck();
+    check.running(0);
+    check.running(50_000);
+    check.crashed();
+    expect(check.running(200_000)).toBe("pending");
+    expect(check.running(209_000)).toBe("pending");
+    expect(check.running(210_000)).toBe("healthy");
+  });
+
+  it("calls three crashes inside the window a crash loop", () => {
+    const check = createDaemonHealthCheck();
+    check.running(0);
+    expect(check.crashed()).toBe("pending");
+    expect(check.crashed()).toBe("pending");
+    expect(check.crashed()).toBe("crash_loop");
+    expect(check.running(120_000)).toBe("crash_loop");
+  });
+});
+
+describe("daemon rollback state", () => {
+  const knownGood = { version: "1.4.0", dir: "/home/.paseo/daemon-runtimes/1.4.0" };
+
+  it("runs the kept runtime only while the app version is held back", () => {
+    const rolledBack = { version: "1.5.0", to: "1.4.0", at: "2026-10-16T10:00:00.000Z" };
+    expect(resolveRollbackRuntime({ knownGood, rolledBack }, "1.5.0")).toEqual(knownGood);
+    expect(resolveRollbackRuntime({ knownGood, rolledBack }, "1.5.1")).toBeNull();
+    expect(resolveRollbackRuntime({ knownGood, rolledBack: null }, "1.5.0")).toBeNull();
+  });
+
+  it("health-checks a version until it is kept or held back", () => {
+    expect(needsDaemonHealthCheck({ knownGood, rolledBack: null }, "1.5.0")).toBe(true);
+    expect(needsDaemonHealthCheck({ knownGood, rolledBack: null }, "1.4.0")).toBe(false);
+    const rolledBack = { version: "1.5.0", to: "1.4.0", at: "2026-10-16T10:00:00.000Z" };
+    expect(needsDaemonHealthCheck({ knownGood, rolledBack }, "1.5.0")).toBe(false);
+  });
+
+  it("drops malformed entries", () => {
+    expect(coerceDaemonRollbackState({ knownGood: { version: 1 }, rolledBack: "x" })).toEqual({
+      knownGood: null,
+      rolledBack: null,
+    });
+    expect(coerceDaemonRollbackState(null)).toEqual({ knownGood: null, rolledBack: null });
+  });
+});
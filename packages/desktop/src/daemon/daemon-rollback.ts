@@ -0,0 +1,122 @@
+import { isRecord } from "../utils/is-record.js";
+
+/** Supervised runtime a new daemon version must survive before it replaces the previous one. */
+export const DAEMON_HEALTH_WINDOW_MS = 60_000;
+/** Crashes inside the health window that count as a crash loop. */
+export const DAEMON_CRASH_LIMIT = 3;
+
+export interface DaemonRuntimeRecord {
+  version: string;
+  /** Directory holding the kept copy of that version's daemon runtime. */
+  dir: string;
+}
+
+export interface DaemonRollbackState {
+  /** Last version that passed its health check; the one a crash loop falls back to. */
+  knownGood: DaemonRuntimeRecord | null;
+  /** Set while `version` is held back after crash-looping; the next app version clears it. */
+  rolledBack: { version: string; to: string; at: string } | null;
+}
+
+export const EMPTY_DAEMON_ROLLBACK_STATE: DaemonRollbackState = {
+  knownGood: null,
+  rolledBack: null,
+};
+
+function coerceRuntimeRecord(value: unknown): DaemonRuntimeRecord | null {
+  if (!isRecord(value) || typeof value.version !== "string" || typeof value.dir !== "string") {
+    return null;
+  }
+  return { version: value.version, dir: value.dir };
+}
+
+function coerceRolledBack(value: unknown): DaemonRollbackState["rolledBack"] {
+  if (
+    !isRecord(value) ||
+    typeof value.version !== "string" ||
+    typeof value.to !== "string" ||
+    typeof value.at !== "string"
+  ) {
+    return null;
+  }
+  return { version: value.version, to: value.to, at: value.at };
+}
+
+export function coerceDaemonRollbackState(raw: unknown): DaemonRollbackState {
+  if (!isRecord(raw)) {
+    return EMPTY_DAEMON_ROLLBACK_STATE;
+  }
+  return {
+    knownGood: coerceRuntimeRecord(raw.knownGood),
+    rolledBack: coerceRolledBack(raw.rolledBack),
+  };
+}
+
+/** The kept runtime to start instead of the bundled one, while `appVersion` is held back. */
+export function resolveRollbackRuntime(
+  state: DaemonRollbackState,
+  appVersion: string,
+): DaemonRuntimeRecord | null {
+  const { knownGood, rolledBack } = state;
+  if (!knownGood || rolledBack?.version !== appVersion || rolledBack.to !== knownGood.version) {
+    return null;
+  }
+  return knownGood;
+}
+
+/** Whether the bundled daemon still has to prove itself before it is kept. */
+export function needsDaemonHealthCheck(state: DaemonRollbackState, appVersion: string): boolean {
+  return state.knownGood?.version !== appVersion && state.rolledBack?.version !== appVersion;
+}
+
+export type DaemonHealthCheckVerdict = "pending" | "healthy" | "crash_loop";
+
+export interface DaemonHealthCheck {
+  /** A sample found the daemon up at `now`. */
+  running(now: number): DaemonHealthCheckVerdict;
+  /** The daemon exited or failed to start. */
+  crashed(): DaemonHealthCheckVerdict;
+}
+
+/**
+ * Counts supervised runtime between samples that found the daemon up. Time
+ * spent down, or between the last good sample and a crash, does not count,
+ * so a daemon that keeps dying never accumulates its way to healthy.
+ */
+export function createDaemonHealthCheck(
+  input: { windowMs?: number; crashLimit?: number } = {},
+): DaemonHealthCheck {
+  const windowMs = input.windowMs ?? DAEMON_HEALTH_WINDOW_MS;
+  const crashLimit = input.crashLimit ?? DAEMON_CRASH_LIMIT;
+  let verdict: DaemonHealthCheckVerdict = "pending";
+  let runtimeMs = 0;
+  let lastRunningAt: number | null = null;
+  let crashes = 0;
+
+  return {
+    running(now) {
+      if (verdict !== "pending") {
+        return verdict;
+      }
+      if (lastRunningAt !== null) {
+        runtimeMs += Math.max(0, now - lastRunningAt);
+      }
+      lastRunningAt = now;
+      if (runtimeMs >= windowMs) {
+        verdict = "healthy";
+      }
+      return verdict;
+    },
+    crashed() {
+      if (verdict !== "pending") {
+        return verdict;
+      }
+      lastRunningAt = null;
+      crashes += 1;
+      if (crashes >= crashLimit) {
+        verdict = "crash_loop";
+      }
+      return verdict;
+    },
+  };
+}
@@ -0,0 +1,54 @@
+import { session } from "electron";
+import log from "electron-log/main";
+import { readDaemonTlsCertificate } from "./daemon-tls.js";
+
+// Chromium verify-proc results: 0 accepts, -3 defers to its own verification.
+const CERTIFICATE_ACCEPTED = 0;
+const USE_CHROMIUM_VERIFICATION = -3;
+
+let trusted: { certificate: string; hostnames: Set<string> } | null = null;
+let installed = false;
+
+function normalizePem(pem: string): string {
+  return pem.replace(/\s+/g, "");
+}
+
+/**
+ * Trusts the daemon's own server certificate for the desktop app's
+ * connections without touching the OS trust store. Only an exact match on
+ * both the certificate and one of its hostnames is accepted; everything
+ * else keeps Chromium's normal verification.
+ */
+export function trustDaemonCertificate(input: {
+  certificatePem: string;
+  hostnames: string[];
+}): void {
+  trusted = {
+    certificate: normalizePem(input.certificatePem),
+    hostnames: new Set(input.hostnames),
+  };
+  if (installed) {
+    return;
+  }
+  installed = true;
+  session.defaultSession.setCertificateVerifyProc((request, callback) => {
+    const hostname = request.hostname.replace(/^\[|\]$/g, "");
+    const matches =
+      trusted !== null &&
+      trusted.hostnames.has(hostname) &&
+      normalizePem(request.certificate.data) === trusted.certificate;
+    callback(matches ? CERTIFICATE_ACCEPTED : USE_CHROMIUM_VERIFICATION);
+  });
+}
+
+/** Re-installs trust for material generated in an earlier session. */
+export async function restoreDaemonCertificateTrust(dir: string): Promise<void> {
+  try {
+    const existing = await readDaemonTlsCertificate(dir);
+    if (existing) {
+      trustDaemonCertificate(existing);
+    }
+  } catch (error) {
+    log.warn("[daemon-tls] failed to read the daemon certificate", error);
+  }
+}
@@ -0,0 +1,109 @@
+import { X509Certificate } from "node:crypto";
+import { mkdtemp, readFile, rm, stat } from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+import {
+  coerceTlsHostnames,
+  DEFAULT_TLS_HOSTNAMES,
+  generateDaemonTlsMaterial,
+  parseSubjectAltNames,
+  readDaemonTlsCertificate,
+  resolveDaemonTlsEnv,
+} from "./daemon-tls";
+
+describe("coerceTlsHostnames", () => {
+  it("defaults to the loopback names", () => {
+    expect(coerceTlsHostnames(undefined)).toEqual(DEFAULT_TLS_HOSTNAMES);
+  });
+
+  it("lower-cases and de-duplicates names and addresses", () => {
+    expect(coerceTlsHostnames(["Paseo.local", "paseo.local", " 10.0.0.2 ", "fe80::1"])).toEqual([
+      "paseo.local",
+      "10.0.0.2",
+      "fe80::1",
+    ]);
+  });
+
+  it("rejects wildcards, ports and empty lists", () => {
+    expect(() => coerceTlsHostnames(["*.example.com"])).toThrow("Invalid TLS hostname");
+    expect(() => coerceTlsHostnames(["localhost:6767"])).toThrow("Invalid TLS hostname");
+    expect(() => coerceTlsHostnames([])).toThrow("TLS hostnames");
+  });
+});
+
+describe("parseSubjectAltNames", () => {
+  it("reads DNS names and compresses IPv6 addresses", () => {
+    expect(
+      parseSubjectAltNames("DNS:localhost, IP Address:127.0.0.1, IP Address:0:0:0:0:0:0:0:1"),
+    ).toEqual(["localhost", "127.0.0.1", "::1"]);
+  });
+});
+
+describe("generateDaemonTlsMaterial", () => {
+  let dir: string;
+
+  beforeEach(async () => {
+    dir = path.join(await mkdtemp(path.join(os.tmpdir(), "paseo-daemon-tls-")), "tls");
+  });
+
+  afterEach(async () => {
+    await rm(path.dirname(dir), { recursive: true, force: true });
+  });
+
+  it("issues a server certificate signed by a fresh CA", async () => {
+    const now = new Date("2026-03-01T12:00:00.000Z");
+    const material = await generateDaemonTlsMaterial({
+      dir,
+      hostnames: ["localhost", "127.0.0.1", "::1"],
+      now,
+    });
+
+    const ca = new X509Certificate(await readFile(material.caCertPath));
+    const leaf = new X509Certificate(await readFile(material.certPath));
+    expect(ca.ca).toBe(true);
+    expect(leaf.ca).toBe(false);
+    expect(leaf.verify(ca.publicKey)).toBe(true);
+    expect(leaf.checkIssued(ca)).toBe(true);
+    expect(leaf.checkHost("localhost")).toBe("localhost");
+    expect(leaf.checkIP("127.0.0.1")).toBe("127.0.0.1");
+    expect(leaf.checkIP("::1")).toBe("::1");
+    expect(leaf.keyUsage).toEqual(["1.3.6.1.5.5.7.3.1"]);
+    expect(material.fingerprint256).toBe(leaf.fingerprint256);
+    expect(material.expiresAt).toBe("2027-04-02T12:00:00.000Z");
+  });
+
+  it("keeps private keys owner-only", async () => {
+    if (process.platform === "win32") {
+      return;
+    }
+    const material = await generateDaemonTlsMaterial({ dir, hostnames: ["localhost"] });
+
+    expect((await stat(dir)).mode & 0o777).toBe(0o700);
+    expect((await stat(material.keyPath)).mode & 0o777).toBe(0o600);
+    expect((await stat(path.join(dir, "ca.key"))).mode & 0o777).toBe(0o600);
+  });
+
+  it("reads back the certificate and its hostnames", async () => {
+    expect(await readDaemonTlsCertificate(dir)).toBeNull();
+
+    const material = await generateDaemonTlsMaterial({ dir, hostnames: ["localhost", "::1"] });
+
+    expect(await readDaemonTlsCertificate(dir)).toEqual({
+      certificatePem: material.certificatePem,
+      hostnames: ["localhost", "::1"],
+    });
+  });
+
+  it("points the daemon at the generated files once they exist", async () => {
+    expect(await resolveDaemonTlsEnv(dir)).toBeNull();
+
+    const material = await generateDaemonTlsMaterial({ dir, hostnames: ["localhost"] });
+
+    expect(await resolveDaemonTlsEnv(dir)).toEqual({
+      PASEO_TLS_CERT: material.certPath,
+      PASEO_TLS_KEY: material.keyPath,
+      NODE_EXTRA_CA_CERTS: material.caCertPath,
+    });
+  });
+});
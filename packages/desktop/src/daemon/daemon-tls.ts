@@ -0,0 +1,184 @@
+import { generateKeyPairSync, type KeyObject, X509Certificate } from "node:crypto";
+import { access, chmod, mkdir, readFile } from "node:fs/promises";
+import { isIP } from "node:net";
+import path from "node:path";
+import { writeConfigFile } from "../settings/config-file.js";
+import { createCertificateDer, toPem } from "./x509.js";
+
+export const DEFAULT_TLS_HOSTNAMES = ["localhost", "127.0.0.1", "::1"];
+
+const CA_COMMON_NAME = "Paseo Local Daemon CA";
+const ORGANIZATION = "Paseo";
+const CA_VALIDITY_DAYS = 3650;
+// Apple and Chromium reject leaf certificates valid for longer than 398 days.
+const SERVER_VALIDITY_DAYS = 397;
+// Absorbs clock skew between the desktop app and the daemon host.
+const BACKDATE_MS = 60 * 60 * 1000;
+const DAY_MS = 24 * 60 * 60 * 1000;
+const MAX_HOSTNAMES = 32;
+const HOSTNAME_LABEL = "[a-z0-9]([a-z0-9-]{0,61}[a-z0-9])?";
+const HOSTNAME_PATTERN = new RegExp(`^(?=.{1,253}$)${HOSTNAME_LABEL}(\\.${HOSTNAME_LABEL})*$`);
+
+export const DAEMON_TLS_FILES = {
+  caCert: "ca.crt",
+  caKey: "ca.key",
+  cert: "server.crt",
+  key: "server.key",
+} as const;
+
+export interface DaemonTlsMaterial {
+  dir: string;
+  caCertPath: string;
+  certPath: string;
+  keyPath: string;
+  hostnames: string[];
+  /** SHA-256 fingerprint of the server certificate, colon-separated hex. */
+  fingerprint256: string;
+  expiresAt: string;
+  certificatePem: string;
+}
+
+export function coerceTlsHostnames(value: unknown): string[] {
+  if (value === undefined || value === null) {
+    return [...DEFAULT_TLS_HOSTNAMES];
+  }
+  if (!Array.isArray(value) || value.length === 0 || value.length > MAX_HOSTNAMES) {
+    throw new Error(`TLS hostnames must be a list of 1 to ${MAX_HOSTNAMES} names.`);
+  }
+  const hostnames: string[] = [];
+  for (const entry of value) {
+    const hostname = typeof entry === "string" ? entry.trim().toLowerCase() : "";
+    if (!isIP(hostname) && !HOSTNAME_PATTERN.test(hostname)) {
+      throw new Error(`Invalid TLS hostname: ${String(entry)}`);
+    }
+    if (!hostnames.includes(hostname)) {
+      hostnames.push(hostname);
+    }
+  }
+  return hostnames;
+}
+
+/**
+ * Creates a fresh CA and a server certificate it signs for `hostnames`.
+ * Regenerating replaces both, so an old CA can never vouch for a new key.
+ * Private keys are owner-only; Windows ignores the modes and relies on the
+ * per-user ACLs of the Paseo home directory instead.
+ */
+export async function generateDaemonTlsMaterial(input: {
+  dir: string;
+  hostnames: string[];
+  now?: Date;
+}): Promise<DaemonTlsMaterial> {
+  const now = input.now ?? new Date();
+  const notBefore = new Date(now.getTime() - BACKDATE_MS);
+  const caKeys = generateKeyPairSync("ec", { namedCurve: "prime256v1" });
+  const serverKeys = generateKeyPairSync("ec", { namedCurve: "prime256v1" });
+  const caName = { commonName: CA_COMMON_NAME, organization: ORGANIZATION };
+
+  const caDer = createCertificateDer({
+    subject: caName,
+    issuer: caName,
+    publicKey: caKeys.publicKey,
+    signingKey: caKeys.privateKey,
+    issuerPublicKey: caKeys.publicKey,
+    notBefore,
+    notAfter: new Date(now.getTime() + CA_VALIDITY_DAYS * DAY_MS),
+    role: { kind: "ca" },
+  });
+  const serverDer = createCertificateDer({
+    subject: { commonName: input.hostnames[0] ?? "localhost", organization: ORGANIZATION },
+    issuer: caName,
+    publicKey: serverKeys.publicKey,
+    signingKey: caKeys.privateKey,
+    issuerPublicKey: caKeys.publicKey,
+    notBefore,
+    notAfter: new Date(now.getTime() + SERVER_VALIDITY_DAYS * DAY_MS),
+    role: { kind: "server", hostnames: input.hostnames },
+  });
+
+  const exportKey = (key: KeyObject) => key.export({ type: "pkcs8", format: "pem" }).toString();
+  const certificatePem = toPem("CERTIFICATE", serverDer);
+  const paths = {
+    caCertPath: path.join(input.dir, DAEMON_TLS_FILES.caCert),
+    caKeyPath: path.join(input.dir, DAEMON_TLS_FILES.caKey),
+    certPath: path.join(input.dir, DAEMON_TLS_FILES.cert),
+    keyPath: path.join(input.dir, DAEMON_TLS_FILES.key),
+  };
+  await mkdir(input.dir, { recursive: true, mode: 0o700 });
+  await chmod(input.dir, 0o700);
+  await writeConfigFile(paths.caKeyPath, exportKey(caKeys.privateKey), { mode: 0o600 });
+  await writeConfigFile(paths.keyPath, exportKey(serverKeys.privateKey), { mode: 0o600 });
+  await writeConfigFile(paths.caCertPath, toPem("CERTIFICATE", caDer), { mode: 0o644 });
+  await writeConfigFile(paths.certPath, certificatePem, { mode: 0o644 });
+
+  const certificate = new X509Certificate(serverDer);
+  return {
+    dir: input.dir,
+    caCertPath: paths.caCertPath,
+    certPath: paths.certPath,
+    keyPath: paths.keyPath,
+    hostnames: input.hostnames,
+    fingerprint256: certificate.fingerprint256,
+    expiresAt: new Date(certificate.validTo).toISOString(),
+    certificatePem,
+  };
+}
+
+/** `DNS:localhost, IP Address:127.0.0.1` → `["localhost", "127.0.0.1"]`. */
+export function parseSubjectAltNames(value: string | undefined): string[] {
+  return (value ?? "")
+    .split(",")
+    .map((entry) => entry.trim())
+    .flatMap((entry) => {
+      const match = /^(DNS|IP Address):(.+)$/.exec(entry);
+      if (!match) {
+        return [];
+      }
+      // Node prints IPv6 SANs uncompressed; normalize so they compare with user input.
+      return [match[1] === "DNS" ? match[2]! : normalizeIp(match[2]!)];
+    });
+}
+
+function normalizeIp(address: string): string {
+  if (isIP(address) !== 6) {
+    return address;
+  }
+  return new URL(`http://[${address}]/`).hostname.slice(1, -1);
+}
+
+/** The server certificate on disk and the names it covers, or `null` before generation. */
+export async function readDaemonTlsCertificate(
+  dir: string,
+): Promise<{ certificatePem: string; hostnames: string[] } | null> {
+  let certificatePem: string;
+  try {
+    certificatePem = await readFile(path.join(dir, DAEMON_TLS_FILES.cert), "utf8");
+  } catch {
+    return null;
+  }
+  const certificate = new X509Certificate(certificatePem);
+  return { certificatePem, hostnames: parseSubjectAltNames(certificate.subjectAltName) };
+}
+
+/**
+ * Environment that makes a daemon started now serve HTTPS and WSS with the
+ * material in `dir`, or `null` before it is generated. Agents the daemon
+ * spawns inherit the CA, so their MCP calls back to it verify.
+ */
+export async function resolveDaemonTlsEnv(dir: string): Promise<Record<string, string> | null> {
+  const paths = {
+    caCertPath: path.join(dir, DAEMON_TLS_FILES.caCert),
+    certPath: path.join(dir, DAEMON_TLS_FILES.cert),
+    keyPath: path.join(dir, DAEMON_TLS_FILES.key),
+  };
+  try {
+    await Promise.all(Object.values(paths).map((filePath) => access(filePath)));
+  } catch {
+    return null;
+  }
+  return {
+    PASEO_TLS_CERT: paths.certPath,
+    PASEO_TLS_KEY: paths.keyPath,
+    NODE_EXTRA_CA_CERTS: paths.caCertPath,
+  };
+}
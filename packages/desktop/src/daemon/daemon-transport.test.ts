@@ -0,0 +1,109 @@
+import { mkdtemp, rm } from "node:fs/promises";
+import { createServer, type Server } from "node:http";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import { WebSocketServer } from "ws";
+import { createDaemonTransport, type DaemonTransportHandlers } from "./daemon-transport";
+
+function createHandlers() {
+  let opened!: () => void;
+  let closed!: (code: number) => void;
+  const openedPromise = new Promise<void>((resolve) => (opened = resolve));
+  const closedPromise = new Promise<number>((resolve) => (closed = resolve));
+  const handlers: DaemonTransportHandlers = {
+    onOpen: vi.fn(() => opened()),
+    onText: vi.fn(),
+    onBinary: vi.fn(),
+    onPong: vi.fn(),
+    onClose: vi.fn((code: number) => closed(code)),
+    onError: vi.fn(),
+  };
+  return { handlers, opened: openedPromise, closed: closedPromise };
+}
+
+describe("websocket daemon transport", () => {
+  let dir: string;
+  let socketPath: string;
+  let server: Server;
+  let wss: WebSocketServer;
+
+  beforeEach(async () => {
+    dir = await mkdtemp(path.join(os.tmpdir(), "paseo-daemon-transport-"));
+    socketPath = path.join(dir, "daemon.sock");
+    server = createServer();
+    wss = new WebSocketServer({ server, path: "/ws" });
+    wss.on("connection", (socket) => {
+      socket.on("message", (data, isBinary) => socket.send(data, { binary: isBinary }));
+    });
+    await new Promise<void>((resolve) => server.listen(socketPath, resolve));
+  });
+
+  afterEach(async () => {
+    wss.close();
+    await new Promise<void>((resolve) => server.close(() => resolve()));
+    await rm(dir, { recursive: true, force: true });
+  });
+
+  it("moves frames and pongs through the handlers and closes cleanly", async () => {
+    const { handlers, opened, closed } = createHandlers();
+    const transport = createDaemonTransport("websocket", {
+      target: { transportType: "socket", transportPath: socketPath },
+      authToken: null,
+      perMessageDeflate: false,
+      connectTimeoutMs: 5_000,
+      handlers,
+    });
+    expect(transport.state()).toBe("connecting");
+    await opened;
+    expect(transport.state()).toBe("open");
+
+    await transport.send(Buffer.from([1, 2, 3]));
+    transport.ping(Buffer.from([0, 0, 0, 7]));
+    await vi.waitFor(() => {
+      expect(handlers.onBinary).toHaveBeenCalledWith(Buffer.from([1, 2, 3]), 3);
+      expect(handlers.onPong).toHaveBeenCalledWith(Buffer.from([0, 0, 0, 7]));
+    });
+    expect(transport.wireBytes()?.sent).toBeGreaterThan(0);
+
+    transport.close();
+    expect(await closed).toBe(1005);
+    expect(transport.state()).toBe("closed");
+  });
+
+  it("stops reporting to its handlers once abandoned", async () => {
+    const { handlers, opened } = createHandlers();
+    const transport = createDaemonTransport("websocket", {
+      target: { transportType: "socket", transportPath: socketPath },
+      authToken: null,
+      perMessageDeflate: false,
+      connectTimeoutMs: 5_000,
+      handlers,
+    });
+    await opened;
+    transport.abandon();
+    await vi.waitFor(() => expect(transport.state()).toBe("closed"));
+    expect(handlers.onClose).not.toHaveBeenCalled();
+    expect(handlers.onError).not.toHaveBeenCalled();
+  });
+
+  it("offers the daemon password the way the daemon client does", async () => {
+    const upgrade = new Promise<Record<string, unknown>>((resolve) =>
+      wss.once("connection", (_socket, request) => resolve(request.headers)),
+    );
+    const { handlers, opened } = createHandlers();
+    const transport = createDaemonTransport("websocket", {
+      target: { transportType: "socket", transportPath: socketPath },
+      authToken: "secret",
+      perMessageDeflate: false,
+      connectTimeoutMs: 5_000,
+      handlers,
+    });
+    await opened;
+    expect(await upgrade).toMatchObject({
+      authorization: "Bearer secret",
+      "sec-websocket-protocol": "paseo.bearer.secret",
+    });
+    transport.abandon();
+  });
+});
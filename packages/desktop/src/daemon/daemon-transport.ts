@@ -0,0 +1,194 @@
+import type { Socket } from "node:net";
+import { WebSocket, type PerMessageDeflateOptions, type RawData } from "ws";
+
+const WS_ENDPOINT_PATH = "/ws";
+
+export interface DaemonTransportTarget {
+  transportType: "socket" | "pipe";
+  transportPath: string;
+}
+
+/** Connection kinds the desktop can open to a daemon. */
+export type DaemonTransportKind = "websocket";
+
+export type DaemonTransportState = "connecting" | "open" | "closing" | "closed";
+
+export interface DaemonTransportHandlers {
+  onOpen(): void;
+  onText(text: string, byteLength: number): void;
+  onBinary(data: Buffer, byteLength: number): void;
+  onPong(payload: Buffer): void;
+  onClose(code: number, reason: string): void;
+  onError(error: Error): void;
+}
+
+export interface DaemonTransportOptions {
+  target: DaemonTransportTarget;
+  /** Daemon password, sent the way the renderer's daemon client sends it. */
+  authToken: string | null;
+  perMessageDeflate: PerMessageDeflateOptions | false;
+  connectTimeoutMs: number;
+  handlers: DaemonTransportHandlers;
+}
+
+/**
+ * One connection to the daemon. Sessions own reconnects, resume and
+ * heartbeats; a transport only moves frames and reports what happened to
+ * them, so another wire protocol can sit behind the same session.
+ */
+export interface DaemonTransport {
+  readonly kind: DaemonTransportKind;
+  state(): DaemonTransportState;
+  send(payload: string | Buffer): Promise<void>;
+  /** Heartbeat probe; the daemon echoes `payload` back through `onPong`. */
+  ping(payload: Buffer): void;
+  /** Negotiated extensions such as permessage-deflate; empty when there are none. */
+  extensions(): string;
+  /** Bytes on the wire after framing and compression; `null` before connecting. */
+  wireBytes(): { sent: number; received: number } | null;
+  /** Closes cleanly; `onClose` follows once the daemon acknowledges. */
+  close(): void;
+  /** Drops the connection at once without calling any handler again. */
+  abandon(): void;
+}
+
+function rawDataByteLength(data: RawData): number {
+  if (Array.isArray(data)) {
+    return data.reduce((sum, chunk) => sum + chunk.byteLength, 0);
+  }
+  return data.byteLength;
+}
+
+/**
+ * Build a WebSocket URL that connects through a Unix domain socket or Windows
+ * named pipe.  The `ws` library supports these via the `ws+unix://` scheme:
+ *
+ *   ws+unix:///path/to/socket:/ws
+ *   ws+unix://./pipe/paseo:/ws        (Windows named pipe)
+ *
+ * The part before `:` is the IPC path, the part after is the HTTP request
+ * path used during the WebSocket upgrade handshake.
+ */
+function buildLocalWebSocketUrl(target: DaemonTransportTarget): string {
+  const ipcPath = target.transportPath;
+  return `ws+unix://${ipcPath}:${WS_ENDPOINT_PATH}`;
+}
+
+export function createWebSocketTransport(options: DaemonTransportOptions): DaemonTransport {
+  const { authToken } = options;
+  const ws = new WebSocket(
+    buildLocalWebSocketUrl(options.target),
+    authToken ? [`paseo.bearer.${authToken}`] : [],
+    {
+      perMessageDeflate: options.perMessageDeflate,
+      handshakeTimeout: options.connectTimeoutMs,
+      ...(authToken ? { headers: { Authorization: `Bearer ${authToken}` } } : {}),
+    },
+  );
+  let socket: Socket | null = null;
+  let abandoned = false;
+  const handlers = options.handlers;
+
+  ws.once("upgrade", (response) => {
+    socket = response.socket as Socket;
+  });
+  ws.once("open", () => {
+    if (!abandoned) {
+      handlers.onOpen();
+    }
+  });
+  ws.on("message", (data: RawData, isBinary: boolean) => {
+    if (abandoned) {
+      return;
+    }
+    const byteLength = rawDataByteLength(data);
+    if (isBinary || data instanceof Buffer) {
+      const buf = Buffer.isBuffer(data) ? data : Buffer.from(data as ArrayBuffer);
+      handlers.onBinary(buf, byteLength);
+      return;
+    }
+    handlers.onText(data.toString(), byteLength);
+  });
+  ws.on("pong", (data: Buffer) => {
+    if (!abandoned) {
+      handlers.onPong(data);
+    }
+  });
+  ws.on("close", (code: number, reason?: Buffer | string) => {
+    if (!abandoned) {
+      handlers.onClose(code, reason ? String(reason) : "");
+    }
+  });
+  ws.on("error", (error: Error) => {
+    if (!abandoned) {
+      handlers.onError(error);
+    }
+  });
+
+  return {
+    kind: "websocket",
+
+    state() {
+      switch (ws.readyState) {
+        case WebSocket.CONNECTING:
+          return "connecting";
+        case WebSocket.OPEN:
+          return "open";
+        case WebSocket.CLOSING:
+          return "closing";
+        default:
+          return "closed";
+      }
+    },
+
+    send(payload) {
+      return new Promise<void>((resolve, reject) => {
+        ws.send(payload, (error) => {
+          if (error) {
+            reject(new Error(`Local transport write failed: ${error.message}`));
+            return;
+          }
+          resolve();
+        });
+      });
+    },
+
+    ping(payload) {
+      ws.ping(payload);
+    },
+
+    extensions() {
+      return ws.extensions.trim();
+    },
+
+    wireBytes() {
+      return socket ? { sent: socket.bytesWritten, received: socket.bytesRead } : null;
+    },
+
+    close() {
+      if (ws.readyState === WebSocket.CONNECTING) {
+        ws.terminate();
+      } else if (ws.readyState === WebSocket.OPEN) {
+        ws.close();
+      }
+    },
+
+    abandon() {
+      abandoned = true;
+      ws.terminate();
+    },
+  };
+}
+
+type DaemonTransportFactory = (options: DaemonTransportOptions) => DaemonTransport;
+
+const DAEMON_TRANSPORTS: Record<DaemonTransportKind, DaemonTransportFactory> = {
+  websocket: createWebSocketTransport,
+};
+
+export function createDaemonTransport(
+  kind: DaemonTransportKind,
+  options: DaemonTransportOptions,
+): DaemonTransport {
+  return DAEMON_TRANSPORTS[kind](options);
+}
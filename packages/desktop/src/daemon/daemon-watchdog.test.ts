@@ -0,0 +1,72 @@
+import { describe, expect, it, vi } from "vitest";
+import {
+  createDaemonWatchdog,
+  detectDaemonHealthIssue,
+  type DaemonHealthSample,
+} from "./daemon-watchdog";
+
+const HEALTHY: DaemonHealthSample = { running: true, listening: true, freeBytes: 10e9 };
+
+describe("detectDaemonHealthIssue", () => {
+  it("only calls a stopped daemon crashed when it was running before", () => {
+    const stopped = { ...HEALTHY, running: false, listening: null };
+    expect(detectDaemonHealthIssue(stopped, { wasRunning: true })).toBe("crashed");
+    expect(detectDaemonHealthIssue(stopped, { wasRunning: false })).toBeNull();
+  });
+
+  it("reports a running daemon that no longer holds its port", () => {
+    expect(detectDaemonHealthIssue({ ...HEALTHY, listening: false }, { wasRunning: true })).toBe(
+      "port_lost",
+    );
+  });
+
+  it("puts low disk space first", () => {
+    const sample = { running: false, listening: null, freeBytes: 1024 };
+    expect(detectDaemonHealthIssue(sample, { wasRunning: true })).toBe("disk_full");
+    expect(detectDaemonHealthIssue(HEALTHY, { wasRunning: true })).toBeNull();
+  });
+});
+
+describe("createDaemonWatchdog", () => {
+  it("reports each issue once until a healthy sample clears it", async () => {
+    const samples: DaemonHealthSample[] = [
+      HEALTHY,
+      { ...HEALTHY, listening: false },
+      { ...HEALTHY, listening: false },
+      HEALTHY,
+      { running: false, listening: null, freeBytes: 10e9 },
+      { running: false, listening: null, freeBytes: 10e9 },
+      HEALTHY,
+      { running: false, listening: null, freeBytes: 10e9 },
+    ];
+    const onIssue = vi.fn();
+    const watchdog = createDaemonWatchdog({
+      sample: async () => samples.shift()!,
+      onIssue,
+    });
+
+    for (let index = 0; index < 8; index += 1) {
+      await watchdog.check();
+    }
+
+    expect(onIssue.mock.calls.map(([issue]) => issue)).toEqual([
+      "port_lost",
+      "crashed",
+      "crashed",
+    ]);
+  });
+
+  it("polls on the configured interval", async () => {
+    vi.useFakeTimers();
+    const sample = vi.fn(async () => HEALTHY);
+    const watchdog = createDaemonWatchdog({ sample, onIssue: vi.fn(), intervalMs: 1_000 });
+
+    watchdog.start();
+    await vi.advanceTimersByTimeAsync(3_000);
+    watchdog.stop();
+    await vi.advanceTimersByTimeAsync(3_000);
+
+    expect(sample).toHaveBeenCalledTimes(3);
+    vi.useRealTimers();
+  });
+});
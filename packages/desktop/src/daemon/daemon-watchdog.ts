@@ -0,0 +1,89 @@
+const DEFAULT_WATCHDOG_INTERVAL_MS = 60_000;
+export const LOW_DISK_FREE_BYTES = 512 * 1024 * 1024;
+
+export type DaemonHealthIssue = "crashed" | "port_lost" | "disk_full";
+
+export interface DaemonHealthSample {
+  running: boolean;
+  /** `false` when the daemon reports running but nothing accepts on its port. */
+  listening: boolean | null;
+  /** Free bytes on the Paseo home volume, when it could be read. */
+  freeBytes: number | null;
+}
+
+export function detectDaemonHealthIssue(
+  sample: DaemonHealthSample,
+  input: { wasRunning: boolean; lowDiskBytes?: number },
+): DaemonHealthIssue | null {
+  const lowDiskBytes = input.lowDiskBytes ?? LOW_DISK_FREE_BYTES;
+  if (sample.freeBytes !== null && sample.freeBytes < lowDiskBytes) {
+    return "disk_full";
+  }
+  if (!sample.running) {
+    // A daemon that was never up is a startup failure, which is reported where it happens.
+    return input.wasRunning ? "crashed" : null;
+  }
+  return sample.listening === false ? "port_lost" : null;
+}
+
+export interface DaemonWatchdog {
+  start(): void;
+  stop(): void;
+  /** Runs one check immediately; exposed for tests and resume handlers. */
+  check(): Promise<DaemonHealthIssue | null>;
+}
+
+/**
+ * Polls daemon health and reports each issue once, when it first appears.
+ * The same issue is reported again only after a healthy sample clears it.
+ */
+export function createDaemonWatchdog(input: {
+  sample: () => Promise<DaemonHealthSample>;
+  onIssue: (issue: DaemonHealthIssue) => void;
+  intervalMs?: number;
+  lowDiskBytes?: number;
+}): DaemonWatchdog {
+  let timer: ReturnType<typeof setInterval> | null = null;
+  let wasRunning = false;
+  let reported: DaemonHealthIssue | null = null;
+
+  async function check(): Promise<DaemonHealthIssue | null> {
+    const sample = await input.sample();
+    const issue = detectDaemonHealthIssue(sample, {
+      wasRunning,
+      lowDiskBytes: input.lowDiskBytes,
+    });
+    if (sample.running) {
+      wasRunning = true;
+    } else if (issue === "crashed") {
+      // Report the crash once; a later restart sets this again.
+      wasRunning = false;
+    }
+    if (issue !== reported && issue !== null) {
+      input.onIssue(issue);
+    }
+    if (issue !== null || sample.running) {
+      reported = issue;
+    }
+    return issue;
+  }
+
+  return {
+    start() {
+      if (timer) {
+        return;
+      }
+      timer = setInterval(() => {
+        void check().catch(() => undefined);
+      }, input.intervalMs ?? DEFAULT_WATCHDOG_INTERVAL_MS);
+      timer.unref?.();
+    },
+    stop() {
+      if (timer) {
+        clearInterval(timer);
+        timer = null;
+      }
+    },
+    check,
+  };
+}
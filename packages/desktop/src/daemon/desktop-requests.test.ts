@@ -0,0 +1,16 @@
+import { describe, expect, it } from "vitest";
+import { readReplyRequestId } from "./desktop-requests";
+
+describe("readReplyRequestId", () => {
+  it("reads the request id from a session reply payload", () => {
+    const reply = { type: "session", message: { type: "pong", payload: { requestId: "r1" } } };
+    expect(readReplyRequestId(JSON.stringify(reply))).toBe("r1");
+  });
+
+  it("ignores frames without one", () => {
+    expect(readReplyRequestId(JSON.stringify({ type: "session", message: { type: "x" } }))).toBe(
+      null,
+    );
+    expect(readReplyRequestId("not json")).toBeNull();
+  });
+});
@@ -0,0 +1,15 @@
+import { isRecord } from "../utils/is-record.js";
+
+/** `requestId` of a daemon reply; replies carry it in their payload. */
+export function readReplyRequestId(text: string): string | null {
+  try {
+    const parsed: unknown = JSON.parse(text);
+    if (!isRecord(parsed) || parsed.type !== "session" || !isRecord(parsed.message)) {
+      return null;
+    }
+    const payload = parsed.message.payload;
+    return isRecord(payload) && typeof payload.requestId === "string" ? payload.requestId : null;
+  } catch {
+    return null;
+  }
+}
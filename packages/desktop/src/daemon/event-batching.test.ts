@@ -0,0 +1,70 @@
+import { afterEach, describe, expect, it, vi } from "vitest";
+import {
+  coerceEventBatchingConfig,
+  createEventBatcher,
+  IMMEDIATE_EVENT_BATCHING,
+  type EventBatchingConfig,
+} from "./event-batching";
+
+describe("event batching", () => {
+  afterEach(() => {
+    vi.useRealTimers();
+  });
+
+  it("delivers each event on its own when batching is off", () => {
+    const batches: number[][] = [];
+    const batcher = createEventBatcher<number>({
+      deliver: (events) => batches.push(events),
+      getConfig: () => IMMEDIATE_EVENT_BATCHING,
+    });
+
+    batcher.push(1);
+    batcher.push(2);
+
+    expect(batches).toEqual([[1], [2]]);
+  });
+
+  it("groups events until the interval passes or the batch fills", () => {
+    vi.useFakeTimers();
+    const batches: number[][] = [];
+    const batcher = createEventBatcher<number>({
+      deliver: (events) => batches.push(events),
+      getConfig: () => ({ intervalMs: 50, maxBatch: 3 }),
+    });
+
+    [1, 2, 3, 4].forEach((event) => batcher.push(event));
+    expect(batches).toEqual([[1, 2, 3]]);
+
+    vi.advanceTimersByTime(50);
+    expect(batches).toEqual([[1, 2, 3], [4]]);
+  });
+
+  it("flushes early for ordering-sensitive events and follows config changes", () => {
+    vi.useFakeTimers();
+    let config: EventBatchingConfig = { intervalMs: 100, maxBatch: 10 };
+    const batches: number[][] = [];
+    const batcher = createEventBatcher<number>({
+      deliver: (events) => batches.push(events),
+      getConfig: () => config,
+    });
+
+    batcher.push(1);
+    batcher.flush();
+    config = IMMEDIATE_EVENT_BATCHING;
+    batcher.push(2);
+    vi.advanceTimersByTime(100);
+
+    expect(batches).toEqual([[1], [2]]);
+  });
+
+  it("rejects out-of-range settings", () => {
+    expect(coerceEventBatchingConfig({ intervalMs: 16, maxBatch: 50 })).toEqual({
+      intervalMs: 16,
+      maxBatch: 50,
+    });
+    expect(() => coerceEventBatchingConfig({ intervalMs: -1, maxBatch: 50 })).toThrow(
+      "intervalMs",
+    );
+    expect(() => coerceEventBatchingConfig({ intervalMs: 16, maxBatch: 0 })).toThrow("maxBatch");
+  });
+});
@@ -0,0 +1,88 @@
+const MAX_INTERVAL_MS = 1_000;
+const MAX_BATCH_LIMIT = 1_000;
+
+export interface EventBatchingConfig {
+  /** How long events wait for company before delivery; 0 delivers each one as it arrives. */
+  intervalMs: number;
+  /** A batch is delivered as soon as it holds this many events. */
+  maxBatch: number;
+}
+
+export const IMMEDIATE_EVENT_BATCHING: EventBatchingConfig = { intervalMs: 0, maxBatch: 1 };
+/** Used while a woken machine drains its backlog, unless batching is already configured. */
+export const CATCH_UP_EVENT_BATCHING: EventBatchingConfig = { intervalMs: 50, maxBatch: 200 };
+
+export interface EventBatcher<T> {
+  push(event: T): void;
+  /** Delivers whatever is queued now; ordering-sensitive events call this first. */
+  flush(): void;
+  dispose(): void;
+}
+
+export function coerceEventBatchingConfig(
+  args: Record<string, unknown> | undefined,
+): EventBatchingConfig {
+  const { intervalMs, maxBatch } = args ?? {};
+  if (
+    typeof intervalMs !== "number" ||
+    !Number.isInteger(intervalMs) ||
+    intervalMs < 0 ||
+    intervalMs > MAX_INTERVAL_MS
+  ) {
+    throw new Error(`intervalMs must be an integer between 0 and ${MAX_INTERVAL_MS}.`);
+  }
+  if (
+    typeof maxBatch !== "number" ||
+    !Number.isInteger(maxBatch) ||
+    maxBatch < 1 ||
+    maxBatch > MAX_BATCH_LIMIT
+  ) {
+    throw new Error(`maxBatch must be an integer between 1 and ${MAX_BATCH_LIMIT}.`);
+  }
+  return { intervalMs, maxBatch };
+}
+
+/**
+ * Groups events for delivery under whatever config is current when each one
+ * arrives, so changing the config takes effect without recreating batchers.
+ */
+export function createEventBatcher<T>(input: {
+  deliver: (events: T[]) => void;
+  getConfig: () => EventBatchingConfig;
+}): EventBatcher<T> {
+  let queue: T[] = [];
+  let timer: NodeJS.Timeout | null = null;
+
+  function flush(): void {
+    if (timer) {
+      clearTimeout(timer);
+      timer = null;
+    }
+    if (queue.length === 0) {
+      return;
+    }
+    const events = queue;
+    queue = [];
+    input.deliver(events);
+  }
+
+  return {
+    push(event) {
+      const config = input.getConfig();
+      queue.push(event);
+      if (config.intervalMs === 0 || queue.length >= config.maxBatch) {
+        flush();
+        return;
+      }
+      timer ??= setTimeout(flush, config.intervalMs);
+    },
+    flush,
+    dispose() {
+      if (timer) {
+        clearTimeout(timer);
+        timer = null;
+      }
+      queue = [];
+    },
+  };
+}
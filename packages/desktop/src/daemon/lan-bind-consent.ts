@@ -0,0 +1,41 @@
+import { BrowserWindow, dialog } from "electron";
+
+/**
+ * Asks before the daemon listens beyond loopback. When the OS firewall is
+ * going to ask too, say so up front so its prompt is not a surprise.
+ */
+export async function confirmLanBind(input: {
+  listen: string;
+  firewallPrompts: boolean;
+}): Promise<boolean> {
+  const detail = [
+    `The daemon is configured to listen on ${input.listen}, which other devices on your ` +
+      "network can reach. Anyone who can connect can control your agents unless a password " +
+      "is set.",
+  ];
+  if (input.firewallPrompts) {
+    detail.push(
+      process.platform === "win32"
+        ? "Windows Defender Firewall will then ask whether to allow Paseo on private or " +
+            "public networks."
+        : "macOS will then ask whether Paseo may accept incoming network connections.",
+    );
+  }
+  detail.push("Keeping it local-only binds the daemon to 127.0.0.1 instead.");
+
+  const options: Electron.MessageBoxOptions = {
+    type: "warning",
+    title: "Network Access",
+    message: "Allow the Paseo daemon to accept connections from your network?",
+    detail: detail.join("\n\n"),
+    buttons: ["Keep Local Only", "Allow Network Access"],
+    defaultId: 0,
+    cancelId: 0,
+    noLink: true,
+  };
+  const win = BrowserWindow.getFocusedWindow();
+  const result = win
+    ? await dialog.showMessageBox(win, options)
+    : await dialog.showMessageBox(options);
+  return result.response === 1;
+}
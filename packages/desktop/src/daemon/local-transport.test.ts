@@ -0,0 +1,89 @@
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import type { DaemonTransportOptions, DaemonTransportState } from "./daemon-transport";
+
+const transport = vi.hoisted(() => ({
+  handlers: null as DaemonTransportOptions["handlers"] | null,
+  state: "connecting" as DaemonTransportState,
+}));
+
+vi.mock("electron", () => ({
+  BrowserWindow: { getAllWindows: () => [] },
+}));
+
+vi.mock("electron-log/main", () => ({
+  default: { warn: vi.fn(), info: vi.fn() },
+}));
+
+vi.mock("./daemon-transport.js", () => ({
+  createDaemonTransport: (_kind: string, options: DaemonTransportOptions) => {
+    transport.handlers = options.handlers;
+    return {
+      kind: "websocket",
+      state: () => transport.state,
+      send: async () => {},
+      ping: () => {},
+      extensions: () => "",
+      wireBytes: () => null,
+      close: () => {
+        transport.state = "closing";
+      },
+      abandon: () => {},
+    };
+  },
+}));
+
+vi.mock("../auth/daemon-auth-electron.js", () => ({
+  getDaemonAuthStore: () => ({ getToken: async () => null }),
+  onDaemonAuthStateChanged: () => () => {},
+}));
+
+vi.mock("../system/power-status.js", () => ({
+  onPollIntervalScaleChanged: () => () => {},
+  scalePollInterval: (ms: number) => ms,
+}));
+
+vi.mock("../window/desktop-events.js", () => ({
+  emitDesktopEvent: vi.fn(),
+}));
+
+vi.mock("../window/window-visibility.js", () => ({
+  isAnyWindowVisible: () => true,
+  onWindowVisibilityChanged: () => () => {},
+}));
+
+import {
+  closeLocalTransportSession,
+  observeLocalTransport,
+  openLocalTransportSession,
+} from "./local-transport";
+
+describe("closeLocalTransportSession", () => {
+  beforeEach(() => {
+    vi.useFakeTimers();
+    transport.handlers = null;
+    transport.state = "connecting";
+  });
+
+  afterEach(() => {
+    vi.useRealTimers();
+  });
+
+  it("reports the close once when the socket acknowledges it afterwards", async () => {
+    const closed = vi.fn();
+    const unobserve = observeLocalTransport({ closed });
+    const opening = openLocalTransportSession({
+      transportType: "socket",
+      transportPath: "/tmp/paseo/daemon.sock",
+    });
+    transport.state = "open";
+    transport.handlers?.onOpen();
+    const sessionId = await opening;
+
+    closeLocalTransportSession(sessionId);
+    transport.handlers?.onClose(1000, "closed by desktop");
+    unobserve();
+
+    expect(closed).toHaveBeenCalledTimes(1);
+    expect(closed).toHaveBeenCalledWith(expect.objectContaining({ sessionId }));
+  });
+});
@@ -1,10 +1,61 @@
 import { BrowserWindow } from "electron";
-import { WebSocket, type RawData } from "ws";
-
-interface TransportTarget {
-  transportType: "socket" | "pipe";
-  transportPath: string;
-}
+import log from "electron-log/main";
+import type { PerMessageDeflateOptions } from "ws";
+import { createBandwidthMeter, type BandwidthStats } from "./bandwidth-meter.js";
+import {
+  CATCH_UP_EVENT_BATCHING,
+  createEventBatcher,
+  IMMEDIATE_EVENT_BATCHING,
+  type EventBatcher,
+  type EventBatchingConfig,
+} from "./event-batching.js";
+import { createLowBandwidthFilter, type LowBandwidthFilter } from "./low-bandwidth-filter.js";
+import { computeCompressionRatio, type SessionCompressionStats } from "./transport-compression.js";
+import {
+  createConnectionHealthTracker,
+  type ConnectionHealth,
+  type ConnectionHealthStatus,
+  type ConnectionHealthTracker,
+} from "./connection-health.js";
+import {
+  createDaemonTransport,
+  type DaemonTransport,
+  type DaemonTransportKind,
+  type DaemonTransportTarget,
+} from "./daemon-transport.js";
+import { readReplyRequestId } from "./desktop-requests.js";
+import {
+  ALL_STREAMS,
+  classifyInboundBinary,
+  classifyInboundText,
+  coerceStreamKinds,
+  listResumedKinds,
+  listSubscribedKinds,
+  resolveEffectiveStreams,
+  STREAM_KINDS,
+  type StreamKind,
+  type StreamSubscriptions,
+} from "./stream-subscriptions.js";
+import {
+  applyTransportPolicyPatch,
+  coerceTransportPolicyPatch,
+  DEFAULT_TRANSPORT_POLICY,
+  type TransportPolicy,
+} from "./transport-policy.js";
+import {
+  computeReconnectDelayMs,
+  createResumeCursorTracker,
+  type ResumeCursorTracker,
+} from "./resume-cursor.js";
+import {
+  createLocalTransportObservers,
+  type LocalTransportObserver,
+  type LocalTransportSessionInfo,
+} from "./transport-observers.js";
+import { getDaemonAuthStore, onDaemonAuthStateChanged } from "../auth/daemon-auth-electron.js";
+import { onPollIntervalScaleChanged, scalePollInterval } from "../system/power-status.js";
+import { emitDesktopEvent } from "../window/desktop-events.js";
+import { isAnyWindowVisible, onWindowVisibilityChanged } from "../window/window-visibility.js";
 
 interface TransportEventPayload {
   sessionId: string;
@@ -16,39 +67,397 @@ interface TransportEventPayload {
   error?: string | null;
 }
 
+interface TransportResumeEventPayload {
+  sessionId: string;
+  status: "reconnecting" | "resumed" | "failed";
+  attempt: number;
+  delayMs?: number | null;
+  resumedAgents?: number | null;
+}
+
 interface Session {
   id: string;
-  ws: WebSocket;
-  state: "opening" | "open" | "closing" | "closed";
+  target: DaemonTransportTarget;
+  /** Server whose stored password the session connects with. */
+  serverId: string | null;
+  /** Kept current with the auth store, so a reconnect uses the latest password. */
+  authToken: string | null;
+  transportKind: DaemonTransportKind;
+  perMessageDeflate: PerMessageDeflateOptions | false;
+  transport: DaemonTransport;
+  state: "opening" | "open" | "reconnecting" | "closing" | "closed";
+  inbound: LowBandwidthFilter;
+  payloadBytesSent: number;
+  payloadBytesReceived: number;
+  health: ConnectionHealthTracker;
+  healthStatus: ConnectionHealthStatus;
+  heartbeatTimer: NodeJS.Timeout | null;
+  resume: ResumeCursorTracker;
+  reconnectAttempt: number;
+  reconnectTimer: NodeJS.Timeout | null;
+  /** Set after a long sleep so the next resume fetches only each agent's latest rows. */
+  catchUpTailLimit: number | null;
+  /** Renderer frames sent while the socket is down, flushed after the hello is replayed. */
+  pendingOutbound: Array<string | Buffer>;
+  /** Requests sent by the desktop itself; their replies are not forwarded to the renderer. */
+  pendingReplies: Map<string, (text: string) => boolean>;
+  /** Observers and the bandwidth meter have been told the session closed. */
+  closeNotified: boolean;
+  policy: TransportPolicy;
+  /** Stream kinds the renderer asked for. */
+  streams: StreamSubscriptions;
+  /** What is delivered right now, after the downgrade for hidden windows. */
+  effectiveStreams: StreamSubscriptions;
+}
+
+export interface LocalTransportStreamState {
+  connectionId: string | null;
+  requested: StreamKind[];
+  effective: StreamKind[];
+  windowsVisible: boolean;
 }
 
-const WS_ENDPOINT_PATH = "/ws";
+const BANDWIDTH_EVENT_INTERVAL_MS = 5_000;
+const DEFAULT_BANDWIDTH_WINDOW_MS = 60_000;
+const HEARTBEAT_INTERVAL_MS = 5_000;
+const MAX_PENDING_OUTBOUND_MESSAGES = 500;
+// With heartbeats unsubscribed, every sixth tick still pings so a dead socket is noticed.
+const BACKGROUND_HEARTBEAT_EVERY = 6;
+const CATCH_UP_WINDOW_MS = 10_000;
 
 let nextSessionId = 0;
 const sessions = new Map<string, Session>();
+const bandwidthMeter = createBandwidthMeter();
+let bandwidthEventTimer: NodeJS.Timeout | null = null;
+let lowBandwidthMode = false;
+let eventBatching: EventBatchingConfig = IMMEDIATE_EVENT_BATCHING;
+// Copied into each session as it opens; sessions can then be tuned one by one.
+let defaultTransportPolicy: TransportPolicy = DEFAULT_TRANSPORT_POLICY;
+let defaultStreams: StreamSubscriptions = ALL_STREAMS;
+let catchUpUntil = 0;
+let authTokenUpdatesRegistered = false;
+const observers = createLocalTransportObservers({
+  onError: (hook, error) => {
+    log.warn(`[local-transport] ${hook} observer failed`, error);
+  },
+});
+// Keyed by session id; outlives the session long enough to deliver its close event.
+const rendererBatchers = new Map<string, EventBatcher<TransportEventPayload>>();
+
+export interface LocalTransportEventBatchingState extends EventBatchingConfig {
+  /** Set while delivery is batched for a post-wake catch-up. */
+  catchUpUntil: string | null;
+}
+
+function resolveEventBatching(): EventBatchingConfig {
+  if (eventBatching.intervalMs === 0 && Date.now() < catchUpUntil) {
+    return CATCH_UP_EVENT_BATCHING;
+  }
+  return eventBatching;
+}
+
+function sendTransportEvents(events: TransportEventPayload[]): void {
+  const [first] = events;
+  if (!first) {
+    return;
+  }
+  for (const win of BrowserWindow.getAllWindows()) {
+    if (events.length === 1) {
+      win.webContents.send("paseo:event:local-daemon-transport-event", first);
+    } else {
+      win.webContents.send("paseo:event:local-daemon-transport-batch", {
+        sessionId: first.sessionId,
+        events,
+      });
+    }
+  }
+}
 
 function emitTransportEvent(payload: TransportEventPayload): void {
+  const batcher = rendererBatchers.get(payload.sessionId);
+  if (payload.kind === "message" && batcher) {
+    batcher.push(payload);
+    return;
+  }
+  // Anything else would overtake the messages still waiting in the batch.
+  batcher?.flush();
+  sendTransportEvents([payload]);
+}
+
+function disposeRendererBatcher(sessionId: string): void {
+  rendererBatchers.get(sessionId)?.flush();
+  rendererBatchers.get(sessionId)?.dispose();
+  rendererBatchers.delete(sessionId);
+}
+
+function emitResumeEvent(payload: TransportResumeEventPayload): void {
   for (const win of BrowserWindow.getAllWindows()) {
-    win.webContents.send("paseo:event:local-daemon-transport-event", payload);
+    win.webContents.send("paseo:event:local-daemon-transport-resume", payload);
   }
 }
 
+function emitBandwidthStats(): void {
+  const stats = getLocalTransportBandwidthStats({ windowMs: BANDWIDTH_EVENT_INTERVAL_MS });
+  for (const win of BrowserWindow.getAllWindows()) {
+    win.webContents.send("paseo:event:local-daemon-bandwidth-stats", stats);
+  }
+  if (!bandwidthMeter.hasOpenConnections()) {
+    stopBandwidthEvents();
+  }
+}
+
+function startBandwidthEvents(): void {
+  bandwidthEventTimer ??= setInterval(
+    emitBandwidthStats,
+    scalePollInterval(BANDWIDTH_EVENT_INTERVAL_MS),
+  );
+}
+
+function stopBandwidthEvents(): void {
+  if (bandwidthEventTimer) {
+    clearInterval(bandwidthEventTimer);
+    bandwidthEventTimer = null;
+  }
+}
+
+onPollIntervalScaleChanged(() => {
+  if (bandwidthEventTimer) {
+    stopBandwidthEvents();
+    startBandwidthEvents();
+  }
+});
+
+function isFilteringStreams(session: Session): boolean {
+  return listSubscribedKinds(session.effectiveStreams).length < STREAM_KINDS.length;
+}
+
 /**
- * Build a WebSocket URL that connects through a Unix domain socket or Windows
- * named pipe.  The `ws` library supports these via the `ws+unix://` scheme:
- *
- *   ws+unix:///path/to/socket:/ws
- *   ws+unix://./pipe/paseo:/ws        (Windows named pipe)
- *
- * The part before `:` is the IPC path, the part after is the HTTP request
- * path used during the WebSocket upgrade handshake.
+ * Recomputes what `session` delivers. Kinds that come back on are announced
+ * so the renderer refetches what was dropped while they were off.
  */
-function buildLocalWebSocketUrl(target: TransportTarget): string {
-  const ipcPath = target.transportPath;
-  return `ws+unix://${ipcPath}:${WS_ENDPOINT_PATH}`;
+function applyStreamSubscriptions(session: Session): void {
+  const next = resolveEffectiveStreams({
+    requested: session.streams,
+    windowsVisible: isAnyWindowVisible(),
+  });
+  const resumed = listResumedKinds(session.effectiveStreams, next).filter(
+    (kind) => kind !== "heartbeats",
+  );
+  session.effectiveStreams = next;
+  if (resumed.length > 0) {
+    emitDesktopEvent("stream-resync", { sessionId: session.id, kinds: resumed });
+  }
+}
+
+onWindowVisibilityChanged(() => {
+  for (const session of sessions.values()) {
+    applyStreamSubscriptions(session);
+  }
+});
+
+function startHeartbeat(session: Session): void {
+  let ticks = 0;
+  const tick = () => {
+    const now = Date.now();
+    session.health.expirePings(now, session.policy.timeouts.heartbeatMs);
+    const health = session.health.snapshot();
+    if (health.status !== session.healthStatus) {
+      session.healthStatus = health.status;
+      for (const win of BrowserWindow.getAllWindows()) {
+        win.webContents.send("paseo:event:local-daemon-connection-health", health);
+      }
+    }
+    if (session.transport.state() !== "open") {
+      return;
+    }
+    ticks += 1;
+    if (!session.effectiveStreams.heartbeats && ticks % BACKGROUND_HEARTBEAT_EVERY !== 0) {
+      return;
+    }
+    const seq = session.health.recordPing(now);
+    const payload = Buffer.alloc(4);
+    payload.writeUInt32BE(seq);
+    session.transport.ping(payload);
+  };
+
+  tick();
+  session.heartbeatTimer = setInterval(tick, HEARTBEAT_INTERVAL_MS);
 }
 
-function describeTransportTarget(target: TransportTarget): string {
+function stopHeartbeat(session: Session): void {
+  if (session.heartbeatTimer) {
+    clearInterval(session.heartbeatTimer);
+    session.heartbeatTimer = null;
+  }
+}
+
+function recordPong(session: Session, data: Buffer): void {
+  if (data.byteLength === 4) {
+    session.health.recordPong(data.readUInt32BE(0), Date.now());
+  }
+}
+
+export function setLocalTransportLowBandwidthMode(enabled: boolean): boolean {
+  lowBandwidthMode = enabled;
+  for (const session of sessions.values()) {
+    session.inbound.setEnabled(enabled);
+  }
+  return lowBandwidthMode;
+}
+
+export function isLocalTransportLowBandwidthMode(): boolean {
+  return lowBandwidthMode;
+}
+
+export function getLocalTransportEventBatching(): LocalTransportEventBatchingState {
+  return {
+    ...eventBatching,
+    catchUpUntil: Date.now() < catchUpUntil ? new Date(catchUpUntil).toISOString() : null,
+  };
+}
+
+/** `intervalMs: 0` turns batching off outside the catch-up window. */
+export function setLocalTransportEventBatching(
+  config: EventBatchingConfig,
+): LocalTransportEventBatchingState {
+  eventBatching = config;
+  return getLocalTransportEventBatching();
+}
+
+/**
+ * A woken machine receives its whole backlog at once; batching it keeps the
+ * renderer from re-rendering per event until the burst has drained.
+ */
+export function startLocalTransportCatchUpWindow(): void {
+  catchUpUntil = Date.now() + CATCH_UP_WINDOW_MS;
+}
+
+/**
+ * Policy of one session, or the defaults new sessions start with when
+ * `connectionId` is left out.
+ */
+export function getLocalTransportPolicy(input?: { connectionId?: unknown }): TransportPolicy {
+  if (typeof input?.connectionId !== "string") {
+    return defaultTransportPolicy;
+  }
+  const session = sessions.get(input.connectionId);
+  if (!session) {
+    throw new Error(`Local transport session not found: ${input.connectionId}`);
+  }
+  return session.policy;
+}
+
+/**
+ * Changes one session's timeouts and retries, or the defaults for sessions
+ * opened from now on. Reconnects and requests already waiting keep theirs.
+ */
+export function setLocalTransportPolicy(
+  args: Record<string, unknown> | undefined,
+): TransportPolicy {
+  const patch = coerceTransportPolicyPatch(args);
+  if (typeof args?.connectionId !== "string") {
+    defaultTransportPolicy = applyTransportPolicyPatch(defaultTransportPolicy, patch);
+    return defaultTransportPolicy;
+  }
+  const session = sessions.get(args.connectionId);
+  if (!session) {
+    throw new Error(`Local transport session not found: ${args.connectionId}`);
+  }
+  session.policy = applyTransportPolicyPatch(session.policy, patch);
+  return session.policy;
+}
+
+function describeStreams(session: Session | null): LocalTransportStreamState {
+  const requested = session?.streams ?? defaultStreams;
+  const windowsVisible = isAnyWindowVisible();
+  return {
+    connectionId: session?.id ?? null,
+    requested: listSubscribedKinds(requested),
+    effective: listSubscribedKinds(
+      session?.effectiveStreams ?? resolveEffectiveStreams({ requested, windowsVisible }),
+    ),
+    windowsVisible,
+  };
+}
+
+/** Stream kinds of one session, or the ones new sessions start with. */
+export function getLocalTransportStreamSubscriptions(input?: {
+  connectionId?: unknown;
+}): LocalTransportStreamState {
+  if (typeof input?.connectionId !== "string") {
+    return describeStreams(null);
+  }
+  const session = sessions.get(input.connectionId);
+  if (!session) {
+    throw new Error(`Local transport session not found: ${input.connectionId}`);
+  }
+  return describeStreams(session);
+}
+
+/**
+ * Chooses which stream kinds a session delivers to the renderer. The daemon
+ * has no per-kind subscriptions, so unwanted frames are dropped here, before
+ * they cost renderer IPC and parsing; heartbeats are sent less often instead.
+ */
+export function setLocalTransportStreamSubscriptions(
+  args: Record<string, unknown> | undefined,
+): LocalTransportStreamState {
+  const streams = coerceStreamKinds(args?.kinds);
+  if (typeof args?.connectionId !== "string") {
+    defaultStreams = streams;
+    return describeStreams(null);
+  }
+  const session = sessions.get(args.connectionId);
+  if (!session) {
+    throw new Error(`Local transport session not found: ${args.connectionId}`);
+  }
+  session.streams = streams;
+  applyStreamSubscriptions(session);
+  return describeStreams(session);
+}
+
+export function getLocalTransportConnectionHealth(input?: {
+  connectionId?: unknown;
+}): ConnectionHealth | ConnectionHealth[] {
+  if (typeof input?.connectionId !== "string") {
+    return [...sessions.values()].map((session) => session.health.snapshot());
+  }
+  const session = sessions.get(input.connectionId);
+  if (!session) {
+    throw new Error(`Local transport session not found: ${input.connectionId}`);
+  }
+  return session.health.snapshot();
+}
+
+export function getLocalTransportCompressionStats(): SessionCompressionStats[] {
+  return [...sessions.values()].map((session) => {
+    const wire = session.transport.wireBytes();
+    const counters = {
+      payloadBytesSent: session.payloadBytesSent,
+      payloadBytesReceived: session.payloadBytesReceived,
+      wireBytesSent: wire?.sent ?? 0,
+      wireBytesReceived: wire?.received ?? 0,
+    };
+    const extensions = session.transport.extensions();
+    return {
+      sessionId: session.id,
+      negotiated: extensions.includes("permessage-deflate"),
+      extensions: extensions.length > 0 ? extensions : null,
+      ...counters,
+      ratio: computeCompressionRatio(counters),
+    };
+  });
+}
+
+export function getLocalTransportBandwidthStats(input?: { windowMs?: unknown }): BandwidthStats {
+  const windowMs =
+    typeof input?.windowMs === "number" && Number.isFinite(input.windowMs)
+      ? input.windowMs
+      : DEFAULT_BANDWIDTH_WINDOW_MS;
+  return bandwidthMeter.getStats({ windowMs, nowMs: Date.now() });
+}
+
+function describeTransportTarget(target: DaemonTransportTarget): string {
   return target.transportType === "pipe" ? "local daemon pipe" : "local daemon socket";
 }
 
@@ -64,92 +473,323 @@ function decodeTransportMessage(input: { text?: string; binaryBase64?: string })
   throw new Error("Local transport send requires text or binary payload.");
 }
 
-export function openLocalTransportSession(target: TransportTarget): Promise<string> {
-  const sessionId = `local-session-${++nextSessionId}`;
-  const url = buildLocalWebSocketUrl(target);
+function sendRaw(session: Session, payload: string | Buffer): Promise<void> {
+  const byteLength = typeof payload === "string" ? Buffer.byteLength(payload) : payload.byteLength;
+  session.payloadBytesSent += byteLength;
+  bandwidthMeter.recordSent(session.id, byteLength, Date.now());
+  return session.transport.send(payload);
+}
 
-  return new Promise((resolve, reject) => {
-    const ws = new WebSocket(url);
-    const session: Session = {
-      id: sessionId,
-      ws,
-      state: "opening",
-    };
-    sessions.set(sessionId, session);
+/** Lets a desktop feature watch every session's frames; returns the unregister function. */
+export function observeLocalTransport(observer: LocalTransportObserver): () => void {
+  return observers.add(observer);
+}
 
-    let openSettled = false;
+// A session closed from the desktop is finalized again when its socket reports the close.
+function notifySessionClosed(session: Session): void {
+  if (session.closeNotified) {
+    return;
+  }
+  session.closeNotified = true;
+  observers.closed(describeSession(session));
+  bandwidthMeter.close(session.id, Date.now());
+}
 
-    const finalizeOpenFailure = (message: string): void => {
-      if (openSettled) {
-        return;
-      }
+function finalizeSessionClose(session: Session, input: { code: number; reason: string }): void {
+  session.state = "closed";
+  sessions.delete(session.id);
+  notifySessionClosed(session);
+  session.pendingOutbound = [];
+  emitTransportEvent({
+    sessionId: session.id,
+    kind: "close",
+    code: input.code,
+    reason: input.reason,
+  });
+  disposeRendererBatcher(session.id);
+}
 
-      openSettled = true;
-      session.state = "closed";
-      sessions.delete(sessionId);
-      reject(new Error(message));
-    };
+function scheduleReconnect(session: Session, input: { code: number; reason: string }): void {
+  if (session.state === "closing" || session.state === "closed") {
+    return;
+  }
+  const { retries } = session.policy;
+  if (session.reconnectAttempt >= retries.maxReconnectAttempts) {
+    emitResumeEvent({
+      sessionId: session.id,
+      status: "failed",
+      attempt: session.reconnectAttempt,
+    });
+    finalizeSessionClose(session, input);
+    return;
+  }
 
-    ws.once("open", () => {
-      openSettled = true;
-      session.state = "open";
-      resolve(sessionId);
-      emitTransportEvent({ sessionId, kind: "open" });
+  const delayMs = computeReconnectDelayMs({
+    attempt: session.reconnectAttempt,
+    baseDelayMs: retries.reconnectBaseDelayMs,
+    maxDelayMs: retries.reconnectMaxDelayMs,
+  });
+  session.reconnectAttempt += 1;
+  session.state = "reconnecting";
+  emitResumeEvent({
+    sessionId: session.id,
+    status: "reconnecting",
+    attempt: session.reconnectAttempt,
+    delayMs,
+  });
+  session.reconnectTimer = setTimeout(() => {
+    session.reconnectTimer = null;
+    if (session.state !== "reconnecting") {
+      return;
+    }
+    connectSessionSocket(session, {
+      onOpen: () => resumeSession(session),
+      onOpenFailure: () => scheduleReconnect(session, input),
     });
+  }, delayMs);
+}
 
-    ws.on("message", (data: RawData, isBinary: boolean) => {
-      if (isBinary || data instanceof Buffer) {
-        const buf = Buffer.isBuffer(data) ? data : Buffer.from(data as ArrayBuffer);
-        emitTransportEvent({
-          sessionId,
-          kind: "message",
-          binaryBase64: buf.toString("base64"),
-        });
-        return;
-      }
+/**
+ * The daemon keeps a client's session alive across socket drops, keyed by the
+ * clientId in its hello. Replaying the renderer's hello reattaches to it, and
+ * the timeline cursors ask only for the rows the renderer has not seen yet.
+ */
+function resumeSession(session: Session): void {
+  const attempt = session.reconnectAttempt;
+  const hello = session.resume.getHelloMessage();
+  const resumeRequests = hello
+    ? session.resume.buildResumeRequests({
+        requestIdPrefix: `${session.id}-resume-${attempt}`,
+        tailLimit: session.catchUpTailLimit ?? undefined,
+      })
+    : [];
+  session.catchUpTailLimit = null;
+  const frames = [...(hello ? [hello] : []), ...resumeRequests, ...session.pendingOutbound];
+  session.pendingOutbound = [];
+  session.reconnectAttempt = 0;
 
-      emitTransportEvent({
-        sessionId,
-        kind: "message",
-        text: data.toString(),
+  // Transports queue sends synchronously, so the replayed hello stays ahead of any
+  // frame the renderer sends after this point.
+  Promise.all(frames.map((frame) => sendRaw(session, frame)))
+    .then(() => {
+      emitResumeEvent({
+        sessionId: session.id,
+        status: "resumed",
+        attempt,
+        resumedAgents: resumeRequests.length,
       });
+      if (hello) {
+        observers.helloDelivered(describeSession(session));
+      }
+    })
+    .catch((error: unknown) => {
+      const message = error instanceof Error ? error.message : String(error);
+      emitTransportEvent({ sessionId: session.id, kind: "error", error: message });
     });
+}
+
+function observeInboundText(session: Session, text: string): void {
+  session.resume.observeInbound(text);
+  observers.inbound(text, describeSession(session));
+  if (session.pendingReplies.size > 0) {
+    const requestId = readReplyRequestId(text);
+    const consume = requestId ? session.pendingReplies.get(requestId) : undefined;
+    if (requestId && consume?.(text)) {
+      session.pendingReplies.delete(requestId);
+      return;
+    }
+  }
+  if (isFilteringStreams(session)) {
+    const kind = classifyInboundText(text);
+    if (kind && !session.effectiveStreams[kind]) {
+      return;
+    }
+  }
+  session.inbound.pushText(text);
+}
 
-    ws.on("close", (code: number, reason?: Buffer | string) => {
-      const shouldEmitClose = session.state === "open" || session.state === "closing";
-      session.state = "closed";
-      sessions.delete(sessionId);
+function connectSessionSocket(
+  session: Session,
+  handlers: { onOpen: () => void; onOpenFailure: (message: string) => void },
+): void {
+  let openSettled = false;
 
-      if (!openSettled) {
-        finalizeOpenFailure(
-          `${describeTransportTarget(target)} closed before the session became ready.`,
-        );
-        return;
-      }
+  const finalizeOpenFailure = (message: string): void => {
+    if (openSettled) {
+      return;
+    }
+    openSettled = true;
+    handlers.onOpenFailure(message);
+  };
+
+  const recordReceived = (byteLength: number): void => {
+    session.payloadBytesReceived += byteLength;
+    bandwidthMeter.recordReceived(session.id, byteLength, Date.now());
+  };
+
+  session.transport = createDaemonTransport(session.transportKind, {
+    target: session.target,
+    authToken: session.authToken,
+    perMessageDeflate: session.perMessageDeflate,
+    connectTimeoutMs: session.policy.timeouts.connectMs,
+    handlers: {
+      onOpen: () => {
+        openSettled = true;
+        session.state = "open";
+        bandwidthMeter.open(session.id, Date.now());
+        startBandwidthEvents();
+        startHeartbeat(session);
+        handlers.onOpen();
+      },
+
+      onText: (text, byteLength) => {
+        recordReceived(byteLength);
+        observeInboundText(session, text);
+      },
+
+      onBinary: (data, byteLength) => {
+        recordReceived(byteLength);
+        if (isFilteringStreams(session)) {
+          const kind = classifyInboundBinary(data);
+          if (kind && !session.effectiveStreams[kind]) {
+            return;
+          }
+        }
+        session.inbound.pushBinary(data);
+      },
+
+      onPong: (data) => recordPong(session, data),
+
+      onClose: (code, reason) => {
+        stopHeartbeat(session);
+        session.inbound.flush();
+
+        if (!openSettled) {
+          finalizeOpenFailure(
+            `${describeTransportTarget(session.target)} closed before the session became ready.`,
+          );
+          return;
+        }
+
+        const closeInfo = { code, reason };
+        if (session.state === "open") {
+          scheduleReconnect(session, closeInfo);
+          return;
+        }
+        if (session.state === "closing") {
+          finalizeSessionClose(session, closeInfo);
+        }
+      },
+
+      onError: (err) => {
+        if (!openSettled) {
+          finalizeOpenFailure(
+            `Failed to connect to ${describeTransportTarget(session.target)}: ${err.message}`,
+          );
+          return;
+        }
+
+        emitTransportEvent({
+          sessionId: session.id,
+          kind: "error",
+          error: err.message,
+        });
+      },
+    },
+  });
+}
+
+function registerAuthTokenUpdates(): void {
+  if (authTokenUpdatesRegistered) {
+    return;
+  }
+  authTokenUpdatesRegistered = true;
+  onDaemonAuthStateChanged(({ serverId }) => {
+    void getDaemonAuthStore()
+      .getToken(serverId)
+      .then((token) => {
+        for (const session of sessions.values()) {
+          if (session.serverId === serverId) {
+            session.authToken = token;
+          }
+        }
+      });
+  });
+}
+
+export function openLocalTransportSession(
+  target: DaemonTransportTarget,
+  options?: {
+    perMessageDeflate?: PerMessageDeflateOptions | false;
+    transportKind?: DaemonTransportKind;
+    serverId?: string | null;
+    authToken?: string | null;
+  },
+): Promise<string> {
+  const sessionId = `local-session-${++nextSessionId}`;
+  registerAuthTokenUpdates();
 
-      if (shouldEmitClose) {
+  return new Promise((resolve, reject) => {
+    rendererBatchers.set(
+      sessionId,
+      createEventBatcher({ deliver: sendTransportEvents, getConfig: resolveEventBatching }),
+    );
+    const inbound = createLowBandwidthFilter({
+      deliverText: (text) => {
+        emitTransportEvent({ sessionId, kind: "message", text });
+      },
+      deliverBinary: (data) => {
         emitTransportEvent({
           sessionId,
-          kind: "close",
-          code,
-          reason: reason ? String(reason) : "",
+          kind: "message",
+          binaryBase64: data.toString("base64"),
         });
-      }
+      },
     });
+    inbound.setEnabled(lowBandwidthMode);
+    const session: Session = {
+      id: sessionId,
+      target,
+      serverId: options?.serverId ?? null,
+      authToken: options?.authToken ?? null,
+      transportKind: options?.transportKind ?? "websocket",
+      perMessageDeflate: options?.perMessageDeflate ?? false,
+      // Assigned by connectSessionSocket before any handler can observe it.
+      transport: null as unknown as DaemonTransport,
+      state: "opening",
+      inbound,
+      payloadBytesSent: 0,
+      payloadBytesReceived: 0,
+      health: createConnectionHealthTracker({ connectionId: sessionId }),
+      healthStatus: "unknown",
+      heartbeatTimer: null,
+      resume: createResumeCursorTracker(),
+      reconnectAttempt: 0,
+      reconnectTimer: null,
+      catchUpTailLimit: null,
+      pendingOutbound: [],
+      pendingReplies: new Map(),
+      closeNotified: false,
+      policy: defaultTransportPolicy,
+      streams: defaultStreams,
+      effectiveStreams: resolveEffectiveStreams({
+        requested: defaultStreams,
+        windowsVisible: isAnyWindowVisible(),
+      }),
+    };
+    sessions.set(sessionId, session);
 
-    ws.on("error", (err: Error) => {
-      if (!openSettled) {
-        finalizeOpenFailure(
-          `Failed to connect to ${describeTransportTarget(target)}: ${err.message}`,
-        );
-        return;
-      }
-
-      emitTransportEvent({
-        sessionId,
-        kind: "error",
-        error: err.message,
-      });
+    connectSessionSocket(session, {
+      onOpen: () => {
+        resolve(sessionId);
+        emitTransportEvent({ sessionId, kind: "open" });
+      },
+      onOpenFailure: (message) => {
+        session.state = "closed";
+        sessions.delete(sessionId);
+        disposeRendererBatcher(sessionId);
+        reject(new Error(message));
+      },
     });
   });
 }
@@ -164,7 +804,23 @@ export async function sendLocalTransportMessage(input: {
     throw new Error(`Local transport session not found: ${input.sessionId}`);
   }
 
-  if (session.state !== "open" || session.ws.readyState !== WebSocket.OPEN) {
+  const payload = decodeTransportMessage(input);
+  const hadHello = session.resume.getHelloMessage() !== null;
+  await observers.screenOutbound(payload, describeSession(session));
+  if (typeof payload === "string") {
+    session.resume.observeOutbound(payload);
+    observers.outbound(payload, describeSession(session));
+  }
+
+  if (session.state === "reconnecting") {
+    if (session.pendingOutbound.length >= MAX_PENDING_OUTBOUND_MESSAGES) {
+      throw new Error("Local transport session is reconnecting.");
+    }
+    session.pendingOutbound.push(payload);
+    return;
+  }
+
+  if (session.state !== "open" || session.transport.state() !== "open") {
     throw new Error(
       session.state === "opening"
         ? "Local transport session is not open yet."
@@ -172,34 +828,164 @@ export async function sendLocalTransportMessage(input: {
     );
   }
 
-  const payload = decodeTransportMessage(input);
-  await new Promise<void>((resolve, reject) => {
-    session.ws.send(payload, (error) => {
-      if (error) {
-        reject(new Error(`Local transport write failed: ${error.message}`));
-        return;
+  await sendRaw(session, payload);
+  if (!hadHello && session.resume.getHelloMessage() !== null) {
+    observers.helloDelivered(describeSession(session));
+  }
+}
+
+/**
+ * Sends a request of the desktop's own over `session` and resolves with the
+ * first reply `parseReply` accepts for its requestId.
+ */
+function sendDesktopRequest<T>(
+  session: Session,
+  input: {
+    requestId: string;
+    frame: string;
+    parseReply: (text: string) => T | null;
+    timeoutMs: number;
+  },
+): Promise<T> {
+  return new Promise((resolve, reject) => {
+    const timer = setTimeout(() => {
+      session.pendingReplies.delete(input.requestId);
+      reject(new Error("The daemon did not answer in time."));
+    }, input.timeoutMs);
+    session.pendingReplies.set(input.requestId, (text) => {
+      const reply = input.parseReply(text);
+      if (reply === null) {
+        return false;
       }
-      resolve();
+      clearTimeout(timer);
+      resolve(reply);
+      return true;
     });
+    sendRaw(session, input.frame).catch((error: unknown) => {
+      clearTimeout(timer);
+      session.pendingReplies.delete(input.requestId);
+      reject(error instanceof Error ? error : new Error(String(error)));
+    });
+  });
+}
+
+function isSessionOpen(session: Session): boolean {
+  return session.state === "open" && session.transport.state() === "open";
+}
+
+function describeSession(session: Session): LocalTransportSessionInfo {
+  return { sessionId: session.id, daemon: session.target.transportPath };
+}
+
+export function listOpenLocalTransportSessions(): LocalTransportSessionInfo[] {
+  return [...sessions.values()].filter(isSessionOpen).map(describeSession);
+}
+
+/** Session `connectionId` if it is open, or the first open session when it is left out. */
+export function findOpenLocalTransportSession(
+  connectionId?: string | null,
+): LocalTransportSessionInfo | null {
+  const session = connectionId
+    ? sessions.get(connectionId)
+    : [...sessions.values()].find(isSessionOpen);
+  return session && isSessionOpen(session) ? describeSession(session) : null;
+}
+
+function requireOpenSession(sessionId: string): Session {
+  const session = sessions.get(sessionId);
+  if (!session || !isSessionOpen(session)) {
+    throw new Error(`Local transport session not found: ${sessionId}`);
+  }
+  return session;
+}
+
+/**
+ * Sends a request of a desktop feature to the daemon behind `sessionId`. The
+ * reply `parseReply` accepts is kept from the renderer.
+ */
+export async function sendLocalDaemonRequest<T>(
+  sessionId: string,
+  input: { requestId: string; frame: string; parseReply: (text: string) => T | null },
+): Promise<T> {
+  const session = requireOpenSession(sessionId);
+  return await sendDesktopRequest(session, {
+    ...input,
+    timeoutMs: session.policy.timeouts.requestMs,
   });
 }
 
+/** Sends a frame of a desktop feature that expects no reply. */
+export async function sendLocalDaemonFrame(sessionId: string, frame: string): Promise<void> {
+  await sendRaw(requireOpenSession(sessionId), frame);
+}
+
+export interface LocalTransportReconnectResult {
+  sessionId: string;
+  status: "resumed" | "failed";
+  error?: string;
+}
+
+/**
+ * After the machine wakes, sockets that look open are often dead and the
+ * heartbeat takes several missed pings to notice. Drops every live session's
+ * socket straight away and resumes it on a fresh one, without backoff.
+ * `catchUpTailLimit` resumes from each agent's latest rows instead of
+ * replaying everything it missed.
+ */
+export function reconnectAllLocalTransportSessions(options?: {
+  catchUpTailLimit?: number;
+}): Promise<LocalTransportReconnectResult[]> {
+  const pending = [...sessions.values()]
+    .filter((session) => session.state === "open" || session.state === "reconnecting")
+    .map(
+      (session) =>
+        new Promise<LocalTransportReconnectResult>((resolve) => {
+          if (session.reconnectTimer) {
+            clearTimeout(session.reconnectTimer);
+            session.reconnectTimer = null;
+          }
+          stopHeartbeat(session);
+          session.inbound.flush();
+          session.transport.abandon();
+          session.state = "reconnecting";
+          session.reconnectAttempt = 0;
+          session.catchUpTailLimit = options?.catchUpTailLimit ?? null;
+          connectSessionSocket(session, {
+            onOpen: () => {
+              resumeSession(session);
+              resolve({ sessionId: session.id, status: "resumed" });
+            },
+            onOpenFailure: (message) => {
+              // Falls back to the regular backoff, which reports its own outcome.
+              scheduleReconnect(session, { code: 1006, reason: message });
+              resolve({ sessionId: session.id, status: "failed", error: message });
+            },
+          });
+        }),
+    );
+  return Promise.all(pending);
+}
+
 export function closeLocalTransportSession(sessionId: string): void {
   const session = sessions.get(sessionId);
   if (!session) return;
 
+  if (session.reconnectTimer) {
+    clearTimeout(session.reconnectTimer);
+    session.reconnectTimer = null;
+  }
   try {
-    if (session.ws.readyState === WebSocket.CONNECTING) {
-      session.state = "closed";
-      session.ws.terminate();
-    } else {
-      session.state = "closing";
-      session.ws.close();
-    }
+    const transportState = session.transport.state();
+    session.state =
+      transportState === "connecting" || transportState === "closed" ? "closed" : "closing";
+    session.transport.close();
   } catch {
     // ignore close errors
   }
   sessions.delete(sessionId);
+  notifySessionClosed(session);
+  stopHeartbeat(session);
+  disposeRendererBatcher(sessionId);
 }
 
 export function closeAllTransportSessions(): void {
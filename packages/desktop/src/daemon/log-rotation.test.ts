@@ -0,0 +1,39 @@
+import { mkdtemp, readFile, rm, writeFile } from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+import { rotateLogFile } from "./log-rotation";
+
+describe("rotateLogFile", () => {
+  let dir: string;
+
+  beforeEach(async () => {
+    dir = await mkdtemp(path.join(os.tmpdir(), "paseo-log-rotation-"));
+  });
+
+  afterEach(async () => {
+    await rm(dir, { recursive: true, force: true });
+  });
+
+  it("leaves small or missing logs alone", async () => {
+    const filePath = path.join(dir, "daemon.log");
+    expect(await rotateLogFile({ filePath, maxBytes: 10, keep: 2 })).toBe(false);
+
+    await writeFile(filePath, "short");
+    expect(await rotateLogFile({ filePath, maxBytes: 10, keep: 2 })).toBe(false);
+    expect(await readFile(filePath, "utf8")).toBe("short");
+  });
+
+  it("shifts archives and truncates the live file in place", async () => {
+    const filePath = path.join(dir, "daemon.log");
+    await writeFile(`${filePath}.1`, "older");
+    await writeFile(`${filePath}.2`, "oldest");
+    await writeFile(filePath, "current log contents");
+
+    expect(await rotateLogFile({ filePath, maxBytes: 10, keep: 2 })).toBe(true);
+
+    expect(await readFile(filePath, "utf8")).toBe("");
+    expect(await readFile(`${filePath}.1`, "utf8")).toBe("current log contents");
+    expect(await readFile(`${filePath}.2`, "utf8")).toBe("older");
+  });
+});
@@ -0,0 +1,30 @@
+import { copyFile, rename, rm, stat, truncate } from "node:fs/promises";
+
+/**
+ * Size-based rotation for a log another process keeps open in append mode.
+ * The live file is copied aside and truncated in place (copytruncate), so
+ * the writer never loses its handle; lines written mid-copy may be dropped.
+ */
+export async function rotateLogFile(input: {
+  filePath: string;
+  maxBytes: number;
+  keep: number;
+}): Promise<boolean> {
+  let size: number;
+  try {
+    size = (await stat(input.filePath)).size;
+  } catch {
+    return false;
+  }
+  if (size < input.maxBytes) {
+    return false;
+  }
+
+  await rm(`${input.filePath}.${input.keep}`, { force: true });
+  for (let index = input.keep - 1; index >= 1; index -= 1) {
+    await rename(`${input.filePath}.${index}`, `${input.filePath}.${index + 1}`).catch(() => {});
+  }
+  await copyFile(input.filePath, `${input.filePath}.1`);
+  await truncate(input.filePath, 0);
+  return true;
+}
@@ -0,0 +1,124 @@
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import {
+  OMITTED_IMAGE_PLACEHOLDER,
+  classifyLowBandwidthText,
+  createLowBandwidthFilter,
+} from "./low-bandwidth-filter";
+
+function agentStreamMessage(event: Record<string, unknown>): string {
+  return JSON.stringify({
+    type: "session",
+    message: {
+      type: "agent_stream",
+      payload: { agentId: "agent-1", event, timestamp: "2026-01-01T00:00:00.000Z" },
+    },
+  });
+}
+
+function createHarness() {
+  const delivered: Array<string | number[]> = [];
+  const filter = createLowBandwidthFilter({
+    deliverText: (text) => delivered.push(text),
+    deliverBinary: (data) => delivered.push([...data]),
+  });
+  return { delivered, filter };
+}
+
+describe("classifyLowBandwidthText", () => {
+  it("replaces inline image data URLs in agent stream events", () => {
+    const text = agentStreamMessage({
+      type: "timeline",
+      item: {
+        type: "tool_call",
+        detail: { type: "read", filePath: "a.png", content: "data:image/png;base64,AAAA" },
+      },
+    });
+
+    const classified = classifyLowBandwidthText(text);
+
+    expect(classified.kind).toBe("timeline");
+    expect(JSON.parse(classified.text).message.payload.event.item.detail.content).toBe(
+      OMITTED_IMAGE_PLACEHOLDER,
+    );
+  });
+
+  it("passes through non-stream and unparsable messages untouched", () => {
+    const response = JSON.stringify({ type: "session", message: { type: "agent_list" } });
+
+    expect(classifyLowBandwidthText(response)).toEqual({ kind: "passthrough", text: response });
+    expect(classifyLowBandwidthText("not json")).toEqual({
+      kind: "passthrough",
+      text: "not json",
+    });
+  });
+});
+
+describe("createLowBandwidthFilter", () => {
+  beforeEach(() => {
+    vi.useFakeTimers();
+  });
+
+  afterEach(() => {
+    vi.useRealTimers();
+  });
+
+  it("delivers immediately while disabled", () => {
+    const { delivered, filter } = createHarness();
+    const timeline = agentStreamMessage({ type: "timeline", item: { type: "reasoning" } });
+
+    filter.pushText(timeline);
+
+    expect(delivered).toEqual([timeline]);
+  });
+
+  it("batches timeline rows at the interval and flushes them before other messages", () => {
+    const { delivered, filter } = createHarness();
+    filter.setEnabled(true);
+    const first = agentStreamMessage({ type: "timeline", item: { type: "reasoning", text: "a" } });
+    const second = agentStreamMessage({ type: "timeline", item: { type: "reasoning", text: "b" } });
+    const status = agentStreamMessage({ type: "turn_completed" });
+
+    filter.pushText(first);
+    filter.pushText(second);
+    expect(delivered).toEqual([]);
+
+    vi.advanceTimersByTime(1_000);
+    expect(delivered).toEqual([first, second]);
+
+    filter.pushText(first);
+    filter.pushText(status);
+    expect(delivered).toEqual([first, second, first, status]);
+  });
+
+  it("coalesces terminal output frames per slot", () => {
+    const { delivered, filter } = createHarness();
+    filter.setEnabled(true);
+
+    filter.pushBinary(Buffer.from([0x01, 2, 104]));
+    filter.pushBinary(Buffer.from([0x01, 2, 105]));
+    filter.pushBinary(Buffer.from([0x01, 3, 33]));
+    expect(delivered).toEqual([]);
+
+    vi.advanceTimersByTime(250);
+    expect(delivered).toEqual([
+      [0x01, 2, 104, 105],
+      [0x01, 3, 33],
+    ]);
+  });
+
+  it("flushes held output for a slot before its snapshot and when disabled", () => {
+    const { delivered, filter } = createHarness();
+    filter.setEnabled(true);
+
+    filter.pushBinary(Buffer.from([0x01, 1, 65]));
+    filter.pushBinary(Buffer.from([0x04, 1, 123]));
+    filter.pushBinary(Buffer.from([0x01, 1, 66]));
+    filter.setEnabled(false);
+
+    expect(delivered).toEqual([
+      [0x01, 1, 65],
+      [0x04, 1, 123],
+      [0x01, 1, 66],
+    ]);
+  });
+});
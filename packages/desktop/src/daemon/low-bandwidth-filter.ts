@@ -0,0 +1,179 @@
+import { isRecord } from "../utils/is-record.js";
+
+const TERMINAL_OUTPUT_OPCODE = 0x01;
+const TERMINAL_SNAPSHOT_OPCODE = 0x04;
+const DEFAULT_TIMELINE_BATCH_MS = 1_000;
+const DEFAULT_TERMINAL_FRAME_INTERVAL_MS = 250;
+const IMAGE_DATA_URL_PATTERN = /^data:image\/[a-z0-9.+-]+;base64,/i;
+export const OMITTED_IMAGE_PLACEHOLDER = "[image omitted in low-bandwidth mode]";
+
+export interface LowBandwidthFilter {
+  setEnabled(enabled: boolean): void;
+  pushText(text: string): void;
+  pushBinary(data: Buffer): void;
+  flush(): void;
+}
+
+interface LowBandwidthFilterOptions {
+  deliverText: (text: string) => void;
+  deliverBinary: (data: Buffer) => void;
+  timelineBatchMs?: number;
+  terminalFrameIntervalMs?: number;
+}
+
+export function stripInlineImages(value: unknown): { value: unknown; stripped: boolean } {
+  if (typeof value === "string") {
+    return IMAGE_DATA_URL_PATTERN.test(value)
+      ? { value: OMITTED_IMAGE_PLACEHOLDER, stripped: true }
+      : { value, stripped: false };
+  }
+  if (Array.isArray(value)) {
+    let stripped = false;
+    const next = value.map((entry) => {
+      const result = stripInlineImages(entry);
+      stripped ||= result.stripped;
+      return result.value;
+    });
+    return stripped ? { value: next, stripped } : { value, stripped };
+  }
+  if (isRecord(value)) {
+    let stripped = false;
+    const next: Record<string, unknown> = {};
+    for (const [key, entry] of Object.entries(value)) {
+      const result = stripInlineImages(entry);
+      stripped ||= result.stripped;
+      next[key] = result.value;
+    }
+    return stripped ? { value: next, stripped } : { value, stripped };
+  }
+  return { value, stripped: false };
+}
+
+type ClassifiedText =
+  | { kind: "timeline"; text: string }
+  | { kind: "passthrough"; text: string };
+
+/**
+ * Agent stream events are the only high-volume text traffic. Timeline rows are
+ * held for batching; inline image previews are replaced with a placeholder
+ * string so the payload still matches the message schema.
+ */
+export function classifyLowBandwidthText(text: string): ClassifiedText {
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(text);
+  } catch {
+    return { kind: "passthrough", text };
+  }
+
+  if (!isRecord(parsed) || parsed.type !== "session" || !isRecord(parsed.message)) {
+    return { kind: "passthrough", text };
+  }
+  const message = parsed.message;
+  if (message.type !== "agent_stream" || !isRecord(message.payload)) {
+    return { kind: "passthrough", text };
+  }
+
+  const event = message.payload.event;
+  const { value: strippedEvent, stripped } = stripInlineImages(event);
+  const nextText = stripped
+    ? JSON.stringify({
+        ...parsed,
+        message: { ...message, payload: { ...message.payload, event: strippedEvent } },
+      })
+    : text;
+  const isTimeline = isRecord(event) && event.type === "timeline";
+  return { kind: isTimeline ? "timeline" : "passthrough", text: nextText };
+}
+
+export function createLowBandwidthFilter(options: LowBandwidthFilterOptions): LowBandwidthFilter {
+  const timelineBatchMs = options.timelineBatchMs ?? DEFAULT_TIMELINE_BATCH_MS;
+  const terminalFrameIntervalMs =
+    options.terminalFrameIntervalMs ?? DEFAULT_TERMINAL_FRAME_INTERVAL_MS;
+  let enabled = false;
+  let pendingTimeline: string[] = [];
+  const pendingTerminalOutput = new Map<number, Buffer[]>();
+  let timelineTimer: NodeJS.Timeout | null = null;
+  let terminalTimer: NodeJS.Timeout | null = null;
+
+  function flushTimeline(): void {
+    if (timelineTimer) {
+      clearTimeout(timelineTimer);
+      timelineTimer = null;
+    }
+    const batch = pendingTimeline;
+    pendingTimeline = [];
+    for (const text of batch) {
+      options.deliverText(text);
+    }
+  }
+
+  function flushTerminalSlot(slot: number): void {
+    const chunks = pendingTerminalOutput.get(slot);
+    if (!chunks) return;
+    pendingTerminalOutput.delete(slot);
+    options.deliverBinary(
+      Buffer.concat([Buffer.from([TERMINAL_OUTPUT_OPCODE, slot]), ...chunks]),
+    );
+  }
+
+  function flushTerminal(): void {
+    if (terminalTimer) {
+      clearTimeout(terminalTimer);
+      terminalTimer = null;
+    }
+    for (const slot of [...pendingTerminalOutput.keys()]) {
+      flushTerminalSlot(slot);
+    }
+  }
+
+  function flush(): void {
+    flushTerminal();
+    flushTimeline();
+  }
+
+  return {
+    setEnabled(next) {
+      enabled = next;
+      if (!enabled) flush();
+    },
+
+    pushText(text) {
+      if (!enabled) {
+        options.deliverText(text);
+        return;
+      }
+      const classified = classifyLowBandwidthText(text);
+      if (classified.kind === "timeline") {
+        pendingTimeline.push(classified.text);
+        timelineTimer ??= setTimeout(flushTimeline, timelineBatchMs);
+        return;
+      }
+      // Keep responses and status updates in order relative to held timeline rows.
+      flushTimeline();
+      options.deliverText(classified.text);
+    },
+
+    pushBinary(data) {
+      if (!enabled || data.byteLength < 2) {
+        options.deliverBinary(data);
+        return;
+      }
+      const opcode = data[0];
+      const slot = data[1];
+      if (opcode !== TERMINAL_OUTPUT_OPCODE) {
+        if (opcode <= TERMINAL_SNAPSHOT_OPCODE) {
+          flushTerminalSlot(slot);
+        }
+        options.deliverBinary(data);
+        return;
+      }
+      const chunks = pendingTerminalOutput.get(slot) ?? [];
+      chunks.push(data.subarray(2));
+      pendingTerminalOutput.set(slot, chunks);
+      terminalTimer ??= setTimeout(flushTerminal, terminalFrameIntervalMs);
+    },
+
+    flush,
+  };
+}
@@ -0,0 +1,169 @@
+import type http from "node:http";
+import type { AddressInfo } from "node:net";
+import { randomUUID } from "node:crypto";
+import { app } from "electron";
+import log from "electron-log/main";
+import {
+  findOpenLocalTransportSession,
+  observeLocalTransport,
+  sendLocalDaemonRequest,
+} from "./local-transport.js";
+import {
+  buildFetchWorkspacesFrame,
+  coerceForwardPortRequest,
+  createPortForwardServer,
+  findScriptRoute,
+  parseWorkspaceScriptsReply,
+  type WorkspaceScriptRoute,
+} from "./port-forward.js";
+
+export interface PortForward {
+  forwardId: string;
+  /** Local transport session whose daemon serves the service. */
+  connectionId: string;
+  remotePort: number;
+  /** Hostname the daemon serves the service under. */
+  hostname: string;
+  scriptName: string;
+  localPort: number;
+  localUrl: string;
+  createdAt: string;
+}
+
+const MAX_WORKSPACE_PAGES = 10;
+
+const forwards = new Map<string, { forward: PortForward; server: http.Server }>();
+let cleanupRegistered = false;
+
+function registerCleanup(): void {
+  if (cleanupRegistered) {
+    return;
+  }
+  cleanupRegistered = true;
+  observeLocalTransport({
+    closed: ({ sessionId }) => {
+      for (const { forward } of forwards.values()) {
+        if (forward.connectionId === sessionId) {
+          stopForward(forward.forwardId);
+        }
+      }
+    },
+  });
+  app.on("will-quit", () => {
+    stopAllPortForwards();
+  });
+}
+
+function stopForward(forwardId: string): boolean {
+  const entry = forwards.get(forwardId);
+  if (!entry) {
+    return false;
+  }
+  forwards.delete(forwardId);
+  entry.server.close();
+  entry.server.closeAllConnections();
+  log.info(`[port-forward] stopped ${entry.forward.localUrl} -> :${entry.forward.remotePort}`);
+  return true;
+}
+
+function listen(server: http.Server, port: number): Promise<number> {
+  return new Promise((resolve, reject) => {
+    server.once("error", reject);
+    server.listen(port, "127.0.0.1", () => {
+      server.off("error", reject);
+      resolve((server.address() as AddressInfo).port);
+    });
+  });
+}
+
+/**
+ * Workspace services the daemon behind `connectionId`, or the first open
+ * session, proxies by hostname, with the session they were looked up on.
+ */
+async function fetchWorkspaceScriptRoutes(input: {
+  connectionId: string | null;
+}): Promise<{ sessionId: string; routes: WorkspaceScriptRoute[] }> {
+  const session = findOpenLocalTransportSession(input.connectionId);
+  if (!session) {
+    throw new Error(
+      input.connectionId
+        ? `Local transport session not found: ${input.connectionId}`
+        : "No daemon is connected.",
+    );
+  }
+  const routes: WorkspaceScriptRoute[] = [];
+  let cursor: string | null = null;
+  for (let page = 0; page < MAX_WORKSPACE_PAGES; page += 1) {
+    const requestId = `desktop-workspaces-${randomUUID()}`;
+    const reply = await sendLocalDaemonRequest(session.sessionId, {
+      requestId,
+      frame: buildFetchWorkspacesFrame({ requestId, cursor }),
+      parseReply: parseWorkspaceScriptsReply,
+    });
+    routes.push(...reply.routes);
+    cursor = reply.nextCursor;
+    if (!cursor) {
+      break;
+    }
+  }
+  return { sessionId: session.sessionId, routes };
+}
+
+/**
+ * Exposes a workspace service of the daemon behind `connectionId` on a
+ * loopback port. Local transport sessions only reach daemons on this machine,
+ * so the forward connects to the service directly; only ports the daemon
+ * reports as workspace services can be forwarded. A forward lives until it is
+ * stopped, its session closes or the app quits.
+ */
+export async function forwardPort(args: Record<string, unknown> | undefined): Promise<PortForward> {
+  const request = coerceForwardPortRequest(args);
+  const { sessionId, routes } = await fetchWorkspaceScriptRoutes({
+    connectionId: request.connectionId,
+  });
+  const existing = [...forwards.values()].find(
+    ({ forward }) =>
+      forward.remotePort === request.remotePort && forward.connectionId === sessionId,
+  );
+  if (existing) {
+    return existing.forward;
+  }
+  const route = findScriptRoute(routes, request.remotePort);
+  if (!route) {
+    throw new Error(`No workspace service is using port ${request.remotePort}.`);
+  }
+
+  registerCleanup();
+  const server = createPortForwardServer({ upstreamPort: route.port });
+  const localPort = await listen(server, request.localPort ?? 0);
+  const forward: PortForward = {
+    forwardId: randomUUID(),
+    connectionId: sessionId,
+    remotePort: request.remotePort,
+    hostname: route.hostname,
+    scriptName: route.scriptName,
+    localPort,
+    localUrl: `http://127.0.0.1:${localPort}`,
+    createdAt: new Date().toISOString(),
+  };
+  forwards.set(forward.forwardId, { forward, server });
+  log.info(`[port-forward] ${forward.localUrl} -> ${route.hostname} (:${request.remotePort})`);
+  return forward;
+}
+
+export function listPortForwards(): PortForward[] {
+  return [...forwards.values()].map(({ forward }) => forward);
+}
+
+export function stopPortForward(args: Record<string, unknown> | undefined): { stopped: boolean } {
+  if (typeof args?.forwardId !== "string") {
+    throw new Error("forwardId must be a string.");
+  }
+  return { stopped: stopForward(args.forwardId) };
+}
+
+export function stopAllPortForwards(): void {
+  for (const forwardId of [...forwards.keys()]) {
+    stopForward(forwardId);
+  }
+}
@@ -0,0 +1,166 @@
+import http, { type Server } from "node:http";
+import type { AddressInfo } from "node:net";
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+import {
+  buildUpgradeRequestHead,
+  coerceForwardPortRequest,
+  createPortForwardServer,
+  findScriptRoute,
+  isLoopbackRequest,
+  parseWorkspaceScriptsReply,
+} from "./port-forward";
+
+function listenOnLoopback(server: Server): Promise<number> {
+  return new Promise((resolve) => {
+    server.listen(0, "127.0.0.1", () => resolve((server.address() as AddressInfo).port));
+  });
+}
+
+describe("coerceForwardPortRequest", () => {
+  it("requires a remote port and leaves the local port and session optional", () => {
+    expect(coerceForwardPortRequest({ remotePort: 3000 })).toEqual({
+      remotePort: 3000,
+      localPort: null,
+      connectionId: null,
+    });
+    expect(
+      coerceForwardPortRequest({ remotePort: 3000, localPort: 4000, connectionId: "s1" }),
+    ).toEqual({ remotePort: 3000, localPort: 4000, connectionId: "s1" });
+    expect(() => coerceForwardPortRequest({ remotePort: 70_000 })).toThrow("remotePort");
+    expect(() => coerceForwardPortRequest({ remotePort: 3000, localPort: 1.5 })).toThrow(
+      "localPort",
+    );
+  });
+});
+
+describe("workspace script routes", () => {
+  it("collects every service with a port and the next page cursor", () => {
+    const text = JSON.stringify({
+      type: "session",
+      message: {
+        type: "fetch_workspaces_response",
+        payload: {
+          requestId: "r1",
+          entries: [
+            {
+              id: "w1",
+              scripts: [
+                {
+                  scriptName: "web",
+                  hostname: "web.repo.localhost",
+                  port: 3000,
+                  lifecycle: "running",
+                },
+                { scriptName: "lint", hostname: "lint.repo.localhost", port: null },
+              ],
+            },
+          ],
+          pageInfo: { nextCursor: "c2", prevCursor: null, hasMore: true },
+        },
+      },
+    });
+    expect(parseWorkspaceScriptsReply(text)).toEqual({
+      routes: [
+        {
+          workspaceId: "w1",
+          scriptName: "web",
+          hostname: "web.repo.localhost",
+          port: 3000,
+          running: true,
+        },
+      ],
+      nextCursor: "c2",
+    });
+    expect(parseWorkspaceScriptsReply('{"type":"session","message":{"type":"pong"}}')).toBeNull();
+  });
+
+  it("prefers the running service when several use the port", () => {
+    const stopped = {
+      workspaceId: "w1",
+      scriptName: "a",
+      hostname: "a",
+      port: 3000,
+      running: false,
+    };
+    const running = { ...stopped, workspaceId: "w2", hostname: "b", running: true };
+    expect(findScriptRoute([stopped, running], 3000)).toBe(running);
+    expect(findScriptRoute([stopped], 3000)).toBe(stopped);
+    expect(findScriptRoute([stopped], 4000)).toBeNull();
+  });
+});
+
+describe("isLoopbackRequest", () => {
+  it("accepts loopback hosts and origins and rejects rebound or cross-site requests", () => {
+    expect(isLoopbackRequest({ host: "127.0.0.1:4000" })).toBe(true);
+    expect(isLoopbackRequest({ host: "localhost:4000", origin: "http://localhost:4000" })).toBe(
+      true,
+    );
+    expect(isLoopbackRequest({ host: "[::1]:4000" })).toBe(true);
+    expect(isLoopbackRequest({ host: "evil.example:4000" })).toBe(false);
+    expect(isLoopbackRequest({ host: "127.0.0.1:4000", origin: "https://evil.example" })).toBe(
+      false,
+    );
+    expect(isLoopbackRequest({ host: "127.0.0.1:4000", origin: "null" })).toBe(false);
+    expect(isLoopbackRequest({})).toBe(false);
+  });
+});
+
+describe("buildUpgradeRequestHead", () => {
+  it("replays the request line and headers unchanged", () => {
+    expect(
+      buildUpgradeRequestHead({
+        method: "GET",
+        url: "/_hmr",
+        rawHeaders: ["Host", "127.0.0.1:4000", "Upgrade", "websocket", "Authorization", "x"],
+      }),
+    ).toBe(
+      "GET /_hmr HTTP/1.1\r\nHost: 127.0.0.1:4000\r\nUpgrade: websocket\r\n" +
+        "Authorization: x\r\n\r\n",
+    );
+  });
+});
+
+describe("createPortForwardServer", () => {
+  let service: Server;
+  let forward: Server;
+  let forwardPort: number;
+
+  beforeEach(async () => {
+    service = http.createServer((req, res) => {
+      res.writeHead(200, { "content-type": "application/json" });
+      const { host, authorization } = req.headers;
+      res.end(JSON.stringify({ host, auth: authorization ?? null, url: req.url }));
+    });
+    const servicePort = await listenOnLoopback(service);
+    forward = createPortForwardServer({ upstreamPort: servicePort });
+    forwardPort = await listenOnLoopback(forward);
+  });
+
+  afterEach(async () => {
+    await new Promise<void>((resolve) => forward.close(() => resolve()));
+    await new Promise<void>((resolve) => service.close(() => resolve()));
+  });
+
+  it("replays local requests to the service without adding credentials", async () => {
+    const response = await fetch(`http://127.0.0.1:${forwardPort}/app?x=1`);
+    expect(await response.json()).toEqual({
+      host: `127.0.0.1:${forwardPort}`,
+      auth: null,
+      url: "/app?x=1",
+    });
+  });
+
+  it("refuses requests from other origins", async () => {
+    const response = await fetch(`http://127.0.0.1:${forwardPort}/`, {
+      headers: { origin: "https://evil.example" },
+    });
+    expect(response.status).toBe(403);
+  });
+
+  it("answers 502 when the service cannot be reached", async () => {
+    await new Promise<void>((resolve) => service.close(() => resolve()));
+    service = http.createServer();
+    const response = await fetch(`http://127.0.0.1:${forwardPort}/`);
+    expect(response.status).toBe(502);
+  });
+});
@@ -0,0 +1,222 @@
+import http, { type RequestOptions } from "node:http";
+import net from "node:net";
+import type { Duplex } from "node:stream";
+import { isRecord } from "../utils/is-record.js";
+
+const MIN_PORT = 1;
+const MAX_PORT = 65_535;
+
+export interface ForwardPortRequest {
+  remotePort: number;
+  /** `null` picks a free port. */
+  localPort: number | null;
+  /** Local transport session to look the route up on; `null` uses the first open one. */
+  connectionId: string | null;
+}
+
+/** A workspace service the daemon proxies by hostname. */
+export interface WorkspaceScriptRoute {
+  workspaceId: string;
+  scriptName: string;
+  hostname: string;
+  port: number;
+  running: boolean;
+}
+
+export interface WorkspaceScriptsPage {
+  routes: WorkspaceScriptRoute[];
+  nextCursor: string | null;
+}
+
+function coercePort(value: unknown, name: string): number {
+  if (
+    typeof value !== "number" ||
+    !Number.isInteger(value) ||
+    value < MIN_PORT ||
+    value > MAX_PORT
+  ) {
+    throw new Error(`${name} must be a port between ${MIN_PORT} and ${MAX_PORT}.`);
+  }
+  return value;
+}
+
+export function coerceForwardPortRequest(
+  args: Record<string, unknown> | undefined,
+): ForwardPortRequest {
+  const connectionId = args?.connectionId;
+  if (connectionId !== undefined && connectionId !== null && typeof connectionId !== "string") {
+    throw new Error("connectionId must be a string.");
+  }
+  return {
+    remotePort: coercePort(args?.remotePort, "remotePort"),
+    localPort:
+      args?.localPort === undefined || args.localPort === null
+        ? null
+        : coercePort(args.localPort, "localPort"),
+    connectionId: connectionId ?? null,
+  };
+}
+
+export function buildFetchWorkspacesFrame(input: {
+  requestId: string;
+  cursor: string | null;
+}): string {
+  return JSON.stringify({
+    type: "session",
+    message: {
+      type: "fetch_workspaces_request",
+      requestId: input.requestId,
+      page: { limit: 200, ...(input.cursor ? { cursor: input.cursor } : {}) },
+    },
+  });
+}
+
+export function parseWorkspaceScriptsReply(text: string): WorkspaceScriptsPage | null {
+  if (!text.includes('"fetch_workspaces_response"')) {
+    return null;
+  }
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(text);
+  } catch {
+    return null;
+  }
+  if (
+    !isRecord(parsed) ||
+    !isRecord(parsed.message) ||
+    parsed.message.type !== "fetch_workspaces_response" ||
+    !isRecord(parsed.message.payload) ||
+    !Array.isArray(parsed.message.payload.entries)
+  ) {
+    return null;
+  }
+  const { entries, pageInfo } = parsed.message.payload;
+  const routes = entries.flatMap((workspace): WorkspaceScriptRoute[] => {
+    if (!isRecord(workspace) || typeof workspace.id !== "string") {
+      return [];
+    }
+    const scripts = Array.isArray(workspace.scripts) ? workspace.scripts : [];
+    return scripts.flatMap((script) =>
+      isRecord(script) &&
+      typeof script.scriptName === "string" &&
+      typeof script.hostname === "string" &&
+      typeof script.port === "number"
+        ? [
+            {
+              workspaceId: workspace.id as string,
+              scriptName: script.scriptName,
+              hostname: script.hostname,
+              port: script.port,
+              running: script.lifecycle === "running",
+            },
+          ]
+        : [],
+    );
+  });
+  const nextCursor =
+    isRecord(pageInfo) && pageInfo.hasMore === true && typeof pageInfo.nextCursor === "string"
+      ? pageInfo.nextCursor
+      : null;
+  return { routes, nextCursor };
+}
+
+/** The running service on `port`, or a stopped one so the forward is ready when it starts. */
+export function findScriptRoute(
+  routes: WorkspaceScriptRoute[],
+  port: number,
+): WorkspaceScriptRoute | null {
+  const matches = routes.filter((route) => route.port === port);
+  return matches.find((route) => route.running) ?? matches[0] ?? null;
+}
+
+const LOOPBACK_HOSTNAMES = new Set(["127.0.0.1", "localhost", "[::1]"]);
+
+function loopbackHostname(value: string): boolean {
+  try {
+    return LOOPBACK_HOSTNAMES.has(new URL(value).hostname);
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * Only the local machine may use a forward: a loopback Host stops DNS
+ * rebinding, and a loopback Origin (when there is one) stops other sites.
+ */
+export function isLoopbackRequest(headers: http.IncomingHttpHeaders): boolean {
+  if (!headers.host || !loopbackHostname(`http://${headers.host}`)) {
+    return false;
+  }
+  return headers.origin === undefined || loopbackHostname(headers.origin);
+}
+
+/** Request head for a forwarded WebSocket upgrade (dev server hot reload). */
+export function buildUpgradeRequestHead(input: {
+  method: string;
+  url: string;
+  rawHeaders: string[];
+}): string {
+  const lines = [`${input.method} ${input.url} HTTP/1.1`];
+  for (let index = 0; index + 1 < input.rawHeaders.length; index += 2) {
+    lines.push(`${input.rawHeaders[index]}: ${input.rawHeaders[index + 1]}`);
+  }
+  return `${lines.join("\r\n")}\r\n\r\n`;
+}
+
+/**
+ * A loopback HTTP server that replays local requests to the workspace service
+ * on `upstreamPort`. The daemon is on this machine, so the service is too.
+ */
+export function createPortForwardServer(input: { upstreamPort: number }): http.Server {
+  const server = http.createServer((req, res) => {
+    if (!isLoopbackRequest(req.headers)) {
+      res.writeHead(403, { "content-type": "text/plain" });
+      res.end("Paseo port forward: only loopback requests are accepted");
+      return;
+    }
+    const options: RequestOptions = {
+      host: "127.0.0.1",
+      port: input.upstreamPort,
+      method: req.method,
+      path: req.url,
+      headers: req.headers,
+    };
+    const upstream = http.request(options, (upstreamRes) => {
+      res.writeHead(upstreamRes.statusCode ?? 502, upstreamRes.headers);
+      upstreamRes.pipe(res);
+    });
+    upstream.on("error", (error) => {
+      if (!res.headersSent) {
+        res.writeHead(502, { "content-type": "text/plain" });
+      }
+      res.end(`Paseo port forward: ${error.message}`);
+    });
+    req.pipe(upstream);
+  });
+
+  server.on("upgrade", (req: http.IncomingMessage, socket: Duplex, head: Buffer) => {
+    if (!isLoopbackRequest(req.headers)) {
+      socket.end("HTTP/1.1 403 Forbidden\r\nConnection: close\r\n\r\n");
+      return;
+    }
+    const upstream = net.connect(input.upstreamPort, "127.0.0.1");
+    upstream.once("connect", () => {
+      upstream.write(
+        buildUpgradeRequestHead({
+          method: req.method ?? "GET",
+          url: req.url ?? "/",
+          rawHeaders: req.rawHeaders,
+        }),
+      );
+      if (head.byteLength > 0) {
+        upstream.write(head);
+      }
+      upstream.pipe(socket);
+      socket.pipe(upstream);
+    });
+    upstream.on("error", () => socket.destroy());
+    socket.on("error", () => upstream.destroy());
+  });
+
+  return server;
+}
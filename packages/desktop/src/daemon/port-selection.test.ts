@@ -0,0 +1,75 @@
+import { createServer, type AddressInfo, type Server } from "node:net";
+import { afterEach, describe, expect, it } from "vitest";
+import {
+  coercePort,
+  findAvailablePort,
+  formatTcpListenAddress,
+  isPortAvailable,
+  parseTcpListenAddress,
+} from "./port-selection";
+
+describe("parseTcpListenAddress", () => {
+  it("parses IPv4, hostname and bracketed IPv6 listen addresses", () => {
+    expect(parseTcpListenAddress("127.0.0.1:6767")).toEqual({ host: "127.0.0.1", port: 6767 });
+    expect(parseTcpListenAddress("localhost:7799")).toEqual({ host: "localhost", port: 7799 });
+    expect(parseTcpListenAddress("[::1]:6767")).toEqual({ host: "::1", port: 6767 });
+  });
+
+  it("ignores unix sockets, pipes and bad ports", () => {
+    expect(parseTcpListenAddress("/tmp/paseo.sock")).toBeNull();
+    expect(parseTcpListenAddress("unix:///tmp/paseo.sock")).toBeNull();
+    expect(parseTcpListenAddress("127.0.0.1:70000")).toBeNull();
+    expect(parseTcpListenAddress(null)).toBeNull();
+  });
+
+  it("round-trips through formatTcpListenAddress", () => {
+    expect(formatTcpListenAddress({ host: "::1", port: 6768 })).toBe("[::1]:6768");
+    expect(formatTcpListenAddress({ host: "127.0.0.1", port: 6768 })).toBe("127.0.0.1:6768");
+  });
+});
+
+describe("coercePort", () => {
+  it("rejects non-integer and out-of-range ports", () => {
+    expect(coercePort(6767)).toBe(6767);
+    expect(() => coercePort(0)).toThrow("Invalid port");
+    expect(() => coercePort("6767")).toThrow("Invalid port");
+  });
+});
+
+describe("findAvailablePort", () => {
+  let server: Server | null = null;
+
+  afterEach(async () => {
+    await new Promise<void>((resolve) => (server ? server.close(() => resolve()) : resolve()));
+    server = null;
+  });
+
+  it("returns the first free port in the range", async () => {
+    const taken = new Set([6767, 6768]);
+    const port = await findAvailablePort({
+      preferred: 6767,
+      isAvailable: async ({ port }) => !taken.has(port),
+    });
+
+    expect(port).toBe(6769);
+  });
+
+  it("returns null when the whole range is taken", async () => {
+    const port = await findAvailablePort({
+      preferred: 6767,
+      range: 3,
+      isAvailable: async () => false,
+    });
+
+    expect(port).toBeNull();
+  });
+
+  it("detects a port bound by another process", async () => {
+    server = createServer();
+    await new Promise<void>((resolve) => server!.listen({ host: "127.0.0.1", port: 0 }, resolve));
+    const { port } = server.address() as AddressInfo;
+
+    expect(await isPortAvailable({ host: "127.0.0.1", port })).toBe(false);
+    expect(await findAvailablePort({ preferred: port, range: 10 })).not.toBe(port);
+  });
+});
@@ -0,0 +1,69 @@
+import { createServer } from "node:net";
+
+const DEFAULT_PORT_RANGE = 20;
+const MAX_PORT = 65_535;
+
+export interface TcpListenAddress {
+  host: string;
+  port: number;
+}
+
+/** `127.0.0.1:6767` / `[::1]:6767`; `null` for unix sockets and pipes. */
+export function parseTcpListenAddress(listen: string | null): TcpListenAddress | null {
+  const match = /^(\[[^\]]+\]|[^:/\s]+):(\d{1,5})$/.exec(listen?.trim() ?? "");
+  if (!match) {
+    return null;
+  }
+  const port = Number(match[2]);
+  if (port < 1 || port > MAX_PORT) {
+    return null;
+  }
+  return { host: match[1]!.replace(/^\[|\]$/g, ""), port };
+}
+
+export function coercePort(value: unknown): number {
+  if (typeof value !== "number" || !Number.isInteger(value) || value < 1 || value > MAX_PORT) {
+    throw new Error(`Invalid port: ${String(value)}`);
+  }
+  return value;
+}
+
+export function formatTcpListenAddress(address: TcpListenAddress): string {
+  return address.host.includes(":")
+    ? `[${address.host}]:${address.port}`
+    : `${address.host}:${address.port}`;
+}
+
+/** Binds and immediately releases the port, the same way the daemon will. */
+export function isPortAvailable(input: { host: string; port: number }): Promise<boolean> {
+  return new Promise((resolve) => {
+    const server = createServer();
+    server.unref();
+    server.once("error", () => resolve(false));
+    server.listen({ host: input.host, port: input.port, exclusive: true }, () => {
+      server.close(() => resolve(true));
+    });
+  });
+}
+
+/**
+ * First free port among `preferred`, `preferred + 1`, … `preferred + range - 1`,
+ * or `null` when the whole range is taken.
+ */
+export async function findAvailablePort(input: {
+  preferred: number;
+  range?: number;
+  host?: string;
+  isAvailable?: (address: TcpListenAddress) => Promise<boolean>;
+}): Promise<number | null> {
+  const host = input.host ?? "127.0.0.1";
+  const range = Math.max(1, Math.floor(input.range ?? DEFAULT_PORT_RANGE));
+  const isAvailable = input.isAvailable ?? isPortAvailable;
+  const last = Math.min(MAX_PORT, input.preferred + range - 1);
+  for (let port = input.preferred; port <= last; port += 1) {
+    if (await isAvailable({ host, port })) {
+      return port;
+    }
+  }
+  return null;
+}
@@ -0,0 +1,70 @@
+import { BrowserWindow, dialog } from "electron";
+import log from "electron-log/main";
+import { getDesktopSettingsStore } from "../settings/desktop-settings-electron.js";
+import { countRunningLocalAgents } from "./agent-interrupts-electron.js";
+import { shouldConfirmQuit, type QuitChoice } from "./quit-lifecycle.js";
+
+const QUIT_CHOICES: QuitChoice[] = ["keep-daemon", "stop-daemon", "cancel"];
+
+let bypassed = false;
+
+function describeRunningWork(runningAgents: number): string {
+  if (runningAgents === 0) {
+    return "The Paseo daemon is running in the background.";
+  }
+  const agents = runningAgents === 1 ? "1 agent is" : `${runningAgents} agents are`;
+  return `${agents} still running.`;
+}
+
+/** For quits the user already asked for elsewhere, such as installing an update. */
+export function skipQuitConfirmation(): void {
+  bypassed = true;
+}
+
+/**
+ * Asks before a quit would stop running agents or leave the daemon behind.
+ * "Don't ask again" keeps the answer as the quit setting from then on.
+ */
+export async function confirmQuitWithRunningWork(input: {
+  isDesktopManagedDaemonRunning: () => boolean;
+}): Promise<QuitChoice | null> {
+  if (bypassed) {
+    return null;
+  }
+  const settings = await getDesktopSettingsStore().get();
+  const runningAgents = countRunningLocalAgents();
+  const daemonRunning = input.isDesktopManagedDaemonRunning();
+  const { confirmQuit } = settings.daemon;
+  if (!shouldConfirmQuit({ confirmQuit, runningAgents, daemonRunning })) {
+    return null;
+  }
+
+  const defaultChoice = settings.daemon.keepRunningAfterQuit ? 0 : 1;
+  const options: Electron.MessageBoxOptions = {
+    type: "warning",
+    title: "Quit Paseo",
+    message: describeRunningWork(runningAgents),
+    detail:
+      "Keeping the daemon running lets agents finish while Paseo is closed. " +
+      "Stopping it ends every running agent.",
+    buttons: ["Quit and Keep Daemon Running", "Quit and Stop Daemon", "Cancel"],
+    defaultId: defaultChoice,
+    cancelId: 2,
+    noLink: true,
+    checkboxLabel: "Don't ask again",
+  };
+  const win = BrowserWindow.getFocusedWindow();
+  const result = win
+    ? await dialog.showMessageBox(win, options)
+    : await dialog.showMessageBox(options);
+  const choice = QUIT_CHOICES[result.response] ?? "cancel";
+
+  if (result.checkboxChecked && choice !== "cancel") {
+    await getDesktopSettingsStore()
+      .patch({ daemon: { confirmQuit: false, keepRunningAfterQuit: choice === "keep-daemon" } })
+      .catch((error: unknown) => {
+        log.warn("[quit] failed to remember the quit choice", error);
+      });
+  }
+  return choice;
+}
@@ -3,6 +3,7 @@ import { describe, expect, it, vi } from "vitest";
 import { DEFAULT_DESKTOP_SETTINGS } from "../settings/desktop-settings";
 import {
   createBeforeQuitHandler,
+  shouldConfirmQuit,
   shouldStopDesktopManagedDaemonOnQuit,
   stopDesktopManagedDaemonOnQuitIfNeeded,
 } from "./quit-lifecycle";
@@ -115,4 +116,66 @@ describe("quit-lifecycle", () => {
     expect(closeTransportSessions).toHaveBeenCalledTimes(2);
     expect(app.exit).toHaveBeenCalledTimes(1);
   });
+
+  it("lets the confirmation choice override the keep-running setting", async () => {
+    const stopDaemon = vi.fn(async () => undefined);
+    const deps = {
+      isDesktopManagedDaemonRunning: () => true,
+      stopDaemon,
+      showShutdownFeedback: vi.fn(),
+    };
+
+    const kept = await stopDesktopManagedDaemonOnQuitIfNeeded({
+      ...deps,
+      settingsStore: { get: async () => SETTINGS_STOP_ON_QUIT },
+      choice: "keep-daemon",
+    });
+    const stopped = await stopDesktopManagedDaemonOnQuitIfNeeded({
+      ...deps,
+      settingsStore: { get: async () => SETTINGS_KEEP_RUNNING },
+      choice: "stop-daemon",
+    });
+
+    expect([kept, stopped]).toEqual([false, true]);
+    expect(stopDaemon).toHaveBeenCalledTimes(1);
+  });
+
+  it("only confirms when agents or the managed daemon would be affected", () => {
+    const idle = { confirmQuit: true, runningAgents: 0, daemonRunning: false };
+
+    expect(shouldConfirmQuit(idle)).toBe(false);
+    expect(shouldConfirmQuit({ ...idle, runningAgents: 2 })).toBe(true);
+    expect(shouldConfirmQuit({ ...idle, daemonRunning: true })).toBe(true);
+    expect(shouldConfirmQuit({ ...idle, runningAgents: 2, confirmQuit: false })).toBe(false);
+  });
+
+  it("stays open when the quit confirmation is cancelled", async () => {
+    const app = { exit: vi.fn() };
+    const closeTransportSessions = vi.fn();
+    const stopDesktopManagedDaemonIfNeeded = vi.fn(async () => false);
+    const confirmQuit = vi
+      .fn<() => Promise<"cancel" | "stop-daemon">>()
+      .mockResolvedValueOnce("cancel")
+      .mockResolvedValueOnce("stop-daemon");
+    const handleBeforeQuit = createBeforeQuitHandler({
+      app,
+      closeTransportSessions,
+      confirmQuit,
+      stopDesktopManagedDaemonIfNeeded,
+      onStopError: vi.fn(),
+    });
+
+    handleBeforeQuit({ preventDefault: vi.fn() });
+    await new Promise((resolve) => setTimeout(resolve, 0));
+
+    expect(confirmQuit).toHaveBeenCalledTimes(1);
+    expect(closeTransportSessions).not.toHaveBeenCalled();
+    expect(app.exit).not.toHaveBeenCalled();
+
+    handleBeforeQuit({ preventDefault: vi.fn() });
+    await vi.waitFor(() => expect(app.exit).toHaveBeenCalledWith(0));
+
+    expect(closeTransportSessions).toHaveBeenCalledTimes(1);
+    expect(stopDesktopManagedDaemonIfNeeded).toHaveBeenCalledWith("stop-daemon");
+  });
 });
@@ -6,6 +6,9 @@ interface QuitLifecycleSettings {
   };
 }
 
+/** Answer from the quit confirmation; `null` when quitting did not need one. */
+export type QuitChoice = "keep-daemon" | "stop-daemon" | "cancel";
+
 interface BeforeQuitEvent {
   preventDefault(): void;
 }
@@ -19,6 +22,8 @@ export interface StopOnQuitDeps {
   isDesktopManagedDaemonRunning: () => boolean;
   stopDaemon: () => Promise<unknown>;
   showShutdownFeedback: () => void;
+  /** What the user picked in the quit confirmation, which overrides the setting. */
+  choice?: QuitChoice | null;
 }
 
 export function shouldStopDesktopManagedDaemonOnQuit(settings: QuitLifecycleSettings): boolean {
@@ -28,10 +33,15 @@ export function shouldStopDesktopManagedDaemonOnQuit(settings: QuitLifecycleSett
 export async function stopDesktopManagedDaemonOnQuitIfNeeded(
   deps: StopOnQuitDeps,
 ): Promise<boolean> {
-  const settings = await deps.settingsStore.get();
-  if (!shouldStopDesktopManagedDaemonOnQuit(settings)) {
+  if (deps.choice === "keep-daemon") {
     return false;
   }
+  if (deps.choice !== "stop-daemon") {
+    const settings = await deps.settingsStore.get();
+    if (!shouldStopDesktopManagedDaemonOnQuit(settings)) {
+      return false;
+    }
+  }
 
   if (!deps.isDesktopManagedDaemonRunning()) {
     return false;
@@ -42,15 +52,30 @@ export async function stopDesktopManagedDaemonOnQuitIfNeeded(
   return true;
 }
 
+/**
+ * Whether quitting deserves a confirmation: something would either be
+ * killed or keep running unseen once the window is gone.
+ */
+export function shouldConfirmQuit(input: {
+  confirmQuit: boolean;
+  runningAgents: number;
+  daemonRunning: boolean;
+}): boolean {
+  return input.confirmQuit && (input.runningAgents > 0 || input.daemonRunning);
+}
+
 export function createBeforeQuitHandler({
   app,
   closeTransportSessions,
+  confirmQuit,
   stopDesktopManagedDaemonIfNeeded,
   onStopError,
 }: {
   app: BeforeQuitApp;
   closeTransportSessions: () => void;
-  stopDesktopManagedDaemonIfNeeded: () => Promise<boolean>;
+  /** Resolves `cancel` to keep the app open. */
+  confirmQuit?: () => Promise<QuitChoice | null>;
+  stopDesktopManagedDaemonIfNeeded: (choice: QuitChoice | null) => Promise<boolean>;
   onStopError: (error: unknown) => void;
 }): (event: BeforeQuitEvent) => void {
   // We always preventDefault on first quit so we can run the async stop
@@ -59,14 +84,12 @@ export function createBeforeQuitHandler({
   // listener is a darwin no-op (macOS convention) and would otherwise
   // veto a re-fired app.quit().
   let quitting = false;
+  let confirming = false;
 
-  return (event) => {
-    closeTransportSessions();
-    if (quitting) return;
+  const quit = (choice: QuitChoice | null) => {
     quitting = true;
-    event.preventDefault();
-
-    void stopDesktopManagedDaemonIfNeeded()
+    closeTransportSessions();
+    void stopDesktopManagedDaemonIfNeeded(choice)
       .catch((error) => {
         onStopError(error);
       })
@@ -74,4 +97,31 @@ export function createBeforeQuitHandler({
         app.exit(0);
       });
   };
+
+  return (event) => {
+    if (quitting) {
+      closeTransportSessions();
+      return;
+    }
+    event.preventDefault();
+    if (!confirmQuit) {
+      quit(null);
+      return;
+    }
+    // A second Cmd+Q while the dialog is up is not an answer to it.
+    if (confirming) return;
+    confirming = true;
+
+    void confirmQuit()
+      .catch((error): QuitChoice | null => {
+        onStopError(error);
+        return null;
+      })
+      .then((choice) => {
+        confirming = false;
+        if (choice !== "cancel") {
+          quit(choice);
+        }
+      });
+  };
 }
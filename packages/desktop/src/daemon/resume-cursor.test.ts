@@ -0,0 +1,90 @@
+import { describe, expect, it } from "vitest";
+import { computeReconnectDelayMs, createResumeCursorTracker } from "./resume-cursor";
+
+function agentStreamMessage(payload: Record<string, unknown>): string {
+  return JSON.stringify({
+    type: "session",
+    message: {
+      type: "agent_stream",
+      payload: {
+        event: { type: "timeline" },
+        timestamp: "2026-01-01T00:00:00.000Z",
+        ...payload,
+      },
+    },
+  });
+}
+
+describe("computeReconnectDelayMs", () => {
+  it("doubles from the base delay and caps at the max", () => {
+    expect(computeReconnectDelayMs({ attempt: 0 })).toBe(250);
+    expect(computeReconnectDelayMs({ attempt: 3 })).toBe(2_000);
+    expect(computeReconnectDelayMs({ attempt: 20 })).toBe(10_000);
+    expect(computeReconnectDelayMs({ attempt: 2, baseDelayMs: 100, maxDelayMs: 300 })).toBe(300);
+  });
+});
+
+describe("createResumeCursorTracker", () => {
+  it("keeps the highest seq per agent and resets on a new epoch", () => {
+    const tracker = createResumeCursorTracker();
+
+    tracker.observeInbound(agentStreamMessage({ agentId: "a", epoch: "e1", seq: 4 }));
+    tracker.observeInbound(agentStreamMessage({ agentId: "a", epoch: "e1", seq: 2 }));
+    tracker.observeInbound(agentStreamMessage({ agentId: "b", epoch: "e1", seq: 9 }));
+    tracker.observeInbound(agentStreamMessage({ agentId: "b", epoch: "e2", seq: 1 }));
+    tracker.observeInbound(agentStreamMessage({ agentId: "c" }));
+
+    expect(tracker.getCursors()).toEqual([
+      { agentId: "a", epoch: "e1", seq: 4 },
+      { agentId: "b", epoch: "e2", seq: 1 },
+    ]);
+  });
+
+  it("remembers the latest hello and ignores other outbound traffic", () => {
+    const tracker = createResumeCursorTracker();
+    const hello = JSON.stringify({ type: "hello", clientId: "client-1", protocolVersion: 1 });
+
+    tracker.observeOutbound(hello);
+    tracker.observeOutbound(JSON.stringify({ type: "session", message: { type: "ping" } }));
+
+    expect(tracker.getHelloMessage()).toBe(hello);
+  });
+
+  it("builds canonical after-cursor timeline requests", () => {
+    const tracker = createResumeCursorTracker();
+    tracker.observeInbound(agentStreamMessage({ agentId: "a", epoch: "e1", seq: 7 }));
+
+    expect(
+      tracker.buildResumeRequests({ requestIdPrefix: "resume" }).map((text) => JSON.parse(text)),
+    ).toEqual([
+      {
+        type: "session",
+        message: {
+          type: "fetch_agent_timeline_request",
+          agentId: "a",
+          requestId: "resume-0",
+          direction: "after",
+          cursor: { epoch: "e1", seq: 7 },
+          limit: 0,
+          projection: "canonical",
+        },
+      },
+    ]);
+  });
+
+  it("asks only for each agent's latest rows when catching up from a snapshot", () => {
+    const tracker = createResumeCursorTracker();
+    tracker.observeInbound(agentStreamMessage({ agentId: "a", epoch: "e1", seq: 7 }));
+    tracker.observeInbound(agentStreamMessage({ agentId: "b", epoch: "e2", seq: 3 }));
+
+    const messages = tracker
+      .buildResumeRequests({ requestIdPrefix: "resume", tailLimit: 25 })
+      .map((text) => JSON.parse(text).message);
+
+    expect(messages).toEqual([
+      expect.objectContaining({ agentId: "a", direction: "tail", limit: 25 }),
+      expect.objectContaining({ agentId: "b", direction: "tail", limit: 25 }),
+    ]);
+    expect(messages.some((message) => "cursor" in message)).toBe(false);
+  });
+});
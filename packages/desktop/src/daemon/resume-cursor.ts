@@ -0,0 +1,109 @@
+import { isRecord } from "../utils/is-record.js";
+
+const DEFAULT_RECONNECT_BASE_DELAY_MS = 250;
+const DEFAULT_RECONNECT_MAX_DELAY_MS = 10_000;
+
+export interface AgentResumeCursor {
+  agentId: string;
+  epoch: string;
+  seq: number;
+}
+
+export interface ResumeCursorTracker {
+  /** Records the highest timeline seq seen per agent from a daemon → renderer frame. */
+  observeInbound(text: string): void;
+  /** Remembers the renderer's hello so a reconnect can reattach to the same daemon session. */
+  observeOutbound(text: string): void;
+  getHelloMessage(): string | null;
+  getCursors(): AgentResumeCursor[];
+  /**
+   * Timeline requests for everything past each cursor, or with `tailLimit`
+   * only each agent's latest rows, which also carry its current status.
+   */
+  buildResumeRequests(input: { requestIdPrefix: string; tailLimit?: number }): string[];
+}
+
+function parseJson(text: string): unknown {
+  try {
+    return JSON.parse(text);
+  } catch {
+    return null;
+  }
+}
+
+export function computeReconnectDelayMs(input: {
+  attempt: number;
+  baseDelayMs?: number;
+  maxDelayMs?: number;
+}): number {
+  const baseDelayMs = input.baseDelayMs ?? DEFAULT_RECONNECT_BASE_DELAY_MS;
+  const maxDelayMs = input.maxDelayMs ?? DEFAULT_RECONNECT_MAX_DELAY_MS;
+  const exponent = Math.max(0, Math.min(input.attempt, 16));
+  return Math.min(maxDelayMs, baseDelayMs * 2 ** exponent);
+}
+
+export function createResumeCursorTracker(): ResumeCursorTracker {
+  const cursors = new Map<string, AgentResumeCursor>();
+  let helloMessage: string | null = null;
+
+  return {
+    observeInbound(text) {
+      // Cheap pre-check so terminal and response traffic skips JSON.parse.
+      if (!text.includes('"agent_stream"')) {
+        return;
+      }
+      const parsed = parseJson(text);
+      if (!isRecord(parsed) || parsed.type !== "session" || !isRecord(parsed.message)) {
+        return;
+      }
+      const message = parsed.message;
+      if (message.type !== "agent_stream" || !isRecord(message.payload)) {
+        return;
+      }
+      const { agentId, epoch, seq } = message.payload;
+      if (typeof agentId !== "string" || typeof epoch !== "string" || typeof seq !== "number") {
+        return;
+      }
+      const current = cursors.get(agentId);
+      if (current && current.epoch === epoch && current.seq >= seq) {
+        return;
+      }
+      cursors.set(agentId, { agentId, epoch, seq });
+    },
+
+    observeOutbound(text) {
+      if (!text.includes('"hello"')) {
+        return;
+      }
+      const parsed = parseJson(text);
+      if (isRecord(parsed) && parsed.type === "hello") {
+        helloMessage = text;
+      }
+    },
+
+    getHelloMessage() {
+      return helloMessage;
+    },
+
+    getCursors() {
+      return [...cursors.values()];
+    },
+
+    buildResumeRequests({ requestIdPrefix, tailLimit }) {
+      return [...cursors.values()].map((cursor, index) =>
+        JSON.stringify({
+          type: "session",
+          message: {
+            type: "fetch_agent_timeline_request",
+            agentId: cursor.agentId,
+            requestId: `${requestIdPrefix}-${index}`,
+            ...(tailLimit === undefined
+              ? { direction: "after", cursor: { epoch: cursor.epoch, seq: cursor.seq }, limit: 0 }
+              : { direction: "tail", limit: tailLimit }),
+            projection: "canonical",
+          },
+        }),
+      );
+    },
+  };
+}
@@ -0,0 +1,96 @@
+import { powerMonitor } from "electron";
+import log from "electron-log/main";
+import { getDaemonAuthStore } from "../auth/daemon-auth-electron.js";
+import type { DaemonAuthState } from "../auth/daemon-auth-store.js";
+import { getDesktopSettingsStore } from "../settings/desktop-settings-electron.js";
+import { emitDesktopEvent } from "../window/desktop-events.js";
+import {
+  reconnectAllLocalTransportSessions,
+  startLocalTransportCatchUpWindow,
+  type LocalTransportReconnectResult,
+} from "./local-transport.js";
+
+const CONNECTIONS_RESTORED_EVENT = "connections-restored";
+// Waking fires resume and unlock-screen close together; reconnect once for both.
+const RESUME_SETTLE_MS = 250;
+
+export interface ConnectionsRestoredPayload {
+  /** How long the machine was suspended, when the suspend was observed. */
+  sleptMs: number | null;
+  /** `snapshot` when the sleep was long enough to skip replaying what agents did meanwhile. */
+  catchUp: "replay" | "snapshot";
+  sessions: LocalTransportReconnectResult[];
+  auth: DaemonAuthState[];
+}
+
+let suspendedAt: number | null = null;
+let settleTimer: NodeJS.Timeout | null = null;
+let running: Promise<void> | null = null;
+
+async function restoreConnections(): Promise<void> {
+  const sleptMs = suspendedAt === null ? null : Date.now() - suspendedAt;
+  suspendedAt = null;
+  startLocalTransportCatchUpWindow();
+  const { transport } = await getDesktopSettingsStore().get();
+  const snapshot =
+    sleptMs !== null &&
+    transport.catchUpAfterSleepMinutes > 0 &&
+    sleptMs > transport.catchUpAfterSleepMinutes * 60_000;
+  const [auth, sessions] = await Promise.all([
+    getDaemonAuthStore()
+      .revalidate()
+      .catch((error: unknown) => {
+        log.warn("[resume] failed to revalidate daemon auth", error);
+        return [];
+      }),
+    reconnectAllLocalTransportSessions(
+      snapshot ? { catchUpTailLimit: transport.catchUpEventsPerAgent } : undefined,
+    ),
+  ]);
+  const failed = sessions.filter((session) => session.status === "failed").length;
+  log.info(
+    `[resume] reconnected ${sessions.length - failed}/${sessions.length} daemon session(s)`,
+  );
+  const payload: ConnectionsRestoredPayload = {
+    sleptMs,
+    catchUp: snapshot ? "snapshot" : "replay",
+    sessions,
+    auth,
+  };
+  emitDesktopEvent(CONNECTIONS_RESTORED_EVENT, payload);
+}
+
+function scheduleRestore(): void {
+  if (settleTimer) {
+    clearTimeout(settleTimer);
+  }
+  settleTimer = setTimeout(() => {
+    settleTimer = null;
+    running ??= restoreConnections()
+      .catch((error: unknown) => {
+        log.warn("[resume] failed to restore daemon connections", error);
+      })
+      .finally(() => {
+        running = null;
+      });
+  }, RESUME_SETTLE_MS);
+}
+
+/**
+ * Re-establishes daemon connections as soon as the machine wakes instead of
+ * waiting for dead sockets to time out, and tells the renderer once when
+ * they are back.
+ */
+export function registerResumeReconnect(): void {
+  powerMonitor.on("suspend", () => {
+    suspendedAt = Date.now();
+  });
+  powerMonitor.on("resume", scheduleRestore);
+  powerMonitor.on("unlock-screen", () => {
+    startLocalTransportCatchUpWindow();
+    // Only a wake that has not been handled yet; a plain lock and unlock keeps its sockets.
+    if (suspendedAt !== null) {
+      scheduleRestore();
+    }
+  });
+}
@@ -73,8 +73,13 @@ function resolveCliPackageInfo(): PackageInfo {
   });
 }
 
-function resolvePackagedAsarPath(): string {
-  return path.join(process.resourcesPath, "app.asar");
+function resolvePackagedAsarPath(resourcesPath = process.resourcesPath): string {
+  return path.join(resourcesPath, "app.asar");
+}
+
+/** The packaged files a daemon runs from; kept together so an older version can be run again. */
+export function resolvePackagedDaemonRuntimeFiles(): string[] {
+  return [resolvePackagedAsarPath(), `${resolvePackagedAsarPath()}.unpacked`];
 }
 
 function resolvePackagedNodeEntrypointRunnerPath(): string {
@@ -103,13 +108,14 @@ export function parseCliPassthroughArgsFromArgv(argv: string[]): string[] | null
   });
 }
 
-export function resolveDaemonRunnerEntrypoint(): NodeEntrypointSpec {
-  if (app.isPackaged) {
+/** `resourcesPath` points at a kept copy of an earlier version's packaged runtime. */
+export function resolveDaemonRunnerEntrypoint(resourcesPath?: string): NodeEntrypointSpec {
+  if (app.isPackaged || resourcesPath) {
     return {
       entryPath: assertPathExists({
-        label: "Bundled daemon runner",
+        label: resourcesPath ? "Kept daemon runner" : "Bundled daemon runner",
         filePath: path.join(
-          resolvePackagedAsarPath(),
+          resolvePackagedAsarPath(resourcesPath),
           "node_modules",
           "@getpaseo",
           "server",
@@ -222,13 +228,20 @@ export function createNodeEntrypointInvocation(input: {
   });
 }
 
+let cliEnvOverlay: Record<string, string> = {};
+
+/** Settings every CLI run needs to reach the daemon, such as its TLS files. */
+export function setCliEnvOverlay(env: Record<string, string>): void {
+  cliEnvOverlay = env;
+}
+
 function createCliInvocation(args: string[]): NodeEntrypointInvocation {
   const cli = resolveCliEntrypoint();
   return createNodeEntrypointInvocation({
     entrypoint: cli,
     argvMode: "node-script",
     args,
-    baseEnv: process.env,
+    baseEnv: { ...process.env, ...cliEnvOverlay },
   });
 }
 
@@ -0,0 +1,93 @@
+import { describe, expect, it } from "vitest";
+import {
+  ALL_STREAMS,
+  classifyInboundBinary,
+  classifyInboundText,
+  coerceStreamKinds,
+  listResumedKinds,
+  listSubscribedKinds,
+  resolveEffectiveStreams,
+} from "./stream-subscriptions";
+
+function sessionMessage(message: unknown): string {
+  return JSON.stringify({ type: "session", message });
+}
+
+describe("coerceStreamKinds", () => {
+  it("turns off every kind that is not listed", () => {
+    expect(coerceStreamKinds(["heartbeats", "file_diffs"])).toEqual({
+      terminal_output: false,
+      token_deltas: false,
+      file_diffs: true,
+      heartbeats: true,
+    });
+    expect(coerceStreamKinds([])).toEqual({
+      terminal_output: false,
+      token_deltas: false,
+      file_diffs: false,
+      heartbeats: false,
+    });
+  });
+
+  it("rejects unknown kinds", () => {
+    expect(() => coerceStreamKinds("heartbeats")).toThrow("kinds must be an array.");
+    expect(() => coerceStreamKinds(["heartbeats", "logs"])).toThrow("Unknown stream kind: logs");
+  });
+});
+
+describe("resolveEffectiveStreams", () => {
+  it("keeps the requested kinds while a window is visible", () => {
+    const requested = coerceStreamKinds(["terminal_output", "heartbeats"]);
+    expect(resolveEffectiveStreams({ requested, windowsVisible: true })).toBe(requested);
+  });
+
+  it("downgrades every stream while all windows are hidden", () => {
+    const effective = resolveEffectiveStreams({ requested: ALL_STREAMS, windowsVisible: false });
+    expect(listSubscribedKinds(effective)).toEqual([]);
+  });
+
+  it("reports the kinds that come back on", () => {
+    const hidden = resolveEffectiveStreams({ requested: ALL_STREAMS, windowsVisible: false });
+    expect(listResumedKinds(hidden, ALL_STREAMS)).toEqual([
+      "terminal_output",
+      "token_deltas",
+      "file_diffs",
+      "heartbeats",
+    ]);
+    expect(listResumedKinds(ALL_STREAMS, hidden)).toEqual([]);
+  });
+});
+
+describe("classifying inbound frames", () => {
+  it("recognizes timeline deltas and diff updates", () => {
+    expect(
+      classifyInboundText(
+        sessionMessage({
+          type: "agent_stream",
+          payload: { agentId: "a1", event: { type: "timeline", item: {} } },
+        }),
+      ),
+    ).toBe("token_deltas");
+    expect(
+      classifyInboundText(sessionMessage({ type: "checkout_diff_update", payload: {} })),
+    ).toBe("file_diffs");
+  });
+
+  it("leaves other traffic unclassified so it is always delivered", () => {
+    expect(
+      classifyInboundText(
+        sessionMessage({
+          type: "agent_stream",
+          payload: { agentId: "a1", event: { type: "permission_requested" } },
+        }),
+      ),
+    ).toBeNull();
+    expect(classifyInboundText(sessionMessage({ type: "agent_update", payload: {} }))).toBeNull();
+    expect(classifyInboundText('"agent_stream" not json')).toBeNull();
+  });
+
+  it("treats terminal output frames as terminal output but not snapshots", () => {
+    expect(classifyInboundBinary(Buffer.from([0x01, 0x00, 0x41]))).toBe("terminal_output");
+    expect(classifyInboundBinary(Buffer.from([0x04, 0x00]))).toBeNull();
+  });
+});
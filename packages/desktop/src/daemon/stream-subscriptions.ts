@@ -0,0 +1,113 @@
+import { isRecord } from "../utils/is-record.js";
+
+const TERMINAL_OUTPUT_OPCODE = 0x01;
+
+/**
+ * Daemon traffic a client can do without for a while. Anything outside these
+ * kinds, such as agent state, permissions and replies, is always delivered.
+ */
+export const STREAM_KINDS = [
+  "terminal_output",
+  "token_deltas",
+  "file_diffs",
+  "heartbeats",
+] as const;
+
+export type StreamKind = (typeof STREAM_KINDS)[number];
+
+export type StreamSubscriptions = Record<StreamKind, boolean>;
+
+export const ALL_STREAMS: StreamSubscriptions = {
+  terminal_output: true,
+  token_deltas: true,
+  file_diffs: true,
+  heartbeats: true,
+};
+
+/**
+ * What a session keeps while no window is visible: the renderer catches up
+ * on what it missed once a window comes back.
+ */
+export const BACKGROUND_STREAMS: StreamSubscriptions = {
+  terminal_output: false,
+  token_deltas: false,
+  file_diffs: false,
+  heartbeats: false,
+};
+
+function isStreamKind(value: unknown): value is StreamKind {
+  return typeof value === "string" && (STREAM_KINDS as readonly string[]).includes(value);
+}
+
+/** `kinds` lists the streams to receive; every kind left out is turned off. */
+export function coerceStreamKinds(value: unknown): StreamSubscriptions {
+  if (!Array.isArray(value)) {
+    throw new Error("kinds must be an array.");
+  }
+  const unknown = value.find((kind) => !isStreamKind(kind));
+  if (unknown !== undefined) {
+    throw new Error(`Unknown stream kind: ${String(unknown)}`);
+  }
+  return Object.fromEntries(
+    STREAM_KINDS.map((kind) => [kind, value.includes(kind)]),
+  ) as StreamSubscriptions;
+}
+
+export function listSubscribedKinds(subscriptions: StreamSubscriptions): StreamKind[] {
+  return STREAM_KINDS.filter((kind) => subscriptions[kind]);
+}
+
+/** Requested kinds, narrowed to the background set while every window is hidden. */
+export function resolveEffectiveStreams(input: {
+  requested: StreamSubscriptions;
+  windowsVisible: boolean;
+}): StreamSubscriptions {
+  if (input.windowsVisible) {
+    return input.requested;
+  }
+  return Object.fromEntries(
+    STREAM_KINDS.map((kind) => [kind, input.requested[kind] && BACKGROUND_STREAMS[kind]]),
+  ) as StreamSubscriptions;
+}
+
+/** Kinds that were off in `previous` and are on in `next`, so the renderer must resync them. */
+export function listResumedKinds(
+  previous: StreamSubscriptions,
+  next: StreamSubscriptions,
+): StreamKind[] {
+  return STREAM_KINDS.filter((kind) => !previous[kind] && next[kind]);
+}
+
+export function classifyInboundText(text: string): StreamKind | null {
+  // Cheap pre-check so other traffic skips JSON.parse.
+  if (!text.includes('"agent_stream"') && !text.includes('"checkout_diff_update"')) {
+    return null;
+  }
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(text);
+  } catch {
+    return null;
+  }
+  if (!isRecord(parsed) || parsed.type !== "session" || !isRecord(parsed.message)) {
+    return null;
+  }
+  const message = parsed.message;
+  if (message.type === "checkout_diff_update") {
+    return "file_diffs";
+  }
+  if (
+    message.type === "agent_stream" &&
+    isRecord(message.payload) &&
+    isRecord(message.payload.event) &&
+    message.payload.event.type === "timeline"
+  ) {
+    return "token_deltas";
+  }
+  return null;
+}
+
+export function classifyInboundBinary(data: Buffer): StreamKind | null {
+  // Snapshots (opcode 0x04) always pass: they are how a terminal catches up.
+  return data[0] === TERMINAL_OUTPUT_OPCODE ? "terminal_output" : null;
+}
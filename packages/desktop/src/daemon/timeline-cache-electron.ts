@@ -0,0 +1,176 @@
+import { randomUUID } from "node:crypto";
+import { app } from "electron";
+import log from "electron-log/main";
+import { isFeatureEnabled } from "../features/feature-flags-electron.js";
+import {
+  findOpenLocalTransportSession,
+  observeLocalTransport,
+  sendLocalDaemonRequest,
+} from "./local-transport.js";
+import {
+  buildFetchTimelineFrame,
+  createTimelineCache,
+  parseTimelineReply,
+  readTimelineCacheFile,
+  writeTimelineCacheFile,
+  type CachedAgentTimeline,
+  type TimelineReply,
+  type TimelineSyncCursor,
+} from "./timeline-cache.js";
+
+export interface PrefetchTimelineResult {
+  agentId: string;
+  /** `offline` when no daemon session is open to fetch from. */
+  status: "fetched" | "fresh" | "offline" | "failed";
+  entries: number;
+  error?: string;
+}
+
+const PREFETCH_TAIL_LIMIT = 200;
+// Hovering back and forth over the sidebar should not refetch the same agent.
+const PREFETCH_FRESH_MS = 30_000;
+// Past this many pages an agent is further behind than its tail is worth paging through.
+const TIMELINE_SYNC_MAX_PAGES = 5;
+const TIMELINE_CACHE_SAVE_DELAY_MS = 2_000;
+
+const timelineCache = createTimelineCache();
+const inflightPrefetches = new Map<string, Promise<PrefetchTimelineResult>>();
+let timelineCacheRestored: Promise<void> | null = null;
+let timelineCacheSaveTimer: NodeJS.Timeout | null = null;
+let timelineSyncRunning = false;
+
+function requestAgentTimeline(
+  sessionId: string,
+  input: { agentId: string; after?: { epoch: string; seq: number } },
+): Promise<TimelineReply> {
+  const requestId = `desktop-timeline-${randomUUID()}`;
+  return sendLocalDaemonRequest(sessionId, {
+    requestId,
+    frame: buildFetchTimelineFrame({ ...input, requestId, limit: PREFETCH_TAIL_LIMIT }),
+    parseReply: parseTimelineReply,
+  });
+}
+
+/** The cache outlives the app only while the `timelineCache` flag is on. */
+function restoreTimelineCache(): Promise<void> {
+  timelineCacheRestored ??= (
+    isFeatureEnabled("timelineCache")
+      ? readTimelineCacheFile(app.getPath("userData"))
+      : Promise.resolve([])
+  ).then((timelines) => timelineCache.restore(timelines));
+  return timelineCacheRestored;
+}
+
+function scheduleTimelineCacheSave(): void {
+  if (!isFeatureEnabled("timelineCache") || timelineCacheSaveTimer) {
+    return;
+  }
+  timelineCacheSaveTimer = setTimeout(() => {
+    timelineCacheSaveTimer = null;
+    writeTimelineCacheFile(app.getPath("userData"), timelineCache.list()).catch(
+      (error: unknown) => {
+        log.warn("[timeline-cache] failed to save", error);
+      },
+    );
+  }, TIMELINE_CACHE_SAVE_DELAY_MS);
+  timelineCacheSaveTimer.unref();
+}
+
+async function fetchAgentTimelineTail(
+  sessionId: string,
+  agentId: string,
+): Promise<PrefetchTimelineResult> {
+  try {
+    const reply = await requestAgentTimeline(sessionId, { agentId });
+    if (reply.error !== null) {
+      timelineCache.delete(agentId);
+      return { agentId, status: "failed", entries: 0, error: reply.error };
+    }
+    timelineCache.apply(reply, new Date());
+    scheduleTimelineCacheSave();
+    return { agentId, status: "fetched", entries: reply.entries.length };
+  } catch (error) {
+    const message = error instanceof Error ? error.message : String(error);
+    return { agentId, status: "failed", entries: 0, error: message };
+  }
+}
+
+/**
+ * Brings one cached agent up to date by asking for the rows after its cursor.
+ * A new epoch, a gap or a cursor the daemon no longer holds falls back to the tail.
+ */
+async function syncAgentTimeline(sessionId: string, cursor: TimelineSyncCursor): Promise<void> {
+  let after = { epoch: cursor.epoch, seq: cursor.seq };
+  for (let page = 0; page < TIMELINE_SYNC_MAX_PAGES; page += 1) {
+    const reply = await requestAgentTimeline(sessionId, { agentId: cursor.agentId, after });
+    if (timelineCache.apply(reply, new Date()) === "needs-tail") {
+      break;
+    }
+    scheduleTimelineCacheSave();
+    const synced = timelineCache.get(cursor.agentId);
+    if (!reply.hasNewer || !synced) {
+      return;
+    }
+    after = { epoch: synced.epoch, seq: synced.endSeq };
+  }
+  await fetchAgentTimelineTail(sessionId, cursor.agentId);
+}
+
+/**
+ * Runs once the daemon has the renderer's hello, at launch and after each
+ * resume, so cached agents are caught up from their cursors instead of refetched.
+ */
+function syncTimelineCache(sessionId: string): void {
+  if (timelineSyncRunning) {
+    return;
+  }
+  timelineSyncRunning = true;
+  void restoreTimelineCache()
+    .then(async () => {
+      const cursors = timelineCache.syncCursors();
+      const results = await Promise.allSettled(
+        cursors.map((cursor) => syncAgentTimeline(sessionId, cursor)),
+      );
+      const failed = results.filter((result) => result.status === "rejected").length;
+      log.info(`[timeline-cache] synced ${cursors.length - failed} of ${cursors.length} agents`);
+    })
+    .catch((error: unknown) => {
+      log.warn("[timeline-cache] sync failed", error);
+    })
+    .finally(() => {
+      timelineSyncRunning = false;
+    });
+}
+
+/** Catches cached timelines up whenever a renderer's session reaches the daemon. */
+export function startTimelineCache(): void {
+  observeLocalTransport({ helloDelivered: ({ sessionId }) => syncTimelineCache(sessionId) });
+}
+
+/**
+ * Loads an agent's latest timeline rows into the desktop cache ahead of the
+ * renderer opening it. Concurrent calls for one agent share a request.
+ */
+export function prefetchLocalAgentTimeline(agentId: string): Promise<PrefetchTimelineResult> {
+  const cached = timelineCache.get(agentId);
+  if (cached && Date.now() - Date.parse(cached.fetchedAt) < PREFETCH_FRESH_MS) {
+    return Promise.resolve({ agentId, status: "fresh", entries: cached.entries.length });
+  }
+  const inflight = inflightPrefetches.get(agentId);
+  if (inflight) {
+    return inflight;
+  }
+  const session = findOpenLocalTransportSession();
+  if (!session) {
+    return Promise.resolve({ agentId, status: "offline", entries: 0 });
+  }
+  const pending = fetchAgentTimelineTail(session.sessionId, agentId).finally(() => {
+    inflightPrefetches.delete(agentId);
+  });
+  inflightPrefetches.set(agentId, pending);
+  return pending;
+}
+
+export function getCachedLocalAgentTimeline(agentId: string): CachedAgentTimeline | null {
+  return timelineCache.get(agentId);
+}
@@ -0,0 +1,180 @@
+import { mkdtemp, readFile, rm, writeFile } from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+import {
+  buildFetchTimelineFrame,
+  createTimelineCache,
+  parseTimelineReply,
+  readTimelineCacheFile,
+  writeTimelineCacheFile,
+  type TimelineReply,
+} from "./timeline-cache";
+
+const NOW = new Date("2026-06-01T12:00:00.000Z");
+
+function entry(seq: number) {
+  return { provider: "claude", item: { type: "assistant_message" }, seqStart: seq, seqEnd: seq };
+}
+
+function reply(overrides: Partial<TimelineReply>): TimelineReply {
+  return {
+    requestId: "r1",
+    agentId: "a1",
+    agent: null,
+    direction: "tail",
+    epoch: "e1",
+    discontinuous: false,
+    entries: [],
+    hasOlder: false,
+    hasNewer: false,
+    error: null,
+    ...overrides,
+  };
+}
+
+describe("timeline frames", () => {
+  it("asks for the tail, or for the rows after a cursor", () => {
+    expect(
+      JSON.parse(buildFetchTimelineFrame({ agentId: "a1", requestId: "r1", limit: 50 })),
+    ).toEqual({
+      type: "session",
+      message: {
+        type: "fetch_agent_timeline_request",
+        agentId: "a1",
+        requestId: "r1",
+        direction: "tail",
+        limit: 50,
+        projection: "canonical",
+      },
+    });
+    const after = JSON.parse(
+      buildFetchTimelineFrame({
+        agentId: "a1",
+        requestId: "r2",
+        limit: 50,
+        after: { epoch: "e1", seq: 9 },
+      }),
+    );
+    expect(after.message).toMatchObject({ direction: "after", cursor: { epoch: "e1", seq: 9 } });
+  });
+
+  it("reads a timeline response and folds reset, stale and gap into one flag", () => {
+    const text = JSON.stringify({
+      type: "session",
+      message: {
+        type: "fetch_agent_timeline_response",
+        payload: {
+          requestId: "r1",
+          agentId: "a1",
+          agent: { id: "a1" },
+          direction: "after",
+          epoch: "e1",
+          reset: false,
+          staleCursor: true,
+          gap: false,
+          entries: [entry(3), { broken: true }],
+          hasOlder: true,
+          hasNewer: true,
+          error: null,
+        },
+      },
+    });
+    expect(parseTimelineReply(text)).toEqual(
+      reply({
+        agent: { id: "a1" },
+        direction: "after",
+        discontinuous: true,
+        entries: [entry(3)],
+        hasOlder: true,
+        hasNewer: true,
+      }),
+    );
+    expect(parseTimelineReply('{"type":"session","message":{"type":"pong"}}')).toBeNull();
+    expect(parseTimelineReply('"fetch_agent_timeline_response"{')).toBeNull();
+  });
+});
+
+describe("createTimelineCache", () => {
+  it("replaces on a tail and appends rows past the cached end", () => {
+    const cache = createTimelineCache();
+    expect(cache.apply(reply({ entries: [entry(1), entry(2)] }), NOW)).toBe("replaced");
+    expect(
+      cache.apply(reply({ direction: "after", entries: [entry(2), entry(3)] }), NOW),
+    ).toBe("appended");
+    expect(cache.get("a1")).toMatchObject({
+      endSeq: 3,
+      entries: [entry(1), entry(2), entry(3)],
+      fetchedAt: NOW.toISOString(),
+    });
+  });
+
+  it("asks for a tail when an incremental reply cannot continue the cache", () => {
+    const cache = createTimelineCache();
+    expect(cache.apply(reply({ direction: "after" }), NOW)).toBe("needs-tail");
+    cache.apply(reply({ entries: [entry(1)] }), NOW);
+    expect(cache.apply(reply({ direction: "after", epoch: "e2" }), NOW)).toBe("needs-tail");
+    expect(cache.apply(reply({ direction: "after", discontinuous: true }), NOW)).toBe(
+      "needs-tail",
+    );
+    expect(cache.get("a1")?.entries).toEqual([entry(1)]);
+  });
+
+  it("keeps the newest rows per agent and evicts the least recently used agent", () => {
+    const cache = createTimelineCache({ maxAgents: 2, maxEntriesPerAgent: 2 });
+    cache.apply(reply({ agentId: "a1", entries: [entry(1), entry(2), entry(3)] }), NOW);
+    expect(cache.get("a1")).toMatchObject({ entries: [entry(2), entry(3)], hasOlder: true });
+
+    cache.apply(reply({ agentId: "a2" }), NOW);
+    cache.get("a1");
+    cache.apply(reply({ agentId: "a3" }), NOW);
+    expect(cache.list().map((timeline) => timeline.agentId)).toEqual(["a1", "a3"]);
+  });
+});
+
+describe("timeline sync", () => {
+  let userDataPath: string;
+
+  beforeEach(async () => {
+    userDataPath = await mkdtemp(path.join(os.tmpdir(), "paseo-timeline-cache-"));
+  });
+
+  afterEach(async () => {
+    await rm(userDataPath, { recursive: true, force: true });
+  });
+
+  it("resumes each agent from the end of what was cached", () => {
+    const cache = createTimelineCache();
+    cache.apply(reply({ agentId: "a1", entries: [entry(4), entry(7)] }), NOW);
+    cache.apply(reply({ agentId: "a2", epoch: "e9" }), NOW);
+    expect(cache.syncCursors()).toEqual([
+      { agentId: "a1", epoch: "e1", seq: 7 },
+      { agentId: "a2", epoch: "e9", seq: 0 },
+    ]);
+  });
+
+  it("restores saved timelines and drops any that fail their content hash", async () => {
+    const cache = createTimelineCache();
+    cache.apply(reply({ agentId: "a1", entries: [entry(1)] }), NOW);
+    cache.apply(reply({ agentId: "a2", entries: [entry(2)] }), NOW);
+    await writeTimelineCacheFile(userDataPath, cache.list());
+
+    const filePath = path.join(userDataPath, "timeline-cache.json");
+    const saved = JSON.parse(await readFile(filePath, "utf8"));
+    saved.timelines[1].entries.push(entry(3));
+    await writeFile(filePath, JSON.stringify(saved));
+
+    const restored = createTimelineCache();
+    restored.apply(reply({ agentId: "a1", entries: [entry(1), entry(5)] }), NOW);
+    restored.restore(await readTimelineCacheFile(userDataPath));
+    expect(restored.list().map((timeline) => [timeline.agentId, timeline.endSeq])).toEqual([
+      ["a1", 5],
+    ]);
+  });
+
+  it("reads a missing or malformed file as an empty cache", async () => {
+    expect(await readTimelineCacheFile(userDataPath)).toEqual([]);
+    await writeFile(path.join(userDataPath, "timeline-cache.json"), "{");
+    expect(await readTimelineCacheFile(userDataPath)).toEqual([]);
+  });
+});
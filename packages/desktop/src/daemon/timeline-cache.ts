@@ -0,0 +1,296 @@
+import { createHash } from "node:crypto";
+import { readFile } from "node:fs/promises";
+import path from "node:path";
+import { writeJsonConfigFile } from "../settings/config-file.js";
+import { isRecord } from "../utils/is-record.js";
+
+const TIMELINE_CACHE_FILENAME = "timeline-cache.json";
+const DEFAULT_MAX_AGENTS = 32;
+const DEFAULT_MAX_ENTRIES_PER_AGENT = 500;
+
+/** One row of a `fetch_agent_timeline_response`, kept as the daemon sent it. */
+export interface TimelineEntry extends Record<string, unknown> {
+  seqStart: number;
+  seqEnd: number;
+}
+
+export interface TimelineReply {
+  requestId: string;
+  agentId: string;
+  agent: unknown;
+  direction: "tail" | "before" | "after";
+  epoch: string;
+  /** The daemon could not continue from the cursor; the cache must start over from a tail. */
+  discontinuous: boolean;
+  entries: TimelineEntry[];
+  hasOlder: boolean;
+  /** More rows follow the last entry; an `after` sync keeps paging until this is false. */
+  hasNewer: boolean;
+  error: string | null;
+}
+
+export interface CachedAgentTimeline {
+  agentId: string;
+  /** Agent snapshot from the latest reply. */
+  agent: unknown;
+  epoch: string;
+  /** Highest seq the entries cover; where an incremental fetch continues from. */
+  endSeq: number;
+  hasOlder: boolean;
+  entries: TimelineEntry[];
+  fetchedAt: string;
+  /** Digest of the epoch and entries, checked when the cache is read back from disk. */
+  contentHash: string;
+}
+
+/** Where a sync asks the daemon to continue from for one cached agent. */
+export interface TimelineSyncCursor {
+  agentId: string;
+  epoch: string;
+  seq: number;
+}
+
+interface PersistedTimelineCacheDocument {
+  version: 1;
+  timelines: CachedAgentTimeline[];
+}
+
+export type TimelineApplyResult = "replaced" | "appended" | "needs-tail";
+
+export interface TimelineCache {
+  get(agentId: string): CachedAgentTimeline | null;
+  list(): CachedAgentTimeline[];
+  /**
+   * A tail replaces what is cached; an `after` reply is appended when it
+   * continues the cached epoch, and otherwise asks for a fresh tail.
+   */
+  apply(reply: TimelineReply, now: Date): TimelineApplyResult;
+  delete(agentId: string): boolean;
+  /** Loads timelines read from disk, oldest first, without replacing newer fetches. */
+  restore(timelines: CachedAgentTimeline[]): void;
+  syncCursors(): TimelineSyncCursor[];
+}
+
+function isTimelineEntry(value: unknown): value is TimelineEntry {
+  return isRecord(value) && typeof value.seqStart === "number" && typeof value.seqEnd === "number";
+}
+
+export function hashTimeline(input: { epoch: string; entries: TimelineEntry[] }): string {
+  return createHash("sha256").update(JSON.stringify([input.epoch, input.entries])).digest("hex");
+}
+
+function coerceCachedTimeline(value: unknown): CachedAgentTimeline | null {
+  if (
+    !isRecord(value) ||
+    typeof value.agentId !== "string" ||
+    typeof value.epoch !== "string" ||
+    typeof value.endSeq !== "number" ||
+    typeof value.fetchedAt !== "string" ||
+    typeof value.contentHash !== "string" ||
+    !Array.isArray(value.entries) ||
+    !value.entries.every(isTimelineEntry)
+  ) {
+    return null;
+  }
+  const timeline: CachedAgentTimeline = {
+    agentId: value.agentId,
+    agent: value.agent ?? null,
+    epoch: value.epoch,
+    endSeq: value.endSeq,
+    hasOlder: value.hasOlder === true,
+    entries: value.entries,
+    fetchedAt: value.fetchedAt,
+    contentHash: value.contentHash,
+  };
+  // A truncated or hand-edited file would otherwise resume from rows it does not hold.
+  return hashTimeline(timeline) === timeline.contentHash ? timeline : null;
+}
+
+/** Timelines saved by the last run; unreadable files and corrupted agents are dropped. */
+export async function readTimelineCacheFile(userDataPath: string): Promise<CachedAgentTimeline[]> {
+  try {
+    const parsed: unknown = JSON.parse(
+      await readFile(path.join(userDataPath, TIMELINE_CACHE_FILENAME), "utf8"),
+    );
+    if (!isRecord(parsed) || !Array.isArray(parsed.timelines)) {
+      return [];
+    }
+    return parsed.timelines.flatMap((value) => coerceCachedTimeline(value) ?? []);
+  } catch {
+    return [];
+  }
+}
+
+export async function writeTimelineCacheFile(
+  userDataPath: string,
+  timelines: CachedAgentTimeline[],
+): Promise<void> {
+  const document: PersistedTimelineCacheDocument = { version: 1, timelines };
+  // Timelines hold conversation text, so the file is private to the user.
+  await writeJsonConfigFile(path.join(userDataPath, TIMELINE_CACHE_FILENAME), document, {
+    mode: 0o600,
+  });
+}
+
+export function buildFetchTimelineFrame(input: {
+  agentId: string;
+  requestId: string;
+  /** Continue after this seq of `epoch`; without it the latest `limit` rows are fetched. */
+  after?: { epoch: string; seq: number };
+  limit: number;
+}): string {
+  return JSON.stringify({
+    type: "session",
+    message: {
+      type: "fetch_agent_timeline_request",
+      agentId: input.agentId,
+      requestId: input.requestId,
+      ...(input.after
+        ? { direction: "after", cursor: input.after, limit: input.limit }
+        : { direction: "tail", limit: input.limit }),
+      // Canonical rows never merge across fetches, so appended ranges line up.
+      projection: "canonical",
+    },
+  });
+}
+
+export function parseTimelineReply(text: string): TimelineReply | null {
+  if (!text.includes('"fetch_agent_timeline_response"')) {
+    return null;
+  }
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(text);
+  } catch {
+    return null;
+  }
+  if (
+    !isRecord(parsed) ||
+    !isRecord(parsed.message) ||
+    parsed.message.type !== "fetch_agent_timeline_response" ||
+    !isRecord(parsed.message.payload)
+  ) {
+    return null;
+  }
+  const payload = parsed.message.payload;
+  if (
+    typeof payload.requestId !== "string" ||
+    typeof payload.agentId !== "string" ||
+    typeof payload.epoch !== "string" ||
+    !Array.isArray(payload.entries)
+  ) {
+    return null;
+  }
+  const direction = payload.direction;
+  return {
+    requestId: payload.requestId,
+    agentId: payload.agentId,
+    agent: payload.agent ?? null,
+    direction: direction === "before" || direction === "after" ? direction : "tail",
+    epoch: payload.epoch,
+    discontinuous: payload.reset === true || payload.staleCursor === true || payload.gap === true,
+    entries: payload.entries.filter(isTimelineEntry),
+    hasOlder: payload.hasOlder === true,
+    hasNewer: payload.hasNewer === true,
+    error: typeof payload.error === "string" ? payload.error : null,
+  };
+}
+
+export function createTimelineCache(input?: {
+  maxAgents?: number;
+  maxEntriesPerAgent?: number;
+}): TimelineCache {
+  const maxAgents = input?.maxAgents ?? DEFAULT_MAX_AGENTS;
+  const maxEntries = input?.maxEntriesPerAgent ?? DEFAULT_MAX_ENTRIES_PER_AGENT;
+  // Insertion order doubles as recency: touched agents are moved to the end.
+  const timelines = new Map<string, CachedAgentTimeline>();
+
+  function store(timeline: Omit<CachedAgentTimeline, "contentHash">): void {
+    const overflow = timeline.entries.length - maxEntries;
+    const entries = overflow > 0 ? timeline.entries.slice(overflow) : timeline.entries;
+    const trimmed: CachedAgentTimeline = {
+      ...timeline,
+      entries,
+      hasOlder: timeline.hasOlder || overflow > 0,
+      contentHash: hashTimeline({ epoch: timeline.epoch, entries }),
+    };
+    timelines.delete(trimmed.agentId);
+    timelines.set(trimmed.agentId, trimmed);
+    while (timelines.size > maxAgents) {
+      const oldest = timelines.keys().next().value;
+      if (oldest === undefined) {
+        break;
+      }
+      timelines.delete(oldest);
+    }
+  }
+
+  return {
+    get(agentId) {
+      const timeline = timelines.get(agentId);
+      if (!timeline) {
+        return null;
+      }
+      timelines.delete(agentId);
+      timelines.set(agentId, timeline);
+      return timeline;
+    },
+
+    list() {
+      return [...timelines.values()];
+    },
+
+    apply(reply, now) {
+      if (reply.error !== null || reply.direction === "before") {
+        return "needs-tail";
+      }
+      const fetchedAt = now.toISOString();
+      const lastSeq = reply.entries.at(-1)?.seqEnd;
+      if (reply.direction === "tail") {
+        store({
+          agentId: reply.agentId,
+          agent: reply.agent,
+          epoch: reply.epoch,
+          endSeq: lastSeq ?? 0,
+          hasOlder: reply.hasOlder,
+          entries: reply.entries,
+          fetchedAt,
+        });
+        return "replaced";
+      }
+      const current = timelines.get(reply.agentId);
+      if (!current || reply.discontinuous || current.epoch !== reply.epoch) {
+        return "needs-tail";
+      }
+      const fresh = reply.entries.filter((entry) => entry.seqStart > current.endSeq);
+      store({
+        ...current,
+        agent: reply.agent ?? current.agent,
+        endSeq: fresh.at(-1)?.seqEnd ?? current.endSeq,
+        entries: [...current.entries, ...fresh],
+        fetchedAt,
+      });
+      return "appended";
+    },
+
+    delete(agentId) {
+      return timelines.delete(agentId);
+    },
+
+    restore(restored) {
+      for (const timeline of restored) {
+        if (!timelines.has(timeline.agentId)) {
+          store(timeline);
+        }
+      }
+    },
+
+    syncCursors() {
+      return [...timelines.values()].map(({ agentId, epoch, endSeq }) => ({
+        agentId,
+        epoch,
+        seq: endSeq,
+      }));
+    },
+  };
+}
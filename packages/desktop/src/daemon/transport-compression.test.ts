@@ -0,0 +1,39 @@
+import { describe, expect, it } from "vitest";
+import { buildPerMessageDeflateOptions, computeCompressionRatio } from "./transport-compression";
+
+describe("buildPerMessageDeflateOptions", () => {
+  it("offers permessage-deflate at the configured level", () => {
+    expect(buildPerMessageDeflateOptions({ compression: true, compressionLevel: 3 })).toEqual({
+      threshold: 1024,
+      zlibDeflateOptions: { level: 3 },
+    });
+  });
+
+  it("does not offer the extension when compression is disabled", () => {
+    expect(buildPerMessageDeflateOptions({ compression: false, compressionLevel: 6 })).toBe(false);
+  });
+});
+
+describe("computeCompressionRatio", () => {
+  it("compares payload bytes with bytes on the wire", () => {
+    expect(
+      computeCompressionRatio({
+        payloadBytesSent: 1_000,
+        payloadBytesReceived: 9_000,
+        wireBytesSent: 1_000,
+        wireBytesReceived: 2_000,
+      }),
+    ).toBe(3.33);
+  });
+
+  it("returns null before any traffic", () => {
+    expect(
+      computeCompressionRatio({
+        payloadBytesSent: 0,
+        payloadBytesReceived: 0,
+        wireBytesSent: 120,
+        wireBytesReceived: 0,
+      }),
+    ).toBeNull();
+  });
+});
@@ -0,0 +1,41 @@
+import type { PerMessageDeflateOptions } from "ws";
+import type { DesktopSettings } from "../settings/desktop-settings.js";
+
+// Small frames (pings, acks, key input) cost more to deflate than they save.
+const COMPRESSION_THRESHOLD_BYTES = 1024;
+
+export interface CompressionCounters {
+  payloadBytesSent: number;
+  payloadBytesReceived: number;
+  wireBytesSent: number;
+  wireBytesReceived: number;
+}
+
+export interface SessionCompressionStats extends CompressionCounters {
+  sessionId: string;
+  negotiated: boolean;
+  extensions: string | null;
+  /** Payload bytes per wire byte; `null` until something has been transferred. */
+  ratio: number | null;
+}
+
+export function buildPerMessageDeflateOptions(
+  transport: Pick<DesktopSettings["transport"], "compression" | "compressionLevel">,
+): PerMessageDeflateOptions | false {
+  if (!transport.compression) {
+    return false;
+  }
+  return {
+    threshold: COMPRESSION_THRESHOLD_BYTES,
+    zlibDeflateOptions: { level: transport.compressionLevel },
+  };
+}
+
+export function computeCompressionRatio(counters: CompressionCounters): number | null {
+  const payloadBytes = counters.payloadBytesSent + counters.payloadBytesReceived;
+  const wireBytes = counters.wireBytesSent + counters.wireBytesReceived;
+  if (payloadBytes === 0 || wireBytes === 0) {
+    return null;
+  }
+  return Math.round((payloadBytes / wireBytes) * 100) / 100;
+}
@@ -0,0 +1,50 @@
+import { describe, expect, it, vi } from "vitest";
+import { createLocalTransportObservers } from "./transport-observers";
+
+const context = { sessionId: "local-session-1", daemon: "/tmp/paseo/daemon.sock" };
+
+describe("createLocalTransportObservers", () => {
+  it("keeps notifying the others when one observer throws", () => {
+    const onError = vi.fn();
+    const observers = createLocalTransportObservers({ onError });
+    const seen: string[] = [];
+    observers.add({
+      inbound: () => {
+        throw new Error("broken feature");
+      },
+    });
+    observers.add({ inbound: (text) => seen.push(text) });
+
+    observers.inbound("frame", context);
+
+    expect(seen).toEqual(["frame"]);
+    expect(onError).toHaveBeenCalledWith("inbound", expect.any(Error));
+  });
+
+  it("runs screens in order and stops at the first rejection", async () => {
+    const observers = createLocalTransportObservers({ onError: vi.fn() });
+    const later = vi.fn();
+    observers.add({
+      screenOutbound: async () => {
+        throw new Error("declined");
+      },
+    });
+    observers.add({ screenOutbound: later });
+
+    await expect(observers.screenOutbound("frame", context)).rejects.toThrow("declined");
+    expect(later).not.toHaveBeenCalled();
+  });
+
+  it("stops notifying an observer once it is removed", () => {
+    const observers = createLocalTransportObservers({ onError: vi.fn() });
+    const closed = vi.fn();
+    const remove = observers.add({ closed });
+
+    observers.closed(context);
+    remove();
+    observers.closed(context);
+
+    expect(closed).toHaveBeenCalledTimes(1);
+    expect(closed).toHaveBeenCalledWith(context);
+  });
+});
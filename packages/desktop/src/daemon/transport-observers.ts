@@ -0,0 +1,88 @@
+export interface LocalTransportSessionInfo {
+  sessionId: string;
+  /** Socket or pipe path of the daemon behind the session. */
+  daemon: string;
+}
+
+/**
+ * What a desktop feature sees of the local transport. Features register an
+ * observer instead of being called from the transport, which stays unaware of
+ * them.
+ */
+export interface LocalTransportObserver {
+  /** Every text frame from the daemon, before stream filtering and reply routing. */
+  inbound?(text: string, context: LocalTransportSessionInfo): void;
+  /**
+   * A renderer frame about to go to the daemon. Throwing or rejecting drops
+   * the frame and fails the renderer's send with that error.
+   */
+  screenOutbound?(
+    frame: string | Buffer,
+    context: LocalTransportSessionInfo,
+  ): void | Promise<void>;
+  /** A renderer text frame that passed every screen and is being sent. */
+  outbound?(text: string, context: LocalTransportSessionInfo): void;
+  /** The daemon has the renderer's hello: after the first send and after each resume. */
+  helloDelivered?(context: LocalTransportSessionInfo): void;
+  closed?(context: LocalTransportSessionInfo): void;
+}
+
+type NotifyHook = "inbound" | "outbound" | "helloDelivered" | "closed";
+
+export interface LocalTransportObservers {
+  add(observer: LocalTransportObserver): () => void;
+  inbound(text: string, context: LocalTransportSessionInfo): void;
+  /** Runs the screens in registration order; the first rejection stops the frame. */
+  screenOutbound(frame: string | Buffer, context: LocalTransportSessionInfo): Promise<void>;
+  outbound(text: string, context: LocalTransportSessionInfo): void;
+  helloDelivered(context: LocalTransportSessionInfo): void;
+  closed(context: LocalTransportSessionInfo): void;
+}
+
+/** A failing observer is reported and skipped, so it cannot break the transport or the others. */
+export function createLocalTransportObservers(input: {
+  onError: (hook: NotifyHook, error: unknown) => void;
+}): LocalTransportObservers {
+  const observers = new Set<LocalTransportObserver>();
+
+  function notify(hook: NotifyHook, call: (observer: LocalTransportObserver) => void): void {
+    for (const observer of observers) {
+      try {
+        call(observer);
+      } catch (error) {
+        input.onError(hook, error);
+      }
+    }
+  }
+
+  return {
+    add(observer) {
+      observers.add(observer);
+      return () => {
+        observers.delete(observer);
+      };
+    },
+
+    inbound(text, context) {
+      notify("inbound", (observer) => observer.inbound?.(text, context));
+    },
+
+    async screenOutbound(frame, context) {
+      for (const observer of [...observers]) {
+        await observer.screenOutbound?.(frame, context);
+      }
+    },
+
+    outbound(text, context) {
+      notify("outbound", (observer) => observer.outbound?.(text, context));
+    },
+
+    helloDelivered(context) {
+      notify("helloDelivered", (observer) => observer.helloDelivered?.(context));
+    },
+
+    closed(context) {
+      notify("closed", (observer) => observer.closed?.(context));
+    },
+  };
+}
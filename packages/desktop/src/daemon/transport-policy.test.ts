@@ -0,0 +1,48 @@
+import { describe, expect, it } from "vitest";
+import {
+  applyTransportPolicyPatch,
+  coerceTransportPolicyPatch,
+  DEFAULT_TRANSPORT_POLICY,
+} from "./transport-policy";
+
+describe("transport policy", () => {
+  it("changes only the fields that are given", () => {
+    const patch = coerceTransportPolicyPatch({
+      connectionId: "local-session-1",
+      timeouts: { requestMs: 60_000 },
+      retries: { maxReconnectAttempts: 20 },
+    });
+    const next = applyTransportPolicyPatch(DEFAULT_TRANSPORT_POLICY, patch);
+
+    expect(next.timeouts).toEqual({ ...DEFAULT_TRANSPORT_POLICY.timeouts, requestMs: 60_000 });
+    expect(next.retries).toEqual({
+      ...DEFAULT_TRANSPORT_POLICY.retries,
+      maxReconnectAttempts: 20,
+    });
+    expect(DEFAULT_TRANSPORT_POLICY.timeouts.requestMs).toBe(10_000);
+  });
+
+  it("accepts an empty patch", () => {
+    expect(coerceTransportPolicyPatch(undefined)).toEqual({});
+    expect(coerceTransportPolicyPatch({ timeouts: null })).toEqual({});
+  });
+
+  it("rejects unknown fields and out-of-range values", () => {
+    expect(() => coerceTransportPolicyPatch({ timeouts: { readMs: 1_000 } })).toThrow(/Unknown/);
+    expect(() => coerceTransportPolicyPatch({ timeouts: { connectMs: 10 } })).toThrow(
+      /timeouts.connectMs/,
+    );
+    expect(() => coerceTransportPolicyPatch({ retries: { maxReconnectAttempts: 1.5 } })).toThrow(
+      /integer/,
+    );
+    expect(() => coerceTransportPolicyPatch({ retries: [] })).toThrow(/object/);
+  });
+
+  it("keeps the reconnect backoff ceiling above its base", () => {
+    expect(() =>
+      applyTransportPolicyPatch(DEFAULT_TRANSPORT_POLICY, {
+        retries: { reconnectBaseDelayMs: 30_000 },
+      }),
+    ).toThrow(/reconnectMaxDelayMs/);
+  });
+});
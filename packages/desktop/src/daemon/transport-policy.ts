@@ -0,0 +1,106 @@
+import { isRecord } from "../utils/is-record.js";
+
+export interface TransportPolicy {
+  timeouts: {
+    /** WebSocket handshake, for the first connect and every reconnect. */
+    connectMs: number;
+    /** Requests the desktop sends itself, such as interrupts and clock samples. */
+    requestMs: number;
+    /** A heartbeat ping unanswered this long counts as missed. */
+    heartbeatMs: number;
+  };
+  retries: {
+    /** Reconnects tried after a drop before the session is closed for good. */
+    maxReconnectAttempts: number;
+    /** Delay before the first reconnect; it doubles with each attempt. */
+    reconnectBaseDelayMs: number;
+    reconnectMaxDelayMs: number;
+  };
+}
+
+export interface TransportPolicyPatch {
+  timeouts?: Partial<TransportPolicy["timeouts"]>;
+  retries?: Partial<TransportPolicy["retries"]>;
+}
+
+export const DEFAULT_TRANSPORT_POLICY: TransportPolicy = {
+  timeouts: {
+    connectMs: 15_000,
+    requestMs: 10_000,
+    heartbeatMs: 5_000,
+  },
+  retries: {
+    maxReconnectAttempts: 8,
+    reconnectBaseDelayMs: 250,
+    reconnectMaxDelayMs: 10_000,
+  },
+};
+
+type Range = { min: number; max: number };
+
+const TIMEOUT_RANGES: Record<keyof TransportPolicy["timeouts"], Range> = {
+  connectMs: { min: 1_000, max: 120_000 },
+  requestMs: { min: 1_000, max: 300_000 },
+  heartbeatMs: { min: 1_000, max: 60_000 },
+};
+
+const RETRY_RANGES: Record<keyof TransportPolicy["retries"], Range> = {
+  maxReconnectAttempts: { min: 0, max: 100 },
+  reconnectBaseDelayMs: { min: 50, max: 60_000 },
+  reconnectMaxDelayMs: { min: 50, max: 300_000 },
+};
+
+function coerceGroup<K extends string>(
+  group: string,
+  value: unknown,
+  ranges: Record<K, Range>,
+): Partial<Record<K, number>> | undefined {
+  if (value === undefined || value === null) {
+    return undefined;
+  }
+  if (!isRecord(value)) {
+    throw new Error(`${group} must be an object.`);
+  }
+  const result: Partial<Record<K, number>> = {};
+  for (const [key, input] of Object.entries(value)) {
+    if (!(key in ranges)) {
+      throw new Error(`Unknown ${group} field: ${key}`);
+    }
+    const range = ranges[key as K];
+    if (
+      typeof input !== "number" ||
+      !Number.isInteger(input) ||
+      input < range.min ||
+      input > range.max
+    ) {
+      throw new Error(`${group}.${key} must be an integer between ${range.min} and ${range.max}.`);
+    }
+    result[key as K] = input;
+  }
+  return result;
+}
+
+export function coerceTransportPolicyPatch(
+  args: Record<string, unknown> | undefined,
+): TransportPolicyPatch {
+  const timeouts = coerceGroup("timeouts", args?.timeouts, TIMEOUT_RANGES);
+  const retries = coerceGroup("retries", args?.retries, RETRY_RANGES);
+  return {
+    ...(timeouts ? { timeouts } : {}),
+    ...(retries ? { retries } : {}),
+  };
+}
+
+export function applyTransportPolicyPatch(
+  current: TransportPolicy,
+  patch: TransportPolicyPatch,
+): TransportPolicy {
+  const next: TransportPolicy = {
+    timeouts: { ...current.timeouts, ...patch.timeouts },
+    retries: { ...current.retries, ...patch.retries },
+  };
+  if (next.retries.reconnectMaxDelayMs < next.retries.reconnectBaseDelayMs) {
+    throw new Error("retries.reconnectMaxDelayMs must not be below reconnectBaseDelayMs.");
+  }
+  return next;
+}
@@ -0,0 +1,220 @@
+import { createHash, randomBytes, sign, type KeyObject } from "node:crypto";
+import { isIP } from "node:net";
+
+// Minimal DER/X.509 v3 writer for the daemon's local CA and server certificate.
+// Only what those two certificates need: ECDSA P-256 with SHA-256.
+
+const OID_ECDSA_WITH_SHA256 = "1.2.840.10045.4.3.2";
+const OID_COMMON_NAME = "2.5.4.3";
+const OID_ORGANIZATION = "2.5.4.10";
+const OID_SUBJECT_KEY_IDENTIFIER = "2.5.29.14";
+const OID_KEY_USAGE = "2.5.29.15";
+const OID_SUBJECT_ALT_NAME = "2.5.29.17";
+const OID_BASIC_CONSTRAINTS = "2.5.29.19";
+const OID_AUTHORITY_KEY_IDENTIFIER = "2.5.29.35";
+const OID_EXT_KEY_USAGE = "2.5.29.37";
+const OID_SERVER_AUTH = "1.3.6.1.5.5.7.3.1";
+
+// KeyUsage bit positions (RFC 5280 §4.2.1.3).
+const KEY_USAGE_DIGITAL_SIGNATURE = 0;
+const KEY_USAGE_KEY_CERT_SIGN = 5;
+const KEY_USAGE_CRL_SIGN = 6;
+
+const GENERALIZED_TIME_FROM_YEAR = 2050;
+
+function encodeLength(length: number): Buffer {
+  if (length < 0x80) {
+    return Buffer.from([length]);
+  }
+  const bytes: number[] = [];
+  for (let remaining = length; remaining > 0; remaining = Math.floor(remaining / 256)) {
+    bytes.unshift(remaining & 0xff);
+  }
+  return Buffer.from([0x80 | bytes.length, ...bytes]);
+}
+
+function tlv(tag: number, value: Buffer): Buffer {
+  return Buffer.concat([Buffer.from([tag]), encodeLength(value.length), value]);
+}
+
+function sequence(...items: Buffer[]): Buffer {
+  return tlv(0x30, Buffer.concat(items));
+}
+
+function set(...items: Buffer[]): Buffer {
+  return tlv(0x31, Buffer.concat(items));
+}
+
+function explicit(tagNumber: number, value: Buffer): Buffer {
+  return tlv(0xa0 + tagNumber, value);
+}
+
+function integer(value: Buffer): Buffer {
+  let bytes = value;
+  while (bytes.length > 1 && bytes[0] === 0 && bytes[1]! < 0x80) {
+    bytes = bytes.subarray(1);
+  }
+  // A set high bit would make the integer negative.
+  return tlv(0x02, bytes[0]! >= 0x80 ? Buffer.concat([Buffer.from([0]), bytes]) : bytes);
+}
+
+function boolean(value: boolean): Buffer {
+  return tlv(0x01, Buffer.from([value ? 0xff : 0x00]));
+}
+
+function octetString(value: Buffer): Buffer {
+  return tlv(0x04, value);
+}
+
+function bitString(value: Buffer, unusedBits = 0): Buffer {
+  return tlv(0x03, Buffer.concat([Buffer.from([unusedBits]), value]));
+}
+
+function utf8String(value: string): Buffer {
+  return tlv(0x0c, Buffer.from(value, "utf8"));
+}
+
+export function encodeOid(oid: string): Buffer {
+  const parts = oid.split(".").map(Number);
+  const bytes = [parts[0]! * 40 + parts[1]!];
+  for (const part of parts.slice(2)) {
+    // Base-128, high bit set on every byte but the last.
+    const chunk = [part & 0x7f];
+    let remaining = Math.floor(part / 128);
+    while (remaining > 0) {
+      chunk.unshift((remaining & 0x7f) | 0x80);
+      remaining = Math.floor(remaining / 128);
+    }
+    bytes.push(...chunk);
+  }
+  return tlv(0x06, Buffer.from(bytes));
+}
+
+function formatTime(date: Date): Buffer {
+  const iso = date.toISOString().replace(/[-:T]/g, "").slice(0, 14);
+  return date.getUTCFullYear() < GENERALIZED_TIME_FROM_YEAR
+    ? tlv(0x17, Buffer.from(`${iso.slice(2)}Z`, "ascii"))
+    : tlv(0x18, Buffer.from(`${iso}Z`, "ascii"));
+}
+
+function name(input: { commonName: string; organization: string }): Buffer {
+  return sequence(
+    set(sequence(encodeOid(OID_ORGANIZATION), utf8String(input.organization))),
+    set(sequence(encodeOid(OID_COMMON_NAME), utf8String(input.commonName))),
+  );
+}
+
+function keyUsage(bits: number[]): Buffer {
+  const highest = Math.max(...bits);
+  const bytes = Buffer.alloc(Math.floor(highest / 8) + 1);
+  for (const bit of bits) {
+    const index = Math.floor(bit / 8);
+    bytes[index] = (bytes[index] ?? 0) | (0x80 >> bit % 8);
+  }
+  return bitString(bytes, 7 - (highest % 8));
+}
+
+function extension(oid: string, critical: boolean, value: Buffer): Buffer {
+  return sequence(encodeOid(oid), ...(critical ? [boolean(true)] : []), octetString(value));
+}
+
+function subjectAltNames(hostnames: string[]): Buffer {
+  return sequence(
+    ...hostnames.map((hostname) => {
+      if (isIP(hostname) === 4) {
+        return tlv(0x87, Buffer.from(hostname.split(".").map(Number)));
+      }
+      if (isIP(hostname) === 6) {
+        return tlv(0x87, ipv6Bytes(hostname));
+      }
+      return tlv(0x82, Buffer.from(hostname, "ascii"));
+    }),
+  );
+}
+
+function ipv6Bytes(address: string): Buffer {
+  const [head = "", tail = ""] = address.split("::");
+  const headGroups = head ? head.split(":") : [];
+  const tailGroups = tail ? tail.split(":") : [];
+  const groups = address.includes("::")
+    ? [
+        ...headGroups,
+        ...Array<string>(8 - headGroups.length - tailGroups.length).fill("0"),
+        ...tailGroups,
+      ]
+    : headGroups;
+  const bytes = Buffer.alloc(16);
+  groups.forEach((group, index) => bytes.writeUInt16BE(Number.parseInt(group, 16), index * 2));
+  return bytes;
+}
+
+/** SHA-1 of the subjectPublicKey bits, as RFC 5280 suggests for key identifiers. */
+export function computeKeyIdentifier(publicKey: KeyObject): Buffer {
+  const spki = publicKey.export({ type: "spki", format: "der" });
+  // For P-256 the key bits are the trailing 65-byte uncompressed point.
+  return createHash("sha1").update(spki.subarray(spki.length - 65)).digest();
+}
+
+export interface CertificateInput {
+  subject: { commonName: string; organization: string };
+  issuer: { commonName: string; organization: string };
+  publicKey: KeyObject;
+  /** Issuer's key; the subject's own key for a self-signed CA. */
+  signingKey: KeyObject;
+  issuerPublicKey: KeyObject;
+  notBefore: Date;
+  notAfter: Date;
+  /** CA certificates sign leaves; leaves carry the hostnames they serve. */
+  role: { kind: "ca" } | { kind: "server"; hostnames: string[] };
+}
+
+export function createCertificateDer(input: CertificateInput): Buffer {
+  const algorithm = sequence(encodeOid(OID_ECDSA_WITH_SHA256));
+  const extensions =
+    input.role.kind === "ca"
+      ? [
+          // cA=true, pathLenConstraint=0: the CA only ever signs leaves.
+          extension(
+            OID_BASIC_CONSTRAINTS,
+            true,
+            sequence(boolean(true), integer(Buffer.from([0]))),
+          ),
+          extension(OID_KEY_USAGE, true, keyUsage([KEY_USAGE_KEY_CERT_SIGN, KEY_USAGE_CRL_SIGN])),
+        ]
+      : [
+          extension(OID_BASIC_CONSTRAINTS, true, sequence()),
+          extension(OID_KEY_USAGE, true, keyUsage([KEY_USAGE_DIGITAL_SIGNATURE])),
+          extension(OID_EXT_KEY_USAGE, false, sequence(encodeOid(OID_SERVER_AUTH))),
+          extension(OID_SUBJECT_ALT_NAME, false, subjectAltNames(input.role.hostnames)),
+        ];
+  extensions.push(
+    extension(
+      OID_SUBJECT_KEY_IDENTIFIER,
+      false,
+      octetString(computeKeyIdentifier(input.publicKey)),
+    ),
+    extension(
+      OID_AUTHORITY_KEY_IDENTIFIER,
+      false,
+      sequence(tlv(0x80, computeKeyIdentifier(input.issuerPublicKey))),
+    ),
+  );
+
+  const tbs = sequence(
+    explicit(0, integer(Buffer.from([2]))),
+    integer(randomBytes(16)),
+    algorithm,
+    name(input.issuer),
+    sequence(formatTime(input.notBefore), formatTime(input.notAfter)),
+    name(input.subject),
+    input.publicKey.export({ type: "spki", format: "der" }),
+    explicit(3, sequence(...extensions)),
+  );
+  const signature = sign("sha256", tbs, input.signingKey);
+  return sequence(tbs, algorithm, bitString(signature));
+}
+
+export function toPem(label: string, der: Buffer): string {
+  const lines = der.toString("base64").match(/.{1,64}/g) ?? [];
+  return `-----BEGIN ${label}-----\n${lines.join("\n")}\n-----END ${label}-----\n`;
+}
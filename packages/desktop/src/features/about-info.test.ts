@@ -0,0 +1,54 @@
+import { describe, expect, it } from "vitest";
+import { buildAboutInfo, buildAboutPanelOptions } from "./about-info";
+
+const baseInfo = {
+  appName: "Paseo",
+  appVersion: "0.1.65",
+  commit: "0123456789abcdef0123456789abcdef01234567",
+  releaseChannel: "beta",
+  daemonVersion: "0.1.65",
+  daemonStatus: "running",
+  electronVersion: "41.0.3",
+  webviewVersion: "146.0.0.0",
+  nodeVersion: "24.6.0",
+  os: { platform: "darwin" as const, arch: "arm64", release: "25.0.0", version: "Darwin 25" },
+};
+
+describe("buildAboutInfo", () => {
+  it("renders a copyable diagnostics block", () => {
+    expect(buildAboutInfo(baseInfo).diagnostics).toBe(
+      [
+        "Paseo 0.1.65 (beta)",
+        "Commit: 0123456789abcdef0123456789abcdef01234567",
+        "Daemon: 0.1.65 (running)",
+        "Electron: 41.0.3",
+        "Chromium: 146.0.0.0",
+        "Node: 24.6.0",
+        "OS: darwin 25.0.0 (arm64) Darwin 25",
+      ].join("\n"),
+    );
+  });
+
+  it("marks unknown commit and stopped daemon", () => {
+    const { diagnostics } = buildAboutInfo({
+      ...baseInfo,
+      commit: null,
+      daemonVersion: null,
+      daemonStatus: "stopped",
+    });
+
+    expect(diagnostics).toContain("Commit: unknown");
+    expect(diagnostics).toContain("Daemon: stopped");
+  });
+});
+
+describe("buildAboutPanelOptions", () => {
+  it("shows the channel, short commit, and daemon version", () => {
+    expect(buildAboutPanelOptions(buildAboutInfo(baseInfo))).toEqual({
+      applicationName: "Paseo",
+      applicationVersion: "0.1.65 (beta)",
+      version: "0123456789ab",
+      credits: "Daemon 0.1.65\nElectron 41.0.3 · Chromium 146.0.0.0",
+    });
+  });
+});
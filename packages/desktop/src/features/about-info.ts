@@ -0,0 +1,85 @@
+import { execFileSync } from "node:child_process";
+import { readFileSync } from "node:fs";
+import path from "node:path";
+
+export interface AboutInfo {
+  appName: string;
+  appVersion: string;
+  /** Full git SHA the app was built from, when known. */
+  commit: string | null;
+  releaseChannel: string;
+  daemonVersion: string | null;
+  daemonStatus: string;
+  electronVersion: string;
+  /** Chromium version backing the renderer and browser webviews. */
+  webviewVersion: string;
+  nodeVersion: string;
+  os: {
+    platform: NodeJS.Platform;
+    arch: string;
+    release: string;
+    version: string;
+  };
+  /** Plain-text block for the About dialog's copy button and bug reports. */
+  diagnostics: string;
+}
+
+const COMMIT_PATTERN = /^[0-9a-f]{7,40}$/i;
+
+/**
+ * Packaged builds read `gitCommit`, injected into the app's package.json via
+ * electron-builder `extraMetadata`; dev builds ask git directly.
+ */
+export function resolveBuildCommit(input: { isPackaged: boolean; appPath: string }): string | null {
+  try {
+    if (input.isPackaged) {
+      const pkg = JSON.parse(readFileSync(path.join(input.appPath, "package.json"), "utf8")) as {
+        gitCommit?: unknown;
+      };
+      return typeof pkg.gitCommit === "string" && COMMIT_PATTERN.test(pkg.gitCommit)
+        ? pkg.gitCommit
+        : null;
+    }
+    const commit = execFileSync("git", ["rev-parse", "HEAD"], {
+      cwd: input.appPath,
+      encoding: "utf8",
+      stdio: ["ignore", "pipe", "ignore"],
+      timeout: 2_000,
+    }).trim();
+    return COMMIT_PATTERN.test(commit) ? commit : null;
+  } catch {
+    return null;
+  }
+}
+
+export function formatAboutDiagnostics(info: Omit<AboutInfo, "diagnostics">): string {
+  const daemon = info.daemonVersion
+    ? `${info.daemonVersion} (${info.daemonStatus})`
+    : info.daemonStatus;
+  return [
+    `${info.appName} ${info.appVersion} (${info.releaseChannel})`,
+    `Commit: ${info.commit ?? "unknown"}`,
+    `Daemon: ${daemon}`,
+    `Electron: ${info.electronVersion}`,
+    `Chromium: ${info.webviewVersion}`,
+    `Node: ${info.nodeVersion}`,
+    `OS: ${info.os.platform} ${info.os.release} (${info.os.arch}) ${info.os.version}`.trimEnd(),
+  ].join("\n");
+}
+
+export function buildAboutInfo(input: Omit<AboutInfo, "diagnostics">): AboutInfo {
+  return { ...input, diagnostics: formatAboutDiagnostics(input) };
+}
+
+/** Same facts as the renderer's About dialog, laid out for `app.setAboutPanelOptions`. */
+export function buildAboutPanelOptions(info: AboutInfo): Electron.AboutPanelOptionsOptions {
+  return {
+    applicationName: info.appName,
+    applicationVersion: `${info.appVersion} (${info.releaseChannel})`,
+    version: info.commit ? info.commit.slice(0, 12) : "",
+    credits: [
+      `Daemon ${info.daemonVersion ?? info.daemonStatus}`,
+      `Electron ${info.electronVersion} · Chromium ${info.webviewVersion}`,
+    ].join("\n"),
+  };
+}
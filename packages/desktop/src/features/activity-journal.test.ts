@@ -0,0 +1,117 @@
+import { mkdtemp, rm } from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+import { createActivityJournal, measureFileChange } from "./activity-journal";
+
+function frame(message: Record<string, unknown>): string {
+  return JSON.stringify({ type: "session", message });
+}
+
+function stream(agentId: string, event: Record<string, unknown>, timestamp: string): string {
+  return frame({ type: "agent_stream", payload: { agentId, event, timestamp } });
+}
+
+describe("measureFileChange", () => {
+  it("counts diff lines for edits and content lines for writes", () => {
+    expect(
+      measureFileChange({
+        type: "edit",
+        filePath: "src/a.ts",
+        unifiedDiff: "--- a/src/a.ts\n+++ b/src/a.ts\n@@ -1 +1,2 @@\n-old\n+new\n+more",
+      }),
+    ).toEqual({ path: "src/a.ts", additions: 2, deletions: 1 });
+    expect(measureFileChange({ type: "write", filePath: "b.md", content: "one\ntwo" })).toEqual({
+      path: "b.md",
+      additions: 2,
+      deletions: 0,
+    });
+    expect(measureFileChange({ type: "read", filePath: "c.ts" })).toBeNull();
+  });
+});
+
+describe("createActivityJournal", () => {
+  let userDataPath: string;
+  const now = () => Date.parse("2026-05-01T18:00:00");
+
+  beforeEach(async () => {
+    userDataPath = await mkdtemp(path.join(os.tmpdir(), "paseo-activity-journal-"));
+  });
+
+  afterEach(async () => {
+    await rm(userDataPath, { recursive: true, force: true });
+  });
+
+  it("records finished turns, file changes and pending approvals per local day", async () => {
+    const journal = createActivityJournal({ userDataPath, now });
+    const at = new Date("2026-05-01T10:30:00").toISOString();
+    journal.observeInbound(
+      frame({
+        type: "agent_update",
+        payload: {
+          kind: "upsert",
+          agent: { id: "a1", title: "Fix login", cwd: "/repo", pendingPermissions: [] },
+        },
+      }),
+    );
+    const prompt = { type: "user_message", text: "Fix  the\nlogin" };
+    journal.observeInbound(stream("a1", { type: "timeline", item: prompt }, at));
+    journal.observeInbound(
+      stream(
+        "a1",
+        {
+          type: "timeline",
+          item: {
+            type: "tool_call",
+            status: "completed",
+            detail: { type: "edit", filePath: "login.ts", oldString: "a", newString: "b\nc" },
+          },
+        },
+        at,
+      ),
+    );
+    journal.observeInbound(stream("a1", { type: "turn_completed" }, at));
+    journal.observeInbound(
+      frame({
+        type: "agent_permission_request",
+        payload: { agentId: "a1", request: { id: "p1", name: "Bash", title: "Run tests" } },
+      }),
+    );
+    await journal.flush();
+
+    const reopened = createActivityJournal({ userDataPath, now });
+    expect(await reopened.getDay("2026-05-01")).toEqual({
+      date: "2026-05-01",
+      agents: [
+        {
+          agentId: "a1",
+          title: "Fix login",
+          cwd: "/repo",
+          tasks: [{ prompt: "Fix the login", endedAt: at, outcome: "completed" }],
+          files: [{ path: "login.ts", additions: 2, deletions: 1 }],
+        },
+      ],
+      pendingApprovals: [
+        {
+          agentId: "a1",
+          requestId: "p1",
+          title: "Run tests",
+          requestedAt: new Date(now()).toISOString(),
+        },
+      ],
+    });
+  });
+
+  it("drops approvals once they are resolved", async () => {
+    const journal = createActivityJournal({ userDataPath, now });
+    const request = { agentId: "a1", request: { id: "p1", name: "Bash" } };
+    journal.observeInbound(frame({ type: "agent_permission_request", payload: request }));
+    journal.observeInbound(
+      frame({
+        type: "agent_permission_resolved",
+        payload: { agentId: "a1", requestId: "p1", resolution: { behavior: "allow" } },
+      }),
+    );
+    expect((await journal.getDay("2026-05-01")).pendingApprovals).toEqual([]);
+  });
+});
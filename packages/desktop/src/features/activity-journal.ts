@@ -0,0 +1,448 @@
+import { readFile } from "node:fs/promises";
+import path from "node:path";
+import { updateJsonConfigFile } from "../settings/config-file.js";
+import { localDateKey } from "./usage-stats.js";
+import { isRecord } from "../utils/is-record.js";
+
+const ACTIVITY_JOURNAL_FILENAME = "activity-journal.json";
+const MAX_RETAINED_DAYS = 31;
+const DAY_MS = 24 * 60 * 60 * 1000;
+// Enough to recognise the task in a digest without keeping whole prompts on disk.
+const MAX_PROMPT_LENGTH = 200;
+
+export type ActivityTaskOutcome = "completed" | "failed" | "canceled";
+
+export interface ActivityTask {
+  prompt: string | null;
+  endedAt: string;
+  outcome: ActivityTaskOutcome;
+}
+
+export interface ActivityFileChange {
+  path: string;
+  additions: number;
+  deletions: number;
+}
+
+export interface AgentActivity {
+  agentId: string;
+  title: string | null;
+  cwd: string | null;
+  tasks: ActivityTask[];
+  files: ActivityFileChange[];
+}
+
+export interface PendingApproval {
+  agentId: string;
+  requestId: string;
+  title: string;
+  requestedAt: string;
+}
+
+export interface ActivityDay {
+  /** Local calendar date, `YYYY-MM-DD`. */
+  date: string;
+  agents: AgentActivity[];
+  /** Approvals requested on or before `date` that are still waiting. */
+  pendingApprovals: PendingApproval[];
+}
+
+interface PersistedActivityJournalDocument {
+  version: 1;
+  days: Record<string, AgentActivity[]>;
+  pendingApprovals: PendingApproval[];
+}
+
+export interface ActivityJournal {
+  /** Records completed turns, file edits and approvals from inbound local-transport frames. */
+  observeInbound(text: string): void;
+  getDay(date: string): Promise<ActivityDay>;
+  /** Resolves once everything observed so far is on disk. */
+  flush(): Promise<void>;
+}
+
+const TURN_OUTCOMES: Record<string, ActivityTaskOutcome> = {
+  turn_completed: "completed",
+  turn_failed: "failed",
+  turn_canceled: "canceled",
+};
+
+function isActivityTaskOutcome(value: unknown): value is ActivityTaskOutcome {
+  return Object.values(TURN_OUTCOMES).includes(value as ActivityTaskOutcome);
+}
+
+function optionalString(value: unknown): string | null {
+  return typeof value === "string" ? value : null;
+}
+
+function parseSessionMessage(text: string): Record<string, unknown> | null {
+  try {
+    const parsed: unknown = JSON.parse(text);
+    if (isRecord(parsed) && parsed.type === "session" && isRecord(parsed.message)) {
+      return parsed.message;
+    }
+  } catch {
+    // not JSON
+  }
+  return null;
+}
+
+function countLines(text: string): number {
+  return text.length === 0 ? 0 : text.split("\n").length;
+}
+
+/** Line counts for an `edit` or `write` tool call; `null` for every other detail. */
+export function measureFileChange(detail: unknown): ActivityFileChange | null {
+  if (!isRecord(detail) || typeof detail.filePath !== "string") {
+    return null;
+  }
+  if (detail.type === "write") {
+    const additions = countLines(String(detail.content ?? ""));
+    return { path: detail.filePath, additions, deletions: 0 };
+  }
+  if (detail.type !== "edit") {
+    return null;
+  }
+  if (typeof detail.unifiedDiff === "string") {
+    let additions = 0;
+    let deletions = 0;
+    for (const line of detail.unifiedDiff.split("\n")) {
+      if (line.startsWith("+") && !line.startsWith("+++")) additions += 1;
+      if (line.startsWith("-") && !line.startsWith("---")) deletions += 1;
+    }
+    return { path: detail.filePath, additions, deletions };
+  }
+  return {
+    path: detail.filePath,
+    additions: countLines(String(detail.newString ?? "")),
+    deletions: countLines(String(detail.oldString ?? "")),
+  };
+}
+
+function truncatePrompt(text: string): string | null {
+  const collapsed = text.replace(/\s+/g, " ").trim();
+  if (collapsed.length === 0) {
+    return null;
+  }
+  return collapsed.length > MAX_PROMPT_LENGTH
+    ? `${collapsed.slice(0, MAX_PROMPT_LENGTH - 1)}…`
+    : collapsed;
+}
+
+function coercePendingApproval(value: unknown): PendingApproval | null {
+  if (
+    !isRecord(value) ||
+    typeof value.agentId !== "string" ||
+    typeof value.requestId !== "string" ||
+    typeof value.requestedAt !== "string"
+  ) {
+    return null;
+  }
+  return {
+    agentId: value.agentId,
+    requestId: value.requestId,
+    title: typeof value.title === "string" ? value.title : "Permission request",
+    requestedAt: value.requestedAt,
+  };
+}
+
+function coerceAgentActivity(value: unknown): AgentActivity | null {
+  if (!isRecord(value) || typeof value.agentId !== "string") {
+    return null;
+  }
+  const tasks = Array.isArray(value.tasks) ? value.tasks : [];
+  const files = Array.isArray(value.files) ? value.files : [];
+  return {
+    agentId: value.agentId,
+    title: optionalString(value.title),
+    cwd: optionalString(value.cwd),
+    tasks: tasks.flatMap((task: unknown): ActivityTask[] =>
+      isRecord(task) && typeof task.endedAt === "string" && isActivityTaskOutcome(task.outcome)
+        ? [{ prompt: optionalString(task.prompt), endedAt: task.endedAt, outcome: task.outcome }]
+        : [],
+    ),
+    files: files.flatMap((file: unknown): ActivityFileChange[] =>
+      isRecord(file) && typeof file.path === "string"
+        ? [
+            {
+              path: file.path,
+              additions: typeof file.additions === "number" ? file.additions : 0,
+              deletions: typeof file.deletions === "number" ? file.deletions : 0,
+            },
+          ]
+        : [],
+    ),
+  };
+}
+
+function coerceDocument(input: unknown): PersistedActivityJournalDocument {
+  const document: PersistedActivityJournalDocument = {
+    version: 1,
+    days: {},
+    pendingApprovals: [],
+  };
+  if (!isRecord(input)) {
+    return document;
+  }
+  if (isRecord(input.days)) {
+    for (const [date, agents] of Object.entries(input.days)) {
+      if (/^\d{4}-\d{2}-\d{2}$/.test(date) && Array.isArray(agents)) {
+        document.days[date] = agents.flatMap((agent) => coerceAgentActivity(agent) ?? []);
+      }
+    }
+  }
+  if (Array.isArray(input.pendingApprovals)) {
+    document.pendingApprovals = input.pendingApprovals.flatMap(
+      (approval) => coercePendingApproval(approval) ?? [],
+    );
+  }
+  return document;
+}
+
+function permissionTitle(request: Record<string, unknown>): string {
+  return optionalString(request.title) ?? optionalString(request.name) ?? "Permission request";
+}
+
+/** Applies one observed change to a journal document; `false` when it changed nothing. */
+type JournalMutation = (target: PersistedActivityJournalDocument) => boolean;
+
+export function createActivityJournal(input: {
+  userDataPath: string;
+  now?: () => number;
+}): ActivityJournal {
+  const filePath = path.join(input.userDataPath, ACTIVITY_JOURNAL_FILENAME);
+  const now = input.now ?? (() => Date.now());
+  let document: PersistedActivityJournalDocument = coerceDocument(null);
+  // Latest titles and in-flight prompts; copied onto a day once it records activity.
+  const agentInfo = new Map<string, { title: string | null; cwd: string | null }>();
+  const lastPrompts = new Map<string, string | null>();
+  let loaded: Promise<void> | null = null;
+  let queue: Promise<void> = Promise.resolve();
+
+  function ensureLoaded(): Promise<void> {
+    loaded ??= readFile(filePath, "utf8")
+      .then((raw) => {
+        document = coerceDocument(JSON.parse(raw));
+      })
+      .catch(() => undefined);
+    return loaded;
+  }
+
+  function prune(target: PersistedActivityJournalDocument): PersistedActivityJournalDocument {
+    const oldest = localDateKey(now() - MAX_RETAINED_DAYS * DAY_MS);
+    for (const date of Object.keys(target.days)) {
+      if (date < oldest) {
+        delete target.days[date];
+      }
+    }
+    return target;
+  }
+
+  function activityFor(
+    target: PersistedActivityJournalDocument,
+    agentId: string,
+    atMs: number,
+  ): AgentActivity {
+    const date = localDateKey(atMs);
+    const agents = (target.days[date] ??= []);
+    let activity = agents.find((entry) => entry.agentId === agentId);
+    if (!activity) {
+      activity = { agentId, title: null, cwd: null, tasks: [], files: [] };
+      agents.push(activity);
+    }
+    const info = agentInfo.get(agentId);
+    if (info) {
+      activity.title = info.title ?? activity.title;
+      activity.cwd = info.cwd ?? activity.cwd;
+    }
+    return activity;
+  }
+
+  function setPendingApprovals(
+    target: PersistedActivityJournalDocument,
+    agentId: string,
+    pending: PendingApproval[],
+  ): boolean {
+    const others = target.pendingApprovals.filter((approval) => approval.agentId !== agentId);
+    const current = target.pendingApprovals.filter((approval) => approval.agentId === agentId);
+    const kept = pending.map(
+      (approval) =>
+        current.find((existing) => existing.requestId === approval.requestId) ?? approval,
+    );
+    const changed =
+      kept.length !== current.length ||
+      kept.some((approval, index) => approval.requestId !== current[index]?.requestId);
+    target.pendingApprovals = [...others, ...kept];
+    return changed;
+  }
+
+  function applyAgentStream(payload: Record<string, unknown>): JournalMutation | null {
+    const agentId = payload.agentId;
+    const event = payload.event;
+    if (typeof agentId !== "string" || !isRecord(event)) {
+      return null;
+    }
+    const parsedAt = typeof payload.timestamp === "string" ? Date.parse(payload.timestamp) : NaN;
+    const atMs = Number.isFinite(parsedAt) ? parsedAt : now();
+    const outcome = TURN_OUTCOMES[String(event.type)];
+    if (outcome) {
+      const prompt = lastPrompts.get(agentId) ?? null;
+      lastPrompts.delete(agentId);
+      return (target) => {
+        activityFor(target, agentId, atMs).tasks.push({
+          prompt,
+          endedAt: new Date(atMs).toISOString(),
+          outcome,
+        });
+        return true;
+      };
+    }
+    if (event.type !== "timeline" || !isRecord(event.item)) {
+      return null;
+    }
+    const item = event.item;
+    if (item.type === "user_message" && typeof item.text === "string") {
+      lastPrompts.set(agentId, truncatePrompt(item.text));
+      return null;
+    }
+    if (item.type !== "tool_call" || item.status !== "completed") {
+      return null;
+    }
+    const change = measureFileChange(item.detail);
+    if (!change) {
+      return null;
+    }
+    return (target) => {
+      const files = activityFor(target, agentId, atMs).files;
+      const existing = files.find((file) => file.path === change.path);
+      if (existing) {
+        existing.additions += change.additions;
+        existing.deletions += change.deletions;
+      } else {
+        files.push({ ...change });
+      }
+      return true;
+    };
+  }
+
+  /**
+   * Reads one daemon message into a change to the journal. The change is
+   * applied to the in-memory copy and, if that reports a difference, to the
+   * file on disk, which another instance may have written meanwhile.
+   */
+  function apply(message: Record<string, unknown>): JournalMutation | null {
+    const payload = isRecord(message.payload) ? message.payload : null;
+    if (!payload) {
+      return null;
+    }
+    switch (message.type) {
+      case "agent_update": {
+        if (payload.kind === "remove" && typeof payload.agentId === "string") {
+          const agentId = payload.agentId;
+          agentInfo.delete(agentId);
+          return (target) => setPendingApprovals(target, agentId, []);
+        }
+        const agent = isRecord(payload.agent) ? payload.agent : null;
+        if (payload.kind !== "upsert" || !agent || typeof agent.id !== "string") {
+          return null;
+        }
+        const agentId = agent.id;
+        agentInfo.set(agentId, {
+          title: optionalString(agent.title),
+          cwd: optionalString(agent.cwd),
+        });
+        if (!Array.isArray(agent.pendingPermissions)) {
+          return null;
+        }
+        const requestedAt = new Date(now()).toISOString();
+        const pending = agent.pendingPermissions.flatMap((request: unknown): PendingApproval[] =>
+          isRecord(request) && typeof request.id === "string"
+            ? [{ agentId, requestId: request.id, title: permissionTitle(request), requestedAt }]
+            : [],
+        );
+        return (target) => setPendingApprovals(target, agentId, pending);
+      }
+      case "agent_permission_request": {
+        const request = isRecord(payload.request) ? payload.request : null;
+        const agentId = payload.agentId;
+        if (typeof agentId !== "string" || !request || typeof request.id !== "string") {
+          return null;
+        }
+        const approval: PendingApproval = {
+          agentId,
+          requestId: request.id,
+          title: permissionTitle(request),
+          requestedAt: new Date(now()).toISOString(),
+        };
+        return (target) => {
+          if (
+            target.pendingApprovals.some(
+              (existing) =>
+                existing.agentId === approval.agentId &&
+                existing.requestId === approval.requestId,
+            )
+          ) {
+            return false;
+          }
+          target.pendingApprovals.push({ ...approval });
+          return true;
+        };
+      }
+      case "agent_permission_resolved":
+        return (target) => {
+          const before = target.pendingApprovals.length;
+          target.pendingApprovals = target.pendingApprovals.filter(
+            (approval) =>
+              approval.agentId !== payload.agentId || approval.requestId !== payload.requestId,
+          );
+          return target.pendingApprovals.length !== before;
+        };
+      case "agent_stream":
+        return applyAgentStream(payload);
+      default:
+        return null;
+    }
+  }
+
+  function enqueue(task: () => Promise<void>): Promise<void> {
+    queue = queue.catch(() => undefined).then(task);
+    return queue;
+  }
+
+  return {
+    observeInbound(text) {
+      const message = parseSessionMessage(text);
+      if (!message) {
+        return;
+      }
+      void enqueue(async () => {
+        await ensureLoaded();
+        const mutation = apply(message);
+        if (!mutation?.(document)) {
+          return;
+        }
+        document = await updateJsonConfigFile(filePath, (raw) => {
+          const current = coerceDocument(raw);
+          mutation(current);
+          return prune(current);
+        });
+      }).catch(() => undefined);
+    },
+
+    async getDay(date) {
+      await enqueue(ensureLoaded);
+      const endOfDay = new Date(`${date}T23:59:59.999`).getTime();
+      return {
+        date,
+        agents: structuredClone(document.days[date] ?? []),
+        pendingApprovals: document.pendingApprovals.filter(
+          (approval) => Date.parse(approval.requestedAt) <= endOfDay,
+        ),
+      };
+    },
+
+    async flush() {
+      await enqueue(ensureLoaded);
+    },
+  };
+}
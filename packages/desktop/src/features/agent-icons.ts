@@ -0,0 +1,96 @@
+import { nativeImage } from "electron";
+import {
+  type AgentIdentity,
+  coerceAgentIdentity,
+  renderAgentIconBitmap,
+  resolveFallbackAgentColor,
+} from "../system/agent-icons.js";
+
+interface KnownAgent {
+  identity: AgentIdentity;
+  label: string | null;
+}
+
+const agents = new Map<string, KnownAgent>();
+// Keyed by `${agentId}:${size}`; dropped per agent whenever its identity changes.
+const iconCache = new Map<string, Electron.NativeImage>();
+const listeners = new Set<() => void>();
+
+function dropCachedIcons(agentId: string): void {
+  for (const key of iconCache.keys()) {
+    if (key.startsWith(`${agentId}:`)) {
+      iconCache.delete(key);
+    }
+  }
+}
+
+/**
+ * The renderer reports each agent's color, shape and label so native
+ * surfaces draw the same identity it shows. `replace` forgets agents that
+ * are not in the list.
+ */
+export function setAgentIdentities(args: Record<string, unknown> | undefined): {
+  count: number;
+} {
+  if (!Array.isArray(args?.agents)) {
+    throw new Error("agents must be an array.");
+  }
+  const next = args.agents.map(coerceAgentIdentity);
+  if (args.replace === true) {
+    for (const agentId of agents.keys()) {
+      dropCachedIcons(agentId);
+    }
+    agents.clear();
+  }
+  for (const { agentId, identity, label } of next) {
+    dropCachedIcons(agentId);
+    agents.set(agentId, { identity, label });
+  }
+  for (const listener of listeners) {
+    listener();
+  }
+  return { count: agents.size };
+}
+
+export function getAgentIdentity(agentId: string): AgentIdentity {
+  return (
+    agents.get(agentId)?.identity ?? {
+      color: resolveFallbackAgentColor(agentId),
+      shape: "circle",
+      initial: null,
+    }
+  );
+}
+
+export function getAgentLabel(agentId: string): string | null {
+  return agents.get(agentId)?.label ?? null;
+}
+
+/** Rendered on first use at 1x and 2x, then reused until the identity changes. */
+export function getAgentIcon(agentId: string, size = 16): Electron.NativeImage {
+  const key = `${agentId}:${size}`;
+  const cached = iconCache.get(key);
+  if (cached) {
+    return cached;
+  }
+  const identity = getAgentIdentity(agentId);
+  const icon = nativeImage.createEmpty();
+  for (const scaleFactor of [1, 2]) {
+    const pixels = size * scaleFactor;
+    icon.addRepresentation({
+      scaleFactor,
+      width: pixels,
+      height: pixels,
+      buffer: renderAgentIconBitmap({ identity, size: pixels }),
+    });
+  }
+  iconCache.set(key, icon);
+  return icon;
+}
+
+export function onAgentIdentitiesChanged(listener: () => void): () => void {
+  listeners.add(listener);
+  return () => {
+    listeners.delete(listener);
+  };
+}
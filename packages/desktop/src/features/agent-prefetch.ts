@@ -0,0 +1,37 @@
+import {
+  getCachedLocalAgentTimeline,
+  prefetchLocalAgentTimeline,
+  type PrefetchTimelineResult,
+} from "../daemon/timeline-cache-electron.js";
+import type { CachedAgentTimeline } from "../daemon/timeline-cache.js";
+import { getAgentIcon } from "./agent-icons.js";
+import { NOTIFICATION_AGENT_ICON_SIZE } from "./notifications.js";
+
+const AGENT_ID_PATTERN = /^[A-Za-z0-9._:-]{1,128}$/;
+
+function coerceAgentId(args: Record<string, unknown> | undefined): string {
+  const agentId = args?.agentId;
+  if (typeof agentId !== "string" || !AGENT_ID_PATTERN.test(agentId)) {
+    throw new Error("agentId must be an agent id.");
+  }
+  return agentId;
+}
+
+/**
+ * The renderer calls this when the pointer rests on an agent, so its timeline
+ * and the icons the tray, menus and notifications draw are ready on open.
+ */
+export async function prefetchAgent(
+  args: Record<string, unknown> | undefined,
+): Promise<PrefetchTimelineResult> {
+  const agentId = coerceAgentId(args);
+  getAgentIcon(agentId);
+  getAgentIcon(agentId, NOTIFICATION_AGENT_ICON_SIZE);
+  return await prefetchLocalAgentTimeline(agentId);
+}
+
+export function getCachedAgentTimeline(
+  args: Record<string, unknown> | undefined,
+): CachedAgentTimeline | null {
+  return getCachedLocalAgentTimeline(coerceAgentId(args));
+}
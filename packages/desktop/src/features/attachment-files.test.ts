@@ -0,0 +1,103 @@
+import {
+  link,
+  lstat,
+  mkdir,
+  mkdtemp,
+  readFile,
+  readdir,
+  rm,
+  symlink,
+  writeFile,
+} from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+import {
+  copyIntoManagedFile,
+  listManagedFiles,
+  readManagedFile,
+  removeManagedFile,
+  writeManagedFile,
+} from "./attachment-files";
+import { resolveAttachmentPathInStorage } from "./attachment-storage";
+
+describe.skipIf(process.platform === "win32")("managed attachment files", () => {
+  let dir: string;
+  let root: string;
+  let outside: string;
+
+  beforeEach(async () => {
+    dir = await mkdtemp(path.join(os.tmpdir(), "paseo-attachment-files-"));
+    root = path.join(dir, "attachments");
+    outside = path.join(dir, "secret.txt");
+    await mkdir(root);
+    await writeFile(outside, "secret");
+  });
+
+  afterEach(async () => {
+    await rm(dir, { recursive: true, force: true });
+  });
+
+  it("replaces a planted symlink instead of writing through it", async () => {
+    const target = path.join(root, "a.png");
+    await symlink(outside, target);
+
+    await writeManagedFile(target, Buffer.from("image"));
+
+    expect(await readFile(outside, "utf8")).toBe("secret");
+    expect((await lstat(target)).isSymbolicLink()).toBe(false);
+    expect(await readFile(target, "utf8")).toBe("image");
+    expect(await readdir(root)).toEqual(["a.png"]);
+  });
+
+  it("copies over a planted symlink without touching its target", async () => {
+    const source = path.join(dir, "upload.png");
+    const target = path.join(root, "b.png");
+    await writeFile(source, "upload");
+    await symlink(outside, target);
+
+    await copyIntoManagedFile(source, target);
+
+    expect(await readFile(outside, "utf8")).toBe("secret");
+    expect(await readFile(target, "utf8")).toBe("upload");
+  });
+
+  it("refuses to read symlinks and hard links", async () => {
+    await symlink(outside, path.join(root, "link.png"));
+    await link(outside, path.join(root, "hard.png"));
+
+    await expect(readManagedFile(path.join(root, "link.png"))).rejects.toThrow("symbolic link");
+    await expect(readManagedFile(path.join(root, "hard.png"))).rejects.toThrow("hard link");
+  });
+
+  it("catches a symlink swapped in after the path was validated", async () => {
+    const target = path.join(root, "race.png");
+    await writeFile(target, "image");
+    const validated = resolveAttachmentPathInStorage(target, { root, previousRoots: [] });
+
+    await rm(target);
+    await symlink(outside, target);
+
+    await expect(readManagedFile(validated)).rejects.toThrow("symbolic link");
+  });
+
+  it("skips symlinks and in-flight temp files when listing for GC", async () => {
+    await writeFile(path.join(root, "keep.png"), "image");
+    await symlink(outside, path.join(root, "link.png"));
+    await mkdir(path.join(root, "nested"));
+    await writeFile(path.join(root, ".c.png.123e4567-e89b-12d3-a456-426614174000.tmp"), "");
+
+    expect(await listManagedFiles(root)).toEqual(["keep.png"]);
+  });
+
+  it("removes a symlink without deleting what it points at", async () => {
+    const target = path.join(root, "link.png");
+    await symlink(outside, target);
+
+    await removeManagedFile(target);
+
+    expect(await readdir(root)).toEqual([]);
+    expect(await readFile(outside, "utf8")).toBe("secret");
+    await expect(removeManagedFile(root)).rejects.toThrow("directory");
+  });
+});
@@ -0,0 +1,106 @@
+import { randomUUID } from "node:crypto";
+import { constants } from "node:fs";
+import { copyFile, lstat, open, readdir, rename, rm, writeFile } from "node:fs/promises";
+import path from "node:path";
+import { toFileSystemPath } from "../system/windows-paths.js";
+import { readGuardedHandle } from "./file-read-guard.js";
+
+// Missing on Windows, where the lstat check in readManagedFile is the only guard.
+const O_NOFOLLOW = constants.O_NOFOLLOW ?? 0;
+const TEMP_FILE_PATTERN = /^\..+\.[0-9a-f-]{36}\.tmp$/;
+
+function tempPathFor(targetPath: string): string {
+  return path.join(
+    path.dirname(targetPath),
+    `.${path.basename(targetPath)}.${randomUUID()}.tmp`,
+  );
+}
+
+/**
+ * Writes into a fresh exclusive temp file and renames it over the target.
+ * The rename replaces a planted symlink instead of writing through it.
+ */
+export async function writeManagedFile(targetPath: string, data: Uint8Array): Promise<void> {
+  const tempPath = toFileSystemPath(tempPathFor(targetPath));
+  try {
+    await writeFile(tempPath, data, { flag: "wx" });
+    await rename(tempPath, toFileSystemPath(targetPath));
+  } catch (error) {
+    await rm(tempPath, { force: true });
+    throw error;
+  }
+}
+
+/** Like writeManagedFile, for copying a user-picked source into storage. */
+export async function copyIntoManagedFile(sourcePath: string, targetPath: string): Promise<void> {
+  const tempPath = toFileSystemPath(tempPathFor(targetPath));
+  try {
+    await copyFile(toFileSystemPath(sourcePath), tempPath, constants.COPYFILE_EXCL);
+    await rename(tempPath, toFileSystemPath(targetPath));
+  } catch (error) {
+    await rm(tempPath, { force: true });
+    throw error;
+  }
+}
+
+/**
+ * Reads a managed file without following a symlink in its place, and
+ * refuses hard links, which could expose a file from outside storage.
+ * Checks run on the opened handle so a swap after validation is caught.
+ * Attachments may be binary; `maxBytes` still bounds what is read.
+ */
+export async function readManagedFile(
+  managedPath: string,
+  options: { maxBytes: number } = { maxBytes: Number.POSITIVE_INFINITY },
+): Promise<Buffer> {
+  const filePath = toFileSystemPath(managedPath);
+  const info = await lstat(filePath);
+  if (info.isSymbolicLink()) {
+    throw new Error("Attachment path must not be a symbolic link.");
+  }
+  const handle = await open(filePath, constants.O_RDONLY | O_NOFOLLOW).catch((error) => {
+    if ((error as NodeJS.ErrnoException).code === "ELOOP") {
+      throw new Error("Attachment path must not be a symbolic link.");
+    }
+    throw error;
+  });
+  try {
+    const opened = await handle.stat();
+    if (!opened.isFile()) {
+      throw new Error("Attachment path must be a regular file.");
+    }
+    if (opened.nlink > 1) {
+      throw new Error("Attachment path must not be a hard link.");
+    }
+    return await readGuardedHandle(handle, managedPath, {
+      maxBytes: options.maxBytes,
+      allowBinary: true,
+    });
+  } finally {
+    await handle.close();
+  }
+}
+
+/**
+ * Regular files directly in `dir`. Symlinks, directories and in-flight temp
+ * files are left out, so garbage collection never touches anything else.
+ */
+export async function listManagedFiles(dir: string): Promise<string[]> {
+  const entries = await readdir(toFileSystemPath(dir), { withFileTypes: true });
+  return entries
+    .filter((entry) => entry.isFile() && !TEMP_FILE_PATTERN.test(entry.name))
+    .map((entry) => entry.name);
+}
+
+/**
+ * Removes a managed file. `rm` unlinks a symlink or hard link itself and
+ * never what it points at; the lstat check still refuses directories.
+ */
+export async function removeManagedFile(managedPath: string): Promise<void> {
+  const filePath = toFileSystemPath(managedPath);
+  const info = await lstat(filePath).catch(() => null);
+  if (info?.isDirectory()) {
+    throw new Error("Attachment path must not be a directory.");
+  }
+  await rm(filePath, { force: true });
+}
@@ -0,0 +1,42 @@
+import path from "node:path";
+import { app } from "electron";
+import log from "electron-log/main";
+import { emitDesktopEvent } from "../window/desktop-events.js";
+import {
+  ATTACHMENT_STORAGE_INDEX_FILENAME,
+  type AttachmentRelocationResult,
+  readAttachmentStorageIndex,
+  relocateAttachmentStorage,
+  resolveRelocatedRoot,
+} from "./attachment-storage.js";
+import { attachmentsDirPath, configureAttachmentStorage } from "./attachments.js";
+
+let relocation: Promise<unknown> = Promise.resolve();
+
+function indexPath(): string {
+  return path.join(app.getPath("userData"), ATTACHMENT_STORAGE_INDEX_FILENAME);
+}
+
+export async function initializeAttachmentStorage(): Promise<void> {
+  configureAttachmentStorage(await readAttachmentStorageIndex(indexPath()));
+}
+
+export function setAttachmentStorageLocation(
+  chosenDir: unknown,
+): Promise<AttachmentRelocationResult> {
+  const run = async () => {
+    const result = await relocateAttachmentStorage({
+      indexPath: indexPath(),
+      from: attachmentsDirPath(),
+      to: resolveRelocatedRoot(chosenDir),
+    });
+    configureAttachmentStorage(await readAttachmentStorageIndex(indexPath()));
+    log.info("[attachments] storage relocated", result);
+    emitDesktopEvent("attachment-storage-changed", { root: result.root });
+    return result;
+  };
+  // One move at a time; a second request starts from wherever the first one landed.
+  const next = relocation.then(run, run);
+  relocation = next.catch(() => undefined);
+  return next;
+}
@@ -0,0 +1,109 @@
+import { mkdir, mkdtemp, readdir, readFile, rm, writeFile } from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+import {
+  attachmentNameKey,
+  findAttachmentNameCollision,
+  readAttachmentStorageIndex,
+  relocateAttachmentStorage,
+  resolveAttachmentPathInStorage,
+  resolveRelocatedRoot,
+} from "./attachment-storage";
+
+describe("resolveAttachmentPathInStorage", () => {
+  const storage = { root: "/volumes/big/paseo-attachments", previousRoots: ["/home/me/.paseo/a"] };
+
+  it("accepts files directly in the current root", () => {
+    expect(resolveAttachmentPathInStorage("/volumes/big/paseo-attachments/x.png", storage)).toBe(
+      "/volumes/big/paseo-attachments/x.png",
+    );
+  });
+
+  it("re-points paths saved under a previous root", () => {
+    expect(resolveAttachmentPathInStorage("/home/me/.paseo/a/x.png", storage)).toBe(
+      "/volumes/big/paseo-attachments/x.png",
+    );
+  });
+
+  it("rejects anything outside managed storage", () => {
+    for (const candidate of [
+      "/etc/passwd",
+      "/volumes/big/paseo-attachments/../secret.txt",
+      "/volumes/big/paseo-attachments/nested/x.png",
+      "/home/me/.paseo/a/../b/x.png",
+    ]) {
+      expect(() => resolveAttachmentPathInStorage(candidate, storage)).toThrow(
+        "desktop-managed storage",
+      );
+    }
+  });
+});
+
+describe("resolveRelocatedRoot", () => {
+  it("uses a dedicated subfolder of the chosen directory", () => {
+    expect(resolveRelocatedRoot("/volumes/big")).toBe(
+      path.join("/volumes/big", "paseo-attachments"),
+    );
+    expect(() => resolveRelocatedRoot("relative/dir")).toThrow("absolute path");
+  });
+});
+
+describe("relocateAttachmentStorage", () => {
+  let dir: string;
+
+  beforeEach(async () => {
+    dir = await mkdtemp(path.join(os.tmpdir(), "paseo-attachment-storage-"));
+  });
+
+  afterEach(async () => {
+    await rm(dir, { recursive: true, force: true });
+  });
+
+  it("moves every file and records the old root in the index", async () => {
+    const from = path.join(dir, "old");
+    const to = path.join(dir, "external", "paseo-attachments");
+    const indexPath = path.join(dir, "userData", "attachment-storage.json");
+    await mkdir(from);
+    await writeFile(path.join(from, "a.png"), "aaaa");
+    await writeFile(path.join(from, "b.txt"), "bb");
+
+    const result = await relocateAttachmentStorage({ indexPath, from, to });
+
+    expect(result).toEqual({ root: to, movedFiles: 2, movedBytes: 6 });
+    expect((await readdir(to)).sort()).toEqual(["a.png", "b.txt"]);
+    expect(await readFile(path.join(to, "a.png"), "utf8")).toBe("aaaa");
+    expect(await readdir(from)).toEqual([]);
+    expect(await readAttachmentStorageIndex(indexPath)).toEqual({
+      root: to,
+      previousRoots: [from],
+    });
+    expect(await readdir(path.dirname(indexPath))).toEqual(["attachment-storage.json"]);
+  });
+
+  it("refuses to nest the new root inside the current one", async () => {
+    const from = path.join(dir, "old");
+    await expect(
+      relocateAttachmentStorage({
+        indexPath: path.join(dir, "index.json"),
+        from,
+        to: path.join(from, "paseo-attachments"),
+      }),
+    ).rejects.toThrow("inside the current one");
+  });
+});
+
+describe("attachment name collisions", () => {
+  it("matches ids case-insensitively and across Unicode normalization forms", () => {
+    expect(attachmentNameKey("Cafe\u0301.PNG")).toBe(attachmentNameKey("caf\u00e9.png"));
+  });
+
+  it("flags a different spelling of an existing file but not the same file", () => {
+    const existing = ["Report.png", "café.png"];
+    expect(findAttachmentNameCollision(existing, "report.png")).toBe("Report.png");
+    expect(findAttachmentNameCollision(existing, "Report.png")).toBeNull();
+    expect(findAttachmentNameCollision(existing, "cafe\u0301.png")).toBeNull();
+    expect(findAttachmentNameCollision(existing, "CAFé.png")).toBe("café.png");
+    expect(findAttachmentNameCollision(existing, "other.png")).toBeNull();
+  });
+});
@@ -0,0 +1,154 @@
+import { mkdir, readFile, stat } from "node:fs/promises";
+import path from "node:path";
+import { toFileSystemPath } from "../system/windows-paths.js";
+import { copyIntoManagedFile, listManagedFiles, removeManagedFile } from "./attachment-files.js";
+import { updateJsonConfigFile } from "../settings/config-file.js";
+import { isRecord } from "../utils/is-record.js";
+
+export const ATTACHMENT_STORAGE_INDEX_FILENAME = "attachment-storage.json";
+// Attachment GC deletes every unreferenced file in the root, so the root is never
+// the chosen folder itself.
+export const RELOCATED_ATTACHMENTS_DIRNAME = "paseo-attachments";
+
+export interface AttachmentStorageIndex {
+  /** `null` keeps the default under the Paseo home. */
+  root: string | null;
+  /** Earlier roots, so attachment paths saved before a move still resolve. */
+  previousRoots: string[];
+}
+
+export interface AttachmentRelocationResult {
+  root: string;
+  movedFiles: number;
+  movedBytes: number;
+}
+
+function coerceAttachmentStorageIndex(parsed: unknown): AttachmentStorageIndex {
+  if (!isRecord(parsed)) {
+    return { root: null, previousRoots: [] };
+  }
+  return {
+    root: typeof parsed.root === "string" && path.isAbsolute(parsed.root) ? parsed.root : null,
+    previousRoots: Array.isArray(parsed.previousRoots)
+      ? parsed.previousRoots.filter(
+          (entry): entry is string => typeof entry === "string" && path.isAbsolute(entry),
+        )
+      : [],
+  };
+}
+
+export async function readAttachmentStorageIndex(
+  filePath: string,
+): Promise<AttachmentStorageIndex> {
+  try {
+    return coerceAttachmentStorageIndex(JSON.parse(await readFile(filePath, "utf8")));
+  } catch {
+    // Missing or unreadable: attachments stay in the default location.
+    return { root: null, previousRoots: [] };
+  }
+}
+
+function isWithin(parent: string, child: string): boolean {
+  const relative = path.relative(parent, child);
+  return relative === "" || (!relative.startsWith("..") && !path.isAbsolute(relative));
+}
+
+/**
+ * Maps a managed attachment path onto the current root. Paths under a
+ * previous root are re-pointed by file name; anything else is rejected.
+ */
+export function resolveAttachmentPathInStorage(
+  inputPath: string,
+  storage: { root: string; previousRoots: string[] },
+): string {
+  const resolvedPath = path.resolve(inputPath);
+  const root = path.resolve(storage.root);
+  if (path.dirname(resolvedPath) === root) {
+    return resolvedPath;
+  }
+  for (const previousRoot of storage.previousRoots) {
+    if (path.dirname(resolvedPath) === path.resolve(previousRoot)) {
+      return path.join(root, path.basename(resolvedPath));
+    }
+  }
+  throw new Error("Attachment path must stay within desktop-managed storage.");
+}
+
+/**
+ * Comparison key for attachment ids and file names. macOS volumes are
+ * case-insensitive and may hand back decomposed Unicode, so ids are matched
+ * on the NFC, lower-cased form everywhere to behave the same on Linux.
+ */
+export function attachmentNameKey(name: string): string {
+  return name.normalize("NFC").toLowerCase();
+}
+
+/** An existing file that `fileName` would silently overwrite on a case-insensitive volume. */
+export function findAttachmentNameCollision(
+  existingNames: string[],
+  fileName: string,
+): string | null {
+  const key = attachmentNameKey(fileName);
+  const normalized = fileName.normalize("NFC");
+  return (
+    existingNames.find(
+      (name) => name.normalize("NFC") !== normalized && attachmentNameKey(name) === key,
+    ) ?? null
+  );
+}
+
+/** The directory attachments move into when the user picks `chosenDir`. */
+export function resolveRelocatedRoot(chosenDir: unknown): string {
+  if (typeof chosenDir !== "string" || !path.isAbsolute(chosenDir.trim())) {
+    throw new Error("Attachment storage location must be an absolute path.");
+  }
+  return path.join(path.resolve(chosenDir.trim()), RELOCATED_ATTACHMENTS_DIRNAME);
+}
+
+/**
+ * Copies every attachment into `to`, points the index at it in one atomic
+ * write, and only then removes the originals. An interruption before the
+ * index write leaves the old root intact and in use.
+ */
+export async function relocateAttachmentStorage(input: {
+  indexPath: string;
+  from: string;
+  to: string;
+}): Promise<AttachmentRelocationResult> {
+  const from = path.resolve(input.from);
+  const to = path.resolve(input.to);
+  if (from === to) {
+    return { root: to, movedFiles: 0, movedBytes: 0 };
+  }
+  if (isWithin(from, to) || isWithin(to, from)) {
+    throw new Error("The new attachment location cannot be inside the current one.");
+  }
+
+  await mkdir(toFileSystemPath(to), { recursive: true });
+  const files = await listManagedFiles(from).catch(() => []);
+  let movedBytes = 0;
+  for (const name of files) {
+    const source = path.join(from, name);
+    const target = path.join(to, name);
+    await copyIntoManagedFile(source, target);
+    const [sourceInfo, targetInfo] = await Promise.all([
+      stat(toFileSystemPath(source)),
+      stat(toFileSystemPath(target)),
+    ]);
+    if (sourceInfo.size !== targetInfo.size) {
+      throw new Error(`Attachment copy is incomplete: ${name}`);
+    }
+    movedBytes += sourceInfo.size;
+  }
+
+  await updateJsonConfigFile(input.indexPath, (raw): AttachmentStorageIndex => {
+    const index = coerceAttachmentStorageIndex(raw);
+    return {
+      root: to,
+      previousRoots: [...new Set([...index.previousRoots, from])].filter((entry) => entry !== to),
+    };
+  });
+
+  await Promise.all(files.map((name) => removeManagedFile(path.join(from, name))));
+  return { root: to, movedFiles: files.length, movedBytes };
+}
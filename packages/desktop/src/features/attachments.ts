@@ -1,9 +1,23 @@
-import { copyFile, mkdir, readFile, readdir, rm, stat, writeFile } from "node:fs/promises";
+import { mkdir, stat } from "node:fs/promises";
 import path from "node:path";
 import { resolvePaseoHome } from "@getpaseo/server";
+import { fromFileSystemPath, toFileSystemPath } from "../system/windows-paths.js";
+import {
+  copyIntoManagedFile,
+  listManagedFiles,
+  readManagedFile,
+  removeManagedFile,
+  writeManagedFile,
+} from "./attachment-files.js";
+import {
+  attachmentNameKey,
+  type AttachmentStorageIndex,
+  findAttachmentNameCollision,
+  resolveAttachmentPathInStorage,
+} from "./attachment-storage.js";
 
 const ATTACHMENTS_DIRNAME = "desktop-attachments";
-const ATTACHMENT_ID_PATTERN = /^[A-Za-z0-9_-]+$/;
+const ATTACHMENT_ID_PATTERN = /^[\p{L}\p{N}_-]+$/u;
 const EXTENSION_PATTERN = /^\.[A-Za-z0-9]{1,16}$/;
 
 interface AttachmentFileResult {
@@ -11,13 +25,24 @@ interface AttachmentFileResult {
   byteSize: number;
 }
 
-function attachmentsDirPath(): string {
+let storageIndex: AttachmentStorageIndex = { root: null, previousRoots: [] };
+
+function defaultAttachmentsDirPath(): string {
   return path.join(resolvePaseoHome(process.env), ATTACHMENTS_DIRNAME);
 }
 
+export function attachmentsDirPath(): string {
+  return storageIndex.root ?? defaultAttachmentsDirPath();
+}
+
+/** Points managed storage at a relocated root; see attachment-storage.ts. */
+export function configureAttachmentStorage(index: AttachmentStorageIndex): void {
+  storageIndex = index;
+}
+
 async function ensureAttachmentsDir(): Promise<string> {
   const dirPath = attachmentsDirPath();
-  await mkdir(dirPath, { recursive: true });
+  await mkdir(toFileSystemPath(dirPath), { recursive: true });
   return dirPath;
 }
 
@@ -25,7 +50,7 @@ function normalizeAttachmentId(value: unknown): string {
   if (typeof value !== "string") {
     throw new Error("Attachment id is required.");
   }
-  const normalized = value.trim();
+  const normalized = value.trim().normalize("NFC");
   if (!ATTACHMENT_ID_PATTERN.test(normalized)) {
     throw new Error(`Invalid attachment id: ${value}`);
   }
@@ -53,19 +78,22 @@ async function buildManagedAttachmentPath(input: {
   const dirPath = await ensureAttachmentsDir();
   const attachmentId = normalizeAttachmentId(input.attachmentId);
   const extension = normalizeExtension(input.extension);
-  return path.join(dirPath, `${attachmentId}${extension}`);
+  const fileName = `${attachmentId}${extension}`;
+  const collision = findAttachmentNameCollision(await listManagedFiles(dirPath), fileName);
+  if (collision) {
+    throw new Error(`Attachment ${fileName} collides with existing ${collision}.`);
+  }
+  return path.join(dirPath, fileName);
 }
 
-function resolveManagedAttachmentPath(inputPath: unknown): string {
+export function resolveManagedAttachmentPath(inputPath: unknown): string {
   if (typeof inputPath !== "string" || inputPath.trim().length === 0) {
     throw new Error("Attachment path is required.");
   }
-  const resolvedDir = `${path.resolve(attachmentsDirPath())}${path.sep}`;
-  const resolvedPath = path.resolve(inputPath.trim());
-  if (!resolvedPath.startsWith(resolvedDir)) {
-    throw new Error("Attachment path must stay within desktop-managed storage.");
-  }
-  return resolvedPath;
+  return resolveAttachmentPathInStorage(fromFileSystemPath(inputPath.trim()), {
+    root: attachmentsDirPath(),
+    previousRoots: storageIndex.previousRoots,
+  });
 }
 
 export async function writeAttachmentBase64(input: {
@@ -82,8 +110,8 @@ export async function writeAttachmentBase64(input: {
     attachmentId: input.attachmentId,
     extension: input.extension,
   });
-  await writeFile(targetPath, Buffer.from(base64, "base64"));
-  const fileInfo = await stat(targetPath);
+  await writeManagedFile(targetPath, Buffer.from(base64, "base64"));
+  const fileInfo = await stat(toFileSystemPath(targetPath));
   return {
     path: targetPath,
     byteSize: fileInfo.size,
@@ -113,8 +141,8 @@ export async function writeAttachmentBytes(input: {
     attachmentId: input.attachmentId,
     extension: input.extension,
   });
-  await writeFile(targetPath, bytes);
-  const fileInfo = await stat(targetPath);
+  await writeManagedFile(targetPath, bytes);
+  const fileInfo = await stat(toFileSystemPath(targetPath));
   return {
     path: targetPath,
     byteSize: fileInfo.size,
@@ -130,54 +158,77 @@ export async function copyAttachmentFileToManagedStorage(input: {
     throw new Error("Attachment source path is required.");
   }
 
-  const sourcePath = path.resolve(input.sourcePath.trim());
+  const sourcePath = path.resolve(fromFileSystemPath(input.sourcePath.trim()));
   const targetPath = await buildManagedAttachmentPath({
     attachmentId: input.attachmentId,
     extension: input.extension,
   });
 
   if (sourcePath !== targetPath) {
-    await copyFile(sourcePath, targetPath);
+    await copyIntoManagedFile(sourcePath, targetPath);
   }
 
-  const fileInfo = await stat(targetPath);
+  const fileInfo = await stat(toFileSystemPath(targetPath));
   return {
     path: targetPath,
     byteSize: fileInfo.size,
   };
 }
 
-export async function readManagedFileBase64(input: { path?: unknown }): Promise<string> {
+/** Base64 grows the payload by a third, so `maxBytes` keeps huge files off the IPC channel. */
+export async function readManagedFileBase64(
+  input: { path?: unknown },
+  options: { maxBytes: number },
+): Promise<string> {
   const filePath = resolveManagedAttachmentPath(input.path);
-  const bytes = await readFile(filePath);
+  const bytes = await readManagedFile(filePath, options);
   return bytes.toString("base64");
 }
 
-export async function deleteManagedAttachmentFile(input: { path?: unknown }): Promise<boolean> {
+/**
+ * Sends the attachment to the OS trash so an accidental delete is
+ * recoverable; `permanent: true` removes it outright, through `remove`.
+ */
+export async function deleteManagedAttachmentFile(
+  input: { path?: unknown; permanent?: unknown },
+  trash: (filePath: string) => Promise<void>,
+  remove: (filePath: string) => Promise<void> = removeManagedFile,
+): Promise<boolean> {
   const filePath = resolveManagedAttachmentPath(input.path);
-  await rm(filePath, { force: true });
+  if (input.permanent === true) {
+    await remove(filePath);
+  } else {
+    await trash(filePath);
+  }
   return true;
 }
 
-export async function garbageCollectManagedAttachmentFiles(input: {
-  referencedIds?: unknown;
-}): Promise<number> {
+async function removeManagedFiles(filePaths: string[]): Promise<void> {
+  await Promise.all(filePaths.map((filePath) => removeManagedFile(filePath)));
+}
+
+export async function garbageCollectManagedAttachmentFiles(
+  input: { referencedIds?: unknown },
+  removeAll: (filePaths: string[]) => Promise<void> = removeManagedFiles,
+): Promise<number> {
   const dirPath = await ensureAttachmentsDir();
   const referencedIds = Array.isArray(input.referencedIds)
     ? new Set(
         input.referencedIds
           .filter((value): value is string => typeof value === "string")
-          .map((value) => value.trim())
-          .filter((value) => ATTACHMENT_ID_PATTERN.test(value)),
+          .map((value) => value.trim().normalize("NFC"))
+          .filter((value) => ATTACHMENT_ID_PATTERN.test(value))
+          .map(attachmentNameKey),
       )
     : new Set<string>();
 
-  const entries = await readdir(dirPath, { withFileTypes: true });
-  const toDelete = entries.filter(
-    (entry) => entry.isFile() && !referencedIds.has(path.parse(entry.name).name),
+  const toDelete = (await listManagedFiles(dirPath)).filter(
+    (name) => !referencedIds.has(attachmentNameKey(path.parse(name).name)),
   );
 
-  await Promise.all(toDelete.map((entry) => rm(path.join(dirPath, entry.name), { force: true })));
+  if (toDelete.length > 0) {
+    await removeAll(toDelete.map((name) => path.join(dirPath, name)));
+  }
 
   return toDelete.length;
 }
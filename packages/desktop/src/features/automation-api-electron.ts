@@ -0,0 +1,224 @@
+import { randomBytes } from "node:crypto";
+import { chmod, mkdir, rm } from "node:fs/promises";
+import http from "node:http";
+import type { AddressInfo } from "node:net";
+import path from "node:path";
+import { app } from "electron";
+import log from "electron-log/main";
+import { writeJsonConfigFile } from "../settings/config-file.js";
+import type { DesktopSettings } from "../settings/desktop-settings.js";
+import { getDesktopSettingsStore } from "../settings/desktop-settings-electron.js";
+import { emitDesktopEvent } from "../window/desktop-events.js";
+import {
+  AUTOMATION_API_VERSION,
+  AUTOMATION_DISCOVERY_FILENAME,
+  type AutomationAction,
+  type AutomationDiscovery,
+  AutomationRequestError,
+  isAuthorizedAutomationRequest,
+  MAX_AUTOMATION_BODY_BYTES,
+  parseAutomationRequest,
+  resolveAutomationEndpoint,
+} from "./automation-api.js";
+import { applyStatusPopoverSettings, focusMainWindow } from "./status-popover.js";
+
+interface RunningAutomationServer {
+  server: http.Server;
+  discovery: AutomationDiscovery;
+}
+
+let running: Promise<RunningAutomationServer> | null = null;
+let quitHookInstalled = false;
+
+function getDiscoveryPath(): string {
+  return path.join(app.getPath("userData"), AUTOMATION_DISCOVERY_FILENAME);
+}
+
+function readBody(request: http.IncomingMessage): Promise<string> {
+  return new Promise((resolve, reject) => {
+    const chunks: Buffer[] = [];
+    let size = 0;
+    request.on("data", (chunk: Buffer) => {
+      size += chunk.byteLength;
+      if (size > MAX_AUTOMATION_BODY_BYTES) {
+        reject(new AutomationRequestError(413, "Request body is too large."));
+        request.destroy();
+        return;
+      }
+      chunks.push(chunk);
+    });
+    request.on("end", () => resolve(Buffer.concat(chunks).toString("utf8")));
+    request.on("error", reject);
+  });
+}
+
+function requireMainWindow(): void {
+  if (!focusMainWindow()) {
+    throw new AutomationRequestError(409, "The main window is not open.");
+  }
+}
+
+async function runAutomationAction(action: AutomationAction): Promise<Record<string, unknown>> {
+  switch (action.kind) {
+    case "status":
+      return { version: AUTOMATION_API_VERSION, appVersion: app.getVersion() };
+    case "focus":
+      requireMainWindow();
+      return {};
+    case "open-agent":
+      requireMainWindow();
+      emitDesktopEvent("automation-open-agent", { agentId: action.agentId });
+      return {};
+    case "send-prompt":
+      // The renderer owns the composer, so it decides where the text lands and sends it.
+      requireMainWindow();
+      emitDesktopEvent("automation-send-prompt", { agentId: action.agentId, text: action.text });
+      return {};
+    case "set-tray-mode": {
+      const store = getDesktopSettingsStore();
+      const enabled = action.enabled ?? !(await store.get()).statusPopover.enabled;
+      const settings = await store.patch({ statusPopover: { enabled } });
+      applyStatusPopoverSettings(settings.statusPopover);
+      return { enabled: settings.statusPopover.enabled };
+    }
+  }
+}
+
+function sendJson(response: http.ServerResponse, status: number, body: unknown): void {
+  response.writeHead(status, { "content-type": "application/json" });
+  response.end(JSON.stringify(body));
+}
+
+async function handleRequest(
+  token: string,
+  request: http.IncomingMessage,
+  response: http.ServerResponse,
+): Promise<void> {
+  try {
+    // Scripts never send an Origin; a browser tab probing localhost always does.
+    if (request.headers.origin !== undefined) {
+      throw new AutomationRequestError(403, "Browser requests are not allowed.");
+    }
+    if (!isAuthorizedAutomationRequest(request.headers.authorization, token)) {
+      throw new AutomationRequestError(401, "Missing or wrong token.");
+    }
+    const action = parseAutomationRequest({
+      method: request.method ?? "GET",
+      url: request.url ?? "/",
+      body: await readBody(request),
+    });
+    sendJson(response, 200, { ok: true, ...(await runAutomationAction(action)) });
+  } catch (error) {
+    const status = error instanceof AutomationRequestError ? error.status : 500;
+    if (status === 500) {
+      log.warn("[automation] request failed", error);
+    }
+    sendJson(response, status, {
+      ok: false,
+      error: error instanceof Error ? error.message : String(error),
+    });
+  }
+}
+
+async function startServer(): Promise<RunningAutomationServer> {
+  const token = randomBytes(32).toString("base64url");
+  const endpoint = resolveAutomationEndpoint({
+    platform: process.platform,
+    userDataPath: app.getPath("userData"),
+  });
+  const server = http.createServer((request, response) => {
+    void handleRequest(token, request, response);
+  });
+
+  let discovery: AutomationDiscovery;
+  if (endpoint.transport === "socket") {
+    // The socket inherits the umask, so it is only private because its directory is.
+    const socketDir = path.dirname(endpoint.socketPath);
+    await mkdir(socketDir, { recursive: true, mode: 0o700 });
+    await chmod(socketDir, 0o700);
+    // A socket left behind by a crash would make listen fail with EADDRINUSE.
+    await rm(endpoint.socketPath, { force: true });
+    await new Promise<void>((resolve, reject) => {
+      server.once("error", reject);
+      server.listen(endpoint.socketPath, resolve);
+    });
+    discovery = {
+      version: AUTOMATION_API_VERSION,
+      transport: "socket",
+      socketPath: endpoint.socketPath,
+      url: null,
+      token,
+    };
+  } else {
+    await new Promise<void>((resolve, reject) => {
+      server.once("error", reject);
+      server.listen(0, endpoint.host, resolve);
+    });
+    const { port } = server.address() as AddressInfo;
+    discovery = {
+      version: AUTOMATION_API_VERSION,
+      transport: "tcp",
+      socketPath: null,
+      url: `http://${endpoint.host}:${port}`,
+      token,
+    };
+  }
+  await writeJsonConfigFile(getDiscoveryPath(), discovery, { mode: 0o600 });
+  log.info(`[automation] listening on ${discovery.socketPath ?? discovery.url}`);
+  return { server, discovery };
+}
+
+async function stopServer(): Promise<void> {
+  const current = running;
+  running = null;
+  if (!current) {
+    return;
+  }
+  const { server, discovery } = await current.catch(() => ({ server: null, discovery: null }));
+  await rm(getDiscoveryPath(), { force: true });
+  if (!server) {
+    return;
+  }
+  const closed = new Promise<void>((resolve) => server.close(() => resolve()));
+  server.closeAllConnections();
+  await closed;
+  if (discovery?.socketPath) {
+    await rm(discovery.socketPath, { force: true });
+  }
+  log.info("[automation] stopped");
+}
+
+/** Starts or stops the control API to match the setting. */
+export async function applyAutomationSettings(
+  settings: DesktopSettings["automation"],
+): Promise<void> {
+  if (!settings.enabled) {
+    await stopServer();
+    return;
+  }
+  if (running) {
+    return;
+  }
+  const starting = startServer();
+  running = starting;
+  starting.catch(() => {
+    if (running === starting) {
+      running = null;
+    }
+  });
+  if (!quitHookInstalled) {
+    quitHookInstalled = true;
+    app.once("will-quit", () => {
+      void stopServer();
+    });
+  }
+  await starting;
+}
+
+/** Where scripts should connect, or `null` while the API is off. */
+export async function getAutomationEndpoint(): Promise<AutomationDiscovery | null> {
+  if (!running) {
+    return null;
+  }
+  return (await running).discovery;
+}
@@ -0,0 +1,95 @@
+import path from "node:path";
+import { describe, expect, it } from "vitest";
+import {
+  AutomationRequestError,
+  isAuthorizedAutomationRequest,
+  parseAutomationRequest,
+  resolveAutomationEndpoint,
+} from "./automation-api";
+
+function statusOf(run: () => unknown): number | null {
+  try {
+    run();
+    return null;
+  } catch (error) {
+    return error instanceof AutomationRequestError ? error.status : -1;
+  }
+}
+
+describe("automation api", () => {
+  it("routes the supported actions", () => {
+    expect(parseAutomationRequest({ method: "GET", url: "/v1/status", body: "" })).toEqual({
+      kind: "status",
+    });
+    expect(parseAutomationRequest({ method: "post", url: "/v1/focus", body: "" })).toEqual({
+      kind: "focus",
+    });
+    expect(
+      parseAutomationRequest({ method: "POST", url: "/v1/agents/agent-7/open", body: "" }),
+    ).toEqual({ kind: "open-agent", agentId: "agent-7" });
+    expect(
+      parseAutomationRequest({
+        method: "POST",
+        url: "/v1/prompt",
+        body: JSON.stringify({ text: "Run the tests", agentId: "agent-7" }),
+      }),
+    ).toEqual({ kind: "send-prompt", agentId: "agent-7", text: "Run the tests" });
+    expect(parseAutomationRequest({ method: "POST", url: "/v1/tray", body: "" })).toEqual({
+      kind: "set-tray-mode",
+      enabled: null,
+    });
+    expect(
+      parseAutomationRequest({ method: "POST", url: "/v1/tray?x=1", body: '{"enabled":true}' }),
+    ).toEqual({ kind: "set-tray-mode", enabled: true });
+  });
+
+  it("rejects unknown routes, wrong methods and bad bodies", () => {
+    expect(statusOf(() => parseAutomationRequest({ method: "GET", url: "/v2", body: "" }))).toBe(
+      404,
+    );
+    expect(
+      statusOf(() => parseAutomationRequest({ method: "GET", url: "/v1/focus", body: "" })),
+    ).toBe(405);
+    expect(
+      statusOf(() => parseAutomationRequest({ method: "POST", url: "/v1/prompt", body: "{" })),
+    ).toBe(400);
+    expect(
+      statusOf(() =>
+        parseAutomationRequest({ method: "POST", url: "/v1/prompt", body: '{"text":"  "}' }),
+      ),
+    ).toBe(400);
+    expect(
+      statusOf(() =>
+        parseAutomationRequest({
+          method: "POST",
+          url: "/v1/prompt",
+          body: JSON.stringify({ text: "x".repeat(40_000) }),
+        }),
+      ),
+    ).toBe(413);
+    expect(
+      statusOf(() =>
+        parseAutomationRequest({ method: "POST", url: "/v1/agents/a%2Fb/open", body: "" }),
+      ),
+    ).toBe(400);
+  });
+
+  it("only accepts the exact bearer token", () => {
+    expect(isAuthorizedAutomationRequest("Bearer secret-token", "secret-token")).toBe(true);
+    expect(isAuthorizedAutomationRequest("bearer secret-token", "secret-token")).toBe(true);
+    expect(isAuthorizedAutomationRequest("Bearer secret-tokex", "secret-token")).toBe(false);
+    expect(isAuthorizedAutomationRequest("Bearer secret", "secret-token")).toBe(false);
+    expect(isAuthorizedAutomationRequest(undefined, "secret-token")).toBe(false);
+  });
+
+  it("uses a unix socket except on Windows", () => {
+    expect(resolveAutomationEndpoint({ platform: "darwin", userDataPath: "/data" })).toEqual({
+      transport: "socket",
+      socketPath: path.join("/data", "automation", "automation.sock"),
+    });
+    expect(resolveAutomationEndpoint({ platform: "win32", userDataPath: "C:\\data" })).toEqual({
+      transport: "tcp",
+      host: "127.0.0.1",
+    });
+  });
+});
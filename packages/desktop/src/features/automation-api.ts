@@ -0,0 +1,162 @@
+import { timingSafeEqual } from "node:crypto";
+import path from "node:path";
+import { isRecord } from "../utils/is-record.js";
+
+export const AUTOMATION_API_VERSION = 1;
+export const AUTOMATION_DISCOVERY_FILENAME = "automation.json";
+const AUTOMATION_SOCKET_DIRNAME = "automation";
+const AUTOMATION_SOCKET_FILENAME = "automation.sock";
+export const MAX_AUTOMATION_BODY_BYTES = 64 * 1024;
+const MAX_PROMPT_CHARS = 32_000;
+const AGENT_ID_PATTERN = /^[A-Za-z0-9._:-]{1,128}$/;
+
+export type AutomationAction =
+  | { kind: "status" }
+  | { kind: "focus" }
+  | { kind: "open-agent"; agentId: string }
+  | { kind: "send-prompt"; agentId: string | null; text: string }
+  /** `enabled: null` flips the current state. */
+  | { kind: "set-tray-mode"; enabled: boolean | null };
+
+/**
+ * A unix socket where there is one; Windows gets a loopback port instead.
+ * Both also require the token from the discovery file.
+ */
+export type AutomationEndpoint =
+  | { transport: "socket"; socketPath: string }
+  | { transport: "tcp"; host: "127.0.0.1" };
+
+/** Written next to the settings with owner-only permissions for scripts to read. */
+export interface AutomationDiscovery {
+  version: typeof AUTOMATION_API_VERSION;
+  transport: "socket" | "tcp";
+  socketPath: string | null;
+  url: string | null;
+  token: string;
+}
+
+export class AutomationRequestError extends Error {
+  readonly status: number;
+
+  constructor(status: number, message: string) {
+    super(message);
+    this.name = "AutomationRequestError";
+    this.status = status;
+  }
+}
+
+export function resolveAutomationEndpoint(input: {
+  platform: NodeJS.Platform;
+  userDataPath: string;
+}): AutomationEndpoint {
+  if (input.platform === "win32") {
+    return { transport: "tcp", host: "127.0.0.1" };
+  }
+  return {
+    transport: "socket",
+    socketPath: path.join(
+      input.userDataPath,
+      AUTOMATION_SOCKET_DIRNAME,
+      AUTOMATION_SOCKET_FILENAME,
+    ),
+  };
+}
+
+/** Constant-time check of `Authorization: Bearer <token>`. */
+export function isAuthorizedAutomationRequest(
+  authorization: string | undefined,
+  token: string,
+): boolean {
+  const match = /^Bearer\s+(\S+)$/i.exec(authorization ?? "");
+  if (!match) {
+    return false;
+  }
+  const given = Buffer.from(match[1]!);
+  const expected = Buffer.from(token);
+  return given.length === expected.length && timingSafeEqual(given, expected);
+}
+
+function parseJsonBody(body: string): Record<string, unknown> {
+  if (body.trim().length === 0) {
+    return {};
+  }
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(body);
+  } catch {
+    throw new AutomationRequestError(400, "Body must be JSON.");
+  }
+  if (!isRecord(parsed)) {
+    throw new AutomationRequestError(400, "Body must be a JSON object.");
+  }
+  return parsed;
+}
+
+function coerceAgentId(value: unknown): string {
+  const agentId = typeof value === "string" ? value.trim() : "";
+  if (!AGENT_ID_PATTERN.test(agentId)) {
+    throw new AutomationRequestError(400, "Invalid agent id.");
+  }
+  return agentId;
+}
+
+/**
+ * Routes of the control API:
+ *
+ * - `GET /v1/status`
+ * - `POST /v1/focus`
+ * - `POST /v1/agents/<agentId>/open`
+ * - `POST /v1/prompt` with `{ "text": "...", "agentId"?: "..." }`
+ * - `POST /v1/tray` with `{ "enabled"?: boolean }`; without it the tray mode flips
+ */
+export function parseAutomationRequest(input: {
+  method: string;
+  url: string;
+  body: string;
+}): AutomationAction {
+  const { pathname } = new URL(input.url, "http://automation.invalid");
+  const method = input.method.toUpperCase();
+  const route = (expected: string) => {
+    if (method !== expected) {
+      throw new AutomationRequestError(405, `${pathname} expects ${expected}.`);
+    }
+  };
+
+  if (pathname === "/v1/status") {
+    route("GET");
+    return { kind: "status" };
+  }
+  if (pathname === "/v1/focus") {
+    route("POST");
+    return { kind: "focus" };
+  }
+  const openAgent = /^\/v1\/agents\/([^/]+)\/open$/.exec(pathname);
+  if (openAgent) {
+    route("POST");
+    return { kind: "open-agent", agentId: coerceAgentId(openAgent[1]) };
+  }
+  if (pathname === "/v1/prompt") {
+    route("POST");
+    const { text, agentId } = parseJsonBody(input.body);
+    if (typeof text !== "string" || text.trim().length === 0) {
+      throw new AutomationRequestError(400, "text is required.");
+    }
+    if (text.length > MAX_PROMPT_CHARS) {
+      throw new AutomationRequestError(413, `text is limited to ${MAX_PROMPT_CHARS} characters.`);
+    }
+    return {
+      kind: "send-prompt",
+      agentId: agentId === undefined || agentId === null ? null : coerceAgentId(agentId),
+      text,
+    };
+  }
+  if (pathname === "/v1/tray") {
+    route("POST");
+    const { enabled } = parseJsonBody(input.body);
+    if (enabled !== undefined && typeof enabled !== "boolean") {
+      throw new AutomationRequestError(400, "enabled must be true or false.");
+    }
+    return { kind: "set-tray-mode", enabled: enabled ?? null };
+  }
+  throw new AutomationRequestError(404, `No route for ${pathname}.`);
+}
@@ -0,0 +1,65 @@
+import { describe, expect, it } from "vitest";
+import { buildFileClipboardFormat, coerceClipboardContent } from "./clipboard-content";
+
+describe("coerceClipboardContent", () => {
+  it("accepts text, image, and file content with or without a content wrapper", () => {
+    expect(coerceClipboardContent({ content: { kind: "text", text: "hello" } })).toEqual({
+      kind: "text",
+      text: "hello",
+    });
+    expect(coerceClipboardContent({ kind: "image", base64: " aGk= " })).toEqual({
+      kind: "image",
+      base64: "aGk=",
+      path: null,
+    });
+    expect(coerceClipboardContent({ kind: "file", path: "/tmp/report.pdf" })).toEqual({
+      kind: "file",
+      paths: ["/tmp/report.pdf"],
+    });
+  });
+
+  it("rejects incomplete or relative payloads", () => {
+    expect(() => coerceClipboardContent({ kind: "image" })).toThrow(/base64 bytes or a path/);
+    expect(() => coerceClipboardContent({ kind: "file", paths: ["notes.txt"] })).toThrow(
+      /absolute paths/,
+    );
+    expect(() => coerceClipboardContent({ kind: "html" })).toThrow(/Unsupported/);
+  });
+});
+
+describe("buildFileClipboardFormat", () => {
+  it("writes a filename plist on macOS", () => {
+    const { format, data } = buildFileClipboardFormat({
+      paths: ["/Users/me/a&b.png"],
+      platform: "darwin",
+    });
+
+    expect(format).toBe("NSFilenamesPboardType");
+    expect(data.toString("utf8")).toContain(
+      "<array><string>/Users/me/a&amp;b.png</string></array>",
+    );
+  });
+
+  it("writes a null-terminated UTF-16 path on Windows and refuses multiple files", () => {
+    const { format, data } = buildFileClipboardFormat({
+      paths: ["C:\\a.txt"],
+      platform: "win32",
+    });
+
+    expect(format).toBe("FileNameW");
+    expect(data.toString("utf16le")).toBe("C:\\a.txt\0");
+    expect(() =>
+      buildFileClipboardFormat({ paths: ["C:\\a.txt", "C:\\b.txt"], platform: "win32" }),
+    ).toThrow();
+  });
+
+  it("writes a file URI list elsewhere", () => {
+    const { format, data } = buildFileClipboardFormat({
+      paths: ["/home/me/my file.txt", "/tmp/b.txt"],
+      platform: "linux",
+    });
+
+    expect(format).toBe("text/uri-list");
+    expect(data.toString("utf8")).toBe("file:///home/me/my%20file.txt\r\nfile:///tmp/b.txt\r\n");
+  });
+});
@@ -0,0 +1,94 @@
+import path from "node:path";
+import { pathToFileURL } from "node:url";
+import { isRecord } from "../utils/is-record.js";
+
+export type ClipboardContent =
+  | { kind: "text"; text: string }
+  | { kind: "image"; base64: string | null; path: string | null }
+  | { kind: "file"; paths: string[] };
+
+export interface ClipboardBufferFormat {
+  format: string;
+  data: Buffer;
+}
+
+function escapeXml(value: string): string {
+  return value
+    .replace(/&/g, "&amp;")
+    .replace(/</g, "&lt;")
+    .replace(/>/g, "&gt;")
+    .replace(/"/g, "&quot;");
+}
+
+export function coerceClipboardContent(
+  args: Record<string, unknown> | undefined,
+): ClipboardContent {
+  const content = isRecord(args?.content) ? args.content : args;
+  switch (content?.kind) {
+    case "text":
+      if (typeof content.text !== "string") {
+        throw new Error("Clipboard text is required.");
+      }
+      return { kind: "text", text: content.text };
+    case "image": {
+      const base64 =
+        typeof content.base64 === "string" && content.base64.trim().length > 0
+          ? content.base64.trim()
+          : null;
+      const imagePath =
+        typeof content.path === "string" && content.path.trim().length > 0
+          ? content.path.trim()
+          : null;
+      if (!base64 && !imagePath) {
+        throw new Error("Clipboard image needs base64 bytes or a path.");
+      }
+      return { kind: "image", base64, path: imagePath };
+    }
+    case "file": {
+      const paths = Array.isArray(content.paths)
+        ? content.paths.filter((value): value is string => typeof value === "string")
+        : typeof content.path === "string"
+          ? [content.path]
+          : [];
+      const resolved = paths.map((value) => value.trim()).filter((value) => value.length > 0);
+      if (resolved.length === 0) {
+        throw new Error("Clipboard file references need at least one path.");
+      }
+      if (!resolved.every((value) => path.isAbsolute(value))) {
+        throw new Error("Clipboard file references must be absolute paths.");
+      }
+      return { kind: "file", paths: resolved };
+    }
+    default:
+      throw new Error(`Unsupported clipboard content kind: ${String(content?.kind)}`);
+  }
+}
+
+/**
+ * The native pasteboard format each platform's file managers read file
+ * references from. Electron only writes one raw buffer per call, so each
+ * platform gets the single format with the widest support.
+ */
+export function buildFileClipboardFormat(input: {
+  paths: string[];
+  platform?: NodeJS.Platform;
+}): ClipboardBufferFormat {
+  const platform = input.platform ?? process.platform;
+  if (platform === "darwin") {
+    const items = input.paths.map((value) => `<string>${escapeXml(value)}</string>`).join("");
+    const plist =
+      '<?xml version="1.0" encoding="UTF-8"?>' +
+      '<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" ' +
+      '"http://www.apple.com/DTDs/PropertyList-1.0.dtd">' +
+      `<plist version="1.0"><array>${items}</array></plist>`;
+    return { format: "NSFilenamesPboardType", data: Buffer.from(plist, "utf8") };
+  }
+  if (platform === "win32") {
+    if (input.paths.length > 1) {
+      throw new Error("Copying more than one file at a time is not supported on Windows.");
+    }
+    return { format: "FileNameW", data: Buffer.from(`${input.paths[0]}\0`, "utf16le") };
+  }
+  const uris = input.paths.map((value) => pathToFileURL(value).href);
+  return { format: "text/uri-list", data: Buffer.from(`${uris.join("\r\n")}\r\n`, "utf8") };
+}
@@ -0,0 +1,85 @@
+import { app, clipboard, powerMonitor } from "electron";
+import log from "electron-log/main";
+import type { DesktopSettings } from "../settings/desktop-settings.js";
+import {
+  createClipboardHistory,
+  type ClipboardHistory,
+  type ClipboardHistoryEntry,
+} from "./clipboard-history.js";
+
+// Electron has no clipboard change event, so the watcher polls.
+const CLIPBOARD_POLL_INTERVAL_MS = 1_000;
+
+let clipboardHistory: ClipboardHistory | null = null;
+let pollTimer: NodeJS.Timeout | null = null;
+let lastSeenText: string | null = null;
+let lockListenerRegistered = false;
+
+function getClipboardHistory(settings: DesktopSettings["clipboardHistory"]): ClipboardHistory {
+  clipboardHistory ??= createClipboardHistory({
+    userDataPath: app.getPath("userData"),
+    options: { maxEntries: settings.maxEntries, persist: settings.persist },
+  });
+  return clipboardHistory;
+}
+
+function pollClipboard(history: ClipboardHistory): void {
+  const text = clipboard.readText();
+  if (text === lastSeenText) {
+    return;
+  }
+  lastSeenText = text;
+  void history.record(text).catch((error) => {
+    log.warn("[clipboard-history] failed to record snippet", error);
+  });
+}
+
+function stopPolling(): void {
+  if (pollTimer) {
+    clearInterval(pollTimer);
+    pollTimer = null;
+  }
+}
+
+function registerLockListener(): void {
+  if (lockListenerRegistered) {
+    return;
+  }
+  lockListenerRegistered = true;
+  powerMonitor.on("lock-screen", () => {
+    void clearClipboardHistory().catch((error) => {
+      log.warn("[clipboard-history] failed to clear on lock", error);
+    });
+  });
+}
+
+/** Starts or stops the watcher to match settings; safe to call on every settings change. */
+export async function applyClipboardHistorySettings(
+  settings: DesktopSettings["clipboardHistory"],
+): Promise<void> {
+  if (!settings.enabled) {
+    stopPolling();
+    lastSeenText = null;
+    // Opting out also drops anything a previous session left on disk.
+    await getClipboardHistory(settings).clear();
+    return;
+  }
+
+  const history = getClipboardHistory(settings);
+  await history.configure({ maxEntries: settings.maxEntries, persist: settings.persist });
+  registerLockListener();
+  if (!pollTimer) {
+    // Whatever is on the clipboard before opting in is not part of the history.
+    lastSeenText = clipboard.readText();
+    pollTimer = setInterval(() => pollClipboard(history), CLIPBOARD_POLL_INTERVAL_MS);
+    pollTimer.unref?.();
+  }
+}
+
+export async function getClipboardHistoryEntries(): Promise<ClipboardHistoryEntry[]> {
+  return pollTimer && clipboardHistory ? clipboardHistory.list() : [];
+}
+
+export async function clearClipboardHistory(): Promise<void> {
+  await clipboardHistory?.clear();
+}
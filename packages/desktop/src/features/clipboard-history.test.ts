@@ -0,0 +1,71 @@
+import { mkdtemp, readFile, rm } from "node:fs/promises";
+import { tmpdir } from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+import { createClipboardHistory } from "./clipboard-history";
+
+describe("createClipboardHistory", () => {
+  let userDataPath: string;
+
+  beforeEach(async () => {
+    userDataPath = await mkdtemp(path.join(tmpdir(), "paseo-clipboard-history-"));
+  });
+
+  afterEach(async () => {
+    await rm(userDataPath, { recursive: true, force: true });
+  });
+
+  it("keeps the newest N snippets and moves repeats to the front", async () => {
+    const history = createClipboardHistory({
+      userDataPath,
+      options: { maxEntries: 3, persist: false },
+    });
+
+    for (const text of ["a", "b", "  ", "c", "a", "d"]) {
+      await history.record(text);
+    }
+
+    expect((await history.list()).map((entry) => entry.text)).toEqual(["d", "a", "c"]);
+  });
+
+  it("never writes to disk unless persistence is enabled", async () => {
+    const history = createClipboardHistory({
+      userDataPath,
+      options: { maxEntries: 5, persist: false },
+    });
+    await history.record("TypeError: x is undefined");
+
+    await expect(
+      readFile(path.join(userDataPath, "clipboard-history.json"), "utf8"),
+    ).rejects.toThrow();
+  });
+
+  it("reloads persisted snippets and deletes them when persistence is turned off", async () => {
+    const options = { maxEntries: 5, persist: true };
+    await createClipboardHistory({ userDataPath, options }).record("error: ENOENT");
+
+    const reloaded = createClipboardHistory({ userDataPath, options });
+    expect((await reloaded.list()).map((entry) => entry.text)).toEqual(["error: ENOENT"]);
+
+    await reloaded.configure({ maxEntries: 5, persist: false });
+    await expect(
+      readFile(path.join(userDataPath, "clipboard-history.json"), "utf8"),
+    ).rejects.toThrow();
+    expect(await reloaded.list()).toHaveLength(1);
+  });
+
+  it("clears memory and disk", async () => {
+    const history = createClipboardHistory({
+      userDataPath,
+      options: { maxEntries: 5, persist: true },
+    });
+    await history.record("secret");
+
+    await history.clear();
+
+    expect(await history.list()).toEqual([]);
+    await expect(
+      readFile(path.join(userDataPath, "clipboard-history.json"), "utf8"),
+    ).rejects.toThrow();
+  });
+});
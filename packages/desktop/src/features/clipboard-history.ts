@@ -0,0 +1,133 @@
+import { readFile, rm } from "node:fs/promises";
+import path from "node:path";
+import { updateJsonConfigFile } from "../settings/config-file.js";
+import { isRecord } from "../utils/is-record.js";
+
+const CLIPBOARD_HISTORY_FILENAME = "clipboard-history.json";
+// Bigger copies are almost always whole files; keep the history about snippets.
+const MAX_SNIPPET_LENGTH = 20_000;
+
+export interface ClipboardHistoryEntry {
+  text: string;
+  copiedAt: string;
+}
+
+export interface ClipboardHistoryOptions {
+  maxEntries: number;
+  persist: boolean;
+}
+
+interface PersistedClipboardHistoryDocument {
+  version: 1;
+  entries: ClipboardHistoryEntry[];
+}
+
+export interface ClipboardHistory {
+  /** Applies new limits; turning `persist` off deletes whatever was written to disk. */
+  configure(options: ClipboardHistoryOptions): Promise<void>;
+  /** Records a snippet, moving it to the front if it is already in the history. */
+  record(text: string): Promise<void>;
+  /** Newest first. */
+  list(): Promise<ClipboardHistoryEntry[]>;
+  clear(): Promise<void>;
+}
+
+function coerceEntries(value: unknown): ClipboardHistoryEntry[] {
+  if (!isRecord(value) || !Array.isArray(value.entries)) {
+    return [];
+  }
+  return value.entries.flatMap((entry: unknown): ClipboardHistoryEntry[] => {
+    if (!isRecord(entry) || typeof entry.text !== "string" || entry.text.length === 0) {
+      return [];
+    }
+    return [
+      { text: entry.text, copiedAt: typeof entry.copiedAt === "string" ? entry.copiedAt : "" },
+    ];
+  });
+}
+
+export function createClipboardHistory(input: {
+  userDataPath: string;
+  options: ClipboardHistoryOptions;
+  now?: () => number;
+}): ClipboardHistory {
+  const filePath = path.join(input.userDataPath, CLIPBOARD_HISTORY_FILENAME);
+  const now = input.now ?? (() => Date.now());
+  let options = { ...input.options };
+  let entries: ClipboardHistoryEntry[] = [];
+  let loaded: Promise<void> | null = null;
+
+  function ensureLoaded(): Promise<void> {
+    loaded ??= (async () => {
+      if (!options.persist) {
+        return;
+      }
+      try {
+        const raw = await readFile(filePath, "utf8");
+        entries = coerceEntries(JSON.parse(raw)).slice(0, options.maxEntries);
+      } catch {
+        entries = [];
+      }
+    })();
+    return loaded;
+  }
+
+  async function update(
+    mutate: (current: ClipboardHistoryEntry[]) => ClipboardHistoryEntry[],
+  ): Promise<void> {
+    if (!options.persist) {
+      entries = mutate(entries).slice(0, options.maxEntries);
+      return;
+    }
+    const document = await updateJsonConfigFile(
+      filePath,
+      (raw): PersistedClipboardHistoryDocument => ({
+        version: 1,
+        entries: mutate(coerceEntries(raw)).slice(0, options.maxEntries),
+      }),
+      { mode: 0o600 },
+    );
+    entries = document.entries;
+  }
+
+  return {
+    async configure(next) {
+      await ensureLoaded();
+      const wasPersisted = options.persist;
+      options = { ...next };
+      if (wasPersisted && !options.persist) {
+        entries = entries.slice(0, options.maxEntries);
+        await rm(filePath, { force: true });
+        return;
+      }
+      // Turning persistence on saves what was only kept in memory so far.
+      const inMemory = entries;
+      await update((current) => (wasPersisted ? current : inMemory));
+    },
+
+    async record(text) {
+      if (text.trim().length === 0 || text.length > MAX_SNIPPET_LENGTH) {
+        return;
+      }
+      await ensureLoaded();
+      if (entries[0]?.text === text) {
+        return;
+      }
+      await update((current) => [
+        { text, copiedAt: new Date(now()).toISOString() },
+        ...current.filter((entry) => entry.text !== text),
+      ]);
+    },
+
+    async list() {
+      await ensureLoaded();
+      return [...entries];
+    },
+
+    async clear() {
+      await ensureLoaded();
+      entries = [];
+      await rm(filePath, { force: true });
+    },
+  };
+}
@@ -0,0 +1,29 @@
+import { stat } from "node:fs/promises";
+import { clipboard, nativeImage } from "electron";
+import { resolveManagedAttachmentPath } from "./attachments.js";
+import { buildFileClipboardFormat, coerceClipboardContent } from "./clipboard-content.js";
+
+export async function copyToClipboard(args: Record<string, unknown> | undefined): Promise<void> {
+  const content = coerceClipboardContent(args);
+
+  if (content.kind === "text") {
+    clipboard.writeText(content.text);
+    return;
+  }
+
+  if (content.kind === "image") {
+    // Image paths are read into memory, so they are limited to desktop-managed attachments.
+    const image = content.base64
+      ? nativeImage.createFromBuffer(Buffer.from(content.base64, "base64"))
+      : nativeImage.createFromPath(resolveManagedAttachmentPath(content.path));
+    if (image.isEmpty()) {
+      throw new Error("Clipboard image could not be decoded.");
+    }
+    clipboard.writeImage(image);
+    return;
+  }
+
+  await Promise.all(content.paths.map((filePath) => stat(filePath)));
+  const { format, data } = buildFileClipboardFormat({ paths: content.paths });
+  clipboard.writeBuffer(format, data);
+}
@@ -0,0 +1,75 @@
+import { execFile } from "node:child_process";
+import { app } from "electron";
+import log from "electron-log/main";
+import {
+  buildGitSigningEnv,
+  type CommandRunner,
+  type CommitSigningAvailability,
+  type CommitSigningStore,
+  createCommitSigningStore,
+  detectCommitSigningAvailability,
+  resolveCommitSigningConfig,
+} from "./commit-signing.js";
+
+const PROBE_TIMEOUT_MS = 5_000;
+
+let commitSigningStore: CommitSigningStore | null = null;
+
+function getCommitSigningStore(): CommitSigningStore {
+  commitSigningStore ??= createCommitSigningStore({ userDataPath: app.getPath("userData") });
+  return commitSigningStore;
+}
+
+// process.env already holds the login shell's environment, agent sockets included.
+const runProbe: CommandRunner = (command, args) =>
+  new Promise((resolve) => {
+    execFile(
+      command,
+      args,
+      { env: process.env, timeout: PROBE_TIMEOUT_MS, windowsHide: true },
+      (error, stdout) => {
+        const code = error ? (typeof error.code === "number" ? error.code : null) : 0;
+        resolve({ code, stdout: String(stdout ?? "") });
+      },
+    );
+  });
+
+export async function getCommitSigningAvailability(): Promise<CommitSigningAvailability> {
+  return await detectCommitSigningAvailability({
+    env: process.env,
+    run: runProbe,
+    configured: await getCommitSigningStore().get(),
+  });
+}
+
+/**
+ * `keyOrAgent: null` stops signing. The daemon reads the config when it
+ * starts, so a running daemon signs from its next restart.
+ */
+export async function configureCommitSigning(
+  args: Record<string, unknown> | undefined,
+): Promise<CommitSigningAvailability> {
+  if (args?.keyOrAgent === null) {
+    await getCommitSigningStore().set(null);
+    log.info("[commit-signing] signing turned off");
+    return await getCommitSigningAvailability();
+  }
+  if (typeof args?.keyOrAgent !== "string" || args.keyOrAgent.trim().length === 0) {
+    throw new Error("keyOrAgent must be a key path, key id, ssh-agent, gpg-agent, or null.");
+  }
+  const availability = await getCommitSigningAvailability();
+  const config = await resolveCommitSigningConfig({ keyOrAgent: args.keyOrAgent, availability });
+  await getCommitSigningStore().set(config);
+  log.info(`[commit-signing] signing commits with ${config.format}`);
+  return { ...availability, configured: config };
+}
+
+/** Environment overlay that makes git sign commits with the configured key. */
+export async function getCommitSigningEnv(): Promise<Record<string, string>> {
+  try {
+    return buildGitSigningEnv(process.env, await getCommitSigningStore().get());
+  } catch (error) {
+    log.warn("[commit-signing] failed to read the signing config", error);
+    return {};
+  }
+}
@@ -0,0 +1,116 @@
+import { mkdtemp, rm, writeFile } from "node:fs/promises";
+import { tmpdir } from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+import {
+  buildGitSigningEnv,
+  type CommandRunner,
+  createCommitSigningStore,
+  detectCommitSigningAvailability,
+  resolveCommitSigningConfig,
+} from "./commit-signing";
+
+const SSH_KEY = "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIBx user@laptop";
+
+function createRunner(outputs: Record<string, { code: number | null; stdout: string }>) {
+  const run: CommandRunner = async (command, args) =>
+    outputs[[command, ...args].join(" ")] ?? { code: null, stdout: "" };
+  return run;
+}
+
+describe("commit signing", () => {
+  let userDataPath: string;
+
+  beforeEach(async () => {
+    userDataPath = await mkdtemp(path.join(tmpdir(), "paseo-commit-signing-"));
+  });
+
+  afterEach(async () => {
+    await rm(userDataPath, { recursive: true, force: true });
+  });
+
+  it("detects agents and the user's git signing config", async () => {
+    const availability = await detectCommitSigningAvailability({
+      env: { SSH_AUTH_SOCK: "/tmp/ssh-agent.sock" },
+      run: createRunner({
+        "gpg --version": { code: 0, stdout: "gpg (GnuPG) 2.4.5\nlibgcrypt 1.10.3\n" },
+        "ssh-add -L": { code: 0, stdout: `${SSH_KEY}\n` },
+        "git config --global --get gpg.format": { code: 0, stdout: "ssh\n" },
+        "git config --global --get commit.gpgsign": { code: 0, stdout: "true\n" },
+      }),
+      configured: null,
+    });
+
+    expect(availability.gpg).toEqual({
+      available: true,
+      version: "gpg (GnuPG) 2.4.5",
+      agentSocket: null,
+    });
+    expect(availability.ssh).toEqual({
+      available: true,
+      agentSocket: "/tmp/ssh-agent.sock",
+      keys: [SSH_KEY],
+    });
+    expect(availability.gitConfig).toEqual({ format: "ssh", signingKey: null, gpgSign: true });
+  });
+
+  it("reports no ssh signing when the agent holds no keys", async () => {
+    const availability = await detectCommitSigningAvailability({
+      env: { SSH_AUTH_SOCK: "/tmp/ssh-agent.sock" },
+      run: createRunner({ "ssh-add -L": { code: 1, stdout: "The agent has no identities.\n" } }),
+      configured: null,
+    });
+
+    expect(availability.ssh.available).toBe(false);
+    expect(availability.gpg.available).toBe(false);
+  });
+
+  it("resolves agents, key files and gpg key ids", async () => {
+    const keyPath = path.join(userDataPath, "id_ed25519.pub");
+    await writeFile(keyPath, `${SSH_KEY}\n`);
+    const availability = {
+      gpg: { available: true, version: "gpg (GnuPG) 2.4.5", agentSocket: null },
+      ssh: { available: true, agentSocket: "/tmp/ssh-agent.sock", keys: [SSH_KEY] },
+    };
+
+    await expect(
+      resolveCommitSigningConfig({ keyOrAgent: "ssh-agent", availability }),
+    ).resolves.toEqual({ format: "ssh", signingKey: `key::${SSH_KEY}` });
+    await expect(
+      resolveCommitSigningConfig({ keyOrAgent: keyPath, availability }),
+    ).resolves.toEqual({ format: "ssh", signingKey: keyPath });
+    await expect(
+      resolveCommitSigningConfig({ keyOrAgent: "0xDEADBEEFCAFEBABE", availability }),
+    ).resolves.toEqual({ format: "openpgp", signingKey: "0xDEADBEEFCAFEBABE" });
+    await expect(
+      resolveCommitSigningConfig({ keyOrAgent: path.join(userDataPath, "missing"), availability }),
+    ).rejects.toThrow("Signing key not found");
+  });
+
+  it("appends signing entries after git config already in the environment", () => {
+    expect(
+      buildGitSigningEnv(
+        { GIT_CONFIG_COUNT: "1" },
+        { format: "openpgp", signingKey: "user@example.com" },
+      ),
+    ).toEqual({
+      GIT_CONFIG_COUNT: "4",
+      GIT_CONFIG_KEY_1: "commit.gpgsign",
+      GIT_CONFIG_VALUE_1: "true",
+      GIT_CONFIG_KEY_2: "gpg.format",
+      GIT_CONFIG_VALUE_2: "openpgp",
+      GIT_CONFIG_KEY_3: "user.signingkey",
+      GIT_CONFIG_VALUE_3: "user@example.com",
+    });
+    expect(buildGitSigningEnv({}, null)).toEqual({});
+  });
+
+  it("persists the configured key", async () => {
+    await createCommitSigningStore({ userDataPath }).set({ format: "ssh", signingKey: "/k.pub" });
+
+    await expect(createCommitSigningStore({ userDataPath }).get()).resolves.toEqual({
+      format: "ssh",
+      signingKey: "/k.pub",
+    });
+  });
+});
@@ -0,0 +1,210 @@
+import { readFile, stat } from "node:fs/promises";
+import { homedir } from "node:os";
+import path from "node:path";
+import { writeJsonConfigFile } from "../settings/config-file.js";
+import { isRecord } from "../utils/is-record.js";
+
+const COMMIT_SIGNING_FILENAME = "commit-signing.json";
+const GPG_KEY_ID_PATTERN = /^(?:0x)?[0-9a-f]{8,40}$/i;
+const SSH_PUBLIC_KEY_PATTERN = /^(?:ssh-|ecdsa-|sk-)\S+ \S+/;
+
+export type SigningFormat = "openpgp" | "ssh";
+
+export interface CommitSigningConfig {
+  format: SigningFormat;
+  /**
+   * GPG key id or email, SSH key path, or `key::<public key>` for a key held
+   * by ssh-agent. `null` lets gpg pick its default secret key.
+   */
+  signingKey: string | null;
+}
+
+export interface CommandResult {
+  code: number | null;
+  stdout: string;
+}
+
+/** Runs a tool from the login environment; never throws for a missing binary. */
+export type CommandRunner = (command: string, args: string[]) => Promise<CommandResult>;
+
+export interface CommitSigningAvailability {
+  gpg: { available: boolean; version: string | null; agentSocket: string | null };
+  ssh: { available: boolean; agentSocket: string | null; keys: string[] };
+  /** What the user's own git config signs with, so the app can match it. */
+  gitConfig: { format: SigningFormat | null; signingKey: string | null; gpgSign: boolean };
+  configured: CommitSigningConfig | null;
+}
+
+export interface CommitSigningStore {
+  get(): Promise<CommitSigningConfig | null>;
+  set(config: CommitSigningConfig | null): Promise<CommitSigningConfig | null>;
+}
+
+function isSigningFormat(value: unknown): value is SigningFormat {
+  return value === "openpgp" || value === "ssh";
+}
+
+function coerceConfig(value: unknown): CommitSigningConfig | null {
+  if (!isRecord(value) || !isRecord(value.config) || !isSigningFormat(value.config.format)) {
+    return null;
+  }
+  const { signingKey } = value.config;
+  return {
+    format: value.config.format,
+    signingKey: typeof signingKey === "string" && signingKey.length > 0 ? signingKey : null,
+  };
+}
+
+async function isSocket(filePath: string): Promise<boolean> {
+  try {
+    return (await stat(filePath)).isSocket();
+  } catch {
+    return false;
+  }
+}
+
+async function readGitConfig(run: CommandRunner, key: string): Promise<string | null> {
+  const result = await run("git", ["config", "--global", "--get", key]);
+  const value = result.stdout.trim();
+  return result.code === 0 && value.length > 0 ? value : null;
+}
+
+export async function detectCommitSigningAvailability(input: {
+  env: NodeJS.ProcessEnv;
+  run: CommandRunner;
+  configured: CommitSigningConfig | null;
+}): Promise<CommitSigningAvailability> {
+  const { run } = input;
+  const [gpgVersion, gpgSocket, sshKeys, format, signingKey, gpgSign] = await Promise.all([
+    run("gpg", ["--version"]),
+    run("gpgconf", ["--list-dirs", "agent-socket"]),
+    input.env.SSH_AUTH_SOCK ? run("ssh-add", ["-L"]) : Promise.resolve(null),
+    readGitConfig(run, "gpg.format"),
+    readGitConfig(run, "user.signingkey"),
+    readGitConfig(run, "commit.gpgsign"),
+  ]);
+
+  const gpgAgentSocket = gpgSocket.code === 0 ? gpgSocket.stdout.trim() : "";
+  const gpgAvailable = gpgVersion.code === 0;
+  const sshAgentSocket = input.env.SSH_AUTH_SOCK ?? null;
+  // `ssh-add -L` exits 1 when the agent is up but holds no keys.
+  const keys =
+    sshKeys?.code === 0
+      ? sshKeys.stdout
+          .split("\n")
+          .map((line) => line.trim())
+          .filter((line) => SSH_PUBLIC_KEY_PATTERN.test(line))
+      : [];
+
+  return {
+    gpg: {
+      available: gpgAvailable,
+      version: gpgAvailable ? (gpgVersion.stdout.split("\n")[0]?.trim() ?? null) : null,
+      agentSocket: gpgAgentSocket && (await isSocket(gpgAgentSocket)) ? gpgAgentSocket : null,
+    },
+    ssh: {
+      available: sshAgentSocket !== null && keys.length > 0,
+      agentSocket: sshAgentSocket,
+      keys,
+    },
+    gitConfig: {
+      format: isSigningFormat(format) ? format : null,
+      signingKey,
+      gpgSign: gpgSign === "true",
+    },
+    configured: input.configured,
+  };
+}
+
+/**
+ * Turns what the user picked into a signing config: `ssh-agent` signs with
+ * the agent's first key, `gpg-agent` with gpg's default key, a file path
+ * with that SSH key, and anything else is taken as a GPG key id or email.
+ */
+export async function resolveCommitSigningConfig(input: {
+  keyOrAgent: string;
+  availability: Pick<CommitSigningAvailability, "gpg" | "ssh">;
+}): Promise<CommitSigningConfig> {
+  const value = input.keyOrAgent.trim();
+  if (value === "ssh-agent") {
+    const [key] = input.availability.ssh.keys;
+    if (!key) {
+      throw new Error("ssh-agent is not running or holds no keys.");
+    }
+    return { format: "ssh", signingKey: `key::${key}` };
+  }
+  if (value === "gpg-agent") {
+    if (!input.availability.gpg.available) {
+      throw new Error("gpg is not installed in the login environment.");
+    }
+    return { format: "openpgp", signingKey: null };
+  }
+  if (path.isAbsolute(value) || value.startsWith("~")) {
+    const keyPath = value.startsWith("~") ? path.join(homedir(), value.slice(1)) : value;
+    try {
+      await stat(keyPath);
+    } catch {
+      throw new Error(`Signing key not found: ${keyPath}`);
+    }
+    return { format: "ssh", signingKey: keyPath };
+  }
+  if (GPG_KEY_ID_PATTERN.test(value) || value.includes("@")) {
+    if (!input.availability.gpg.available) {
+      throw new Error("gpg is not installed in the login environment.");
+    }
+    return { format: "openpgp", signingKey: value };
+  }
+  throw new Error("Expected ssh-agent, gpg-agent, a path to an SSH key, or a GPG key id or email.");
+}
+
+/**
+ * Git reads `GIT_CONFIG_COUNT` entries as if they were `-c` flags, which
+ * reaches every git the daemon and its agents run without touching the
+ * user's config files. Entries already in `env` are kept.
+ */
+export function buildGitSigningEnv(
+  env: NodeJS.ProcessEnv,
+  config: CommitSigningConfig | null,
+): Record<string, string> {
+  if (!config) {
+    return {};
+  }
+  const entries: Array<[string, string]> = [
+    ["commit.gpgsign", "true"],
+    ["gpg.format", config.format],
+  ];
+  if (config.signingKey) {
+    entries.push(["user.signingkey", config.signingKey]);
+  }
+  const existing = Number.parseInt(env.GIT_CONFIG_COUNT ?? "0", 10);
+  const offset = Number.isInteger(existing) && existing > 0 ? existing : 0;
+  const result: Record<string, string> = {
+    GIT_CONFIG_COUNT: String(offset + entries.length),
+  };
+  entries.forEach(([key, value], index) => {
+    result[`GIT_CONFIG_KEY_${offset + index}`] = key;
+    result[`GIT_CONFIG_VALUE_${offset + index}`] = value;
+  });
+  return result;
+}
+
+export function createCommitSigningStore(input: { userDataPath: string }): CommitSigningStore {
+  const filePath = path.join(input.userDataPath, COMMIT_SIGNING_FILENAME);
+  let loaded: Promise<CommitSigningConfig | null> | null = null;
+
+  async function load(): Promise<CommitSigningConfig | null> {
+    loaded ??= readFile(filePath, "utf8")
+      .then((raw) => coerceConfig(JSON.parse(raw)))
+      .catch(() => null);
+    return await loaded;
+  }
+
+  return {
+    get: load,
+    async set(config) {
+      await writeJsonConfigFile(filePath, { version: 1, config });
+      loaded = Promise.resolve(config);
+      return config;
+    },
+  };
+}
@@ -0,0 +1,36 @@
+import { describe, expect, it } from "vitest";
+import { nextCronRun, parseCronExpression } from "./cron-schedule";
+
+function next(expression: string, after: Date): Date | null {
+  return nextCronRun(parseCronExpression(expression), after);
+}
+
+describe("cron schedules", () => {
+  it("finds the next matching minute in local time", () => {
+    expect(next("30 3 * * *", new Date(2026, 9, 16, 2, 0))).toEqual(new Date(2026, 9, 16, 3, 30));
+    expect(next("30 3 * * *", new Date(2026, 9, 16, 3, 30))).toEqual(new Date(2026, 9, 17, 3, 30));
+    expect(next("*/15 * * * *", new Date(2026, 9, 16, 2, 7, 42))).toEqual(
+      new Date(2026, 9, 16, 2, 15),
+    );
+  });
+
+  it("supports ranges, lists, and macros", () => {
+    // 2026-10-16 is a Friday, so the next weekday run is Monday.
+    expect(next("0 9 * * 1-5", new Date(2026, 9, 16, 10, 0))).toEqual(new Date(2026, 9, 19, 9, 0));
+    expect(next("0 0,12 * * *", new Date(2026, 9, 16, 1, 0))).toEqual(new Date(2026, 9, 16, 12, 0));
+    expect(next("@monthly", new Date(2026, 9, 16))).toEqual(new Date(2026, 10, 1));
+    expect(next("0 0 * * 7", new Date(2026, 9, 16))).toEqual(new Date(2026, 9, 18));
+  });
+
+  it("ORs day of month and day of week when both are set", () => {
+    expect(next("0 0 20 * 0", new Date(2026, 9, 16))).toEqual(new Date(2026, 9, 18));
+    expect(next("0 0 29 2 *", new Date(2026, 9, 16))).toEqual(new Date(2028, 1, 29));
+  });
+
+  it("rejects malformed expressions and reports impossible ones", () => {
+    expect(() => parseCronExpression("* * * *")).toThrow("five fields");
+    expect(() => parseCronExpression("60 * * * *")).toThrow("Invalid cron minute");
+    expect(() => parseCronExpression("0 5-1 * * *")).toThrow("range");
+    expect(next("0 0 31 2 *", new Date(2026, 9, 16))).toBeNull();
+  });
+});
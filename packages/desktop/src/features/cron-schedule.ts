@@ -0,0 +1,134 @@
+export interface CronSchedule {
+  minutes: Set<number>;
+  hours: Set<number>;
+  daysOfMonth: Set<number>;
+  months: Set<number>;
+  daysOfWeek: Set<number>;
+  /** Cron ORs day-of-month and day-of-week when both are restricted. */
+  dayOfMonthRestricted: boolean;
+  dayOfWeekRestricted: boolean;
+}
+
+const CRON_MACROS: Record<string, string> = {
+  "@hourly": "0 * * * *",
+  "@daily": "0 0 * * *",
+  "@midnight": "0 0 * * *",
+  "@weekly": "0 0 * * 0",
+  "@monthly": "0 0 1 * *",
+};
+
+const FIELD_RANGES: Array<{ name: string; min: number; max: number }> = [
+  { name: "minute", min: 0, max: 59 },
+  { name: "hour", min: 0, max: 23 },
+  { name: "day of month", min: 1, max: 31 },
+  { name: "month", min: 1, max: 12 },
+  // 7 is accepted as Sunday and folded into 0.
+  { name: "day of week", min: 0, max: 7 },
+];
+
+// Far enough to find any valid date (e.g. Feb 29) while still bounding impossible ones.
+const MAX_SEARCH_YEARS = 8;
+
+function parseCronNumber(value: string, field: { name: string; min: number; max: number }) {
+  const parsed = /^\d+$/.test(value) ? Number(value) : Number.NaN;
+  if (!Number.isInteger(parsed) || parsed < field.min || parsed > field.max) {
+    throw new Error(`Invalid cron ${field.name}: ${value}`);
+  }
+  return parsed;
+}
+
+function parseCronField(
+  source: string,
+  field: { name: string; min: number; max: number },
+): Set<number> {
+  const values = new Set<number>();
+  for (const part of source.split(",")) {
+    const [range = "", stepSource] = part.split("/");
+    const step = stepSource === undefined ? 1 : parseCronNumber(stepSource, { ...field, min: 1 });
+    let start: number;
+    let end: number;
+    if (range === "*") {
+      start = field.min;
+      end = field.max;
+    } else if (range.includes("-")) {
+      const [from = "", to = ""] = range.split("-");
+      start = parseCronNumber(from, field);
+      end = parseCronNumber(to, field);
+      if (start > end) {
+        throw new Error(`Invalid cron ${field.name} range: ${range}`);
+      }
+    } else {
+      start = parseCronNumber(range, field);
+      end = stepSource === undefined ? start : field.max;
+    }
+    for (let value = start; value <= end; value += step) {
+      values.add(value);
+    }
+  }
+  return values;
+}
+
+/** Five-field cron (`minute hour day-of-month month day-of-week`) plus `@daily`-style macros. */
+export function parseCronExpression(expression: string): CronSchedule {
+  const normalized = expression.trim().toLowerCase();
+  const fields = (CRON_MACROS[normalized] ?? normalized).split(/\s+/);
+  if (fields.length !== 5) {
+    throw new Error(`Cron expressions need five fields: ${expression}`);
+  }
+  const [minutes, hours, daysOfMonth, months, daysOfWeek] = fields.map((field, index) =>
+    parseCronField(field, FIELD_RANGES[index]!),
+  ) as [Set<number>, Set<number>, Set<number>, Set<number>, Set<number>];
+  if (daysOfWeek.delete(7)) {
+    daysOfWeek.add(0);
+  }
+  return {
+    minutes,
+    hours,
+    daysOfMonth,
+    months,
+    daysOfWeek,
+    dayOfMonthRestricted: fields[2] !== "*",
+    dayOfWeekRestricted: fields[4] !== "*",
+  };
+}
+
+function matchesDay(schedule: CronSchedule, date: Date): boolean {
+  const dayOfMonth = schedule.daysOfMonth.has(date.getDate());
+  const dayOfWeek = schedule.daysOfWeek.has(date.getDay());
+  if (schedule.dayOfMonthRestricted && schedule.dayOfWeekRestricted) {
+    return dayOfMonth || dayOfWeek;
+  }
+  return dayOfMonth && dayOfWeek;
+}
+
+/** First matching minute strictly after `after`, in local time; `null` if none exists. */
+export function nextCronRun(schedule: CronSchedule, after: Date): Date | null {
+  const candidate = new Date(after.getTime());
+  candidate.setSeconds(0, 0);
+  candidate.setMinutes(candidate.getMinutes() + 1);
+  const limit = new Date(after.getTime());
+  limit.setFullYear(limit.getFullYear() + MAX_SEARCH_YEARS);
+
+  while (candidate <= limit) {
+    if (!schedule.months.has(candidate.getMonth() + 1)) {
+      candidate.setMonth(candidate.getMonth() + 1, 1);
+      candidate.setHours(0, 0, 0, 0);
+      continue;
+    }
+    if (!matchesDay(schedule, candidate)) {
+      candidate.setDate(candidate.getDate() + 1);
+      candidate.setHours(0, 0, 0, 0);
+      continue;
+    }
+    if (!schedule.hours.has(candidate.getHours())) {
+      candidate.setHours(candidate.getHours() + 1, 0, 0, 0);
+      continue;
+    }
+    if (!schedule.minutes.has(candidate.getMinutes())) {
+      candidate.setMinutes(candidate.getMinutes() + 1, 0, 0);
+      continue;
+    }
+    return candidate;
+  }
+  return null;
+}
@@ -0,0 +1,113 @@
+import { writeFile } from "node:fs/promises";
+import path from "node:path";
+import { app, BrowserWindow, dialog } from "electron";
+import log from "electron-log/main";
+import { observeLocalTransport } from "../daemon/local-transport.js";
+import { getDesktopSettingsStore } from "../settings/desktop-settings-electron.js";
+import {
+  createActivityJournal,
+  type ActivityDay,
+  type ActivityJournal,
+} from "./activity-journal.js";
+import {
+  coerceDailyDigestRequest,
+  digestFileName,
+  filterActivityDay,
+  renderDigestHtml,
+  renderDigestMarkdown,
+  type DigestFormat,
+} from "./daily-digest.js";
+
+export interface DailyDigestResult {
+  path: string;
+  date: string;
+  format: DigestFormat;
+  agentCount: number;
+}
+
+let activityJournal: ActivityJournal | null = null;
+
+function getActivityJournal(): ActivityJournal {
+  activityJournal ??= createActivityJournal({ userDataPath: app.getPath("userData") });
+  return activityJournal;
+}
+
+/** Records agent activity from the daemon stream for the daily digest. */
+export function startActivityJournal(): void {
+  observeLocalTransport({ inbound: (text) => getActivityJournal().observeInbound(text) });
+}
+
+async function renderPdf(day: ActivityDay): Promise<Buffer> {
+  const printer = new BrowserWindow({
+    show: false,
+    webPreferences: { javascript: false, sandbox: true, contextIsolation: true },
+  });
+  try {
+    const html = renderDigestHtml(day);
+    await printer.loadURL(`data:text/html;charset=utf-8,${encodeURIComponent(html)}`);
+    return await printer.webContents.printToPDF({
+      pageSize: "A4",
+      margins: { marginType: "default" },
+      printBackground: false,
+    });
+  } finally {
+    printer.destroy();
+  }
+}
+
+async function writeDigest(input: {
+  folder: string;
+  date: string;
+  agentIds: string[] | null;
+  format: DigestFormat;
+}): Promise<DailyDigestResult> {
+  const journal = getActivityJournal();
+  await journal.flush();
+  const day = filterActivityDay(await journal.getDay(input.date), input.agentIds);
+  const filePath = path.join(input.folder, digestFileName(input.date, input.format));
+  const contents = input.format === "pdf" ? await renderPdf(day) : renderDigestMarkdown(day);
+  await writeFile(filePath, contents);
+  log.info(`[daily-digest] wrote ${filePath}`);
+  return { path: filePath, date: input.date, format: input.format, agentCount: day.agents.length };
+}
+
+/**
+ * `folder` defaults to the configured digest folder and falls back to a
+ * picker; `null` means the picker was dismissed.
+ */
+export async function generateDailyDigest(input: {
+  args: Record<string, unknown> | undefined;
+  win: BrowserWindow | null;
+}): Promise<DailyDigestResult | null> {
+  const request = coerceDailyDigestRequest(input.args);
+  const settings = (await getDesktopSettingsStore().get()).digest;
+  let folder = typeof input.args?.folder === "string" ? input.args.folder : settings.folder;
+  if (folder !== null && !path.isAbsolute(folder)) {
+    throw new Error("Digest folder must be an absolute path.");
+  }
+  if (folder === null) {
+    const options: Electron.OpenDialogOptions = {
+      title: "Save digest to",
+      properties: ["openDirectory", "createDirectory"],
+    };
+    const picked = input.win
+      ? await dialog.showOpenDialog(input.win, options)
+      : await dialog.showOpenDialog(options);
+    if (picked.canceled || !picked.filePaths[0]) {
+      return null;
+    }
+    folder = picked.filePaths[0];
+  }
+  const format = input.args?.format === undefined ? settings.format : request.format;
+  return await writeDigest({ ...request, format, folder });
+}
+
+/** `daily_digest` local task: today's digest for every agent, into the configured folder. */
+export async function runScheduledDailyDigest(): Promise<DailyDigestResult> {
+  const settings = (await getDesktopSettingsStore().get()).digest;
+  if (!settings.folder) {
+    throw new Error("Choose a digest folder in settings before scheduling digests.");
+  }
+  const request = coerceDailyDigestRequest({ format: settings.format });
+  return await writeDigest({ ...request, folder: settings.folder });
+}
@@ -0,0 +1,72 @@
+import { describe, expect, it } from "vitest";
+import type { ActivityDay } from "./activity-journal";
+import {
+  coerceDailyDigestRequest,
+  digestFileName,
+  filterActivityDay,
+  renderDigestHtml,
+  renderDigestMarkdown,
+} from "./daily-digest";
+
+const day: ActivityDay = {
+  date: "2026-05-01",
+  agents: [
+    {
+      agentId: "a1",
+      title: "Fix login",
+      cwd: "/repo",
+      tasks: [
+        { prompt: "Fix the *login*", endedAt: "2026-05-01T08:05:00.000Z", outcome: "completed" },
+        { prompt: null, endedAt: "2026-05-01T09:00:00.000Z", outcome: "failed" },
+      ],
+      files: [{ path: "src/login.ts", additions: 4, deletions: 1 }],
+    },
+    { agentId: "b2", title: null, cwd: null, tasks: [], files: [] },
+  ],
+  pendingApprovals: [
+    { agentId: "a1", requestId: "p1", title: "Run <tests>", requestedAt: "2026-05-01T09:00:00Z" },
+  ],
+};
+
+describe("coerceDailyDigestRequest", () => {
+  it("defaults to today, every agent and markdown", () => {
+    expect(coerceDailyDigestRequest(undefined, Date.parse("2026-05-01T12:00:00"))).toEqual({
+      date: "2026-05-01",
+      agentIds: null,
+      format: "markdown",
+    });
+  });
+
+  it("rejects impossible dates and unknown formats", () => {
+    expect(() => coerceDailyDigestRequest({ date: "2026-02-30" })).toThrow(/calendar date/);
+    expect(() => coerceDailyDigestRequest({ format: "docx" })).toThrow(/markdown or pdf/);
+    expect(() => coerceDailyDigestRequest({ agents: "a1" })).toThrow(/list of agent ids/);
+  });
+});
+
+describe("renderDigestMarkdown", () => {
+  it("lists approvals, tasks and changed files per agent", () => {
+    const markdown = renderDigestMarkdown(day);
+    expect(markdown).toContain("2 agents · 1 completed tasks · 1 files changed · 1 pending");
+    expect(markdown).toContain("- [ ] Run \\<tests\\> — Fix login");
+    expect(markdown).toContain("Fix the \\*login\\*");
+    expect(markdown).toContain("_Untitled turn_ (failed)");
+    expect(markdown).toContain("| src/login.ts | 4 | 1 |");
+    expect(markdown).toContain("## Agent b2");
+  });
+
+  it("only keeps the requested agents", () => {
+    const filtered = filterActivityDay(day, ["b2"]);
+    expect(filtered.agents.map((agent) => agent.agentId)).toEqual(["b2"]);
+    expect(filtered.pendingApprovals).toEqual([]);
+  });
+});
+
+describe("renderDigestHtml", () => {
+  it("escapes agent-provided text", () => {
+    const html = renderDigestHtml(day);
+    expect(html).toContain("Run &lt;tests&gt;");
+    expect(html).not.toContain("<tests>");
+    expect(digestFileName(day.date, "pdf")).toBe("paseo-digest-2026-05-01.pdf");
+  });
+});
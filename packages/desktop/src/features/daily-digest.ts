@@ -0,0 +1,199 @@
+import type { ActivityDay, AgentActivity } from "./activity-journal.js";
+import { buildExportFilename } from "./export-naming.js";
+import { localDateKey } from "./usage-stats.js";
+
+export type DigestFormat = "markdown" | "pdf";
+
+export interface DailyDigestRequest {
+  date: string;
+  /** `null` includes every agent with activity that day. */
+  agentIds: string[] | null;
+  format: DigestFormat;
+}
+
+export function coerceDigestDate(value: unknown, nowMs: number = Date.now()): string {
+  if (value === undefined || value === null) {
+    return localDateKey(nowMs);
+  }
+  if (typeof value !== "string" || !/^\d{4}-\d{2}-\d{2}$/.test(value)) {
+    throw new Error("Digest date must be a YYYY-MM-DD string.");
+  }
+  const parsed = new Date(`${value}T12:00:00`);
+  if (Number.isNaN(parsed.getTime()) || localDateKey(parsed.getTime()) !== value) {
+    throw new Error(`Digest date is not a calendar date: ${value}`);
+  }
+  return value;
+}
+
+export function coerceDigestFormat(value: unknown): DigestFormat {
+  if (value === undefined || value === null || value === "markdown") {
+    return "markdown";
+  }
+  if (value === "pdf") {
+    return "pdf";
+  }
+  throw new Error("Digest format must be markdown or pdf.");
+}
+
+export function coerceDailyDigestRequest(
+  args: Record<string, unknown> | undefined,
+  nowMs?: number,
+): DailyDigestRequest {
+  const agents = args?.agents;
+  if (agents !== undefined && agents !== null && !Array.isArray(agents)) {
+    throw new Error("Digest agents must be a list of agent ids.");
+  }
+  return {
+    date: coerceDigestDate(args?.date, nowMs),
+    agentIds: Array.isArray(agents)
+      ? agents.filter((id): id is string => typeof id === "string" && id.length > 0)
+      : null,
+    format: coerceDigestFormat(args?.format),
+  };
+}
+
+export function filterActivityDay(day: ActivityDay, agentIds: string[] | null): ActivityDay {
+  if (!agentIds) {
+    return day;
+  }
+  const wanted = new Set(agentIds);
+  return {
+    date: day.date,
+    agents: day.agents.filter((agent) => wanted.has(agent.agentId)),
+    pendingApprovals: day.pendingApprovals.filter((approval) => wanted.has(approval.agentId)),
+  };
+}
+
+export function digestFileName(date: string, format: DigestFormat): string {
+  return buildExportFilename("digest", { day: date, extension: format === "pdf" ? "pdf" : "md" });
+}
+
+function agentLabel(agent: Pick<AgentActivity, "agentId" | "title">): string {
+  return agent.title?.trim() || `Agent ${agent.agentId.slice(0, 8)}`;
+}
+
+function formatTime(iso: string): string {
+  const date = new Date(iso);
+  const hours = String(date.getHours()).padStart(2, "0");
+  const minutes = String(date.getMinutes()).padStart(2, "0");
+  return `${hours}:${minutes}`;
+}
+
+function escapeMarkdown(text: string): string {
+  return text.replace(/([\\`*_[\]#|<>])/g, "\\$1");
+}
+
+export function renderDigestMarkdown(day: ActivityDay): string {
+  const lines = [`# Paseo digest — ${day.date}`, ""];
+  const completed = day.agents.reduce(
+    (total, agent) => total + agent.tasks.filter((task) => task.outcome === "completed").length,
+    0,
+  );
+  const fileCount = day.agents.reduce((total, agent) => total + agent.files.length, 0);
+  lines.push(
+    [
+      `${day.agents.length} agents`,
+      `${completed} completed tasks`,
+      `${fileCount} files changed`,
+      `${day.pendingApprovals.length} pending approvals`,
+    ].join(" · "),
+    "",
+  );
+
+  if (day.pendingApprovals.length > 0) {
+    const titles = new Map(day.agents.map((agent) => [agent.agentId, agentLabel(agent)]));
+    lines.push("## Pending approvals", "");
+    for (const approval of day.pendingApprovals) {
+      const agent = titles.get(approval.agentId) ?? `Agent ${approval.agentId.slice(0, 8)}`;
+      lines.push(`- [ ] ${escapeMarkdown(approval.title)} — ${escapeMarkdown(agent)}`);
+    }
+    lines.push("");
+  }
+
+  for (const agent of day.agents) {
+    lines.push(`## ${escapeMarkdown(agentLabel(agent))}`, "");
+    if (agent.cwd) {
+      lines.push(`\`${agent.cwd}\``, "");
+    }
+    if (agent.tasks.length > 0) {
+      lines.push("### Tasks", "");
+      for (const task of agent.tasks) {
+        const status = task.outcome === "completed" ? "" : ` (${task.outcome})`;
+        const prompt = task.prompt ? escapeMarkdown(task.prompt) : "_Untitled turn_";
+        lines.push(`- ${formatTime(task.endedAt)} ${prompt}${status}`);
+      }
+      lines.push("");
+    }
+    if (agent.files.length > 0) {
+      lines.push("### Changes", "", "| File | + | − |", "| --- | ---: | ---: |");
+      for (const file of agent.files) {
+        lines.push(`| ${escapeMarkdown(file.path)} | ${file.additions} | ${file.deletions} |`);
+      }
+      lines.push("");
+    }
+  }
+
+  if (day.agents.length === 0 && day.pendingApprovals.length === 0) {
+    lines.push("No agent activity was recorded on this machine.", "");
+  }
+  return lines.join("\n");
+}
+
+function escapeHtml(text: string): string {
+  return text
+    .replace(/&/g, "&amp;")
+    .replace(/</g, "&lt;")
+    .replace(/>/g, "&gt;")
+    .replace(/"/g, "&quot;");
+}
+
+/** Self-contained page for printToPDF; no scripts or remote resources. */
+export function renderDigestHtml(day: ActivityDay): string {
+  const sections: string[] = [];
+  if (day.pendingApprovals.length > 0) {
+    const titles = new Map(day.agents.map((agent) => [agent.agentId, agentLabel(agent)]));
+    const items = day.pendingApprovals.map((approval) => {
+      const agent = titles.get(approval.agentId) ?? `Agent ${approval.agentId.slice(0, 8)}`;
+      return `<li>☐ ${escapeHtml(approval.title)} — ${escapeHtml(agent)}</li>`;
+    });
+    sections.push(`<h2>Pending approvals</h2><ul>${items.join("")}</ul>`);
+  }
+  for (const agent of day.agents) {
+    const parts = [`<h2>${escapeHtml(agentLabel(agent))}</h2>`];
+    if (agent.cwd) {
+      parts.push(`<p class="cwd">${escapeHtml(agent.cwd)}</p>`);
+    }
+    if (agent.tasks.length > 0) {
+      const tasks = agent.tasks.map((task) => {
+        const status = task.outcome === "completed" ? "" : ` (${task.outcome})`;
+        const prompt = task.prompt ? escapeHtml(task.prompt) : "<em>Untitled turn</em>";
+        return `<li><span class="time">${formatTime(task.endedAt)}</span> ${prompt}${status}</li>`;
+      });
+      parts.push(`<h3>Tasks</h3><ul>${tasks.join("")}</ul>`);
+    }
+    if (agent.files.length > 0) {
+      const rows = agent.files.map(
+        (file) =>
+          `<tr><td>${escapeHtml(file.path)}</td><td>+${file.additions}</td>` +
+          `<td>−${file.deletions}</td></tr>`,
+      );
+      parts.push(`<h3>Changes</h3><table>${rows.join("")}</table>`);
+    }
+    sections.push(`<section>${parts.join("")}</section>`);
+  }
+  if (sections.length === 0) {
+    sections.push("<p>No agent activity was recorded on this machine.</p>");
+  }
+  return [
+    "<!doctype html><html><head><meta charset=\"utf-8\">",
+    `<title>Paseo digest — ${day.date}</title><style>`,
+    "body{font:11pt -apple-system,system-ui,sans-serif;color:#111;margin:0}",
+    "h1{font-size:18pt}h2{font-size:14pt;margin-top:18pt}h3{font-size:11pt}",
+    "section{break-inside:avoid-page}.cwd,.time{font-family:ui-monospace,monospace;color:#555}",
+    "table{border-collapse:collapse}td{padding:2pt 8pt 2pt 0;font-family:ui-monospace,monospace}",
+    "</style></head><body>",
+    `<h1>Paseo digest — ${day.date}</h1>`,
+    ...sections,
+    "</body></html>",
+  ].join("");
+}
@@ -0,0 +1,50 @@
+import path from "node:path";
+import { describe, expect, it } from "vitest";
+import {
+  buildExportFilename,
+  resolveAvailableFileName,
+  sanitizeFileNameSegment,
+} from "./export-naming";
+
+describe("export naming", () => {
+  it("builds sortable names from the kind, label and local time", () => {
+    const at = new Date(2026, 9, 16, 14, 5, 9);
+
+    expect(buildExportFilename("screenshot", { label: "Fix login", at, extension: "PNG" })).toBe(
+      "paseo-screenshot-Fix login-2026-10-16 14.05.09.png",
+    );
+    expect(buildExportFilename("diagnostics", { at, extension: "zip" })).toBe(
+      "paseo-diagnostics-2026-10-16 14.05.09.zip",
+    );
+    expect(buildExportFilename("digest", { day: "2026-05-01", extension: "md" })).toBe(
+      "paseo-digest-2026-05-01.md",
+    );
+    expect(() => buildExportFilename("export", { extension: "tar.gz" })).toThrow("extension");
+  });
+
+  it("strips characters no desktop OS accepts and composes Unicode", () => {
+    expect(sanitizeFileNameSegment('refactor: "auth"/<tokens>?')).toBe("refactor- -auth-tokens");
+    expect(sanitizeFileNameSegment("Café  notes\t.")).toBe("Café notes");
+    expect(sanitizeFileNameSegment("...")).toBe("");
+  });
+
+  it("keeps long names under the filesystem limit without splitting characters", () => {
+    const name = buildExportFilename("recording", {
+      label: "🎬".repeat(80),
+      day: "2026-10-16",
+      extension: "mp4",
+    });
+
+    expect(Buffer.byteLength(name)).toBeLessThanOrEqual(240);
+    expect(name.endsWith("-2026-10-16.mp4")).toBe(true);
+    expect(name).not.toContain("�");
+  });
+
+  it("adds a counter until the name is free", async () => {
+    const taken = new Set([path.join("/exports", "a.png"), path.join("/exports", "a (2).png")]);
+
+    await expect(
+      resolveAvailableFileName("/exports", "a.png", async (filePath) => taken.has(filePath)),
+    ).resolves.toBe("a (3).png");
+  });
+});
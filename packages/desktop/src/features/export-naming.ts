@@ -0,0 +1,147 @@
+import { access } from "node:fs/promises";
+import path from "node:path";
+
+export const EXPORT_KINDS = ["screenshot", "recording", "export", "diagnostics", "digest"] as const;
+
+export type ExportKind = (typeof EXPORT_KINDS)[number];
+
+export interface ExportNameContext {
+  /** Agent or session title; sanitized, shortened, and left out when empty. */
+  label?: string | null;
+  /** Timestamp in local time; defaults to now. */
+  at?: Date;
+  /** A `YYYY-MM-DD` day, for files that cover a whole day. Wins over `at`. */
+  day?: string | null;
+  /** Without the dot, e.g. `png`. */
+  extension: string;
+}
+
+// Most filesystems cap a name at 255 bytes; leave room for a " (99)" collision suffix.
+const MAX_FILENAME_BYTES = 240;
+const MAX_LABEL_CHARS = 64;
+const DAY_PATTERN = /^\d{4}-\d{2}-\d{2}$/;
+const EXTENSION_PATTERN = /^[A-Za-z0-9]{1,16}$/;
+// Reserved on Windows, and `/` everywhere; `:` also shows up as `/` in Finder.
+const UNSAFE_CHARACTERS = /[<>:"/\\|?*]|\p{Cc}/gu;
+
+function isExportKind(value: unknown): value is ExportKind {
+  return (EXPORT_KINDS as readonly unknown[]).includes(value);
+}
+
+function pad(value: number): string {
+  return String(value).padStart(2, "0");
+}
+
+/**
+ * Sortable local time without colons, which Windows rejects and macOS
+ * shows as slashes: `2026-10-16 14.05.09`.
+ */
+export function formatExportTimestamp(date: Date): string {
+  const day = `${date.getFullYear()}-${pad(date.getMonth() + 1)}-${pad(date.getDate())}`;
+  return `${day} ${pad(date.getHours())}.${pad(date.getMinutes())}.${pad(date.getSeconds())}`;
+}
+
+/**
+ * Makes free text safe as part of a file name on every desktop OS: unsafe
+ * and control characters become `-`, whitespace collapses, Unicode is
+ * composed so macOS and Windows agree on it, and trailing dots and spaces
+ * (dropped by Windows) are trimmed. Empty when nothing usable is left.
+ */
+export function sanitizeFileNameSegment(value: string, maxChars = MAX_LABEL_CHARS): string {
+  const cleaned = value
+    .normalize("NFC")
+    .replace(UNSAFE_CHARACTERS, "-")
+    .replace(/\s+/g, " ")
+    .replace(/-{2,}/g, "-")
+    .trim();
+  const shortened = Array.from(cleaned).slice(0, maxChars).join("");
+  return shortened.replace(/^[\s.-]+|[\s.-]+$/g, "");
+}
+
+/** Cuts on a code point boundary so a multi-byte character is never split. */
+function truncateToBytes(value: string, maxBytes: number): string {
+  let result = "";
+  for (const character of value) {
+    if (Buffer.byteLength(result + character) > maxBytes) {
+      break;
+    }
+    result += character;
+  }
+  return result;
+}
+
+/**
+ * `paseo-<kind>[-<label>]-<timestamp>.<extension>`, for example
+ * `paseo-screenshot-Fix login-2026-10-16 14.05.09.png`.
+ */
+export function buildExportFilename(kind: ExportKind, context: ExportNameContext): string {
+  if (!EXTENSION_PATTERN.test(context.extension)) {
+    throw new Error(`Invalid file extension: ${context.extension}`);
+  }
+  if (context.day && !DAY_PATTERN.test(context.day)) {
+    throw new Error(`day must be YYYY-MM-DD: ${context.day}`);
+  }
+  const stamp = context.day || formatExportTimestamp(context.at ?? new Date());
+  const extension = `.${context.extension.toLowerCase()}`;
+  // Only the label gives way, so the kind and timestamp always survive.
+  const fixedBytes = Buffer.byteLength(`paseo-${kind}--${stamp}${extension}`);
+  const label = context.label
+    ? truncateToBytes(sanitizeFileNameSegment(context.label), MAX_FILENAME_BYTES - fixedBytes)
+    : "";
+  return `${["paseo", kind, label, stamp].filter((part) => part.length > 0).join("-")}${extension}`;
+}
+
+/** `name.ext`, then `name (2).ext` and so on, until `exists` says the name is free. */
+export async function resolveAvailableFileName(
+  dir: string,
+  fileName: string,
+  exists: (filePath: string) => Promise<boolean> = pathExists,
+): Promise<string> {
+  const extension = path.extname(fileName);
+  const stem = fileName.slice(0, fileName.length - extension.length);
+  for (let attempt = 1; attempt < 100; attempt += 1) {
+    const candidate = attempt === 1 ? fileName : `${stem} (${attempt})${extension}`;
+    if (!(await exists(path.join(dir, candidate)))) {
+      return candidate;
+    }
+  }
+  throw new Error(`No free file name for ${fileName} in ${dir}`);
+}
+
+async function pathExists(filePath: string): Promise<boolean> {
+  try {
+    await access(filePath);
+    return true;
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * `build_export_filename`: `{ kind, label?, day?, extension, directory? }`.
+ * With `directory`, the name is also made unique within that folder.
+ */
+export async function buildExportFilenameCommand(
+  args: Record<string, unknown> | undefined,
+): Promise<{ fileName: string; path: string | null }> {
+  const kind = args?.kind;
+  if (!isExportKind(kind)) {
+    throw new Error(`kind must be one of ${EXPORT_KINDS.join(", ")}.`);
+  }
+  if (typeof args?.extension !== "string") {
+    throw new Error("extension is required.");
+  }
+  const fileName = buildExportFilename(kind, {
+    label: typeof args.label === "string" ? args.label : null,
+    day: typeof args.day === "string" ? args.day : null,
+    extension: args.extension.replace(/^\./, ""),
+  });
+  if (typeof args.directory !== "string") {
+    return { fileName, path: null };
+  }
+  if (!path.isAbsolute(args.directory)) {
+    throw new Error("directory must be an absolute path.");
+  }
+  const available = await resolveAvailableFileName(args.directory, fileName);
+  return { fileName: available, path: path.join(args.directory, available) };
+}
@@ -0,0 +1,76 @@
+import { app, net } from "electron";
+import log from "electron-log/main";
+import { emitDesktopEvent } from "../window/desktop-events.js";
+import { registerInitStateProvider } from "../window/init-state.js";
+import {
+  coerceFeatureFlagName,
+  coerceFeatureFlagValue,
+  createFeatureFlagStore,
+  FEATURE_FLAG_DEFAULTS,
+  parseRemoteFeatureFlags,
+  type FeatureFlagsSnapshot,
+  type FeatureFlagStore,
+} from "./feature-flags.js";
+
+const REMOTE_FETCH_TIMEOUT_MS = 10_000;
+const REMOTE_REFRESH_INTERVAL_MS = 6 * 60 * 60 * 1000;
+
+let featureFlagStore: FeatureFlagStore | null = null;
+// Kept in sync with the store so init state and main-process checks never wait on disk.
+let enabledFlags: Record<string, boolean> = { ...FEATURE_FLAG_DEFAULTS };
+
+function getFeatureFlagStore(): FeatureFlagStore {
+  featureFlagStore ??= createFeatureFlagStore({ userDataPath: app.getPath("userData") });
+  return featureFlagStore;
+}
+
+function publish(snapshot: FeatureFlagsSnapshot): FeatureFlagsSnapshot {
+  enabledFlags = Object.fromEntries(snapshot.flags.map((flag) => [flag.name, flag.enabled]));
+  emitDesktopEvent("feature-flags-changed", snapshot);
+  return snapshot;
+}
+
+/** Remote flags are opt-in per install: only fetched when PASEO_FEATURE_FLAGS_URL is set. */
+async function refreshRemoteFeatureFlags(url: string): Promise<void> {
+  const response = await net.fetch(url, { signal: AbortSignal.timeout(REMOTE_FETCH_TIMEOUT_MS) });
+  if (!response.ok) {
+    throw new Error(`Feature flag fetch failed with HTTP ${response.status}.`);
+  }
+  const values = parseRemoteFeatureFlags(await response.json());
+  publish(
+    await getFeatureFlagStore().applyRemote({ values, fetchedAt: new Date().toISOString() }),
+  );
+}
+
+export async function startFeatureFlags(): Promise<void> {
+  registerInitStateProvider("featureFlags", () => enabledFlags);
+  publish(await getFeatureFlagStore().get());
+
+  const remoteUrl = process.env.PASEO_FEATURE_FLAGS_URL?.trim();
+  if (!remoteUrl) {
+    return;
+  }
+  const refresh = () => {
+    void refreshRemoteFeatureFlags(remoteUrl).catch((error) => {
+      log.warn("[feature-flags] failed to fetch remote flags", error);
+    });
+  };
+  refresh();
+  setInterval(refresh, REMOTE_REFRESH_INTERVAL_MS).unref();
+}
+
+export function isFeatureEnabled(name: string): boolean {
+  return enabledFlags[name] === true;
+}
+
+export async function getFeatureFlags(): Promise<FeatureFlagsSnapshot> {
+  return await getFeatureFlagStore().get();
+}
+
+export async function setFeatureFlag(
+  args: Record<string, unknown> | undefined,
+): Promise<FeatureFlagsSnapshot> {
+  const name = coerceFeatureFlagName(args?.name);
+  const enabled = coerceFeatureFlagValue(args?.value);
+  return publish(await getFeatureFlagStore().set({ name, enabled }));
+}
@@ -0,0 +1,72 @@
+import { mkdtemp, readFile, rm } from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+import {
+  coerceFeatureFlagName,
+  coerceFeatureFlagValue,
+  createFeatureFlagStore,
+  parseRemoteFeatureFlags,
+  resolveFeatureFlags,
+} from "./feature-flags";
+
+describe("resolveFeatureFlags", () => {
+  it("prefers local overrides, then remote values, then defaults", () => {
+    expect(
+      resolveFeatureFlags({
+        defaults: { timelineCache: false, nativeWebSocketTransport: false },
+        remote: { timelineCache: true, newThing: true },
+        overrides: { nativeWebSocketTransport: true, timelineCache: false },
+      }),
+    ).toEqual([
+      { name: "nativeWebSocketTransport", enabled: true, source: "local" },
+      { name: "newThing", enabled: true, source: "remote" },
+      { name: "timelineCache", enabled: false, source: "local" },
+    ]);
+  });
+});
+
+describe("feature flag input", () => {
+  it("accepts dotted camelCase names and boolean or null values", () => {
+    expect(coerceFeatureFlagName("timeline.diskCache")).toBe("timeline.diskCache");
+    expect(() => coerceFeatureFlagName("Timeline cache")).toThrow();
+    expect(coerceFeatureFlagValue(null)).toBeNull();
+    expect(() => coerceFeatureFlagValue("true")).toThrow();
+  });
+
+  it("keeps only well-formed remote flags", () => {
+    expect(
+      parseRemoteFeatureFlags({ flags: { timelineCache: true, "bad name": true, other: "yes" } }),
+    ).toEqual({ timelineCache: true });
+    expect(parseRemoteFeatureFlags([])).toEqual({});
+  });
+});
+
+describe("createFeatureFlagStore", () => {
+  let userDataPath: string;
+
+  beforeEach(async () => {
+    userDataPath = await mkdtemp(path.join(os.tmpdir(), "paseo-feature-flags-"));
+  });
+
+  afterEach(async () => {
+    await rm(userDataPath, { recursive: true, force: true });
+  });
+
+  it("persists overrides and remote values across stores", async () => {
+    const store = createFeatureFlagStore({ userDataPath, defaults: { timelineCache: false } });
+    await store.applyRemote({ values: { timelineCache: true }, fetchedAt: "2026-05-01T00:00:00Z" });
+    await store.set({ name: "timelineCache", enabled: false });
+
+    const reopened = createFeatureFlagStore({ userDataPath, defaults: { timelineCache: false } });
+    expect(await reopened.get()).toEqual({
+      flags: [{ name: "timelineCache", enabled: false, source: "local" }],
+      remoteFetchedAt: "2026-05-01T00:00:00Z",
+    });
+
+    const cleared = await reopened.set({ name: "timelineCache", enabled: null });
+    expect(cleared.flags).toEqual([{ name: "timelineCache", enabled: true, source: "remote" }]);
+    const raw = JSON.parse(await readFile(path.join(userDataPath, "feature-flags.json"), "utf8"));
+    expect(raw.overrides).toEqual({});
+  });
+});
@@ -0,0 +1,181 @@
+import { readFile } from "node:fs/promises";
+import path from "node:path";
+import { updateJsonConfigFile } from "../settings/config-file.js";
+import { isRecord } from "../utils/is-record.js";
+
+const FEATURE_FLAGS_FILENAME = "feature-flags.json";
+const FEATURE_FLAG_NAME_PATTERN = /^[a-z][a-zA-Z0-9]*(\.[a-z][a-zA-Z0-9]*)*$/;
+const MAX_FEATURE_FLAG_NAME_LENGTH = 64;
+
+/** Flags this build knows about. Remote and local values may add others for newer renderers. */
+export const FEATURE_FLAG_DEFAULTS: Readonly<Record<string, boolean>> = {
+  nativeWebSocketTransport: false,
+  timelineCache: false,
+};
+
+export type FeatureFlagSource = "default" | "remote" | "local";
+
+export interface FeatureFlag {
+  name: string;
+  enabled: boolean;
+  source: FeatureFlagSource;
+}
+
+export interface FeatureFlagsSnapshot {
+  flags: FeatureFlag[];
+  remoteFetchedAt: string | null;
+}
+
+interface PersistedFeatureFlagsDocument {
+  version: 1;
+  overrides: Record<string, boolean>;
+  remote: { values: Record<string, boolean>; fetchedAt: string | null };
+}
+
+export interface FeatureFlagStore {
+  get(): Promise<FeatureFlagsSnapshot>;
+  /** `null` drops the local override so the remote or default value applies again. */
+  set(input: { name: string; enabled: boolean | null }): Promise<FeatureFlagsSnapshot>;
+  applyRemote(input: { values: Record<string, boolean>; fetchedAt: string }): Promise<
+    FeatureFlagsSnapshot
+  >;
+}
+
+function isFeatureFlagName(value: unknown): value is string {
+  return (
+    typeof value === "string" &&
+    value.length <= MAX_FEATURE_FLAG_NAME_LENGTH &&
+    FEATURE_FLAG_NAME_PATTERN.test(value)
+  );
+}
+
+export function coerceFeatureFlagName(value: unknown): string {
+  if (!isFeatureFlagName(value)) {
+    throw new Error("Feature flag names are camelCase words separated by dots.");
+  }
+  return value;
+}
+
+export function coerceFeatureFlagValue(value: unknown): boolean | null {
+  if (value === null || typeof value === "boolean") {
+    return value;
+  }
+  throw new Error("Feature flag value must be true, false or null.");
+}
+
+function coerceFlagValues(value: unknown): Record<string, boolean> {
+  if (!isRecord(value)) {
+    return {};
+  }
+  return Object.fromEntries(
+    Object.entries(value).filter(
+      (entry): entry is [string, boolean] =>
+        isFeatureFlagName(entry[0]) && typeof entry[1] === "boolean",
+    ),
+  );
+}
+
+/** Remote flag documents are `{ "flags": { name: boolean } }`; anything else is ignored. */
+export function parseRemoteFeatureFlags(raw: unknown): Record<string, boolean> {
+  return isRecord(raw) ? coerceFlagValues(raw.flags) : {};
+}
+
+/** Local overrides win over remote values, which win over this build's defaults. */
+export function resolveFeatureFlags(input: {
+  defaults: Readonly<Record<string, boolean>>;
+  remote: Record<string, boolean>;
+  overrides: Record<string, boolean>;
+}): FeatureFlag[] {
+  const names = new Set([
+    ...Object.keys(input.defaults),
+    ...Object.keys(input.remote),
+    ...Object.keys(input.overrides),
+  ]);
+  return [...names].sort().map((name): FeatureFlag => {
+    if (name in input.overrides) {
+      return { name, enabled: input.overrides[name]!, source: "local" };
+    }
+    if (name in input.remote) {
+      return { name, enabled: input.remote[name]!, source: "remote" };
+    }
+    return { name, enabled: input.defaults[name] ?? false, source: "default" };
+  });
+}
+
+function buildDefaultDocument(): PersistedFeatureFlagsDocument {
+  return { version: 1, overrides: {}, remote: { values: {}, fetchedAt: null } };
+}
+
+function coerceDocument(input: unknown): PersistedFeatureFlagsDocument {
+  if (!isRecord(input)) {
+    return buildDefaultDocument();
+  }
+  const remote = isRecord(input.remote) ? input.remote : {};
+  return {
+    version: 1,
+    overrides: coerceFlagValues(input.overrides),
+    remote: {
+      values: coerceFlagValues(remote.values),
+      fetchedAt: typeof remote.fetchedAt === "string" ? remote.fetchedAt : null,
+    },
+  };
+}
+
+export function createFeatureFlagStore(input: {
+  userDataPath: string;
+  defaults?: Readonly<Record<string, boolean>>;
+}): FeatureFlagStore {
+  const filePath = path.join(input.userDataPath, FEATURE_FLAGS_FILENAME);
+  const defaults = input.defaults ?? FEATURE_FLAG_DEFAULTS;
+  let loaded: Promise<PersistedFeatureFlagsDocument> | null = null;
+
+  function load(): Promise<PersistedFeatureFlagsDocument> {
+    loaded ??= readFile(filePath, "utf8")
+      .then((raw) => coerceDocument(JSON.parse(raw)))
+      .catch(() => buildDefaultDocument());
+    return loaded;
+  }
+
+  async function update(
+    mutate: (current: PersistedFeatureFlagsDocument) => PersistedFeatureFlagsDocument,
+  ): Promise<PersistedFeatureFlagsDocument> {
+    const document = await updateJsonConfigFile(filePath, (raw) => mutate(coerceDocument(raw)));
+    loaded = Promise.resolve(document);
+    return document;
+  }
+
+  function snapshot(document: PersistedFeatureFlagsDocument): FeatureFlagsSnapshot {
+    return {
+      flags: resolveFeatureFlags({
+        defaults,
+        remote: document.remote.values,
+        overrides: document.overrides,
+      }),
+      remoteFetchedAt: document.remote.fetchedAt,
+    };
+  }
+
+  return {
+    async get() {
+      return snapshot(await load());
+    },
+
+    async set({ name, enabled }) {
+      const next = await update((current) => {
+        const overrides = { ...current.overrides };
+        if (enabled === null) {
+          delete overrides[name];
+        } else {
+          overrides[name] = enabled;
+        }
+        return { ...current, overrides };
+      });
+      return snapshot(next);
+    },
+
+    async applyRemote({ values, fetchedAt }) {
+      const next = await update((current) => ({ ...current, remote: { values, fetchedAt } }));
+      return snapshot(next);
+    },
+  };
+}
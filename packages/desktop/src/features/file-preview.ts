@@ -0,0 +1,30 @@
+import { stat } from "node:fs/promises";
+import path from "node:path";
+import { BrowserWindow, shell } from "electron";
+import { toFileSystemPath } from "../system/windows-paths.js";
+import { resolveManagedAttachmentPath } from "./attachments.js";
+
+/**
+ * Quick Look on macOS. Other platforms have no system previewer, so the file
+ * opens in its default app instead.
+ */
+export async function previewFile(input: {
+  path: unknown;
+  win: BrowserWindow;
+}): Promise<{ method: "quick-look" | "default-app" }> {
+  const filePath = resolveManagedAttachmentPath(input.path);
+  const stats = await stat(toFileSystemPath(filePath));
+  if (!stats.isFile()) {
+    throw new Error("Only files can be previewed.");
+  }
+
+  if (process.platform === "darwin") {
+    input.win.previewFile(filePath, path.basename(filePath));
+    return { method: "quick-look" };
+  }
+  const error = await shell.openPath(filePath);
+  if (error) {
+    throw new Error(`Failed to open ${path.basename(filePath)}: ${error}`);
+  }
+  return { method: "default-app" };
+}
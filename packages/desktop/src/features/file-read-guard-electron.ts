@@ -0,0 +1,8 @@
+import { getDesktopSettingsStore } from "../settings/desktop-settings-electron.js";
+import { megabytesToBytes } from "./file-read-guard.js";
+
+/** The user's ceiling for reads that return a whole file, from desktop settings. */
+export async function getFileReadMaxBytes(): Promise<number> {
+  const { fileReads } = await getDesktopSettingsStore().get();
+  return megabytesToBytes(fileReads.maxSizeMb);
+}
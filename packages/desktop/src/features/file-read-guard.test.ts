@@ -0,0 +1,52 @@
+import { mkdtemp, rm, writeFile } from "node:fs/promises";
+import { tmpdir } from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+import { FileReadGuardError, looksBinary, readGuardedFile } from "./file-read-guard";
+
+describe("file read guard", () => {
+  let dir: string;
+
+  beforeEach(async () => {
+    dir = await mkdtemp(path.join(tmpdir(), "paseo-file-read-guard-"));
+  });
+
+  afterEach(async () => {
+    await rm(dir, { recursive: true, force: true });
+  });
+
+  it("treats NUL bytes as binary unless a UTF-16 byte order mark says otherwise", () => {
+    expect(looksBinary(Buffer.from("const answer = 42;\n"))).toBe(false);
+    expect(looksBinary(Buffer.from([0x89, 0x50, 0x4e, 0x47, 0x00, 0x00]))).toBe(true);
+    expect(looksBinary(Buffer.from("\uFEFFhi", "utf16le"))).toBe(false);
+  });
+
+  it("refuses files over the ceiling with the alternatives", async () => {
+    const filePath = path.join(dir, "huge.log");
+    await writeFile(filePath, "x".repeat(2048));
+
+    const error = await readGuardedFile(filePath, { maxBytes: 1024, allowBinary: false }).catch(
+      (caught: unknown) => caught,
+    );
+
+    expect(error).toBeInstanceOf(FileReadGuardError);
+    expect(error).toMatchObject({
+      reason: "too-large",
+      byteSize: 2048,
+      limitBytes: 1024,
+      alternatives: ["preview", "stream"],
+    });
+  });
+
+  it("refuses binary content for text reads only", async () => {
+    const filePath = path.join(dir, "logo.png");
+    await writeFile(filePath, Buffer.from([0x89, 0x50, 0x4e, 0x47, 0x00, 0x1a]));
+
+    await expect(
+      readGuardedFile(filePath, { maxBytes: 1024, allowBinary: false }),
+    ).rejects.toMatchObject({ reason: "binary", alternatives: ["preview"] });
+    await expect(
+      readGuardedFile(filePath, { maxBytes: 1024, allowBinary: true }),
+    ).resolves.toHaveLength(6);
+  });
+});
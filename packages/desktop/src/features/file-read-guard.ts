@@ -0,0 +1,121 @@
+import { constants } from "node:fs";
+import { type FileHandle, open } from "node:fs/promises";
+
+// git's heuristic: a NUL byte in the first 8000 bytes means binary.
+const BINARY_SNIFF_BYTES = 8000;
+const MB = 1024 * 1024;
+
+export type FileReadGuardReason = "too-large" | "binary";
+
+/**
+ * What the caller can do instead: `preview` hands the file to the system
+ * previewer, `stream` reads it in chunks without holding it in memory.
+ */
+export type FileReadAlternative = "preview" | "stream";
+
+export class FileReadGuardError extends Error {
+  readonly reason: FileReadGuardReason;
+  readonly path: string;
+  readonly byteSize: number;
+  readonly limitBytes: number | null;
+  readonly alternatives: FileReadAlternative[];
+
+  constructor(input: {
+    reason: FileReadGuardReason;
+    path: string;
+    byteSize: number;
+    limitBytes: number | null;
+  }) {
+    const alternatives: FileReadAlternative[] =
+      input.reason === "too-large" ? ["preview", "stream"] : ["preview"];
+    super(
+      input.reason === "too-large"
+        ? `File too large to read (${formatMegabytes(input.byteSize)}, ` +
+            `limit ${formatMegabytes(input.limitBytes ?? 0)}): ${input.path}. ` +
+            "Preview or stream it instead."
+        : `Binary file cannot be read as text: ${input.path}. Preview it instead.`,
+    );
+    this.name = "FileReadGuardError";
+    this.reason = input.reason;
+    this.path = input.path;
+    this.byteSize = input.byteSize;
+    this.limitBytes = input.limitBytes;
+    this.alternatives = alternatives;
+  }
+}
+
+export interface FileReadGuardOptions {
+  maxBytes: number;
+  /** Text reads refuse binary content; attachments and images allow it. */
+  allowBinary: boolean;
+}
+
+function formatMegabytes(bytes: number): string {
+  return `${(bytes / MB).toFixed(bytes < 10 * MB ? 1 : 0)} MB`;
+}
+
+export function megabytesToBytes(megabytes: number): number {
+  return megabytes * MB;
+}
+
+/** UTF-16 text is full of NUL bytes, so a byte order mark rules it out first. */
+export function looksBinary(sample: Uint8Array): boolean {
+  if (
+    (sample[0] === 0xff && sample[1] === 0xfe) ||
+    (sample[0] === 0xfe && sample[1] === 0xff)
+  ) {
+    return false;
+  }
+  return sample.subarray(0, BINARY_SNIFF_BYTES).includes(0);
+}
+
+export function assertReadableSize(
+  filePath: string,
+  byteSize: number,
+  options: Pick<FileReadGuardOptions, "maxBytes">,
+): void {
+  if (byteSize > options.maxBytes) {
+    throw new FileReadGuardError({
+      reason: "too-large",
+      path: filePath,
+      byteSize,
+      limitBytes: options.maxBytes,
+    });
+  }
+}
+
+/**
+ * Reads an already opened file after checking its size on the handle, so a
+ * file that grows between stat and read cannot slip past the ceiling.
+ */
+export async function readGuardedHandle(
+  handle: FileHandle,
+  filePath: string,
+  options: FileReadGuardOptions,
+): Promise<Buffer> {
+  const { size } = await handle.stat();
+  assertReadableSize(filePath, size, options);
+  const bytes = await handle.readFile();
+  assertReadableSize(filePath, bytes.byteLength, options);
+  if (!options.allowBinary && looksBinary(bytes)) {
+    throw new FileReadGuardError({
+      reason: "binary",
+      path: filePath,
+      byteSize: bytes.byteLength,
+      limitBytes: null,
+    });
+  }
+  return bytes;
+}
+
+export async function readGuardedFile(
+  filePath: string,
+  options: FileReadGuardOptions,
+): Promise<Buffer> {
+  const handle = await open(filePath, constants.O_RDONLY);
+  try {
+    return await readGuardedHandle(handle, filePath, options);
+  } finally {
+    await handle.close();
+  }
+}
@@ -0,0 +1,92 @@
+import { spawn } from "node:child_process";
+
+// Enough for `worktree list` on large repos and for patch previews; more is a runaway command.
+const MAX_OUTPUT_BYTES = 16 * 1024 * 1024;
+
+export class GitCommandError extends Error {
+  readonly exitCode: number | null;
+  readonly stderr: string;
+
+  constructor(input: { args: string[]; exitCode: number | null; stderr: string }) {
+    const detail = input.stderr.trim().split("\n").at(-1) ?? "";
+    super(`git ${input.args[0] ?? ""} failed${detail ? `: ${detail}` : ""}`);
+    this.name = "GitCommandError";
+    this.exitCode = input.exitCode;
+    this.stderr = input.stderr;
+  }
+}
+
+export interface GitRunOptions {
+  cwd: string;
+  /** Merged over the login environment, e.g. the commit signing overlay. */
+  env?: Record<string, string>;
+  stdin?: string;
+  signal?: AbortSignal;
+  /** Progress output (`--progress`) arrives on stderr as it is written. */
+  onStderr?: (chunk: string) => void;
+}
+
+/**
+ * Runs the user's git from the login environment. Prompts are turned off:
+ * nobody can answer one, so credentials must come from helpers or agents.
+ */
+export function runGit(
+  args: string[],
+  options: GitRunOptions,
+): Promise<{ stdout: string; stderr: string }> {
+  return new Promise((resolve, reject) => {
+    const child = spawn("git", args, {
+      cwd: options.cwd,
+      env: {
+        ...process.env,
+        GIT_TERMINAL_PROMPT: "0",
+        GCM_INTERACTIVE: "never",
+        ...options.env,
+      },
+      signal: options.signal,
+      windowsHide: true,
+    });
+    const stdout: Buffer[] = [];
+    const stderr: Buffer[] = [];
+    let outputBytes = 0;
+
+    const collect = (target: Buffer[]) => (chunk: Buffer) => {
+      outputBytes += chunk.byteLength;
+      if (outputBytes > MAX_OUTPUT_BYTES) {
+        child.kill();
+        return;
+      }
+      target.push(chunk);
+    };
+    child.stdout.on("data", collect(stdout));
+    child.stderr.on("data", (chunk: Buffer) => {
+      collect(stderr)(chunk);
+      options.onStderr?.(chunk.toString("utf8"));
+    });
+    child.on("error", (error) => {
+      reject(
+        error.name === "AbortError"
+          ? new Error("Cancelled")
+          : new Error(`Failed to run git: ${error.message}`),
+      );
+    });
+    child.on("close", (exitCode) => {
+      const result = {
+        stdout: Buffer.concat(stdout).toString("utf8"),
+        stderr: Buffer.concat(stderr).toString("utf8"),
+      };
+      if (outputBytes > MAX_OUTPUT_BYTES) {
+        reject(new Error(`git ${args[0] ?? ""} produced more output than the desktop accepts.`));
+      } else if (exitCode === 0) {
+        resolve(result);
+      } else {
+        reject(new GitCommandError({ args, exitCode, stderr: result.stderr }));
+      }
+    });
+    if (options.stdin !== undefined) {
+      child.stdin.end(options.stdin);
+    } else {
+      child.stdin.end();
+    }
+  });
+}
@@ -0,0 +1,178 @@
+import { randomUUID } from "node:crypto";
+import { rm } from "node:fs/promises";
+import path from "node:path";
+import log from "electron-log/main";
+import { emitDesktopEvent } from "../window/desktop-events.js";
+import { GitCommandError, runGit } from "./git-cli.js";
+import {
+  buildCloneArgs,
+  buildCloneCredentialOptions,
+  checkCloneDestination,
+  coerceCloneInput,
+  createCloneProgressParser,
+  describeCloneFailure,
+  redactCloneUrl,
+} from "./git-clone.js";
+import {
+  applyPatch as applyGitPatch,
+  type ApplyPatchResult,
+  coerceApplyPatchInput,
+} from "./git-patch.js";
+import {
+  coerceCreateWorktreeInput,
+  coerceRepoPath,
+  createWorktree as createGitWorktree,
+  type GitWorktree,
+  listWorktrees as listGitWorktrees,
+  removeWorktree as removeGitWorktree,
+} from "./git-worktrees.js";
+import { getWorkspaceTrustRegistry } from "./workspace-trust-electron.js";
+
+const CLONE_PROGRESS_EVENT = "git-clone-progress";
+const PROGRESS_INTERVAL_MS = 250;
+
+const activeClones = new Map<string, AbortController>();
+
+export async function listWorktrees(
+  args: Record<string, unknown> | undefined,
+): Promise<GitWorktree[]> {
+  return await listGitWorktrees(coerceRepoPath(args));
+}
+
+/** Trust is checked for the repository and for the folder the worktree lands in. */
+export async function createWorktree(
+  args: Record<string, unknown> | undefined,
+): Promise<GitWorktree> {
+  const input = coerceCreateWorktreeInput(args);
+  const trust = getWorkspaceTrustRegistry();
+  await trust.ensureTrusted(input.repo);
+  await trust.ensureTrusted(path.dirname(input.dest));
+  const worktree = await createGitWorktree(input);
+  log.info(`[git] created worktree ${worktree.path} on ${worktree.branch ?? "detached HEAD"}`);
+  return worktree;
+}
+
+export async function removeWorktree(
+  args: Record<string, unknown> | undefined,
+): Promise<GitWorktree[]> {
+  const repo = coerceRepoPath(args);
+  if (typeof args?.path !== "string" || !path.isAbsolute(args.path)) {
+    throw new Error("path must be an absolute path.");
+  }
+  await getWorkspaceTrustRegistry().ensureTrusted(repo);
+  const remaining = await removeGitWorktree({
+    repo,
+    path: path.resolve(args.path),
+    force: args.force === true,
+  });
+  log.info(`[git] removed worktree ${args.path}`);
+  return remaining;
+}
+
+async function readCredentialHelper(cwd: string): Promise<string | null> {
+  try {
+    const { stdout } = await runGit(["config", "--get", "credential.helper"], { cwd });
+    return stdout.trim() || null;
+  } catch {
+    return null;
+  }
+}
+
+async function readCurrentBranch(repo: string): Promise<string | null> {
+  try {
+    const { stdout } = await runGit(["symbolic-ref", "--short", "-q", "HEAD"], { cwd: repo });
+    return stdout.trim() || null;
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * `requestId` lets the caller cancel with `cancel_clone_repository` and match
+ * `git-clone-progress` events; one is generated when omitted. A failed or
+ * cancelled clone leaves no partial checkout behind.
+ */
+export async function cloneRepository(
+  args: Record<string, unknown> | undefined,
+): Promise<{ requestId: string; path: string; branch: string | null }> {
+  const input = coerceCloneInput(args);
+  const requestId =
+    typeof args?.requestId === "string" && args.requestId.length > 0
+      ? args.requestId
+      : randomUUID();
+  if (activeClones.has(requestId)) {
+    throw new Error(`A clone is already running for request ${requestId}.`);
+  }
+  const parent = path.dirname(input.dest);
+  await getWorkspaceTrustRegistry().ensureTrusted(parent);
+  const existed = await checkCloneDestination(input.dest);
+  const credentials = buildCloneCredentialOptions({
+    platform: process.platform,
+    env: process.env,
+    credentialHelper: await readCredentialHelper(parent),
+  });
+
+  const controller = new AbortController();
+  activeClones.set(requestId, controller);
+  const parser = createCloneProgressParser();
+  let lastProgressAt = 0;
+  const url = redactCloneUrl(input.url);
+  log.info(`[git] cloning ${url} into ${input.dest}`);
+  try {
+    await runGit([...credentials.args, ...buildCloneArgs(input)], {
+      cwd: parent,
+      env: credentials.env,
+      signal: controller.signal,
+      onStderr: (chunk) => {
+        const progress = parser.push(chunk).at(-1);
+        const at = Date.now();
+        if (progress && (at - lastProgressAt >= PROGRESS_INTERVAL_MS || progress.percent === 100)) {
+          lastProgressAt = at;
+          emitDesktopEvent(CLONE_PROGRESS_EVENT, { requestId, url, dest: input.dest, ...progress });
+        }
+      },
+    });
+  } catch (error) {
+    if (!existed) {
+      await rm(input.dest, { recursive: true, force: true }).catch(() => undefined);
+    }
+    log.warn(`[git] clone of ${url} failed`, error);
+    const friendly =
+      error instanceof GitCommandError ? describeCloneFailure(input.url, error.stderr) : null;
+    throw friendly ? new Error(friendly) : error;
+  } finally {
+    activeClones.delete(requestId);
+  }
+  return { requestId, path: input.dest, branch: await readCurrentBranch(input.dest) };
+}
+
+export function cancelCloneRepository(requestId: unknown): boolean {
+  if (typeof requestId !== "string") {
+    return false;
+  }
+  const controller = activeClones.get(requestId);
+  if (!controller) {
+    return false;
+  }
+  activeClones.delete(requestId);
+  controller.abort();
+  return true;
+}
+
+/** A check-only run reads the checkout and needs no trust; applying writes to it. */
+export async function applyPatch(
+  args: Record<string, unknown> | undefined,
+): Promise<ApplyPatchResult> {
+  const input = coerceApplyPatchInput(args);
+  if (!input.checkOnly) {
+    await getWorkspaceTrustRegistry().ensureTrusted(input.repo);
+  }
+  const result = await applyGitPatch(input);
+  if (!input.checkOnly) {
+    log.info(
+      `[git] patch ${result.applied ? "applied" : "refused"} in ${input.repo}` +
+        ` (${result.files.length} files, ${result.conflicts.length} conflicts)`,
+    );
+  }
+  return result;
+}
@@ -0,0 +1,45 @@
+import path from "node:path";
+import { describe, expect, it } from "vitest";
+import {
+  coerceApplyPatchInput,
+  parsePatchConflicts,
+  parsePatchNumstat,
+  parseThreeWayConflicts,
+} from "./git-patch";
+
+describe("git patch", () => {
+  it("validates input and terminates the last hunk", () => {
+    const repo = path.resolve("/work/paseo");
+
+    const input = coerceApplyPatchInput({ repo, patchText: "diff --git a/x b/x", checkOnly: true });
+
+    expect(input).toEqual({ repo, patchText: "diff --git a/x b/x\n", checkOnly: true });
+    expect(() => coerceApplyPatchInput({ repo, patchText: "  " })).toThrow(
+      "patchText is required.",
+    );
+  });
+
+  it("reads per-file line counts, with binary files uncounted", () => {
+    expect(parsePatchNumstat("3\t1\tsrc/app.ts\n-\t-\tassets/logo.png\n")).toEqual([
+      { path: "src/app.ts", additions: 3, deletions: 1 },
+      { path: "assets/logo.png", additions: null, deletions: null },
+    ]);
+  });
+
+  it("reports the files and hunks that did not apply", () => {
+    const stderr = [
+      "error: patch failed: src/app.ts:12",
+      "error: src/app.ts: patch does not apply",
+      "error: src/removed.ts: No such file or directory",
+      "",
+    ].join("\n");
+
+    expect(parsePatchConflicts(stderr)).toEqual([
+      { path: "src/app.ts", line: 12, reason: "patch does not apply" },
+      { path: "src/removed.ts", line: null, reason: "No such file or directory" },
+    ]);
+    expect(
+      parseThreeWayConflicts("Applied patch to 'src/app.ts' with conflicts.\nU src/app.ts\n"),
+    ).toEqual([{ path: "src/app.ts", line: null, reason: "merged with conflicts" }]);
+  });
+});
@@ -0,0 +1,174 @@
+import path from "node:path";
+import { GitCommandError, runGit } from "./git-cli.js";
+
+// Agent patches are text diffs; anything this large is a mistake or a binary blob.
+const MAX_PATCH_BYTES = 8 * 1024 * 1024;
+const PATCH_FAILED_PATTERN = /^error: patch failed: (.+):(\d+)$/;
+const PATH_ERROR_PATTERN = /^error: (.+?): (.+)$/;
+const THREE_WAY_CONFLICT_PATTERN = /^U (.+)$/;
+
+export interface ApplyPatchInput {
+  repo: string;
+  patchText: string;
+  /** Only report whether the patch applies; the checkout is left untouched. */
+  checkOnly: boolean;
+}
+
+export interface PatchFileStat {
+  path: string;
+  /** `null` for binary files. */
+  additions: number | null;
+  deletions: number | null;
+}
+
+export interface PatchConflict {
+  path: string;
+  /** First line of the hunk that did not match, when git reports one. */
+  line: number | null;
+  reason: string;
+}
+
+export interface ApplyPatchResult {
+  /** Whether the patch applies (`checkOnly`) or was written to the checkout. */
+  applied: boolean;
+  checkOnly: boolean;
+  /** The patch went in with a three-way merge; `conflicts` lists files with markers. */
+  merged: boolean;
+  files: PatchFileStat[];
+  conflicts: PatchConflict[];
+}
+
+export function coerceApplyPatchInput(args: Record<string, unknown> | undefined): ApplyPatchInput {
+  const repo = typeof args?.repo === "string" ? args.repo.trim() : "";
+  if (!path.isAbsolute(repo)) {
+    throw new Error("repo must be an absolute path.");
+  }
+  if (typeof args?.patchText !== "string" || args.patchText.trim().length === 0) {
+    throw new Error("patchText is required.");
+  }
+  if (Buffer.byteLength(args.patchText) > MAX_PATCH_BYTES) {
+    throw new Error("patchText is larger than 8 MB.");
+  }
+  // git rejects a patch whose last hunk is missing its newline.
+  const patchText = args.patchText.endsWith("\n") ? args.patchText : `${args.patchText}\n`;
+  return { repo: path.resolve(repo), patchText, checkOnly: args.checkOnly === true };
+}
+
+/** Parses `git apply --numstat`; binary files report `-` for both counts. */
+export function parsePatchNumstat(output: string): PatchFileStat[] {
+  const files: PatchFileStat[] = [];
+  for (const line of output.split("\n")) {
+    const [additions, deletions, ...rest] = line.split("\t");
+    if (additions === undefined || deletions === undefined || rest.length === 0) {
+      continue;
+    }
+    files.push({
+      path: rest.join("\t"),
+      additions: additions === "-" ? null : Number(additions),
+      deletions: deletions === "-" ? null : Number(deletions),
+    });
+  }
+  return files;
+}
+
+/** Collects per-file reasons from `git apply --check` stderr, one entry per path. */
+export function parsePatchConflicts(stderr: string): PatchConflict[] {
+  const conflicts = new Map<string, PatchConflict>();
+  for (const line of stderr.split("\n").map((entry) => entry.trim())) {
+    const failed = PATCH_FAILED_PATTERN.exec(line);
+    if (failed) {
+      const filePath = failed[1] as string;
+      conflicts.set(filePath, {
+        path: filePath,
+        line: Number(failed[2]),
+        reason: "patch does not apply",
+      });
+      continue;
+    }
+    const pathError = PATH_ERROR_PATTERN.exec(line);
+    if (pathError) {
+      const filePath = pathError[1] as string;
+      const existing = conflicts.get(filePath);
+      conflicts.set(filePath, {
+        path: filePath,
+        line: existing?.line ?? null,
+        reason: pathError[2] as string,
+      });
+    }
+  }
+  return [...conflicts.values()];
+}
+
+export function parseThreeWayConflicts(stderr: string): PatchConflict[] {
+  return stderr
+    .split("\n")
+    .map((line) => THREE_WAY_CONFLICT_PATTERN.exec(line.trim())?.[1])
+    .filter((filePath): filePath is string => filePath !== undefined)
+    .map((filePath) => ({ path: filePath, line: null, reason: "merged with conflicts" }));
+}
+
+async function readPatchStats(input: ApplyPatchInput): Promise<PatchFileStat[]> {
+  try {
+    const { stdout } = await runGit(["apply", "--numstat", "--recount", "-"], {
+      cwd: input.repo,
+      stdin: input.patchText,
+    });
+    return parsePatchNumstat(stdout);
+  } catch (error) {
+    if (error instanceof GitCommandError) {
+      const detail = error.stderr.trim().split("\n").at(-1)?.replace(/^error: /, "") ?? "";
+      throw new Error(`Not a valid unified diff${detail ? `: ${detail}` : "."}`);
+    }
+    throw error;
+  }
+}
+
+async function tryApply(input: ApplyPatchInput, flags: string[]): Promise<string | null> {
+  try {
+    await runGit(["apply", "--recount", ...flags, "-"], {
+      cwd: input.repo,
+      stdin: input.patchText,
+    });
+    return null;
+  } catch (error) {
+    if (error instanceof GitCommandError) {
+      return error.stderr;
+    }
+    throw error;
+  }
+}
+
+/**
+ * Applies a unified diff to the working tree with `git apply`, which either
+ * applies every hunk or none. When the checkout has drifted from what the
+ * agent saw, a three-way merge is tried next; it needs the patch's base
+ * blobs, so a diff made against another machine's checkout may still be
+ * refused, with the files and hunks that did not match.
+ */
+export async function applyPatch(input: ApplyPatchInput): Promise<ApplyPatchResult> {
+  const files = await readPatchStats(input);
+  const checkErrors = await tryApply(input, ["--check"]);
+  const result = { checkOnly: input.checkOnly, files };
+  if (checkErrors === null) {
+    if (!input.checkOnly) {
+      const applyErrors = await tryApply(input, []);
+      if (applyErrors !== null) {
+        const conflicts = parsePatchConflicts(applyErrors);
+        return { ...result, applied: false, merged: false, conflicts };
+      }
+    }
+    return { ...result, applied: true, merged: false, conflicts: [] };
+  }
+  const conflicts = parsePatchConflicts(checkErrors);
+  if (input.checkOnly) {
+    return { ...result, applied: false, merged: false, conflicts };
+  }
+  const mergeErrors = await tryApply(input, ["--3way"]);
+  if (mergeErrors === null) {
+    return { ...result, applied: true, merged: true, conflicts: [] };
+  }
+  const merged = parseThreeWayConflicts(mergeErrors);
+  return merged.length > 0
+    ? { ...result, applied: true, merged: true, conflicts: merged }
+    : { ...result, applied: false, merged: false, conflicts };
+}
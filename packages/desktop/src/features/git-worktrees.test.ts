@@ -0,0 +1,69 @@
+import path from "node:path";
+import { describe, expect, it } from "vitest";
+import { coerceCreateWorktreeInput, parseWorktreeList } from "./git-worktrees";
+
+describe("git worktrees", () => {
+  it("parses porcelain output with the main worktree first", () => {
+    const output = [
+      "worktree /src/app",
+      "HEAD 1111111111111111111111111111111111111111",
+      "branch refs/heads/main",
+      "",
+      "worktree /src/app-feature/x",
+      "HEAD 2222222222222222222222222222222222222222",
+      "branch refs/heads/feature/x",
+      "locked agent run",
+      "",
+      "worktree /src/app-detached",
+      "HEAD 3333333333333333333333333333333333333333",
+      "detached",
+      "prunable gitdir file points to non-existent location",
+      "",
+    ].join("\n");
+
+    expect(parseWorktreeList(output)).toEqual([
+      {
+        path: "/src/app",
+        head: "1111111111111111111111111111111111111111",
+        branch: "main",
+        main: true,
+        bare: false,
+        locked: false,
+        prunable: false,
+      },
+      {
+        path: "/src/app-feature/x",
+        head: "2222222222222222222222222222222222222222",
+        branch: "feature/x",
+        main: false,
+        bare: false,
+        locked: true,
+        prunable: false,
+      },
+      {
+        path: "/src/app-detached",
+        head: "3333333333333333333333333333333333333333",
+        branch: null,
+        main: false,
+        bare: false,
+        locked: false,
+        prunable: true,
+      },
+    ]);
+  });
+
+  it("defaults the destination to a sibling of the repository", () => {
+    const repo = path.resolve("/src/app");
+
+    expect(coerceCreateWorktreeInput({ repo, branch: "agent/fix-login" })).toEqual({
+      repo,
+      branch: "agent/fix-login",
+      dest: path.join(path.dirname(repo), "app-agent-fix-login"),
+      baseRef: null,
+    });
+    expect(() => coerceCreateWorktreeInput({ repo, branch: "x", dest: "relative" })).toThrow(
+      "dest must be an absolute path.",
+    );
+    expect(() => coerceCreateWorktreeInput({ repo })).toThrow("branch is required.");
+  });
+});
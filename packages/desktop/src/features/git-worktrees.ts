@@ -0,0 +1,170 @@
+import { realpath } from "node:fs/promises";
+import path from "node:path";
+import { GitCommandError, runGit } from "./git-cli.js";
+
+export interface GitWorktree {
+  path: string;
+  /** Commit checked out, or `null` for a bare repository entry. */
+  head: string | null;
+  /** Short branch name; `null` when detached or bare. */
+  branch: string | null;
+  /** The repository's own checkout, which cannot be removed. */
+  main: boolean;
+  bare: boolean;
+  locked: boolean;
+  /** Its directory is gone; `git worktree prune` would drop it. */
+  prunable: boolean;
+}
+
+export interface CreateWorktreeInput {
+  repo: string;
+  branch: string;
+  dest: string;
+  /** Where a new branch starts; defaults to the repository's HEAD. */
+  baseRef: string | null;
+}
+
+function requireString(value: unknown, name: string): string {
+  if (typeof value !== "string" || value.trim().length === 0) {
+    throw new Error(`${name} is required.`);
+  }
+  return value.trim();
+}
+
+function requireAbsolutePath(value: unknown, name: string): string {
+  const filePath = requireString(value, name);
+  if (!path.isAbsolute(filePath)) {
+    throw new Error(`${name} must be an absolute path.`);
+  }
+  return path.resolve(filePath);
+}
+
+export function coerceRepoPath(args: Record<string, unknown> | undefined): string {
+  return requireAbsolutePath(args?.repo, "repo");
+}
+
+/** Without `dest`, the worktree goes next to the repository as `<repo>-<branch>`. */
+export function coerceCreateWorktreeInput(
+  args: Record<string, unknown> | undefined,
+): CreateWorktreeInput {
+  const repo = coerceRepoPath(args);
+  const branch = requireString(args?.branch, "branch");
+  const dest =
+    args?.dest === undefined || args.dest === null
+      ? path.join(path.dirname(repo), `${path.basename(repo)}-${branch.replace(/[\\/]/g, "-")}`)
+      : requireAbsolutePath(args.dest, "dest");
+  const baseRef =
+    args?.baseRef === undefined || args.baseRef === null
+      ? null
+      : requireString(args.baseRef, "baseRef");
+  return { repo, branch, dest, baseRef };
+}
+
+/** Parses `git worktree list --porcelain`; the first entry is the main worktree. */
+export function parseWorktreeList(output: string): GitWorktree[] {
+  const worktrees: GitWorktree[] = [];
+  for (const block of output.split(/\n\n+/)) {
+    const lines = block.split("\n").filter((line) => line.length > 0);
+    const worktreeLine = lines.find((line) => line.startsWith("worktree "));
+    if (!worktreeLine) {
+      continue;
+    }
+    const field = (name: string) =>
+      lines.find((line) => line === name || line.startsWith(`${name} `));
+    const branchRef = field("branch")?.slice("branch ".length) ?? null;
+    worktrees.push({
+      path: worktreeLine.slice("worktree ".length),
+      head: field("HEAD")?.slice("HEAD ".length) ?? null,
+      branch: branchRef?.replace(/^refs\/heads\//, "") ?? null,
+      main: worktrees.length === 0,
+      bare: field("bare") !== undefined,
+      locked: field("locked") !== undefined,
+      prunable: field("prunable") !== undefined,
+    });
+  }
+  return worktrees;
+}
+
+async function canonicalPath(filePath: string): Promise<string> {
+  try {
+    return await realpath(filePath);
+  } catch {
+    return path.resolve(filePath);
+  }
+}
+
+// git prints resolved paths (`/private/var` on macOS, forward slashes on Windows).
+async function findWorktree(
+  worktrees: GitWorktree[],
+  filePath: string,
+): Promise<GitWorktree | undefined> {
+  const target = await canonicalPath(filePath);
+  for (const worktree of worktrees) {
+    if ((await canonicalPath(worktree.path)) === target) {
+      return worktree;
+    }
+  }
+  return undefined;
+}
+
+export async function listWorktrees(repo: string): Promise<GitWorktree[]> {
+  const { stdout } = await runGit(["worktree", "list", "--porcelain"], { cwd: repo });
+  return parseWorktreeList(stdout);
+}
+
+async function branchExists(repo: string, branch: string): Promise<boolean> {
+  try {
+    await runGit(["rev-parse", "--verify", "--quiet", `refs/heads/${branch}`], { cwd: repo });
+    return true;
+  } catch (error) {
+    if (error instanceof GitCommandError) {
+      return false;
+    }
+    throw error;
+  }
+}
+
+/**
+ * Checks out `branch` into a new worktree, creating the branch from
+ * `baseRef` when it does not exist yet.
+ */
+export async function createWorktree(input: CreateWorktreeInput): Promise<GitWorktree> {
+  await runGit(["check-ref-format", "--branch", input.branch], { cwd: input.repo }).catch(() => {
+    throw new Error(`Invalid branch name: ${input.branch}`);
+  });
+  const args = (await branchExists(input.repo, input.branch))
+    ? ["worktree", "add", input.dest, input.branch]
+    : [
+        "worktree",
+        "add",
+        "-b",
+        input.branch,
+        input.dest,
+        ...(input.baseRef ? [input.baseRef] : []),
+      ];
+  await runGit(args, { cwd: input.repo });
+  const created = await findWorktree(await listWorktrees(input.repo), input.dest);
+  if (!created) {
+    throw new Error(`git did not register a worktree at ${input.dest}`);
+  }
+  return created;
+}
+
+/** `force` also drops uncommitted changes in the worktree. */
+export async function removeWorktree(input: {
+  repo: string;
+  path: string;
+  force: boolean;
+}): Promise<GitWorktree[]> {
+  const target = await findWorktree(await listWorktrees(input.repo), input.path);
+  if (!target) {
+    throw new Error(`Not a worktree of ${input.repo}: ${input.path}`);
+  }
+  if (target.main) {
+    throw new Error("The repository's main worktree cannot be removed.");
+  }
+  await runGit(["worktree", "remove", ...(input.force ? ["--force"] : []), input.path], {
+    cwd: input.repo,
+  });
+  return await listWorktrees(input.repo);
+}
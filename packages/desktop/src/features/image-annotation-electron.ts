@@ -0,0 +1,104 @@
+import { randomUUID } from "node:crypto";
+import { BrowserWindow, nativeImage, screen, session } from "electron";
+import { toFileSystemPath } from "../system/windows-paths.js";
+import { writeAttachmentBytes } from "./attachments.js";
+import { getFileReadMaxBytes } from "./file-read-guard-electron.js";
+import { readGuardedFile } from "./file-read-guard.js";
+import {
+  buildAnnotationPage,
+  coerceAnnotateImageRequest,
+  fitAnnotationWindow,
+  parseAnnotationResult,
+} from "./image-annotation.js";
+
+// Reserved TLD, so the in-memory handler can never shadow a real site.
+const ANNOTATION_HOST = "annotate.paseo.invalid";
+const ANNOTATION_CSP =
+  "default-src 'none'; img-src 'self'; style-src 'unsafe-inline'; script-src 'unsafe-inline'";
+
+export interface AnnotatedImage {
+  path: string;
+  byteSize: number;
+  sourcePath: string;
+}
+
+/**
+ * Opens the annotation editor over `path` and saves the result as a new PNG
+ * attachment, leaving the original untouched. `null` when the user cancels.
+ */
+export async function annotateImage(input: {
+  args: Record<string, unknown> | undefined;
+  parent: BrowserWindow | null;
+}): Promise<AnnotatedImage | null> {
+  const request = coerceAnnotateImageRequest(input.args);
+  const imageBytes = await readGuardedFile(toFileSystemPath(request.path), {
+    maxBytes: await getFileReadMaxBytes(),
+    allowBinary: true,
+  });
+  const imageSize = nativeImage.createFromBuffer(imageBytes).getSize();
+  if (imageSize.width === 0 || imageSize.height === 0) {
+    throw new Error(`Could not decode image: ${request.path}`);
+  }
+
+  // No `persist:` prefix: the session and its handler vanish with the window.
+  const editorSession = session.fromPartition(`annotate-${randomUUID()}`);
+  editorSession.protocol.handle("https", (editorRequest) => {
+    const url = new URL(editorRequest.url);
+    if (url.hostname !== ANNOTATION_HOST) {
+      return new Response("Not found", { status: 404 });
+    }
+    if (url.pathname === "/image") {
+      return new Response(imageBytes, { headers: { "content-type": request.mimeType } });
+    }
+    return new Response(buildAnnotationPage(), {
+      headers: {
+        "content-type": "text/html; charset=utf-8",
+        "content-security-policy": ANNOTATION_CSP,
+      },
+    });
+  });
+
+  const display = input.parent
+    ? screen.getDisplayMatching(input.parent.getBounds())
+    : screen.getPrimaryDisplay();
+  const editor = new BrowserWindow({
+    ...fitAnnotationWindow({ image: imageSize, workArea: display.workAreaSize }),
+    title: "Annotate image",
+    parent: input.parent ?? undefined,
+    modal: input.parent !== null,
+    minimizable: false,
+    fullscreenable: false,
+    backgroundColor: "#18181b",
+    show: false,
+    webPreferences: {
+      session: editorSession,
+      contextIsolation: true,
+      nodeIntegration: false,
+      sandbox: true,
+    },
+  });
+  try {
+    await editor.loadURL(`https://${ANNOTATION_HOST}/`);
+    editor.show();
+    editor.focus();
+    const result: unknown = await Promise.race([
+      editor.webContents.executeJavaScript("window.paseoAnnotation", true),
+      new Promise((resolve) => editor.once("closed", () => resolve(null))),
+    ]);
+    const annotated = parseAnnotationResult(result);
+    if (!annotated) {
+      return null;
+    }
+    const attachment = await writeAttachmentBytes({
+      attachmentId: randomUUID(),
+      bytes: annotated,
+      extension: ".png",
+    });
+    return { ...attachment, sourcePath: request.path };
+  } finally {
+    if (!editor.isDestroyed()) {
+      editor.destroy();
+    }
+    editorSession.protocol.unhandle("https");
+  }
+}
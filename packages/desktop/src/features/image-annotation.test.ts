@@ -0,0 +1,55 @@
+import path from "node:path";
+import { describe, expect, it } from "vitest";
+import {
+  buildAnnotationPage,
+  coerceAnnotateImageRequest,
+  fitAnnotationWindow,
+  parseAnnotationResult,
+} from "./image-annotation";
+
+describe("coerceAnnotateImageRequest", () => {
+  it("accepts absolute image paths and resolves their mime type", () => {
+    const imagePath = path.resolve("/tmp/Screenshot.PNG");
+    expect(coerceAnnotateImageRequest({ path: imagePath })).toEqual({
+      path: imagePath,
+      mimeType: "image/png",
+    });
+    expect(() => coerceAnnotateImageRequest({ path: "shot.png" })).toThrow(/absolute/);
+    expect(() => coerceAnnotateImageRequest({ path: path.resolve("/tmp/a.svg") })).toThrow(
+      /PNG, JPEG/,
+    );
+  });
+});
+
+describe("fitAnnotationWindow", () => {
+  it("keeps small images at full size and scales large ones into the work area", () => {
+    const workArea = { width: 1440, height: 900 };
+    expect(fitAnnotationWindow({ image: { width: 800, height: 500 }, workArea })).toEqual({
+      width: 800,
+      height: 548,
+    });
+    expect(fitAnnotationWindow({ image: { width: 2880, height: 1800 }, workArea })).toEqual({
+      width: 1219,
+      height: 810,
+    });
+    expect(fitAnnotationWindow({ image: { width: 40, height: 40 }, workArea })).toEqual({
+      width: 480,
+      height: 320,
+    });
+  });
+});
+
+describe("parseAnnotationResult", () => {
+  it("only accepts PNG data URLs", () => {
+    const png = Buffer.from([0x89, 0x50, 0x4e, 0x47]);
+    expect(parseAnnotationResult(`data:image/png;base64,${png.toString("base64")}`)).toEqual(png);
+    expect(parseAnnotationResult(null)).toBeNull();
+    expect(parseAnnotationResult("data:text/html;base64,PGI+")).toBeNull();
+  });
+
+  it("serves an editor that loads the image from its own origin", () => {
+    const page = buildAnnotationPage();
+    expect(page).toContain('image.src = "/image"');
+    expect(page).toContain("window.paseoAnnotation");
+  });
+});
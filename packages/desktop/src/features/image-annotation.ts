@@ -0,0 +1,289 @@
+import path from "node:path";
+
+const IMAGE_MIME_TYPES: Record<string, string> = {
+  ".png": "image/png",
+  ".jpg": "image/jpeg",
+  ".jpeg": "image/jpeg",
+  ".webp": "image/webp",
+  ".gif": "image/gif",
+};
+const PNG_DATA_URL_PREFIX = "data:image/png;base64,";
+// Room for the toolbar above the image.
+const TOOLBAR_HEIGHT = 48;
+const MIN_WINDOW_SIZE = { width: 480, height: 320 };
+
+export interface AnnotateImageRequest {
+  path: string;
+  mimeType: string;
+}
+
+export function coerceAnnotateImageRequest(
+  args: Record<string, unknown> | undefined,
+): AnnotateImageRequest {
+  const imagePath = typeof args?.path === "string" ? args.path.trim() : "";
+  if (!path.isAbsolute(imagePath)) {
+    throw new Error("Image path must be absolute.");
+  }
+  const mimeType = IMAGE_MIME_TYPES[path.extname(imagePath).toLowerCase()];
+  if (!mimeType) {
+    throw new Error("Only PNG, JPEG, WebP and GIF images can be annotated.");
+  }
+  return { path: imagePath, mimeType };
+}
+
+/** Fits the image at 1:1 when possible, otherwise scales it down into the work area. */
+export function fitAnnotationWindow(input: {
+  image: { width: number; height: number };
+  workArea: { width: number; height: number };
+}): { width: number; height: number } {
+  const maxWidth = Math.floor(input.workArea.width * 0.9);
+  const maxHeight = Math.floor(input.workArea.height * 0.9) - TOOLBAR_HEIGHT;
+  const scale = Math.min(1, maxWidth / input.image.width, maxHeight / input.image.height);
+  return {
+    width: Math.max(MIN_WINDOW_SIZE.width, Math.round(input.image.width * scale)),
+    height: Math.max(
+      MIN_WINDOW_SIZE.height,
+      Math.round(input.image.height * scale) + TOOLBAR_HEIGHT,
+    ),
+  };
+}
+
+/** PNG bytes from the editor's result, or `null` when the user cancelled. */
+export function parseAnnotationResult(value: unknown): Buffer | null {
+  if (typeof value !== "string" || !value.startsWith(PNG_DATA_URL_PREFIX)) {
+    return null;
+  }
+  const bytes = Buffer.from(value.slice(PNG_DATA_URL_PREFIX.length), "base64");
+  return bytes.length > 0 ? bytes : null;
+}
+
+/**
+ * Self-contained editor: arrows, boxes, text and a crop rectangle drawn in
+ * image pixels over `/image`. `window.paseoAnnotation` resolves with a PNG
+ * data URL on save and `null` on cancel.
+ */
+export function buildAnnotationPage(): string {
+  return `<!doctype html>
+<html><head><meta charset="utf-8"><title>Annotate image</title>
+<style>
+*{box-sizing:border-box}
+body{margin:0;height:100vh;display:flex;flex-direction:column;background:#18181b;color:#e4e4e7;
+font:13px system-ui,sans-serif;user-select:none}
+#bar{height:${TOOLBAR_HEIGHT}px;display:flex;gap:6px;align-items:center;padding:0 10px;
+border-bottom:1px solid #27272a}
+#bar button{background:#27272a;color:inherit;border:1px solid #3f3f46;border-radius:6px;
+padding:5px 10px;font:inherit}
+#bar button[aria-pressed=true]{background:#3f3f46;border-color:#71717a}
+#bar .spacer{flex:1}
+#bar #save{background:#2563eb;border-color:#2563eb;color:#fff}
+#stage{flex:1;display:flex;align-items:center;justify-content:center;overflow:hidden;
+position:relative}
+canvas{max-width:100%;max-height:100%;cursor:crosshair}
+#text{position:absolute;display:none;font:16px system-ui;color:#ff3b30;background:#fff;
+border:1px solid #ff3b30;padding:2px 4px}
+</style></head><body>
+<div id="bar">
+<button data-tool="arrow" aria-pressed="true">Arrow</button>
+<button data-tool="box">Box</button>
+<button data-tool="text">Text</button>
+<button data-tool="crop">Crop</button>
+<button id="undo">Undo</button>
+<span class="spacer"></span>
+<button id="cancel">Cancel</button>
+<button id="save">Save</button>
+</div>
+<div id="stage"><canvas id="canvas"></canvas><input id="text" type="text"></div>
+<script>
+(() => {
+  const COLOR = "#ff3b30";
+  const canvas = document.getElementById("canvas");
+  const context = canvas.getContext("2d");
+  const textInput = document.getElementById("text");
+  const image = new Image();
+  const shapes = [];
+  let tool = "arrow";
+  let crop = null;
+  let draft = null;
+  let settle;
+  window.paseoAnnotation = new Promise((resolve) => { settle = resolve; });
+
+  const lineWidth = () => Math.max(3, Math.round(Math.max(image.width, image.height) / 300));
+  const toImage = (event) => {
+    const rect = canvas.getBoundingClientRect();
+    return {
+      x: ((event.clientX - rect.left) / rect.width) * canvas.width,
+      y: ((event.clientY - rect.top) / rect.height) * canvas.height,
+    };
+  };
+
+  function drawShape(target, shape) {
+    target.strokeStyle = COLOR;
+    target.fillStyle = COLOR;
+    target.lineWidth = lineWidth();
+    target.lineCap = "round";
+    target.lineJoin = "round";
+    if (shape.type === "box") {
+      target.strokeRect(shape.x1, shape.y1, shape.x2 - shape.x1, shape.y2 - shape.y1);
+    } else if (shape.type === "arrow") {
+      const angle = Math.atan2(shape.y2 - shape.y1, shape.x2 - shape.x1);
+      const head = lineWidth() * 4;
+      target.beginPath();
+      target.moveTo(shape.x1, shape.y1);
+      target.lineTo(shape.x2, shape.y2);
+      target.stroke();
+      target.beginPath();
+      target.moveTo(shape.x2, shape.y2);
+      for (const side of [-0.45, 0.45]) {
+        target.lineTo(
+          shape.x2 - head * Math.cos(angle + side),
+          shape.y2 - head * Math.sin(angle + side),
+        );
+      }
+      target.closePath();
+      target.fill();
+    } else if (shape.type === "text") {
+      target.font = "600 " + lineWidth() * 7 + "px system-ui, sans-serif";
+      target.textBaseline = "top";
+      target.lineWidth = lineWidth();
+      target.strokeStyle = "#fff";
+      target.strokeText(shape.text, shape.x1, shape.y1);
+      target.fillText(shape.text, shape.x1, shape.y1);
+    }
+  }
+
+  function normalizedCrop(rect) {
+    if (!rect) return null;
+    const x = Math.max(0, Math.min(rect.x1, rect.x2));
+    const y = Math.max(0, Math.min(rect.y1, rect.y2));
+    const width = Math.min(canvas.width, Math.max(rect.x1, rect.x2)) - x;
+    const height = Math.min(canvas.height, Math.max(rect.y1, rect.y2)) - y;
+    return width >= 4 && height >= 4 ? { x, y, width, height } : null;
+  }
+
+  function render() {
+    context.clearRect(0, 0, canvas.width, canvas.height);
+    context.drawImage(image, 0, 0);
+    for (const shape of shapes) drawShape(context, shape);
+    if (draft && draft.type !== "crop") drawShape(context, draft);
+    const area = normalizedCrop(draft && draft.type === "crop" ? draft : crop);
+    if (area) {
+      context.save();
+      context.fillStyle = "rgba(0,0,0,0.5)";
+      context.beginPath();
+      context.rect(0, 0, canvas.width, canvas.height);
+      context.rect(area.x, area.y, area.width, area.height);
+      context.fill("evenodd");
+      context.setLineDash([lineWidth() * 2, lineWidth() * 2]);
+      context.strokeStyle = "#fff";
+      context.lineWidth = Math.max(1, lineWidth() / 2);
+      context.strokeRect(area.x, area.y, area.width, area.height);
+      context.restore();
+    }
+  }
+
+  function commitText() {
+    const text = textInput.value.trim();
+    if (text && textInput.dataset.x) {
+      const x1 = Number(textInput.dataset.x);
+      const y1 = Number(textInput.dataset.y);
+      shapes.push({ type: "text", text, x1, y1 });
+    }
+    textInput.style.display = "none";
+    textInput.value = "";
+    delete textInput.dataset.x;
+    render();
+  }
+
+  function save() {
+    commitText();
+    const output = document.createElement("canvas");
+    const area = normalizedCrop(crop) || { x: 0, y: 0, width: canvas.width, height: canvas.height };
+    output.width = Math.round(area.width);
+    output.height = Math.round(area.height);
+    const target = output.getContext("2d");
+    target.drawImage(image, -area.x, -area.y);
+    for (const shape of shapes) {
+      target.save();
+      target.translate(-area.x, -area.y);
+      drawShape(target, shape);
+      target.restore();
+    }
+    settle(output.toDataURL("image/png"));
+  }
+
+  for (const button of document.querySelectorAll("[data-tool]")) {
+    button.addEventListener("click", () => {
+      commitText();
+      tool = button.dataset.tool;
+      for (const other of document.querySelectorAll("[data-tool]")) {
+        other.setAttribute("aria-pressed", String(other === button));
+      }
+    });
+  }
+  document.getElementById("undo").addEventListener("click", () => {
+    if (crop) crop = null; else shapes.pop();
+    render();
+  });
+  document.getElementById("cancel").addEventListener("click", () => settle(null));
+  document.getElementById("save").addEventListener("click", save);
+
+  canvas.addEventListener("pointerdown", (event) => {
+    const point = toImage(event);
+    if (tool === "text") {
+      commitText();
+      const stage = document.getElementById("stage").getBoundingClientRect();
+      textInput.style.left = event.clientX - stage.left + "px";
+      textInput.style.top = event.clientY - stage.top + "px";
+      textInput.style.display = "block";
+      textInput.dataset.x = String(point.x);
+      textInput.dataset.y = String(point.y);
+      setTimeout(() => textInput.focus(), 0);
+      return;
+    }
+    canvas.setPointerCapture(event.pointerId);
+    draft = { type: tool, x1: point.x, y1: point.y, x2: point.x, y2: point.y };
+  });
+  canvas.addEventListener("pointermove", (event) => {
+    if (!draft) return;
+    const point = toImage(event);
+    draft.x2 = point.x;
+    draft.y2 = point.y;
+    render();
+  });
+  canvas.addEventListener("pointerup", () => {
+    if (!draft) return;
+    if (draft.type === "crop") {
+      crop = normalizedCrop(draft) ? draft : null;
+    } else if (Math.hypot(draft.x2 - draft.x1, draft.y2 - draft.y1) > lineWidth()) {
+      shapes.push(draft);
+    }
+    draft = null;
+    render();
+  });
+  textInput.addEventListener("keydown", (event) => {
+    if (event.key === "Enter") { event.preventDefault(); commitText(); }
+    if (event.key === "Escape") { event.stopPropagation(); textInput.value = ""; commitText(); }
+  });
+  textInput.addEventListener("blur", commitText);
+  window.addEventListener("keydown", (event) => {
+    if (event.target === textInput) return;
+    const mod = event.metaKey || event.ctrlKey;
+    if (event.key === "Escape") settle(null);
+    else if (mod && event.key.toLowerCase() === "s") { event.preventDefault(); save(); }
+    else if (mod && event.key.toLowerCase() === "z") {
+      event.preventDefault();
+      document.getElementById("undo").click();
+    }
+    else if (event.key === "Enter") save();
+  });
+
+  image.onload = () => {
+    canvas.width = image.naturalWidth;
+    canvas.height = image.naturalHeight;
+    render();
+  };
+  image.onerror = () => settle(null);
+  image.src = "/image";
+})();
+</script></body></html>`;
+}
@@ -0,0 +1,37 @@
+import { app } from "electron";
+import log from "electron-log/main";
+import { emitDesktopEvent } from "../window/desktop-events.js";
+import {
+  createLocalTaskScheduler,
+  type LocalTaskAction,
+  type LocalTaskScheduler,
+} from "./local-tasks.js";
+
+let runLocalTaskAction: ((action: LocalTaskAction) => Promise<unknown>) | null = null;
+let localTaskScheduler: LocalTaskScheduler | null = null;
+
+export function getLocalTaskScheduler(): LocalTaskScheduler {
+  localTaskScheduler ??= createLocalTaskScheduler({
+    userDataPath: app.getPath("userData"),
+    runAction: async (action) => {
+      if (!runLocalTaskAction) {
+        throw new Error("Local tasks are not configured yet.");
+      }
+      return await runLocalTaskAction(action);
+    },
+    onRun: (run) => {
+      if (run.error) {
+        log.warn(`[local-tasks] ${run.task.action} failed`, run.error);
+      }
+      emitDesktopEvent("local-task-completed", run);
+    },
+  });
+  return localTaskScheduler;
+}
+
+export async function startLocalTaskScheduler(input: {
+  runAction: (action: LocalTaskAction) => Promise<unknown>;
+}): Promise<void> {
+  runLocalTaskAction = input.runAction;
+  await getLocalTaskScheduler().start();
+}
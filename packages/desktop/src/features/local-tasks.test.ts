@@ -0,0 +1,100 @@
+import { mkdtemp, readFile, rm, writeFile } from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import { createLocalTaskScheduler, type LocalTaskRun } from "./local-tasks";
+
+describe("createLocalTaskScheduler", () => {
+  let userDataPath: string;
+
+  beforeEach(async () => {
+    userDataPath = await mkdtemp(path.join(os.tmpdir(), "paseo-local-tasks-"));
+    vi.useFakeTimers();
+    vi.setSystemTime(new Date(2026, 9, 16, 2, 0));
+  });
+
+  afterEach(async () => {
+    vi.useRealTimers();
+    await rm(userDataPath, { recursive: true, force: true });
+  });
+
+  it("persists one schedule per action", async () => {
+    const scheduler = createLocalTaskScheduler({ userDataPath, runAction: async () => null });
+
+    const first = await scheduler.schedule({ cron: "0 3 * * *", action: "attachment_gc" });
+    const second = await scheduler.schedule({ cron: "0 4 * * *", action: "attachment_gc" });
+
+    expect(second.id).toBe(first.id);
+    expect(second.nextRunAt).toBe(new Date(2026, 9, 16, 4, 0).toISOString());
+    const reloaded = createLocalTaskScheduler({ userDataPath, runAction: async () => null });
+    expect(await reloaded.list()).toEqual([second]);
+  });
+
+  it("rejects unknown actions and bad cron expressions", async () => {
+    const scheduler = createLocalTaskScheduler({ userDataPath, runAction: async () => null });
+
+    await expect(scheduler.schedule({ cron: "0 3 * * *", action: "rm_rf" })).rejects.toThrow(
+      "Unknown local task action",
+    );
+    await expect(
+      scheduler.schedule({ cron: "0 0 31 2 *", action: "update_check" }),
+    ).rejects.toThrow("never runs");
+  });
+
+  it("runs tasks when due and records the outcome", async () => {
+    const runs: LocalTaskRun[] = [];
+    const runAction = vi.fn(async () => {
+      throw new Error("offline");
+    });
+    const scheduler = createLocalTaskScheduler({
+      userDataPath,
+      runAction,
+      onRun: (run) => runs.push(run),
+    });
+    await scheduler.start();
+    await scheduler.schedule({ cron: "0 3 * * *", action: "update_check" });
+
+    await vi.advanceTimersByTimeAsync(60 * 60 * 1000);
+
+    expect(runAction).toHaveBeenCalledWith("update_check");
+    expect(runs).toHaveLength(1);
+    expect(runs[0]!.task).toMatchObject({
+      lastStatus: "error",
+      lastError: "offline",
+      nextRunAt: new Date(2026, 9, 17, 3, 0).toISOString(),
+    });
+    scheduler.stop();
+  });
+
+  it("catches up on runs missed while the app was closed", async () => {
+    await writeFile(
+      path.join(userDataPath, "local-tasks.json"),
+      JSON.stringify({
+        version: 1,
+        tasks: [
+          {
+            id: "task-1",
+            cron: "0 1 * * *",
+            action: "diagnostics_rotation",
+            createdAt: "",
+            nextRunAt: new Date(2026, 9, 16, 1, 0).toISOString(),
+          },
+        ],
+      }),
+    );
+    const runAction = vi.fn(async () => ({ rotated: false }));
+    const scheduler = createLocalTaskScheduler({ userDataPath, runAction });
+
+    await scheduler.start();
+
+    expect(runAction).toHaveBeenCalledTimes(1);
+    const persisted = JSON.parse(
+      await readFile(path.join(userDataPath, "local-tasks.json"), "utf8"),
+    );
+    expect(persisted.tasks[0]).toMatchObject({
+      lastStatus: "ok",
+      nextRunAt: new Date(2026, 9, 17, 1, 0).toISOString(),
+    });
+    scheduler.stop();
+  });
+});
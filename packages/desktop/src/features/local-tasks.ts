@@ -0,0 +1,258 @@
+import { randomUUID } from "node:crypto";
+import { readFile } from "node:fs/promises";
+import path from "node:path";
+import { updateJsonConfigFile } from "../settings/config-file.js";
+import { nextCronRun, parseCronExpression } from "./cron-schedule.js";
+import { isRecord } from "../utils/is-record.js";
+
+const LOCAL_TASKS_FILENAME = "local-tasks.json";
+// Re-arm at least this often so sleep and clock changes cannot strand a long timer.
+const MAX_TIMER_DELAY_MS = 60 * 60 * 1000;
+
+export const LOCAL_TASK_ACTIONS = [
+  "attachment_gc",
+  "diagnostics_rotation",
+  "update_check",
+  "daemon_health_probe",
+  "daily_digest",
+] as const;
+
+export type LocalTaskAction = (typeof LOCAL_TASK_ACTIONS)[number];
+
+export interface LocalTask {
+  id: string;
+  cron: string;
+  action: LocalTaskAction;
+  createdAt: string;
+  nextRunAt: string | null;
+  lastRunAt: string | null;
+  lastStatus: "ok" | "error" | null;
+  lastError: string | null;
+}
+
+export interface LocalTaskRun {
+  task: LocalTask;
+  result: unknown;
+  error: string | null;
+}
+
+interface PersistedLocalTasksDocument {
+  version: 1;
+  tasks: LocalTask[];
+}
+
+export interface LocalTaskScheduler {
+  /** Schedules `action`; an action has one schedule, so this replaces any existing one. */
+  schedule(input: { cron: unknown; action: unknown }): Promise<LocalTask>;
+  unschedule(taskId: string): Promise<boolean>;
+  list(): Promise<LocalTask[]>;
+  /** Runs anything missed while the app was closed, then keeps the timer armed. */
+  start(): Promise<void>;
+  stop(): void;
+}
+
+export function isLocalTaskAction(value: unknown): value is LocalTaskAction {
+  return (LOCAL_TASK_ACTIONS as readonly unknown[]).includes(value);
+}
+
+function optionalString(value: unknown): string | null {
+  return typeof value === "string" ? value : null;
+}
+
+function coerceTasks(value: unknown): LocalTask[] {
+  if (!isRecord(value) || !Array.isArray(value.tasks)) {
+    return [];
+  }
+  return value.tasks.flatMap((task: unknown): LocalTask[] => {
+    if (
+      !isRecord(task) ||
+      typeof task.id !== "string" ||
+      typeof task.cron !== "string" ||
+      !isLocalTaskAction(task.action)
+    ) {
+      return [];
+    }
+    try {
+      parseCronExpression(task.cron);
+    } catch {
+      return [];
+    }
+    return [
+      {
+        id: task.id,
+        cron: task.cron,
+        action: task.action,
+        createdAt: optionalString(task.createdAt) ?? "",
+        nextRunAt: optionalString(task.nextRunAt),
+        lastRunAt: optionalString(task.lastRunAt),
+        lastStatus:
+          task.lastStatus === "ok" || task.lastStatus === "error" ? task.lastStatus : null,
+        lastError: optionalString(task.lastError),
+      },
+    ];
+  });
+}
+
+function computeNextRunAt(cron: string, after: number): string | null {
+  return nextCronRun(parseCronExpression(cron), new Date(after))?.toISOString() ?? null;
+}
+
+export function createLocalTaskScheduler(input: {
+  userDataPath: string;
+  runAction: (action: LocalTaskAction) => Promise<unknown>;
+  onRun?: (run: LocalTaskRun) => void;
+  now?: () => number;
+}): LocalTaskScheduler {
+  const filePath = path.join(input.userDataPath, LOCAL_TASKS_FILENAME);
+  const now = input.now ?? (() => Date.now());
+  let tasks: LocalTask[] = [];
+  let loaded: Promise<void> | null = null;
+  let timer: NodeJS.Timeout | null = null;
+  let started = false;
+  const running = new Set<string>();
+
+  function ensureLoaded(): Promise<void> {
+    loaded ??= (async () => {
+      try {
+        tasks = coerceTasks(JSON.parse(await readFile(filePath, "utf8")));
+      } catch {
+        tasks = [];
+      }
+    })();
+    return loaded;
+  }
+
+  async function update(mutate: (current: LocalTask[]) => LocalTask[]): Promise<LocalTask[]> {
+    const document = await updateJsonConfigFile(
+      filePath,
+      (raw): PersistedLocalTasksDocument => ({ version: 1, tasks: mutate(coerceTasks(raw)) }),
+    );
+    tasks = document.tasks;
+    loaded = Promise.resolve();
+    return tasks;
+  }
+
+  async function runTask(task: LocalTask): Promise<void> {
+    running.add(task.id);
+    let result: unknown = null;
+    let error: string | null = null;
+    try {
+      result = await input.runAction(task.action);
+    } catch (runError) {
+      error = runError instanceof Error ? runError.message : String(runError);
+    } finally {
+      running.delete(task.id);
+    }
+
+    const finishedAt = now();
+    const finish = (current: LocalTask): LocalTask => ({
+      ...current,
+      nextRunAt: computeNextRunAt(current.cron, finishedAt),
+      lastRunAt: new Date(finishedAt).toISOString(),
+      lastStatus: error ? "error" : "ok",
+      lastError: error,
+    });
+    const updated = await update((current) =>
+      current.map((candidate) => (candidate.id === task.id ? finish(candidate) : candidate)),
+    );
+    const next = updated.find((candidate) => candidate.id === task.id);
+    if (!next) {
+      // Unscheduled while it was running.
+      return;
+    }
+    input.onRun?.({ task: next, result, error });
+  }
+
+  async function runDueTasks(): Promise<void> {
+    const due = tasks.filter(
+      (task) =>
+        task.nextRunAt !== null && Date.parse(task.nextRunAt) <= now() && !running.has(task.id),
+    );
+    await Promise.all(due.map((task) => runTask(task)));
+  }
+
+  function arm(): void {
+    if (timer) {
+      clearTimeout(timer);
+      timer = null;
+    }
+    if (!started) {
+      return;
+    }
+    const upcoming = tasks
+      .filter((task) => task.nextRunAt !== null && !running.has(task.id))
+      .map((task) => Date.parse(task.nextRunAt!));
+    if (upcoming.length === 0) {
+      return;
+    }
+    const delay = Math.max(0, Math.min(Math.min(...upcoming) - now(), MAX_TIMER_DELAY_MS));
+    timer = setTimeout(() => {
+      timer = null;
+      void runDueTasks().finally(arm);
+    }, delay);
+    timer.unref?.();
+  }
+
+  return {
+    async schedule({ cron, action }) {
+      if (typeof cron !== "string" || cron.trim().length === 0) {
+        throw new Error("A cron expression is required.");
+      }
+      if (!isLocalTaskAction(action)) {
+        throw new Error(`Unknown local task action: ${String(action)}`);
+      }
+      const normalizedCron = cron.trim();
+      const nextRunAt = computeNextRunAt(normalizedCron, now());
+      if (nextRunAt === null) {
+        throw new Error(`Cron expression never runs: ${normalizedCron}`);
+      }
+
+      const updated = await update((current) => {
+        const existing = current.find((task) => task.action === action);
+        const task: LocalTask = {
+          id: existing?.id ?? randomUUID(),
+          cron: normalizedCron,
+          action,
+          createdAt: existing?.createdAt ?? new Date(now()).toISOString(),
+          nextRunAt,
+          lastRunAt: existing?.lastRunAt ?? null,
+          lastStatus: existing?.lastStatus ?? null,
+          lastError: existing?.lastError ?? null,
+        };
+        return existing
+          ? current.map((entry) => (entry === existing ? task : entry))
+          : [...current, task];
+      });
+      arm();
+      return { ...updated.find((task) => task.action === action)! };
+    },
+
+    async unschedule(taskId) {
+      let removed = false;
+      await update((current) => {
+        const remaining = current.filter((task) => task.id !== taskId);
+        removed = remaining.length < current.length;
+        return remaining;
+      });
+      arm();
+      return removed;
+    },
+
+    async list() {
+      await ensureLoaded();
+      return tasks.map((task) => ({ ...task }));
+    },
+
+    async start() {
+      await ensureLoaded();
+      started = true;
+      await runDueTasks();
+      arm();
+    },
+
+    stop() {
+      started = false;
+      arm();
+    },
+  };
+}
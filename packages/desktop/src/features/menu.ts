@@ -3,6 +3,21 @@ import {
   getActivePaseoBrowserWebContents,
   getPaseoBrowserIdForWebContents,
 } from "./browser-webviews.js";
+import {
+  setWindowVisibleOnAllWorkspaces,
+  syncVisibleOnAllWorkspacesMenuItem,
+  VISIBLE_ON_ALL_WORKSPACES_MENU_ITEM_ID,
+} from "../window/window-manager.js";
+import { listLabeledWindows } from "../window/window-labels.js";
+import { reloadWindowPreservingState } from "../window/window-reload.js";
+import { adjustWindowZoom, resetWindowZoom } from "../window/window-zoom.js";
+import { STATUS_POPOVER_WINDOW_LABEL } from "./status-popover.js";
+import { getAgentIcon } from "./agent-icons.js";
+import {
+  getWindowAgentId,
+  getWindowUnreadCount,
+  onUnreadIndicatorsChanged,
+} from "./unread-indicators.js";
 
 interface ShowContextMenuInput {
   kind?: "terminal";
@@ -10,11 +25,11 @@ interface ShowContextMenuInput {
 }
 
 function withBrowserWindow(
-  callback: (win: BrowserWindow) => void,
-): (_item: Electron.MenuItem, baseWin: Electron.BaseWindow | undefined) => void {
-  return (_item, baseWin) => {
+  callback: (win: BrowserWindow, item: Electron.MenuItem) => void,
+): (item: Electron.MenuItem, baseWin: Electron.BaseWindow | undefined) => void {
+  return (item, baseWin) => {
     const win = baseWin instanceof BrowserWindow ? baseWin : BrowserWindow.getFocusedWindow();
-    if (win) callback(win);
+    if (win) callback(win, item);
   };
 }
 
@@ -42,17 +57,41 @@ function reloadFocusedContentsOrWindow(win: BrowserWindow, options?: { ignoreCac
     return;
   }
 
-  if (options?.ignoreCache) {
-    win.webContents.reloadIgnoringCache();
+  void reloadWindowPreservingState({ win, ignoreCache: options?.ignoreCache });
+}
+
+function focusWindow(win: BrowserWindow): void {
+  if (win.isDestroyed()) {
     return;
   }
-  win.webContents.reload();
+  if (win.isMinimized()) {
+    win.restore();
+  }
+  win.show();
+  win.focus();
 }
 
-export function setupApplicationMenu(): void {
+/** One entry per open window, decorated with the icon and unread count of the agent it shows. */
+function buildWindowListItems(): Electron.MenuItemConstructorOptions[] {
+  const items = listLabeledWindows()
+    .filter(({ label }) => label !== STATUS_POPOVER_WINDOW_LABEL)
+    .map(({ win }): Electron.MenuItemConstructorOptions => {
+      const title = win.getTitle() || app.name;
+      const unread = getWindowUnreadCount(win);
+      const agentId = getWindowAgentId(win);
+      return {
+        label: unread > 0 ? `${title} (${unread})` : title,
+        ...(agentId ? { icon: getAgentIcon(agentId) } : {}),
+        click: () => focusWindow(win),
+      };
+    });
+  return items.length > 0 ? [{ type: "separator" }, ...items] : [];
+}
+
+function buildApplicationMenuTemplate(): Electron.MenuItemConstructorOptions[] {
   const isMac = process.platform === "darwin";
 
-  const template: Electron.MenuItemConstructorOptions[] = [
+  return [
     ...(isMac
       ? [
           {
@@ -90,21 +129,21 @@ export function setupApplicationMenu(): void {
           label: "Zoom In",
           accelerator: "CmdOrCtrl+=",
           click: withBrowserWindow((win) => {
-            win.webContents.setZoomLevel(win.webContents.getZoomLevel() + 0.5);
+            adjustWindowZoom({ win, levels: 0.5 });
           }),
         },
         {
           label: "Zoom Out",
           accelerator: "CmdOrCtrl+-",
           click: withBrowserWindow((win) => {
-            win.webContents.setZoomLevel(win.webContents.getZoomLevel() - 0.5);
+            adjustWindowZoom({ win, levels: -0.5 });
           }),
         },
         {
           label: "Actual Size",
           accelerator: "CmdOrCtrl+0",
           click: withBrowserWindow((win) => {
-            win.webContents.setZoomLevel(0);
+            resetWindowZoom(win);
           }),
         },
         { type: "separator" },
@@ -125,6 +164,18 @@ export function setupApplicationMenu(): void {
         { role: "toggleDevTools" },
         { type: "separator" },
         { role: "togglefullscreen" },
+        ...(process.platform === "win32"
+          ? []
+          : [
+              {
+                id: VISIBLE_ON_ALL_WORKSPACES_MENU_ITEM_ID,
+                label: isMac ? "Show on All Spaces" : "Show on All Workspaces",
+                type: "checkbox" as const,
+                click: withBrowserWindow((win, item) => {
+                  setWindowVisibleOnAllWorkspaces({ win, enabled: item.checked });
+                }),
+              },
+            ]),
       ],
     },
     {
@@ -135,12 +186,26 @@ export function setupApplicationMenu(): void {
         ...(isMac
           ? [{ type: "separator" as const }, { role: "front" as const }]
           : [{ role: "close" as const }]),
+        ...buildWindowListItems(),
       ],
     },
   ];
+}
 
-  const menu = Menu.buildFromTemplate(template);
-  Menu.setApplicationMenu(menu);
+function applyApplicationMenu(): void {
+  Menu.setApplicationMenu(Menu.buildFromTemplate(buildApplicationMenuTemplate()));
+  const focused = BrowserWindow.getFocusedWindow();
+  if (focused) {
+    syncVisibleOnAllWorkspacesMenuItem(focused);
+  }
+}
+
+export function setupApplicationMenu(): void {
+  applyApplicationMenu();
+  onUnreadIndicatorsChanged(applyApplicationMenu);
+  app.on("browser-window-focus", (_event, win) => {
+    syncVisibleOnAllWorkspacesMenuItem(win);
+  });
 
   ipcMain.handle("paseo:menu:showContextMenu", (event, input?: ShowContextMenuInput) => {
     const win = BrowserWindow.fromWebContents(event.sender);
@@ -0,0 +1,125 @@
+import { BrowserWindow, dialog, systemPreferences } from "electron";
+import log from "electron-log/main";
+import type { ApprovalRiskCategory } from "@getpaseo/server";
+import type { NativeApprovalDetails } from "../daemon/approval-gate.js";
+import type { DesktopSettings } from "../settings/desktop-settings.js";
+import { getDesktopSettingsStore } from "../settings/desktop-settings-electron.js";
+
+export interface NativeApprovalResult {
+  approved: boolean;
+  method: "dialog" | "touch_id";
+}
+
+const RISK_LABELS: Record<ApprovalRiskCategory, string> = {
+  destructive: "Destructive — may delete data or rewrite history",
+  privilege_escalation: "Privilege escalation — runs with elevated permissions",
+  remote_code: "Remote code — downloads and executes a script",
+};
+
+const pendingPrompts = new Map<string, Promise<NativeApprovalResult>>();
+
+async function promptTouchId(reason: string): Promise<boolean> {
+  if (process.platform !== "darwin" || !systemPreferences.canPromptTouchID()) {
+    return true;
+  }
+  try {
+    await systemPreferences.promptTouchID(reason);
+    return true;
+  } catch (error) {
+    log.info("[approval] Touch ID declined", error);
+    return false;
+  }
+}
+
+async function showApprovalPrompt(input: {
+  details: NativeApprovalDetails;
+  requireTouchId: boolean;
+}): Promise<NativeApprovalResult> {
+  const { details } = input;
+  const options: Electron.MessageBoxOptions = {
+    type: "warning",
+    title: "Approve Agent Action",
+    message: details.title,
+    detail: [
+      details.command ? `Command:\n${details.command}` : null,
+      details.cwd ? `Directory: ${details.cwd}` : null,
+      `Risk: ${RISK_LABELS[details.riskCategory]}`,
+    ]
+      .filter((line): line is string => line !== null)
+      .join("\n\n"),
+    buttons: ["Deny", "Approve"],
+    defaultId: 0,
+    cancelId: 0,
+    noLink: true,
+  };
+  const win = BrowserWindow.getFocusedWindow() ?? BrowserWindow.getAllWindows()[0];
+  const result = win
+    ? await dialog.showMessageBox(win, options)
+    : await dialog.showMessageBox(options);
+  if (result.response !== 1) {
+    return { approved: false, method: "dialog" };
+  }
+  if (!input.requireTouchId) {
+    return { approved: true, method: "dialog" };
+  }
+  const reason = `approve a ${details.riskCategory} agent command`;
+  return { approved: await promptTouchId(reason), method: "touch_id" };
+}
+
+/** Shows one modal per request even if the renderer and the transport gate both ask. */
+export function promptNativeApproval(input: {
+  details: NativeApprovalDetails;
+  requireTouchId: boolean;
+}): Promise<NativeApprovalResult> {
+  const key = `${input.details.agentId}:${input.details.requestId}`;
+  const pending = pendingPrompts.get(key);
+  if (pending) {
+    return pending;
+  }
+  const prompt = showApprovalPrompt(input).finally(() => {
+    pendingPrompts.delete(key);
+  });
+  pendingPrompts.set(key, prompt);
+  return prompt;
+}
+
+/**
+ * `set_approval_touch_id`: the only way to change `approvals.requireTouchId`,
+ * which renderer settings patches cannot touch. Turning it off takes Touch ID.
+ */
+export async function setApprovalTouchIdRequired(
+  args: Record<string, unknown> | undefined,
+): Promise<DesktopSettings["approvals"]> {
+  if (typeof args?.enabled !== "boolean") {
+    throw new Error("enabled must be a boolean.");
+  }
+  const enabled = args.enabled;
+  const store = getDesktopSettingsStore();
+  const current = (await store.get()).approvals;
+  if (current.requireTouchId === enabled) {
+    return current;
+  }
+  const options: Electron.MessageBoxOptions = {
+    type: "question",
+    title: enabled ? "Require Touch ID" : "Stop Requiring Touch ID",
+    message: enabled
+      ? "Require Touch ID to approve high-risk agent commands?"
+      : "Stop requiring Touch ID for high-risk agent commands?",
+    buttons: ["Cancel", enabled ? "Require" : "Stop Requiring"],
+    defaultId: 0,
+    cancelId: 0,
+    noLink: true,
+  };
+  const win = BrowserWindow.getFocusedWindow() ?? BrowserWindow.getAllWindows()[0];
+  const result = win
+    ? await dialog.showMessageBox(win, options)
+    : await dialog.showMessageBox(options);
+  if (result.response !== 1) {
+    return current;
+  }
+  if (!enabled && !(await promptTouchId("stop requiring Touch ID for agent approvals"))) {
+    return current;
+  }
+  log.info(`[approval] Touch ID ${enabled ? "required" : "no longer required"} for approvals`);
+  return (await store.patch({ approvals: { requireTouchId: enabled } })).approvals;
+}
@@ -0,0 +1,65 @@
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import { createNotificationCoalescer, type CoalescedDelivery } from "./notification-coalescer";
+
+describe("createNotificationCoalescer", () => {
+  beforeEach(() => {
+    vi.useFakeTimers();
+  });
+
+  afterEach(() => {
+    vi.useRealTimers();
+  });
+
+  it("shows the first event and folds the rest into one summary per window", () => {
+    const deliveries: Array<CoalescedDelivery<string>> = [];
+    const coalescer = createNotificationCoalescer<string>({
+      windowMs: 10_000,
+      show: (delivery) => deliveries.push(delivery),
+    });
+
+    coalescer.submit("agent-a", "started");
+    coalescer.submit("agent-a", "edited file");
+    coalescer.submit("agent-a", "ran tests");
+    coalescer.submit("agent-b", "waiting");
+    expect(deliveries.map((delivery) => delivery.payload)).toEqual(["started", "waiting"]);
+
+    vi.advanceTimersByTime(10_000);
+    expect(deliveries.at(-1)).toEqual({ key: "agent-a", payload: "ran tests", suppressedCount: 1 });
+    expect(deliveries).toHaveLength(3);
+
+    vi.advanceTimersByTime(10_000);
+    expect(deliveries).toHaveLength(3);
+  });
+
+  it("keeps throttling after a summary and shows immediately once idle", () => {
+    const deliveries: Array<CoalescedDelivery<string>> = [];
+    const coalescer = createNotificationCoalescer<string>({
+      windowMs: 5_000,
+      show: (delivery) => deliveries.push(delivery),
+    });
+
+    coalescer.submit("agent", "one");
+    coalescer.submit("agent", "two");
+    vi.advanceTimersByTime(5_000);
+    coalescer.submit("agent", "three");
+    expect(deliveries.map((delivery) => delivery.payload)).toEqual(["one", "two"]);
+
+    vi.advanceTimersByTime(5_000);
+    expect(deliveries.map((delivery) => delivery.payload)).toEqual(["one", "two", "three"]);
+
+    vi.advanceTimersByTime(20_000);
+    coalescer.submit("agent", "four");
+    expect(deliveries.at(-1)?.payload).toBe("four");
+  });
+
+  it("shows everything when the window is zero", () => {
+    const show = vi.fn();
+    const coalescer = createNotificationCoalescer<string>({ windowMs: 10_000, show });
+    coalescer.setWindowMs(0);
+
+    coalescer.submit("agent", "one");
+    coalescer.submit("agent", "two");
+
+    expect(show).toHaveBeenCalledTimes(2);
+  });
+});
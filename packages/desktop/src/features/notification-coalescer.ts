@@ -0,0 +1,92 @@
+export interface CoalescedDelivery<T> {
+  key: string;
+  /** Latest payload submitted for `key`. */
+  payload: T;
+  /** Submissions folded into this delivery besides `payload` itself. */
+  suppressedCount: number;
+}
+
+export interface NotificationCoalescer<T> {
+  submit(key: string, payload: T): void;
+  setWindowMs(windowMs: number): void;
+  dispose(): void;
+}
+
+interface CoalescerEntry<T> {
+  lastShownAt: number;
+  pending: T | null;
+  suppressedCount: number;
+  timer: NodeJS.Timeout | null;
+}
+
+/**
+ * Shows the first notification for a key immediately, then folds anything
+ * else for that key into one summary delivered when the window elapses.
+ */
+export function createNotificationCoalescer<T>(input: {
+  windowMs: number;
+  show: (delivery: CoalescedDelivery<T>) => void;
+  now?: () => number;
+}): NotificationCoalescer<T> {
+  const now = input.now ?? (() => Date.now());
+  const entries = new Map<string, CoalescerEntry<T>>();
+  let windowMs = input.windowMs;
+
+  function deliver(key: string, entry: CoalescerEntry<T>, payload: T, suppressedCount: number) {
+    entry.lastShownAt = now();
+    entry.pending = null;
+    entry.suppressedCount = 0;
+    input.show({ key, payload, suppressedCount });
+  }
+
+  function flush(key: string): void {
+    const entry = entries.get(key);
+    if (!entry) {
+      return;
+    }
+    entry.timer = null;
+    if (entry.pending !== null) {
+      deliver(key, entry, entry.pending, entry.suppressedCount);
+    }
+  }
+
+  return {
+    submit(key, payload) {
+      const entry = entries.get(key) ?? {
+        lastShownAt: Number.NEGATIVE_INFINITY,
+        pending: null,
+        suppressedCount: 0,
+        timer: null,
+      };
+      entries.set(key, entry);
+
+      const elapsed = now() - entry.lastShownAt;
+      if (windowMs <= 0 || (elapsed >= windowMs && entry.timer === null)) {
+        deliver(key, entry, payload, 0);
+        return;
+      }
+
+      if (entry.pending !== null) {
+        entry.suppressedCount += 1;
+      }
+      entry.pending = payload;
+      if (entry.timer === null) {
+        entry.timer = setTimeout(() => flush(key), Math.max(0, windowMs - elapsed));
+        entry.timer.unref?.();
+      }
+    },
+
+    setWindowMs(next) {
+      windowMs = Math.max(0, next);
+    },
+
+    dispose() {
+      for (const entry of entries.values()) {
+        if (entry.timer) {
+          clearTimeout(entry.timer);
+        }
+      }
+      entries.clear();
+    },
+  };
+}
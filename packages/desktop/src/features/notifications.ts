@@ -1,6 +1,9 @@
 import path from "node:path";
 import { existsSync } from "node:fs";
 import { app, BrowserWindow, Notification, ipcMain, nativeImage } from "electron";
+import { DEFAULT_DESKTOP_SETTINGS } from "../settings/desktop-settings.js";
+import { getAgentIcon } from "./agent-icons.js";
+import { createNotificationCoalescer } from "./notification-coalescer.js";
 
 interface NotificationInput {
   title?: unknown;
@@ -12,7 +15,42 @@ interface NotificationClickPayload {
   data?: Record<string, unknown>;
 }
 
+interface PreparedNotification {
+  title: string;
+  body?: string;
+  data?: Record<string, unknown>;
+  sender: Electron.WebContents;
+}
+
+export const NOTIFICATION_AGENT_ICON_SIZE = 64;
+
 const activeNotifications = new Set<Notification>();
+// Latest notification per agent, closed when a newer summary replaces it.
+const notificationsByKey = new Map<string, Notification>();
+const agentNotificationCoalescer = createNotificationCoalescer<PreparedNotification>({
+  windowMs: DEFAULT_DESKTOP_SETTINGS.notifications.coalesceWindowSeconds * 1000,
+  show: ({ key, payload, suppressedCount }) => {
+    notificationsByKey.get(key)?.close();
+    const notification = showNotification({
+      ...payload,
+      body: summarizeBody(payload.body, suppressedCount),
+    });
+    notificationsByKey.set(key, notification);
+    notification.once("close", () => {
+      if (notificationsByKey.get(key) === notification) {
+        notificationsByKey.delete(key);
+      }
+    });
+  },
+});
+
+function summarizeBody(body: string | undefined, suppressedCount: number): string | undefined {
+  if (suppressedCount === 0) {
+    return body;
+  }
+  const summary = `+${suppressedCount} more update${suppressedCount === 1 ? "" : "s"}`;
+  return body ? `${body}\n${summary}` : summary;
+}
 
 function toTrimmedString(value: unknown): string | null {
   if (typeof value !== "string") {
@@ -77,6 +115,49 @@ export function ensureNotificationCenterRegistration(): void {
   probe.show();
 }
 
+function resolveCoalesceKey(data: Record<string, unknown> | undefined): string | null {
+  if (typeof data?.agentId !== "string") {
+    return null;
+  }
+  return typeof data.serverId === "string" ? `${data.serverId}:${data.agentId}` : data.agentId;
+}
+
+export function setNotificationCoalesceWindow(seconds: number): void {
+  agentNotificationCoalescer.setWindowMs(seconds * 1000);
+}
+
+function showNotification(input: PreparedNotification): Notification {
+  // Agent notifications carry the agent's identity icon, matching the renderer.
+  const icon =
+    typeof input.data?.agentId === "string"
+      ? getAgentIcon(input.data.agentId, NOTIFICATION_AGENT_ICON_SIZE)
+      : getNotificationIcon();
+  const notification = new Notification({
+    title: input.title,
+    ...(input.body ? { body: input.body } : {}),
+    ...(icon ? { icon } : {}),
+    silent: true,
+  });
+
+  activeNotifications.add(notification);
+
+  notification.on("click", () => {
+    const win = focusSenderWindow(input.sender);
+    if (win && input.data && Object.keys(input.data).length > 0) {
+      const payload: NotificationClickPayload = { data: input.data };
+      win.webContents.send("paseo:event:notification-click", payload);
+    }
+    activeNotifications.delete(notification);
+  });
+
+  notification.on("close", () => {
+    activeNotifications.delete(notification);
+  });
+
+  notification.show();
+  return notification;
+}
+
 export function registerNotificationHandlers(): void {
   ipcMain.handle("paseo:notification:isSupported", () => {
     return Notification.isSupported();
@@ -94,30 +175,13 @@ export function registerNotificationHandlers(): void {
 
     const body = toTrimmedString(rawInput?.body) ?? undefined;
     const data = toRecord(rawInput?.data);
-    const icon = getNotificationIcon();
-    const notification = new Notification({
-      title,
-      ...(body ? { body } : {}),
-      ...(icon ? { icon } : {}),
-      silent: true,
-    });
-
-    activeNotifications.add(notification);
-
-    notification.on("click", () => {
-      const win = focusSenderWindow(event.sender);
-      if (win && data && Object.keys(data).length > 0) {
-        const payload: NotificationClickPayload = { data };
-        win.webContents.send("paseo:event:notification-click", payload);
-      }
-      activeNotifications.delete(notification);
-    });
-
-    notification.on("close", () => {
-      activeNotifications.delete(notification);
-    });
-
-    notification.show();
+    const prepared: PreparedNotification = { title, body, data, sender: event.sender };
+    const coalesceKey = resolveCoalesceKey(data);
+    if (coalesceKey) {
+      agentNotificationCoalescer.submit(coalesceKey, prepared);
+    } else {
+      showNotification(prepared);
+    }
     return true;
   });
 }
@@ -0,0 +1,89 @@
+import { app, Notification, systemPreferences } from "electron";
+import { emitDesktopEvent } from "../window/desktop-events.js";
+import { ensureNotificationCenterRegistration } from "./notifications.js";
+import {
+  createOnboardingStore,
+  isOnboardingStep,
+  type OnboardingState,
+  type OnboardingStep,
+  type OnboardingStore,
+} from "./onboarding.js";
+
+let onboardingStore: OnboardingStore | null = null;
+
+export function getOnboardingStore(): OnboardingStore {
+  onboardingStore ??= createOnboardingStore({ userDataPath: app.getPath("userData") });
+  return onboardingStore;
+}
+
+function preflightNotifications(): string {
+  if (!Notification.isSupported()) {
+    return "unsupported";
+  }
+  ensureNotificationCenterRegistration();
+  return "registered";
+}
+
+async function requestMicrophoneAccess(): Promise<string> {
+  if (process.platform === "darwin") {
+    return (await systemPreferences.askForMediaAccess("microphone")) ? "granted" : "denied";
+  }
+  if (process.platform === "win32") {
+    return systemPreferences.getMediaAccessStatus("microphone");
+  }
+  // Linux has no OS-level microphone permission; the renderer's getUserMedia prompt covers it.
+  return "not-required";
+}
+
+function applyLoginItem(openAtLogin: boolean): string {
+  if (process.platform === "linux") {
+    return "unsupported";
+  }
+  app.setLoginItemSettings({ openAtLogin });
+  return openAtLogin ? "enabled" : "disabled";
+}
+
+async function runOnboardingStep(input: {
+  step: OnboardingStep;
+  args: Record<string, unknown> | undefined;
+  ensureDaemon: () => Promise<string>;
+}): Promise<string | null> {
+  switch (input.step) {
+    case "daemon":
+      return await input.ensureDaemon();
+    case "notifications":
+      return preflightNotifications();
+    case "microphone":
+      return await requestMicrophoneAccess();
+    case "login_item":
+      return applyLoginItem(input.args?.openAtLogin === true);
+    case "profile":
+      // The renderer creates the profile; only its outcome is recorded here.
+      return typeof input.args?.detail === "string" ? input.args.detail : null;
+  }
+}
+
+/** Runs the native side of `step`, if any, then records it so a restart resumes after it. */
+export async function completeOnboardingStep(input: {
+  args: Record<string, unknown> | undefined;
+  ensureDaemon: () => Promise<string>;
+}): Promise<OnboardingState> {
+  const step = input.args?.step;
+  if (!isOnboardingStep(step)) {
+    throw new Error(`Unknown onboarding step: ${String(step)}`);
+  }
+  const store = getOnboardingStore();
+  const { currentStep } = await store.getState();
+  if (currentStep !== step) {
+    // Already recorded or out of order; the store returns or rejects without side effects.
+    return await store.completeStep({ step });
+  }
+
+  const skipped = input.args?.skipped === true;
+  const detail = skipped
+    ? null
+    : await runOnboardingStep({ step, args: input.args, ensureDaemon: input.ensureDaemon });
+  const state = await store.completeStep({ step, skipped, detail });
+  emitDesktopEvent("onboarding-state-changed", state);
+  return state;
+}
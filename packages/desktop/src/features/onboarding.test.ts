@@ -0,0 +1,71 @@
+import { mkdtemp, rm, writeFile } from "node:fs/promises";
+import { tmpdir } from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+import { createOnboardingStore } from "./onboarding";
+
+describe("createOnboardingStore", () => {
+  let userDataPath: string;
+
+  beforeEach(async () => {
+    userDataPath = await mkdtemp(path.join(tmpdir(), "paseo-onboarding-"));
+  });
+
+  afterEach(async () => {
+    await rm(userDataPath, { recursive: true, force: true });
+  });
+
+  it("starts a fresh install at the first step", async () => {
+    const state = await createOnboardingStore({ userDataPath }).getState();
+
+    expect(state.firstRun).toBe(true);
+    expect(state.currentStep).toBe("daemon");
+    expect(state.finishedAt).toBeNull();
+  });
+
+  it("skips onboarding for installs that already have desktop settings", async () => {
+    await writeFile(path.join(userDataPath, "desktop-settings.json"), "{}\n");
+
+    const state = await createOnboardingStore({ userDataPath }).getState();
+
+    expect(state.firstRun).toBe(false);
+    expect(state.currentStep).toBeNull();
+  });
+
+  it("enforces step order and resumes from disk after a restart", async () => {
+    const store = createOnboardingStore({ userDataPath, now: () => 0 });
+    await store.completeStep({ step: "daemon", detail: "0.1.65" });
+    await expect(store.completeStep({ step: "login_item" })).rejects.toThrow(
+      'Complete onboarding step "notifications" before "login_item".',
+    );
+    await store.completeStep({ step: "notifications", skipped: true });
+
+    const resumed = await createOnboardingStore({ userDataPath }).getState();
+    expect(resumed.firstRun).toBe(true);
+    expect(resumed.currentStep).toBe("microphone");
+    expect(resumed.steps.slice(0, 2)).toEqual([
+      {
+        step: "daemon",
+        result: { status: "done", detail: "0.1.65", completedAt: "1970-01-01T00:00:00.000Z" },
+      },
+      {
+        step: "notifications",
+        result: { status: "skipped", detail: null, completedAt: "1970-01-01T00:00:00.000Z" },
+      },
+    ]);
+  });
+
+  it("finishes after the last step and treats repeats as no-ops", async () => {
+    const store = createOnboardingStore({ userDataPath, now: () => 0 });
+    for (const step of ["daemon", "notifications", "microphone", "login_item"] as const) {
+      await store.completeStep({ step });
+    }
+    const finished = await store.completeStep({ step: "profile", detail: "default" });
+    const repeated = await store.completeStep({ step: "daemon", detail: "ignored" });
+
+    expect(finished.currentStep).toBeNull();
+    expect(finished.finishedAt).toBe("1970-01-01T00:00:00.000Z");
+    expect(repeated).toEqual(finished);
+    expect((await createOnboardingStore({ userDataPath }).getState()).firstRun).toBe(false);
+  });
+});
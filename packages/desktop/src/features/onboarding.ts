@@ -0,0 +1,173 @@
+import { access, readFile } from "node:fs/promises";
+import path from "node:path";
+import { updateJsonConfigFile } from "../settings/config-file.js";
+import { isRecord } from "../utils/is-record.js";
+
+const ONBOARDING_FILENAME = "onboarding.json";
+// Installs that predate onboarding already have settings and should not be walked through it.
+const LEGACY_MARKER_FILENAME = "desktop-settings.json";
+
+export const ONBOARDING_STEPS = [
+  "daemon",
+  "notifications",
+  "microphone",
+  "login_item",
+  "profile",
+] as const;
+
+export type OnboardingStep = (typeof ONBOARDING_STEPS)[number];
+
+export interface OnboardingStepResult {
+  status: "done" | "skipped";
+  /** Step-specific outcome, e.g. whether the microphone permission was granted. */
+  detail: string | null;
+  completedAt: string;
+}
+
+export interface OnboardingState {
+  firstRun: boolean;
+  /** Next step to run; `null` once every step is done or skipped. */
+  currentStep: OnboardingStep | null;
+  steps: Array<{ step: OnboardingStep; result: OnboardingStepResult | null }>;
+  finishedAt: string | null;
+}
+
+interface PersistedOnboardingDocument {
+  version: 1;
+  results: Partial<Record<OnboardingStep, OnboardingStepResult>>;
+  finishedAt: string | null;
+}
+
+export interface OnboardingStore {
+  getState(): Promise<OnboardingState>;
+  /**
+   * Records `step` and advances to the next one. Steps complete strictly in
+   * order; re-completing a finished step is a no-op so retries after a crash
+   * are safe.
+   */
+  completeStep(input: {
+    step: OnboardingStep;
+    skipped?: boolean;
+    detail?: string | null;
+  }): Promise<OnboardingState>;
+}
+
+export function isOnboardingStep(value: unknown): value is OnboardingStep {
+  return (ONBOARDING_STEPS as readonly unknown[]).includes(value);
+}
+
+function coerceDocument(value: unknown): PersistedOnboardingDocument {
+  const document: PersistedOnboardingDocument = { version: 1, results: {}, finishedAt: null };
+  if (!isRecord(value)) {
+    return document;
+  }
+  if (isRecord(value.results)) {
+    for (const step of ONBOARDING_STEPS) {
+      const entry = value.results[step];
+      if (!isRecord(entry) || (entry.status !== "done" && entry.status !== "skipped")) {
+        continue;
+      }
+      document.results[step] = {
+        status: entry.status,
+        detail: typeof entry.detail === "string" ? entry.detail : null,
+        completedAt: typeof entry.completedAt === "string" ? entry.completedAt : "",
+      };
+    }
+  }
+  document.finishedAt = typeof value.finishedAt === "string" ? value.finishedAt : null;
+  return document;
+}
+
+async function fileExists(filePath: string): Promise<boolean> {
+  try {
+    await access(filePath);
+    return true;
+  } catch {
+    return false;
+  }
+}
+
+export function createOnboardingStore(input: {
+  userDataPath: string;
+  now?: () => number;
+}): OnboardingStore {
+  const filePath = path.join(input.userDataPath, ONBOARDING_FILENAME);
+  const now = input.now ?? (() => Date.now());
+  let document: PersistedOnboardingDocument | null = null;
+  let firstRun = false;
+  let loaded: Promise<PersistedOnboardingDocument> | null = null;
+
+  async function load(): Promise<PersistedOnboardingDocument> {
+    try {
+      document = coerceDocument(JSON.parse(await readFile(filePath, "utf8")));
+      firstRun = document.finishedAt === null;
+    } catch {
+      const legacyInstall = await fileExists(path.join(input.userDataPath, LEGACY_MARKER_FILENAME));
+      document = {
+        version: 1,
+        results: {},
+        finishedAt: legacyInstall ? new Date(now()).toISOString() : null,
+      };
+      firstRun = !legacyInstall;
+    }
+    return document;
+  }
+
+  async function getDocument(): Promise<PersistedOnboardingDocument> {
+    loaded ??= load();
+    const initial = await loaded;
+    return document ?? initial;
+  }
+
+  async function update(
+    mutate: (current: PersistedOnboardingDocument) => PersistedOnboardingDocument,
+  ): Promise<PersistedOnboardingDocument> {
+    // Without a file on disk, start from what load() made of the legacy marker.
+    const fallback = await getDocument();
+    const next = await updateJsonConfigFile(filePath, (raw) =>
+      mutate(raw === null ? fallback : coerceDocument(raw)),
+    );
+    document = next;
+    return next;
+  }
+
+  function toState(current: PersistedOnboardingDocument): OnboardingState {
+    const currentStep =
+      current.finishedAt === null
+        ? (ONBOARDING_STEPS.find((step) => !current.results[step]) ?? null)
+        : null;
+    return {
+      firstRun,
+      currentStep,
+      steps: ONBOARDING_STEPS.map((step) => ({ step, result: current.results[step] ?? null })),
+      finishedAt: current.finishedAt,
+    };
+  }
+
+  return {
+    async getState() {
+      return toState(await getDocument());
+    },
+
+    async completeStep({ step, skipped, detail }) {
+      const next = await update((current) => {
+        if (current.results[step] || current.finishedAt !== null) {
+          return current;
+        }
+        const expected = toState(current).currentStep;
+        if (step !== expected) {
+          throw new Error(`Complete onboarding step "${expected}" before "${step}".`);
+        }
+
+        const completedAt = new Date(now()).toISOString();
+        const results: PersistedOnboardingDocument["results"] = {
+          ...current.results,
+          [step]: { status: skipped ? "skipped" : "done", detail: detail ?? null, completedAt },
+        };
+        const finished = ONBOARDING_STEPS.every((candidate) => results[candidate]);
+        return { version: 1, results, finishedAt: finished ? completedAt : null };
+      });
+      return toState(next);
+    },
+  };
+}
@@ -0,0 +1,99 @@
+import path from "node:path";
+import { app } from "electron";
+import log from "electron-log/main";
+import {
+  createOperationJournal,
+  type JournalOperation,
+  type OperationJournal,
+  type UndoOperationResult,
+} from "./operation-journal.js";
+
+export const DESKTOP_SETTINGS_CONFIG_TARGET = "desktop-settings";
+
+let operationJournal: OperationJournal | null = null;
+
+function getOperationJournal(): OperationJournal {
+  operationJournal ??= createOperationJournal({ userDataPath: app.getPath("userData") });
+  return operationJournal;
+}
+
+/** Permanent attachment delete: the file moves into the journal instead. */
+export async function removeAttachmentWithJournal(filePath: string): Promise<void> {
+  await getOperationJournal().holdRemovedFiles({
+    kind: "attachment_delete",
+    summary: `Deleted attachment ${path.basename(filePath)}`,
+    paths: [filePath],
+  });
+}
+
+export async function removeGarbageAttachmentsWithJournal(filePaths: string[]): Promise<void> {
+  await getOperationJournal().holdRemovedFiles({
+    kind: "attachment_gc",
+    summary: `Cleaned up ${filePaths.length} unused attachment${filePaths.length === 1 ? "" : "s"}`,
+    paths: filePaths,
+  });
+}
+
+/**
+ * Journals `filePath` and then runs `trash`. A journal failure is logged and
+ * does not block the trash, which is recoverable on its own.
+ */
+export async function trashWithJournal(
+  filePath: string,
+  trash: (filePath: string) => Promise<void>,
+): Promise<void> {
+  const journal = getOperationJournal();
+  const operation = await journal
+    .recordTrash({ summary: `Moved ${path.basename(filePath)} to the Trash`, path: filePath })
+    .catch((error: unknown) => {
+      log.warn(`[operation-journal] failed to record trashing ${filePath}`, error);
+      return null;
+    });
+  try {
+    await trash(filePath);
+  } catch (error) {
+    if (operation) {
+      await journal.forget(operation.id).catch(() => undefined);
+    }
+    throw error;
+  }
+}
+
+export function recordDesktopSettingsOverwrite(input: {
+  previous: unknown;
+  summary: string;
+}): void {
+  void getOperationJournal()
+    .recordConfigOverwrite({ target: DESKTOP_SETTINGS_CONFIG_TARGET, ...input })
+    .catch((error: unknown) => {
+      log.warn("[operation-journal] failed to record a settings change", error);
+    });
+}
+
+/** `list_recent_operations`: `{ limit? }`, newest first. */
+export async function listRecentOperations(
+  args: Record<string, unknown> | undefined,
+): Promise<JournalOperation[]> {
+  const limit = args?.limit;
+  if (limit !== undefined && (typeof limit !== "number" || !Number.isInteger(limit) || limit < 1)) {
+    throw new Error("limit must be a positive integer.");
+  }
+  return await getOperationJournal().list(limit);
+}
+
+/** `undo_operation`: `{ id }`. Config targets are restored through `restoreConfig`. */
+export async function undoOperation(input: {
+  args: Record<string, unknown> | undefined;
+  restoreConfig: (target: string, previous: unknown) => Promise<void>;
+}): Promise<UndoOperationResult> {
+  const id = input.args?.id;
+  if (typeof id !== "string" || id.length === 0) {
+    throw new Error("id must be a non-empty string.");
+  }
+  const result = await getOperationJournal().undo(id, { restoreConfig: input.restoreConfig });
+  log.info(
+    `[operation-journal] undid ${result.operation.kind} ${id}: ` +
+      `${result.restored.length} restored, ${result.failed.length} failed`,
+  );
+  return result;
+}
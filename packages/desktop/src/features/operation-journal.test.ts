@@ -0,0 +1,145 @@
+import { mkdir, mkdtemp, readFile, rm, writeFile } from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import { createOperationJournal } from "./operation-journal";
+
+describe("createOperationJournal", () => {
+  let root: string;
+  let userDataPath: string;
+  let files: string;
+
+  beforeEach(async () => {
+    root = await mkdtemp(path.join(os.tmpdir(), "paseo-operation-journal-"));
+    userDataPath = path.join(root, "user-data");
+    files = path.join(root, "files");
+    await mkdir(files, { recursive: true });
+  });
+
+  afterEach(async () => {
+    await rm(root, { recursive: true, force: true });
+  });
+
+  it("holds removed files and puts them back on undo", async () => {
+    const journal = createOperationJournal({ userDataPath });
+    const kept = path.join(files, "a.png");
+    const missing = path.join(files, "gone.png");
+    await writeFile(kept, "image");
+
+    const operation = await journal.holdRemovedFiles({
+      kind: "attachment_gc",
+      summary: "Cleaned up 2 unused attachments",
+      paths: [kept, missing],
+    });
+
+    expect(operation?.entries).toHaveLength(1);
+    await expect(readFile(kept)).rejects.toThrow();
+    const result = await journal.undo(operation!.id, { restoreConfig: vi.fn() });
+    expect(result).toMatchObject({ restored: [kept], inTrash: [], failed: [] });
+    expect(result.operation.undoneAt).not.toBeNull();
+    expect(await readFile(kept, "utf8")).toBe("image");
+    await expect(journal.undo(operation!.id, { restoreConfig: vi.fn() })).rejects.toThrow(
+      "already undone",
+    );
+  });
+
+  it("records nothing when every file was already gone", async () => {
+    const journal = createOperationJournal({ userDataPath });
+    const operation = await journal.holdRemovedFiles({
+      kind: "attachment_delete",
+      summary: "Deleted attachment gone.png",
+      paths: [path.join(files, "gone.png")],
+    });
+    expect(operation).toBeNull();
+    expect(await journal.list()).toEqual([]);
+  });
+
+  it("refuses to overwrite a file that reappeared at the original path", async () => {
+    const journal = createOperationJournal({ userDataPath });
+    const filePath = path.join(files, "a.png");
+    await writeFile(filePath, "old");
+    const operation = await journal.holdRemovedFiles({
+      kind: "attachment_delete",
+      summary: "Deleted attachment a.png",
+      paths: [filePath],
+    });
+    await writeFile(filePath, "new");
+
+    const result = await journal.undo(operation!.id, { restoreConfig: vi.fn() });
+
+    expect(result.failed).toEqual([
+      { path: filePath, error: "A file already exists at the original path." },
+    ]);
+    expect(result.operation.undoneAt).toBeNull();
+    expect(await readFile(filePath, "utf8")).toBe("new");
+  });
+
+  it("restores trashed files from its copy and points at the trash for folders", async () => {
+    const journal = createOperationJournal({ userDataPath });
+    const filePath = path.join(files, "notes.md");
+    const folderPath = path.join(files, "build");
+    await writeFile(filePath, "notes");
+    await mkdir(folderPath);
+
+    const trashedFile = await journal.recordTrash({ summary: "Moved notes.md", path: filePath });
+    const trashedFolder = await journal.recordTrash({ summary: "Moved build", path: folderPath });
+    await rm(filePath);
+    await rm(folderPath, { recursive: true });
+
+    const fileResult = await journal.undo(trashedFile.id, { restoreConfig: vi.fn() });
+    const folderResult = await journal.undo(trashedFolder.id, { restoreConfig: vi.fn() });
+
+    expect(fileResult.restored).toEqual([filePath]);
+    expect(await readFile(filePath, "utf8")).toBe("notes");
+    expect(folderResult).toMatchObject({ restored: [], inTrash: [folderPath], failed: [] });
+  });
+
+  it("hands config overwrites to the restorer and persists the journal", async () => {
+    const journal = createOperationJournal({ userDataPath, now: () => Date.UTC(2026, 9, 16) });
+    const previous = { releaseChannel: "stable" };
+    const operation = await journal.recordConfigOverwrite({
+      target: "desktop-settings",
+      summary: "Changed desktop settings",
+      previous,
+    });
+    const forgotten = await journal.recordConfigOverwrite({
+      target: "desktop-settings",
+      summary: "Changed desktop settings again",
+      previous,
+    });
+    await journal.forget(forgotten.id);
+
+    const reopened = createOperationJournal({ userDataPath });
+    expect(await reopened.list()).toEqual([operation]);
+    const restoreConfig = vi.fn(async () => undefined);
+    await reopened.undo(operation.id, { restoreConfig });
+    expect(restoreConfig).toHaveBeenCalledWith("desktop-settings", previous);
+  });
+
+  it("lists newest first and drops operations older than a week", async () => {
+    let nowMs = Date.UTC(2026, 9, 1);
+    const journal = createOperationJournal({ userDataPath, now: () => nowMs });
+    const first = await journal.recordConfigOverwrite({
+      target: "desktop-settings",
+      summary: "first",
+      previous: {},
+    });
+    nowMs += 60_000;
+    const second = await journal.recordConfigOverwrite({
+      target: "desktop-settings",
+      summary: "second",
+      previous: {},
+    });
+
+    expect((await journal.list()).map((operation) => operation.id)).toEqual([second.id, first.id]);
+    expect(await journal.list(1)).toEqual([second]);
+
+    nowMs += 8 * 24 * 60 * 60 * 1000;
+    const third = await journal.recordConfigOverwrite({
+      target: "desktop-settings",
+      summary: "third",
+      previous: {},
+    });
+    expect(await journal.list()).toEqual([third]);
+  });
+});
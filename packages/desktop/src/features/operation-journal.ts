@@ -0,0 +1,385 @@
+import { randomUUID } from "node:crypto";
+import { copyFile, cp, lstat, mkdir, readFile, rename, rm } from "node:fs/promises";
+import path from "node:path";
+import { updateJsonConfigFile } from "../settings/config-file.js";
+import { isRecord } from "../utils/is-record.js";
+
+const OPERATION_JOURNAL_DIRNAME = "operation-journal";
+const OPERATION_JOURNAL_FILENAME = "journal.json";
+const HELD_DIRNAME = "held";
+const MAX_OPERATIONS = 100;
+const MAX_AGE_MS = 7 * 24 * 60 * 60 * 1000;
+// Held files are what a GC run or permanent delete would have freed; past this, oldest go first.
+const MAX_HELD_BYTES = 1024 * 1024 * 1024;
+// The trash keeps the original; a copy only makes undo independent of where the OS put it.
+const MAX_TRASH_COPY_BYTES = 25 * 1024 * 1024;
+
+export type OperationKind = "attachment_delete" | "attachment_gc" | "trash" | "config_overwrite";
+
+export type JournalEntry =
+  /** A removed file, moved into the journal instead of deleted. */
+  | { type: "held"; originalPath: string; heldPath: string; bytes: number }
+  /** A trashed path; `copyPath` is null when it was a folder or too large to copy. */
+  | { type: "trashed"; originalPath: string; copyPath: string | null; bytes: number }
+  /** The whole config `target` as it was before it was overwritten. */
+  | { type: "config"; target: string; previous: unknown };
+
+export interface JournalOperation {
+  id: string;
+  kind: OperationKind;
+  summary: string;
+  at: string;
+  entries: JournalEntry[];
+  undoneAt: string | null;
+}
+
+export interface UndoOperationResult {
+  operation: JournalOperation;
+  restored: string[];
+  /** Trashed without a copy: the user restores these from the Trash / Recycle Bin. */
+  inTrash: string[];
+  failed: Array<{ path: string; error: string }>;
+}
+
+interface PersistedOperationJournalDocument {
+  version: 1;
+  operations: JournalOperation[];
+}
+
+export interface OperationJournal {
+  /** Newest first. */
+  list(limit?: number): Promise<JournalOperation[]>;
+  /**
+   * Removes files by moving them into the journal. Missing files are
+   * skipped; `null` when there was nothing to remove.
+   */
+  holdRemovedFiles(input: {
+    kind: "attachment_delete" | "attachment_gc";
+    summary: string;
+    paths: string[];
+  }): Promise<JournalOperation | null>;
+  /** Call before the path goes to the trash; `forget` the operation if trashing fails. */
+  recordTrash(input: { summary: string; path: string }): Promise<JournalOperation>;
+  recordConfigOverwrite(input: {
+    target: string;
+    summary: string;
+    previous: unknown;
+  }): Promise<JournalOperation>;
+  forget(id: string): Promise<void>;
+  undo(
+    id: string,
+    input: { restoreConfig: (target: string, previous: unknown) => Promise<void> },
+  ): Promise<UndoOperationResult>;
+}
+
+function isOperationKind(value: unknown): value is OperationKind {
+  return (
+    value === "attachment_delete" ||
+    value === "attachment_gc" ||
+    value === "trash" ||
+    value === "config_overwrite"
+  );
+}
+
+function coerceEntry(value: unknown): JournalEntry | null {
+  if (!isRecord(value)) {
+    return null;
+  }
+  const bytes = typeof value.bytes === "number" ? value.bytes : 0;
+  if (
+    value.type === "held" &&
+    typeof value.originalPath === "string" &&
+    typeof value.heldPath === "string"
+  ) {
+    return { type: "held", originalPath: value.originalPath, heldPath: value.heldPath, bytes };
+  }
+  if (value.type === "trashed" && typeof value.originalPath === "string") {
+    const copyPath = typeof value.copyPath === "string" ? value.copyPath : null;
+    return { type: "trashed", originalPath: value.originalPath, copyPath, bytes };
+  }
+  if (value.type === "config" && typeof value.target === "string") {
+    return { type: "config", target: value.target, previous: value.previous };
+  }
+  return null;
+}
+
+function coerceOperation(value: unknown): JournalOperation | null {
+  if (
+    !isRecord(value) ||
+    typeof value.id !== "string" ||
+    !isOperationKind(value.kind) ||
+    typeof value.at !== "string" ||
+    !Array.isArray(value.entries)
+  ) {
+    return null;
+  }
+  return {
+    id: value.id,
+    kind: value.kind,
+    summary: typeof value.summary === "string" ? value.summary : "",
+    at: value.at,
+    entries: value.entries.flatMap((entry) => coerceEntry(entry) ?? []),
+    undoneAt: typeof value.undoneAt === "string" ? value.undoneAt : null,
+  };
+}
+
+function coerceOperations(value: unknown): JournalOperation[] {
+  return isRecord(value) && Array.isArray(value.operations)
+    ? value.operations.flatMap((operation) => coerceOperation(operation) ?? [])
+    : [];
+}
+
+function heldBytes(operation: JournalOperation): number {
+  if (operation.undoneAt) {
+    return 0;
+  }
+  return operation.entries.reduce(
+    (total, entry) =>
+      total +
+      (entry.type === "held" || (entry.type === "trashed" && entry.copyPath) ? entry.bytes : 0),
+    0,
+  );
+}
+
+/** `rename`, or copy and remove when the journal sits on another volume. */
+async function moveFile(from: string, to: string): Promise<void> {
+  try {
+    await rename(from, to);
+  } catch (error) {
+    if ((error as NodeJS.ErrnoException).code !== "EXDEV") {
+      throw error;
+    }
+    await cp(from, to, { errorOnExist: true, force: false, preserveTimestamps: true });
+    await rm(from, { force: true });
+  }
+}
+
+async function pathExists(filePath: string): Promise<boolean> {
+  return (await lstat(filePath).catch(() => null)) !== null;
+}
+
+export function createOperationJournal(input: {
+  userDataPath: string;
+  now?: () => number;
+}): OperationJournal {
+  const rootPath = path.join(input.userDataPath, OPERATION_JOURNAL_DIRNAME);
+  const filePath = path.join(rootPath, OPERATION_JOURNAL_FILENAME);
+  const heldRoot = path.join(rootPath, HELD_DIRNAME);
+  const now = input.now ?? (() => Date.now());
+  let operations: JournalOperation[] = [];
+  let loaded: Promise<void> | null = null;
+  let queue: Promise<unknown> = Promise.resolve();
+
+  function ensureLoaded(): Promise<void> {
+    loaded ??= readFile(filePath, "utf8")
+      .then((raw) => {
+        operations = coerceOperations(JSON.parse(raw));
+      })
+      .catch(() => {
+        operations = [];
+      });
+    return loaded;
+  }
+
+  function enqueue<T>(task: () => Promise<T>): Promise<T> {
+    const run = queue.catch(() => undefined).then(async () => {
+      await ensureLoaded();
+      return await task();
+    });
+    queue = run;
+    return run;
+  }
+
+  function heldDir(id: string): string {
+    return path.join(heldRoot, id);
+  }
+
+  /** Splits off old operations once over budget, always keeping `keepId`. */
+  function partition(
+    all: JournalOperation[],
+    keepId: string,
+  ): { kept: JournalOperation[]; dropped: JournalOperation[] } {
+    const oldest = now() - MAX_AGE_MS;
+    let totalBytes = 0;
+    const kept: JournalOperation[] = [];
+    const dropped: JournalOperation[] = [];
+    // `all` is oldest first; walk newest first so the budget favours recent work.
+    for (const operation of [...all].reverse()) {
+      const bytes = heldBytes(operation);
+      const keep =
+        operation.id === keepId ||
+        (kept.length < MAX_OPERATIONS &&
+          Date.parse(operation.at) >= oldest &&
+          totalBytes + bytes <= MAX_HELD_BYTES);
+      if (keep) {
+        kept.push(operation);
+        totalBytes += bytes;
+      } else {
+        dropped.push(operation);
+      }
+    }
+    return { kept: kept.reverse(), dropped };
+  }
+
+  /**
+   * Applies `mutate` to the journal on disk under the file lock, so operations
+   * another instance recorded meanwhile are kept.
+   */
+  async function update(
+    mutate: (current: JournalOperation[]) => JournalOperation[],
+  ): Promise<void> {
+    const document = await updateJsonConfigFile(
+      filePath,
+      (raw): PersistedOperationJournalDocument => ({
+        version: 1,
+        operations: mutate(coerceOperations(raw)),
+      }),
+    );
+    operations = document.operations;
+  }
+
+  async function record(operation: JournalOperation): Promise<JournalOperation> {
+    let dropped: JournalOperation[] = [];
+    await update((current) => {
+      const split = partition([...current, operation], operation.id);
+      dropped = split.dropped;
+      return split.kept;
+    });
+    for (const old of dropped) {
+      await rm(heldDir(old.id), { recursive: true, force: true });
+    }
+    return structuredClone(operation);
+  }
+
+  function newOperation(kind: OperationKind, summary: string): JournalOperation {
+    return {
+      id: randomUUID(),
+      kind,
+      summary,
+      at: new Date(now()).toISOString(),
+      entries: [],
+      undoneAt: null,
+    };
+  }
+
+  /** Moves a kept file back; one already moved back by an earlier, partly failed undo is done. */
+  async function restoreFile(from: string, to: string): Promise<void> {
+    const [sourceExists, targetExists] = await Promise.all([pathExists(from), pathExists(to)]);
+    if (!sourceExists && targetExists) {
+      return;
+    }
+    if (targetExists) {
+      throw new Error("A file already exists at the original path.");
+    }
+    await mkdir(path.dirname(to), { recursive: true });
+    await moveFile(from, to);
+  }
+
+  return {
+    list(limit) {
+      return enqueue(async () =>
+        structuredClone([...operations].reverse().slice(0, limit ?? MAX_OPERATIONS)),
+      );
+    },
+
+    holdRemovedFiles({ kind, summary, paths }) {
+      return enqueue(async () => {
+        const operation = newOperation(kind, summary);
+        const dir = heldDir(operation.id);
+        for (const [index, originalPath] of paths.entries()) {
+          const info = await lstat(originalPath).catch(() => null);
+          if (!info) {
+            continue;
+          }
+          if (info.isDirectory()) {
+            throw new Error(`Refusing to remove a directory: ${originalPath}`);
+          }
+          await mkdir(dir, { recursive: true });
+          const heldPath = path.join(dir, `${index}-${path.basename(originalPath)}`);
+          await moveFile(originalPath, heldPath);
+          operation.entries.push({ type: "held", originalPath, heldPath, bytes: info.size });
+        }
+        return operation.entries.length > 0 ? await record(operation) : null;
+      });
+    },
+
+    recordTrash({ summary, path: originalPath }) {
+      return enqueue(async () => {
+        const operation = newOperation("trash", summary);
+        const info = await lstat(originalPath).catch(() => null);
+        let copyPath: string | null = null;
+        if (info?.isFile() && info.size <= MAX_TRASH_COPY_BYTES) {
+          const dir = heldDir(operation.id);
+          await mkdir(dir, { recursive: true });
+          copyPath = path.join(dir, path.basename(originalPath));
+          await copyFile(originalPath, copyPath);
+        }
+        operation.entries.push({
+          type: "trashed",
+          originalPath,
+          copyPath,
+          bytes: copyPath && info ? info.size : 0,
+        });
+        return await record(operation);
+      });
+    },
+
+    recordConfigOverwrite({ target, summary, previous }) {
+      return enqueue(async () => {
+        const operation = newOperation("config_overwrite", summary);
+        operation.entries.push({ type: "config", target, previous: structuredClone(previous) });
+        return await record(operation);
+      });
+    },
+
+    forget(id) {
+      return enqueue(async () => {
+        await update((current) => current.filter((operation) => operation.id !== id));
+        await rm(heldDir(id), { recursive: true, force: true });
+      });
+    },
+
+    undo(id, { restoreConfig }) {
+      return enqueue(async () => {
+        const operation = operations.find((entry) => entry.id === id);
+        if (!operation) {
+          throw new Error(`Operation not found: ${id}`);
+        }
+        if (operation.undoneAt) {
+          throw new Error("Operation was already undone.");
+        }
+        const restored: string[] = [];
+        const inTrash: string[] = [];
+        const failed: UndoOperationResult["failed"] = [];
+        for (const entry of operation.entries) {
+          const label = entry.type === "config" ? entry.target : entry.originalPath;
+          try {
+            if (entry.type === "held") {
+              await restoreFile(entry.heldPath, entry.originalPath);
+            } else if (entry.type === "trashed" && entry.copyPath) {
+              await restoreFile(entry.copyPath, entry.originalPath);
+            } else if (entry.type === "trashed") {
+              inTrash.push(entry.originalPath);
+              continue;
+            } else {
+              await restoreConfig(entry.target, entry.previous);
+            }
+            restored.push(label);
+          } catch (error) {
+            const message = error instanceof Error ? error.message : String(error);
+            failed.push({ path: label, error: message });
+          }
+        }
+        if (failed.length === 0) {
+          const undoneAt = new Date(now()).toISOString();
+          operation.undoneAt = undoneAt;
+          await update((current) =>
+            current.map((entry) => (entry.id === id ? { ...entry, undoneAt } : entry)),
+          );
+          await rm(heldDir(operation.id), { recursive: true, force: true });
+        }
+        return { operation: structuredClone(operation), restored, inTrash, failed };
+      });
+    },
+  };
+}
@@ -0,0 +1,40 @@
+import { BrowserWindow } from "electron";
+import log from "electron-log/main";
+import { emitDesktopEvent } from "../window/desktop-events.js";
+import { type OpenedPaseoBundle, readPaseoBundle } from "./paseo-bundle.js";
+
+const OPEN_PASEO_BUNDLE_EVENT = "open-paseo-bundle";
+
+let pendingBundles: OpenedPaseoBundle[] = [];
+
+function hasLoadedWindow(): boolean {
+  return BrowserWindow.getAllWindows().some(
+    (win) => !win.isDestroyed() && !win.webContents.isLoadingMainFrame(),
+  );
+}
+
+/**
+ * Routes a double-clicked `.paseo` file to the renderer's import flow. Until a
+ * window has loaded, bundles wait for `take_pending_paseo_bundles`, the same
+ * pull-on-mount approach used for open-project.
+ */
+export function openPaseoBundle(filePath: string): void {
+  void readPaseoBundle(filePath)
+    .then((bundle) => {
+      log.info("[paseo-bundle] opening", bundle);
+      if (hasLoadedWindow()) {
+        emitDesktopEvent(OPEN_PASEO_BUNDLE_EVENT, bundle);
+      } else {
+        pendingBundles.push(bundle);
+      }
+    })
+    .catch((error) => {
+      log.warn("[paseo-bundle] ignoring file", filePath, error);
+    });
+}
+
+export function takePendingPaseoBundles(): OpenedPaseoBundle[] {
+  const bundles = pendingBundles;
+  pendingBundles = [];
+  return bundles;
+}
@@ -0,0 +1,38 @@
+import { mkdtemp, rm, writeFile } from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+import { classifyPaseoBundle, readPaseoBundle } from "./paseo-bundle";
+
+describe("classifyPaseoBundle", () => {
+  it("routes by the top-level kind", () => {
+    expect(classifyPaseoBundle(JSON.stringify({ kind: "session", version: 1 }))).toBe("session");
+    expect(classifyPaseoBundle(JSON.stringify({ kind: "settings" }))).toBe("settings");
+  });
+
+  it("rejects unknown kinds and non-JSON", () => {
+    expect(classifyPaseoBundle(JSON.stringify({ kind: "plugin" }))).toBeNull();
+    expect(classifyPaseoBundle("[]")).toBeNull();
+    expect(classifyPaseoBundle("not json")).toBeNull();
+  });
+});
+
+describe("readPaseoBundle", () => {
+  let dir: string;
+
+  beforeEach(async () => {
+    dir = await mkdtemp(path.join(os.tmpdir(), "paseo-bundle-"));
+  });
+
+  afterEach(async () => {
+    await rm(dir, { recursive: true, force: true });
+  });
+
+  it("reads a bundle from disk", async () => {
+    const filePath = path.join(dir, "export.paseo");
+    await writeFile(filePath, JSON.stringify({ kind: "session" }));
+
+    await expect(readPaseoBundle(filePath)).resolves.toEqual({ path: filePath, kind: "session" });
+    await expect(readPaseoBundle(dir)).rejects.toThrow("Not a Paseo bundle");
+  });
+});
@@ -0,0 +1,43 @@
+import { stat } from "node:fs/promises";
+import { readGuardedFile } from "./file-read-guard.js";
+import { isRecord } from "../utils/is-record.js";
+
+// Bundles are JSON; anything this large is not one of ours.
+const MAX_BUNDLE_BYTES = 64 * 1024 * 1024;
+
+export const PASEO_BUNDLE_KINDS = ["session", "settings"] as const;
+
+export type PaseoBundleKind = (typeof PASEO_BUNDLE_KINDS)[number];
+
+export interface OpenedPaseoBundle {
+  path: string;
+  kind: PaseoBundleKind;
+}
+
+/** The import flow a `.paseo` document belongs to, from its top-level `kind`. */
+export function classifyPaseoBundle(raw: string): PaseoBundleKind | null {
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(raw);
+  } catch {
+    return null;
+  }
+  if (!isRecord(parsed)) {
+    return null;
+  }
+  const kind = parsed.kind;
+  return PASEO_BUNDLE_KINDS.find((entry) => entry === kind) ?? null;
+}
+
+export async function readPaseoBundle(filePath: string): Promise<OpenedPaseoBundle> {
+  const info = await stat(filePath);
+  if (!info.isFile()) {
+    throw new Error(`Not a Paseo bundle: ${filePath}`);
+  }
+  const bytes = await readGuardedFile(filePath, { maxBytes: MAX_BUNDLE_BYTES, allowBinary: false });
+  const kind = classifyPaseoBundle(bytes.toString("utf8"));
+  if (!kind) {
+    throw new Error(`Not a Paseo bundle: ${filePath}`);
+  }
+  return { path: filePath, kind };
+}
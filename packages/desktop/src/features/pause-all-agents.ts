@@ -0,0 +1,65 @@
+import { globalShortcut, Notification } from "electron";
+import log from "electron-log/main";
+import {
+  interruptAllLocalAgents,
+  type InterruptAgentResult,
+} from "../daemon/agent-interrupts-electron.js";
+
+export interface PauseAllAgentsResult {
+  interrupted: number;
+  failed: number;
+  agents: InterruptAgentResult[];
+}
+
+let registeredAccelerator: string | null = null;
+
+export async function pauseAllAgents(): Promise<PauseAllAgentsResult> {
+  const agents = await interruptAllLocalAgents();
+  const failed = agents.filter((agent) => agent.status === "failed");
+  for (const agent of failed) {
+    log.warn("[pause-all] failed to interrupt agent", agent);
+  }
+  return { interrupted: agents.length - failed.length, failed: failed.length, agents };
+}
+
+function describeResult(result: PauseAllAgentsResult): string {
+  if (result.agents.length === 0) {
+    return "No agents were running.";
+  }
+  const interrupted = `Interrupted ${result.interrupted} of ${result.agents.length} agents.`;
+  return result.failed > 0 ? `${interrupted} ${result.failed} did not stop.` : interrupted;
+}
+
+/** Tray and shortcut entry point: nothing is on screen to return results to, so notify. */
+export async function pauseAllAgentsAndNotify(): Promise<void> {
+  try {
+    const result = await pauseAllAgents();
+    if (Notification.isSupported()) {
+      new Notification({ title: "Paused all agents", body: describeResult(result) }).show();
+    }
+  } catch (error) {
+    log.error("[pause-all] failed to pause agents", error);
+  }
+}
+
+export function applyPauseAllShortcut(accelerator: string | null): void {
+  if (registeredAccelerator === accelerator) {
+    return;
+  }
+  if (registeredAccelerator) {
+    globalShortcut.unregister(registeredAccelerator);
+    registeredAccelerator = null;
+  }
+  if (!accelerator) {
+    return;
+  }
+  try {
+    if (globalShortcut.register(accelerator, () => void pauseAllAgentsAndNotify())) {
+      registeredAccelerator = accelerator;
+    } else {
+      log.warn("[pause-all] shortcut is taken by another app", accelerator);
+    }
+  } catch (error) {
+    log.warn("[pause-all] invalid shortcut", accelerator, error);
+  }
+}
@@ -0,0 +1,107 @@
+import { app, BrowserWindow, dialog, type Session, type WebContents } from "electron";
+import log from "electron-log/main";
+import {
+  type BrokeredCapability,
+  createPermissionBroker,
+  normalizePermissionOrigin,
+  type PermissionBroker,
+  resolveRequestedCapabilities,
+} from "./permission-policy.js";
+
+const CAPABILITY_DESCRIPTIONS: Record<BrokeredCapability, string> = {
+  microphone: "use your microphone",
+  camera: "use your camera",
+  "clipboard-read": "read your clipboard",
+  notifications: "show notifications",
+};
+
+let permissionBroker: PermissionBroker | null = null;
+const brokeredSessions = new WeakSet<Session>();
+
+async function promptForPermission(request: {
+  capability: BrokeredCapability;
+  origin: string;
+}): Promise<boolean> {
+  const options: Electron.MessageBoxOptions = {
+    type: "question",
+    title: "Permission Request",
+    message: `Allow ${request.origin} to ${CAPABILITY_DESCRIPTIONS[request.capability]}?`,
+    detail: "Paseo remembers your answer for this site. You can change it in settings.",
+    buttons: ["Don't Allow", "Allow"],
+    defaultId: 0,
+    cancelId: 0,
+    noLink: true,
+  };
+  const win = BrowserWindow.getFocusedWindow();
+  const result = win
+    ? await dialog.showMessageBox(win, options)
+    : await dialog.showMessageBox(options);
+  return result.response === 1;
+}
+
+export function getPermissionBroker(): PermissionBroker {
+  permissionBroker ??= createPermissionBroker({
+    userDataPath: app.getPath("userData"),
+    prompt: promptForPermission,
+  });
+  return permissionBroker;
+}
+
+async function decideAll(capabilities: BrokeredCapability[], origin: string): Promise<boolean> {
+  const broker = getPermissionBroker();
+  for (const capability of capabilities) {
+    if (!(await broker.decide({ capability, origin }))) {
+      return false;
+    }
+  }
+  return true;
+}
+
+/**
+ * Routes a webview session's permission requests and checks through the
+ * persisted policy instead of each platform's own prompts. Each browser
+ * partition is its own session, so this runs once per attached webview.
+ */
+export function installWebviewPermissionBroker(contents: WebContents): void {
+  const webviewSession = contents.session;
+  if (brokeredSessions.has(webviewSession)) {
+    return;
+  }
+  brokeredSessions.add(webviewSession);
+  const broker = getPermissionBroker();
+  // Checks are synchronous, so the policy has to be in memory before the first one.
+  void broker.load();
+
+  webviewSession.setPermissionRequestHandler((requester, permission, callback, details) => {
+    const mediaTypes = "mediaTypes" in details ? (details.mediaTypes ?? []) : [];
+    const capabilities = resolveRequestedCapabilities(permission, mediaTypes);
+    if (capabilities === null || capabilities.length === 0) {
+      callback(capabilities !== null);
+      return;
+    }
+    const origin = normalizePermissionOrigin(details.requestingUrl ?? requester.getURL());
+    if (!origin) {
+      callback(false);
+      return;
+    }
+    decideAll(capabilities, origin).then(callback, (error) => {
+      log.warn(`[permissions] failed to decide ${permission} for ${origin}`, error);
+      callback(false);
+    });
+  });
+
+  webviewSession.setPermissionCheckHandler((_requester, permission, requestingOrigin, details) => {
+    const mediaType = "mediaType" in details ? details.mediaType : undefined;
+    const capabilities = resolveRequestedCapabilities(
+      permission,
+      mediaType && mediaType !== "unknown" ? [mediaType] : [],
+    );
+    if (capabilities === null) {
+      return false;
+    }
+    const origin = normalizePermissionOrigin(requestingOrigin);
+    return (
+      origin !== null && capabilities.every((capability) => broker.check({ capability, origin }))
+    );
+  });
+}
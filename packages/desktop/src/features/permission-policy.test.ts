@@ -0,0 +1,76 @@
+import { mkdtemp, rm } from "node:fs/promises";
+import { tmpdir } from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import {
+  createPermissionBroker,
+  normalizePermissionOrigin,
+  resolveRequestedCapabilities,
+} from "./permission-policy";
+
+describe("resolveRequestedCapabilities", () => {
+  it("maps Electron permissions onto brokered capabilities", () => {
+    expect(resolveRequestedCapabilities("media", ["audio"])).toEqual(["microphone"]);
+    expect(resolveRequestedCapabilities("media", ["audio", "video"])).toEqual([
+      "microphone",
+      "camera",
+    ]);
+    expect(resolveRequestedCapabilities("clipboard-read")).toEqual(["clipboard-read"]);
+    expect(resolveRequestedCapabilities("fullscreen")).toEqual([]);
+    expect(resolveRequestedCapabilities("geolocation")).toBeNull();
+  });
+
+  it("keys decisions on the origin only", () => {
+    expect(normalizePermissionOrigin("https://meet.example.com:8443/room?x=1")).toBe(
+      "https://meet.example.com:8443",
+    );
+    expect(normalizePermissionOrigin("data:text/html,hi")).toBeNull();
+  });
+});
+
+describe("createPermissionBroker", () => {
+  let userDataPath: string;
+
+  beforeEach(async () => {
+    userDataPath = await mkdtemp(path.join(tmpdir(), "paseo-permissions-"));
+  });
+
+  afterEach(async () => {
+    await rm(userDataPath, { recursive: true, force: true });
+  });
+
+  it("prompts once per origin and capability and remembers the answer", async () => {
+    const prompt = vi.fn(async () => true);
+    const broker = createPermissionBroker({ userDataPath, prompt });
+    const request = { capability: "microphone" as const, origin: "https://meet.example.com" };
+
+    const answers = await Promise.all([broker.decide(request), broker.decide(request)]);
+    expect(answers).toEqual([true, true]);
+    expect(prompt).toHaveBeenCalledTimes(1);
+    expect(broker.check(request)).toBe(true);
+    expect(broker.check({ ...request, origin: "https://other.example.com" })).toBe(false);
+
+    const reopened = createPermissionBroker({ userDataPath, prompt });
+    expect(await reopened.decide(request)).toBe(true);
+    expect(prompt).toHaveBeenCalledTimes(1);
+  });
+
+  it("skips prompts for capabilities set to allow or deny and can forget answers", async () => {
+    const prompt = vi.fn(async () => false);
+    const broker = createPermissionBroker({ userDataPath, prompt });
+    const origin = "https://meet.example.com";
+
+    await broker.setPolicy({ capability: "camera", mode: "deny" });
+    expect(await broker.decide({ capability: "camera", origin })).toBe(false);
+    expect(prompt).not.toHaveBeenCalled();
+
+    await broker.setPolicy({ capability: "notifications", origin, allowed: true });
+    expect(await broker.decide({ capability: "notifications", origin })).toBe(true);
+    const policy = await broker.setPolicy({ capability: "notifications", origin, allowed: null });
+    expect(policy.decisions).toEqual([]);
+    expect(await broker.decide({ capability: "notifications", origin })).toBe(false);
+    expect(prompt).toHaveBeenCalledTimes(1);
+
+    await expect(broker.setPolicy({ capability: "camera", mode: "sometimes" })).rejects.toThrow();
+  });
+});
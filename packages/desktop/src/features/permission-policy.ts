@@ -0,0 +1,264 @@
+import { readFile } from "node:fs/promises";
+import path from "node:path";
+import { updateJsonConfigFile } from "../settings/config-file.js";
+import { isRecord } from "../utils/is-record.js";
+
+const PERMISSION_POLICY_FILENAME = "webview-permissions.json";
+
+export const BROKERED_CAPABILITIES = [
+  "microphone",
+  "camera",
+  "clipboard-read",
+  "notifications",
+] as const;
+export type BrokeredCapability = (typeof BROKERED_CAPABILITIES)[number];
+
+export const CAPABILITY_MODES = ["ask", "allow", "deny"] as const;
+export type CapabilityMode = (typeof CAPABILITY_MODES)[number];
+
+// Harmless for any page; everything else that is not brokered is refused in webviews.
+const ALWAYS_ALLOWED_PERMISSIONS = new Set(["fullscreen", "clipboard-sanitized-write"]);
+
+export interface OriginPermissionDecision {
+  origin: string;
+  capability: BrokeredCapability;
+  allowed: boolean;
+  decidedAt: string;
+}
+
+export interface PermissionPolicy {
+  capabilities: Record<BrokeredCapability, CapabilityMode>;
+  /** Answers remembered from prompts for capabilities in `ask` mode. */
+  decisions: OriginPermissionDecision[];
+}
+
+interface PersistedPermissionPolicyDocument extends PermissionPolicy {
+  version: 1;
+}
+
+export interface PermissionBroker {
+  /** Prompts at most once per origin and capability while the capability is in `ask` mode. */
+  decide(input: { capability: BrokeredCapability; origin: string }): Promise<boolean>;
+  /** Synchronous answer for Chromium's permission queries from the loaded policy; never prompts. */
+  check(input: { capability: BrokeredCapability; origin: string }): boolean;
+  load(): Promise<PermissionPolicy>;
+  /**
+   * `{ capability, mode }` changes a capability's mode; `{ capability, origin,
+   * allowed }` records an answer, and `allowed: null` forgets it so the next
+   * request prompts again.
+   */
+  setPolicy(args: Record<string, unknown> | undefined): Promise<PermissionPolicy>;
+}
+
+export const DEFAULT_CAPABILITY_MODES: Record<BrokeredCapability, CapabilityMode> = {
+  microphone: "ask",
+  camera: "ask",
+  "clipboard-read": "ask",
+  notifications: "ask",
+};
+
+function isBrokeredCapability(value: unknown): value is BrokeredCapability {
+  return (BROKERED_CAPABILITIES as readonly unknown[]).includes(value);
+}
+
+function isCapabilityMode(value: unknown): value is CapabilityMode {
+  return (CAPABILITY_MODES as readonly unknown[]).includes(value);
+}
+
+/**
+ * Capabilities an Electron permission request needs, `[]` for permissions
+ * granted without asking, and `null` for permissions webviews never get.
+ */
+export function resolveRequestedCapabilities(
+  permission: string,
+  mediaTypes: readonly string[] = [],
+): BrokeredCapability[] | null {
+  if (ALWAYS_ALLOWED_PERMISSIONS.has(permission)) {
+    return [];
+  }
+  if (permission === "media") {
+    const capabilities: BrokeredCapability[] = [];
+    if (mediaTypes.includes("audio")) {
+      capabilities.push("microphone");
+    }
+    if (mediaTypes.includes("video")) {
+      capabilities.push("camera");
+    }
+    // A query without types (e.g. enumerateDevices labels) needs both.
+    return capabilities.length > 0 ? capabilities : ["microphone", "camera"];
+  }
+  if (permission === "clipboard-read" || permission === "notifications") {
+    return [permission];
+  }
+  return null;
+}
+
+/** Scheme, host and port only; decisions never key on paths. */
+export function normalizePermissionOrigin(value: string): string | null {
+  try {
+    const { origin } = new URL(value);
+    return origin === "null" ? null : origin;
+  } catch {
+    return null;
+  }
+}
+
+function coercePolicy(value: unknown): PermissionPolicy {
+  const capabilities = { ...DEFAULT_CAPABILITY_MODES };
+  const decisions: OriginPermissionDecision[] = [];
+  if (!isRecord(value)) {
+    return { capabilities, decisions };
+  }
+  if (isRecord(value.capabilities)) {
+    for (const capability of BROKERED_CAPABILITIES) {
+      const mode = value.capabilities[capability];
+      if (isCapabilityMode(mode)) {
+        capabilities[capability] = mode;
+      }
+    }
+  }
+  if (Array.isArray(value.decisions)) {
+    for (const entry of value.decisions) {
+      if (
+        isRecord(entry) &&
+        typeof entry.origin === "string" &&
+        isBrokeredCapability(entry.capability) &&
+        typeof entry.allowed === "boolean"
+      ) {
+        decisions.push({
+          origin: entry.origin,
+          capability: entry.capability,
+          allowed: entry.allowed,
+          decidedAt: typeof entry.decidedAt === "string" ? entry.decidedAt : "",
+        });
+      }
+    }
+  }
+  return { capabilities, decisions };
+}
+
+export function createPermissionBroker(input: {
+  userDataPath: string;
+  prompt: (request: { capability: BrokeredCapability; origin: string }) => Promise<boolean>;
+}): PermissionBroker {
+  const filePath = path.join(input.userDataPath, PERMISSION_POLICY_FILENAME);
+  const pendingPrompts = new Map<string, Promise<boolean>>();
+  let policy: PermissionPolicy = coercePolicy(null);
+  let loaded: Promise<void> | null = null;
+
+  async function load(): Promise<PermissionPolicy> {
+    loaded ??= readFile(filePath, "utf8")
+      .then((raw) => {
+        policy = coercePolicy(JSON.parse(raw));
+      })
+      .catch(() => {
+        policy = coercePolicy(null);
+      });
+    await loaded;
+    return policy;
+  }
+
+  async function update(mutate: (current: PermissionPolicy) => PermissionPolicy): Promise<void> {
+    const document = await updateJsonConfigFile(
+      filePath,
+      (raw): PersistedPermissionPolicyDocument => ({ version: 1, ...mutate(coercePolicy(raw)) }),
+    );
+    policy = { capabilities: document.capabilities, decisions: document.decisions };
+    loaded = Promise.resolve();
+  }
+
+  function findDecision(capability: BrokeredCapability, origin: string): boolean | null {
+    const decision = policy.decisions.find(
+      (entry) => entry.origin === origin && entry.capability === capability,
+    );
+    return decision ? decision.allowed : null;
+  }
+
+  function check(request: { capability: BrokeredCapability; origin: string }): boolean {
+    const mode = policy.capabilities[request.capability];
+    if (mode !== "ask") {
+      return mode === "allow";
+    }
+    return findDecision(request.capability, request.origin) === true;
+  }
+
+  function promptOnce(capability: BrokeredCapability, origin: string): Promise<boolean> {
+    const key = `${capability} ${origin}`;
+    const pending = pendingPrompts.get(key);
+    if (pending) {
+      return pending;
+    }
+    const prompt = (async () => {
+      const allowed = await input.prompt({ capability, origin });
+      await update((current) => ({
+        ...current,
+        decisions: [
+          ...current.decisions.filter(
+            (entry) => entry.origin !== origin || entry.capability !== capability,
+          ),
+          { origin, capability, allowed, decidedAt: new Date().toISOString() },
+        ],
+      }));
+      return allowed;
+    })().finally(() => {
+      pendingPrompts.delete(key);
+    });
+    pendingPrompts.set(key, prompt);
+    return prompt;
+  }
+
+  return {
+    async decide({ capability, origin }) {
+      const current = await load();
+      const mode = current.capabilities[capability];
+      if (mode !== "ask") {
+        return mode === "allow";
+      }
+      return findDecision(capability, origin) ?? (await promptOnce(capability, origin));
+    },
+
+    check,
+
+    async load() {
+      return structuredClone(await load());
+    },
+
+    async setPolicy(args) {
+      if (!isBrokeredCapability(args?.capability)) {
+        throw new Error(`Unknown capability: ${String(args?.capability)}`);
+      }
+      const capability = args.capability;
+      if (args.origin === undefined) {
+        const mode = args.mode;
+        if (!isCapabilityMode(mode)) {
+          throw new Error(`Permission mode must be one of ${CAPABILITY_MODES.join(", ")}.`);
+        }
+        await update((current) => ({
+          ...current,
+          capabilities: { ...current.capabilities, [capability]: mode },
+        }));
+        return structuredClone(policy);
+      }
+
+      const origin =
+        typeof args.origin === "string" ? normalizePermissionOrigin(args.origin) : null;
+      if (!origin) {
+        throw new Error(`Invalid origin: ${String(args.origin)}`);
+      }
+      if (args.allowed !== null && typeof args.allowed !== "boolean") {
+        throw new Error("allowed must be a boolean or null.");
+      }
+      const allowed = args.allowed;
+      await update((current) => {
+        const decisions = current.decisions.filter(
+          (entry) => entry.origin !== origin || entry.capability !== capability,
+        );
+        if (typeof allowed === "boolean") {
+          decisions.push({ origin, capability, allowed, decidedAt: new Date().toISOString() });
+        }
+        return { ...current, decisions };
+      });
+      return structuredClone(policy);
+    },
+  };
+}
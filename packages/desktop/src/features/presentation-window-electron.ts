@@ -0,0 +1,125 @@
+import { writeFile } from "node:fs/promises";
+import path from "node:path";
+import { app, BrowserWindow, session, type Session, type WebContents } from "electron";
+import log from "electron-log/main";
+import { listLabeledWindows, registerWindowLabel } from "../window/window-labels.js";
+import {
+  buildRedactionScript,
+  coercePresentationAgentId,
+  PRESENTATION_COMMANDS,
+  presentationRoute,
+  presentationWindowLabel,
+} from "./presentation-window.js";
+
+// In memory only: nothing the shared window loads outlives it or leaks into the main session.
+const PRESENTATION_PARTITION = "paseo-presentation";
+const REDACTION_SCRIPT_ID = "paseo-presentation-redaction";
+const REDACTION_SCRIPT_FILENAME = "presentation-redaction.js";
+const PRESENTATION_SIZE = { width: 960, height: 720 };
+
+interface PresentationWindowOptions {
+  preloadPath: string;
+  resolveAppUrl: (route: string) => string;
+  appScheme: string;
+  handleAppRequest: (request: Request) => Response | Promise<Response>;
+}
+
+let options: PresentationWindowOptions | null = null;
+let presentationSession: Promise<Session> | null = null;
+const presentationContents = new WeakSet<WebContents>();
+
+export function configurePresentationWindow(next: PresentationWindowOptions): void {
+  options = next;
+}
+
+/** True for IPC coming from a presentation window, which only gets the read-only commands. */
+export function isPresentationWebContents(contents: WebContents): boolean {
+  return presentationContents.has(contents);
+}
+
+export function assertPresentationCommandAllowed(contents: WebContents, command: string): void {
+  if (isPresentationWebContents(contents) && !PRESENTATION_COMMANDS.has(command)) {
+    throw new Error(`${command} is not available in a presentation window.`);
+  }
+}
+
+async function getPresentationSession(config: PresentationWindowOptions): Promise<Session> {
+  presentationSession ??= (async () => {
+    const partition = session.fromPartition(PRESENTATION_PARTITION);
+    const scriptPath = path.join(app.getPath("userData"), REDACTION_SCRIPT_FILENAME);
+    await writeFile(scriptPath, buildRedactionScript(), "utf8");
+    partition.registerPreloadScript({
+      id: REDACTION_SCRIPT_ID,
+      type: "frame",
+      filePath: scriptPath,
+    });
+    partition.protocol.handle(config.appScheme, config.handleAppRequest);
+    partition.setPermissionRequestHandler((_contents, _permission, callback) => callback(false));
+    return partition;
+  })().catch((error: unknown) => {
+    presentationSession = null;
+    throw error;
+  });
+  return await presentationSession;
+}
+
+/** Keeps the window on its agent: other routes and sites are turned away. */
+function pinToAgentRoute(win: BrowserWindow, url: string): void {
+  const { pathname } = new URL(url);
+  const contents = win.webContents;
+  contents.setWindowOpenHandler(() => ({ action: "deny" }));
+  contents.on("will-navigate", (event, target) => {
+    if (target !== url) {
+      event.preventDefault();
+    }
+  });
+  contents.on("did-navigate-in-page", (_event, target) => {
+    if (new URL(target).pathname !== pathname) {
+      void contents.loadURL(url);
+    }
+  });
+}
+
+/**
+ * Opens a read-only window that shows one agent's output for screen sharing.
+ * It runs in its own session, so it shares no storage with the main window,
+ * and secrets are masked in the page before they are painted.
+ */
+export async function openPresentationWindow(
+  args: Record<string, unknown> | undefined,
+): Promise<{ windowLabel: string }> {
+  if (!options) {
+    throw new Error("Presentation windows are not available yet.");
+  }
+  const agentId = coercePresentationAgentId(args);
+  const windowLabel = presentationWindowLabel(agentId);
+  const existing = listLabeledWindows().find((entry) => entry.label === windowLabel)?.win;
+  if (existing) {
+    existing.show();
+    existing.focus();
+    return { windowLabel };
+  }
+
+  const url = options.resolveAppUrl(presentationRoute(agentId));
+  const win = new BrowserWindow({
+    ...PRESENTATION_SIZE,
+    title: "Paseo presentation",
+    show: false,
+    webPreferences: {
+      session: await getPresentationSession(options),
+      preload: options.preloadPath,
+      contextIsolation: true,
+      nodeIntegration: false,
+      sandbox: true,
+      webviewTag: false,
+      devTools: !app.isPackaged,
+    },
+  });
+  presentationContents.add(win.webContents);
+  registerWindowLabel({ win, label: windowLabel });
+  pinToAgentRoute(win, url);
+  win.once("ready-to-show", () => win.show());
+  await win.loadURL(url);
+  log.info(`[presentation] opened a presentation window for ${agentId}`);
+  return { windowLabel };
+}
@@ -0,0 +1,116 @@
+export const PRESENTATION_WINDOW_LABEL_PREFIX = "presentation:";
+export const REDACTED_TEXT = "[redacted]";
+
+const AGENT_ID_PATTERN = /^[A-Za-z0-9._:-]{1,128}$/;
+
+/**
+ * Tokens worth hiding from a screen share. Each match is replaced whole; the
+ * assignment pattern uses a lookbehind so `API_KEY=` stays readable.
+ */
+export const SECRET_PATTERNS: readonly RegExp[] = [
+  /-----BEGIN [A-Z ]*PRIVATE KEY-----[\s\S]*?-----END [A-Z ]*PRIVATE KEY-----/g,
+  /\bsk-[A-Za-z0-9_-]{20,}/g,
+  /\bgh[pousr]_[A-Za-z0-9]{36,}/g,
+  /\bgithub_pat_[A-Za-z0-9_]{22,}/g,
+  /\bAKIA[0-9A-Z]{16}\b/g,
+  /\bxox[abprs]-[A-Za-z0-9-]{10,}/g,
+  /\bAIza[0-9A-Za-z_-]{35}/g,
+  /\beyJ[A-Za-z0-9_-]{10,}\.[A-Za-z0-9_-]{10,}\.[A-Za-z0-9_-]{10,}/g,
+  /(?<=\bBearer\s+)[A-Za-z0-9._~+/-]{20,}=*/g,
+  /(?<=\b(?:api[_-]?key|secret|token|password|passwd)["']?\s*[:=]\s*["']?)[^\s"',;]{8,}/gi,
+];
+
+/**
+ * Commands a presentation window may call: enough to boot the app and stream
+ * the agent's output, nothing that changes settings, files, or agents.
+ */
+export const PRESENTATION_COMMANDS: ReadonlySet<string> = new Set([
+  "desktop_get_runtime_info",
+  "desktop_daemon_status",
+  "get_desktop_settings",
+  "get_feature_flags",
+  "get_system_locale_info",
+  "get_time_info",
+  "get_accessibility_preferences",
+  "get_keyboard_layout",
+  "get_zoom_factor",
+  "get_scroll_profile",
+  "open_local_daemon_transport",
+  "send_local_daemon_transport_message",
+  "close_local_daemon_transport",
+]);
+
+export function coercePresentationAgentId(args: Record<string, unknown> | undefined): string {
+  const agentId = typeof args?.agentId === "string" ? args.agentId.trim() : "";
+  if (!AGENT_ID_PATTERN.test(agentId)) {
+    throw new Error("agentId is required.");
+  }
+  return agentId;
+}
+
+export function presentationWindowLabel(agentId: string): string {
+  return `${PRESENTATION_WINDOW_LABEL_PREFIX}${agentId}`;
+}
+
+export function presentationRoute(agentId: string): string {
+  return `/presentation/${encodeURIComponent(agentId)}`;
+}
+
+export function redactSecrets(text: string, patterns: readonly RegExp[] = SECRET_PATTERNS): string {
+  return patterns.reduce((result, pattern) => result.replace(pattern, REDACTED_TEXT), text);
+}
+
+/**
+ * Runs in the page before the app does and rewrites every text node and
+ * title that matches a secret pattern, including ones React renders later.
+ * Serialized into the init script, so it may only use its arguments.
+ */
+function installRedaction(sources: Array<[string, string]>, replacement: string): void {
+  const patterns = sources.map(([source, flags]) => new RegExp(source, flags));
+  const redact = (text: string) =>
+    patterns.reduce((result, pattern) => result.replace(pattern, replacement), text);
+  const redactNode = (node: Node) => {
+    if (node.nodeType === Node.TEXT_NODE) {
+      const next = redact(node.nodeValue ?? "");
+      if (next !== node.nodeValue) {
+        node.nodeValue = next;
+      }
+      return;
+    }
+    const walker = document.createTreeWalker(node, NodeFilter.SHOW_TEXT);
+    for (let text = walker.nextNode(); text; text = walker.nextNode()) {
+      redactNode(text);
+    }
+  };
+  const start = () => {
+    redactNode(document.documentElement);
+    new MutationObserver((mutations) => {
+      for (const mutation of mutations) {
+        if (mutation.type === "characterData") {
+          redactNode(mutation.target);
+        }
+        mutation.addedNodes.forEach(redactNode);
+      }
+      const title = redact(document.title);
+      if (title !== document.title) {
+        document.title = title;
+      }
+    }).observe(document.documentElement, {
+      childList: true,
+      characterData: true,
+      subtree: true,
+    });
+  };
+  if (document.documentElement) {
+    start();
+  } else {
+    document.addEventListener("readystatechange", start, { once: true });
+  }
+}
+
+export function buildRedactionScript(patterns: readonly RegExp[] = SECRET_PATTERNS): string {
+  const sources = patterns.map((pattern) => [pattern.source, pattern.flags]);
+  return `(${installRedaction.toString()})(${JSON.stringify(sources)}, ${JSON.stringify(
+    REDACTED_TEXT,
+  )});\n`;
+}
@@ -0,0 +1,131 @@
+import { BrowserWindow, shell } from "electron";
+import log from "electron-log/main";
+import { scalePollInterval } from "../system/power-status.js";
+import { listLabeledWindows, registerWindowLabel } from "../window/window-labels.js";
+import {
+  buildWaitingPageUrl,
+  coercePreviewUrl,
+  previewWindowLabel,
+  probePreviewServer,
+  type PreviewServerState,
+} from "./preview-window.js";
+
+// Separate from the app's session so dev server cookies and storage stay out of it.
+const PREVIEW_PARTITION = "paseo-preview";
+const PREVIEW_SIZE = { width: 1280, height: 860 };
+const HEALTH_POLL_INTERVAL_MS = 1_000;
+const HEALTH_PROBE_TIMEOUT_MS = 2_000;
+
+/**
+ * Follows the server's health: the waiting page while it is down, the site
+ * once it answers, and a reload of the last page each time it comes back.
+ */
+function watchPreviewServer(win: BrowserWindow, url: URL): void {
+  let state: PreviewServerState | null = null;
+  let timer: NodeJS.Timeout | null = null;
+  let lastPage = url.href;
+
+  const rememberPage = (_event: unknown, target: string) => {
+    if (URL.canParse(target) && new URL(target).origin === url.origin) {
+      lastPage = target;
+    }
+  };
+  win.webContents.on("did-navigate", rememberPage);
+  win.webContents.on("did-navigate-in-page", rememberPage);
+
+  const poll = async () => {
+    timer = null;
+    const next = await probePreviewServer(url, HEALTH_PROBE_TIMEOUT_MS);
+    if (win.isDestroyed()) {
+      return;
+    }
+    if (next !== state) {
+      state = next;
+      if (next === "up") {
+        void win.loadURL(lastPage).catch((error: unknown) => {
+          log.warn("[preview] failed to load", error);
+        });
+      } else {
+        void win.loadURL(buildWaitingPageUrl(url)).catch(() => undefined);
+      }
+    }
+    timer = setTimeout(() => void poll(), scalePollInterval(HEALTH_POLL_INTERVAL_MS));
+  };
+
+  win.once("closed", () => {
+    if (timer) {
+      clearTimeout(timer);
+    }
+  });
+  void poll();
+}
+
+/** Links to other origins open in the default browser instead of the preview. */
+function keepToOrigin(win: BrowserWindow, url: URL): void {
+  const contents = win.webContents;
+  const openElsewhere = (target: string): boolean => {
+    let parsed: URL;
+    try {
+      parsed = new URL(target);
+    } catch {
+      return true;
+    }
+    if (parsed.origin === url.origin || parsed.protocol === "data:") {
+      return false;
+    }
+    if (parsed.protocol === "http:" || parsed.protocol === "https:") {
+      void shell.openExternal(parsed.href);
+    }
+    return true;
+  };
+  contents.setWindowOpenHandler(({ url: target }) => {
+    openElsewhere(target);
+    return { action: "deny" };
+  });
+  contents.on("will-navigate", (event, target) => {
+    if (openElsewhere(target)) {
+      event.preventDefault();
+    }
+  });
+}
+
+/**
+ * Opens a dev server, usually one forwarded with `forward_port`, in its own
+ * window. The page gets no preload, so it cannot reach desktop commands.
+ */
+export async function openPreviewWindow(
+  args: Record<string, unknown> | undefined,
+): Promise<{ windowLabel: string }> {
+  const url = coercePreviewUrl(args);
+  const windowLabel = previewWindowLabel(url);
+  const existing = listLabeledWindows().find((entry) => entry.label === windowLabel)?.win;
+  if (existing) {
+    if (existing.webContents.getURL() !== url.href) {
+      await existing.loadURL(url.href).catch(() => undefined);
+    }
+    existing.show();
+    existing.focus();
+    return { windowLabel };
+  }
+
+  const win = new BrowserWindow({
+    ...PREVIEW_SIZE,
+    title: `Preview – ${url.host}`,
+    webPreferences: {
+      partition: PREVIEW_PARTITION,
+      contextIsolation: true,
+      nodeIntegration: false,
+      sandbox: true,
+      webviewTag: false,
+      devTools: true,
+    },
+  });
+  registerWindowLabel({ win, label: windowLabel });
+  keepToOrigin(win, url);
+  win.webContents.session.setPermissionRequestHandler((_contents, _permission, callback) =>
+    callback(false),
+  );
+  watchPreviewServer(win, url);
+  log.info(`[preview] opened ${url.origin}`);
+  return { windowLabel };
+}
@@ -0,0 +1,72 @@
+import http, { type Server } from "node:http";
+import type { AddressInfo } from "node:net";
+import { afterEach, describe, expect, it } from "vitest";
+import {
+  buildWaitingPageUrl,
+  coercePreviewUrl,
+  previewWindowLabel,
+  probePreviewServer,
+} from "./preview-window";
+
+describe("coercePreviewUrl", () => {
+  it("accepts http and https servers on this machine", () => {
+    expect(coercePreviewUrl({ url: "http://127.0.0.1:4000/app" }).href).toBe(
+      "http://127.0.0.1:4000/app",
+    );
+    expect(coercePreviewUrl({ url: "https://web.repo.localhost:6767" }).hostname).toBe(
+      "web.repo.localhost",
+    );
+    expect(coercePreviewUrl({ url: "http://[::1]:3000" }).port).toBe("3000");
+  });
+
+  it("rejects other hosts and schemes", () => {
+    expect(() => coercePreviewUrl({ url: "https://example.com" })).toThrow("this machine");
+    expect(() => coercePreviewUrl({ url: "file:///etc/passwd" })).toThrow("http and https");
+    expect(() => coercePreviewUrl({ url: "nope" })).toThrow("Not a valid URL");
+    expect(() => coercePreviewUrl({})).toThrow("url must be a string");
+  });
+});
+
+describe("preview windows", () => {
+  it("shares one label per origin", () => {
+    const label = previewWindowLabel(new URL("http://127.0.0.1:4000/a"));
+    expect(label).toMatch(/^preview:[0-9a-f]{16}$/);
+    expect(previewWindowLabel(new URL("http://127.0.0.1:4000/b?x=1"))).toBe(label);
+    expect(previewWindowLabel(new URL("http://127.0.0.1:4001/a"))).not.toBe(label);
+  });
+
+  it("escapes the origin on the waiting page", () => {
+    const page = decodeURIComponent(
+      buildWaitingPageUrl(new URL("http://127.0.0.1:4000")).split(",")[1]!,
+    );
+    expect(page).toContain("Waiting for server…");
+    expect(page).toContain("http://127.0.0.1:4000");
+  });
+});
+
+describe("probePreviewServer", () => {
+  let server: Server | null = null;
+
+  afterEach(async () => {
+    await new Promise<void>((resolve) => (server ? server.close(() => resolve()) : resolve()));
+    server = null;
+  });
+
+  async function serve(status: number): Promise<URL> {
+    server = http.createServer((_req, res) => res.writeHead(status).end());
+    await new Promise<void>((resolve) => server!.listen(0, "127.0.0.1", resolve));
+    return new URL(`http://127.0.0.1:${(server.address() as AddressInfo).port}/`);
+  }
+
+  it("counts any answer as up except gateway errors", async () => {
+    expect(await probePreviewServer(await serve(404), 1_000)).toBe("up");
+  });
+
+  it("keeps waiting on gateway errors and refused connections", async () => {
+    const url = await serve(502);
+    expect(await probePreviewServer(url, 1_000)).toBe("waiting");
+    await new Promise<void>((resolve) => server!.close(() => resolve()));
+    server = null;
+    expect(await probePreviewServer(url, 1_000)).toBe("waiting");
+  });
+});
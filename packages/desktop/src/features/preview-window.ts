@@ -0,0 +1,88 @@
+import { createHash } from "node:crypto";
+import http from "node:http";
+import https from "node:https";
+
+export const PREVIEW_WINDOW_LABEL_PREFIX = "preview:";
+// Gateway errors come from a proxy in front of a dev server that is not listening yet.
+const DOWN_STATUSES = new Set([502, 503, 504]);
+const LOOPBACK_HOSTS = new Set(["127.0.0.1", "localhost", "[::1]"]);
+
+export type PreviewServerState = "waiting" | "up";
+
+/**
+ * Previews are for dev servers on this machine, including ports forwarded
+ * from a daemon, so only http(s) URLs on a loopback host are accepted.
+ */
+export function coercePreviewUrl(args: Record<string, unknown> | undefined): URL {
+  if (typeof args?.url !== "string") {
+    throw new Error("url must be a string.");
+  }
+  let url: URL;
+  try {
+    url = new URL(args.url);
+  } catch {
+    throw new Error(`Not a valid URL: ${args.url}`);
+  }
+  if (url.protocol !== "http:" && url.protocol !== "https:") {
+    throw new Error("Previews only open http and https URLs.");
+  }
+  if (!LOOPBACK_HOSTS.has(url.hostname) && !url.hostname.endsWith(".localhost")) {
+    throw new Error("Previews only open servers on this machine.");
+  }
+  return url;
+}
+
+/** One preview window per origin; opening another path of it reuses the window. */
+export function previewWindowLabel(url: URL): string {
+  const digest = createHash("sha256").update(url.origin).digest("hex").slice(0, 16);
+  return `${PREVIEW_WINDOW_LABEL_PREFIX}${digest}`;
+}
+
+function escapeHtml(value: string): string {
+  return value.replace(/[&<>"']/g, (char) => `&#${char.charCodeAt(0)};`);
+}
+
+/** Shown in place of the site until the server answers. */
+export function buildWaitingPageUrl(url: URL): string {
+  const html = `<!doctype html>
+<html>
+<head>
+<meta charset="utf-8">
+<meta http-equiv="Content-Security-Policy" content="default-src 'none'; style-src 'unsafe-inline'">
+<title>Waiting for server…</title>
+<style>
+  :root { color-scheme: light dark; font-family: system-ui, sans-serif; }
+  body { margin: 0; height: 100vh; display: grid; place-content: center; text-align: center; }
+  p { margin: 4px; opacity: 0.7; }
+</style>
+</head>
+<body>
+<h3>Waiting for server…</h3>
+<p>${escapeHtml(url.origin)}</p>
+<p>The preview reloads as soon as it answers.</p>
+</body>
+</html>`;
+  return `data:text/html;charset=utf-8,${encodeURIComponent(html)}`;
+}
+
+/**
+ * Whether anything answers at `url`. Any HTTP status counts, so a 404 from a
+ * running server is up, except gateway errors from a proxy with nothing behind it.
+ */
+export function probePreviewServer(url: URL, timeoutMs: number): Promise<PreviewServerState> {
+  return new Promise((resolve) => {
+    const onResponse = (res: http.IncomingMessage) => {
+      res.resume();
+      resolve(DOWN_STATUSES.has(res.statusCode ?? 0) ? "waiting" : "up");
+    };
+    const options = { method: "HEAD", timeout: timeoutMs };
+    const req =
+      url.protocol === "https:"
+        ? // Dev servers commonly use self-signed certificates.
+          https.request(url, { ...options, rejectUnauthorized: false }, onResponse)
+        : http.request(url, options, onResponse);
+    req.on("timeout", () => req.destroy());
+    req.on("error", () => resolve("waiting"));
+    req.end();
+  });
+}
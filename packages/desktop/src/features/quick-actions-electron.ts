@@ -0,0 +1,95 @@
+import { stat } from "node:fs/promises";
+import { app, BrowserWindow, dialog } from "electron";
+import log from "electron-log/main";
+import { approveFromLink } from "../daemon/approval-gate-electron.js";
+import { emitDesktopEvent } from "../window/desktop-events.js";
+import {
+  describeNewAgentQuickAction,
+  parseQuickActionUrl,
+  type QuickAction,
+} from "./quick-actions.js";
+import { focusMainWindow } from "./status-popover.js";
+
+const QUICK_ACTION_EVENT = "quick-action";
+
+/**
+ * What reaches the renderer: only new-agent actions the user has already
+ * confirmed. Approvals are carried out here and never reach it.
+ */
+export type ConfirmedQuickAction = Extract<QuickAction, { kind: "new-agent" }>;
+
+let pendingActions: ConfirmedQuickAction[] = [];
+
+function hasLoadedWindow(): boolean {
+  return BrowserWindow.getAllWindows().some(
+    (win) => !win.isDestroyed() && !win.webContents.isLoadingMainFrame(),
+  );
+}
+
+async function showMessageBox(options: Electron.MessageBoxOptions) {
+  const win = BrowserWindow.getFocusedWindow() ?? BrowserWindow.getAllWindows()[0];
+  return win ? await dialog.showMessageBox(win, options) : await dialog.showMessageBox(options);
+}
+
+async function confirmQuickAction(url: string): Promise<ConfirmedQuickAction | null> {
+  const action = parseQuickActionUrl(url);
+  if (action.kind === "approve") {
+    const result = await approveFromLink(action);
+    log.info(`[quick-actions] approval ${result.requestId} ${result.approved ? "sent" : "denied"}`);
+    return null;
+  }
+
+  const info = await stat(action.cwd).catch(() => null);
+  if (!info?.isDirectory()) {
+    throw new Error(`Folder not found: ${action.cwd}`);
+  }
+  const { response } = await showMessageBox({
+    type: "question",
+    title: "Paseo Link",
+    ...describeNewAgentQuickAction(action),
+    buttons: ["Cancel", "Start Agent"],
+    defaultId: 0,
+    cancelId: 0,
+    noLink: true,
+  });
+  if (response !== 1) {
+    log.info("[quick-actions] cancelled");
+    return null;
+  }
+  return action;
+}
+
+/**
+ * Runs a `paseo://` launcher link after a native confirmation. Until a window
+ * has loaded, confirmed actions wait for `take_pending_quick_actions`.
+ */
+export function openQuickActionUrl(url: string): void {
+  void (async () => {
+    await app.whenReady();
+    const action = await confirmQuickAction(url);
+    if (!action) {
+      return;
+    }
+    log.info(`[quick-actions] running ${action.kind}`);
+    focusMainWindow();
+    if (hasLoadedWindow()) {
+      emitDesktopEvent(QUICK_ACTION_EVENT, action);
+    } else {
+      pendingActions.push(action);
+    }
+  })().catch(async (error: unknown) => {
+    log.warn("[quick-actions] ignoring link", error);
+    await showMessageBox({
+      type: "error",
+      title: "Paseo Link",
+      message: "This link could not be opened.",
+      detail: error instanceof Error ? error.message : String(error),
+    }).catch(() => undefined);
+  });
+}
+
+export function takePendingQuickActions(): ConfirmedQuickAction[] {
+  const actions = pendingActions;
+  pendingActions = [];
+  return actions;
+}
@@ -0,0 +1,75 @@
+import path from "node:path";
+import { describe, expect, it } from "vitest";
+import {
+  describeNewAgentQuickAction,
+  isQuickActionUrl,
+  parseQuickActionUrl,
+  parseQuickActionUrlsFromArgv,
+} from "./quick-actions";
+
+describe("quick actions", () => {
+  it("parses new-agent links", () => {
+    const cwd = path.resolve("/work/repo");
+    const url = `paseo://new-agent?cwd=${encodeURIComponent(cwd)}&prompt=Fix%20the%20build`;
+
+    expect(parseQuickActionUrl(url)).toEqual({
+      kind: "new-agent",
+      cwd,
+      prompt: "Fix the build",
+    });
+    expect(parseQuickActionUrl(`paseo://new-agent?cwd=${encodeURIComponent(cwd)}`)).toEqual({
+      kind: "new-agent",
+      cwd,
+      prompt: null,
+    });
+  });
+
+  it("parses approve links with and without an agent id", () => {
+    expect(parseQuickActionUrl("paseo://approve/perm-1")).toEqual({
+      kind: "approve",
+      requestId: "perm-1",
+      agentId: null,
+    });
+    expect(parseQuickActionUrl("paseo://approve/perm-1?agentId=agent-1")).toEqual({
+      kind: "approve",
+      requestId: "perm-1",
+      agentId: "agent-1",
+    });
+  });
+
+  it("rejects malformed links", () => {
+    expect(() => parseQuickActionUrl("paseo://new-agent?cwd=relative/dir")).toThrow(/absolute/);
+    expect(() => parseQuickActionUrl("paseo://new-agent")).toThrow(/absolute/);
+    expect(() => parseQuickActionUrl("paseo://approve")).toThrow(/one request id/);
+    expect(() => parseQuickActionUrl("paseo://approve/a/b")).toThrow(/one request id/);
+    expect(() => parseQuickActionUrl("paseo://approve/x?agentId=a%20b")).toThrow(/agent id/);
+    expect(() => parseQuickActionUrl("paseo://delete-everything")).toThrow(/Unknown/);
+    expect(() => parseQuickActionUrl("https://new-agent?cwd=/tmp")).toThrow(/paseo:/);
+    expect(() =>
+      parseQuickActionUrl(`paseo://new-agent?cwd=%2Ftmp&prompt=${"x".repeat(8001)}`),
+    ).toThrow(/limited/);
+  });
+
+  it("picks links out of argv but never the app's own pages", () => {
+    expect(isQuickActionUrl("paseo://app/settings")).toBe(false);
+    expect(
+      parseQuickActionUrlsFromArgv([
+        "/Applications/Paseo",
+        "--no-sandbox",
+        "paseo://approve/perm-1",
+        "paseo://app/",
+      ]),
+    ).toEqual(["paseo://approve/perm-1"]);
+  });
+
+  it("shows the folder and prompt in the confirmation", () => {
+    const { detail } = describeNewAgentQuickAction({
+      kind: "new-agent",
+      cwd: "/work/repo",
+      prompt: "Fix the build",
+    });
+
+    expect(detail).toContain("/work/repo");
+    expect(detail).toContain("Fix the build");
+  });
+});
@@ -0,0 +1,100 @@
+import path from "node:path";
+
+const QUICK_ACTION_PROTOCOL = "paseo:";
+// `paseo://app/...` serves the bundled renderer and is never a quick action.
+const APP_HOST = "app";
+const MAX_PROMPT_CHARS = 8_000;
+const ID_PATTERN = /^[A-Za-z0-9._:-]{1,128}$/;
+
+/**
+ * Launcher actions carried by `paseo://` links:
+ *
+ * - `paseo://new-agent?cwd=<absolute folder>&prompt=<text>`
+ * - `paseo://approve/<requestId>?agentId=<agentId>`; `agentId` may be left
+ *   out when the request is one the desktop app is already holding
+ */
+export type QuickAction =
+  | { kind: "new-agent"; cwd: string; prompt: string | null }
+  | { kind: "approve"; requestId: string; agentId: string | null };
+
+function coerceId(value: string | null, name: string): string {
+  const id = value?.trim() ?? "";
+  if (!ID_PATTERN.test(id)) {
+    throw new Error(`Quick action has an invalid ${name}.`);
+  }
+  return id;
+}
+
+export function isQuickActionUrl(value: string): boolean {
+  try {
+    const url = new URL(value);
+    return url.protocol === QUICK_ACTION_PROTOCOL && url.hostname !== APP_HOST;
+  } catch {
+    return false;
+  }
+}
+
+/** Throws with a message for the user when the link is malformed. */
+export function parseQuickActionUrl(value: string): QuickAction {
+  let url: URL;
+  try {
+    url = new URL(value);
+  } catch {
+    throw new Error(`Not a valid link: ${value}`);
+  }
+  if (url.protocol !== QUICK_ACTION_PROTOCOL) {
+    throw new Error(`Quick actions must use ${QUICK_ACTION_PROTOCOL}//.`);
+  }
+
+  if (url.hostname === "new-agent") {
+    const cwd = url.searchParams.get("cwd")?.trim() ?? "";
+    if (!path.isAbsolute(cwd)) {
+      throw new Error("new-agent needs an absolute cwd.");
+    }
+    const prompt = url.searchParams.get("prompt");
+    if (prompt !== null && prompt.length > MAX_PROMPT_CHARS) {
+      throw new Error(`The prompt is limited to ${MAX_PROMPT_CHARS} characters.`);
+    }
+    return {
+      kind: "new-agent",
+      cwd: path.resolve(cwd),
+      prompt: prompt?.trim() ? prompt : null,
+    };
+  }
+
+  if (url.hostname === "approve") {
+    const segments = url.pathname.split("/").filter((segment) => segment.length > 0);
+    if (segments.length !== 1) {
+      throw new Error("approve needs exactly one request id.");
+    }
+    const agentId = url.searchParams.get("agentId");
+    return {
+      kind: "approve",
+      requestId: coerceId(segments[0] ?? null, "request id"),
+      agentId: agentId === null ? null : coerceId(agentId, "agent id"),
+    };
+  }
+
+  throw new Error(`Unknown quick action: ${url.hostname || value}`);
+}
+
+/** Links passed on the command line on Windows and Linux, where the OS launches a new process. */
+export function parseQuickActionUrlsFromArgv(argv: string[]): string[] {
+  return argv.filter((arg) => isQuickActionUrl(arg));
+}
+
+/**
+ * Text for the native confirmation shown before an agent starts. Approvals
+ * use the approval prompt instead, which shows the command itself.
+ */
+export function describeNewAgentQuickAction(
+  action: Extract<QuickAction, { kind: "new-agent" }>,
+): { message: string; detail: string } {
+  return {
+    message: "Start a new agent?",
+    detail: [
+      `Folder: ${action.cwd}`,
+      action.prompt ? `Prompt:\n${action.prompt}` : "No prompt; the composer opens empty.",
+    ].join("\n\n"),
+  };
+}
@@ -0,0 +1,70 @@
+import { describe, expect, it } from "vitest";
+import {
+  buildReminderIcs,
+  buildRemindersScript,
+  coerceReminderRequest,
+} from "./reminder-content";
+
+const NOW = Date.parse("2026-10-16T12:00:00.000Z");
+
+describe("reminder requests", () => {
+  it("validates title, time, and deep link", () => {
+    expect(
+      coerceReminderRequest(
+        {
+          title: " Review output ",
+          when: "2026-10-17T09:00:00.000Z",
+          deepLink: "paseo://agent/a1",
+        },
+        NOW,
+      ),
+    ).toEqual({
+      title: "Review output",
+      when: new Date("2026-10-17T09:00:00.000Z"),
+      deepLink: "paseo://agent/a1",
+    });
+    expect(() => coerceReminderRequest({ title: "", when: NOW + 1 }, NOW)).toThrow("title");
+    expect(() => coerceReminderRequest({ title: "x", when: "soon" }, NOW)).toThrow("ISO date");
+    expect(() => coerceReminderRequest({ title: "x", when: NOW - 1 }, NOW)).toThrow("future");
+    expect(() =>
+      coerceReminderRequest({ title: "x", when: NOW + 1, deepLink: "https://x.test" }, NOW),
+    ).toThrow("paseo://");
+  });
+
+  it("builds a locale-independent Reminders script", () => {
+    const script = buildRemindersScript({
+      title: 'Check "build"',
+      when: new Date(2026, 1, 3, 9, 30, 15),
+      deepLink: "paseo://agent/a1",
+    });
+
+    expect(script).toContain("set year of dueDate to 2026");
+    expect(script).toContain("set month of dueDate to 2");
+    expect(script).toContain("set day of dueDate to 3");
+    expect(script).toContain(`set time of dueDate to ${9 * 3600 + 30 * 60 + 15}`);
+    expect(script).toContain(
+      'make new reminder with properties {name:"Check \\"build\\"", remind me date:dueDate, ' +
+        'body:"paseo://agent/a1"}',
+    );
+  });
+
+  it("builds an escaped, folded calendar event", () => {
+    const ics = buildReminderIcs({
+      request: {
+        title: `Review; agent, output ${"x".repeat(80)}`,
+        when: new Date("2026-10-17T09:00:00.000Z"),
+        deepLink: "paseo://agent/a1",
+      },
+      uid: "uid-1@paseo",
+      now: new Date(NOW),
+    });
+    const lines = ics.split("\r\n");
+
+    expect(lines).toContain("DTSTART:20261017T090000Z");
+    expect(lines).toContain("DTEND:20261017T091500Z");
+    expect(lines).toContain("URL:paseo://agent/a1");
+    expect(ics).toContain("SUMMARY:Review\\; agent\\, output");
+    expect(lines.every((line) => Buffer.byteLength(line) <= 75)).toBe(true);
+    expect(ics.endsWith("END:VCALENDAR\r\n")).toBe(true);
+  });
+});
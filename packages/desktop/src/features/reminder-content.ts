@@ -0,0 +1,143 @@
+const DEEP_LINK_PROTOCOL = "paseo:";
+const REMINDER_TITLE_MAX_LENGTH = 200;
+const CALENDAR_EVENT_DURATION_MS = 15 * 60 * 1000;
+// RFC 5545 caps content lines at 75 octets; continuation lines start with a space.
+const ICS_LINE_LIMIT = 75;
+
+export interface ReminderRequest {
+  title: string;
+  when: Date;
+  deepLink: string | null;
+}
+
+export function coerceReminderRequest(
+  args: Record<string, unknown> | undefined,
+  now: number = Date.now(),
+): ReminderRequest {
+  const title = typeof args?.title === "string" ? args.title.trim() : "";
+  if (!title) {
+    throw new Error("Reminder title is required.");
+  }
+  if (title.length > REMINDER_TITLE_MAX_LENGTH) {
+    throw new Error(`Reminder title must be at most ${REMINDER_TITLE_MAX_LENGTH} characters.`);
+  }
+
+  const when =
+    typeof args?.when === "string" || typeof args?.when === "number"
+      ? new Date(args.when)
+      : null;
+  if (!when || Number.isNaN(when.getTime())) {
+    throw new Error("Reminder time must be an ISO date or epoch milliseconds.");
+  }
+  if (when.getTime() <= now) {
+    throw new Error("Reminder time must be in the future.");
+  }
+
+  const rawDeepLink = args?.deepLink;
+  if (rawDeepLink !== undefined && rawDeepLink !== null && typeof rawDeepLink !== "string") {
+    throw new Error("Reminder deep link must be a string.");
+  }
+  let deepLink: string | null = null;
+  if (typeof rawDeepLink === "string" && rawDeepLink.trim()) {
+    let parsed: URL;
+    try {
+      parsed = new URL(rawDeepLink.trim());
+    } catch {
+      throw new Error(`Invalid reminder deep link: ${rawDeepLink}`);
+    }
+    if (parsed.protocol !== DEEP_LINK_PROTOCOL) {
+      throw new Error(`Reminder deep links must use ${DEEP_LINK_PROTOCOL}//.`);
+    }
+    deepLink = parsed.toString();
+  }
+  return { title, when, deepLink };
+}
+
+function escapeAppleScriptString(value: string): string {
+  return `"${value.replace(/\\/g, "\\\\").replace(/"/g, '\\"')}"`;
+}
+
+/**
+ * Creates the reminder through Reminders.app, which stores it in EventKit.
+ * The date is assembled from local components because AppleScript's date
+ * string parsing follows the user's region format.
+ */
+export function buildRemindersScript(request: ReminderRequest): string {
+  const { when } = request;
+  const secondsIntoDay = when.getHours() * 3600 + when.getMinutes() * 60 + when.getSeconds();
+  const properties = [
+    `name:${escapeAppleScriptString(request.title)}`,
+    "remind me date:dueDate",
+    ...(request.deepLink ? [`body:${escapeAppleScriptString(request.deepLink)}`] : []),
+  ];
+  return [
+    "set dueDate to current date",
+    // Reset the day first so month changes cannot overflow (e.g. Jan 31 -> Feb).
+    "set day of dueDate to 1",
+    `set year of dueDate to ${when.getFullYear()}`,
+    `set month of dueDate to ${when.getMonth() + 1}`,
+    `set day of dueDate to ${when.getDate()}`,
+    `set time of dueDate to ${secondsIntoDay}`,
+    'tell application "Reminders"',
+    `  make new reminder with properties {${properties.join(", ")}}`,
+    "end tell",
+  ].join("\n");
+}
+
+function escapeIcsText(value: string): string {
+  return value
+    .replace(/\\/g, "\\\\")
+    .replace(/;/g, "\\;")
+    .replace(/,/g, "\\,")
+    .replace(/\r?\n/g, "\\n");
+}
+
+function formatIcsDate(date: Date): string {
+  return date.toISOString().replace(/[-:]/g, "").replace(/\.\d{3}/, "");
+}
+
+function foldIcsLine(line: string): string {
+  const chunks: string[] = [];
+  let current = "";
+  for (const char of line) {
+    const limit = chunks.length === 0 ? ICS_LINE_LIMIT : ICS_LINE_LIMIT - 1;
+    if (Buffer.byteLength(current + char) > limit) {
+      chunks.push(current);
+      current = "";
+    }
+    current += char;
+  }
+  chunks.push(current);
+  return chunks.join("\r\n ");
+}
+
+/** A single calendar event with an alert at its start, for any calendar app. */
+export function buildReminderIcs(input: {
+  request: ReminderRequest;
+  uid: string;
+  now: Date;
+}): string {
+  const { request } = input;
+  const lines = [
+    "BEGIN:VCALENDAR",
+    "VERSION:2.0",
+    "PRODID:-//Paseo//Desktop//EN",
+    "BEGIN:VEVENT",
+    `UID:${input.uid}`,
+    `DTSTAMP:${formatIcsDate(input.now)}`,
+    `DTSTART:${formatIcsDate(request.when)}`,
+    `DTEND:${formatIcsDate(new Date(request.when.getTime() + CALENDAR_EVENT_DURATION_MS))}`,
+    `SUMMARY:${escapeIcsText(request.title)}`,
+    ...(request.deepLink
+      ? [`DESCRIPTION:${escapeIcsText(request.deepLink)}`, `URL:${request.deepLink}`]
+      : []),
+    "BEGIN:VALARM",
+    "ACTION:DISPLAY",
+    `DESCRIPTION:${escapeIcsText(request.title)}`,
+    "TRIGGER:PT0S",
+    "END:VALARM",
+    "END:VEVENT",
+    "END:VCALENDAR",
+  ];
+  return `${lines.map(foldIcsLine).join("\r\n")}\r\n`;
+}
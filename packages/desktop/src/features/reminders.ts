@@ -0,0 +1,47 @@
+import { execFile } from "node:child_process";
+import { randomUUID } from "node:crypto";
+import { mkdir, writeFile } from "node:fs/promises";
+import path from "node:path";
+import { promisify } from "node:util";
+import { app, shell } from "electron";
+import log from "electron-log/main";
+import {
+  buildReminderIcs,
+  buildRemindersScript,
+  coerceReminderRequest,
+  type ReminderRequest,
+} from "./reminder-content.js";
+
+const execFileAsync = promisify(execFile);
+
+async function openCalendarEvent(request: ReminderRequest): Promise<void> {
+  const uid = `${randomUUID()}@paseo`;
+  const dirPath = path.join(app.getPath("temp"), "paseo-reminders");
+  await mkdir(dirPath, { recursive: true });
+  const filePath = path.join(dirPath, `${uid}.ics`);
+  await writeFile(filePath, buildReminderIcs({ request, uid, now: new Date() }), "utf8");
+  const error = await shell.openPath(filePath);
+  if (error) {
+    throw new Error(`No calendar app could open the reminder: ${error}`);
+  }
+}
+
+/**
+ * Adds a reminder to Reminders.app on macOS. Elsewhere, or when Reminders
+ * access is denied, the default calendar app is handed an .ics event instead.
+ */
+export async function createReminder(
+  args: Record<string, unknown> | undefined,
+): Promise<{ method: "reminders" | "calendar-file" }> {
+  const request = coerceReminderRequest(args);
+  if (process.platform === "darwin") {
+    try {
+      await execFileAsync("osascript", ["-e", buildRemindersScript(request)]);
+      return { method: "reminders" };
+    } catch (error) {
+      log.warn("[reminders] Reminders.app rejected the reminder, using a calendar file", error);
+    }
+  }
+  await openCalendarEvent(request);
+  return { method: "calendar-file" };
+}
@@ -0,0 +1,104 @@
+import { mkdir, mkdtemp, readdir, rm, utimes, writeFile } from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+import { applyRetention, selectRetentionDeletions } from "./retention";
+
+const DAY_MS = 24 * 60 * 60 * 1000;
+const MB = 1024 * 1024;
+const NOW = Date.parse("2026-05-01T00:00:00.000Z");
+
+describe("selectRetentionDeletions", () => {
+  it("drops expired files and then the oldest until under the size cap", () => {
+    const files = [
+      { path: "new", bytes: 2 * MB, modifiedAt: NOW - DAY_MS },
+      { path: "middle", bytes: 2 * MB, modifiedAt: NOW - 2 * DAY_MS },
+      { path: "old", bytes: 2 * MB, modifiedAt: NOW - 3 * DAY_MS },
+      { path: "expired", bytes: 1, modifiedAt: NOW - 40 * DAY_MS },
+    ];
+
+    const selected = selectRetentionDeletions({
+      files,
+      policy: { maxAgeDays: 30, maxSizeMb: 5 },
+      now: NOW,
+    });
+
+    expect(selected.map((file) => [file.path, file.reason])).toEqual([
+      ["old", "size"],
+      ["expired", "age"],
+    ]);
+  });
+});
+
+describe("applyRetention", () => {
+  let dir: string;
+
+  beforeEach(async () => {
+    dir = await mkdtemp(path.join(os.tmpdir(), "paseo-retention-"));
+  });
+
+  afterEach(async () => {
+    await rm(dir, { recursive: true, force: true });
+  });
+
+  async function writeAged(name: string, ageDays: number): Promise<string> {
+    const filePath = path.join(dir, name);
+    await mkdir(path.dirname(filePath), { recursive: true });
+    await writeFile(filePath, "log line\n");
+    const time = new Date(NOW - ageDays * DAY_MS);
+    await utimes(filePath, time, time);
+    return filePath;
+  }
+
+  it("deletes matching expired files, keeps the live file and reports what it freed", async () => {
+    const live = await writeAged("main.log", 60);
+    await writeAged("main.old.log", 60);
+    await writeAged("nested/renderer.log", 60);
+    await writeAged("recent.log", 1);
+    await writeAged("notes.txt", 60);
+
+    const report = await applyRetention({
+      targets: [
+        {
+          name: "app_logs",
+          dir,
+          include: (name) => name.endsWith(".log"),
+          keep: [live],
+          recursive: true,
+        },
+      ],
+      policy: { maxAgeDays: 30, maxSizeMb: 100 },
+      now: new Date(NOW),
+    });
+
+    expect(report.deleted.map((entry) => path.relative(dir, entry.path)).sort()).toEqual([
+      "main.old.log",
+      path.join("nested", "renderer.log"),
+    ]);
+    expect(report.freedBytes).toBe(18);
+    expect((await readdir(dir)).sort()).toEqual(["main.log", "nested", "notes.txt", "recent.log"]);
+  });
+
+  it("stays in the top-level directory unless recursive", async () => {
+    await writeAged("daemon.log.1", 60);
+    await writeAged("agents/agent.json", 60);
+
+    const report = await applyRetention({
+      targets: [{ name: "daemon_logs", dir }],
+      policy: { maxAgeDays: 30, maxSizeMb: 100 },
+      now: new Date(NOW),
+    });
+
+    expect(report.deleted.map((entry) => path.basename(entry.path))).toEqual(["daemon.log.1"]);
+  });
+
+  it("treats a missing directory as empty", async () => {
+    const report = await applyRetention({
+      targets: [{ name: "crash_reports", dir: path.join(dir, "missing") }],
+      policy: { maxAgeDays: 1, maxSizeMb: 1 },
+    });
+
+    expect(report.deleted).toEqual([]);
+    expect(report.errors).toEqual([]);
+  });
+});
@@ -0,0 +1,125 @@
+import type { Dirent } from "node:fs";
+import { readdir, rm, stat } from "node:fs/promises";
+import path from "node:path";
+
+const DAY_MS = 24 * 60 * 60 * 1000;
+const MB = 1024 * 1024;
+
+export interface RetentionPolicy {
+  maxAgeDays: number;
+  /** Per target; oldest files go first once a target grows past it. */
+  maxSizeMb: number;
+}
+
+export interface RetentionTarget {
+  name: string;
+  dir: string;
+  /** Only files whose base name matches are managed; defaults to every file. */
+  include?: (fileName: string) => boolean;
+  /** Absolute paths that are never deleted, e.g. the log file currently being written. */
+  keep?: string[];
+  /** Descend into subdirectories; off so a shared home directory is never walked. */
+  recursive?: boolean;
+}
+
+export interface RetentionDeletion {
+  target: string;
+  path: string;
+  bytes: number;
+  reason: "age" | "size";
+}
+
+export interface RetentionReport {
+  ranAt: string;
+  deleted: RetentionDeletion[];
+  freedBytes: number;
+  errors: Array<{ target: string; message: string }>;
+}
+
+export interface ManagedFile {
+  path: string;
+  bytes: number;
+  modifiedAt: number;
+}
+
+async function listFiles(dir: string, recursive: boolean): Promise<ManagedFile[]> {
+  let entries: Dirent[];
+  try {
+    entries = await readdir(dir, { withFileTypes: true });
+  } catch {
+    return [];
+  }
+  const files: ManagedFile[] = [];
+  for (const entry of entries) {
+    const entryPath = path.join(dir, entry.name);
+    if (entry.isDirectory() && recursive) {
+      files.push(...(await listFiles(entryPath, true)));
+    } else if (entry.isFile()) {
+      const info = await stat(entryPath);
+      files.push({ path: entryPath, bytes: info.size, modifiedAt: info.mtimeMs });
+    }
+  }
+  return files;
+}
+
+/** Files to delete: everything past `maxAgeDays`, then the oldest until under `maxSizeMb`. */
+export function selectRetentionDeletions(input: {
+  files: ManagedFile[];
+  policy: RetentionPolicy;
+  now: number;
+}): Array<ManagedFile & { reason: RetentionDeletion["reason"] }> {
+  const cutoff = input.now - input.policy.maxAgeDays * DAY_MS;
+  const newestFirst = [...input.files].sort((a, b) => b.modifiedAt - a.modifiedAt);
+  const selected: Array<ManagedFile & { reason: RetentionDeletion["reason"] }> = [];
+  let keptBytes = 0;
+  for (const file of newestFirst) {
+    if (file.modifiedAt < cutoff) {
+      selected.push({ ...file, reason: "age" });
+    } else if (keptBytes + file.bytes > input.policy.maxSizeMb * MB) {
+      selected.push({ ...file, reason: "size" });
+    } else {
+      keptBytes += file.bytes;
+    }
+  }
+  return selected;
+}
+
+export async function applyRetention(input: {
+  targets: RetentionTarget[];
+  policy: RetentionPolicy;
+  now?: Date;
+}): Promise<RetentionReport> {
+  const now = input.now ?? new Date();
+  const report: RetentionReport = {
+    ranAt: now.toISOString(),
+    deleted: [],
+    freedBytes: 0,
+    errors: [],
+  };
+  for (const target of input.targets) {
+    const keep = new Set((target.keep ?? []).map((entry) => path.resolve(entry)));
+    const files = (await listFiles(target.dir, target.recursive ?? false)).filter(
+      (file) =>
+        !keep.has(path.resolve(file.path)) &&
+        (target.include?.(path.basename(file.path)) ?? true),
+    );
+    for (const file of selectRetentionDeletions({ files, policy: input.policy, now: +now })) {
+      try {
+        await rm(file.path, { force: true });
+        report.deleted.push({
+          target: target.name,
+          path: file.path,
+          bytes: file.bytes,
+          reason: file.reason,
+        });
+        report.freedBytes += file.bytes;
+      } catch (error) {
+        report.errors.push({
+          target: target.name,
+          message: error instanceof Error ? error.message : String(error),
+        });
+      }
+    }
+  }
+  return report;
+}
@@ -0,0 +1,139 @@
+import { cp, mkdir, mkdtemp, realpath, rm, stat } from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import { app } from "electron";
+import log from "electron-log/main";
+import { runGit } from "./git-cli.js";
+import {
+  coerceScratchWorkspaceRequest,
+  createScratchWorkspaceStore,
+  isInsideScratchRoot,
+  type ScratchWorkspace,
+  type ScratchWorkspaceStore,
+  scratchExpiry,
+  selectExpiredScratchWorkspaces,
+} from "./scratch-workspaces.js";
+import { getWorkspaceTrustRegistry } from "./workspace-trust-electron.js";
+
+const SCRATCH_ROOT_DIRNAME = "paseo-scratch";
+const CLEANUP_STARTUP_DELAY_MS = 60_000;
+const CLEANUP_INTERVAL_MS = 60 * 60_000;
+// Build output and dependencies are regenerated by the agent; copying them is slow and huge.
+const SKIPPED_TEMPLATE_DIRS = new Set(["node_modules", "target", ".venv"]);
+
+let scratchWorkspaceStore: ScratchWorkspaceStore | null = null;
+
+function getScratchWorkspaceStore(): ScratchWorkspaceStore {
+  scratchWorkspaceStore ??= createScratchWorkspaceStore({
+    userDataPath: app.getPath("userData"),
+  });
+  return scratchWorkspaceStore;
+}
+
+/** Resolved, so trust decisions match the paths agents report (`/private/var` on macOS). */
+async function getScratchRoot(): Promise<string> {
+  const root = path.join(os.tmpdir(), SCRATCH_ROOT_DIRNAME);
+  await mkdir(root, { recursive: true });
+  return await realpath(root);
+}
+
+async function copyTemplate(source: string, dest: string): Promise<void> {
+  const info = await stat(source).catch(() => null);
+  if (!info?.isDirectory()) {
+    throw new Error(`Template folder not found: ${source}`);
+  }
+  await cp(source, dest, {
+    recursive: true,
+    verbatimSymlinks: true,
+    filter: (entry) => !SKIPPED_TEMPLATE_DIRS.has(path.basename(entry)),
+  });
+}
+
+/**
+ * Makes a throwaway folder under the temp directory and trusts it, so agents
+ * can run there without a prompt. It is deleted once `retentionHours` pass.
+ */
+export async function createScratchWorkspace(
+  args: Record<string, unknown> | undefined,
+): Promise<ScratchWorkspace> {
+  const request = coerceScratchWorkspaceRequest(args);
+  const root = await getScratchRoot();
+  const workspacePath = await mkdtemp(path.join(root, "scratch-"));
+  try {
+    if (request.template.kind === "git") {
+      await runGit(["init", "--quiet"], { cwd: workspacePath });
+    } else if (request.template.kind === "copy") {
+      await copyTemplate(request.template.source, workspacePath);
+    }
+    const createdAt = new Date();
+    const workspace: ScratchWorkspace = {
+      path: workspacePath,
+      template: request.template,
+      createdAt: createdAt.toISOString(),
+      expiresAt: scratchExpiry(createdAt, request.retentionHours),
+    };
+    await getWorkspaceTrustRegistry().trust(workspacePath);
+    await getScratchWorkspaceStore().add(workspace);
+    log.info(`[scratch] created ${workspacePath} (${request.template.kind})`);
+    return workspace;
+  } catch (error) {
+    await rm(workspacePath, { recursive: true, force: true });
+    throw error;
+  }
+}
+
+export async function listScratchWorkspaces(): Promise<ScratchWorkspace[]> {
+  return await getScratchWorkspaceStore().list();
+}
+
+async function removeScratchWorkspace(root: string, workspace: ScratchWorkspace): Promise<void> {
+  if (isInsideScratchRoot(root, workspace.path)) {
+    await rm(workspace.path, { recursive: true, force: true });
+  } else {
+    log.warn(`[scratch] not deleting ${workspace.path}: outside ${root}`);
+  }
+  await getWorkspaceTrustRegistry().revoke(workspace.path);
+  await getScratchWorkspaceStore().remove(workspace.path);
+}
+
+export async function deleteScratchWorkspace(
+  args: Record<string, unknown> | undefined,
+): Promise<boolean> {
+  const workspacePath = typeof args?.path === "string" ? args.path : "";
+  const workspace = (await listScratchWorkspaces()).find((entry) => entry.path === workspacePath);
+  if (!workspace) {
+    return false;
+  }
+  await removeScratchWorkspace(await getScratchRoot(), workspace);
+  log.info(`[scratch] deleted ${workspace.path}`);
+  return true;
+}
+
+export async function cleanUpExpiredScratchWorkspaces(now = new Date()): Promise<string[]> {
+  const expired = selectExpiredScratchWorkspaces(await listScratchWorkspaces(), now);
+  if (expired.length === 0) {
+    return [];
+  }
+  const root = await getScratchRoot();
+  const deleted: string[] = [];
+  for (const workspace of expired) {
+    try {
+      await removeScratchWorkspace(root, workspace);
+      deleted.push(workspace.path);
+    } catch (error) {
+      log.warn(`[scratch] failed to delete ${workspace.path}`, error);
+    }
+  }
+  log.info(`[scratch] cleaned up ${deleted.length} expired workspaces`);
+  return deleted;
+}
+
+export function startScratchWorkspaceCleanup(): void {
+  const run = () => {
+    void cleanUpExpiredScratchWorkspaces().catch((error) => {
+      log.warn("[scratch] cleanup failed", error);
+    });
+  };
+  setTimeout(run, CLEANUP_STARTUP_DELAY_MS).unref();
+  setInterval(run, CLEANUP_INTERVAL_MS).unref();
+}
@@ -0,0 +1,80 @@
+import { mkdtemp, rm } from "node:fs/promises";
+import { tmpdir } from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+import {
+  coerceScratchWorkspaceRequest,
+  createScratchWorkspaceStore,
+  isInsideScratchRoot,
+  scratchExpiry,
+  selectExpiredScratchWorkspaces,
+} from "./scratch-workspaces";
+
+describe("scratch workspaces", () => {
+  let userDataPath: string;
+
+  beforeEach(async () => {
+    userDataPath = await mkdtemp(path.join(tmpdir(), "paseo-scratch-workspaces-"));
+  });
+
+  afterEach(async () => {
+    await rm(userDataPath, { recursive: true, force: true });
+  });
+
+  it("reads the template and retention period", () => {
+    const source = path.resolve("/work/template");
+
+    expect(coerceScratchWorkspaceRequest(undefined)).toEqual({
+      template: { kind: "empty" },
+      retentionHours: 24,
+    });
+    expect(coerceScratchWorkspaceRequest({ template: source, retentionHours: 2 })).toEqual({
+      template: { kind: "copy", source },
+      retentionHours: 2,
+    });
+    expect(() => coerceScratchWorkspaceRequest({ template: "relative/dir" })).toThrow("template");
+    expect(() => coerceScratchWorkspaceRequest({ retentionHours: 0 })).toThrow("retentionHours");
+  });
+
+  it("expires workspaces once their retention period passes", () => {
+    const createdAt = new Date("2026-10-16T08:00:00.000Z");
+    const workspace = {
+      path: "/tmp/paseo-scratch/scratch-a",
+      template: { kind: "git" as const },
+      createdAt: createdAt.toISOString(),
+      expiresAt: scratchExpiry(createdAt, 24),
+    };
+
+    expect(workspace.expiresAt).toBe("2026-10-17T08:00:00.000Z");
+    expect(selectExpiredScratchWorkspaces([workspace], new Date("2026-10-17T07:59:00Z"))).toEqual(
+      [],
+    );
+    expect(selectExpiredScratchWorkspaces([workspace], new Date("2026-10-17T08:00:00Z"))).toEqual(
+      [workspace],
+    );
+  });
+
+  it("only deletes direct children of the scratch root", () => {
+    const root = path.resolve("/tmp/paseo-scratch");
+
+    expect(isInsideScratchRoot(root, path.join(root, "scratch-a"))).toBe(true);
+    expect(isInsideScratchRoot(root, root)).toBe(false);
+    expect(isInsideScratchRoot(root, path.join(root, "scratch-a", "nested"))).toBe(false);
+    expect(isInsideScratchRoot(root, path.resolve("/home/me/repo"))).toBe(false);
+  });
+
+  it("persists created workspaces", async () => {
+    const workspace = {
+      path: "/tmp/paseo-scratch/scratch-a",
+      template: { kind: "empty" as const },
+      createdAt: "2026-10-16T08:00:00.000Z",
+      expiresAt: "2026-10-17T08:00:00.000Z",
+    };
+    await createScratchWorkspaceStore({ userDataPath }).add(workspace);
+
+    const reloaded = createScratchWorkspaceStore({ userDataPath });
+    expect(await reloaded.list()).toEqual([workspace]);
+    expect(await reloaded.remove(workspace.path)).toBe(true);
+    expect(await reloaded.list()).toEqual([]);
+  });
+});
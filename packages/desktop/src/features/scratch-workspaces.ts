@@ -0,0 +1,158 @@
+import { readFile } from "node:fs/promises";
+import path from "node:path";
+import { updateJsonConfigFile } from "../settings/config-file.js";
+import { isRecord } from "../utils/is-record.js";
+
+const SCRATCH_WORKSPACES_FILENAME = "scratch-workspaces.json";
+const HOUR_MS = 60 * 60 * 1000;
+export const DEFAULT_SCRATCH_RETENTION_HOURS = 24;
+const MAX_SCRATCH_RETENTION_HOURS = 30 * 24;
+
+/** An empty folder, an empty git repository, or a copy of an existing folder. */
+export type ScratchTemplate =
+  | { kind: "empty" }
+  | { kind: "git" }
+  | { kind: "copy"; source: string };
+
+export interface ScratchWorkspace {
+  path: string;
+  template: ScratchTemplate;
+  createdAt: string;
+  /** When cleanup deletes the folder. */
+  expiresAt: string;
+}
+
+export interface ScratchWorkspaceStore {
+  list(): Promise<ScratchWorkspace[]>;
+  add(workspace: ScratchWorkspace): Promise<void>;
+  remove(workspacePath: string): Promise<boolean>;
+}
+
+function coerceStoredTemplate(value: unknown): ScratchTemplate | null {
+  if (!isRecord(value)) {
+    return null;
+  }
+  if (value.kind === "empty" || value.kind === "git") {
+    return { kind: value.kind };
+  }
+  if (value.kind === "copy" && typeof value.source === "string") {
+    return { kind: "copy", source: value.source };
+  }
+  return null;
+}
+
+function coerceWorkspaces(value: unknown): ScratchWorkspace[] {
+  if (!isRecord(value) || !Array.isArray(value.workspaces)) {
+    return [];
+  }
+  return value.workspaces.flatMap((entry: unknown): ScratchWorkspace[] => {
+    if (
+      !isRecord(entry) ||
+      typeof entry.path !== "string" ||
+      typeof entry.createdAt !== "string" ||
+      typeof entry.expiresAt !== "string"
+    ) {
+      return [];
+    }
+    const template = coerceStoredTemplate(entry.template);
+    return template
+      ? [{ path: entry.path, template, createdAt: entry.createdAt, expiresAt: entry.expiresAt }]
+      : [];
+  });
+}
+
+/** `template` is `"empty"` (the default), `"git"`, or an absolute folder to copy. */
+export function coerceScratchWorkspaceRequest(args: Record<string, unknown> | undefined): {
+  template: ScratchTemplate;
+  retentionHours: number;
+} {
+  const value = args?.template;
+  let template: ScratchTemplate;
+  if (value === undefined || value === null || value === "empty") {
+    template = { kind: "empty" };
+  } else if (value === "git") {
+    template = { kind: "git" };
+  } else if (typeof value === "string" && path.isAbsolute(value.trim())) {
+    template = { kind: "copy", source: path.resolve(value.trim()) };
+  } else {
+    throw new Error('template must be "empty", "git", or an absolute folder path to copy.');
+  }
+
+  const hours = args?.retentionHours;
+  if (hours === undefined || hours === null) {
+    return { template, retentionHours: DEFAULT_SCRATCH_RETENTION_HOURS };
+  }
+  if (
+    typeof hours !== "number" ||
+    !Number.isFinite(hours) ||
+    hours <= 0 ||
+    hours > MAX_SCRATCH_RETENTION_HOURS
+  ) {
+    throw new Error(`retentionHours must be between 0 and ${MAX_SCRATCH_RETENTION_HOURS}.`);
+  }
+  return { template, retentionHours: hours };
+}
+
+export function scratchExpiry(createdAt: Date, retentionHours: number): string {
+  return new Date(createdAt.getTime() + retentionHours * HOUR_MS).toISOString();
+}
+
+export function selectExpiredScratchWorkspaces(
+  workspaces: ScratchWorkspace[],
+  now: Date,
+): ScratchWorkspace[] {
+  return workspaces.filter((workspace) => Date.parse(workspace.expiresAt) <= now.getTime());
+}
+
+/** Cleanup only ever deletes folders directly inside the scratch root. */
+export function isInsideScratchRoot(root: string, workspacePath: string): boolean {
+  const relative = path.relative(root, workspacePath);
+  return (
+    relative.length > 0 &&
+    !relative.startsWith("..") &&
+    !path.isAbsolute(relative) &&
+    !relative.includes(path.sep)
+  );
+}
+
+export function createScratchWorkspaceStore(input: {
+  userDataPath: string;
+}): ScratchWorkspaceStore {
+  const filePath = path.join(input.userDataPath, SCRATCH_WORKSPACES_FILENAME);
+  let loaded: Promise<ScratchWorkspace[]> | null = null;
+
+  async function load(): Promise<ScratchWorkspace[]> {
+    loaded ??= readFile(filePath, "utf8")
+      .then((raw) => coerceWorkspaces(JSON.parse(raw)))
+      .catch(() => []);
+    return await loaded;
+  }
+
+  async function update(
+    mutate: (current: ScratchWorkspace[]) => ScratchWorkspace[],
+  ): Promise<void> {
+    const { workspaces } = await updateJsonConfigFile(filePath, (raw) => ({
+      version: 1,
+      workspaces: mutate(coerceWorkspaces(raw)),
+    }));
+    loaded = Promise.resolve(workspaces);
+  }
+
+  return {
+    async list() {
+      return [...(await load())];
+    },
+    async add(workspace) {
+      await update((current) => [...current, workspace]);
+    },
+    async remove(workspacePath) {
+      let removed = false;
+      await update((current) => {
+        const next = current.filter((workspace) => workspace.path !== workspacePath);
+        removed = next.length < current.length;
+        return next;
+      });
+      return removed;
+    },
+  };
+}
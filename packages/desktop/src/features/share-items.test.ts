@@ -0,0 +1,28 @@
+import { describe, expect, it } from "vitest";
+import { coerceShareRequest, formatShareText, resolveShareMethod } from "./share-items";
+
+describe("share items", () => {
+  it("accepts single values or lists", () => {
+    expect(coerceShareRequest({ text: "hello", paths: ["/tmp/a.pdf", " "] })).toEqual({
+      texts: ["hello"],
+      filePaths: ["/tmp/a.pdf"],
+      urls: [],
+    });
+  });
+
+  it("rejects relative paths, bad URLs, and empty requests", () => {
+    expect(() => coerceShareRequest({ path: "notes.md" })).toThrow("absolute");
+    expect(() => coerceShareRequest({ url: "not a url" })).toThrow("Invalid share URL");
+    expect(() => coerceShareRequest({ text: "  " })).toThrow("Nothing to share.");
+  });
+
+  it("falls back to revealing files or copying text without a share sheet", () => {
+    const files = coerceShareRequest({ path: "/tmp/a.pdf", text: "see attached" });
+    const text = coerceShareRequest({ text: "hello", url: "paseo://agent/1" });
+
+    expect(resolveShareMethod({ platform: "darwin", request: files })).toBe("share-sheet");
+    expect(resolveShareMethod({ platform: "win32", request: files })).toBe("revealed");
+    expect(resolveShareMethod({ platform: "linux", request: text })).toBe("copied");
+    expect(formatShareText(text)).toBe("hello\npaseo://agent/1");
+  });
+});
@@ -0,0 +1,60 @@
+import path from "node:path";
+import { isRecord } from "../utils/is-record.js";
+
+export interface ShareRequest {
+  texts: string[];
+  filePaths: string[];
+  urls: string[];
+}
+
+/** How the items reached the user: the native sheet, or the fallback used without one. */
+export type ShareMethod = "share-sheet" | "revealed" | "copied";
+
+function stringList(value: unknown): string[] {
+  const values = Array.isArray(value) ? value : typeof value === "string" ? [value] : [];
+  return values
+    .filter((entry): entry is string => typeof entry === "string")
+    .map((entry) => entry.trim())
+    .filter((entry) => entry.length > 0);
+}
+
+export function coerceShareRequest(args: Record<string, unknown> | undefined): ShareRequest {
+  const input = isRecord(args) ? args : {};
+  const request: ShareRequest = {
+    texts: stringList(input.texts ?? input.text),
+    filePaths: stringList(input.paths ?? input.path),
+    urls: stringList(input.urls ?? input.url),
+  };
+  if (!request.filePaths.every((value) => path.isAbsolute(value))) {
+    throw new Error("Shared files must be absolute paths.");
+  }
+  for (const url of request.urls) {
+    try {
+      new URL(url);
+    } catch {
+      throw new Error(`Invalid share URL: ${url}`);
+    }
+  }
+  if (request.texts.length + request.filePaths.length + request.urls.length === 0) {
+    throw new Error("Nothing to share.");
+  }
+  return request;
+}
+
+/**
+ * Only macOS exposes a share sheet to Electron. Elsewhere files are revealed in
+ * the file manager, whose own Share entry takes over, and text is copied.
+ */
+export function resolveShareMethod(input: {
+  platform: NodeJS.Platform;
+  request: ShareRequest;
+}): ShareMethod {
+  if (input.platform === "darwin") {
+    return "share-sheet";
+  }
+  return input.request.filePaths.length > 0 ? "revealed" : "copied";
+}
+
+export function formatShareText(request: ShareRequest): string {
+  return [...request.texts, ...request.urls].join("\n");
+}
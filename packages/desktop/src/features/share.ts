@@ -0,0 +1,33 @@
+import { stat } from "node:fs/promises";
+import { BrowserWindow, clipboard, shell, ShareMenu } from "electron";
+import {
+  coerceShareRequest,
+  formatShareText,
+  resolveShareMethod,
+  type ShareMethod,
+} from "./share-items.js";
+
+export async function shareItems(input: {
+  args: Record<string, unknown> | undefined;
+  win: BrowserWindow;
+}): Promise<{ method: ShareMethod }> {
+  const request = coerceShareRequest(input.args);
+  await Promise.all(request.filePaths.map((filePath) => stat(filePath)));
+
+  const method = resolveShareMethod({ platform: process.platform, request });
+  if (method === "share-sheet") {
+    const menu = new ShareMenu({
+      texts: request.texts,
+      filePaths: request.filePaths,
+      urls: request.urls,
+    });
+    await new Promise<void>((resolve) => {
+      menu.popup({ window: input.win, callback: () => resolve() });
+    });
+  } else if (method === "revealed") {
+    shell.showItemInFolder(request.filePaths[0]!);
+  } else {
+    clipboard.writeText(formatShareText(request));
+  }
+  return { method };
+}
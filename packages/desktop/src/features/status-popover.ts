@@ -0,0 +1,190 @@
+import { app, BrowserWindow, screen } from "electron";
+import log from "electron-log/main";
+import type { DesktopSettings } from "../settings/desktop-settings.js";
+import { getDesktopSettingsStore } from "../settings/desktop-settings-electron.js";
+import { computePopoverPosition, type ScreenRect } from "../window/popover-position.js";
+import { emitDesktopEvent } from "../window/desktop-events.js";
+import {
+  listLabeledWindows,
+  MAIN_WINDOW_LABEL,
+  registerWindowLabel,
+} from "../window/window-labels.js";
+import { pauseAllAgentsAndNotify } from "./pause-all-agents.js";
+import { destroyTray, ensureTray, getTray } from "./tray.js";
+
+export const STATUS_POPOVER_WINDOW_LABEL = "status-popover";
+
+const STATUS_POPOVER_ROUTE = "/status-popover";
+const STATUS_POPOVER_SIZE = { width: 360, height: 420 };
+// Clicking the tray icon blurs the open popover before the click lands; without
+// this window the click would immediately reopen what the blur just hid.
+const REOPEN_AFTER_BLUR_GRACE_MS = 250;
+
+interface StatusPopoverOptions {
+  preloadPath: string;
+  resolveAppUrl: (route: string) => string;
+}
+
+let options: StatusPopoverOptions | null = null;
+let popover: BrowserWindow | null = null;
+let hiddenByBlurAt = 0;
+
+export function configureStatusPopover(next: StatusPopoverOptions): void {
+  options = next;
+}
+
+function createPopoverWindow(config: StatusPopoverOptions): BrowserWindow {
+  const win = new BrowserWindow({
+    ...STATUS_POPOVER_SIZE,
+    show: false,
+    frame: false,
+    resizable: false,
+    movable: false,
+    minimizable: false,
+    maximizable: false,
+    fullscreenable: false,
+    skipTaskbar: true,
+    alwaysOnTop: true,
+    // A panel takes key focus for the prompt box without activating the main window.
+    ...(process.platform === "darwin" ? { type: "panel" } : {}),
+    webPreferences: {
+      preload: config.preloadPath,
+      contextIsolation: true,
+      nodeIntegration: false,
+    },
+  });
+  registerWindowLabel({ win, label: STATUS_POPOVER_WINDOW_LABEL });
+  win.on("blur", () => {
+    if (!win.isDestroyed() && win.isVisible()) {
+      hiddenByBlurAt = Date.now();
+      win.hide();
+    }
+  });
+  win.once("closed", () => {
+    if (popover === win) {
+      popover = null;
+    }
+  });
+  void win.loadURL(config.resolveAppUrl(STATUS_POPOVER_ROUTE));
+  return win;
+}
+
+function resolveAnchor(): ScreenRect {
+  const bounds = getTray()?.getBounds();
+  // Linux trays report empty bounds; the cursor is where the click happened.
+  if (bounds && bounds.width > 0 && bounds.height > 0) {
+    return bounds;
+  }
+  const cursor = screen.getCursorScreenPoint();
+  return { x: cursor.x, y: cursor.y, width: 1, height: 1 };
+}
+
+export function toggleStatusPopover(): boolean {
+  if (popover && !popover.isDestroyed() && popover.isVisible()) {
+    popover.hide();
+    return false;
+  }
+  if (Date.now() - hiddenByBlurAt < REOPEN_AFTER_BLUR_GRACE_MS) {
+    return false;
+  }
+  if (!options) {
+    throw new Error("Status popover is not configured.");
+  }
+
+  popover ??= createPopoverWindow(options);
+  const anchor = resolveAnchor();
+  const { workArea } = screen.getDisplayMatching(anchor);
+  const { x, y } = computePopoverPosition({ anchor, size: STATUS_POPOVER_SIZE, workArea });
+  popover.setPosition(x, y);
+  popover.show();
+  return true;
+}
+
+/** Restores and raises the main window; `null` when it is not open. */
+export function focusMainWindow(): BrowserWindow | null {
+  const win = listLabeledWindows().find(({ label }) => label === MAIN_WINDOW_LABEL)?.win;
+  if (!win) {
+    return null;
+  }
+  if (win.isMinimized()) {
+    win.restore();
+  }
+  // An accessory app is not activated by showing a window, so it would open behind others.
+  if (process.platform === "darwin" && app.dock && !app.dock.isVisible()) {
+    app.focus({ steal: true });
+  }
+  win.show();
+  win.focus();
+  return win;
+}
+
+/** Brings the main window forward and asks it to show the agent picked in the tray menu. */
+function openAgentFromTray(agentId: string): void {
+  if (focusMainWindow()) {
+    emitDesktopEvent("tray-agent-selected", { agentId });
+  }
+}
+
+/** Hiding the Dock icon needs the menu bar icon; without either the app is unreachable. */
+export function resolveDockIconVisible(settings: DesktopSettings["statusPopover"]): boolean {
+  return settings.showDockIcon || !settings.enabled;
+}
+
+/**
+ * Switches between the regular and accessory activation policy. The focused
+ * window is brought back afterwards, since macOS deactivates the app when
+ * it leaves the Dock.
+ */
+async function applyDockIconVisibility(visible: boolean): Promise<void> {
+  if (process.platform !== "darwin" || !app.dock || app.dock.isVisible() === visible) {
+    return;
+  }
+  const focused = BrowserWindow.getFocusedWindow();
+  if (visible) {
+    await app.dock.show();
+    app.setActivationPolicy("regular");
+  } else {
+    app.setActivationPolicy("accessory");
+  }
+  if (focused && !focused.isDestroyed()) {
+    app.focus({ steal: true });
+    focused.show();
+  }
+  log.info(`[status-popover] Dock icon ${visible ? "shown" : "hidden"}`);
+}
+
+export function applyStatusPopoverSettings(settings: DesktopSettings["statusPopover"]): void {
+  void applyDockIconVisibility(resolveDockIconVisible(settings)).catch((error) => {
+    log.warn("[status-popover] failed to change the Dock icon", error);
+  });
+  if (settings.enabled) {
+    ensureTray({
+      onClick: () => void toggleStatusPopover(),
+      onPauseAllAgents: () => void pauseAllAgentsAndNotify(),
+      onOpenAgent: openAgentFromTray,
+    });
+    return;
+  }
+  destroyTray();
+  popover?.destroy();
+  popover = null;
+}
+
+/** Persists the choice; hiding is refused while the menu bar icon is off. */
+export async function setDockIconVisible(
+  args: Record<string, unknown> | undefined,
+): Promise<{ visible: boolean }> {
+  if (process.platform !== "darwin") {
+    throw new Error("The Dock icon only exists on macOS.");
+  }
+  if (typeof args?.visible !== "boolean") {
+    throw new Error("visible must be true or false.");
+  }
+  const store = getDesktopSettingsStore();
+  if (!args.visible && !(await store.get()).statusPopover.enabled) {
+    throw new Error("Turn on the menu bar icon before hiding the Dock icon.");
+  }
+  const settings = await store.patch({ statusPopover: { showDockIcon: args.visible } });
+  applyStatusPopoverSettings(settings.statusPopover);
+  return { visible: resolveDockIconVisible(settings.statusPopover) };
+}
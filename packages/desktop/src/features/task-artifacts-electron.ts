@@ -0,0 +1,90 @@
+import path from "node:path";
+import { BrowserWindow, dialog } from "electron";
+import log from "electron-log/main";
+import { observeLocalTransport } from "../daemon/local-transport.js";
+import { getDesktopSettingsStore } from "../settings/desktop-settings-electron.js";
+import { emitDesktopEvent } from "../window/desktop-events.js";
+import { runGit } from "./git-cli.js";
+import {
+  collectTaskArtifacts,
+  createTaskArtifactTracker,
+  type CollectedTaskArtifacts,
+  type TaskRecord,
+} from "./task-artifacts.js";
+
+const tracker = createTaskArtifactTracker();
+
+/** Tracked and staged changes against HEAD; `null` outside a repository or without changes. */
+async function readWorkspaceDiff(cwd: string): Promise<string | null> {
+  try {
+    const { stdout } = await runGit(["diff", "HEAD", "--binary"], { cwd });
+    return stdout.length > 0 ? stdout : null;
+  } catch {
+    return null;
+  }
+}
+
+async function collect(task: TaskRecord, outputsDir: string): Promise<CollectedTaskArtifacts> {
+  const result = await collectTaskArtifacts({ task, outputsDir, readDiff: readWorkspaceDiff });
+  log.info(`[task-artifacts] collected ${result.copiedFiles} files into ${result.path}`);
+  emitDesktopEvent("task-artifacts-collected", result);
+  return result;
+}
+
+function observeInbound(text: string): void {
+  const task = tracker.observeInbound(text);
+  if (!task) {
+    return;
+  }
+  void (async () => {
+    const { outputs } = await getDesktopSettingsStore().get();
+    if (outputs.collectOnCompletion && outputs.folder) {
+      await collect(task, outputs.folder);
+    }
+  })().catch((error: unknown) => {
+    log.warn(`[task-artifacts] failed to collect artifacts for ${task.agentId}`, error);
+  });
+}
+
+/** Collects each finished task's outputs when the settings ask for it. */
+export function startTaskArtifactCollection(): void {
+  observeLocalTransport({ inbound: observeInbound });
+}
+
+/**
+ * `collect_task_artifacts`: `{ agentId, dest? }`. `dest` defaults to the
+ * configured outputs folder and falls back to a picker; `null` means the
+ * picker was dismissed.
+ */
+export async function collectTaskArtifactsCommand(input: {
+  args: Record<string, unknown> | undefined;
+  win: BrowserWindow | null;
+}): Promise<CollectedTaskArtifacts | null> {
+  const agentId = input.args?.agentId;
+  if (typeof agentId !== "string" || agentId.length === 0) {
+    throw new Error("agentId must be a non-empty string.");
+  }
+  const dest = input.args?.dest;
+  if (dest !== undefined && (typeof dest !== "string" || !path.isAbsolute(dest))) {
+    throw new Error("dest must be an absolute path.");
+  }
+  const task = tracker.get(agentId);
+  if (!task) {
+    throw new Error(`No task seen for agent ${agentId} since the app started.`);
+  }
+  let outputsDir = dest ?? (await getDesktopSettingsStore().get()).outputs.folder;
+  if (outputsDir === null) {
+    const options: Electron.OpenDialogOptions = {
+      title: "Collect task outputs into",
+      properties: ["openDirectory", "createDirectory"],
+    };
+    const picked = input.win
+      ? await dialog.showOpenDialog(input.win, options)
+      : await dialog.showOpenDialog(options);
+    if (picked.canceled || !picked.filePaths[0]) {
+      return null;
+    }
+    outputsDir = picked.filePaths[0];
+  }
+  return await collect(task, outputsDir);
+}
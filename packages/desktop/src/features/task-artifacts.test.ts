@@ -0,0 +1,175 @@
+import { mkdir, mkdtemp, readFile, readdir, rm, writeFile } from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it } from "vitest";
+import {
+  artifactRelativePath,
+  collectTaskArtifacts,
+  createTaskArtifactTracker,
+  taskArtifactsFolderName,
+  type TaskRecord,
+} from "./task-artifacts";
+
+function sessionMessage(message: unknown): string {
+  return JSON.stringify({ type: "session", message });
+}
+
+function timeline(agentId: string, item: unknown): string {
+  return sessionMessage({
+    type: "agent_stream",
+    payload: { agentId, event: { type: "timeline", item } },
+  });
+}
+
+function turnEnded(agentId: string, type: string, timestamp: string): string {
+  return sessionMessage({
+    type: "agent_stream",
+    payload: { agentId, timestamp, event: { type } },
+  });
+}
+
+function agentUpsert(id: string, title: string, cwd: string): string {
+  return sessionMessage({
+    type: "agent_update",
+    payload: { kind: "upsert", agent: { id, title, cwd } },
+  });
+}
+
+describe("createTaskArtifactTracker", () => {
+  it("records the prompt, written files and final message of a finished task", () => {
+    const tracker = createTaskArtifactTracker();
+    tracker.observeInbound(agentUpsert("a1", "Fix login", "/repo"));
+    tracker.observeInbound(timeline("a1", { type: "user_message", text: " Fix the login form " }));
+    tracker.observeInbound(timeline("a1", { type: "assistant_message", text: "Looking" }));
+    tracker.observeInbound(
+      timeline("a1", {
+        type: "tool_call",
+        status: "completed",
+        detail: { type: "write", filePath: "src/login.ts", content: "x" },
+      }),
+    );
+    tracker.observeInbound(
+      timeline("a1", {
+        type: "tool_call",
+        status: "completed",
+        detail: { type: "edit", filePath: "/repo/src/login.ts", oldString: "x", newString: "y" },
+      }),
+    );
+    tracker.observeInbound(timeline("a1", { type: "assistant_message", text: "Done, " }));
+    tracker.observeInbound(timeline("a1", { type: "assistant_message", text: "fixed it." }));
+
+    expect(tracker.get("a1")?.outcome).toBeNull();
+    const finished = tracker.observeInbound(
+      turnEnded("a1", "turn_completed", "2026-10-16T12:00:00.000Z"),
+    );
+    expect(finished).toEqual({
+      agentId: "a1",
+      title: "Fix login",
+      cwd: "/repo",
+      prompt: "Fix the login form",
+      outcome: "completed",
+      endedAt: "2026-10-16T12:00:00.000Z",
+      files: ["/repo/src/login.ts"],
+      finalMessage: "Done, fixed it.",
+    });
+    expect(tracker.get("a1")).toEqual(finished);
+  });
+
+  it("keeps the last finished task while the next one runs", () => {
+    const tracker = createTaskArtifactTracker({ now: () => 0 });
+    tracker.observeInbound(timeline("a1", { type: "user_message", text: "first" }));
+    tracker.observeInbound(turnEnded("a1", "turn_failed", "not a date"));
+    tracker.observeInbound(timeline("a1", { type: "user_message", text: "second" }));
+
+    expect(tracker.get("a1")).toMatchObject({
+      prompt: "first",
+      outcome: "failed",
+      endedAt: "1970-01-01T00:00:00.000Z",
+    });
+    expect(tracker.get("missing")).toBeNull();
+  });
+});
+
+describe("naming", () => {
+  const task: TaskRecord = {
+    agentId: "a1",
+    title: "Fix: login/logout",
+    cwd: "/repo",
+    prompt: null,
+    outcome: "completed",
+    endedAt: null,
+    files: [],
+    finalMessage: null,
+  };
+
+  it("names the folder after the end time and the sanitized title", () => {
+    const at = new Date(2026, 9, 16, 14, 5, 9);
+    expect(taskArtifactsFolderName(task, at)).toBe("2026-10-16 14.05.09 Fix- login-logout");
+    expect(taskArtifactsFolderName({ ...task, title: null }, at)).toBe("2026-10-16 14.05.09 a1");
+  });
+
+  it("keeps workspace paths and sets aside files written elsewhere", () => {
+    expect(artifactRelativePath("/repo", "/repo/src/a.ts")).toBe(path.join("src", "a.ts"));
+    expect(artifactRelativePath("/repo", "/tmp/out.csv")).toBe(
+      path.join("_outside-workspace", "out.csv"),
+    );
+    expect(artifactRelativePath(null, "/tmp/out.csv")).toBe(
+      path.join("_outside-workspace", "out.csv"),
+    );
+  });
+});
+
+describe("collectTaskArtifacts", () => {
+  const directories = new Set<string>();
+
+  afterEach(async () => {
+    for (const directory of directories) {
+      await rm(directory, { recursive: true, force: true });
+    }
+    directories.clear();
+  });
+
+  it("copies files, the diff and a summary into a new folder each time", async () => {
+    const root = await mkdtemp(path.join(os.tmpdir(), "paseo-task-artifacts-"));
+    directories.add(root);
+    const workspace = path.join(root, "repo");
+    await mkdir(path.join(workspace, "src"), { recursive: true });
+    await writeFile(path.join(workspace, "src", "login.ts"), "export {};\n");
+    const task: TaskRecord = {
+      agentId: "a1",
+      title: "Fix login",
+      cwd: workspace,
+      prompt: "Fix the login form",
+      outcome: "completed",
+      endedAt: "2026-10-16T12:00:00.000Z",
+      files: [path.join(workspace, "src", "login.ts"), path.join(workspace, "gone.ts")],
+      finalMessage: "Done.",
+    };
+    const outputsDir = path.join(root, "Paseo Outputs");
+    const readDiff = async (cwd: string) => (cwd === workspace ? "diff --git a b\n" : null);
+
+    const first = await collectTaskArtifacts({ task, outputsDir, readDiff });
+    const second = await collectTaskArtifacts({ task, outputsDir, readDiff });
+
+    expect(first).toMatchObject({
+      agentId: "a1",
+      copiedFiles: 1,
+      skippedFiles: [{ path: path.join(workspace, "gone.ts"), reason: "missing" }],
+      hasDiff: true,
+    });
+    expect(second.path).toBe(`${first.path} (2)`);
+    expect((await readdir(first.path)).sort()).toEqual([
+      "changes.diff",
+      "files",
+      "summary.md",
+      "task.json",
+    ]);
+    expect(await readFile(path.join(first.path, "files", "src", "login.ts"), "utf8")).toBe(
+      "export {};\n",
+    );
+    const summary = await readFile(path.join(first.path, "summary.md"), "utf8");
+    expect(summary).toContain("# Fix login");
+    expect(summary).toContain("[src/login.ts](files/src/login.ts)");
+    expect(summary).toContain("not copied: missing");
+  });
+});
@@ -0,0 +1,326 @@
+import { copyFile, mkdir, stat, writeFile } from "node:fs/promises";
+import path from "node:path";
+import { measureFileChange, type ActivityTaskOutcome } from "./activity-journal.js";
+import { formatExportTimestamp, sanitizeFileNameSegment } from "./export-naming.js";
+import { isRecord } from "../utils/is-record.js";
+
+// Generated bundles and datasets are better left in the workspace than copied per task.
+const MAX_ARTIFACT_FILE_BYTES = 50 * 1024 * 1024;
+const OUTSIDE_WORKSPACE_DIR = "_outside-workspace";
+
+const TURN_OUTCOMES: Record<string, ActivityTaskOutcome> = {
+  turn_completed: "completed",
+  turn_failed: "failed",
+  turn_canceled: "canceled",
+};
+
+export interface TaskRecord {
+  agentId: string;
+  title: string | null;
+  cwd: string | null;
+  prompt: string | null;
+  /** `null` while the task is still running. */
+  outcome: ActivityTaskOutcome | null;
+  endedAt: string | null;
+  /** Absolute paths of files the agent wrote or edited, in first-touched order. */
+  files: string[];
+  /** The agent's last message, joined from its streamed chunks. */
+  finalMessage: string | null;
+}
+
+export interface TaskArtifactTracker {
+  /** The task `text` finished, if it finished one. */
+  observeInbound(text: string): TaskRecord | null;
+  /** The last finished task of `agentId`, or the running one when none has finished. */
+  get(agentId: string): TaskRecord | null;
+}
+
+export interface SkippedArtifact {
+  path: string;
+  reason: "missing" | "too-large" | "not-a-file";
+}
+
+export interface CollectedTaskArtifacts {
+  path: string;
+  agentId: string;
+  copiedFiles: number;
+  skippedFiles: SkippedArtifact[];
+  hasDiff: boolean;
+}
+
+function optionalString(value: unknown): string | null {
+  return typeof value === "string" ? value : null;
+}
+
+function parseSessionMessage(text: string): Record<string, unknown> | null {
+  try {
+    const parsed: unknown = JSON.parse(text);
+    if (isRecord(parsed) && parsed.type === "session" && isRecord(parsed.message)) {
+      return parsed.message;
+    }
+  } catch {
+    // not JSON
+  }
+  return null;
+}
+
+interface RunningTask {
+  record: TaskRecord;
+  assistantChunks: string[];
+  lastItemWasAssistant: boolean;
+}
+
+export function createTaskArtifactTracker(input: { now?: () => number } = {}): TaskArtifactTracker {
+  const now = input.now ?? (() => Date.now());
+  const agentInfo = new Map<string, { title: string | null; cwd: string | null }>();
+  const running = new Map<string, RunningTask>();
+  const finished = new Map<string, TaskRecord>();
+
+  function runningTask(agentId: string): RunningTask {
+    let task = running.get(agentId);
+    if (!task) {
+      const info = agentInfo.get(agentId);
+      task = {
+        record: {
+          agentId,
+          title: info?.title ?? null,
+          cwd: info?.cwd ?? null,
+          prompt: null,
+          outcome: null,
+          endedAt: null,
+          files: [],
+          finalMessage: null,
+        },
+        assistantChunks: [],
+        lastItemWasAssistant: false,
+      };
+      running.set(agentId, task);
+    }
+    return task;
+  }
+
+  function applyTimelineItem(agentId: string, item: Record<string, unknown>): void {
+    const task = runningTask(agentId);
+    if (item.type === "assistant_message" && typeof item.text === "string") {
+      // Providers stream one message as consecutive chunks.
+      if (!task.lastItemWasAssistant) {
+        task.assistantChunks = [];
+      }
+      task.assistantChunks.push(item.text);
+      task.lastItemWasAssistant = true;
+      return;
+    }
+    task.lastItemWasAssistant = false;
+    if (item.type === "user_message" && typeof item.text === "string") {
+      task.record.prompt ??= item.text.trim() || null;
+      return;
+    }
+    if (item.type !== "tool_call" || item.status !== "completed") {
+      return;
+    }
+    const change = measureFileChange(item.detail);
+    if (!change) {
+      return;
+    }
+    const cwd = task.record.cwd;
+    const filePath = cwd ? path.resolve(cwd, change.path) : change.path;
+    if (path.isAbsolute(filePath) && !task.record.files.includes(filePath)) {
+      task.record.files.push(filePath);
+    }
+  }
+
+  function finish(agentId: string, outcome: ActivityTaskOutcome, atMs: number): TaskRecord {
+    const task = runningTask(agentId);
+    running.delete(agentId);
+    const info = agentInfo.get(agentId);
+    const record: TaskRecord = {
+      ...task.record,
+      title: info?.title ?? task.record.title,
+      cwd: task.record.cwd ?? info?.cwd ?? null,
+      outcome,
+      endedAt: new Date(atMs).toISOString(),
+      finalMessage: task.assistantChunks.join("").trim() || null,
+    };
+    finished.set(agentId, record);
+    return record;
+  }
+
+  return {
+    observeInbound(text) {
+      const message = parseSessionMessage(text);
+      const payload = isRecord(message?.payload) ? message.payload : null;
+      if (!message || !payload) {
+        return null;
+      }
+      if (message.type === "agent_update") {
+        if (payload.kind === "remove" && typeof payload.agentId === "string") {
+          agentInfo.delete(payload.agentId);
+          running.delete(payload.agentId);
+          return null;
+        }
+        const agent = isRecord(payload.agent) ? payload.agent : null;
+        if (payload.kind === "upsert" && agent && typeof agent.id === "string") {
+          agentInfo.set(agent.id, {
+            title: optionalString(agent.title),
+            cwd: optionalString(agent.cwd),
+          });
+        }
+        return null;
+      }
+      const agentId = payload.agentId;
+      const event = payload.event;
+      if (message.type !== "agent_stream" || typeof agentId !== "string" || !isRecord(event)) {
+        return null;
+      }
+      const outcome = TURN_OUTCOMES[String(event.type)];
+      if (outcome) {
+        const parsedAt =
+          typeof payload.timestamp === "string" ? Date.parse(payload.timestamp) : NaN;
+        return finish(agentId, outcome, Number.isFinite(parsedAt) ? parsedAt : now());
+      }
+      if (event.type === "timeline" && isRecord(event.item)) {
+        applyTimelineItem(agentId, event.item);
+      }
+      return null;
+    },
+
+    get(agentId) {
+      const record = finished.get(agentId) ?? running.get(agentId)?.record ?? null;
+      return record ? structuredClone(record) : null;
+    },
+  };
+}
+
+/** `2026-10-16 14.05.09 Fix login`, after the task's end, or `at` while it runs. */
+export function taskArtifactsFolderName(task: TaskRecord, at: Date): string {
+  const endedAt = task.endedAt ? new Date(task.endedAt) : at;
+  const label = sanitizeFileNameSegment(task.title ?? "") || sanitizeFileNameSegment(task.agentId);
+  return [formatExportTimestamp(endedAt), label].filter((part) => part.length > 0).join(" ");
+}
+
+/**
+ * Where a produced file lands under `files/`: its path in the workspace, or
+ * its bare name in a separate folder when the agent wrote outside the workspace.
+ */
+export function artifactRelativePath(cwd: string | null, filePath: string): string {
+  if (cwd) {
+    const relative = path.relative(cwd, filePath);
+    if (relative && !relative.startsWith("..") && !path.isAbsolute(relative)) {
+      return relative;
+    }
+  }
+  return path.join(OUTSIDE_WORKSPACE_DIR, path.basename(filePath));
+}
+
+export function renderTaskSummary(
+  task: TaskRecord,
+  input: { copied: string[]; skipped: SkippedArtifact[]; hasDiff: boolean },
+): string {
+  const lines = [`# ${task.title ?? "Untitled agent"}`, ""];
+  lines.push(`- Agent: \`${task.agentId}\``);
+  if (task.cwd) {
+    lines.push(`- Workspace: \`${task.cwd}\``);
+  }
+  lines.push(`- Outcome: ${task.outcome ?? "still running"}`);
+  if (task.endedAt) {
+    lines.push(`- Ended: ${task.endedAt}`);
+  }
+  if (task.prompt) {
+    lines.push("", "## Task", "", task.prompt);
+  }
+  if (task.finalMessage) {
+    lines.push("", "## Final message", "", task.finalMessage);
+  }
+  lines.push("", "## Files", "");
+  if (input.copied.length === 0 && input.skipped.length === 0) {
+    lines.push("The agent did not write any files.");
+  }
+  for (const relative of input.copied) {
+    lines.push(`- [${relative}](files/${relative.split(path.sep).join("/")})`);
+  }
+  for (const skipped of input.skipped) {
+    lines.push(`- \`${skipped.path}\` (not copied: ${skipped.reason})`);
+  }
+  if (input.hasDiff) {
+    lines.push("", "The workspace diff at the end of the task is in `changes.diff`.");
+  }
+  return `${lines.join("\n")}\n`;
+}
+
+async function createUniqueFolder(parent: string, name: string): Promise<string> {
+  for (let attempt = 1; attempt < 100; attempt += 1) {
+    const candidate = path.join(parent, attempt === 1 ? name : `${name} (${attempt})`);
+    try {
+      await mkdir(candidate);
+      return candidate;
+    } catch (error) {
+      if ((error as NodeJS.ErrnoException).code !== "EEXIST") {
+        throw error;
+      }
+    }
+  }
+  throw new Error(`No free folder name for ${name} in ${parent}`);
+}
+
+/**
+ * Copies a task's files, its workspace diff and a summary into a new folder
+ * under `outputsDir`, so they outlive the workspace and the daemon's state.
+ * `readDiff` returns `null` when the workspace has no diff to offer.
+ */
+export async function collectTaskArtifacts(input: {
+  task: TaskRecord;
+  outputsDir: string;
+  readDiff: (cwd: string) => Promise<string | null>;
+  now?: Date;
+}): Promise<CollectedTaskArtifacts> {
+  const { task } = input;
+  await mkdir(input.outputsDir, { recursive: true });
+  const folder = await createUniqueFolder(
+    input.outputsDir,
+    taskArtifactsFolderName(task, input.now ?? new Date()),
+  );
+
+  const copied: string[] = [];
+  const skipped: SkippedArtifact[] = [];
+  for (const filePath of task.files) {
+    const info = await stat(filePath).catch(() => null);
+    if (!info) {
+      skipped.push({ path: filePath, reason: "missing" });
+      continue;
+    }
+    if (!info.isFile()) {
+      skipped.push({ path: filePath, reason: "not-a-file" });
+      continue;
+    }
+    if (info.size > MAX_ARTIFACT_FILE_BYTES) {
+      skipped.push({ path: filePath, reason: "too-large" });
+      continue;
+    }
+    const relative = artifactRelativePath(task.cwd, filePath);
+    const target = path.join(folder, "files", relative);
+    await mkdir(path.dirname(target), { recursive: true });
+    await copyFile(filePath, target);
+    copied.push(relative);
+  }
+
+  const diff = task.cwd ? await input.readDiff(task.cwd) : null;
+  const hasDiff = diff !== null && diff.length > 0;
+  if (hasDiff) {
+    await writeFile(path.join(folder, "changes.diff"), diff);
+  }
+  await writeFile(
+    path.join(folder, "summary.md"),
+    renderTaskSummary(task, { copied, skipped, hasDiff }),
+  );
+  await writeFile(
+    path.join(folder, "task.json"),
+    `${JSON.stringify({ ...task, copiedFiles: copied, skippedFiles: skipped }, null, 2)}\n`,
+  );
+  return {
+    path: folder,
+    agentId: task.agentId,
+    copiedFiles: copied.length,
+    skippedFiles: skipped,
+    hasDiff,
+  };
+}
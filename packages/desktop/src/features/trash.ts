@@ -0,0 +1,49 @@
+import { lstat } from "node:fs/promises";
+import path from "node:path";
+import { shell } from "electron";
+import { fromFileSystemPath } from "../system/windows-paths.js";
+import { resolveManagedAttachmentPath } from "./attachments.js";
+import { trashWithJournal } from "./operation-journal-electron.js";
+import { getWorkspaceTrustRegistry } from "./workspace-trust-electron.js";
+
+/**
+ * Sends a file or folder to the Trash / Recycle Bin, recorded in the
+ * operation journal for `undo_operation`. A missing path is already gone.
+ */
+export async function trashPath(filePath: string): Promise<void> {
+  try {
+    await lstat(filePath);
+  } catch {
+    return;
+  }
+  await trashWithJournal(filePath, (target) => shell.trashItem(target));
+}
+
+function isManagedAttachmentPath(filePath: string): boolean {
+  try {
+    resolveManagedAttachmentPath(filePath);
+    return true;
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * `move_to_trash`: recoverable removal for agent artifacts. Paths outside
+ * managed attachment storage must sit in a trusted workspace.
+ */
+export async function moveToTrash(input: { path?: unknown }): Promise<boolean> {
+  if (typeof input.path !== "string" || input.path.trim().length === 0) {
+    throw new Error("Path is required.");
+  }
+  const filePath = fromFileSystemPath(input.path.trim());
+  if (!path.isAbsolute(filePath)) {
+    throw new Error("Path to trash must be absolute.");
+  }
+  const resolved = path.resolve(filePath);
+  if (!isManagedAttachmentPath(resolved)) {
+    await getWorkspaceTrustRegistry().ensureTrusted(path.dirname(resolved));
+  }
+  await trashPath(resolved);
+  return true;
+}
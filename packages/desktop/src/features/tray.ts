@@ -0,0 +1,119 @@
+import path from "node:path";
+import { app, Menu, nativeImage, nativeTheme, Tray } from "electron";
+import {
+  extractGlyphCoverage,
+  getTrayGlyphColor,
+  renderTintedGlyph,
+  resolveTrayIconVariant,
+} from "../system/tray-icons.js";
+import { getAgentIcon, getAgentLabel } from "./agent-icons.js";
+import type { UnreadCounts } from "./unread-tracker.js";
+
+const TRAY_ICON_SIZE = 16;
+const TRAY_MENU_AGENT_LIMIT = 8;
+
+let tray: Tray | null = null;
+let unreadCounts: UnreadCounts = { total: 0, byAgent: {}, reported: 0 };
+
+function getTrayIconPath(): string {
+  if (app.isPackaged) {
+    return path.join(process.resourcesPath, "tray-icon.png");
+  }
+  return path.resolve(__dirname, "../../assets/32x32.png");
+}
+
+function isTaskbarDark(): boolean {
+  // Windows themes the taskbar separately from apps; elsewhere the app theme is the best signal.
+  return process.platform === "win32"
+    ? nativeTheme.shouldUseDarkColorsForSystemIntegratedUI
+    : nativeTheme.shouldUseDarkColors;
+}
+
+function createTrayIcon(): Electron.NativeImage {
+  const source = nativeImage.createFromPath(getTrayIconPath());
+  if (source.isEmpty()) {
+    return source;
+  }
+  const variant = resolveTrayIconVariant({
+    platform: process.platform,
+    darkTaskbar: isTaskbarDark(),
+  });
+  const color = getTrayGlyphColor(variant);
+  const icon = nativeImage.createEmpty();
+  for (const scaleFactor of [1, 2]) {
+    const size = TRAY_ICON_SIZE * scaleFactor;
+    const bitmap = source.resize({ width: size, height: size }).toBitmap();
+    icon.addRepresentation({
+      scaleFactor,
+      width: size,
+      height: size,
+      buffer: renderTintedGlyph({ coverage: extractGlyphCoverage(bitmap), color }),
+    });
+  }
+  icon.setTemplateImage(variant === "template");
+  return icon;
+}
+
+function trayTooltip(): string {
+  const { total } = unreadCounts;
+  return total > 0 ? `${app.getName()} — ${total} unread` : app.getName();
+}
+
+/** Agents waiting on the user, most unread first, each with its identity icon. */
+function buildUnreadAgentItems(
+  onOpenAgent: (agentId: string) => void,
+): Electron.MenuItemConstructorOptions[] {
+  const items = Object.entries(unreadCounts.byAgent)
+    .sort(([, a], [, b]) => b - a)
+    .slice(0, TRAY_MENU_AGENT_LIMIT)
+    .map(([agentId, count]): Electron.MenuItemConstructorOptions => ({
+      label: `${getAgentLabel(agentId) ?? "Agent"} (${count})`,
+      icon: getAgentIcon(agentId),
+      click: () => onOpenAgent(agentId),
+    }));
+  return items.length > 0 ? [...items, { type: "separator" }] : [];
+}
+
+function refreshTrayIcon(): void {
+  getTray()?.setImage(createTrayIcon());
+}
+
+export function getTray(): Tray | null {
+  return tray && !tray.isDestroyed() ? tray : null;
+}
+
+export function ensureTray(input: {
+  onClick: () => void;
+  onPauseAllAgents: () => void;
+  onOpenAgent: (agentId: string) => void;
+}): Tray {
+  const existing = getTray();
+  if (existing) {
+    return existing;
+  }
+  tray = new Tray(createTrayIcon());
+  tray.setToolTip(trayTooltip());
+  tray.on("click", input.onClick);
+  // Left click belongs to the popover, so the menu only opens on right click.
+  tray.on("right-click", () => {
+    getTray()?.popUpContextMenu(
+      Menu.buildFromTemplate([
+        ...buildUnreadAgentItems(input.onOpenAgent),
+        { label: "Pause All Agents", click: input.onPauseAllAgents },
+      ]),
+    );
+  });
+  nativeTheme.on("updated", refreshTrayIcon);
+  return tray;
+}
+
+export function setTrayUnreadCounts(counts: UnreadCounts): void {
+  unreadCounts = counts;
+  getTray()?.setToolTip(trayTooltip());
+}
+
+export function destroyTray(): void {
+  nativeTheme.off("updated", refreshTrayIcon);
+  getTray()?.destroy();
+  tray = null;
+}
@@ -0,0 +1,173 @@
+import { app, BrowserWindow, ipcMain, nativeImage } from "electron";
+import log from "electron-log/main";
+import { observeLocalTransport } from "../daemon/local-transport.js";
+import { parseHexColor } from "../system/agent-icons.js";
+import { formatBadgeLabel, renderBadgeBitmap, type RgbColor } from "../system/tray-icons.js";
+import { emitDesktopEvent } from "../window/desktop-events.js";
+import { getWindowLabel, listLabeledWindows } from "../window/window-labels.js";
+import { readBadgeCount } from "../window/window-manager.js";
+import { getAgentIdentity, onAgentIdentitiesChanged } from "./agent-icons.js";
+import { setTrayUnreadCounts } from "./tray.js";
+import { createUnreadTracker, type UnreadCounts } from "./unread-tracker.js";
+
+const WINDOWS_OVERLAY_ICON_SIZE = 16;
+
+const tracker = createUnreadTracker();
+const listeners = new Set<() => void>();
+
+/** Takes the agent's color when every unread update belongs to one agent. */
+function resolveBadgeBackground(counts: UnreadCounts): RgbColor | undefined {
+  const agentIds = Object.keys(counts.byAgent);
+  return agentIds.length === 1 && counts.reported === 0
+    ? (parseHexColor(getAgentIdentity(agentIds[0]!).color) ?? undefined)
+    : undefined;
+}
+
+/** Windows has no app badge; the taskbar overlay icon carries the count instead. */
+function applyWindowsBadgeOverlay(win: BrowserWindow, counts: UnreadCounts): void {
+  const badgeCount = counts.total;
+  const label = formatBadgeLabel(badgeCount);
+  if (!label) {
+    win.setOverlayIcon(null, "");
+    return;
+  }
+  const background = resolveBadgeBackground(counts);
+  const icon = nativeImage.createEmpty();
+  for (const scaleFactor of [1, 2]) {
+    const size = WINDOWS_OVERLAY_ICON_SIZE * scaleFactor;
+    icon.addRepresentation({
+      scaleFactor,
+      width: size,
+      height: size,
+      buffer: renderBadgeBitmap({ label, size, background }),
+    });
+  }
+  win.setOverlayIcon(icon, `${badgeCount} unread`);
+}
+
+function applyBadge(counts: UnreadCounts): void {
+  if (process.platform === "win32") {
+    for (const { win } of listLabeledWindows()) {
+      applyWindowsBadgeOverlay(win, counts);
+    }
+    return;
+  }
+  try {
+    app.setBadgeCount(counts.total);
+  } catch (error) {
+    log.warn("[unread] failed to update badge count", error);
+  }
+}
+
+function notifyListeners(): void {
+  for (const listener of listeners) {
+    listener();
+  }
+}
+
+/** Every indicator is redrawn from the tracker, so they cannot drift apart. */
+function publish(): void {
+  const counts = tracker.snapshot();
+  applyBadge(counts);
+  setTrayUnreadCounts(counts);
+  emitDesktopEvent("unread-counts-changed", counts);
+  notifyListeners();
+}
+
+function publishIf(changed: boolean): void {
+  if (changed) {
+    publish();
+  }
+}
+
+function focusedWindowLabel(): string | null {
+  const win = BrowserWindow.getFocusedWindow();
+  return win ? getWindowLabel(win) : null;
+}
+
+export function startUnreadIndicators(): void {
+  observeLocalTransport({
+    inbound: (text) => publishIf(tracker.observeInbound(text)),
+    outbound: (text) => publishIf(tracker.observeOutbound(text)),
+  });
+  onAgentIdentitiesChanged(() => {
+    applyBadge(tracker.snapshot());
+    notifyListeners();
+  });
+  app.on("browser-window-focus", (_event, win) => {
+    publishIf(tracker.focusWindow(getWindowLabel(win)));
+  });
+  app.on("browser-window-blur", () => {
+    // Focus moving to another Paseo window fires blur first, then focus.
+    setImmediate(() => {
+      if (!BrowserWindow.getFocusedWindow()) {
+        tracker.focusWindow(null);
+      }
+    });
+  });
+  app.on("browser-window-created", (_event, win) => {
+    if (process.platform === "win32") {
+      win.once("ready-to-show", () => {
+        applyWindowsBadgeOverlay(win, tracker.snapshot());
+      });
+    }
+    win.once("closed", () => {
+      // Runs before the label is released, since this listener was added first.
+      const label = getWindowLabel(win);
+      if (label) {
+        tracker.removeWindow(label);
+      }
+      notifyListeners();
+    });
+    notifyListeners();
+  });
+
+  ipcMain.handle("paseo:window:setViewedAgent", (event, agentId?: unknown) => {
+    const win = BrowserWindow.fromWebContents(event.sender);
+    const windowId = win ? getWindowLabel(win) : null;
+    if (!windowId) {
+      return;
+    }
+    const changed = tracker.setWindowAgent({
+      windowId,
+      agentId: typeof agentId === "string" && agentId.length > 0 ? agentId : null,
+      focused: windowId === focusedWindowLabel(),
+    });
+    if (changed) {
+      publish();
+    } else {
+      // The Window menu shows the viewed agent's count even when nothing was cleared.
+      notifyListeners();
+    }
+  });
+
+  ipcMain.handle("paseo:window:reportUnreadCount", (event, count?: unknown) => {
+    const win = BrowserWindow.fromWebContents(event.sender);
+    const windowId = win ? getWindowLabel(win) : null;
+    if (windowId) {
+      publishIf(tracker.reportWindowUnread({ windowId, count: readBadgeCount(count) }));
+    }
+  });
+}
+
+export function getUnreadCounts(): UnreadCounts {
+  return tracker.snapshot();
+}
+
+export function getWindowUnreadCount(win: BrowserWindow): number {
+  const label = getWindowLabel(win);
+  return label ? tracker.countForWindow(label) : 0;
+}
+
+export function getWindowAgentId(win: BrowserWindow): string | null {
+  const label = getWindowLabel(win);
+  return label ? tracker.agentForWindow(label) : null;
+}
+
+/** Fires whenever counts, the viewed agents, agent identities, or the set of windows change. */
+export function onUnreadIndicatorsChanged(listener: () => void): () => void {
+  listeners.add(listener);
+  return () => {
+    listeners.delete(listener);
+  };
+}
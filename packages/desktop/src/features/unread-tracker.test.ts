@@ -0,0 +1,103 @@
+import { describe, expect, it } from "vitest";
+import { createUnreadTracker } from "./unread-tracker";
+
+function agentUpdate(agent: Record<string, unknown>): string {
+  return JSON.stringify({
+    type: "session",
+    message: { type: "agent_update", payload: { kind: "upsert", agent } },
+  });
+}
+
+function attention(agentId: string, attentionTimestamp: string): string {
+  return agentUpdate({ id: agentId, requiresAttention: true, attentionTimestamp });
+}
+
+describe("createUnreadTracker", () => {
+  it("counts each new attention timestamp once", () => {
+    const tracker = createUnreadTracker();
+    expect(tracker.observeInbound(attention("a1", "t1"))).toBe(true);
+    expect(tracker.observeInbound(attention("a1", "t1"))).toBe(false);
+    expect(tracker.observeInbound(attention("a1", "t2"))).toBe(true);
+    expect(tracker.observeInbound(attention("a2", "t1"))).toBe(true);
+    expect(tracker.snapshot()).toEqual({ total: 3, byAgent: { a1: 2, a2: 1 }, reported: 0 });
+  });
+
+  it("clears when the daemon drops attention, the agent is removed, or the user clears it", () => {
+    const tracker = createUnreadTracker();
+    tracker.observeInbound(attention("a1", "t1"));
+    tracker.observeInbound(attention("a2", "t1"));
+    tracker.observeInbound(attention("a3", "t1"));
+
+    expect(tracker.observeInbound(agentUpdate({ id: "a1", requiresAttention: false }))).toBe(true);
+    expect(
+      tracker.observeInbound(
+        JSON.stringify({
+          type: "session",
+          message: { type: "agent_update", payload: { kind: "remove", agentId: "a2" } },
+        }),
+      ),
+    ).toBe(true);
+    expect(
+      tracker.observeOutbound(
+        JSON.stringify({
+          type: "session",
+          message: { type: "clear_agent_attention", agentId: ["a3"] },
+        }),
+      ),
+    ).toBe(true);
+    expect(tracker.snapshot()).toEqual({ total: 0, byAgent: {}, reported: 0 });
+  });
+
+  it("reads agents from directory pages", () => {
+    const tracker = createUnreadTracker();
+    tracker.observeInbound(
+      JSON.stringify({
+        type: "session",
+        message: {
+          type: "fetch_agents_response",
+          payload: {
+            entries: [
+              { agent: { id: "a1", requiresAttention: true, attentionTimestamp: "t1" } },
+              { agent: { id: "a2", requiresAttention: false } },
+            ],
+          },
+        },
+      }),
+    );
+    expect(tracker.snapshot().byAgent).toEqual({ a1: 1 });
+  });
+
+  it("clears an agent when a window showing it gains focus", () => {
+    const tracker = createUnreadTracker();
+    tracker.observeInbound(attention("a1", "t1"));
+    tracker.setWindowAgent({ windowId: "w2", agentId: "a1", focused: false });
+    expect(tracker.countForWindow("w2")).toBe(1);
+    expect(tracker.agentForWindow("w2")).toBe("a1");
+
+    expect(tracker.focusWindow("w2")).toBe(true);
+    expect(tracker.countForWindow("w2")).toBe(0);
+  });
+
+  it("does not count attention for an agent the focused window is showing", () => {
+    const tracker = createUnreadTracker();
+    tracker.setWindowAgent({ windowId: "main", agentId: "a1", focused: true });
+    expect(tracker.observeInbound(attention("a1", "t1"))).toBe(false);
+
+    tracker.focusWindow(null);
+    expect(tracker.observeInbound(attention("a1", "t2"))).toBe(true);
+    expect(tracker.snapshot().total).toBe(1);
+  });
+
+  it("adds what windows report for other hosts without double counting", () => {
+    const tracker = createUnreadTracker();
+    tracker.observeInbound(attention("a1", "t1"));
+
+    expect(tracker.reportWindowUnread({ windowId: "main", count: 2 })).toBe(true);
+    expect(tracker.reportWindowUnread({ windowId: "w2", count: 2 })).toBe(false);
+    expect(tracker.snapshot()).toEqual({ total: 3, byAgent: { a1: 1 }, reported: 2 });
+
+    tracker.removeWindow("main");
+    expect(tracker.reportWindowUnread({ windowId: "w2", count: 0 })).toBe(true);
+    expect(tracker.snapshot().total).toBe(1);
+  });
+});
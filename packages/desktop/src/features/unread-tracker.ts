@@ -0,0 +1,220 @@
+import { isRecord } from "../utils/is-record.js";
+
+export interface UnreadCounts {
+  total: number;
+  byAgent: Record<string, number>;
+  /**
+   * Workspaces needing attention on hosts a window reaches without the local
+   * transport, so their frames never pass through here. Reported by windows.
+   */
+  reported: number;
+}
+
+export interface UnreadTracker {
+  /** Returns true when any count changed. */
+  observeInbound(text: string): boolean;
+  observeOutbound(text: string): boolean;
+  /** The agent a window currently shows; a focused window reads it immediately. */
+  setWindowAgent(input: { windowId: string; agentId: string | null; focused: boolean }): boolean;
+  /**
+   * What a window counts for hosts outside the local transport. Windows see
+   * the same hosts, so the largest report is used rather than their sum.
+   */
+  reportWindowUnread(input: { windowId: string; count: number }): boolean;
+  /** `null` when no Paseo window has focus, so new attention counts as unread. */
+  focusWindow(windowId: string | null): boolean;
+  removeWindow(windowId: string): void;
+  countForWindow(windowId: string): number;
+  agentForWindow(windowId: string): string | null;
+  snapshot(): UnreadCounts;
+}
+
+interface AttentionSnapshot {
+  agentId: string;
+  requiresAttention: boolean;
+  attentionTimestamp: string | null;
+}
+
+function parseSessionMessage(text: string): Record<string, unknown> | null {
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(text);
+  } catch {
+    return null;
+  }
+  if (!isRecord(parsed) || parsed.type !== "session" || !isRecord(parsed.message)) {
+    return null;
+  }
+  return parsed.message;
+}
+
+function readAttentionSnapshot(agent: unknown): AttentionSnapshot | null {
+  if (!isRecord(agent) || typeof agent.id !== "string" || agent.id.length === 0) {
+    return null;
+  }
+  return {
+    agentId: agent.id,
+    requiresAttention: agent.requiresAttention === true,
+    attentionTimestamp:
+      typeof agent.attentionTimestamp === "string" ? agent.attentionTimestamp : null,
+  };
+}
+
+/** Agent snapshots carried by `agent_update` upserts and `fetch_agents_response` pages. */
+function readInboundSnapshots(message: Record<string, unknown>): {
+  snapshots: AttentionSnapshot[];
+  removedAgentId: string | null;
+} {
+  const payload = isRecord(message.payload) ? message.payload : null;
+  if (!payload) {
+    return { snapshots: [], removedAgentId: null };
+  }
+  if (message.type === "agent_update") {
+    if (payload.kind === "remove" && typeof payload.agentId === "string") {
+      return { snapshots: [], removedAgentId: payload.agentId };
+    }
+    const snapshot = payload.kind === "upsert" ? readAttentionSnapshot(payload.agent) : null;
+    return { snapshots: snapshot ? [snapshot] : [], removedAgentId: null };
+  }
+  if (message.type === "fetch_agents_response" && Array.isArray(payload.entries)) {
+    const snapshots = payload.entries
+      .map((entry: unknown) => readAttentionSnapshot(isRecord(entry) ? entry.agent : null))
+      .filter((snapshot): snapshot is AttentionSnapshot => snapshot !== null);
+    return { snapshots, removedAgentId: null };
+  }
+  return { snapshots: [], removedAgentId: null };
+}
+
+function readClearedAgentIds(message: Record<string, unknown>): string[] {
+  if (message.type !== "clear_agent_attention") {
+    return [];
+  }
+  const ids = Array.isArray(message.agentId) ? message.agentId : [message.agentId];
+  return ids.filter((id): id is string => typeof id === "string" && id.length > 0);
+}
+
+/**
+ * Unread counts per agent, fed by daemon frames. Each new attention timestamp
+ * counts once; the daemon clearing attention, or a focused window showing the
+ * agent, resets it to zero.
+ */
+export function createUnreadTracker(): UnreadTracker {
+  const counts = new Map<string, number>();
+  const lastAttention = new Map<string, string>();
+  const windowAgents = new Map<string, string>();
+  const reportedByWindow = new Map<string, number>();
+  let focusedWindowId: string | null = null;
+
+  function reportedCount(): number {
+    return Math.max(0, ...reportedByWindow.values());
+  }
+
+  function isBeingRead(agentId: string): boolean {
+    return focusedWindowId !== null && windowAgents.get(focusedWindowId) === agentId;
+  }
+
+  function clearAgent(agentId: string): boolean {
+    return counts.delete(agentId);
+  }
+
+  function applySnapshot(snapshot: AttentionSnapshot): boolean {
+    if (!snapshot.requiresAttention) {
+      lastAttention.delete(snapshot.agentId);
+      return clearAgent(snapshot.agentId);
+    }
+    const stamp = snapshot.attentionTimestamp ?? "";
+    if (lastAttention.get(snapshot.agentId) === stamp) {
+      return false;
+    }
+    lastAttention.set(snapshot.agentId, stamp);
+    if (isBeingRead(snapshot.agentId)) {
+      return false;
+    }
+    counts.set(snapshot.agentId, (counts.get(snapshot.agentId) ?? 0) + 1);
+    return true;
+  }
+
+  return {
+    observeInbound(text) {
+      const message = parseSessionMessage(text);
+      if (!message) {
+        return false;
+      }
+      const { snapshots, removedAgentId } = readInboundSnapshots(message);
+      if (removedAgentId) {
+        lastAttention.delete(removedAgentId);
+        return clearAgent(removedAgentId);
+      }
+      let changed = false;
+      for (const snapshot of snapshots) {
+        changed = applySnapshot(snapshot) || changed;
+      }
+      return changed;
+    },
+
+    observeOutbound(text) {
+      const message = parseSessionMessage(text);
+      if (!message) {
+        return false;
+      }
+      let changed = false;
+      for (const agentId of readClearedAgentIds(message)) {
+        changed = clearAgent(agentId) || changed;
+      }
+      return changed;
+    },
+
+    setWindowAgent({ windowId, agentId, focused }) {
+      if (agentId) {
+        windowAgents.set(windowId, agentId);
+      } else {
+        windowAgents.delete(windowId);
+      }
+      if (!focused) {
+        return false;
+      }
+      focusedWindowId = windowId;
+      return agentId ? clearAgent(agentId) : false;
+    },
+
+    reportWindowUnread({ windowId, count }) {
+      const before = reportedCount();
+      if (count > 0) {
+        reportedByWindow.set(windowId, count);
+      } else {
+        reportedByWindow.delete(windowId);
+      }
+      return reportedCount() !== before;
+    },
+
+    focusWindow(windowId) {
+      focusedWindowId = windowId;
+      const agentId = windowId === null ? undefined : windowAgents.get(windowId);
+      return agentId ? clearAgent(agentId) : false;
+    },
+
+    removeWindow(windowId) {
+      windowAgents.delete(windowId);
+      reportedByWindow.delete(windowId);
+      if (focusedWindowId === windowId) {
+        focusedWindowId = null;
+      }
+    },
+
+    countForWindow(windowId) {
+      const agentId = windowAgents.get(windowId);
+      return agentId ? (counts.get(agentId) ?? 0) : 0;
+    },
+
+    agentForWindow(windowId) {
+      return windowAgents.get(windowId) ?? null;
+    },
+
+    snapshot() {
+      const byAgent = Object.fromEntries(counts);
+      const reported = reportedCount();
+      const total = [...counts.values()].reduce((sum, count) => sum + count, reported);
+      return { total, byAgent, reported };
+    },
+  };
+}
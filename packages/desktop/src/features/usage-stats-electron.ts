@@ -0,0 +1,84 @@
+import { app, BrowserWindow, powerMonitor } from "electron";
+import log from "electron-log/main";
+import { observeLocalTransport } from "../daemon/local-transport.js";
+import {
+  coerceUsageRangeDays,
+  createUsageStatsStore,
+  extractUsageEvent,
+  type UsageStats,
+  type UsageStatsStore,
+} from "./usage-stats.js";
+
+// Focus time is checkpointed so a crash loses at most this much.
+const FOCUS_CHECKPOINT_INTERVAL_MS = 5 * 60_000;
+
+let usageStatsStore: UsageStatsStore | null = null;
+let focusedSince: number | null = null;
+
+function getUsageStatsStore(): UsageStatsStore {
+  usageStatsStore ??= createUsageStatsStore({ userDataPath: app.getPath("userData") });
+  return usageStatsStore;
+}
+
+function recordFocusUntilNow(): void {
+  if (focusedSince === null) {
+    return;
+  }
+  const startMs = focusedSince;
+  const endMs = Date.now();
+  focusedSince = BrowserWindow.getFocusedWindow() ? endMs : null;
+  void getUsageStatsStore()
+    .recordFocus({ startMs, endMs })
+    .catch((error) => {
+      log.warn("[usage-stats] failed to record focus time", error);
+    });
+}
+
+function recordOutboundUsage(text: string): void {
+  const event = extractUsageEvent(text);
+  if (!event) {
+    return;
+  }
+  void getUsageStatsStore()
+    .recordEvent(event)
+    .catch((error) => {
+      log.warn("[usage-stats] failed to record agent activity", error);
+    });
+}
+
+/**
+ * Counts the agent work the renderer starts, and time while any Paseo window
+ * has focus; moving between our own windows is not a gap.
+ */
+export function startUsageTracking(): void {
+  observeLocalTransport({ outbound: recordOutboundUsage });
+  app.on("browser-window-focus", () => {
+    focusedSince ??= Date.now();
+  });
+  app.on("browser-window-blur", () => {
+    // Focus moving to another Paseo window fires blur first, then focus.
+    setImmediate(() => {
+      if (!BrowserWindow.getFocusedWindow()) {
+        recordFocusUntilNow();
+      }
+    });
+  });
+  powerMonitor.on("lock-screen", recordFocusUntilNow);
+  powerMonitor.on("suspend", recordFocusUntilNow);
+  app.on("before-quit", recordFocusUntilNow);
+  setInterval(() => {
+    if (focusedSince !== null) {
+      recordFocusUntilNow();
+    }
+  }, FOCUS_CHECKPOINT_INTERVAL_MS).unref();
+  if (BrowserWindow.getFocusedWindow()) {
+    focusedSince = Date.now();
+  }
+}
+
+export async function getUsageStats(
+  args: Record<string, unknown> | undefined,
+): Promise<UsageStats> {
+  recordFocusUntilNow();
+  return await getUsageStatsStore().getStats({ days: coerceUsageRangeDays(args?.days) });
+}
@@ -0,0 +1,85 @@
+import { mkdtemp, rm } from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+import {
+  coerceUsageRangeDays,
+  createUsageStatsStore,
+  extractUsageEvent,
+  localDateKey,
+  splitByLocalDay,
+} from "./usage-stats";
+
+const HOUR_MS = 60 * 60 * 1000;
+
+function localTime(day: number, hour: number): number {
+  return new Date(2026, 4, day, hour).getTime();
+}
+
+describe("splitByLocalDay", () => {
+  it("splits focus that spans local midnight", () => {
+    expect(splitByLocalDay(localTime(1, 23), localTime(2, 1))).toEqual([
+      { date: "2026-05-01", ms: HOUR_MS },
+      { date: "2026-05-02", ms: HOUR_MS },
+    ]);
+  });
+});
+
+describe("extractUsageEvent", () => {
+  it("counts prompts and other agent-addressed messages", () => {
+    const frame = (message: Record<string, unknown>) =>
+      JSON.stringify({ type: "session", message });
+
+    expect(
+      extractUsageEvent(frame({ type: "send_agent_message_request", agentId: "a1", text: "hi" })),
+    ).toEqual({ kind: "prompt", agentId: "a1" });
+    expect(extractUsageEvent(frame({ type: "fetch_agent_request", agentId: "a2" }))).toEqual({
+      kind: "interaction",
+      agentId: "a2",
+    });
+    expect(extractUsageEvent(frame({ type: "ping" }))).toBeNull();
+    expect(extractUsageEvent("not json")).toBeNull();
+  });
+});
+
+describe("coerceUsageRangeDays", () => {
+  it("defaults to a week and rejects out-of-range values", () => {
+    expect(coerceUsageRangeDays(undefined)).toBe(7);
+    expect(coerceUsageRangeDays(30)).toBe(30);
+    expect(() => coerceUsageRangeDays(0)).toThrow();
+    expect(() => coerceUsageRangeDays(366)).toThrow("365");
+  });
+});
+
+describe("createUsageStatsStore", () => {
+  let dir: string;
+
+  beforeEach(async () => {
+    dir = await mkdtemp(path.join(os.tmpdir(), "paseo-usage-stats-"));
+  });
+
+  afterEach(async () => {
+    await rm(dir, { recursive: true, force: true });
+  });
+
+  it("aggregates per day and survives a restart", async () => {
+    const now = () => localTime(3, 12);
+    const store = createUsageStatsStore({ userDataPath: dir, now });
+    await store.recordFocus({ startMs: localTime(2, 9), endMs: localTime(2, 11) });
+    await store.recordEvent({ kind: "prompt", agentId: "a1" }, localTime(2, 10));
+    await store.recordEvent({ kind: "prompt", agentId: "a1" }, localTime(3, 10));
+    await store.recordEvent({ kind: "interaction", agentId: "a2" }, localTime(3, 11));
+
+    const reloaded = createUsageStatsStore({ userDataPath: dir, now });
+    const stats = await reloaded.getStats({ days: 3 });
+
+    expect(stats.from).toBe("2026-05-01");
+    expect(stats.to).toBe(localDateKey(now()));
+    expect(stats.days).toEqual([
+      { date: "2026-05-01", focusedMs: 0, promptsSent: 0, agentsInteracted: 0 },
+      { date: "2026-05-02", focusedMs: 2 * HOUR_MS, promptsSent: 1, agentsInteracted: 1 },
+      { date: "2026-05-03", focusedMs: 0, promptsSent: 1, agentsInteracted: 2 },
+    ]);
+    expect(stats.totals).toEqual({ focusedMs: 2 * HOUR_MS, promptsSent: 2, agentsInteracted: 2 });
+  });
+});
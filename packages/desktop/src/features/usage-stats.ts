@@ -0,0 +1,244 @@
+import { readFile } from "node:fs/promises";
+import path from "node:path";
+import { updateJsonConfigFile } from "../settings/config-file.js";
+import { isRecord } from "../utils/is-record.js";
+
+const USAGE_STATS_FILENAME = "usage-stats.json";
+const DAY_MS = 24 * 60 * 60 * 1000;
+// A little over a year, so a "last 365 days" view is always complete.
+const MAX_RETAINED_DAYS = 400;
+export const MAX_USAGE_RANGE_DAYS = 365;
+const PROMPT_MESSAGE_TYPES = new Set(["send_agent_message_request", "send_agent_message"]);
+
+export interface UsageDay {
+  /** Local calendar date, `YYYY-MM-DD`. */
+  date: string;
+  focusedMs: number;
+  promptsSent: number;
+  agentIds: string[];
+}
+
+export interface UsageStats {
+  from: string;
+  to: string;
+  days: Array<{ date: string; focusedMs: number; promptsSent: number; agentsInteracted: number }>;
+  totals: { focusedMs: number; promptsSent: number; agentsInteracted: number };
+}
+
+export interface UsageEvent {
+  kind: "prompt" | "interaction";
+  agentId: string;
+}
+
+interface PersistedUsageStatsDocument {
+  version: 1;
+  days: UsageDay[];
+}
+
+export interface UsageStatsStore {
+  recordFocus(input: { startMs: number; endMs: number }): Promise<void>;
+  recordEvent(event: UsageEvent, atMs?: number): Promise<void>;
+  /** The last `days` local days, oldest first, including days without activity. */
+  getStats(input: { days: number }): Promise<UsageStats>;
+}
+
+export function localDateKey(ms: number): string {
+  const date = new Date(ms);
+  const month = String(date.getMonth() + 1).padStart(2, "0");
+  const day = String(date.getDate()).padStart(2, "0");
+  return `${date.getFullYear()}-${month}-${day}`;
+}
+
+function startOfNextLocalDay(ms: number): number {
+  const date = new Date(ms);
+  return new Date(date.getFullYear(), date.getMonth(), date.getDate() + 1).getTime();
+}
+
+/** Splits a focus interval at local midnights so each day gets its own share. */
+export function splitByLocalDay(
+  startMs: number,
+  endMs: number,
+): Array<{ date: string; ms: number }> {
+  const parts: Array<{ date: string; ms: number }> = [];
+  let cursor = startMs;
+  while (cursor < endMs) {
+    const boundary = Math.min(startOfNextLocalDay(cursor), endMs);
+    parts.push({ date: localDateKey(cursor), ms: boundary - cursor });
+    cursor = boundary;
+  }
+  return parts;
+}
+
+/**
+ * Reads an outbound local-transport frame: prompts and any other message
+ * addressed to an agent. Only ids and counts are kept, never content.
+ */
+export function extractUsageEvent(text: string): UsageEvent | null {
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(text);
+  } catch {
+    return null;
+  }
+  if (!isRecord(parsed) || parsed.type !== "session" || !isRecord(parsed.message)) {
+    return null;
+  }
+  const message = parsed.message;
+  if (typeof message.agentId !== "string" || message.agentId.length === 0) {
+    return null;
+  }
+  const kind = PROMPT_MESSAGE_TYPES.has(String(message.type)) ? "prompt" : "interaction";
+  return { kind, agentId: message.agentId };
+}
+
+function coerceDays(value: unknown): UsageDay[] {
+  if (!isRecord(value) || !Array.isArray(value.days)) {
+    return [];
+  }
+  return value.days.flatMap((entry: unknown): UsageDay[] => {
+    if (!isRecord(entry) || typeof entry.date !== "string") {
+      return [];
+    }
+    return [
+      {
+        date: entry.date,
+        focusedMs: typeof entry.focusedMs === "number" ? entry.focusedMs : 0,
+        promptsSent: typeof entry.promptsSent === "number" ? entry.promptsSent : 0,
+        agentIds: Array.isArray(entry.agentIds)
+          ? entry.agentIds.filter((id): id is string => typeof id === "string")
+          : [],
+      },
+    ];
+  });
+}
+
+/** `get_usage_stats` range: whole days ending today. */
+export function coerceUsageRangeDays(value: unknown): number {
+  if (value === undefined || value === null) {
+    return 7;
+  }
+  if (typeof value !== "number" || !Number.isInteger(value) || value < 1) {
+    throw new Error("Usage range must be a positive whole number of days.");
+  }
+  if (value > MAX_USAGE_RANGE_DAYS) {
+    throw new Error(`Usage range is limited to ${MAX_USAGE_RANGE_DAYS} days.`);
+  }
+  return value;
+}
+
+export function createUsageStatsStore(input: {
+  userDataPath: string;
+  now?: () => number;
+}): UsageStatsStore {
+  const filePath = path.join(input.userDataPath, USAGE_STATS_FILENAME);
+  const now = input.now ?? (() => Date.now());
+  const days = new Map<string, UsageDay>();
+  let loaded: Promise<void> | null = null;
+
+  function ensureLoaded(): Promise<void> {
+    loaded ??= readFile(filePath, "utf8")
+      .then((raw) => {
+        for (const day of coerceDays(JSON.parse(raw))) {
+          days.set(day.date, day);
+        }
+      })
+      .catch(() => {
+        days.clear();
+      });
+    return loaded;
+  }
+
+  function dayFor(target: Map<string, UsageDay>, date: string): UsageDay {
+    let day = target.get(date);
+    if (!day) {
+      day = { date, focusedMs: 0, promptsSent: 0, agentIds: [] };
+      target.set(date, day);
+    }
+    return day;
+  }
+
+  /** Adds to the counts on disk rather than overwriting another instance's. */
+  async function update(mutate: (current: Map<string, UsageDay>) => void): Promise<void> {
+    const oldest = localDateKey(now() - MAX_RETAINED_DAYS * DAY_MS);
+    const document = await updateJsonConfigFile(
+      filePath,
+      (raw): PersistedUsageStatsDocument => {
+        const current = new Map(coerceDays(raw).map((day) => [day.date, day]));
+        mutate(current);
+        return {
+          version: 1,
+          days: [...current.values()]
+            .filter((day) => day.date >= oldest)
+            .sort((a, b) => a.date.localeCompare(b.date)),
+        };
+      },
+    );
+    days.clear();
+    for (const day of document.days) {
+      days.set(day.date, day);
+    }
+  }
+
+  return {
+    async recordFocus({ startMs, endMs }) {
+      if (!(endMs > startMs)) {
+        return;
+      }
+      await ensureLoaded();
+      await update((current) => {
+        for (const part of splitByLocalDay(startMs, endMs)) {
+          dayFor(current, part.date).focusedMs += part.ms;
+        }
+      });
+    },
+
+    async recordEvent(event, atMs = now()) {
+      await ensureLoaded();
+      const date = localDateKey(atMs);
+      if (event.kind !== "prompt" && days.get(date)?.agentIds.includes(event.agentId)) {
+        // Nothing changed; most outbound frames end here without touching disk.
+        return;
+      }
+      await update((current) => {
+        const day = dayFor(current, date);
+        if (!day.agentIds.includes(event.agentId)) {
+          day.agentIds.push(event.agentId);
+        }
+        if (event.kind === "prompt") {
+          day.promptsSent += 1;
+        }
+      });
+    },
+
+    async getStats({ days: count }) {
+      await ensureLoaded();
+      const end = now();
+      const rows: UsageStats["days"] = [];
+      const agents = new Set<string>();
+      const today = new Date(end);
+      for (let offset = count - 1; offset >= 0; offset -= 1) {
+        const date = localDateKey(
+          new Date(today.getFullYear(), today.getMonth(), today.getDate() - offset).getTime(),
+        );
+        const day = days.get(date);
+        day?.agentIds.forEach((id) => agents.add(id));
+        rows.push({
+          date,
+          focusedMs: day?.focusedMs ?? 0,
+          promptsSent: day?.promptsSent ?? 0,
+          agentsInteracted: day?.agentIds.length ?? 0,
+        });
+      }
+      return {
+        from: rows[0]?.date ?? localDateKey(end),
+        to: localDateKey(end),
+        days: rows,
+        totals: {
+          focusedMs: rows.reduce((sum, row) => sum + row.focusedMs, 0),
+          promptsSent: rows.reduce((sum, row) => sum + row.promptsSent, 0),
+          agentsInteracted: agents.size,
+        },
+      };
+    },
+  };
+}
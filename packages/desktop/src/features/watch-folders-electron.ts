@@ -0,0 +1,193 @@
+import { randomUUID } from "node:crypto";
+import { watch, type FSWatcher } from "node:fs";
+import { readdir, stat } from "node:fs/promises";
+import path from "node:path";
+import { app } from "electron";
+import log from "electron-log/main";
+import { toFileSystemPath } from "../system/windows-paths.js";
+import { emitDesktopEvent } from "../window/desktop-events.js";
+import { copyAttachmentFileToManagedStorage } from "./attachments.js";
+import {
+  coerceWatchFolderInput,
+  compileFileGlob,
+  createNewFileDetector,
+  createWatchFolderStore,
+  type WatchedFileState,
+  type WatchFolder,
+  type WatchFolderStore,
+} from "./watch-folders.js";
+import { getWorkspaceTrustRegistry } from "./workspace-trust-electron.js";
+
+// Screenshots and build outputs are written in several steps; wait for them to stop growing.
+const FILE_SETTLE_MS = 1_000;
+// fs.watch misses events on network and some virtualised volumes, so listings are also polled.
+const POLL_INTERVAL_MS = 2_000;
+const MAX_ATTACHED_FILE_BYTES = 100 * 1024 * 1024;
+const ATTACHMENT_EXTENSION_PATTERN = /^\.[A-Za-z0-9]{1,16}$/;
+
+interface ActiveWatch {
+  stop(): void;
+}
+
+let watchFolderStore: WatchFolderStore | null = null;
+const activeWatches = new Map<string, ActiveWatch>();
+
+function getWatchFolderStore(): WatchFolderStore {
+  watchFolderStore ??= createWatchFolderStore({ userDataPath: app.getPath("userData") });
+  return watchFolderStore;
+}
+
+async function listMatchingFiles(
+  folder: WatchFolder,
+  pattern: RegExp,
+): Promise<Map<string, WatchedFileState>> {
+  const listing = new Map<string, WatchedFileState>();
+  const entries = await readdir(toFileSystemPath(folder.path), { withFileTypes: true });
+  for (const entry of entries) {
+    // Hidden names cover in-progress writes such as macOS's `.Screenshot …` temp files.
+    if (!entry.isFile() || entry.name.startsWith(".") || !pattern.test(entry.name)) {
+      continue;
+    }
+    const info = await stat(toFileSystemPath(path.join(folder.path, entry.name))).catch(
+      () => null,
+    );
+    if (info) {
+      listing.set(entry.name, { size: info.size, mtimeMs: info.mtimeMs });
+    }
+  }
+  return listing;
+}
+
+async function attachFile(folder: WatchFolder, fileName: string, size: number): Promise<void> {
+  const sourcePath = path.join(folder.path, fileName);
+  if (size > MAX_ATTACHED_FILE_BYTES) {
+    log.warn(`[watch-folders] skipped ${sourcePath}: larger than 100 MB`);
+    return;
+  }
+  const extension = path.extname(fileName).toLowerCase();
+  const attachment = await copyAttachmentFileToManagedStorage({
+    attachmentId: randomUUID(),
+    sourcePath,
+    extension: ATTACHMENT_EXTENSION_PATTERN.test(extension) ? extension : undefined,
+  });
+  emitDesktopEvent("watch-folder-file-attached", {
+    watchId: folder.id,
+    agentId: folder.agentId,
+    fileName,
+    sourcePath,
+    attachment,
+  });
+}
+
+function startWatch(folder: WatchFolder): ActiveWatch {
+  const pattern = compileFileGlob(folder.glob);
+  const detector = createNewFileDetector({ settleMs: FILE_SETTLE_MS });
+  let scanning = false;
+  let watcher: FSWatcher | null = null;
+
+  const scan = async () => {
+    if (scanning) {
+      return;
+    }
+    scanning = true;
+    try {
+      const listing = await listMatchingFiles(folder, pattern);
+      for (const fileName of detector.observe(listing, Date.now())) {
+        await attachFile(folder, fileName, listing.get(fileName)?.size ?? 0).catch((error) => {
+          log.warn(`[watch-folders] failed to attach ${fileName}`, error);
+        });
+      }
+    } catch (error) {
+      log.warn(`[watch-folders] failed to scan ${folder.path}`, error);
+    } finally {
+      scanning = false;
+    }
+  };
+
+  try {
+    watcher = watch(toFileSystemPath(folder.path), { persistent: false }, () => void scan());
+    watcher.on("error", (error) => {
+      log.warn(`[watch-folders] watcher stopped for ${folder.path}; polling only`, error);
+      watcher?.close();
+      watcher = null;
+    });
+  } catch (error) {
+    log.warn(`[watch-folders] cannot watch ${folder.path}; polling only`, error);
+  }
+  const poll = setInterval(() => void scan(), POLL_INTERVAL_MS);
+  poll.unref();
+  void scan();
+
+  return {
+    stop() {
+      clearInterval(poll);
+      watcher?.close();
+    },
+  };
+}
+
+function syncWatch(folder: WatchFolder): void {
+  activeWatches.get(folder.id)?.stop();
+  activeWatches.delete(folder.id);
+  if (folder.enabled) {
+    activeWatches.set(folder.id, startWatch(folder));
+  }
+}
+
+/** Resumes enabled watches from the last session; files added while closed are not offered. */
+export async function startWatchFolders(): Promise<void> {
+  for (const folder of await getWatchFolderStore().list()) {
+    if (folder.enabled) {
+      try {
+        await getWorkspaceTrustRegistry().ensureTrusted(folder.path);
+      } catch (error) {
+        log.warn(`[watch-folders] not resuming ${folder.path}`, error);
+        continue;
+      }
+    }
+    syncWatch(folder);
+  }
+}
+
+export async function watchAttachFolder(
+  args: Record<string, unknown> | undefined,
+): Promise<WatchFolder> {
+  const input = coerceWatchFolderInput(args);
+  await getWorkspaceTrustRegistry().ensureTrusted(input.path);
+  const info = await stat(toFileSystemPath(input.path));
+  if (!info.isDirectory()) {
+    throw new Error(`Not a folder: ${input.path}`);
+  }
+  const folder = await getWatchFolderStore().upsert(input);
+  syncWatch(folder);
+  return folder;
+}
+
+export async function setWatchFolderEnabled(
+  args: Record<string, unknown> | undefined,
+): Promise<WatchFolder> {
+  if (typeof args?.id !== "string" || typeof args.enabled !== "boolean") {
+    throw new Error("A watch folder id and enabled flag are required.");
+  }
+  const store = getWatchFolderStore();
+  const existing = (await store.list()).find((folder) => folder.id === args.id);
+  if (existing && args.enabled) {
+    await getWorkspaceTrustRegistry().ensureTrusted(existing.path);
+  }
+  const folder = await store.setEnabled({ id: args.id, enabled: args.enabled });
+  syncWatch(folder);
+  return folder;
+}
+
+export async function unwatchAttachFolder(
+  args: Record<string, unknown> | undefined,
+): Promise<boolean> {
+  const id = typeof args?.id === "string" ? args.id : "";
+  activeWatches.get(id)?.stop();
+  activeWatches.delete(id);
+  return await getWatchFolderStore().remove(id);
+}
+
+export async function listWatchFolders(): Promise<WatchFolder[]> {
+  return await getWatchFolderStore().list();
+}
@@ -0,0 +1,81 @@
+import { mkdtemp, rm } from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+import {
+  coerceWatchFolderInput,
+  compileFileGlob,
+  createNewFileDetector,
+  createWatchFolderStore,
+} from "./watch-folders";
+
+describe("compileFileGlob", () => {
+  it("matches file names with wildcards and brace alternatives", () => {
+    const screenshots = compileFileGlob("Screenshot*.{png,jpg}");
+    expect(screenshots.test("Screenshot 2026-05-01 at 10.00.00.png")).toBe(true);
+    expect(screenshots.test("screenshot.JPG")).toBe(true);
+    expect(screenshots.test("Screenshot.gif")).toBe(false);
+    expect(compileFileGlob("build-?.zip").test("build-1.zip")).toBe(true);
+    expect(compileFileGlob("a+b(1).txt").test("a+b(1).txt")).toBe(true);
+    expect(() => compileFileGlob("*.{png")).toThrow(/Unclosed brace/);
+  });
+});
+
+describe("coerceWatchFolderInput", () => {
+  it("requires an absolute folder and an agent, defaulting the glob", () => {
+    const folder = path.resolve(os.tmpdir(), "shots");
+    expect(coerceWatchFolderInput({ path: folder, agentId: "a1" })).toEqual({
+      path: folder,
+      agentId: "a1",
+      glob: "*",
+    });
+    expect(() => coerceWatchFolderInput({ path: "shots", agentId: "a1" })).toThrow(/absolute/);
+    expect(() => coerceWatchFolderInput({ path: folder })).toThrow(/agent id/);
+    expect(() => coerceWatchFolderInput({ path: folder, agentId: "a1", glob: "out/*" })).toThrow(
+      /path separators/,
+    );
+  });
+});
+
+describe("createNewFileDetector", () => {
+  it("offers only files added after the first listing, once they stop changing", () => {
+    const detector = createNewFileDetector({ settleMs: 1_000 });
+    expect(detector.observe(new Map([["old.png", { size: 5, mtimeMs: 1 }]]), 0)).toEqual([]);
+
+    const growing = new Map([
+      ["old.png", { size: 5, mtimeMs: 1 }],
+      ["new.png", { size: 10, mtimeMs: 2 }],
+    ]);
+    expect(detector.observe(growing, 100)).toEqual([]);
+    const grown = new Map([...growing, ["new.png", { size: 20, mtimeMs: 3 }]]);
+    expect(detector.observe(grown, 900)).toEqual([]);
+    expect(detector.observe(grown, 1_500)).toEqual([]);
+    expect(detector.observe(grown, 1_900)).toEqual(["new.png"]);
+    expect(detector.observe(grown, 5_000)).toEqual([]);
+  });
+});
+
+describe("createWatchFolderStore", () => {
+  let userDataPath: string;
+
+  beforeEach(async () => {
+    userDataPath = await mkdtemp(path.join(os.tmpdir(), "paseo-watch-folders-"));
+  });
+
+  afterEach(async () => {
+    await rm(userDataPath, { recursive: true, force: true });
+  });
+
+  it("keeps one watch per folder and persists enablement", async () => {
+    const store = createWatchFolderStore({ userDataPath });
+    const first = await store.upsert({ path: "/shots", agentId: "a1", glob: "*.png" });
+    const retargeted = await store.upsert({ path: "/shots", agentId: "a2", glob: "*" });
+    await store.setEnabled({ id: first.id, enabled: false });
+
+    const reopened = createWatchFolderStore({ userDataPath });
+    expect(retargeted.id).toBe(first.id);
+    expect(await reopened.list()).toEqual([{ ...retargeted, enabled: false }]);
+    expect(await reopened.remove(first.id)).toBe(true);
+    expect(await reopened.list()).toEqual([]);
+  });
+});
@@ -0,0 +1,255 @@
+import { randomUUID } from "node:crypto";
+import { readFile } from "node:fs/promises";
+import path from "node:path";
+import { updateJsonConfigFile } from "../settings/config-file.js";
+import { isRecord } from "../utils/is-record.js";
+
+const WATCH_FOLDERS_FILENAME = "watch-folders.json";
+const MAX_GLOB_LENGTH = 200;
+export const DEFAULT_WATCH_GLOB = "*";
+
+export interface WatchFolder {
+  id: string;
+  path: string;
+  agentId: string;
+  /** Matched against file names directly inside `path`; subfolders are not watched. */
+  glob: string;
+  enabled: boolean;
+  createdAt: string;
+}
+
+export interface WatchedFileState {
+  size: number;
+  mtimeMs: number;
+}
+
+interface PersistedWatchFoldersDocument {
+  version: 1;
+  folders: WatchFolder[];
+}
+
+export interface WatchFolderStore {
+  list(): Promise<WatchFolder[]>;
+  /** A folder has one watch; watching it again retargets the agent and glob. */
+  upsert(input: { path: string; agentId: string; glob: string }): Promise<WatchFolder>;
+  setEnabled(input: { id: string; enabled: boolean }): Promise<WatchFolder>;
+  remove(id: string): Promise<boolean>;
+}
+
+export interface NewFileDetector {
+  /**
+   * Feeds a full listing of the folder and returns files that appeared after
+   * the first listing and have stopped changing for `settleMs`.
+   */
+  observe(listing: Map<string, WatchedFileState>, atMs: number): string[];
+}
+
+function escapeRegExp(text: string): string {
+  return text.replace(/[.+^$(){}|[\]\\]/g, "\\$&");
+}
+
+/** `*`, `?` and `{a,b}` over a single file name, case-insensitively. */
+export function compileFileGlob(glob: string): RegExp {
+  let source = "";
+  let inGroup = false;
+  for (const char of glob) {
+    if (char === "*") {
+      source += "[^/\\\\]*";
+    } else if (char === "?") {
+      source += "[^/\\\\]";
+    } else if (char === "{" && !inGroup) {
+      inGroup = true;
+      source += "(?:";
+    } else if (char === "}" && inGroup) {
+      inGroup = false;
+      source += ")";
+    } else if (char === "," && inGroup) {
+      source += "|";
+    } else {
+      source += escapeRegExp(char);
+    }
+  }
+  if (inGroup) {
+    throw new Error(`Unclosed brace in watch glob: ${glob}`);
+  }
+  return new RegExp(`^${source}$`, "i");
+}
+
+export function coerceWatchFolderInput(args: Record<string, unknown> | undefined): {
+  path: string;
+  agentId: string;
+  glob: string;
+} {
+  const folderPath = typeof args?.path === "string" ? args.path.trim() : "";
+  if (!path.isAbsolute(folderPath)) {
+    throw new Error("Watch folder path must be absolute.");
+  }
+  const agentId = typeof args?.agentId === "string" ? args.agentId.trim() : "";
+  if (agentId.length === 0) {
+    throw new Error("An agent id is required to watch a folder.");
+  }
+  const rawGlob = args?.glob;
+  if (rawGlob !== undefined && rawGlob !== null && typeof rawGlob !== "string") {
+    throw new Error("Watch glob must be a string.");
+  }
+  const glob = rawGlob?.trim() || DEFAULT_WATCH_GLOB;
+  if (glob.length > MAX_GLOB_LENGTH || /[/\\]/.test(glob)) {
+    throw new Error("Watch globs match file names only and cannot contain path separators.");
+  }
+  compileFileGlob(glob);
+  return { path: path.resolve(folderPath), agentId, glob };
+}
+
+export function createNewFileDetector(input: { settleMs: number }): NewFileDetector {
+  let baseline: Set<string> | null = null;
+  const candidates = new Map<string, WatchedFileState & { since: number }>();
+
+  return {
+    observe(listing, atMs) {
+      if (!baseline) {
+        baseline = new Set(listing.keys());
+        return [];
+      }
+      for (const name of candidates.keys()) {
+        if (!listing.has(name)) {
+          candidates.delete(name);
+        }
+      }
+      const settled: string[] = [];
+      for (const [name, state] of listing) {
+        if (baseline.has(name)) {
+          continue;
+        }
+        const candidate = candidates.get(name);
+        if (!candidate || candidate.size !== state.size || candidate.mtimeMs !== state.mtimeMs) {
+          candidates.set(name, { ...state, since: atMs });
+          continue;
+        }
+        if (atMs - candidate.since >= input.settleMs) {
+          candidates.delete(name);
+          baseline.add(name);
+          settled.push(name);
+        }
+      }
+      // Names that vanished can be offered again if they come back.
+      for (const name of baseline) {
+        if (!listing.has(name)) {
+          baseline.delete(name);
+        }
+      }
+      return settled.sort();
+    },
+  };
+}
+
+function coerceFolders(value: unknown): WatchFolder[] {
+  if (!isRecord(value) || !Array.isArray(value.folders)) {
+    return [];
+  }
+  return value.folders.flatMap((entry: unknown): WatchFolder[] => {
+    if (
+      !isRecord(entry) ||
+      typeof entry.id !== "string" ||
+      typeof entry.path !== "string" ||
+      !path.isAbsolute(entry.path) ||
+      typeof entry.agentId !== "string"
+    ) {
+      return [];
+    }
+    const glob = typeof entry.glob === "string" ? entry.glob : DEFAULT_WATCH_GLOB;
+    try {
+      compileFileGlob(glob);
+    } catch {
+      return [];
+    }
+    return [
+      {
+        id: entry.id,
+        path: entry.path,
+        agentId: entry.agentId,
+        glob,
+        enabled: entry.enabled !== false,
+        createdAt: typeof entry.createdAt === "string" ? entry.createdAt : "",
+      },
+    ];
+  });
+}
+
+export function createWatchFolderStore(input: {
+  userDataPath: string;
+  now?: () => number;
+}): WatchFolderStore {
+  const filePath = path.join(input.userDataPath, WATCH_FOLDERS_FILENAME);
+  const now = input.now ?? (() => Date.now());
+  let folders: WatchFolder[] = [];
+  let loaded: Promise<void> | null = null;
+
+  function ensureLoaded(): Promise<void> {
+    loaded ??= readFile(filePath, "utf8")
+      .then((raw) => {
+        folders = coerceFolders(JSON.parse(raw));
+      })
+      .catch(() => {
+        folders = [];
+      });
+    return loaded;
+  }
+
+  /** Edits the folder list as it is on disk, under the file lock. */
+  async function update(
+    mutate: (current: WatchFolder[]) => WatchFolder[],
+  ): Promise<WatchFolder[]> {
+    const document = await updateJsonConfigFile(
+      filePath,
+      (raw): PersistedWatchFoldersDocument => ({ version: 1, folders: mutate(coerceFolders(raw)) }),
+    );
+    folders = document.folders;
+    loaded = Promise.resolve();
+    return folders;
+  }
+
+  return {
+    async list() {
+      await ensureLoaded();
+      return folders.map((folder) => ({ ...folder }));
+    },
+
+    async upsert(folder) {
+      const next = await update((current) => {
+        const existing = current.find((entry) => entry.path === folder.path);
+        const upserted: WatchFolder = {
+          id: existing?.id ?? randomUUID(),
+          path: folder.path,
+          agentId: folder.agentId,
+          glob: folder.glob,
+          enabled: true,
+          createdAt: existing?.createdAt ?? new Date(now()).toISOString(),
+        };
+        return existing
+          ? current.map((entry) => (entry === existing ? upserted : entry))
+          : [...current, upserted];
+      });
+      return { ...next.find((entry) => entry.path === folder.path)! };
+    },
+
+    async setEnabled({ id, enabled }) {
+      const next = await update((current) => {
+        if (!current.some((entry) => entry.id === id)) {
+          throw new Error(`Unknown watch folder: ${id}`);
+        }
+        return current.map((entry) => (entry.id === id ? { ...entry, enabled } : entry));
+      });
+      return { ...next.find((entry) => entry.id === id)! };
+    },
+
+    async remove(id) {
+      let removed = false;
+      await update((current) => {
+        const remaining = current.filter((entry) => entry.id !== id);
+        removed = remaining.length < current.length;
+        return remaining;
+      });
+      return removed;
+    },
+  };
+}
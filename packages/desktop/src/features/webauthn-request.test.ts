@@ -0,0 +1,59 @@
+import { describe, expect, it } from "vitest";
+import {
+  buildAssertionPromptPage,
+  buildAssertionScript,
+  coerceAssertionRequest,
+} from "./webauthn-request";
+
+describe("coerceAssertionRequest", () => {
+  it("normalizes the relying party and applies defaults", () => {
+    expect(coerceAssertionRequest({ challenge: "abc-_123", rpId: " Relay.Paseo.sh " })).toEqual({
+      challenge: "abc-_123",
+      rpId: "relay.paseo.sh",
+      allowCredentialIds: [],
+      userVerification: "preferred",
+      timeoutMs: 60_000,
+    });
+  });
+
+  it("clamps the timeout and keeps a valid user verification requirement", () => {
+    expect(
+      coerceAssertionRequest({
+        challenge: "abc",
+        rpId: "paseo.sh",
+        userVerification: "required",
+        timeoutMs: 10 * 60_000,
+      }),
+    ).toMatchObject({ userVerification: "required", timeoutMs: 5 * 60_000 });
+  });
+
+  it("rejects malformed input", () => {
+    expect(() => coerceAssertionRequest({ challenge: "a+b/", rpId: "paseo.sh" })).toThrow(
+      "base64url",
+    );
+    expect(() => coerceAssertionRequest({ challenge: "abc", rpId: "127.0.0.1" })).toThrow(
+      "relying party",
+    );
+    expect(() => coerceAssertionRequest({ challenge: "abc", rpId: "paseo.sh:443" })).toThrow(
+      "relying party",
+    );
+    expect(() =>
+      coerceAssertionRequest({ challenge: "abc", rpId: "paseo.sh", allowCredentialIds: ["x y"] }),
+    ).toThrow("credential ids");
+  });
+});
+
+describe("assertion prompt", () => {
+  it("escapes the relying party in the prompt page", () => {
+    expect(buildAssertionPromptPage("<x>")).toContain("to sign in to &lt;x&gt;");
+  });
+
+  it("embeds the request as JSON in the script", () => {
+    const script = buildAssertionScript(
+      coerceAssertionRequest({ challenge: "abc", rpId: "paseo.sh", allowCredentialIds: ["id1"] }),
+    );
+    expect(script).toContain('"rpId":"paseo.sh"');
+    expect(script).toContain('"allowCredentialIds":["id1"]');
+    expect(script).toContain("navigator.credentials.get");
+  });
+});
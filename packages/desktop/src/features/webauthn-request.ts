@@ -0,0 +1,116 @@
+const BASE64URL_PATTERN = /^[A-Za-z0-9_-]+$/;
+// RFC 1123 hostname; WebAuthn RP IDs are registrable domains, never IPs or ports.
+const RP_ID_PATTERN = /^(?=.{1,253}$)([a-z0-9]([a-z0-9-]{0,61}[a-z0-9])?\.)+[a-z]{2,63}$/;
+const DEFAULT_TIMEOUT_MS = 60_000;
+const MAX_TIMEOUT_MS = 5 * 60_000;
+const USER_VERIFICATION_VALUES = ["required", "preferred", "discouraged"] as const;
+
+export type UserVerificationRequirement = (typeof USER_VERIFICATION_VALUES)[number];
+
+export interface AssertionRequest {
+  /** base64url, as issued by the daemon or relay. */
+  challenge: string;
+  rpId: string;
+  allowCredentialIds: string[];
+  userVerification: UserVerificationRequirement;
+  timeoutMs: number;
+}
+
+export interface AssertionResult {
+  credentialId: string;
+  clientDataJSON: string;
+  authenticatorData: string;
+  signature: string;
+  userHandle: string | null;
+}
+
+function isBase64Url(value: unknown): value is string {
+  return typeof value === "string" && BASE64URL_PATTERN.test(value);
+}
+
+export function coerceAssertionRequest(
+  args: Record<string, unknown> | undefined,
+): AssertionRequest {
+  if (!isBase64Url(args?.challenge)) {
+    throw new Error("WebAuthn challenge must be a base64url string.");
+  }
+  const rpId = typeof args?.rpId === "string" ? args.rpId.trim().toLowerCase() : "";
+  if (!RP_ID_PATTERN.test(rpId)) {
+    throw new Error(`Invalid WebAuthn relying party id: ${String(args?.rpId)}`);
+  }
+  const allowCredentialIds = Array.isArray(args?.allowCredentialIds)
+    ? args.allowCredentialIds
+    : [];
+  if (!allowCredentialIds.every(isBase64Url)) {
+    throw new Error("WebAuthn credential ids must be base64url strings.");
+  }
+  const userVerification = (USER_VERIFICATION_VALUES as readonly unknown[]).includes(
+    args?.userVerification,
+  )
+    ? (args?.userVerification as UserVerificationRequirement)
+    : "preferred";
+  const timeoutMs =
+    typeof args?.timeoutMs === "number" && Number.isFinite(args.timeoutMs)
+      ? Math.min(MAX_TIMEOUT_MS, Math.max(1_000, Math.round(args.timeoutMs)))
+      : DEFAULT_TIMEOUT_MS;
+  return { challenge: args.challenge, rpId, allowCredentialIds, userVerification, timeoutMs };
+}
+
+function escapeHtml(value: string): string {
+  return value
+    .replace(/&/g, "&amp;")
+    .replace(/</g, "&lt;")
+    .replace(/>/g, "&gt;")
+    .replace(/"/g, "&quot;");
+}
+
+/** Page served at `https://<rpId>/` inside the isolated prompt window. */
+export function buildAssertionPromptPage(rpId: string): string {
+  return [
+    "<!doctype html>",
+    '<html><head><meta charset="utf-8"><title>Security key</title>',
+    "<style>body{font:14px system-ui;margin:0;height:100vh;display:flex;align-items:center;",
+    "justify-content:center;text-align:center;color:#e4e4e7;background:#18181b}</style>",
+    "</head><body><div>",
+    "<p><strong>Touch your security key</strong></p>",
+    `<p>to sign in to ${escapeHtml(rpId)}</p>`,
+    "</div></body></html>",
+  ].join("");
+}
+
+/** Runs `navigator.credentials.get` in the prompt page and returns base64url fields. */
+export function buildAssertionScript(request: AssertionRequest): string {
+  return `(async () => {
+  const decode = (value) => {
+    const base64 = value.replace(/-/g, "+").replace(/_/g, "/");
+    const padded = base64 + "===".slice((base64.length + 3) % 4);
+    return Uint8Array.from(atob(padded), (char) => char.charCodeAt(0));
+  };
+  const encode = (buffer) =>
+    btoa(String.fromCharCode(...new Uint8Array(buffer)))
+      .replace(/\\+/g, "-").replace(/\\//g, "_").replace(/=+$/, "");
+  const request = ${JSON.stringify(request)};
+  const credential = await navigator.credentials.get({
+    publicKey: {
+      challenge: decode(request.challenge),
+      rpId: request.rpId,
+      timeout: request.timeoutMs,
+      userVerification: request.userVerification,
+      allowCredentials: request.allowCredentialIds.map((id) => ({
+        type: "public-key",
+        id: decode(id),
+      })),
+    },
+  });
+  if (!credential) {
+    return null;
+  }
+  return {
+    credentialId: encode(credential.rawId),
+    clientDataJSON: encode(credential.response.clientDataJSON),
+    authenticatorData: encode(credential.response.authenticatorData),
+    signature: encode(credential.response.signature),
+    userHandle: credential.response.userHandle ? encode(credential.response.userHandle) : null,
+  };
+})()`;
+}
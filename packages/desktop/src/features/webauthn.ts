@@ -0,0 +1,74 @@
+import { randomUUID } from "node:crypto";
+import { BrowserWindow, session } from "electron";
+import {
+  buildAssertionPromptPage,
+  buildAssertionScript,
+  coerceAssertionRequest,
+  type AssertionResult,
+} from "./webauthn-request.js";
+
+const PROMPT_WINDOW_SIZE = { width: 360, height: 180 };
+
+/**
+ * Security keys are driven by Chromium's own CTAP stack, which only answers
+ * for the page's origin. The prompt window therefore loads `https://<rpId>/`
+ * from an in-memory session whose https handler serves a local page, so the
+ * assertion is scoped to the relay's RP ID without any network request.
+ */
+export async function getWebAuthnAssertion(input: {
+  args: Record<string, unknown> | undefined;
+  parent: BrowserWindow | null;
+}): Promise<AssertionResult | null> {
+  const request = coerceAssertionRequest(input.args);
+  // No `persist:` prefix: the session and its handler vanish with the window.
+  const promptSession = session.fromPartition(`webauthn-${randomUUID()}`);
+  promptSession.protocol.handle("https", (httpsRequest) => {
+    if (new URL(httpsRequest.url).hostname !== request.rpId) {
+      return new Response("Not found", { status: 404 });
+    }
+    return new Response(buildAssertionPromptPage(request.rpId), {
+      headers: { "content-type": "text/html; charset=utf-8" },
+    });
+  });
+
+  const prompt = new BrowserWindow({
+    ...PROMPT_WINDOW_SIZE,
+    title: "Security key",
+    parent: input.parent ?? undefined,
+    modal: input.parent !== null,
+    resizable: false,
+    minimizable: false,
+    maximizable: false,
+    fullscreenable: false,
+    show: false,
+    webPreferences: {
+      session: promptSession,
+      contextIsolation: true,
+      nodeIntegration: false,
+      sandbox: true,
+    },
+  });
+  try {
+    await prompt.loadURL(`https://${request.rpId}/`);
+    // WebAuthn refuses to run in an unfocused document.
+    prompt.show();
+    prompt.focus();
+    const result: unknown = await Promise.race([
+      prompt.webContents.executeJavaScript(buildAssertionScript(request), true),
+      new Promise((resolve) => prompt.once("closed", () => resolve(null))),
+    ]);
+    return (result as AssertionResult | null) ?? null;
+  } catch (error) {
+    const message = error instanceof Error ? error.message : String(error);
+    // NotAllowedError covers both cancellation and timeout.
+    if (message.includes("NotAllowedError")) {
+      return null;
+    }
+    throw error;
+  } finally {
+    if (!prompt.isDestroyed()) {
+      prompt.destroy();
+    }
+    promptSession.protocol.unhandle("https");
+  }
+}
@@ -0,0 +1,79 @@
+import { execFile } from "node:child_process";
+import { randomUUID } from "node:crypto";
+import { promisify } from "node:util";
+import { desktopCapturer, screen } from "electron";
+import log from "electron-log/main";
+import { ensureScreenRecordingAccess } from "../system/window-list-electron.js";
+import { writeAttachmentBytes } from "./attachments.js";
+import {
+  buildMacBundleWindowIdsScript,
+  coerceCaptureTarget,
+  parseMacBundleWindowIds,
+  selectCaptureSource,
+} from "./window-capture.js";
+
+const execFileAsync = promisify(execFile);
+
+export interface CapturedWindow {
+  path: string;
+  byteSize: number;
+  title: string;
+  width: number;
+  height: number;
+}
+
+async function resolveBundleWindowIds(bundleId: string): Promise<number[]> {
+  try {
+    const { stdout } = await execFileAsync(
+      "osascript",
+      ["-l", "JavaScript", "-e", buildMacBundleWindowIdsScript(bundleId)],
+      { timeout: 5_000 },
+    );
+    return parseMacBundleWindowIds(stdout);
+  } catch (error) {
+    log.warn("[window-capture] failed to list windows for", bundleId, error);
+    return [];
+  }
+}
+
+/** Largest display in physical pixels, so captures keep full resolution. */
+function captureSize(): { width: number; height: number } {
+  return screen.getAllDisplays().reduce(
+    (largest, display) => ({
+      width: Math.max(largest.width, Math.round(display.size.width * display.scaleFactor)),
+      height: Math.max(largest.height, Math.round(display.size.height * display.scaleFactor)),
+    }),
+    { width: 0, height: 0 },
+  );
+}
+
+/** Screenshots one window of another app into managed attachment storage. */
+export async function captureWindowOfApp(
+  args: Record<string, unknown> | undefined,
+): Promise<CapturedWindow> {
+  const target = coerceCaptureTarget(args?.bundleIdOrTitle);
+  await ensureScreenRecordingAccess();
+
+  const bundleWindowIds =
+    target.bundleId && process.platform === "darwin"
+      ? await resolveBundleWindowIds(target.bundleId)
+      : [];
+  const sources = await desktopCapturer.getSources({
+    types: ["window"],
+    thumbnailSize: captureSize(),
+  });
+  const source = selectCaptureSource({ sources, target, bundleWindowIds });
+  if (!source) {
+    throw new Error(`No on-screen window matches "${target.query}".`);
+  }
+  if (source.thumbnail.isEmpty()) {
+    throw new Error(`"${source.name}" could not be captured.`);
+  }
+
+  const file = await writeAttachmentBytes({
+    attachmentId: `capture-${randomUUID()}`,
+    bytes: source.thumbnail.toPNG(),
+    extension: ".png",
+  });
+  return { ...file, title: source.name, ...source.thumbnail.getSize() };
+}
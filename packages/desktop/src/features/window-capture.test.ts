@@ -0,0 +1,64 @@
+import { describe, expect, it } from "vitest";
+import {
+  buildMacBundleWindowIdsScript,
+  coerceCaptureTarget,
+  parseCaptureSourceWindowId,
+  parseMacBundleWindowIds,
+  selectCaptureSource,
+} from "./window-capture";
+
+const SOURCES = [
+  { id: "window:101:0", name: "Inbox — Mail" },
+  { id: "window:202:0", name: "localhost:3000 — Safari" },
+  { id: "window:303:0", name: "Safari" },
+];
+
+describe("coerceCaptureTarget", () => {
+  it("recognizes bundle ids and rejects empty input", () => {
+    expect(coerceCaptureTarget(" com.apple.Safari ")).toEqual({
+      query: "com.apple.Safari",
+      bundleId: "com.apple.Safari",
+    });
+    expect(coerceCaptureTarget("My App").bundleId).toBeNull();
+    expect(() => coerceCaptureTarget("  ")).toThrow("required");
+  });
+});
+
+describe("selectCaptureSource", () => {
+  it("prefers a window owned by the bundle id", () => {
+    const target = coerceCaptureTarget("com.apple.Safari");
+    expect(selectCaptureSource({ sources: SOURCES, target, bundleWindowIds: [202] })?.id).toBe(
+      "window:202:0",
+    );
+  });
+
+  it("falls back to an exact, then partial, title match", () => {
+    const exact = coerceCaptureTarget("safari");
+    expect(selectCaptureSource({ sources: SOURCES, target: exact, bundleWindowIds: [] })?.id).toBe(
+      "window:303:0",
+    );
+    const partial = coerceCaptureTarget("inbox");
+    expect(
+      selectCaptureSource({ sources: SOURCES, target: partial, bundleWindowIds: [] })?.id,
+    ).toBe("window:101:0");
+    const missing = coerceCaptureTarget("Figma");
+    expect(selectCaptureSource({ sources: SOURCES, target: missing, bundleWindowIds: [] })).toBe(
+      null,
+    );
+  });
+});
+
+describe("macOS window ids", () => {
+  it("parses capture source ids and script output", () => {
+    expect(parseCaptureSourceWindowId("window:4242:0")).toBe(4242);
+    expect(parseCaptureSourceWindowId("screen:1:0")).toBeNull();
+    expect(parseMacBundleWindowIds("[12, 34]\n")).toEqual([12, 34]);
+    expect(parseMacBundleWindowIds("execution error")).toEqual([]);
+  });
+
+  it("embeds the bundle id as a string literal", () => {
+    expect(buildMacBundleWindowIdsScript('evil"); doShellScript("x')).toContain(
+      'const bundleId = "evil\\"); doShellScript(\\"x";',
+    );
+  });
+});
@@ -0,0 +1,81 @@
+const BUNDLE_ID_PATTERN = /^[A-Za-z][A-Za-z0-9-]*(\.[A-Za-z0-9-]+)+$/;
+const SOURCE_WINDOW_ID_PATTERN = /^window:(\d+):/;
+
+export interface CaptureTarget {
+  query: string;
+  /** Set when the query looks like `com.apple.Safari`; titles like `index.html` can too. */
+  bundleId: string | null;
+}
+
+export interface CaptureSourceLike {
+  id: string;
+  name: string;
+}
+
+export function coerceCaptureTarget(value: unknown): CaptureTarget {
+  const query = typeof value === "string" ? value.trim() : "";
+  if (query.length === 0) {
+    throw new Error("A bundle id or window title is required.");
+  }
+  return { query, bundleId: BUNDLE_ID_PATTERN.test(query) ? query : null };
+}
+
+/** desktopCapturer ids are `window:<native id>:0`; on macOS the native id is the CGWindowID. */
+export function parseCaptureSourceWindowId(sourceId: string): number | null {
+  const match = SOURCE_WINDOW_ID_PATTERN.exec(sourceId);
+  return match ? Number(match[1]) : null;
+}
+
+/**
+ * Picks the window to capture: one owned by the bundle id first, then an
+ * exact title match, then the first title containing the query.
+ */
+export function selectCaptureSource<T extends CaptureSourceLike>(input: {
+  sources: T[];
+  target: CaptureTarget;
+  bundleWindowIds: number[];
+}): T | null {
+  const byOwner = input.sources.find((source) => {
+    const windowId = parseCaptureSourceWindowId(source.id);
+    return windowId !== null && input.bundleWindowIds.includes(windowId);
+  });
+  if (byOwner) {
+    return byOwner;
+  }
+  const query = input.target.query.toLowerCase();
+  return (
+    input.sources.find((source) => source.name.toLowerCase() === query) ??
+    input.sources.find((source) => source.name.toLowerCase().includes(query)) ??
+    null
+  );
+}
+
+/** JXA printing the on-screen, normal-layer CGWindowIDs owned by `bundleId`. */
+export function buildMacBundleWindowIdsScript(bundleId: string): string {
+  return [
+    'ObjC.import("AppKit");',
+    'ObjC.import("CoreGraphics");',
+    `const bundleId = ${JSON.stringify(bundleId)};`,
+    "const apps = $.NSRunningApplication.runningApplicationsWithBundleIdentifier(bundleId);",
+    "const pids = [];",
+    "for (let i = 0; i < apps.count; i++) pids.push(apps.objectAtIndex(i).processIdentifier);",
+    "const windows = ObjC.deepUnwrap(ObjC.castRefToObject($.CGWindowListCopyWindowInfo(",
+    "  $.kCGWindowListOptionOnScreenOnly | $.kCGWindowListExcludeDesktopElements,",
+    "  $.kCGNullWindowID",
+    "))) || [];",
+    "JSON.stringify(windows",
+    "  .filter((w) => pids.includes(w.kCGWindowOwnerPID) && w.kCGWindowLayer === 0)",
+    "  .map((w) => w.kCGWindowNumber));",
+  ].join("\n");
+}
+
+export function parseMacBundleWindowIds(stdout: string): number[] {
+  try {
+    const parsed: unknown = JSON.parse(stdout.trim());
+    return Array.isArray(parsed)
+      ? parsed.filter((value): value is number => Number.isInteger(value))
+      : [];
+  } catch {
+    return [];
+  }
+}
@@ -0,0 +1,60 @@
+import path from "node:path";
+import { app, BrowserWindow, dialog } from "electron";
+import { observeLocalTransport } from "../daemon/local-transport.js";
+import {
+  createWorkspaceTrustRegistry,
+  extractWorkspaceExecutionPath,
+  WORKSPACE_TRUST_FILENAME,
+  type WorkspaceTrustRegistry,
+} from "./workspace-trust.js";
+
+let workspaceTrustRegistry: WorkspaceTrustRegistry | null = null;
+
+// Closing the dialog answers nothing: the request fails and the next one asks again.
+async function promptForWorkspaceTrust(workspacePath: string): Promise<boolean | null> {
+  const options: Electron.MessageBoxOptions = {
+    type: "warning",
+    title: "Workspace Trust",
+    message: "Do you trust the authors of the files in this folder?",
+    detail: [
+      workspacePath,
+      "Paseo can run commands and watch files in trusted folders. " +
+        "Only trust folders whose contents you recognize. " +
+        "You can change this later in Settings > Permissions.",
+    ].join("\n\n"),
+    buttons: ["Cancel", "Don't Trust", "Trust Folder"],
+    defaultId: 0,
+    cancelId: 0,
+    noLink: true,
+  };
+  const win = BrowserWindow.getFocusedWindow();
+  const result = win
+    ? await dialog.showMessageBox(win, options)
+    : await dialog.showMessageBox(options);
+  return result.response === 0 ? null : result.response === 2;
+}
+
+/** Where decisions are kept; the desktop-managed daemon enforces them from this file. */
+export function getWorkspaceTrustFilePath(): string {
+  return path.join(app.getPath("userData"), WORKSPACE_TRUST_FILENAME);
+}
+
+export function getWorkspaceTrustRegistry(): WorkspaceTrustRegistry {
+  workspaceTrustRegistry ??= createWorkspaceTrustRegistry({
+    userDataPath: app.getPath("userData"),
+    prompt: promptForWorkspaceTrust,
+  });
+  return workspaceTrustRegistry;
+}
+
+/** Holds renderer requests that would run something in an untrusted folder. */
+export function startWorkspaceTrustGate(): void {
+  observeLocalTransport({
+    screenOutbound: async (frame) => {
+      const workspacePath = extractWorkspaceExecutionPath(frame);
+      if (workspacePath) {
+        await getWorkspaceTrustRegistry().ensureTrusted(workspacePath);
+      }
+    },
+  });
+}
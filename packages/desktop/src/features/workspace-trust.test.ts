@@ -0,0 +1,133 @@
+import { mkdir, mkdtemp, rm, symlink } from "node:fs/promises";
+import { tmpdir } from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import {
+  WorkspaceNotTrustedError,
+  createWorkspaceTrustRegistry,
+  extractWorkspaceExecutionPath,
+} from "./workspace-trust";
+
+describe("extractWorkspaceExecutionPath", () => {
+  const frame = (message: unknown) => JSON.stringify({ type: "session", message });
+
+  it("finds the cwd of requests that run something in a workspace", () => {
+    expect(
+      extractWorkspaceExecutionPath(
+        frame({ type: "create_agent_request", config: { provider: "claude", cwd: "/work/repo" } }),
+      ),
+    ).toBe("/work/repo");
+    expect(
+      extractWorkspaceExecutionPath(
+        Buffer.from(frame({ type: "create_terminal_request", cwd: "/work/repo" })),
+      ),
+    ).toBe("/work/repo");
+  });
+
+  it("ignores reads and frames that are not session messages", () => {
+    expect(
+      extractWorkspaceExecutionPath(frame({ type: "checkout_status_request", cwd: "/work/repo" })),
+    ).toBeNull();
+    expect(extractWorkspaceExecutionPath(JSON.stringify({ type: "ping" }))).toBeNull();
+    expect(extractWorkspaceExecutionPath(Buffer.from([0x01, 0x02]))).toBeNull();
+  });
+});
+
+describe("createWorkspaceTrustRegistry", () => {
+  let userDataPath: string;
+
+  beforeEach(async () => {
+    userDataPath = await mkdtemp(path.join(tmpdir(), "paseo-workspace-trust-"));
+  });
+
+  afterEach(async () => {
+    await rm(userDataPath, { recursive: true, force: true });
+  });
+
+  it("prompts once, persists the decision, and covers nested paths", async () => {
+    const prompt = vi.fn(async () => true);
+    const registry = createWorkspaceTrustRegistry({ userDataPath, prompt, platform: "linux" });
+
+    await Promise.all([
+      registry.ensureTrusted("/work/repo"),
+      registry.ensureTrusted("/work/repo/"),
+    ]);
+    await registry.ensureTrusted("/work/repo/packages/app");
+
+    expect(prompt).toHaveBeenCalledTimes(1);
+    const reloaded = createWorkspaceTrustRegistry({
+      userDataPath,
+      prompt: vi.fn(async () => false),
+      platform: "linux",
+    });
+    await expect(reloaded.ensureTrusted("/work/repo")).resolves.toBeUndefined();
+    await expect(reloaded.ensureTrusted("/work/repository")).rejects.toBeInstanceOf(
+      WorkspaceNotTrustedError,
+    );
+  });
+
+  it("remembers a refusal until it is revoked", async () => {
+    const prompt = vi.fn(async () => false);
+    const registry = createWorkspaceTrustRegistry({ userDataPath, prompt, platform: "linux" });
+
+    await expect(registry.ensureTrusted("/work/evil")).rejects.toBeInstanceOf(
+      WorkspaceNotTrustedError,
+    );
+    await expect(registry.ensureTrusted("/work/evil")).rejects.toBeInstanceOf(
+      WorkspaceNotTrustedError,
+    );
+    expect(prompt).toHaveBeenCalledTimes(1);
+
+    expect(await registry.revoke("/work/evil/")).toBe(true);
+    expect(await registry.list()).toEqual([]);
+    prompt.mockResolvedValueOnce(true);
+    await expect(registry.ensureTrusted("/work/evil")).resolves.toBeUndefined();
+  });
+
+  it("asks again after the prompt was dismissed", async () => {
+    const prompt = vi.fn(async (): Promise<boolean | null> => null);
+    const registry = createWorkspaceTrustRegistry({ userDataPath, prompt, platform: "linux" });
+
+    await expect(registry.ensureTrusted("/work/repo")).rejects.toBeInstanceOf(
+      WorkspaceNotTrustedError,
+    );
+    expect(await registry.list()).toEqual([]);
+    prompt.mockResolvedValueOnce(true);
+    await expect(registry.ensureTrusted("/work/repo")).resolves.toBeUndefined();
+    expect(prompt).toHaveBeenCalledTimes(2);
+  });
+
+  it("trusts folders the app created without prompting", async () => {
+    const prompt = vi.fn(async () => false);
+    const registry = createWorkspaceTrustRegistry({ userDataPath, prompt, platform: "linux" });
+
+    await registry.trust("/tmp/paseo-scratch/scratch-1");
+
+    await expect(registry.ensureTrusted("/tmp/paseo-scratch/scratch-1")).resolves.toBeUndefined();
+    expect(prompt).not.toHaveBeenCalled();
+  });
+
+  it.skipIf(process.platform === "win32")(
+    "judges a symlink by the folder it points to",
+    async () => {
+      const trusted = path.join(userDataPath, "trusted");
+      const untrusted = path.join(userDataPath, "untrusted");
+      await mkdir(trusted);
+      await mkdir(untrusted);
+      await symlink(untrusted, path.join(trusted, "escape"));
+      await symlink(trusted, path.join(userDataPath, "alias"));
+      const prompt = vi.fn(async () => false);
+      const registry = createWorkspaceTrustRegistry({ userDataPath, prompt, platform: "linux" });
+
+      await registry.trust(trusted);
+
+      await expect(
+        registry.ensureTrusted(path.join(userDataPath, "alias", "new-worktree")),
+      ).resolves.toBeUndefined();
+      await expect(registry.ensureTrusted(path.join(trusted, "escape"))).rejects.toBeInstanceOf(
+        WorkspaceNotTrustedError,
+      );
+      expect(prompt).toHaveBeenCalledTimes(1);
+    },
+  );
+});
@@ -0,0 +1,166 @@
+import { readFile } from "node:fs/promises";
+import path from "node:path";
+import {
+  canonicalizeWorkspacePath,
+  coerceWorkspaceTrustDecisions,
+  extractWorkspaceExecutionCwd,
+  findWorkspaceTrustDecision,
+  type WorkspaceTrustDecision,
+} from "@getpaseo/server";
+import { updateJsonConfigFile } from "../settings/config-file.js";
+import { isRecord } from "../utils/is-record.js";
+
+export const WORKSPACE_TRUST_FILENAME = "workspace-trust.json";
+
+interface PersistedWorkspaceTrustDocument {
+  version: 1;
+  decisions: WorkspaceTrustDecision[];
+}
+
+export interface WorkspaceTrustRegistry {
+  /**
+   * Resolves when `workspacePath` is trusted, prompting the first time a path
+   * outside every known decision is touched. Rejects when the user declined
+   * or dismissed the prompt; only an explicit answer is remembered.
+   */
+  ensureTrusted(workspacePath: string): Promise<void>;
+  /** Records trust without asking, for folders the app created itself. */
+  trust(workspacePath: string): Promise<void>;
+  list(): Promise<WorkspaceTrustDecision[]>;
+  revoke(workspacePath: string): Promise<boolean>;
+}
+
+export class WorkspaceNotTrustedError extends Error {
+  readonly workspacePath: string;
+
+  constructor(workspacePath: string) {
+    super(`Workspace is not trusted: ${workspacePath}`);
+    this.workspacePath = workspacePath;
+    this.name = "WorkspaceNotTrustedError";
+  }
+}
+
+/**
+ * The workspace a renderer frame would make the daemon execute in, or `null`
+ * when the frame runs nothing there.
+ */
+export function extractWorkspaceExecutionPath(frame: string | Buffer): string | null {
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(typeof frame === "string" ? frame : frame.toString());
+  } catch {
+    return null;
+  }
+  if (!isRecord(parsed) || parsed.type !== "session") {
+    return null;
+  }
+  return extractWorkspaceExecutionCwd(parsed.message);
+}
+
+export function createWorkspaceTrustRegistry(input: {
+  userDataPath: string;
+  /** `null` when the prompt was dismissed without an answer. */
+  prompt: (workspacePath: string) => Promise<boolean | null>;
+  platform?: NodeJS.Platform;
+}): WorkspaceTrustRegistry {
+  const filePath = path.join(input.userDataPath, WORKSPACE_TRUST_FILENAME);
+  const platform = input.platform ?? process.platform;
+  const pendingPrompts = new Map<string, Promise<boolean | null>>();
+  let decisions: WorkspaceTrustDecision[] = [];
+  let loaded: Promise<void> | null = null;
+
+  async function load(): Promise<WorkspaceTrustDecision[]> {
+    loaded ??= readFile(filePath, "utf8")
+      .then((raw) => {
+        decisions = coerceWorkspaceTrustDecisions(JSON.parse(raw));
+      })
+      .catch(() => {
+        decisions = [];
+      });
+    await loaded;
+    return decisions;
+  }
+
+  function canonicalizeRequiredPath(workspacePath: string): Promise<string> {
+    if (workspacePath.trim().length === 0) {
+      throw new Error("Workspace path is required.");
+    }
+    return canonicalizeWorkspacePath(workspacePath, platform);
+  }
+
+  async function update(
+    mutate: (current: WorkspaceTrustDecision[]) => WorkspaceTrustDecision[],
+  ): Promise<void> {
+    const document = await updateJsonConfigFile(
+      filePath,
+      (raw): PersistedWorkspaceTrustDocument => ({
+        version: 1,
+        decisions: mutate(coerceWorkspaceTrustDecisions(raw)),
+      }),
+    );
+    decisions = document.decisions;
+    loaded = Promise.resolve();
+  }
+
+  function withDecision(normalizedPath: string, trusted: boolean) {
+    return (current: WorkspaceTrustDecision[]): WorkspaceTrustDecision[] => [
+      ...current.filter((decision) => decision.path !== normalizedPath),
+      { path: normalizedPath, trusted, decidedAt: new Date().toISOString() },
+    ];
+  }
+
+  async function promptOnce(normalizedPath: string, displayPath: string): Promise<boolean | null> {
+    const pending = pendingPrompts.get(normalizedPath);
+    if (pending) {
+      return pending;
+    }
+    const prompt = (async () => {
+      const trusted = await input.prompt(displayPath);
+      if (trusted !== null) {
+        await update(withDecision(normalizedPath, trusted));
+      }
+      return trusted;
+    })().finally(() => {
+      pendingPrompts.delete(normalizedPath);
+    });
+    pendingPrompts.set(normalizedPath, prompt);
+    return prompt;
+  }
+
+  return {
+    async ensureTrusted(workspacePath) {
+      const normalizedPath = await canonicalizeRequiredPath(workspacePath);
+      const decision = findWorkspaceTrustDecision({
+        decisions: await load(),
+        normalizedPath,
+        platform,
+      });
+      const trusted = decision
+        ? decision.trusted
+        : await promptOnce(normalizedPath, workspacePath);
+      if (trusted !== true) {
+        throw new WorkspaceNotTrustedError(workspacePath);
+      }
+    },
+
+    async trust(workspacePath) {
+      const normalizedPath = await canonicalizeRequiredPath(workspacePath);
+      await update(withDecision(normalizedPath, true));
+    },
+
+    async list() {
+      return [...(await load())];
+    },
+
+    async revoke(workspacePath) {
+      const normalizedPath = await canonicalizeRequiredPath(workspacePath);
+      let revoked = false;
+      await update((current) => {
+        const next = current.filter((decision) => decision.path !== normalizedPath);
+        revoked = next.length < current.length;
+        return next;
+      });
+      return revoked;
+    },
+  };
+}
@@ -10,7 +10,21 @@ import { pathToFileURL } from "node:url";
 import { existsSync } from "node:fs";
 import { execFileSync } from "node:child_process";
 import { app, BrowserWindow, ipcMain, nativeImage, net, protocol } from "electron";
-import { createDaemonCommandHandlers, registerDaemonManager } from "./daemon/daemon-manager.js";
+import {
+  createDaemonCommandHandlers,
+  getAboutInfo,
+  registerDaemonManager,
+  registerDesktopDiskSpaceEvents,
+  restoreDaemonTlsTrust,
+  startDaemonHealthWatchdog,
+  startDesktopLocalTasks,
+  startDesktopMaintenance,
+} from "./daemon/daemon-manager.js";
+import { registerResumeReconnect } from "./daemon/resume-reconnect.js";
+import { startNativeApprovalGate } from "./daemon/approval-gate-electron.js";
+import { startActiveAgentTracking } from "./daemon/agent-interrupts-electron.js";
+import { startTimelineCache } from "./daemon/timeline-cache-electron.js";
+import { startScratchWorkspaceCleanup } from "./features/scratch-workspaces-electron.js";
 import {
   parseCliPassthroughArgsFromArgv,
   runCliPassthroughCommand,
@@ -26,9 +40,11 @@ import {
   setupDefaultContextMenu,
   setupDragDropPrevention,
 } from "./window/window-manager.js";
+import { MAIN_WINDOW_LABEL, registerWindowLabel } from "./window/window-labels.js";
 import { registerDialogHandlers } from "./features/dialogs.js";
 import {
   registerNotificationHandlers,
+  setNotificationCoalesceWindow,
   ensureNotificationCenterRegistration,
 } from "./features/notifications.js";
 import { registerOpenerHandlers } from "./features/opener.js";
@@ -38,7 +54,14 @@ import {
   registerPaseoBrowserWebContents,
   setActivePaseoBrowserPaneId,
 } from "./features/browser-webviews.js";
-import { parseOpenProjectPathFromArgv } from "./open-project-routing.js";
+import { installWebviewPermissionBroker } from "./features/permission-policy-electron.js";
+import {
+  parseOpenPaseoFilePathsFromArgv,
+  parseOpenProjectPathFromArgv,
+} from "./open-project-routing.js";
+import { openPaseoBundle } from "./features/paseo-bundle-electron.js";
+import { openQuickActionUrl } from "./features/quick-actions-electron.js";
+import { parseQuickActionUrlsFromArgv } from "./features/quick-actions.js";
 import { getDesktopSettingsStore } from "./settings/desktop-settings-electron.js";
 import {
   isDesktopManagedDaemonRunningSync,
@@ -48,7 +71,36 @@ import {
   createBeforeQuitHandler,
   stopDesktopManagedDaemonOnQuitIfNeeded,
 } from "./daemon/quit-lifecycle.js";
+import { confirmQuitWithRunningWork } from "./daemon/quit-confirmation.js";
 import { autoUpdateSkillsIfInstalled } from "./integrations/integrations-manager.js";
+import { getSystemLocaleInfo, registerSystemLocaleEvents } from "./system/system-locale.js";
+import { registerTimeZoneEvents } from "./system/time-info.js";
+import { registerDisplayConfigurationEvents } from "./system/display-info.js";
+import { registerPowerStatusEvents } from "./system/power-status.js";
+import { registerAccessibilityEvents } from "./system/accessibility.js";
+import { registerKeyboardLayoutEvents } from "./system/keyboard-layout.js";
+import { registerSystemTextScaleEvents } from "./system/text-scale.js";
+import { registerWindowVisibilityEvents } from "./window/window-visibility.js";
+import { getWindowZoomState, setSystemTextScale, setupWindowZoom } from "./window/window-zoom.js";
+import { registerInitStateHandler, registerInitStateProvider } from "./window/init-state.js";
+import { setupWindowReloadRecovery } from "./window/window-reload.js";
+import { setupNavigationGestures } from "./window/navigation-gestures.js";
+import { setupScrollNormalization } from "./window/scroll-events.js";
+import { applyProxySettings } from "./system/proxy-config.js";
+import { applyClipboardHistorySettings } from "./features/clipboard-history-electron.js";
+import { applyAutomationSettings } from "./features/automation-api-electron.js";
+import { configurePresentationWindow } from "./features/presentation-window-electron.js";
+import { applyStatusPopoverSettings, configureStatusPopover } from "./features/status-popover.js";
+import { getOnboardingStore } from "./features/onboarding-electron.js";
+import { initializeAttachmentStorage } from "./features/attachment-storage-electron.js";
+import { startUnreadIndicators } from "./features/unread-indicators.js";
+import { startActivityJournal } from "./features/daily-digest-electron.js";
+import { startTaskArtifactCollection } from "./features/task-artifacts-electron.js";
+import { startWorkspaceTrustGate } from "./features/workspace-trust-electron.js";
+import { applyPauseAllShortcut } from "./features/pause-all-agents.js";
+import { startFeatureFlags } from "./features/feature-flags-electron.js";
+import { startUsageTracking } from "./features/usage-stats-electron.js";
+import { startWatchFolders } from "./features/watch-folders-electron.js";
 
 const DEV_SERVER_URL = process.env.EXPO_DEV_URL ?? "http://localhost:8081";
 const APP_SCHEME = "paseo";
@@ -202,6 +254,25 @@ let pendingOpenProjectPath = parseOpenProjectPathFromArgv({
   isDefaultApp: process.defaultApp,
 });
 
+const launchPaseoBundlePaths = parseOpenPaseoFilePathsFromArgv({
+  argv: process.argv,
+  isDefaultApp: process.defaultApp,
+});
+
+const launchQuickActionUrls = parseQuickActionUrlsFromArgv(process.argv);
+
+// macOS delivers double-clicked `.paseo` files here, possibly before the app is ready.
+app.on("open-file", (event, filePath) => {
+  event.preventDefault();
+  openPaseoBundle(filePath);
+});
+
+// Likewise for `paseo://` links; Windows and Linux pass them on the command line instead.
+app.on("open-url", (event, url) => {
+  event.preventDefault();
+  openQuickActionUrl(url);
+});
+
 log.info("[open-project] argv:", process.argv);
 log.info("[open-project] isDefaultApp:", process.defaultApp);
 log.info("[open-project] pendingOpenProjectPath:", pendingOpenProjectPath);
@@ -281,6 +352,33 @@ function applyAppIcon(): void {
   app.dock?.setIcon(icon);
 }
 
+function handleAppProtocolRequest(request: Request): Response | Promise<Response> {
+  const appDistDir = getAppDistDir();
+  const { pathname, search, hash } = new URL(request.url);
+  const decodedPath = decodeURIComponent(pathname);
+
+  // Chromium can occasionally request the exported entrypoint directly.
+  // Canonicalize it back to the route URL so Expo Router sees `/`, not `/index.html`.
+  if (decodedPath.endsWith("/index.html")) {
+    const normalizedPath = decodedPath.slice(0, -"/index.html".length) || "/";
+    return Response.redirect(`${APP_SCHEME}://app${normalizedPath}${search}${hash}`, 307);
+  }
+
+  const filePath = path.join(appDistDir, decodedPath);
+  const relativePath = path.relative(appDistDir, filePath);
+
+  if (relativePath.startsWith("..") || path.isAbsolute(relativePath)) {
+    return new Response("Not found", { status: 404 });
+  }
+
+  // SPA fallback: serve index.html for routes without a file extension
+  if (!relativePath || !path.extname(relativePath)) {
+    return net.fetch(pathToFileURL(path.join(appDistDir, "index.html")).toString());
+  }
+
+  return net.fetch(pathToFileURL(filePath).toString());
+}
+
 async function createMainWindow(): Promise<void> {
   const iconPath = getWindowIconPath();
   const systemTheme = resolveSystemWindowTheme();
@@ -309,8 +407,13 @@ async function createMainWindow(): Promise<void> {
     app.dock?.setBadge(devWorktreeName);
   }
 
+  registerWindowLabel({ win: mainWindow, label: MAIN_WINDOW_LABEL });
   setupDarwinPaintRefresh(mainWindow);
   setupWindowResizeEvents(mainWindow);
+  setupWindowZoom(mainWindow);
+  setupWindowReloadRecovery(mainWindow, app.isPackaged ? `${APP_SCHEME}://app/` : DEV_SERVER_URL);
+  setupNavigationGestures(mainWindow);
+  setupScrollNormalization(mainWindow);
   setupDefaultContextMenu(mainWindow);
   setupDragDropPrevention(mainWindow);
   mainWindow.webContents.on("will-attach-webview", (event, webPreferences, params) => {
@@ -342,6 +445,7 @@ async function createMainWindow(): Promise<void> {
     if (browserId) {
       registerPaseoBrowserWebContents(contents, browserId);
     }
+    installWebviewPermissionBroker(contents);
     contents.on("before-input-event", (event, input) => {
       if (isBrowserRefreshInput(input)) {
         event.preventDefault();
@@ -438,6 +542,15 @@ function setupSingleInstanceLock(): boolean {
       isDefaultApp: false,
     });
     log.info("[open-project] second-instance openProjectPath:", openProjectPath);
+    for (const bundlePath of parseOpenPaseoFilePathsFromArgv({
+      argv: commandLine,
+      isDefaultApp: false,
+    })) {
+      openPaseoBundle(bundlePath);
+    }
+    for (const url of parseQuickActionUrlsFromArgv(commandLine)) {
+      openQuickActionUrl(url);
+    }
     const win = BrowserWindow.getAllWindows()[0];
     if (win) {
       win.show();
@@ -518,7 +631,11 @@ function waitForDesktopSmokeStopRequest(): Promise<void> {
 }
 
 async function bootstrap(): Promise<void> {
-  if (!pendingOpenProjectPath && (await runCliPassthroughIfRequested())) {
+  const openedWithPath =
+    pendingOpenProjectPath ||
+    launchPaseoBundlePaths.length > 0 ||
+    launchQuickActionUrls.length > 0;
+  if (!openedWithPath && (await runCliPassthroughIfRequested())) {
     return;
   }
 
@@ -527,33 +644,19 @@ async function bootstrap(): Promise<void> {
   }
 
   await app.whenReady();
+  for (const bundlePath of launchPaseoBundlePaths) {
+    openPaseoBundle(bundlePath);
+  }
+  for (const url of launchQuickActionUrls) {
+    openQuickActionUrl(url);
+  }
+  if (app.isPackaged && !app.isDefaultProtocolClient(APP_SCHEME)) {
+    app.setAsDefaultProtocolClient(APP_SCHEME);
+  }
+  // First-run detection looks for desktop-settings.json, so it runs before anything creates it.
+  await getOnboardingStore().getState();
 
-  const appDistDir = getAppDistDir();
-  protocol.handle(APP_SCHEME, (request) => {
-    const { pathname, search, hash } = new URL(request.url);
-    const decodedPath = decodeURIComponent(pathname);
-
-    // Chromium can occasionally request the exported entrypoint directly.
-    // Canonicalize it back to the route URL so Expo Router sees `/`, not `/index.html`.
-    if (decodedPath.endsWith("/index.html")) {
-      const normalizedPath = decodedPath.slice(0, -"/index.html".length) || "/";
-      return Response.redirect(`${APP_SCHEME}://app${normalizedPath}${search}${hash}`, 307);
-    }
-
-    const filePath = path.join(appDistDir, decodedPath);
-    const relativePath = path.relative(appDistDir, filePath);
-
-    if (relativePath.startsWith("..") || path.isAbsolute(relativePath)) {
-      return new Response("Not found", { status: 404 });
-    }
-
-    // SPA fallback: serve index.html for routes without a file extension
-    if (!relativePath || !path.extname(relativePath)) {
-      return net.fetch(pathToFileURL(path.join(appDistDir, "index.html")).toString());
-    }
-
-    return net.fetch(pathToFileURL(filePath).toString());
-  });
+  protocol.handle(APP_SCHEME, handleAppProtocolRequest);
 
   applyAppIcon();
   setupApplicationMenu();
@@ -563,9 +666,86 @@ async function bootstrap(): Promise<void> {
   }
   registerDaemonManager();
   registerWindowManager();
+  registerInitStateHandler();
+  registerInitStateProvider("zoom", (win) => getWindowZoomState(win));
+  registerInitStateProvider("theme", () => resolveSystemWindowTheme());
+  registerInitStateProvider("locale", () => getSystemLocaleInfo());
+  registerInitStateProvider("profile", () => ({
+    packaged: app.isPackaged,
+    devWorktreeName,
+  }));
+  await startFeatureFlags().catch((error) => {
+    log.warn("[feature-flags] failed to load flags", error);
+  });
+  // Transport observers go in before any window can open a daemon session.
+  startNativeApprovalGate();
+  startWorkspaceTrustGate();
+  startActiveAgentTracking();
+  startTimelineCache();
+  startUnreadIndicators();
+  startActivityJournal();
+  startTaskArtifactCollection();
   registerDialogHandlers();
   registerNotificationHandlers();
   registerOpenerHandlers();
+  registerSystemLocaleEvents();
+  registerTimeZoneEvents();
+  registerDisplayConfigurationEvents();
+  registerPowerStatusEvents();
+  registerWindowVisibilityEvents();
+  registerAccessibilityEvents();
+  registerKeyboardLayoutEvents();
+  registerSystemTextScaleEvents({ onChange: setSystemTextScale });
+
+  try {
+    await applyProxySettings((await getDesktopSettingsStore().get()).proxy);
+  } catch (error) {
+    log.warn("[proxy] failed to apply proxy settings", error);
+  }
+
+  void getDesktopSettingsStore()
+    .get()
+    .then((settings) => applyClipboardHistorySettings(settings.clipboardHistory))
+    .catch((error) => {
+      log.warn("[clipboard-history] failed to apply settings", error);
+    });
+
+  configureStatusPopover({
+    preloadPath: getPreloadPath(),
+    resolveAppUrl: (route) =>
+      app.isPackaged ? `${APP_SCHEME}://app${route}` : `${DEV_SERVER_URL}${route}`,
+  });
+  configurePresentationWindow({
+    preloadPath: getPreloadPath(),
+    resolveAppUrl: (route) =>
+      app.isPackaged ? `${APP_SCHEME}://app${route}` : `${DEV_SERVER_URL}${route}`,
+    appScheme: APP_SCHEME,
+    handleAppRequest: handleAppProtocolRequest,
+  });
+  const startupSettings = await getDesktopSettingsStore().get();
+  applyStatusPopoverSettings(startupSettings.statusPopover);
+  await initializeAttachmentStorage();
+  setNotificationCoalesceWindow(startupSettings.notifications.coalesceWindowSeconds);
+  applyPauseAllShortcut(startupSettings.shortcuts.pauseAllAgents);
+  void applyAutomationSettings(startupSettings.automation).catch((error) => {
+    log.warn("[automation] failed to start the control API", error);
+  });
+
+  void getAboutInfo().catch((error) => {
+    log.warn("[about] failed to resolve about info", error);
+  });
+
+  await restoreDaemonTlsTrust();
+  startDaemonHealthWatchdog();
+  registerResumeReconnect();
+  registerDesktopDiskSpaceEvents();
+  startDesktopMaintenance();
+  startScratchWorkspaceCleanup();
+  startUsageTracking();
+
+  void startDesktopLocalTasks().catch((error) => {
+    log.warn("[local-tasks] failed to start the scheduler", error);
+  });
 
   void autoUpdateSkillsIfInstalled().catch((error) => {
     log.warn("[integrations] auto-update skills failed", error);
@@ -573,6 +753,11 @@ async function bootstrap(): Promise<void> {
 
   await createMainWindow();
 
+  // After the first window, so a trust prompt for a resumed folder has a parent.
+  void startWatchFolders().catch((error) => {
+    log.warn("[watch-folders] failed to resume watches", error);
+  });
+
   app.on("activate", async () => {
     if (BrowserWindow.getAllWindows().length === 0) {
       await createMainWindow();
@@ -597,12 +782,23 @@ app.on(
   createBeforeQuitHandler({
     app,
     closeTransportSessions: closeAllTransportSessions,
-    stopDesktopManagedDaemonIfNeeded: () =>
+    confirmQuit: async () => {
+      const choice = await confirmQuitWithRunningWork({
+        isDesktopManagedDaemonRunning: isDesktopManagedDaemonRunningSync,
+      });
+      // Closing the last window quits on Windows and Linux; cancelling must not leave none.
+      if (choice === "cancel" && BrowserWindow.getAllWindows().length === 0) {
+        await createMainWindow();
+      }
+      return choice;
+    },
+    stopDesktopManagedDaemonIfNeeded: (choice) =>
       stopDesktopManagedDaemonOnQuitIfNeeded({
         settingsStore: getDesktopSettingsStore(),
         isDesktopManagedDaemonRunning: isDesktopManagedDaemonRunningSync,
         stopDaemon: stopDesktopDaemonViaCli,
         showShutdownFeedback: showDaemonShutdownDialog,
+        choice,
       }),
     onStopError: (error) => {
       log.error("[desktop daemon] failed to stop managed daemon on quit", error);
@@ -1,8 +1,11 @@
-import { mkdtempSync, mkdirSync } from "node:fs";
+import { mkdtempSync, mkdirSync, writeFileSync } from "node:fs";
 import { tmpdir } from "node:os";
 import path from "node:path";
 import { describe, expect, it } from "vitest";
-import { parseOpenProjectPathFromArgv } from "./open-project-routing";
+import {
+  parseOpenPaseoFilePathsFromArgv,
+  parseOpenProjectPathFromArgv,
+} from "./open-project-routing";
 
 describe("open-project-routing", () => {
   it("returns a bare absolute path argument", () => {
@@ -62,4 +65,19 @@ describe("open-project-routing", () => {
       }),
     ).toBe(projectPath);
   });
+
+  it("collects existing .paseo files and leaves them out of project routing", () => {
+    const dir = mkdtempSync(path.join(tmpdir(), "paseo-open-file-"));
+    const bundlePath = path.join(dir, "session.PASEO");
+    writeFileSync(bundlePath, "{}");
+    const argv = [
+      "C:\\Program Files\\Paseo\\Paseo.exe",
+      bundlePath,
+      path.join(dir, "missing.paseo"),
+      path.join(dir, "notes.txt"),
+    ];
+
+    expect(parseOpenPaseoFilePathsFromArgv({ argv, isDefaultApp: false })).toEqual([bundlePath]);
+    expect(parseOpenProjectPathFromArgv({ argv, isDefaultApp: false })).toBeNull();
+  });
 });
@@ -3,6 +3,7 @@ import path from "node:path";
 
 const OPEN_PROJECT_FLAG = "--open-project";
 const OPEN_PROJECT_IGNORED_ARG_PREFIXES = ["-psn_", "--no-sandbox"];
+const PASEO_FILE_EXTENSION = ".paseo";
 
 function isExistingDirectoryAbsolutePath(candidate: string): boolean {
   if (!path.isAbsolute(candidate) || !existsSync(candidate)) {
@@ -16,13 +17,38 @@ function isExistingDirectoryAbsolutePath(candidate: string): boolean {
   }
 }
 
+function isExistingFileAbsolutePath(candidate: string): boolean {
+  try {
+    return path.isAbsolute(candidate) && statSync(candidate).isFile();
+  } catch {
+    return false;
+  }
+}
+
+function effectiveArgsFromArgv(input: { argv: string[]; isDefaultApp: boolean }): string[] {
+  return input.argv
+    .slice(input.isDefaultApp ? 2 : 1)
+    .filter((arg) => !OPEN_PROJECT_IGNORED_ARG_PREFIXES.some((prefix) => arg.startsWith(prefix)));
+}
+
+/** `.paseo` bundles passed by the OS file association on Windows and Linux. */
+export function parseOpenPaseoFilePathsFromArgv(input: {
+  argv: string[];
+  isDefaultApp: boolean;
+}): string[] {
+  return effectiveArgsFromArgv(input).filter(
+    (arg) =>
+      !arg.startsWith("-") &&
+      path.extname(arg).toLowerCase() === PASEO_FILE_EXTENSION &&
+      isExistingFileAbsolutePath(arg),
+  );
+}
+
 export function parseOpenProjectPathFromArgv(input: {
   argv: string[];
   isDefaultApp: boolean;
 }): string | null {
-  const effectiveArgs = input.argv
-    .slice(input.isDefaultApp ? 2 : 1)
-    .filter((arg) => !OPEN_PROJECT_IGNORED_ARG_PREFIXES.some((prefix) => arg.startsWith(prefix)));
+  const effectiveArgs = effectiveArgsFromArgv(input);
 
   const positionalProjectPath = effectiveArgs.find(
     (arg) => !arg.startsWith("-") && isExistingDirectoryAbsolutePath(arg),
@@ -1,9 +1,17 @@
-import { contextBridge, ipcRenderer } from "electron";
+import { contextBridge, ipcRenderer, webFrame } from "electron";
 
 type EventHandler = (payload: unknown) => void;
 
+// Read before the app bundle runs, so first paint already has the right zoom, theme and locale.
+const initialState: unknown = ipcRenderer.sendSync("paseo:window:getInitState");
+const initialZoom = (initialState as { zoom?: { effectiveFactor?: unknown } } | null)?.zoom;
+if (typeof initialZoom?.effectiveFactor === "number") {
+  webFrame.setZoomFactor(initialZoom.effectiveFactor);
+}
+
 contextBridge.exposeInMainWorld("paseoDesktop", {
   platform: process.platform,
+  initialState,
   invoke: (command: string, args?: Record<string, unknown>) =>
     ipcRenderer.invoke("paseo:invoke", command, args),
   getPendingOpenProject: () =>
@@ -37,7 +45,10 @@ contextBridge.exposeInMainWorld("paseoDesktop", {
           ipcRenderer.removeListener("paseo:window:resized", listener);
         };
       },
-      setBadgeCount: (count?: number) => ipcRenderer.invoke("paseo:window:setBadgeCount", count),
+      setViewedAgent: (agentId: string | null) =>
+        ipcRenderer.invoke("paseo:window:setViewedAgent", agentId),
+      reportUnreadCount: (count: number) =>
+        ipcRenderer.invoke("paseo:window:reportUnreadCount", count),
     }),
   },
   dialog: {
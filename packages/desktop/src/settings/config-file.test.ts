@@ -0,0 +1,115 @@
+import { mkdtemp, readdir, readFile, rm, stat, writeFile } from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+import {
+  updateJsonConfigFile,
+  withConfigFileLock,
+  writeConfigFile,
+  writeJsonConfigFile,
+} from "./config-file";
+
+describe("config file writes", () => {
+  let dir: string;
+  let filePath: string;
+
+  beforeEach(async () => {
+    dir = await mkdtemp(path.join(os.tmpdir(), "paseo-config-file-"));
+    filePath = path.join(dir, "nested", "settings.json");
+  });
+
+  afterEach(async () => {
+    await rm(dir, { recursive: true, force: true });
+  });
+
+  it("serialises concurrent writers and leaves no temp or lock files behind", async () => {
+    await Promise.all(
+      Array.from({ length: 20 }, (_, index) =>
+        writeJsonConfigFile(filePath, { version: 1, index, padding: "x".repeat(index * 500) }),
+      ),
+    );
+
+    const document = JSON.parse(await readFile(filePath, "utf8")) as { index: number };
+    expect(document.index).toBe(19);
+    expect(await readdir(path.dirname(filePath))).toEqual(["settings.json"]);
+  });
+
+  it("applies the mode to files that already exist", async () => {
+    await writeConfigFile(filePath, "first", { mode: 0o644 });
+    await writeConfigFile(filePath, "second", { mode: 0o600 });
+
+    expect(await readFile(filePath, "utf8")).toBe("second");
+    if (process.platform !== "win32") {
+      expect((await stat(filePath)).mode & 0o777).toBe(0o600);
+    }
+  });
+
+  it("takes over a lock left by a process that is gone", async () => {
+    await writeConfigFile(filePath, "before");
+    await writeFile(
+      `${filePath}.lock`,
+      JSON.stringify({ pid: 2 ** 30, acquiredAt: Date.now() }),
+      "utf8",
+    );
+
+    await writeConfigFile(filePath, "after");
+    expect(await readFile(filePath, "utf8")).toBe("after");
+    expect(await readdir(path.dirname(filePath))).toEqual(["settings.json"]);
+  });
+
+  it("reads and writes under one lock so concurrent updates all land", async () => {
+    await Promise.all(
+      Array.from({ length: 20 }, (_, index) =>
+        updateJsonConfigFile(filePath, (current) => {
+          const seen = Array.isArray(current) ? (current as number[]) : [];
+          return [...seen, index];
+        }),
+      ),
+    );
+
+    const document = JSON.parse(await readFile(filePath, "utf8")) as number[];
+    expect([...document].sort((a, b) => a - b)).toEqual(Array.from({ length: 20 }, (_, i) => i));
+  });
+
+  it("passes null for a missing or unreadable file", async () => {
+    const seen: unknown[] = [];
+    await updateJsonConfigFile(filePath, (current) => {
+      seen.push(current);
+      return { version: 1 };
+    });
+    await writeConfigFile(filePath, "{not json");
+    await updateJsonConfigFile(filePath, (current) => {
+      seen.push(current);
+      return { version: 2 };
+    });
+
+    expect(seen).toEqual([null, null]);
+    expect(JSON.parse(await readFile(filePath, "utf8"))).toEqual({ version: 2 });
+  });
+
+  it("leaves a lock alone on release once another holder has replaced it", async () => {
+    const lockPath = `${filePath}.lock`;
+    const other = JSON.stringify({ pid: process.pid, acquiredAt: Date.now(), token: "other" });
+    await writeConfigFile(filePath, "before");
+
+    await withConfigFileLock(filePath, async () => {
+      await writeFile(lockPath, other, "utf8");
+    });
+
+    expect(await readFile(lockPath, "utf8")).toBe(other);
+  });
+
+  it("waits for a live holder and gives up after the timeout", async () => {
+    await writeConfigFile(filePath, "before");
+    await writeFile(
+      `${filePath}.lock`,
+      JSON.stringify({ pid: process.pid, acquiredAt: Date.now() }),
+      "utf8",
+    );
+
+    await expect(
+      withConfigFileLock(filePath, async () => "unreachable", { timeoutMs: 100 }),
+    ).rejects.toThrow(/Timed out/);
+    expect(await readFile(filePath, "utf8")).toBe("before");
+  });
+});
@@ -0,0 +1,252 @@
+import { randomUUID } from "node:crypto";
+import { link, mkdir, open, readFile, rename, rm, stat } from "node:fs/promises";
+import path from "node:path";
+
+const LOCK_RETRY_MS = 25;
+const LOCK_TIMEOUT_MS = 5_000;
+// A write holds the lock for milliseconds; anything this old was left by a hung or dead writer.
+const LOCK_STALE_MS = 30_000;
+
+interface LockOwner {
+  pid: number;
+  acquiredAt: number;
+  /** Tells this holder's lock apart from one that replaced it. */
+  token: string;
+}
+
+/** What a stale lock looked like when it was judged, to recognise it again after claiming it. */
+interface LockSnapshot {
+  contents: string;
+  mtimeMs: number;
+}
+
+// Serialises writers inside this process; the lock file covers other instances.
+const pendingWrites = new Map<string, Promise<unknown>>();
+
+function delay(ms: number): Promise<void> {
+  return new Promise((resolve) => setTimeout(resolve, ms));
+}
+
+function isProcessAlive(pid: number): boolean {
+  try {
+    process.kill(pid, 0);
+    return true;
+  } catch (error) {
+    // EPERM: the process exists but belongs to someone else.
+    return (error as NodeJS.ErrnoException).code === "EPERM";
+  }
+}
+
+async function readLockSnapshot(lockPath: string): Promise<LockSnapshot | null> {
+  try {
+    const [contents, info] = await Promise.all([readFile(lockPath, "utf8"), stat(lockPath)]);
+    return { contents, mtimeMs: info.mtimeMs };
+  } catch {
+    return null;
+  }
+}
+
+function parseLockOwner(contents: string): LockOwner | null {
+  try {
+    const parsed: unknown = JSON.parse(contents);
+    if (
+      typeof parsed === "object" &&
+      parsed !== null &&
+      typeof (parsed as LockOwner).pid === "number" &&
+      typeof (parsed as LockOwner).acquiredAt === "number"
+    ) {
+      return parsed as LockOwner;
+    }
+  } catch {
+    // Caught between create and write.
+  }
+  return null;
+}
+
+function isStaleLock(snapshot: LockSnapshot, now: number): boolean {
+  const owner = parseLockOwner(snapshot.contents);
+  if (owner) {
+    return now - owner.acquiredAt > LOCK_STALE_MS || !isProcessAlive(owner.pid);
+  }
+  // Unreadable owner: only a writer that died right after creating the file leaves it empty.
+  return now - snapshot.mtimeMs > LOCK_STALE_MS;
+}
+
+/**
+ * Moves a stale lock out of the way. Renaming is atomic, so of several
+ * instances taking over the same lock only one gets it; if the file it got is
+ * no longer the one judged stale, a live holder replaced it meanwhile and it
+ * is put back.
+ */
+async function takeOverStaleLock(lockPath: string, judged: LockSnapshot): Promise<void> {
+  const claimedPath = `${lockPath}.${process.pid}.${randomUUID()}.stale`;
+  try {
+    await rename(lockPath, claimedPath);
+  } catch (error) {
+    if ((error as NodeJS.ErrnoException).code === "ENOENT") {
+      return;
+    }
+    throw error;
+  }
+  const claimed = await readLockSnapshot(claimedPath);
+  if (!claimed || claimed.contents !== judged.contents || claimed.mtimeMs !== judged.mtimeMs) {
+    // link() fails if yet another writer already holds the path, which is theirs to keep.
+    await link(claimedPath, lockPath).catch(() => undefined);
+  }
+  await rm(claimedPath, { force: true });
+}
+
+async function acquireLock(lockPath: string, timeoutMs: number): Promise<string> {
+  const deadline = Date.now() + timeoutMs;
+  const token = randomUUID();
+  for (;;) {
+    try {
+      const handle = await open(lockPath, "wx");
+      try {
+        const owner: LockOwner = { pid: process.pid, acquiredAt: Date.now(), token };
+        await handle.writeFile(JSON.stringify(owner), "utf8");
+      } finally {
+        await handle.close();
+      }
+      return token;
+    } catch (error) {
+      if ((error as NodeJS.ErrnoException).code !== "EEXIST") {
+        throw error;
+      }
+    }
+    const snapshot = await readLockSnapshot(lockPath);
+    if (snapshot && isStaleLock(snapshot, Date.now())) {
+      await takeOverStaleLock(lockPath, snapshot);
+      continue;
+    }
+    if (Date.now() >= deadline) {
+      throw new Error(`Timed out waiting for another Paseo instance to finish writing ${lockPath}`);
+    }
+    await delay(LOCK_RETRY_MS);
+  }
+}
+
+/** Removes the lock only while it is still ours; a holder that overran may have lost it. */
+async function releaseLock(lockPath: string, token: string): Promise<void> {
+  const snapshot = await readLockSnapshot(lockPath);
+  if (snapshot && parseLockOwner(snapshot.contents)?.token === token) {
+    await rm(lockPath, { force: true });
+  }
+}
+
+/**
+ * Runs `task` holding an advisory `<file>.lock` next to `filePath`, so two
+ * app instances sharing a data directory never write the same file at once.
+ * Locks left by a crashed process are taken over.
+ */
+export async function withConfigFileLock<T>(
+  filePath: string,
+  task: () => Promise<T>,
+  options: { timeoutMs?: number } = {},
+): Promise<T> {
+  const previous = pendingWrites.get(filePath) ?? Promise.resolve();
+  const run = previous
+    .catch(() => undefined)
+    .then(async () => {
+      const lockPath = `${filePath}.lock`;
+      const token = await acquireLock(lockPath, options.timeoutMs ?? LOCK_TIMEOUT_MS);
+      try {
+        return await task();
+      } finally {
+        await releaseLock(lockPath, token);
+      }
+    });
+  pendingWrites.set(filePath, run);
+  try {
+    return await run;
+  } finally {
+    if (pendingWrites.get(filePath) === run) {
+      pendingWrites.delete(filePath);
+    }
+  }
+}
+
+async function syncDirectory(dir: string): Promise<void> {
+  // Persists the rename itself. Windows cannot open directories, and NTFS journals renames anyway.
+  if (process.platform === "win32") {
+    return;
+  }
+  try {
+    const handle = await open(dir, "r");
+    try {
+      await handle.sync();
+    } finally {
+      await handle.close();
+    }
+  } catch {
+    // Best effort: some filesystems refuse fsync on directories.
+  }
+}
+
+async function replaceFile(
+  filePath: string,
+  contents: string,
+  options: { mode?: number },
+): Promise<void> {
+  const tempFilePath = `${filePath}.tmp`;
+  const handle = await open(tempFilePath, "w", options.mode);
+  try {
+    await handle.writeFile(contents, "utf8");
+    if (options.mode !== undefined) {
+      await handle.chmod(options.mode);
+    }
+    await handle.sync();
+  } finally {
+    await handle.close();
+  }
+  await rename(tempFilePath, filePath);
+  await syncDirectory(path.dirname(filePath));
+}
+
+function formatJson(document: unknown): string {
+  return `${JSON.stringify(document, null, 2)}\n`;
+}
+
+/**
+ * Crash-safe replacement of `filePath`: the contents are flushed to
+ * `<file>.tmp` before it is renamed over the target, all under the file's
+ * lock. A crash at any point leaves either the old or the new file, never a
+ * torn one. `mode` is applied to the file even when it already exists.
+ */
+export async function writeConfigFile(
+  filePath: string,
+  contents: string,
+  options: { mode?: number } = {},
+): Promise<void> {
+  await mkdir(path.dirname(filePath), { recursive: true });
+  await withConfigFileLock(filePath, () => replaceFile(filePath, contents, options));
+}
+
+export async function writeJsonConfigFile(
+  filePath: string,
+  document: unknown,
+  options: { mode?: number } = {},
+): Promise<void> {
+  await writeConfigFile(filePath, formatJson(document), options);
+}
+
+/**
+ * Read-modify-write of a JSON config file under its lock. `update` gets what
+ * is on disk right now (`null` when the file is missing or not JSON), so a
+ * change another instance made since this one last read is never lost.
+ */
+export async function updateJsonConfigFile<T>(
+  filePath: string,
+  update: (current: unknown) => T | Promise<T>,
+  options: { mode?: number } = {},
+): Promise<T> {
+  await mkdir(path.dirname(filePath), { recursive: true });
+  return await withConfigFileLock(filePath, async () => {
+    const current: unknown = await readFile(filePath, "utf8")
+      .then((raw) => JSON.parse(raw) as unknown)
+      .catch(() => null);
+    const next = await update(current);
+    await replaceFile(filePath, formatJson(next), options);
+    return next;
+  });
+}
@@ -16,6 +16,8 @@ function createStoreMock(): DesktopSettingsStore {
       daemon: {
         manageBuiltInDaemon: false,
         keepRunningAfterQuit: true,
+        port: null,
+        lanBindListen: null,
       },
     })),
   };
@@ -42,6 +44,18 @@ describe("desktop-settings-commands", () => {
     });
   });
 
+  it("drops main-process-only settings from renderer patches", async () => {
+    const store = createStoreMock();
+    const handlers = createDesktopSettingsCommandHandlers({ settingsStore: store });
+
+    await handlers.patch_desktop_settings({
+      approvals: { requireTouchId: false },
+      releaseChannel: "beta",
+    });
+
+    expect(store.patch).toHaveBeenCalledWith({ approvals: {}, releaseChannel: "beta" });
+  });
+
   it("accepts legacy renderer settings migration payloads", async () => {
     const store = createStoreMock();
     const handlers = createDesktopSettingsCommandHandlers({ settingsStore: store });
@@ -57,6 +71,8 @@ describe("desktop-settings-commands", () => {
       daemon: {
         manageBuiltInDaemon: false,
         keepRunningAfterQuit: true,
+        port: null,
+        lanBindListen: null,
       },
     });
     expect(store.migrateLegacyRendererSettings).toHaveBeenCalledWith({
@@ -64,4 +80,36 @@ describe("desktop-settings-commands", () => {
       manageBuiltInDaemon: false,
     });
   });
+
+  it("notifies listeners with the persisted settings after a patch", async () => {
+    const store = createStoreMock();
+    const onSettingsChanged = vi.fn();
+    const handlers = createDesktopSettingsCommandHandlers({
+      settingsStore: store,
+      onSettingsChanged,
+    });
+
+    await handlers.patch_desktop_settings({ releaseChannel: "beta" });
+
+    expect(onSettingsChanged).toHaveBeenCalledWith({
+      ...DEFAULT_DESKTOP_SETTINGS,
+      releaseChannel: "beta",
+    });
+  });
+
+  it("reports the replaced settings only when a patch changes them", async () => {
+    const store = createStoreMock();
+    const onSettingsOverwritten = vi.fn();
+    const handlers = createDesktopSettingsCommandHandlers({
+      settingsStore: store,
+      onSettingsOverwritten,
+    });
+
+    await handlers.patch_desktop_settings({ releaseChannel: "beta" });
+    vi.mocked(store.patch).mockResolvedValueOnce(DEFAULT_DESKTOP_SETTINGS);
+    await handlers.patch_desktop_settings({ releaseChannel: "stable" });
+
+    expect(onSettingsOverwritten).toHaveBeenCalledTimes(1);
+    expect(onSettingsOverwritten).toHaveBeenCalledWith(DEFAULT_DESKTOP_SETTINGS);
+  });
 });
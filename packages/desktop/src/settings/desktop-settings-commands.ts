@@ -1,15 +1,32 @@
-import type { DesktopSettingsStore } from "./desktop-settings.js";
+import {
+  omitMainProcessOnlySettings,
+  type DesktopSettings,
+  type DesktopSettingsStore,
+} from "./desktop-settings.js";
 
 export type DesktopCommandHandler = (args?: Record<string, unknown>) => Promise<unknown> | unknown;
 
 export function createDesktopSettingsCommandHandlers({
   settingsStore,
+  onSettingsChanged,
+  onSettingsOverwritten,
 }: {
   settingsStore: DesktopSettingsStore;
+  onSettingsChanged?: (settings: DesktopSettings) => void;
+  /** Called with the settings a patch replaced, when it changed anything. */
+  onSettingsOverwritten?: (previous: DesktopSettings) => void;
 }): Record<string, DesktopCommandHandler> {
   return {
     get_desktop_settings: () => settingsStore.get(),
-    patch_desktop_settings: (args) => settingsStore.patch(args),
+    patch_desktop_settings: async (args) => {
+      const previous = onSettingsOverwritten ? await settingsStore.get() : null;
+      const settings = await settingsStore.patch(omitMainProcessOnlySettings(args));
+      if (previous && JSON.stringify(previous) !== JSON.stringify(settings)) {
+        onSettingsOverwritten?.(previous);
+      }
+      onSettingsChanged?.(settings);
+      return settings;
+    },
     migrate_legacy_desktop_settings: (args) => settingsStore.migrateLegacyRendererSettings(args),
   };
 }
@@ -68,7 +68,23 @@ describe("desktop-settings", () => {
       daemon: {
         manageBuiltInDaemon: true,
         keepRunningAfterQuit: false,
+        port: null,
+        lanBindListen: null,
+        confirmQuit: true,
       },
+      proxy: DEFAULT_DESKTOP_SETTINGS.proxy,
+      transport: DEFAULT_DESKTOP_SETTINGS.transport,
+      approvals: DEFAULT_DESKTOP_SETTINGS.approvals,
+      clipboardHistory: DEFAULT_DESKTOP_SETTINGS.clipboardHistory,
+      statusPopover: DEFAULT_DESKTOP_SETTINGS.statusPopover,
+      notifications: DEFAULT_DESKTOP_SETTINGS.notifications,
+      retention: DEFAULT_DESKTOP_SETTINGS.retention,
+      shortcuts: DEFAULT_DESKTOP_SETTINGS.shortcuts,
+      digest: DEFAULT_DESKTOP_SETTINGS.digest,
+      presentation: DEFAULT_DESKTOP_SETTINGS.presentation,
+      fileReads: DEFAULT_DESKTOP_SETTINGS.fileReads,
+      automation: DEFAULT_DESKTOP_SETTINGS.automation,
+      outputs: DEFAULT_DESKTOP_SETTINGS.outputs,
     });
   });
 
@@ -89,7 +105,23 @@ describe("desktop-settings", () => {
       daemon: {
         manageBuiltInDaemon: true,
         keepRunningAfterQuit: false,
+        port: null,
+        lanBindListen: null,
+        confirmQuit: true,
       },
+      proxy: DEFAULT_DESKTOP_SETTINGS.proxy,
+      transport: DEFAULT_DESKTOP_SETTINGS.transport,
+      approvals: DEFAULT_DESKTOP_SETTINGS.approvals,
+      clipboardHistory: DEFAULT_DESKTOP_SETTINGS.clipboardHistory,
+      statusPopover: DEFAULT_DESKTOP_SETTINGS.statusPopover,
+      notifications: DEFAULT_DESKTOP_SETTINGS.notifications,
+      retention: DEFAULT_DESKTOP_SETTINGS.retention,
+      shortcuts: DEFAULT_DESKTOP_SETTINGS.shortcuts,
+      digest: DEFAULT_DESKTOP_SETTINGS.digest,
+      presentation: DEFAULT_DESKTOP_SETTINGS.presentation,
+      fileReads: DEFAULT_DESKTOP_SETTINGS.fileReads,
+      automation: DEFAULT_DESKTOP_SETTINGS.automation,
+      outputs: DEFAULT_DESKTOP_SETTINGS.outputs,
     });
     expect(files).toEqual(["desktop-settings.json"]);
   });
@@ -120,8 +152,147 @@ describe("desktop-settings", () => {
       daemon: {
         manageBuiltInDaemon: false,
         keepRunningAfterQuit: false,
+        port: null,
+        lanBindListen: null,
+        confirmQuit: true,
       },
+      proxy: DEFAULT_DESKTOP_SETTINGS.proxy,
+      transport: DEFAULT_DESKTOP_SETTINGS.transport,
+      approvals: DEFAULT_DESKTOP_SETTINGS.approvals,
+      clipboardHistory: DEFAULT_DESKTOP_SETTINGS.clipboardHistory,
+      statusPopover: DEFAULT_DESKTOP_SETTINGS.statusPopover,
+      notifications: DEFAULT_DESKTOP_SETTINGS.notifications,
+      retention: DEFAULT_DESKTOP_SETTINGS.retention,
+      shortcuts: DEFAULT_DESKTOP_SETTINGS.shortcuts,
+      digest: DEFAULT_DESKTOP_SETTINGS.digest,
+      presentation: DEFAULT_DESKTOP_SETTINGS.presentation,
+      fileReads: DEFAULT_DESKTOP_SETTINGS.fileReads,
+      automation: DEFAULT_DESKTOP_SETTINGS.automation,
+      outputs: DEFAULT_DESKTOP_SETTINGS.outputs,
     });
     expect(ignoredSecondMigration).toEqual(migrated);
   });
+
+  it("patches the proxy override and clears blank urls", async () => {
+    const userDataPath = await createTempUserDataDir();
+    directories.add(userDataPath);
+    const store = createDesktopSettingsStore({ userDataPath });
+
+    const fixed = await store.patch({
+      proxy: { mode: "fixed", url: " http=proxy.corp:8080 " },
+    });
+    const cleared = await store.patch({
+      proxy: { mode: "bogus", url: "  " },
+    });
+
+    expect(fixed.proxy).toEqual({ mode: "fixed", url: "http=proxy.corp:8080" });
+    expect(cleared.proxy).toEqual({ mode: "fixed", url: null });
+  });
+
+  it("only accepts zlib compression levels between 1 and 9", async () => {
+    const userDataPath = await createTempUserDataDir();
+    directories.add(userDataPath);
+    const store = createDesktopSettingsStore({ userDataPath });
+
+    const fast = await store.patch({ transport: { compression: false, compressionLevel: 1 } });
+    const rejected = await store.patch({ transport: { compressionLevel: 12 } });
+
+    expect(fast.transport).toMatchObject({ compression: false, compressionLevel: 1 });
+    expect(rejected.transport).toMatchObject({ compression: false, compressionLevel: 1 });
+  });
+
+  it("bounds the post-sleep catch-up settings", async () => {
+    const userDataPath = await createTempUserDataDir();
+    directories.add(userDataPath);
+    const store = createDesktopSettingsStore({ userDataPath });
+
+    const never = await store.patch({
+      transport: { catchUpAfterSleepMinutes: 0, catchUpEventsPerAgent: 20 },
+    });
+    const rejected = await store.patch({
+      transport: { catchUpAfterSleepMinutes: -5, catchUpEventsPerAgent: 10_000 },
+    });
+
+    expect(never.transport).toMatchObject({
+      catchUpAfterSleepMinutes: 0,
+      catchUpEventsPerAgent: 20,
+    });
+    expect(rejected.transport).toEqual(never.transport);
+  });
+
+  it("trims the pause-all shortcut and turns blank accelerators off", async () => {
+    const userDataPath = await createTempUserDataDir();
+    directories.add(userDataPath);
+    const store = createDesktopSettingsStore({ userDataPath });
+
+    const bound = await store.patch({ shortcuts: { pauseAllAgents: " CmdOrCtrl+Alt+Shift+P " } });
+    const cleared = await store.patch({ shortcuts: { pauseAllAgents: "" } });
+
+    expect(bound.shortcuts).toEqual({ pauseAllAgents: "CmdOrCtrl+Alt+Shift+P" });
+    expect(cleared.shortcuts).toEqual({ pauseAllAgents: null });
+  });
+
+  it("only keeps absolute digest folders", async () => {
+    const userDataPath = await createTempUserDataDir();
+    directories.add(userDataPath);
+    const store = createDesktopSettingsStore({ userDataPath });
+    const folder = path.join(userDataPath, "digests");
+
+    const chosen = await store.patch({ digest: { folder: ` ${folder} `, format: "pdf" } });
+    const relative = await store.patch({ digest: { folder: "digests", format: "docx" } });
+
+    expect(chosen.digest).toEqual({ folder, format: "pdf" });
+    expect(relative.digest).toEqual(chosen.digest);
+  });
+
+  it("only keeps absolute outputs folders", async () => {
+    const userDataPath = await createTempUserDataDir();
+    directories.add(userDataPath);
+    const store = createDesktopSettingsStore({ userDataPath });
+    const folder = path.join(userDataPath, "Paseo Outputs");
+
+    const chosen = await store.patch({ outputs: { folder, collectOnCompletion: true } });
+    const relative = await store.patch({ outputs: { folder: "Paseo Outputs" } });
+    const cleared = await store.patch({ outputs: { folder: null } });
+
+    expect(chosen.outputs).toEqual({ folder, collectOnCompletion: true });
+    expect(relative.outputs).toEqual(chosen.outputs);
+    expect(cleared.outputs).toEqual({ folder: null, collectOnCompletion: true });
+  });
+
+  it("bounds the presentation mode maximum to a day", async () => {
+    const userDataPath = await createTempUserDataDir();
+    directories.add(userDataPath);
+    const store = createDesktopSettingsStore({ userDataPath });
+
+    const shortened = await store.patch({ presentation: { maxDurationMinutes: 45 } });
+    const rejected = await store.patch({ presentation: { maxDurationMinutes: 60 * 48 } });
+
+    expect(shortened.presentation).toEqual({ maxDurationMinutes: 45 });
+    expect(rejected.presentation).toEqual(shortened.presentation);
+  });
+
+  it("keeps the file read ceiling between 1 MB and 1 GB", async () => {
+    const userDataPath = await createTempUserDataDir();
+    directories.add(userDataPath);
+    const store = createDesktopSettingsStore({ userDataPath });
+
+    const raised = await store.patch({ fileReads: { maxSizeMb: 200 } });
+    const rejected = await store.patch({ fileReads: { maxSizeMb: 0 } });
+
+    expect(raised.fileReads).toEqual({ maxSizeMb: 200 });
+    expect(rejected.fileReads).toEqual(raised.fileReads);
+  });
+
+  it("keeps the Dock icon setting separate from the menu bar icon", async () => {
+    const userDataPath = await createTempUserDataDir();
+    directories.add(userDataPath);
+    const store = createDesktopSettingsStore({ userDataPath });
+
+    const hidden = await store.patch({ statusPopover: { showDockIcon: false } });
+    const ignored = await store.patch({ statusPopover: { showDockIcon: "no" } });
+
+    expect(hidden.statusPopover).toEqual({ enabled: false, showDockIcon: false });
+    expect(ignored.statusPopover).toEqual(hidden.statusPopover);
+  });
 });
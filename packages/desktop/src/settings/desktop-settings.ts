@@ -1,19 +1,112 @@
-import { access, mkdir, readFile, rename, writeFile } from "node:fs/promises";
 import path from "node:path";
 
 import type { AppReleaseChannel } from "../features/auto-updater.js";
+import type { DigestFormat } from "../features/daily-digest.js";
+import { updateJsonConfigFile } from "./config-file.js";
+import { isRecord } from "../utils/is-record.js";
+
+export type DesktopProxyMode = "system" | "direct" | "fixed" | "pac";
 
 export interface DesktopSettings {
   releaseChannel: AppReleaseChannel;
   daemon: {
     manageBuiltInDaemon: boolean;
     keepRunningAfterQuit: boolean;
+    /** Port the supervisor moved the daemon to when the configured one was taken. */
+    port: number | null;
+    /**
+     * The non-loopback listen address the user agreed to expose, firewall
+     * prompts included. Any other non-loopback address asks again.
+     */
+    lanBindListen: string | null;
+    /** Ask before quitting while agents or the managed daemon are running. */
+    confirmQuit: boolean;
+  };
+  proxy: {
+    mode: DesktopProxyMode;
+    /** Proxy rules for `fixed`, PAC script URL for `pac`. */
+    url: string | null;
+  };
+  transport: {
+    /** Offers permessage-deflate; only used if the daemon has `daemon.compression` enabled. */
+    compression: boolean;
+    /** zlib level (1–9) for permessage-deflate on the local daemon transport. */
+    compressionLevel: number;
+    /** Minutes asleep after which resume fetches a per-agent snapshot, not a replay; 0 is never. */
+    catchUpAfterSleepMinutes: number;
+    /** Latest timeline events fetched per agent when resuming from a snapshot. */
+    catchUpEventsPerAgent: number;
+  };
+  approvals: {
+    /** Ask for Touch ID after the native approval modal, where available. */
+    requireTouchId: boolean;
+  };
+  clipboardHistory: {
+    enabled: boolean;
+    maxEntries: number;
+    /** Keep snippets on disk across restarts; memory-only otherwise. */
+    persist: boolean;
+  };
+  statusPopover: {
+    /** Show a menu bar/tray icon that opens the mini status popover. */
+    enabled: boolean;
+    /** macOS only; the Dock icon comes back while the menu bar icon is off. */
+    showDockIcon: boolean;
+  };
+  notifications: {
+    /** Minimum gap between notifications for the same agent; 0 shows every one. */
+    coalesceWindowSeconds: number;
+  };
+  retention: {
+    /** Applied to each of logs, crash reports and caches on its own. */
+    maxAgeDays: number;
+    maxSizeMb: number;
+  };
+  shortcuts: {
+    /** Global accelerator for interrupting every running agent; off when null. */
+    pauseAllAgents: string | null;
+  };
+  digest: {
+    /** Where scheduled daily digests are written; scheduled runs fail until one is chosen. */
+    folder: string | null;
+    format: DigestFormat;
+  };
+  presentation: {
+    /** Presentation mode lets the display sleep again after this long. */
+    maxDurationMinutes: number;
+  };
+  fileReads: {
+    /** Largest file a read command returns whole; bigger ones must be previewed or streamed. */
+    maxSizeMb: number;
+  };
+  automation: {
+    /** Serve the local control API for scripts and launchers; off until the user opts in. */
+    enabled: boolean;
+  };
+  outputs: {
+    /** The "Paseo Outputs" folder that task artifacts are collected into. */
+    folder: string | null;
+    /** Collect artifacts each time an agent finishes a task; needs `folder`. */
+    collectOnCompletion: boolean;
   };
 }
 
 interface DesktopSettingsPatch {
   releaseChannel?: AppReleaseChannel;
   daemon?: Partial<DesktopSettings["daemon"]>;
+  proxy?: Partial<DesktopSettings["proxy"]>;
+  transport?: Partial<DesktopSettings["transport"]>;
+  approvals?: Partial<DesktopSettings["approvals"]>;
+  clipboardHistory?: Partial<DesktopSettings["clipboardHistory"]>;
+  statusPopover?: Partial<DesktopSettings["statusPopover"]>;
+  notifications?: Partial<DesktopSettings["notifications"]>;
+  retention?: Partial<DesktopSettings["retention"]>;
+  shortcuts?: Partial<DesktopSettings["shortcuts"]>;
+  digest?: Partial<DesktopSettings["digest"]>;
+  presentation?: Partial<DesktopSettings["presentation"]>;
+  fileReads?: Partial<DesktopSettings["fileReads"]>;
+  automation?: Partial<DesktopSettings["automation"]>;
+  outputs?: Partial<DesktopSettings["outputs"]>;
 }
 
 interface PersistedDesktopSettingsDocument {
@@ -35,14 +128,67 @@ export const DEFAULT_DESKTOP_SETTINGS: DesktopSettings = {
   daemon: {
     manageBuiltInDaemon: true,
     keepRunningAfterQuit: true,
+    port: null,
+    lanBindListen: null,
+    confirmQuit: true,
+  },
+  proxy: {
+    mode: "system",
+    url: null,
+  },
+  transport: {
+    compression: false,
+    compressionLevel: 1,
+    catchUpAfterSleepMinutes: 30,
+    catchUpEventsPerAgent: 50,
+  },
+  approvals: {
+    requireTouchId: false,
+  },
+  clipboardHistory: {
+    enabled: false,
+    maxEntries: 20,
+    persist: false,
+  },
+  statusPopover: {
+    enabled: false,
+    showDockIcon: true,
+  },
+  notifications: {
+    coalesceWindowSeconds: 10,
+  },
+  retention: {
+    maxAgeDays: 14,
+    maxSizeMb: 200,
+  },
+  shortcuts: {
+    pauseAllAgents: null,
+  },
+  digest: {
+    folder: null,
+    format: "markdown",
+  },
+  presentation: {
+    maxDurationMinutes: 120,
+  },
+  fileReads: {
+    maxSizeMb: 50,
+  },
+  automation: {
+    enabled: false,
+  },
+  outputs: {
+    folder: null,
+    collectOnCompletion: false,
   },
 };
 
 const DESKTOP_SETTINGS_FILENAME = "desktop-settings.json";
-
-function isRecord(value: unknown): value is Record<string, unknown> {
-  return typeof value === "object" && value !== null && !Array.isArray(value);
-}
+// Safeguards a compromised renderer must not switch off; each has a native confirmation instead.
+const MAIN_PROCESS_ONLY_SETTINGS: Record<string, readonly string[]> = {
+  approvals: ["requireTouchId"],
+  daemon: ["lanBindListen"],
+};
 
 function coerceReleaseChannel(value: unknown): AppReleaseChannel | null {
   if (value === "beta") {
@@ -58,6 +204,286 @@ function coerceBoolean(value: unknown): boolean | null {
   return typeof value === "boolean" ? value : null;
 }
 
+function coerceProxyMode(value: unknown): DesktopProxyMode | null {
+  if (value === "system" || value === "direct" || value === "fixed" || value === "pac") {
+    return value;
+  }
+  return null;
+}
+
+function coerceNullableString(value: unknown): string | null | undefined {
+  if (value === null) {
+    return null;
+  }
+  if (typeof value !== "string") {
+    return undefined;
+  }
+  const trimmed = value.trim();
+  return trimmed.length > 0 ? trimmed : null;
+}
+
+function coerceDaemonPort(value: unknown): number | null | undefined {
+  if (value === null) {
+    return null;
+  }
+  if (typeof value !== "number" || !Number.isInteger(value) || value < 1 || value > 65_535) {
+    return undefined;
+  }
+  return value;
+}
+
+function coerceCompressionLevel(value: unknown): number | null {
+  if (typeof value !== "number" || !Number.isInteger(value) || value < 1 || value > 9) {
+    return null;
+  }
+  return value;
+}
+
+function coerceTransportPatch(input: unknown): Partial<DesktopSettings["transport"]> {
+  if (!isRecord(input)) {
+    return {};
+  }
+
+  const patch: Partial<DesktopSettings["transport"]> = {};
+  const compression = coerceBoolean(input.compression);
+  if (compression !== null) {
+    patch.compression = compression;
+  }
+  const compressionLevel = coerceCompressionLevel(input.compressionLevel);
+  if (compressionLevel !== null) {
+    patch.compressionLevel = compressionLevel;
+  }
+  const minutes = input.catchUpAfterSleepMinutes;
+  if (typeof minutes === "number" && Number.isInteger(minutes) && minutes >= 0 && minutes <= 1440) {
+    patch.catchUpAfterSleepMinutes = minutes;
+  }
+  const events = input.catchUpEventsPerAgent;
+  if (typeof events === "number" && Number.isInteger(events) && events >= 1 && events <= 500) {
+    patch.catchUpEventsPerAgent = events;
+  }
+  return patch;
+}
+
+function coerceApprovalsPatch(input: unknown): Partial<DesktopSettings["approvals"]> {
+  if (!isRecord(input)) {
+    return {};
+  }
+
+  const patch: Partial<DesktopSettings["approvals"]> = {};
+  const requireTouchId = coerceBoolean(input.requireTouchId);
+  if (requireTouchId !== null) {
+    patch.requireTouchId = requireTouchId;
+  }
+  return patch;
+}
+
+function coerceClipboardHistoryMaxEntries(value: unknown): number | null {
+  if (typeof value !== "number" || !Number.isInteger(value) || value < 1 || value > 200) {
+    return null;
+  }
+  return value;
+}
+
+function coerceClipboardHistoryPatch(
+  input: unknown,
+): Partial<DesktopSettings["clipboardHistory"]> {
+  if (!isRecord(input)) {
+    return {};
+  }
+
+  const patch: Partial<DesktopSettings["clipboardHistory"]> = {};
+  const enabled = coerceBoolean(input.enabled);
+  if (enabled !== null) {
+    patch.enabled = enabled;
+  }
+  const maxEntries = coerceClipboardHistoryMaxEntries(input.maxEntries);
+  if (maxEntries !== null) {
+    patch.maxEntries = maxEntries;
+  }
+  const persist = coerceBoolean(input.persist);
+  if (persist !== null) {
+    patch.persist = persist;
+  }
+  return patch;
+}
+
+function coerceStatusPopoverPatch(input: unknown): Partial<DesktopSettings["statusPopover"]> {
+  if (!isRecord(input)) {
+    return {};
+  }
+
+  const patch: Partial<DesktopSettings["statusPopover"]> = {};
+  const enabled = coerceBoolean(input.enabled);
+  if (enabled !== null) {
+    patch.enabled = enabled;
+  }
+  const showDockIcon = coerceBoolean(input.showDockIcon);
+  if (showDockIcon !== null) {
+    patch.showDockIcon = showDockIcon;
+  }
+  return patch;
+}
+
+function coerceNotificationsPatch(input: unknown): Partial<DesktopSettings["notifications"]> {
+  if (!isRecord(input)) {
+    return {};
+  }
+
+  const patch: Partial<DesktopSettings["notifications"]> = {};
+  const seconds = input.coalesceWindowSeconds;
+  if (typeof seconds === "number" && Number.isInteger(seconds) && seconds >= 0 && seconds <= 300) {
+    patch.coalesceWindowSeconds = seconds;
+  }
+  return patch;
+}
+
+function coerceShortcutsPatch(input: unknown): Partial<DesktopSettings["shortcuts"]> {
+  if (!isRecord(input)) {
+    return {};
+  }
+
+  const patch: Partial<DesktopSettings["shortcuts"]> = {};
+  const accelerator = input.pauseAllAgents;
+  if (accelerator === null) {
+    patch.pauseAllAgents = null;
+  } else if (typeof accelerator === "string" && accelerator.trim().length <= 64) {
+    patch.pauseAllAgents = accelerator.trim() || null;
+  }
+  return patch;
+}
+
+function coerceDigestPatch(input: unknown): Partial<DesktopSettings["digest"]> {
+  if (!isRecord(input)) {
+    return {};
+  }
+
+  const patch: Partial<DesktopSettings["digest"]> = {};
+  const folder = input.folder;
+  if (folder === null) {
+    patch.folder = null;
+  } else if (typeof folder === "string" && path.isAbsolute(folder.trim())) {
+    patch.folder = folder.trim();
+  }
+  if (input.format === "markdown" || input.format === "pdf") {
+    patch.format = input.format;
+  }
+  return patch;
+}
+
+function coerceRetentionPatch(input: unknown): Partial<DesktopSettings["retention"]> {
+  if (!isRecord(input)) {
+    return {};
+  }
+
+  const patch: Partial<DesktopSettings["retention"]> = {};
+  const days = input.maxAgeDays;
+  if (typeof days === "number" && Number.isInteger(days) && days >= 1 && days <= 365) {
+    patch.maxAgeDays = days;
+  }
+  const sizeMb = input.maxSizeMb;
+  if (typeof sizeMb === "number" && Number.isInteger(sizeMb) && sizeMb >= 10 && sizeMb <= 10_240) {
+    patch.maxSizeMb = sizeMb;
+  }
+  return patch;
+}
+
+function coerceProxyPatch(input: unknown): Partial<DesktopSettings["proxy"]> {
+  if (!isRecord(input)) {
+    return {};
+  }
+
+  const patch: Partial<DesktopSettings["proxy"]> = {};
+  const mode = coerceProxyMode(input.mode);
+  if (mode) {
+    patch.mode = mode;
+  }
+  const url = coerceNullableString(input.url);
+  if (url !== undefined) {
+    patch.url = url;
+  }
+  return patch;
+}
+
+function coercePresentationPatch(input: unknown): Partial<DesktopSettings["presentation"]> {
+  if (!isRecord(input)) {
+    return {};
+  }
+
+  const patch: Partial<DesktopSettings["presentation"]> = {};
+  const minutes = input.maxDurationMinutes;
+  if (typeof minutes === "number" && Number.isInteger(minutes) && minutes >= 1 && minutes <= 1440) {
+    patch.maxDurationMinutes = minutes;
+  }
+  return patch;
+}
+
+function coerceFileReadsPatch(input: unknown): Partial<DesktopSettings["fileReads"]> {
+  if (!isRecord(input)) {
+    return {};
+  }
+
+  const patch: Partial<DesktopSettings["fileReads"]> = {};
+  const maxSizeMb = input.maxSizeMb;
+  if (
+    typeof maxSizeMb === "number" &&
+    Number.isInteger(maxSizeMb) &&
+    maxSizeMb >= 1 &&
+    maxSizeMb <= 1024
+  ) {
+    patch.maxSizeMb = maxSizeMb;
+  }
+  return patch;
+}
+
+function coerceAutomationPatch(input: unknown): Partial<DesktopSettings["automation"]> {
+  if (!isRecord(input)) {
+    return {};
+  }
+
+  const patch: Partial<DesktopSettings["automation"]> = {};
+  const enabled = coerceBoolean(input.enabled);
+  if (enabled !== null) {
+    patch.enabled = enabled;
+  }
+  return patch;
+}
+
+function coerceOutputsPatch(input: unknown): Partial<DesktopSettings["outputs"]> {
+  if (!isRecord(input)) {
+    return {};
+  }
+
+  const patch: Partial<DesktopSettings["outputs"]> = {};
+  const folder = input.folder;
+  if (folder === null) {
+    patch.folder = null;
+  } else if (typeof folder === "string" && path.isAbsolute(folder.trim())) {
+    patch.folder = folder.trim();
+  }
+  const collectOnCompletion = coerceBoolean(input.collectOnCompletion);
+  if (collectOnCompletion !== null) {
+    patch.collectOnCompletion = collectOnCompletion;
+  }
+  return patch;
+}
+
+/** `patch` without the settings only the main process may change, for renderer patches. */
+export function omitMainProcessOnlySettings(patch: unknown): unknown {
+  if (!isRecord(patch)) {
+    return patch;
+  }
+  const result: Record<string, unknown> = { ...patch };
+  for (const [section, keys] of Object.entries(MAIN_PROCESS_ONLY_SETTINGS)) {
+    const value = result[section];
+    if (isRecord(value)) {
+      result[section] = Object.fromEntries(
+        Object.entries(value).filter(([key]) => !keys.includes(key)),
+      );
+    }
+  }
+  return result;
+}
+
 function buildDefaultDocument(): PersistedDesktopSettingsDocument {
   return {
     version: 1,
@@ -72,6 +498,19 @@ function coerceDesktopSettings(input: unknown): DesktopSettings {
   const result: DesktopSettings = {
     releaseChannel: DEFAULT_DESKTOP_SETTINGS.releaseChannel,
     daemon: { ...DEFAULT_DESKTOP_SETTINGS.daemon },
+    proxy: { ...DEFAULT_DESKTOP_SETTINGS.proxy },
+    transport: { ...DEFAULT_DESKTOP_SETTINGS.transport },
+    approvals: { ...DEFAULT_DESKTOP_SETTINGS.approvals },
+    clipboardHistory: { ...DEFAULT_DESKTOP_SETTINGS.clipboardHistory },
+    statusPopover: { ...DEFAULT_DESKTOP_SETTINGS.statusPopover },
+    notifications: { ...DEFAULT_DESKTOP_SETTINGS.notifications },
+    retention: { ...DEFAULT_DESKTOP_SETTINGS.retention },
+    shortcuts: { ...DEFAULT_DESKTOP_SETTINGS.shortcuts },
+    digest: { ...DEFAULT_DESKTOP_SETTINGS.digest },
+    presentation: { ...DEFAULT_DESKTOP_SETTINGS.presentation },
+    fileReads: { ...DEFAULT_DESKTOP_SETTINGS.fileReads },
+    automation: { ...DEFAULT_DESKTOP_SETTINGS.automation },
+    outputs: { ...DEFAULT_DESKTOP_SETTINGS.outputs },
   };
 
   if (!isRecord(input)) {
@@ -93,8 +532,55 @@ function coerceDesktopSettings(input: unknown): DesktopSettings {
     if (keepRunningAfterQuit !== null) {
       result.daemon.keepRunningAfterQuit = keepRunningAfterQuit;
     }
+
+    const port = coerceDaemonPort(input.daemon.port);
+    if (port !== undefined) {
+      result.daemon.port = port;
+    }
+
+    const lanBindListen = coerceNullableString(input.daemon.lanBindListen);
+    if (lanBindListen !== undefined) {
+      result.daemon.lanBindListen = lanBindListen;
+    }
+
+    const confirmQuit = coerceBoolean(input.daemon.confirmQuit);
+    if (confirmQuit !== null) {
+      result.daemon.confirmQuit = confirmQuit;
+    }
   }
 
+  result.proxy = { ...result.proxy, ...coerceProxyPatch(input.proxy) };
+  result.transport = { ...result.transport, ...coerceTransportPatch(input.transport) };
+  result.approvals = { ...result.approvals, ...coerceApprovalsPatch(input.approvals) };
+  result.clipboardHistory = {
+    ...result.clipboardHistory,
+    ...coerceClipboardHistoryPatch(input.clipboardHistory),
+  };
+  result.statusPopover = {
+    ...result.statusPopover,
+    ...coerceStatusPopoverPatch(input.statusPopover),
+  };
+  result.notifications = {
+    ...result.notifications,
+    ...coerceNotificationsPatch(input.notifications),
+  };
+  result.retention = { ...result.retention, ...coerceRetentionPatch(input.retention) };
+  result.shortcuts = { ...result.shortcuts, ...coerceShortcutsPatch(input.shortcuts) };
+  result.digest = { ...result.digest, ...coerceDigestPatch(input.digest) };
+  result.presentation = {
+    ...result.presentation,
+    ...coercePresentationPatch(input.presentation),
+  };
+  result.fileReads = {
+    ...result.fileReads,
+    ...coerceFileReadsPatch(input.fileReads),
+  };
+  result.automation = {
+    ...result.automation,
+    ...coerceAutomationPatch(input.automation),
+  };
+  result.outputs = { ...result.outputs, ...coerceOutputsPatch(input.outputs) };
+
   return result;
 }
 
@@ -120,11 +606,88 @@ function coerceDesktopSettingsPatch(input: unknown): DesktopSettingsPatch {
     if (keepRunningAfterQuit !== null) {
       daemonPatch.keepRunningAfterQuit = keepRunningAfterQuit;
     }
+    const port = coerceDaemonPort(input.daemon.port);
+    if (port !== undefined) {
+      daemonPatch.port = port;
+    }
+    const lanBindListen = coerceNullableString(input.daemon.lanBindListen);
+    if (lanBindListen !== undefined) {
+      daemonPatch.lanBindListen = lanBindListen;
+    }
+    const confirmQuit = coerceBoolean(input.daemon.confirmQuit);
+    if (confirmQuit !== null) {
+      daemonPatch.confirmQuit = confirmQuit;
+    }
     if (Object.keys(daemonPatch).length > 0) {
       patch.daemon = daemonPatch;
     }
   }
 
+  const proxyPatch = coerceProxyPatch(input.proxy);
+  if (Object.keys(proxyPatch).length > 0) {
+    patch.proxy = proxyPatch;
+  }
+
+  const transportPatch = coerceTransportPatch(input.transport);
+  if (Object.keys(transportPatch).length > 0) {
+    patch.transport = transportPatch;
+  }
+
+  const approvalsPatch = coerceApprovalsPatch(input.approvals);
+  if (Object.keys(approvalsPatch).length > 0) {
+    patch.approvals = approvalsPatch;
+  }
+
+  const clipboardHistoryPatch = coerceClipboardHistoryPatch(input.clipboardHistory);
+  if (Object.keys(clipboardHistoryPatch).length > 0) {
+    patch.clipboardHistory = clipboardHistoryPatch;
+  }
+
+  const statusPopoverPatch = coerceStatusPopoverPatch(input.statusPopover);
+  if (Object.keys(statusPopoverPatch).length > 0) {
+    patch.statusPopover = statusPopoverPatch;
+  }
+
+  const notificationsPatch = coerceNotificationsPatch(input.notifications);
+  if (Object.keys(notificationsPatch).length > 0) {
+    patch.notifications = notificationsPatch;
+  }
+
+  const retentionPatch = coerceRetentionPatch(input.retention);
+  if (Object.keys(retentionPatch).length > 0) {
+    patch.retention = retentionPatch;
+  }
+
+  const shortcutsPatch = coerceShortcutsPatch(input.shortcuts);
+  if (Object.keys(shortcutsPatch).length > 0) {
+    patch.shortcuts = shortcutsPatch;
+  }
+
+  const digestPatch = coerceDigestPatch(input.digest);
+  if (Object.keys(digestPatch).length > 0) {
+    patch.digest = digestPatch;
+  }
+
+  const presentationPatch = coercePresentationPatch(input.presentation);
+  if (Object.keys(presentationPatch).length > 0) {
+    patch.presentation = presentationPatch;
+  }
+
+  const fileReadsPatch = coerceFileReadsPatch(input.fileReads);
+  if (Object.keys(fileReadsPatch).length > 0) {
+    patch.fileReads = fileReadsPatch;
+  }
+
+  const automationPatch = coerceAutomationPatch(input.automation);
+  if (Object.keys(automationPatch).length > 0) {
+    patch.automation = automationPatch;
+  }
+
+  const outputsPatch = coerceOutputsPatch(input.outputs);
+  if (Object.keys(outputsPatch).length > 0) {
+    patch.outputs = outputsPatch;
+  }
+
   return patch;
 }
 
@@ -156,6 +719,19 @@ function mergeDesktopSettings(
   return {
     releaseChannel: patch.releaseChannel ?? current.releaseChannel,
     daemon: { ...current.daemon, ...patch.daemon },
+    proxy: { ...current.proxy, ...patch.proxy },
+    transport: { ...current.transport, ...patch.transport },
+    approvals: { ...current.approvals, ...patch.approvals },
+    clipboardHistory: { ...current.clipboardHistory, ...patch.clipboardHistory },
+    statusPopover: { ...current.statusPopover, ...patch.statusPopover },
+    notifications: { ...current.notifications, ...patch.notifications },
+    retention: { ...current.retention, ...patch.retention },
+    shortcuts: { ...current.shortcuts, ...patch.shortcuts },
+    digest: { ...current.digest, ...patch.digest },
+    presentation: { ...current.presentation, ...patch.presentation },
+    fileReads: { ...current.fileReads, ...patch.fileReads },
+    automation: { ...current.automation, ...patch.automation },
+    outputs: { ...current.outputs, ...patch.outputs },
   };
 }
 
@@ -188,37 +764,17 @@ export function createDesktopSettingsStore({
   const filePath = path.join(userDataPath, DESKTOP_SETTINGS_FILENAME);
   let cachedDocument: PersistedDesktopSettingsDocument | null = null;
 
-  async function persistDocument(document: PersistedDesktopSettingsDocument): Promise<void> {
-    await mkdir(userDataPath, { recursive: true });
-    const tempFilePath = `${filePath}.tmp`;
-    await writeFile(tempFilePath, `${JSON.stringify(document, null, 2)}\n`, "utf8");
-    await rename(tempFilePath, filePath);
+  async function updateDocument(
+    mutate: (current: PersistedDesktopSettingsDocument) => PersistedDesktopSettingsDocument,
+  ): Promise<PersistedDesktopSettingsDocument> {
+    const document = await updateJsonConfigFile(filePath, (raw) => mutate(coerceDocument(raw)));
     cachedDocument = document;
+    return document;
   }
 
   async function loadDocument(): Promise<PersistedDesktopSettingsDocument> {
-    if (cachedDocument) {
-      return cachedDocument;
-    }
-
-    try {
-      await access(filePath);
-    } catch {
-      const document = buildDefaultDocument();
-      await persistDocument(document);
-      return document;
-    }
-
-    try {
-      const raw = await readFile(filePath, "utf8");
-      const document = coerceDocument(JSON.parse(raw));
-      await persistDocument(document);
-      return document;
-    } catch {
-      const document = buildDefaultDocument();
-      await persistDocument(document);
-      return document;
-    }
+    // The first read also rewrites the file in its normalised form.
+    return cachedDocument ?? (await updateDocument((current) => current));
   }
 
   return {
@@ -228,34 +784,32 @@ export function createDesktopSettingsStore({
     },
 
     async patch(patch: unknown): Promise<DesktopSettings> {
-      const current = await loadDocument();
-      const next = mergeDesktopSettings(current.settings, coerceDesktopSettingsPatch(patch));
-      await persistDocument({
+      const coerced = coerceDesktopSettingsPatch(patch);
+      const next = await updateDocument((current) => ({
         ...current,
-        settings: next,
-      });
-      return next;
+        settings: mergeDesktopSettings(current.settings, coerced),
+      }));
+      return next.settings;
     },
 
     async migrateLegacyRendererSettings(legacySettings: unknown): Promise<DesktopSettings> {
-      const current = await loadDocument();
-      if (current.migrations.legacyRendererSettingsImported) {
-        return current.settings;
-      }
-
-      const next = mergeDesktopSettings(
-        current.settings,
-        pickDesktopSettingsFromLegacyRendererSettings(legacySettings),
-      );
-      await persistDocument({
-        ...current,
-        settings: next,
-        migrations: {
-          ...current.migrations,
-          legacyRendererSettingsImported: true,
-        },
+      const next = await updateDocument((current) => {
+        if (current.migrations.legacyRendererSettingsImported) {
+          return current;
+        }
+        return {
+          ...current,
+          settings: mergeDesktopSettings(
+            current.settings,
+            pickDesktopSettingsFromLegacyRendererSettings(legacySettings),
+          ),
+          migrations: {
+            ...current.migrations,
+            legacyRendererSettingsImported: true,
+          },
+        };
       });
-      return next;
+      return next.settings;
     },
   };
 }
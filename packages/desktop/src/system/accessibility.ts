@@ -0,0 +1,43 @@
+import { app, nativeTheme, systemPreferences } from "electron";
+import { emitDesktopEvent } from "../window/desktop-events.js";
+
+const ACCESSIBILITY_PREFERENCES_CHANGED_EVENT = "accessibility-preferences-changed";
+
+export interface AccessibilityPreferences {
+  /** VoiceOver, Narrator, Orca, or another assistive client is driving Chromium. */
+  screenReaderActive: boolean;
+  reduceMotion: boolean;
+  increaseContrast: boolean;
+  reduceTransparency: boolean;
+}
+
+export function getAccessibilityPreferences(): AccessibilityPreferences {
+  return {
+    screenReaderActive: app.isAccessibilitySupportEnabled(),
+    reduceMotion: systemPreferences.getAnimationSettings().prefersReducedMotion,
+    increaseContrast: nativeTheme.shouldUseHighContrastColors,
+    reduceTransparency: nativeTheme.prefersReducedTransparency,
+  };
+}
+
+/**
+ * Screen reader and contrast changes have events; reduce motion does not, so
+ * it is re-read when the user comes back to the app, as for locale changes.
+ */
+export function registerAccessibilityEvents(): void {
+  let lastSnapshot = JSON.stringify(getAccessibilityPreferences());
+
+  const refresh = () => {
+    const preferences = getAccessibilityPreferences();
+    const snapshot = JSON.stringify(preferences);
+    if (snapshot === lastSnapshot) {
+      return;
+    }
+    lastSnapshot = snapshot;
+    emitDesktopEvent(ACCESSIBILITY_PREFERENCES_CHANGED_EVENT, preferences);
+  };
+
+  app.on("accessibility-support-changed", refresh);
+  nativeTheme.on("updated", refresh);
+  app.on("browser-window-focus", refresh);
+}
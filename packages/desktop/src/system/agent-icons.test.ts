@@ -0,0 +1,53 @@
+import { describe, expect, it } from "vitest";
+import {
+  coerceAgentIdentity,
+  parseHexColor,
+  renderAgentIconBitmap,
+  resolveFallbackAgentColor,
+} from "./agent-icons";
+
+function pixelAt(bitmap: Buffer, size: number, x: number, y: number): number[] {
+  const offset = (y * size + x) * 4;
+  return [...bitmap.subarray(offset, offset + 4)];
+}
+
+describe("coerceAgentIdentity", () => {
+  it("normalizes colors and takes the initial from the label", () => {
+    expect(coerceAgentIdentity({ agentId: "a1", color: " #0090FF ", label: " 2nd try" })).toEqual({
+      agentId: "a1",
+      identity: { color: "#0090ff", shape: "circle", initial: "N" },
+      label: "2nd try",
+    });
+    expect(() => coerceAgentIdentity({ agentId: "a1", color: "blue" })).toThrow();
+    expect(() => coerceAgentIdentity({ agentId: "a1", color: "#000000", shape: "star" })).toThrow();
+  });
+
+  it("gives unknown agents a stable palette color", () => {
+    expect(resolveFallbackAgentColor("agent-42")).toBe(resolveFallbackAgentColor("agent-42"));
+    expect(parseHexColor(resolveFallbackAgentColor("agent-42"))).not.toBeNull();
+  });
+});
+
+describe("renderAgentIconBitmap", () => {
+  it("fills the shape with the agent color and draws the initial in a contrasting color", () => {
+    const size = 16;
+    const bitmap = renderAgentIconBitmap({
+      identity: { color: "#0090ff", shape: "circle", initial: "T" },
+      size,
+    });
+
+    expect(bitmap.length).toBe(size * size * 4);
+    expect(pixelAt(bitmap, size, 0, 0)[3]).toBe(0);
+    expect(pixelAt(bitmap, size, 3, 8)).toEqual([0xff, 0x90, 0x00, 255]);
+    expect(pixelAt(bitmap, size, 5, 3)).toEqual([255, 255, 255, 255]);
+  });
+
+  it("uses dark text on light colors", () => {
+    const bitmap = renderAgentIconBitmap({
+      identity: { color: "#ffc53d", shape: "square", initial: "T" },
+      size: 16,
+    });
+
+    expect(pixelAt(bitmap, 16, 5, 3)).toEqual([0x1a, 0x1b, 0x18, 255]);
+  });
+});
@@ -0,0 +1,187 @@
+// Bitmaps here are Electron's native layout: BGRA, premultiplied alpha, row-major.
+import type { RgbColor } from "./tray-icons.js";
+import { isRecord } from "../utils/is-record.js";
+
+export const AGENT_ICON_SHAPES = ["circle", "square", "diamond", "hexagon"] as const;
+export type AgentIconShape = (typeof AGENT_ICON_SHAPES)[number];
+
+export interface AgentIdentity {
+  /** `#rrggbb`, as the renderer shows it. */
+  color: string;
+  shape: AgentIconShape;
+  /** Single uppercase letter drawn over the shape, or `null` for a plain glyph. */
+  initial: string | null;
+}
+
+// Used until the renderer reports an agent's own color.
+const AGENT_IDENTITY_PALETTE = [
+  "#e5484d",
+  "#f76b15",
+  "#ffc53d",
+  "#46a758",
+  "#12a594",
+  "#0090ff",
+  "#6e56cf",
+  "#d6409f",
+];
+const HEX_COLOR_PATTERN = /^#([0-9a-f]{6})$/i;
+const LIGHT_TEXT_COLOR: RgbColor = { r: 0xff, g: 0xff, b: 0xff };
+const DARK_TEXT_COLOR: RgbColor = { r: 0x18, g: 0x1b, b: 0x1a };
+
+// 3×5 pixel capitals, the same grid as the badge digits.
+const INITIAL_FONT: Record<string, string[]> = {
+  A: ["010", "101", "111", "101", "101"],
+  B: ["110", "101", "110", "101", "110"],
+  C: ["011", "100", "100", "100", "011"],
+  D: ["110", "101", "101", "101", "110"],
+  E: ["111", "100", "110", "100", "111"],
+  F: ["111", "100", "110", "100", "100"],
+  G: ["011", "100", "101", "101", "011"],
+  H: ["101", "101", "111", "101", "101"],
+  I: ["111", "010", "010", "010", "111"],
+  J: ["001", "001", "001", "101", "010"],
+  K: ["101", "101", "110", "101", "101"],
+  L: ["100", "100", "100", "100", "111"],
+  M: ["101", "111", "111", "101", "101"],
+  N: ["110", "101", "101", "101", "101"],
+  O: ["010", "101", "101", "101", "010"],
+  P: ["110", "101", "110", "100", "100"],
+  Q: ["010", "101", "101", "110", "011"],
+  R: ["110", "101", "110", "101", "101"],
+  S: ["011", "100", "010", "001", "110"],
+  T: ["111", "010", "010", "010", "010"],
+  U: ["101", "101", "101", "101", "111"],
+  V: ["101", "101", "101", "101", "010"],
+  W: ["101", "101", "111", "111", "101"],
+  X: ["101", "101", "010", "101", "101"],
+  Y: ["101", "101", "010", "010", "010"],
+  Z: ["111", "001", "010", "100", "111"],
+};
+
+export function parseHexColor(value: string): RgbColor | null {
+  const match = HEX_COLOR_PATTERN.exec(value.trim());
+  if (!match) {
+    return null;
+  }
+  const rgb = Number.parseInt(match[1]!, 16);
+  return { r: (rgb >> 16) & 0xff, g: (rgb >> 8) & 0xff, b: rgb & 0xff };
+}
+
+/** Stable palette color for an agent the renderer has not described yet. */
+export function resolveFallbackAgentColor(agentId: string): string {
+  let hash = 0;
+  for (const char of agentId) {
+    hash = (hash * 31 + char.charCodeAt(0)) >>> 0;
+  }
+  return AGENT_IDENTITY_PALETTE[hash % AGENT_IDENTITY_PALETTE.length]!;
+}
+
+export function resolveAgentInitial(label: unknown): string | null {
+  if (typeof label !== "string") {
+    return null;
+  }
+  const letter = /[a-z]/i.exec(label)?.[0]?.toUpperCase();
+  return letter && INITIAL_FONT[letter] ? letter : null;
+}
+
+/** One `{ agentId, color, shape?, label? }` entry from `set_agent_identities`. */
+export function coerceAgentIdentity(value: unknown): {
+  agentId: string;
+  identity: AgentIdentity;
+  label: string | null;
+} {
+  if (!isRecord(value) || typeof value.agentId !== "string" || value.agentId.length === 0) {
+    throw new Error("Each agent identity needs an agentId.");
+  }
+  const color = typeof value.color === "string" ? value.color.trim().toLowerCase() : "";
+  if (!parseHexColor(color)) {
+    throw new Error(`Agent colors are #rrggbb hex values: ${String(value.color)}`);
+  }
+  const shape = value.shape ?? "circle";
+  if (!(AGENT_ICON_SHAPES as readonly unknown[]).includes(shape)) {
+    throw new Error(`Unknown agent icon shape: ${String(shape)}`);
+  }
+  const label = typeof value.label === "string" ? value.label.trim() || null : null;
+  return {
+    agentId: value.agentId,
+    identity: { color, shape: shape as AgentIconShape, initial: resolveAgentInitial(label) },
+    label,
+  };
+}
+
+function writePixel(bitmap: Buffer, pixel: number, color: RgbColor, alpha: number): void {
+  const offset = pixel * 4;
+  bitmap[offset] = Math.round((color.b * alpha) / 255);
+  bitmap[offset + 1] = Math.round((color.g * alpha) / 255);
+  bitmap[offset + 2] = Math.round((color.r * alpha) / 255);
+  bitmap[offset + 3] = alpha;
+}
+
+/** Signed distance-like coverage in [0, 1] of the pixel centred at (px, py). */
+function shapeCoverage(shape: AgentIconShape, px: number, py: number, size: number): number {
+  const center = size / 2;
+  const radius = size / 2 - Math.max(0.5, size / 32);
+  const dx = px - center;
+  const dy = py - center;
+  let distance: number;
+  if (shape === "square") {
+    // Rounded square, like the app icon.
+    const corner = radius * 0.35;
+    const qx = Math.max(Math.abs(dx) - (radius - corner), 0);
+    const qy = Math.max(Math.abs(dy) - (radius - corner), 0);
+    distance = Math.hypot(qx, qy) - corner;
+  } else if (shape === "diamond") {
+    distance = (Math.abs(dx) + Math.abs(dy) - radius) / Math.SQRT2;
+  } else if (shape === "hexagon") {
+    const ax = Math.abs(dx);
+    const ay = Math.abs(dy);
+    distance = Math.max(ax * 0.866 + ay * 0.5, ay) - radius * 0.95;
+  } else {
+    distance = Math.hypot(dx, dy) - radius;
+  }
+  // One pixel of falloff keeps edges from looking jagged.
+  return Math.max(0, Math.min(1, 0.5 - distance));
+}
+
+function relativeLuminance(color: RgbColor): number {
+  return (0.2126 * color.r + 0.7152 * color.g + 0.0722 * color.b) / 255;
+}
+
+/** The agent's shape filled with its color, with its initial on top when it has one. */
+export function renderAgentIconBitmap(input: { identity: AgentIdentity; size: number }): Buffer {
+  const { size } = input;
+  const color = parseHexColor(input.identity.color) ?? { r: 0x88, g: 0x88, b: 0x88 };
+  const bitmap = Buffer.alloc(size * size * 4);
+  for (let y = 0; y < size; y += 1) {
+    for (let x = 0; x < size; x += 1) {
+      const coverage = shapeCoverage(input.identity.shape, x + 0.5, y + 0.5, size);
+      if (coverage > 0) {
+        writePixel(bitmap, y * size + x, color, Math.round(coverage * 255));
+      }
+    }
+  }
+
+  const glyph = input.identity.initial ? INITIAL_FONT[input.identity.initial] : undefined;
+  if (!glyph) {
+    return bitmap;
+  }
+  const textColor = relativeLuminance(color) > 0.6 ? DARK_TEXT_COLOR : LIGHT_TEXT_COLOR;
+  const scale = Math.max(1, Math.floor(size / 8));
+  const originX = Math.floor((size - 3 * scale) / 2);
+  const originY = Math.floor((size - 5 * scale) / 2);
+  glyph.forEach((row, rowIndex) => {
+    [...row].forEach((bit, columnIndex) => {
+      if (bit !== "1") {
+        return;
+      }
+      for (let dy = 0; dy < scale; dy += 1) {
+        for (let dx = 0; dx < scale; dx += 1) {
+          const x = originX + columnIndex * scale + dx;
+          const y = originY + rowIndex * scale + dy;
+          writePixel(bitmap, y * size + x, textColor, 255);
+        }
+      }
+    });
+  });
+  return bitmap;
+}
@@ -0,0 +1,35 @@
+import { describe, expect, it } from "vitest";
+import {
+  parseAppleScriptColor,
+  parseHexColor,
+  parseWindowsColorDialogOutput,
+  parseZenityColor,
+  toPickedColor,
+} from "./color-picker";
+
+describe("color picker output parsing", () => {
+  it("scales AppleScript's 16-bit channels", () => {
+    expect(toPickedColor(parseAppleScriptColor("65535, 0, 32896\n")!)).toEqual({
+      hex: "#ff0080",
+      rgba: { r: 255, g: 0, b: 128, a: 1 },
+    });
+  });
+
+  it("accepts every zenity output format", () => {
+    expect(toPickedColor(parseZenityColor("rgb(18,52,86)\n")!).hex).toBe("#123456");
+    expect(parseZenityColor("rgba(18,52,86,0.5)")).toEqual({ r: 18, g: 52, b: 86, a: 0.5 });
+    expect(parseZenityColor("#123456")).toEqual({ r: 18, g: 52, b: 86, a: 1 });
+    expect(parseZenityColor("")).toBeNull();
+  });
+
+  it("reads the Windows dialog output", () => {
+    expect(parseWindowsColorDialogOutput("1,2,3\r\n")).toEqual({ r: 1, g: 2, b: 3, a: 1 });
+    expect(parseWindowsColorDialogOutput("")).toBeNull();
+  });
+
+  it("parses initial hex colors with or without a hash", () => {
+    expect(parseHexColor("0a0B0c")).toEqual({ r: 10, g: 11, b: 12, a: 1 });
+    expect(parseHexColor("#fff")).toBeNull();
+    expect(parseHexColor(42)).toBeNull();
+  });
+});
@@ -0,0 +1,141 @@
+import { execFile } from "node:child_process";
+import { promisify } from "node:util";
+
+const execFileAsync = promisify(execFile);
+
+export interface RgbaColor {
+  r: number;
+  g: number;
+  b: number;
+  /** 0–1. */
+  a: number;
+}
+
+export interface PickedColor {
+  hex: string;
+  rgba: RgbaColor;
+}
+
+const HEX_COLOR_PATTERN = /^#?([0-9a-f]{6})$/i;
+
+function clampChannel(value: number): number {
+  return Math.max(0, Math.min(255, Math.round(value)));
+}
+
+export function toPickedColor(color: RgbaColor): PickedColor {
+  const rgba = {
+    r: clampChannel(color.r),
+    g: clampChannel(color.g),
+    b: clampChannel(color.b),
+    a: Math.max(0, Math.min(1, color.a)),
+  };
+  const hex = [rgba.r, rgba.g, rgba.b].map((channel) => channel.toString(16).padStart(2, "0"));
+  return { hex: `#${hex.join("")}`, rgba };
+}
+
+export function parseHexColor(value: unknown): RgbaColor | null {
+  const match = typeof value === "string" ? HEX_COLOR_PATTERN.exec(value.trim()) : null;
+  if (!match) {
+    return null;
+  }
+  const int = Number.parseInt(match[1]!, 16);
+  return { r: (int >> 16) & 0xff, g: (int >> 8) & 0xff, b: int & 0xff, a: 1 };
+}
+
+/** `choose color` prints 16-bit channels, e.g. `65535, 0, 32896`. */
+export function parseAppleScriptColor(stdout: string): RgbaColor | null {
+  const channels = stdout.trim().split(/\s*,\s*/).map(Number);
+  if (channels.length !== 3 || channels.some((channel) => !Number.isFinite(channel))) {
+    return null;
+  }
+  const [r, g, b] = channels.map((channel) => (channel / 65535) * 255);
+  return { r: r!, g: g!, b: b!, a: 1 };
+}
+
+/** zenity prints `rgb(r,g,b)`, `rgba(r,g,b,a)`, or a hex string depending on version. */
+export function parseZenityColor(stdout: string): RgbaColor | null {
+  const trimmed = stdout.trim();
+  const functional = /^rgba?\(([^)]+)\)$/i.exec(trimmed);
+  if (!functional) {
+    return parseHexColor(trimmed);
+  }
+  const parts = functional[1]!.split(",").map((part) => Number(part.trim()));
+  if (parts.length < 3 || parts.some((part) => !Number.isFinite(part))) {
+    return null;
+  }
+  return { r: parts[0]!, g: parts[1]!, b: parts[2]!, a: parts[3] ?? 1 };
+}
+
+/** The PowerShell ColorDialog script below prints `r,g,b`. */
+export function parseWindowsColorDialogOutput(stdout: string): RgbaColor | null {
+  const parts = stdout.trim().split(",").map(Number);
+  if (parts.length !== 3 || parts.some((part) => !Number.isInteger(part))) {
+    return null;
+  }
+  return { r: parts[0]!, g: parts[1]!, b: parts[2]!, a: 1 };
+}
+
+function buildPickerCommand(input: {
+  platform: NodeJS.Platform;
+  initial: RgbaColor | null;
+}): { file: string; args: string[]; parse: (stdout: string) => RgbaColor | null } {
+  const initial = input.initial ?? { r: 255, g: 255, b: 255, a: 1 };
+  const hex = toPickedColor(initial).hex;
+
+  if (input.platform === "darwin") {
+    // NSColorPanel, which also carries the system's screen magnifier eyedropper.
+    const [r, g, b] = [initial.r, initial.g, initial.b].map((channel) => channel * 257);
+    return {
+      file: "osascript",
+      args: ["-e", `choose color default color {${r}, ${g}, ${b}}`],
+      parse: parseAppleScriptColor,
+    };
+  }
+  if (input.platform === "win32") {
+    const script = [
+      "Add-Type -AssemblyName System.Windows.Forms",
+      "$dialog = New-Object System.Windows.Forms.ColorDialog",
+      "$dialog.FullOpen = $true",
+      `$dialog.Color = [System.Drawing.ColorTranslator]::FromHtml('${hex}')`,
+      "if ($dialog.ShowDialog() -eq 'OK') {",
+      "  '{0},{1},{2}' -f $dialog.Color.R, $dialog.Color.G, $dialog.Color.B",
+      "}",
+    ].join("\n");
+    return {
+      file: "powershell.exe",
+      args: ["-NoProfile", "-NonInteractive", "-Command", script],
+      parse: parseWindowsColorDialogOutput,
+    };
+  }
+  return {
+    file: "zenity",
+    args: ["--color-selection", "--show-palette", `--color=${hex}`],
+    parse: parseZenityColor,
+  };
+}
+
+/**
+ * Opens the platform color picker. Resolves `null` when the user cancels.
+ * Screen sampling outside macOS is left to the renderer's `EyeDropper` API.
+ */
+export async function pickColor(input: {
+  initial?: unknown;
+  platform?: NodeJS.Platform;
+}): Promise<PickedColor | null> {
+  const command = buildPickerCommand({
+    platform: input.platform ?? process.platform,
+    initial: parseHexColor(input.initial),
+  });
+  let stdout: string;
+  try {
+    ({ stdout } = await execFileAsync(command.file, command.args, { windowsHide: true }));
+  } catch (error) {
+    if ((error as NodeJS.ErrnoException).code === "ENOENT") {
+      throw new Error(`No system color picker is available (${command.file} was not found).`);
+    }
+    // Every picker exits non-zero when dismissed.
+    return null;
+  }
+  const color = command.parse(stdout);
+  return color ? toPickedColor(color) : null;
+}
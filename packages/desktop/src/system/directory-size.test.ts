@@ -0,0 +1,52 @@
+import { mkdir, mkdtemp, rm, writeFile } from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+import { estimateDirectorySize } from "./directory-size";
+
+describe("estimateDirectorySize", () => {
+  let root: string;
+
+  beforeEach(async () => {
+    root = await mkdtemp(path.join(os.tmpdir(), "paseo-directory-size-"));
+    await mkdir(path.join(root, ".git"), { recursive: true });
+    await mkdir(path.join(root, "node_modules", "dep"), { recursive: true });
+    await mkdir(path.join(root, "src", "generated"), { recursive: true });
+    await writeFile(path.join(root, ".git", "HEAD"), "ref: refs/heads/main\n");
+    await writeFile(path.join(root, ".gitignore"), "node_modules/\n");
+    await writeFile(path.join(root, "node_modules", "dep", "index.js"), "x".repeat(1000));
+    await writeFile(path.join(root, "src", "index.ts"), "x".repeat(10));
+    await writeFile(path.join(root, "src", ".gitignore"), "generated/\n");
+    await writeFile(path.join(root, "src", "generated", "api.ts"), "x".repeat(500));
+  });
+
+  afterEach(async () => {
+    await rm(root, { recursive: true, force: true });
+  });
+
+  it("skips .git and ignored directories when respecting gitignore", async () => {
+    const estimate = await estimateDirectorySize({ root, respectGitignore: true });
+    expect(estimate).toMatchObject({
+      fileCount: 3,
+      directoryCount: 1,
+      totalBytes: 10 + "node_modules/\n".length + "generated/\n".length,
+      ignoredCount: 3,
+      unreadableCount: 0,
+    });
+  });
+
+  it("counts everything otherwise", async () => {
+    const estimate = await estimateDirectorySize({ root, respectGitignore: false });
+    expect(estimate.fileCount).toBe(6);
+    expect(estimate.directoryCount).toBe(5);
+    expect(estimate.ignoredCount).toBe(0);
+  });
+
+  it("stops when cancelled", async () => {
+    const controller = new AbortController();
+    controller.abort();
+    await expect(
+      estimateDirectorySize({ root, respectGitignore: true, signal: controller.signal }),
+    ).rejects.toThrow("Cancelled");
+  });
+});
@@ -0,0 +1,238 @@
+import { randomUUID } from "node:crypto";
+import type { Dirent } from "node:fs";
+import { lstat, readdir, readFile, stat } from "node:fs/promises";
+import path from "node:path";
+import { emitDesktopEvent } from "../window/desktop-events.js";
+import { type GitignoreFrame, isGitIgnored, parseGitignore } from "./gitignore.js";
+import { toFileSystemPath } from "./windows-paths.js";
+
+const DIRECTORY_SIZE_PROGRESS_EVENT = "directory-size-progress";
+const PROGRESS_INTERVAL_MS = 250;
+// Directories read at once; enough to hide disk latency without exhausting file handles.
+const DEFAULT_CONCURRENCY = 16;
+const STAT_BATCH_SIZE = 64;
+
+export interface DirectorySizeProgress {
+  fileCount: number;
+  directoryCount: number;
+  totalBytes: number;
+}
+
+export interface DirectorySizeEstimate extends DirectorySizeProgress {
+  path: string;
+  respectGitignore: boolean;
+  /** Entries skipped by ignore rules; what is inside a skipped directory is not counted. */
+  ignoredCount: number;
+  /** Entries that could not be read, such as directories without permission. */
+  unreadableCount: number;
+  elapsedMs: number;
+}
+
+interface DirectoryJob {
+  absolute: string;
+  relative: string;
+  frames: GitignoreFrame[];
+}
+
+const activeEstimates = new Map<string, AbortController>();
+
+function cancelledError(): Error {
+  return new Error("Cancelled");
+}
+
+async function readIgnoreFrame(filePath: string, base: string): Promise<GitignoreFrame | null> {
+  try {
+    const rules = parseGitignore(await readFile(filePath, "utf8"));
+    return rules.length > 0 ? { base, rules } : null;
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Counts regular files and their apparent sizes under `root` without
+ * following symlinks. With `respectGitignore`, `.git` and everything matched
+ * by `.gitignore` files (and the root's `.git/info/exclude`) is skipped, the
+ * way `git status` sees the tree.
+ */
+export async function estimateDirectorySize(input: {
+  root: string;
+  respectGitignore: boolean;
+  signal?: AbortSignal;
+  onProgress?: (progress: DirectorySizeProgress) => void;
+  concurrency?: number;
+  now?: () => number;
+}): Promise<DirectorySizeEstimate> {
+  const now = input.now ?? Date.now;
+  const startedAt = now();
+  const rootInfo = await stat(input.root);
+  if (!rootInfo.isDirectory()) {
+    throw new Error(`Not a folder: ${input.root}`);
+  }
+
+  const totals: DirectorySizeProgress = { fileCount: 0, directoryCount: 0, totalBytes: 0 };
+  let ignoredCount = 0;
+  let unreadableCount = 0;
+  const rootFrames: GitignoreFrame[] = [];
+  if (input.respectGitignore) {
+    const exclude = await readIgnoreFrame(path.join(input.root, ".git", "info", "exclude"), "");
+    if (exclude) {
+      rootFrames.push(exclude);
+    }
+  }
+
+  async function statFiles(files: string[]): Promise<void> {
+    for (let start = 0; start < files.length; start += STAT_BATCH_SIZE) {
+      const infos = await Promise.all(
+        files.slice(start, start + STAT_BATCH_SIZE).map((file) => lstat(file).catch(() => null)),
+      );
+      for (const info of infos) {
+        if (info?.isFile()) {
+          totals.fileCount += 1;
+          totals.totalBytes += info.size;
+        } else if (!info) {
+          unreadableCount += 1;
+        }
+      }
+    }
+  }
+
+  async function visitDirectory(job: DirectoryJob): Promise<DirectoryJob[]> {
+    let entries: Dirent[];
+    try {
+      entries = await readdir(job.absolute, { withFileTypes: true });
+    } catch {
+      unreadableCount += 1;
+      return [];
+    }
+    let frames = job.frames;
+    if (input.respectGitignore && entries.some((entry) => entry.name === ".gitignore")) {
+      const frame = await readIgnoreFrame(path.join(job.absolute, ".gitignore"), job.relative);
+      frames = frame ? [...frames, frame] : frames;
+    }
+
+    const children: DirectoryJob[] = [];
+    const files: string[] = [];
+    for (const entry of entries) {
+      const relative = job.relative === "" ? entry.name : `${job.relative}/${entry.name}`;
+      const isDirectory = entry.isDirectory();
+      if (!isDirectory && !entry.isFile()) {
+        continue;
+      }
+      if (
+        input.respectGitignore &&
+        ((isDirectory && entry.name === ".git") || isGitIgnored(frames, relative, isDirectory))
+      ) {
+        ignoredCount += 1;
+        continue;
+      }
+      if (isDirectory) {
+        totals.directoryCount += 1;
+        children.push({ absolute: path.join(job.absolute, entry.name), relative, frames });
+      } else {
+        files.push(path.join(job.absolute, entry.name));
+      }
+    }
+    await statFiles(files);
+    input.onProgress?.({ ...totals });
+    return children;
+  }
+
+  const pending: DirectoryJob[] = [{ absolute: input.root, relative: "", frames: rootFrames }];
+  const concurrency = input.concurrency ?? DEFAULT_CONCURRENCY;
+  await new Promise<void>((resolve, reject) => {
+    let active = 0;
+    let settled = false;
+    const fail = (error: unknown) => {
+      if (!settled) {
+        settled = true;
+        reject(error);
+      }
+    };
+    const pump = () => {
+      if (settled) {
+        return;
+      }
+      if (input.signal?.aborted) {
+        fail(cancelledError());
+        return;
+      }
+      while (active < concurrency && pending.length > 0) {
+        const job = pending.pop() as DirectoryJob;
+        active += 1;
+        visitDirectory(job).then((children) => {
+          active -= 1;
+          pending.push(...children);
+          pump();
+        }, fail);
+      }
+      if (active === 0 && pending.length === 0) {
+        settled = true;
+        resolve();
+      }
+    };
+    pump();
+  });
+
+  return {
+    path: input.root,
+    respectGitignore: input.respectGitignore,
+    ...totals,
+    ignoredCount,
+    unreadableCount,
+    elapsedMs: now() - startedAt,
+  };
+}
+
+/**
+ * `requestId` lets the caller cancel with `cancel_directory_size_estimate`
+ * and match `directory-size-progress` events; one is generated when omitted.
+ */
+export async function runDirectorySizeEstimate(
+  args: Record<string, unknown> | undefined,
+): Promise<DirectorySizeEstimate & { requestId: string }> {
+  const root = typeof args?.path === "string" ? args.path.trim() : "";
+  if (!path.isAbsolute(root)) {
+    throw new Error("Folder path must be absolute.");
+  }
+  const requestId =
+    typeof args?.requestId === "string" && args.requestId.length > 0
+      ? args.requestId
+      : randomUUID();
+  if (activeEstimates.has(requestId)) {
+    throw new Error(`A size estimate is already running for request ${requestId}.`);
+  }
+  const controller = new AbortController();
+  activeEstimates.set(requestId, controller);
+  let lastProgressAt = 0;
+  try {
+    const estimate = await estimateDirectorySize({
+      root: toFileSystemPath(root),
+      respectGitignore: args?.respectGitignore !== false,
+      signal: controller.signal,
+      onProgress: (progress) => {
+        const at = Date.now();
+        if (at - lastProgressAt >= PROGRESS_INTERVAL_MS) {
+          lastProgressAt = at;
+          emitDesktopEvent(DIRECTORY_SIZE_PROGRESS_EVENT, { requestId, path: root, ...progress });
+        }
+      },
+    });
+    return { ...estimate, path: root, requestId };
+  } finally {
+    activeEstimates.delete(requestId);
+  }
+}
+
+export function cancelDirectorySizeEstimate(requestId: unknown): boolean {
+  if (typeof requestId !== "string") {
+    return false;
+  }
+  const controller = activeEstimates.get(requestId);
+  if (!controller) {
+    return false;
+  }
+  activeEstimates.delete(requestId);
+  controller.abort();
+  return true;
+}
@@ -0,0 +1,61 @@
+import { mkdir, mkdtemp, rm, symlink, writeFile } from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+import { getDiskUsageReport, measurePathSize } from "./disk-usage";
+
+describe("disk usage", () => {
+  let dir: string;
+
+  beforeEach(async () => {
+    dir = await mkdtemp(path.join(os.tmpdir(), "paseo-disk-usage-"));
+  });
+
+  afterEach(async () => {
+    await rm(dir, { recursive: true, force: true });
+  });
+
+  it("sums files recursively without following symlinks", async () => {
+    await mkdir(path.join(dir, "nested", "deeper"), { recursive: true });
+    await writeFile(path.join(dir, "a.bin"), Buffer.alloc(100));
+    await writeFile(path.join(dir, "nested", "deeper", "b.bin"), Buffer.alloc(50));
+    await symlink(os.tmpdir(), path.join(dir, "link"));
+
+    expect(await measurePathSize(dir)).toBe(150);
+    expect(await measurePathSize(path.join(dir, "a.bin"))).toBe(100);
+    expect(await measurePathSize(path.join(dir, "missing"))).toBe(0);
+  });
+
+  it("reports each category and the volumes they live on", async () => {
+    await mkdir(path.join(dir, "attachments"));
+    await writeFile(path.join(dir, "attachments", "image.png"), Buffer.alloc(10));
+    await writeFile(path.join(dir, "daemon.log"), Buffer.alloc(20));
+
+    const report = await getDiskUsageReport({
+      locations: {
+        attachments: [path.join(dir, "attachments")],
+        logs: [path.join(dir, "daemon.log"), path.join(dir, "logs")],
+        caches: [],
+        timeline: [path.join(dir, "agents")],
+      },
+      lowSpaceBytes: Number.MAX_SAFE_INTEGER,
+    });
+
+    expect(report.categories).toEqual([
+      { category: "attachments", bytes: 10, paths: [path.join(dir, "attachments")] },
+      {
+        category: "logs",
+        bytes: 20,
+        paths: [path.join(dir, "daemon.log"), path.join(dir, "logs")],
+      },
+      { category: "caches", bytes: 0, paths: [] },
+      { category: "timeline", bytes: 0, paths: [path.join(dir, "agents")] },
+    ]);
+    expect(report.volumes).toHaveLength(1);
+    expect(report.volumes[0]).toMatchObject({
+      categories: ["attachments", "logs", "timeline"],
+      low: true,
+    });
+    expect(report.lowSpace).toBe(true);
+  });
+});
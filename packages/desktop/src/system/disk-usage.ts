@@ -0,0 +1,156 @@
+import type { Stats } from "node:fs";
+import { lstat, readdir, stat, statfs } from "node:fs/promises";
+import path from "node:path";
+import log from "electron-log/main";
+import { emitDesktopEvent } from "../window/desktop-events.js";
+
+const DISK_SPACE_LOW_EVENT = "disk-space-low";
+const DISK_SPACE_CHECK_INTERVAL_MS = 10 * 60_000;
+export const DEFAULT_LOW_SPACE_BYTES = 1024 * 1024 * 1024;
+
+export const DISK_USAGE_CATEGORIES = ["attachments", "logs", "caches", "timeline"] as const;
+
+export type DiskUsageCategory = (typeof DISK_USAGE_CATEGORIES)[number];
+
+export type DiskUsageLocations = Record<DiskUsageCategory, string[]>;
+
+export interface DiskUsageVolume {
+  /** First measured path on this volume. */
+  path: string;
+  categories: DiskUsageCategory[];
+  freeBytes: number;
+  totalBytes: number;
+  low: boolean;
+}
+
+export interface DiskUsageReport {
+  categories: Array<{ category: DiskUsageCategory; bytes: number; paths: string[] }>;
+  volumes: DiskUsageVolume[];
+  lowSpaceBytes: number;
+  lowSpace: boolean;
+}
+
+/** Apparent size of a file or directory tree; symlinks are not followed. Missing paths are 0. */
+export async function measurePathSize(target: string): Promise<number> {
+  let info: Stats;
+  try {
+    info = await lstat(target);
+  } catch {
+    return 0;
+  }
+  if (!info.isDirectory()) {
+    return info.isFile() ? info.size : 0;
+  }
+  let entries: string[];
+  try {
+    entries = await readdir(target);
+  } catch {
+    return 0;
+  }
+  let total = 0;
+  for (const entry of entries) {
+    total += await measurePathSize(path.join(target, entry));
+  }
+  return total;
+}
+
+/** Nearest existing ancestor, so volumes resolve before a directory is first created. */
+async function resolveExistingPath(target: string): Promise<string> {
+  let current = path.resolve(target);
+  for (;;) {
+    try {
+      await stat(current);
+      return current;
+    } catch {
+      const parent = path.dirname(current);
+      if (parent === current) {
+        return current;
+      }
+      current = parent;
+    }
+  }
+}
+
+/** Free space per volume, de-duplicated when several locations share one. */
+export async function measureVolumes(input: {
+  locations: DiskUsageLocations;
+  lowSpaceBytes: number;
+}): Promise<DiskUsageVolume[]> {
+  const volumesByDevice = new Map<number, DiskUsageVolume>();
+  for (const category of DISK_USAGE_CATEGORIES) {
+    for (const entry of input.locations[category]) {
+      const existing = await resolveExistingPath(entry);
+      const { dev } = await stat(existing);
+      const known = volumesByDevice.get(dev);
+      if (known) {
+        if (!known.categories.includes(category)) {
+          known.categories.push(category);
+        }
+        continue;
+      }
+      const stats = await statfs(existing);
+      const freeBytes = stats.bavail * stats.bsize;
+      volumesByDevice.set(dev, {
+        path: entry,
+        categories: [category],
+        freeBytes,
+        totalBytes: stats.blocks * stats.bsize,
+        low: freeBytes < input.lowSpaceBytes,
+      });
+    }
+  }
+  return [...volumesByDevice.values()];
+}
+
+export async function getDiskUsageReport(input: {
+  locations: DiskUsageLocations;
+  lowSpaceBytes?: number;
+}): Promise<DiskUsageReport> {
+  const lowSpaceBytes = input.lowSpaceBytes ?? DEFAULT_LOW_SPACE_BYTES;
+  const categories = await Promise.all(
+    DISK_USAGE_CATEGORIES.map(async (category) => {
+      const paths = input.locations[category];
+      const sizes = await Promise.all(paths.map((entry) => measurePathSize(entry)));
+      return { category, bytes: sizes.reduce((sum, size) => sum + size, 0), paths };
+    }),
+  );
+  const volumes = await measureVolumes({ locations: input.locations, lowSpaceBytes });
+  return {
+    categories,
+    volumes,
+    lowSpaceBytes,
+    lowSpace: volumes.some((volume) => volume.low),
+  };
+}
+
+/**
+ * Emits `disk-space-low` when any measured volume drops below the threshold,
+ * and again only after it has recovered, so the UI can warn before the
+ * daemon or updater fails on a full disk.
+ */
+export function registerLowDiskSpaceEvents(input: {
+  getLocations: () => DiskUsageLocations;
+  lowSpaceBytes?: number;
+}): void {
+  let wasLow = false;
+
+  const lowSpaceBytes = input.lowSpaceBytes ?? DEFAULT_LOW_SPACE_BYTES;
+
+  const check = async () => {
+    const lowVolumes = (
+      await measureVolumes({ locations: input.getLocations(), lowSpaceBytes })
+    ).filter((volume) => volume.low);
+    if (lowVolumes.length > 0 && !wasLow) {
+      emitDesktopEvent(DISK_SPACE_LOW_EVENT, { lowSpaceBytes, volumes: lowVolumes });
+    }
+    wasLow = lowVolumes.length > 0;
+  };
+  const refresh = () => {
+    void check().catch((error) => {
+      log.warn("[disk-usage] failed to check free space", error);
+    });
+  };
+
+  refresh();
+  setInterval(refresh, DISK_SPACE_CHECK_INTERVAL_MS).unref();
+}
@@ -0,0 +1,98 @@
+import { describe, expect, it } from "vitest";
+import {
+  findDisplayForBounds,
+  getDisplayConfigurationKey,
+  planOffscreenWindowRelocations,
+  resolveDisplayInfo,
+  type DisplaySnapshot,
+} from "./display-info";
+
+const LAPTOP: DisplaySnapshot = {
+  id: 1,
+  label: "Built-in Retina Display",
+  bounds: { x: 0, y: 0, width: 1512, height: 982 },
+  workArea: { x: 0, y: 38, width: 1512, height: 944 },
+  scaleFactor: 2,
+  rotation: 0,
+  internal: true,
+};
+
+const MONITOR: DisplaySnapshot = {
+  id: 2,
+  label: "DELL U2720Q",
+  bounds: { x: 1512, y: 0, width: 2560, height: 1440 },
+  workArea: { x: 1512, y: 25, width: 2560, height: 1415 },
+  scaleFactor: 1.5,
+  rotation: 0,
+  internal: false,
+};
+
+describe("display info", () => {
+  it("reports physical resolution and the primary display", () => {
+    const info = resolveDisplayInfo({
+      displays: [LAPTOP, MONITOR],
+      primaryDisplayId: 2,
+      windows: [],
+    });
+
+    expect(info.displays.map(({ id, primary, pixelSize }) => [id, primary, pixelSize])).toEqual([
+      [1, false, { width: 3024, height: 1964 }],
+      [2, true, { width: 3840, height: 2160 }],
+    ]);
+  });
+
+  it("places a window on the display holding most of it", () => {
+    const displays = [LAPTOP, MONITOR];
+
+    expect(findDisplayForBounds({ x: 1200, y: 100, width: 800, height: 600 }, displays)).toBe(2);
+    expect(findDisplayForBounds({ x: 100, y: 100, width: 800, height: 600 }, displays)).toBe(1);
+    expect(findDisplayForBounds({ x: 5000, y: 0, width: 800, height: 600 }, displays)).toBeNull();
+  });
+
+  it("keys the configuration on the monitor layout only", () => {
+    const before = resolveDisplayInfo({
+      displays: [LAPTOP, MONITOR],
+      primaryDisplayId: 1,
+      windows: [{ windowLabel: "main", bounds: { x: 0, y: 0, width: 800, height: 600 } }],
+    });
+    const moved = resolveDisplayInfo({
+      displays: [LAPTOP, MONITOR],
+      primaryDisplayId: 1,
+      windows: [{ windowLabel: "main", bounds: { x: 1600, y: 0, width: 800, height: 600 } }],
+    });
+    const undocked = resolveDisplayInfo({ displays: [LAPTOP], primaryDisplayId: 1, windows: [] });
+
+    expect(getDisplayConfigurationKey(moved)).toBe(getDisplayConfigurationKey(before));
+    expect(getDisplayConfigurationKey(undocked)).not.toBe(getDisplayConfigurationKey(before));
+  });
+
+  it("moves windows stranded by a removed monitor onto the nearest display", () => {
+    const relocations = planOffscreenWindowRelocations({
+      previousDisplays: [LAPTOP, MONITOR],
+      displays: [LAPTOP],
+      windows: [
+        { windowLabel: "main", bounds: { x: 100, y: 100, width: 800, height: 600 } },
+        { windowLabel: "agent-1", bounds: { x: 1512, y: 25, width: 1280, height: 1415 } },
+      ],
+    });
+
+    expect(relocations).toEqual([
+      {
+        windowLabel: "agent-1",
+        displayId: 1,
+        from: { x: 1512, y: 25, width: 1280, height: 1415 },
+        to: { x: 0, y: 38, width: 756, height: 944 },
+      },
+    ]);
+  });
+
+  it("centres windows that were off every known display", () => {
+    const relocations = planOffscreenWindowRelocations({
+      previousDisplays: [LAPTOP],
+      displays: [LAPTOP],
+      windows: [{ windowLabel: "main", bounds: { x: -3000, y: 200, width: 800, height: 600 } }],
+    });
+
+    expect(relocations.map(({ to }) => to)).toEqual([{ x: 356, y: 210, width: 800, height: 600 }]);
+  });
+});
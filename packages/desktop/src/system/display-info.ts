@@ -0,0 +1,262 @@
+import { screen } from "electron";
+import log from "electron-log/main";
+import { emitDesktopEvent } from "../window/desktop-events.js";
+import { clampToWorkAreas } from "../window/layout-model.js";
+import { listLabeledWindows } from "../window/window-labels.js";
+
+const DISPLAY_CONFIGURATION_CHANGED_EVENT = "display-configuration-changed";
+const WINDOWS_RELOCATED_EVENT = "windows-relocated";
+// Docking fires a burst of added/removed/metrics events; report the settled layout once.
+const DISPLAY_CHANGE_SETTLE_MS = 500;
+
+export interface DisplayRect {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
+export interface DisplayDescriptor {
+  id: number;
+  label: string;
+  primary: boolean;
+  bounds: DisplayRect;
+  workArea: DisplayRect;
+  scaleFactor: number;
+  rotation: number;
+  internal: boolean;
+  /** Native resolution in physical pixels. */
+  pixelSize: { width: number; height: number };
+}
+
+export interface DisplayWindowPlacement {
+  windowLabel: string;
+  /** Display holding most of the window; `null` when it is entirely off-screen. */
+  displayId: number | null;
+  bounds: DisplayRect;
+}
+
+export interface DisplayInfo {
+  displays: DisplayDescriptor[];
+  windows: DisplayWindowPlacement[];
+}
+
+export interface DisplaySnapshot {
+  id: number;
+  label: string;
+  bounds: DisplayRect;
+  workArea: DisplayRect;
+  scaleFactor: number;
+  rotation: number;
+  internal: boolean;
+}
+
+function intersectionArea(left: DisplayRect, right: DisplayRect): number {
+  const width = Math.min(left.x + left.width, right.x + right.width) - Math.max(left.x, right.x);
+  const height =
+    Math.min(left.y + left.height, right.y + right.height) - Math.max(left.y, right.y);
+  return width > 0 && height > 0 ? width * height : 0;
+}
+
+export function findDisplayForBounds(
+  bounds: DisplayRect,
+  displays: Array<{ id: number; bounds: DisplayRect }>,
+): number | null {
+  let bestId: number | null = null;
+  let bestArea = 0;
+  for (const display of displays) {
+    const area = intersectionArea(bounds, display.bounds);
+    if (area > bestArea) {
+      bestArea = area;
+      bestId = display.id;
+    }
+  }
+  return bestId;
+}
+
+export function resolveDisplayInfo(input: {
+  displays: DisplaySnapshot[];
+  primaryDisplayId: number;
+  windows: Array<{ windowLabel: string; bounds: DisplayRect }>;
+}): DisplayInfo {
+  return {
+    displays: input.displays.map((display) => ({
+      ...display,
+      primary: display.id === input.primaryDisplayId,
+      pixelSize: {
+        width: Math.round(display.bounds.width * display.scaleFactor),
+        height: Math.round(display.bounds.height * display.scaleFactor),
+      },
+    })),
+    windows: input.windows.map((win) => ({
+      windowLabel: win.windowLabel,
+      displayId: findDisplayForBounds(win.bounds, input.displays),
+      bounds: win.bounds,
+    })),
+  };
+}
+
+/** Identity of the monitor layout, ignoring where windows happen to be. */
+export function getDisplayConfigurationKey(info: DisplayInfo): string {
+  return JSON.stringify(
+    info.displays.map((display) => [
+      display.id,
+      display.primary,
+      display.bounds,
+      display.workArea,
+      display.scaleFactor,
+      display.rotation,
+    ]),
+  );
+}
+
+export interface WindowRelocation {
+  windowLabel: string;
+  /** Display the window now sits on. */
+  displayId: number;
+  from: DisplayRect;
+  to: DisplayRect;
+}
+
+function distanceToRect(point: { x: number; y: number }, rect: DisplayRect): number {
+  const dx = Math.max(rect.x - point.x, 0, point.x - (rect.x + rect.width));
+  const dy = Math.max(rect.y - point.y, 0, point.y - (rect.y + rect.height));
+  return Math.hypot(dx, dy);
+}
+
+/**
+ * Moves windows that no longer overlap any display onto the one nearest to
+ * them. A window that was on a display that went away keeps its size and
+ * offset relative to that display's work area, so a window filling half an
+ * external monitor fills half the laptop screen.
+ */
+export function planOffscreenWindowRelocations(input: {
+  previousDisplays: Array<{ id: number; bounds: DisplayRect; workArea: DisplayRect }>;
+  displays: Array<{ id: number; bounds: DisplayRect; workArea: DisplayRect }>;
+  windows: Array<{ windowLabel: string; bounds: DisplayRect }>;
+}): WindowRelocation[] {
+  if (input.displays.length === 0) {
+    return [];
+  }
+  return input.windows.flatMap((win): WindowRelocation[] => {
+    if (findDisplayForBounds(win.bounds, input.displays) !== null) {
+      return [];
+    }
+    const center = {
+      x: win.bounds.x + win.bounds.width / 2,
+      y: win.bounds.y + win.bounds.height / 2,
+    };
+    const target = input.displays.reduce((best, display) =>
+      distanceToRect(center, display.bounds) < distanceToRect(center, best.bounds)
+        ? display
+        : best,
+    );
+    const previousId = findDisplayForBounds(win.bounds, input.previousDisplays);
+    const source = input.previousDisplays.find((display) => display.id === previousId);
+    const area = target.workArea;
+    let bounds: DisplayRect;
+    if (source) {
+      const scaleX = area.width / source.workArea.width;
+      const scaleY = area.height / source.workArea.height;
+      bounds = {
+        x: area.x + (win.bounds.x - source.workArea.x) * scaleX,
+        y: area.y + (win.bounds.y - source.workArea.y) * scaleY,
+        width: win.bounds.width * scaleX,
+        height: win.bounds.height * scaleY,
+      };
+    } else {
+      bounds = {
+        x: area.x + (area.width - win.bounds.width) / 2,
+        y: area.y + (area.height - win.bounds.height) / 2,
+        width: win.bounds.width,
+        height: win.bounds.height,
+      };
+    }
+    const to = clampToWorkAreas(
+      {
+        x: Math.round(bounds.x),
+        y: Math.round(bounds.y),
+        width: Math.round(bounds.width),
+        height: Math.round(bounds.height),
+      },
+      [area],
+    );
+    return [{ windowLabel: win.windowLabel, displayId: target.id, from: win.bounds, to }];
+  });
+}
+
+export function getDisplayInfo(): DisplayInfo {
+  return resolveDisplayInfo({
+    displays: screen.getAllDisplays().map((display) => ({
+      id: display.id,
+      label: display.label,
+      bounds: display.bounds,
+      workArea: display.workArea,
+      scaleFactor: display.scaleFactor,
+      rotation: display.rotation,
+      internal: display.internal,
+    })),
+    primaryDisplayId: screen.getPrimaryDisplay().id,
+    windows: listLabeledWindows().map(({ label, win }) => ({
+      windowLabel: label,
+      bounds: win.getBounds(),
+    })),
+  });
+}
+
+function relocateOffscreenWindows(
+  previousDisplays: DisplayDescriptor[],
+  displays: DisplayDescriptor[],
+): void {
+  const windows = listLabeledWindows().filter(
+    // Minimized windows report placeholder bounds, and fullscreen ones follow their own space.
+    ({ win }) => !win.isDestroyed() && !win.isMinimized() && !win.isFullScreen(),
+  );
+  const relocations = planOffscreenWindowRelocations({
+    previousDisplays,
+    displays,
+    windows: windows.map(({ label, win }) => ({
+      windowLabel: label,
+      bounds: win.getBounds(),
+    })),
+  });
+  if (relocations.length === 0) {
+    return;
+  }
+  for (const relocation of relocations) {
+    windows.find(({ label }) => label === relocation.windowLabel)?.win.setBounds(relocation.to);
+  }
+  log.info(`[display] moved ${relocations.length} off-screen window(s) onto a visible display`);
+  emitDesktopEvent(WINDOWS_RELOCATED_EVENT, { relocations });
+}
+
+export function registerDisplayConfigurationEvents(): void {
+  let lastInfo = getDisplayInfo();
+  let lastKey = getDisplayConfigurationKey(lastInfo);
+  let settleTimer: NodeJS.Timeout | null = null;
+
+  const refresh = () => {
+    settleTimer = null;
+    const info = getDisplayInfo();
+    const key = getDisplayConfigurationKey(info);
+    if (key === lastKey) {
+      return;
+    }
+    const previousDisplays = lastInfo.displays;
+    lastInfo = info;
+    lastKey = key;
+    emitDesktopEvent(DISPLAY_CONFIGURATION_CHANGED_EVENT, info);
+    relocateOffscreenWindows(previousDisplays, info.displays);
+  };
+
+  const scheduleRefresh = () => {
+    if (settleTimer) {
+      clearTimeout(settleTimer);
+    }
+    settleTimer = setTimeout(refresh, DISPLAY_CHANGE_SETTLE_MS);
+  };
+
+  screen.on("display-added", scheduleRefresh);
+  screen.on("display-removed", scheduleRefresh);
+  screen.on("display-metrics-changed", scheduleRefresh);
+}
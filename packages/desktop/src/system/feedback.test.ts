@@ -0,0 +1,39 @@
+import { describe, expect, it } from "vitest";
+import { buildFeedbackCommands } from "./feedback";
+
+describe("buildFeedbackCommands", () => {
+  it("pairs a haptic pattern with a system sound on macOS", () => {
+    const commands = buildFeedbackCommands({
+      kind: "deny",
+      platform: "darwin",
+      haptic: true,
+      sound: true,
+    });
+
+    expect(commands.map((command) => [command.channel, command.file])).toEqual([
+      ["haptic", "osascript"],
+      ["sound", "afplay"],
+    ]);
+    expect(commands[0]!.args.at(-1)).toContain("performFeedbackPatternPerformanceTime(2, 1)");
+    expect(commands[1]!.args).toEqual(["/System/Library/Sounds/Basso.aiff"]);
+  });
+
+  it("uses platform sounds elsewhere and has no haptics", () => {
+    expect(
+      buildFeedbackCommands({ kind: "completion", platform: "linux", haptic: true, sound: true }),
+    ).toEqual([{ channel: "sound", file: "canberra-gtk-play", args: ["-i", "complete"] }]);
+    expect(
+      buildFeedbackCommands({ kind: "approve", platform: "win32", haptic: true, sound: true })[0]!
+        .args.at(-1),
+    ).toContain("SystemSounds]::Asterisk.Play()");
+  });
+
+  it("respects disabled channels", () => {
+    expect(
+      buildFeedbackCommands({ kind: "approve", platform: "darwin", haptic: false, sound: false }),
+    ).toEqual([]);
+    expect(
+      buildFeedbackCommands({ kind: "approve", platform: "win32", haptic: true, sound: false }),
+    ).toEqual([]);
+  });
+});
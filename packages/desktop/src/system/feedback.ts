@@ -0,0 +1,140 @@
+import { execFile } from "node:child_process";
+import { promisify } from "node:util";
+import { shell } from "electron";
+import log from "electron-log/main";
+
+const execFileAsync = promisify(execFile);
+
+const FEEDBACK_TIMEOUT_MS = 5_000;
+
+export const FEEDBACK_KINDS = ["approve", "deny", "completion"] as const;
+
+export type FeedbackKind = (typeof FEEDBACK_KINDS)[number];
+
+export interface FeedbackCommand {
+  channel: "haptic" | "sound";
+  file: string;
+  args: string[];
+}
+
+// NSHapticFeedbackPattern: 0 generic, 1 alignment, 2 level change.
+const MAC_HAPTIC_PATTERNS: Record<FeedbackKind, number> = {
+  approve: 1,
+  deny: 2,
+  completion: 0,
+};
+
+const MAC_SOUNDS: Record<FeedbackKind, string> = {
+  approve: "Pop",
+  deny: "Basso",
+  completion: "Glass",
+};
+
+const WINDOWS_SOUNDS: Record<FeedbackKind, string> = {
+  approve: "Asterisk",
+  deny: "Hand",
+  completion: "Exclamation",
+};
+
+// freedesktop sound theme names.
+const LINUX_SOUNDS: Record<FeedbackKind, string> = {
+  approve: "message",
+  deny: "dialog-warning",
+  completion: "complete",
+};
+
+export function isFeedbackKind(value: unknown): value is FeedbackKind {
+  return (FEEDBACK_KINDS as readonly unknown[]).includes(value);
+}
+
+/**
+ * Sounds run out of process so they play even before the renderer has had
+ * the user interaction Chromium requires for audio. Haptics need a Force
+ * Touch trackpad and are silently ignored without one.
+ */
+export function buildFeedbackCommands(input: {
+  kind: FeedbackKind;
+  platform: NodeJS.Platform;
+  haptic: boolean;
+  sound: boolean;
+}): FeedbackCommand[] {
+  const commands: FeedbackCommand[] = [];
+  if (input.platform === "darwin") {
+    if (input.haptic) {
+      const script =
+        "ObjC.import('AppKit'); " +
+        "$.NSHapticFeedbackManager.defaultPerformer" +
+        `.performFeedbackPatternPerformanceTime(${MAC_HAPTIC_PATTERNS[input.kind]}, 1);`;
+      commands.push({
+        channel: "haptic",
+        file: "osascript",
+        args: ["-l", "JavaScript", "-e", script],
+      });
+    }
+    if (input.sound) {
+      commands.push({
+        channel: "sound",
+        file: "afplay",
+        args: [`/System/Library/Sounds/${MAC_SOUNDS[input.kind]}.aiff`],
+      });
+    }
+    return commands;
+  }
+  if (!input.sound) {
+    return commands;
+  }
+  if (input.platform === "win32") {
+    // Play() is asynchronous, so the process has to outlive the sound.
+    const script =
+      `[System.Media.SystemSounds]::${WINDOWS_SOUNDS[input.kind]}.Play(); ` +
+      "Start-Sleep -Milliseconds 500";
+    commands.push({
+      channel: "sound",
+      file: "powershell.exe",
+      args: ["-NoProfile", "-NonInteractive", "-Command", script],
+    });
+    return commands;
+  }
+  commands.push({
+    channel: "sound",
+    file: "canberra-gtk-play",
+    args: ["-i", LINUX_SOUNDS[input.kind]],
+  });
+  return commands;
+}
+
+export async function playFeedback(
+  args: Record<string, unknown> | undefined,
+): Promise<{ haptic: boolean; sound: boolean }> {
+  if (!isFeedbackKind(args?.kind)) {
+    throw new Error(`Unknown feedback kind: ${String(args?.kind)}`);
+  }
+  const commands = buildFeedbackCommands({
+    kind: args.kind,
+    platform: process.platform,
+    haptic: args.haptic !== false,
+    sound: args.sound !== false,
+  });
+  const results = await Promise.all(
+    commands.map(async (command) => {
+      try {
+        await execFileAsync(command.file, command.args, {
+          timeout: FEEDBACK_TIMEOUT_MS,
+          windowsHide: true,
+        });
+        return { channel: command.channel, ok: true };
+      } catch (error) {
+        log.warn(`[feedback] ${command.file} failed`, error);
+        return { channel: command.channel, ok: false };
+      }
+    }),
+  );
+
+  const played = (channel: FeedbackCommand["channel"]) =>
+    results.some((result) => result.channel === channel && result.ok);
+  const soundRequested = commands.some((command) => command.channel === "sound");
+  if (soundRequested && !played("sound")) {
+    shell.beep();
+  }
+  return { haptic: played("haptic"), sound: soundRequested };
+}
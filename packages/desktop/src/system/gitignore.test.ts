@@ -0,0 +1,34 @@
+import { describe, expect, it } from "vitest";
+import { type GitignoreFrame, isGitIgnored, parseGitignore } from "./gitignore";
+
+function frames(...entries: Array<[string, string]>): GitignoreFrame[] {
+  return entries.map(([base, contents]) => ({ base, rules: parseGitignore(contents) }));
+}
+
+describe("isGitIgnored", () => {
+  it("matches unanchored names at any depth and anchored paths from the ignore file", () => {
+    const root = frames(["", "# build output\nnode_modules/\n/dist\n*.log\ndocs/**/*.pdf\n"]);
+    expect(isGitIgnored(root, "packages/app/node_modules", true)).toBe(true);
+    expect(isGitIgnored(root, "packages/app/node_modules", false)).toBe(false);
+    expect(isGitIgnored(root, "dist", true)).toBe(true);
+    expect(isGitIgnored(root, "packages/dist", true)).toBe(false);
+    expect(isGitIgnored(root, "src/debug.log", false)).toBe(true);
+    expect(isGitIgnored(root, "docs/a/b/manual.pdf", false)).toBe(true);
+    expect(isGitIgnored(root, "docs/manual.pdf", false)).toBe(true);
+  });
+
+  it("lets later rules and deeper ignore files re-include paths", () => {
+    const nested = frames(["", "*.log\n!keep.log\n"], ["logs", "!*.log\n"]);
+    expect(isGitIgnored(nested, "keep.log", false)).toBe(false);
+    expect(isGitIgnored(nested, "other.log", false)).toBe(true);
+    expect(isGitIgnored(nested, "logs/today.log", false)).toBe(false);
+  });
+
+  it("supports character classes, escapes and trailing spaces", () => {
+    const rules = frames(["", "file[0-9].txt  \n\\#notes\nspace\\ \n"]);
+    expect(isGitIgnored(rules, "file7.txt", false)).toBe(true);
+    expect(isGitIgnored(rules, "fileA.txt", false)).toBe(false);
+    expect(isGitIgnored(rules, "#notes", false)).toBe(true);
+    expect(isGitIgnored(rules, "space ", false)).toBe(true);
+  });
+});
@@ -0,0 +1,110 @@
+export interface GitignoreRule {
+  regex: RegExp;
+  negated: boolean;
+  dirOnly: boolean;
+}
+
+/** Rules from one ignore file; `base` is its directory relative to the walk root. */
+export interface GitignoreFrame {
+  base: string;
+  rules: GitignoreRule[];
+}
+
+function escapeRegex(char: string): string {
+  return /[\\^$.*+?()[\]{}|/-]/.test(char) ? `\\${char}` : char;
+}
+
+function segmentToRegex(segment: string): string {
+  let source = "";
+  for (let index = 0; index < segment.length; index += 1) {
+    const char = segment[index];
+    if (char === "\\" && index + 1 < segment.length) {
+      index += 1;
+      source += escapeRegex(segment[index]);
+    } else if (char === "*") {
+      source += "[^/]*";
+    } else if (char === "?") {
+      source += "[^/]";
+    } else if (char === "[" && segment.indexOf("]", index + 2) !== -1) {
+      const close = segment.indexOf("]", index + 2);
+      let body = segment.slice(index + 1, close);
+      const negated = body.startsWith("!") || body.startsWith("^");
+      if (negated) {
+        body = body.slice(1);
+      }
+      source += `[${negated ? "^" : ""}${body.replace(/[\\\]]/g, "\\$&")}]`;
+      index = close;
+    } else {
+      source += escapeRegex(char);
+    }
+  }
+  return source;
+}
+
+/** Matches a `/`-separated path relative to the ignore file's directory. */
+function compilePattern(pattern: string): RegExp {
+  // A slash anywhere but the end anchors the pattern to the ignore file's directory.
+  const anchored = pattern.includes("/");
+  const segments = (pattern.startsWith("/") ? pattern.slice(1) : pattern).split("/");
+  let source = "";
+  segments.forEach((segment, index) => {
+    const last = index === segments.length - 1;
+    if (segment === "**") {
+      source += last ? ".*" : "(?:[^/]*/)*";
+    } else {
+      source += segmentToRegex(segment) + (last ? "" : "/");
+    }
+  });
+  return new RegExp(anchored ? `^${source}$` : `^(?:.*/)?${source}$`);
+}
+
+export function parseGitignore(contents: string): GitignoreRule[] {
+  const rules: GitignoreRule[] = [];
+  for (const rawLine of contents.split(/\r?\n/)) {
+    let line = rawLine;
+    while (line.endsWith(" ") && !line.endsWith("\\ ")) {
+      line = line.slice(0, -1);
+    }
+    if (line === "" || line.startsWith("#")) {
+      continue;
+    }
+    const negated = line.startsWith("!");
+    if (negated || line.startsWith("\\!") || line.startsWith("\\#")) {
+      line = line.slice(1);
+    }
+    const dirOnly = line.endsWith("/");
+    if (dirOnly) {
+      line = line.slice(0, -1);
+    }
+    if (line === "" || line === "/") {
+      continue;
+    }
+    rules.push({ regex: compilePattern(line), negated, dirOnly });
+  }
+  return rules;
+}
+
+/**
+ * Git's precedence: frames are ordered root first, and the last matching rule
+ * across all of them decides. Callers stop descending into ignored
+ * directories, which is what keeps files under them from being re-included.
+ */
+export function isGitIgnored(
+  frames: GitignoreFrame[],
+  relativePath: string,
+  isDirectory: boolean,
+): boolean {
+  let ignored = false;
+  for (const frame of frames) {
+    if (frame.base !== "" && !relativePath.startsWith(`${frame.base}/`)) {
+      continue;
+    }
+    const candidate = frame.base === "" ? relativePath : relativePath.slice(frame.base.length + 1);
+    for (const rule of frame.rules) {
+      if ((!rule.dirOnly || isDirectory) && rule.regex.test(candidate)) {
+        ignored = !rule.negated;
+      }
+    }
+  }
+  return ignored;
+}
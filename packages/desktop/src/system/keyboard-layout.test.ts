@@ -0,0 +1,79 @@
+import { describe, expect, it } from "vitest";
+import {
+  describeKeyboardLayout,
+  parseMacInputSourceId,
+  parseSetxkbmapQuery,
+  parseWindowsInputLanguage,
+} from "./keyboard-layout";
+
+describe("keyboard layout parsing", () => {
+  it("reads the macOS input source id", () => {
+    expect(parseMacInputSourceId("com.apple.keylayout.German\n")).toEqual({
+      id: "com.apple.keylayout.German",
+      layout: "german",
+    });
+    expect(parseMacInputSourceId("")).toBeNull();
+  });
+
+  it("reads the first setxkbmap layout", () => {
+    const stdout =
+      "rules:      evdev\nmodel:      pc105\nlayout:     de,us\nvariant:    nodeadkeys,\n";
+    expect(parseSetxkbmapQuery(stdout)).toEqual({ layout: "de", variant: "nodeadkeys" });
+    expect(parseSetxkbmapQuery("layout:     us\n")).toEqual({ layout: "us", variant: null });
+  });
+
+  it("reads the Windows input language", () => {
+    expect(parseWindowsInputLanguage("de-DE|German\r\n")).toEqual({
+      language: "de-DE",
+      layout: "german",
+    });
+    expect(parseWindowsInputLanguage("\r\n")).toBeNull();
+  });
+});
+
+describe("describeKeyboardLayout", () => {
+  it("treats plain US as free of dead keys and AltGr", () => {
+    expect(
+      describeKeyboardLayout({
+        platform: "linux",
+        id: "us",
+        layout: "us",
+        variant: null,
+        language: null,
+      }),
+    ).toMatchObject({ hasDeadKeys: false, usesAltGr: false });
+  });
+
+  it("flags international and European layouts", () => {
+    expect(
+      describeKeyboardLayout({
+        platform: "win32",
+        id: "en-US|united states-international",
+        layout: "united states-international",
+        variant: null,
+        language: "en-US",
+      }),
+    ).toMatchObject({ hasDeadKeys: true, usesAltGr: true });
+    expect(
+      describeKeyboardLayout({
+        platform: "linux",
+        id: "de(nodeadkeys)",
+        layout: "de",
+        variant: "nodeadkeys",
+        language: null,
+      }),
+    ).toMatchObject({ hasDeadKeys: false, usesAltGr: true });
+  });
+
+  it("never reports AltGr on macOS", () => {
+    expect(
+      describeKeyboardLayout({
+        platform: "darwin",
+        id: "com.apple.keylayout.French",
+        layout: "french",
+        variant: null,
+        language: null,
+      }),
+    ).toMatchObject({ usesAltGr: false });
+  });
+});
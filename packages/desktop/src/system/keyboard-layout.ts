@@ -0,0 +1,183 @@
+import { execFile } from "node:child_process";
+import { promisify } from "node:util";
+import { app, powerMonitor } from "electron";
+import log from "electron-log/main";
+import { emitDesktopEvent } from "../window/desktop-events.js";
+
+const execFileAsync = promisify(execFile);
+
+const KEYBOARD_LAYOUT_CHANGED_EVENT = "keyboard-layout-changed";
+const KEYBOARD_QUERY_TIMEOUT_MS = 5_000;
+
+// Layout families whose default variant composes accents from dead keys.
+const DEAD_KEY_LAYOUTS = new Set([
+  "be",
+  "br",
+  "ca",
+  "ch",
+  "cz",
+  "de",
+  "es",
+  "fr",
+  "hu",
+  "is",
+  "latam",
+  "pt",
+  "sk",
+]);
+const DEAD_KEY_VARIANT_PATTERN = /intl|international|extended|deadkeys/i;
+const NO_DEAD_KEY_VARIANT_PATTERN = /nodeadkeys/i;
+// Only plain US layouts leave Right Alt as a regular Alt; nearly every other layout maps AltGr.
+const US_LAYOUTS = new Set(["us", "abc"]);
+
+export interface KeyboardLayout {
+  /** Platform identifier, e.g. `com.apple.keylayout.German`, `de(nodeadkeys)`, `de-DE|german`. */
+  id: string;
+  /** Lower-case layout family, e.g. `de`, `us`, `german`. */
+  layout: string;
+  variant: string | null;
+  /** BCP 47 tag of the input language where the platform reports one. */
+  language: string | null;
+  hasDeadKeys: boolean;
+  /** Right Alt types characters (AltGr), so it must not be read as an Alt accelerator. */
+  usesAltGr: boolean;
+}
+
+/** `defaults read com.apple.HIToolbox AppleCurrentKeyboardLayoutInputSourceID`. */
+export function parseMacInputSourceId(stdout: string): { id: string; layout: string } | null {
+  const id = stdout.trim();
+  if (!id) {
+    return null;
+  }
+  const name = id.split(".").at(-1) ?? id;
+  return { id, layout: name.toLowerCase() };
+}
+
+/** `setxkbmap -query`: `layout:     de` / `variant:    nodeadkeys`, first entry when several. */
+export function parseSetxkbmapQuery(
+  stdout: string,
+): { layout: string; variant: string | null } | null {
+  const field = (name: string) =>
+    new RegExp(`^${name}:\\s*(.+)$`, "m").exec(stdout)?.[1]?.split(",")[0]?.trim() || null;
+  const layout = field("layout");
+  return layout ? { layout: layout.toLowerCase(), variant: field("variant") } : null;
+}
+
+/** The PowerShell query below prints `<culture>|<layout name>`, e.g. `de-DE|German`. */
+export function parseWindowsInputLanguage(
+  stdout: string,
+): { language: string; layout: string } | null {
+  const [language = "", layout = ""] = stdout.trim().split("|");
+  if (!language.trim()) {
+    return null;
+  }
+  return { language: language.trim(), layout: layout.trim().toLowerCase() || language.trim() };
+}
+
+export function describeKeyboardLayout(input: {
+  platform: NodeJS.Platform;
+  id: string;
+  layout: string;
+  variant: string | null;
+  language: string | null;
+}): KeyboardLayout {
+  const layout = input.layout.toLowerCase();
+  const family = (input.language ?? layout).toLowerCase().split(/[-_]/)[0] ?? layout;
+  const variant = input.variant ?? "";
+  const identity = `${input.id} ${layout} ${variant}`;
+  const isPlainUs =
+    (US_LAYOUTS.has(layout) || input.language === "en-US") &&
+    !DEAD_KEY_VARIANT_PATTERN.test(identity);
+  const hasDeadKeys = NO_DEAD_KEY_VARIANT_PATTERN.test(identity)
+    ? false
+    : DEAD_KEY_VARIANT_PATTERN.test(identity) ||
+      DEAD_KEY_LAYOUTS.has(layout) ||
+      DEAD_KEY_LAYOUTS.has(family);
+  return {
+    id: input.id,
+    layout,
+    variant: input.variant,
+    language: input.language,
+    hasDeadKeys,
+    // macOS has no AltGr; Option composes characters on every layout and the terminal handles it.
+    usesAltGr: input.platform !== "darwin" && !isPlainUs,
+  };
+}
+
+async function run(file: string, args: string[]): Promise<string> {
+  const { stdout } = await execFileAsync(file, args, {
+    timeout: KEYBOARD_QUERY_TIMEOUT_MS,
+    windowsHide: true,
+  });
+  return stdout;
+}
+
+async function readKeyboardLayout(platform: NodeJS.Platform): Promise<KeyboardLayout | null> {
+  if (platform === "darwin") {
+    const parsed = parseMacInputSourceId(
+      await run("defaults", [
+        "read",
+        "com.apple.HIToolbox",
+        "AppleCurrentKeyboardLayoutInputSourceID",
+      ]),
+    );
+    return parsed
+      ? describeKeyboardLayout({ platform, ...parsed, variant: null, language: null })
+      : null;
+  }
+  if (platform === "win32") {
+    const script =
+      "Add-Type -AssemblyName System.Windows.Forms; " +
+      "$language = [System.Windows.Forms.InputLanguage]::CurrentInputLanguage; " +
+      "'{0}|{1}' -f $language.Culture.Name, $language.LayoutName";
+    const parsed = parseWindowsInputLanguage(
+      await run("powershell.exe", ["-NoProfile", "-NonInteractive", "-Command", script]),
+    );
+    return parsed
+      ? describeKeyboardLayout({
+          platform,
+          id: `${parsed.language}|${parsed.layout}`,
+          layout: parsed.layout,
+          variant: null,
+          language: parsed.language,
+        })
+      : null;
+  }
+  const parsed = parseSetxkbmapQuery(await run("setxkbmap", ["-query"]));
+  if (!parsed) {
+    return null;
+  }
+  const id = parsed.variant ? `${parsed.layout}(${parsed.variant})` : parsed.layout;
+  return describeKeyboardLayout({ platform, id, ...parsed, language: null });
+}
+
+export async function getKeyboardLayout(): Promise<KeyboardLayout | null> {
+  try {
+    return await readKeyboardLayout(process.platform);
+  } catch (error) {
+    log.warn("[keyboard] failed to read the keyboard layout", error);
+    return null;
+  }
+}
+
+/**
+ * There is no layout-changed notification in Electron. Layouts are switched
+ * from the menu bar or a system shortcut, so they are re-read on focus.
+ */
+export function registerKeyboardLayoutEvents(): void {
+  let lastSnapshot: string | null = null;
+
+  const refresh = () => {
+    void getKeyboardLayout().then((layout) => {
+      const snapshot = JSON.stringify(layout);
+      if (lastSnapshot !== null && snapshot !== lastSnapshot) {
+        emitDesktopEvent(KEYBOARD_LAYOUT_CHANGED_EVENT, layout);
+      }
+      lastSnapshot = snapshot;
+    });
+  };
+
+  refresh();
+  app.on("browser-window-focus", refresh);
+  powerMonitor.on("resume", refresh);
+}
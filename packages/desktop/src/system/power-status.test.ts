@@ -0,0 +1,35 @@
+import { describe, expect, it } from "vitest";
+import {
+  parseBatteryPercentLine,
+  parsePmsetBatteryPercent,
+  parsePmsetLowPowerMode,
+  resolvePollIntervalScale,
+} from "./power-status";
+
+describe("power status parsing", () => {
+  it("reads the battery level from pmset", () => {
+    const stdout =
+      "Now drawing from 'Battery Power'\n" +
+      " -InternalBattery-0 (id=4653155)\t85%; discharging; 4:12 remaining present: true\n";
+    expect(parsePmsetBatteryPercent(stdout)).toBe(85);
+    expect(parsePmsetBatteryPercent("Now drawing from 'AC Power'\n")).toBeNull();
+  });
+
+  it("detects Low Power Mode from pmset settings", () => {
+    expect(parsePmsetLowPowerMode("System-wide power settings:\n lowpowermode         1\n")).toBe(
+      true,
+    );
+    expect(parsePmsetLowPowerMode(" lowpowermode         0\n")).toBe(false);
+  });
+
+  it("reads sysfs and CIM percentages", () => {
+    expect(parseBatteryPercentLine("57\n")).toBe(57);
+    expect(parseBatteryPercentLine("\r\n")).toBeNull();
+  });
+
+  it("stretches polling on battery and further in low power mode", () => {
+    expect(resolvePollIntervalScale({ onBattery: false, lowPowerMode: false })).toBe(1);
+    expect(resolvePollIntervalScale({ onBattery: true, lowPowerMode: false })).toBe(2);
+    expect(resolvePollIntervalScale({ onBattery: true, lowPowerMode: true })).toBe(4);
+  });
+});
@@ -0,0 +1,160 @@
+import { execFile } from "node:child_process";
+import { readdir, readFile } from "node:fs/promises";
+import path from "node:path";
+import { promisify } from "node:util";
+import { powerMonitor } from "electron";
+import log from "electron-log/main";
+import { emitDesktopEvent } from "../window/desktop-events.js";
+
+const execFileAsync = promisify(execFile);
+
+const POWER_STATUS_CHANGED_EVENT = "power-status-changed";
+const POWER_QUERY_TIMEOUT_MS = 5_000;
+const LINUX_POWER_SUPPLY_DIR = "/sys/class/power_supply";
+const BATTERY_POLL_SCALE = 2;
+const LOW_POWER_POLL_SCALE = 4;
+
+export interface PowerStatus {
+  onBattery: boolean;
+  /** Charge level 0–100; `null` on machines without a battery or when it cannot be read. */
+  batteryPercent: number | null;
+  /** macOS Low Power Mode or the Linux power-saver profile. */
+  lowPowerMode: boolean;
+}
+
+/** `pmset -g batt`, e.g. `-InternalBattery-0 (id=123)	85%; discharging; 4:12 remaining`. */
+export function parsePmsetBatteryPercent(stdout: string): number | null {
+  const match = /(\d{1,3})%/.exec(stdout);
+  return match ? Math.min(100, Number(match[1])) : null;
+}
+
+/** `pmset -g` lists the active settings, including ` lowpowermode  1`. */
+export function parsePmsetLowPowerMode(stdout: string): boolean {
+  return /^\s*lowpowermode\s+1\s*$/m.test(stdout);
+}
+
+export function parseBatteryPercentLine(stdout: string): number | null {
+  const value = Number.parseInt(stdout.trim(), 10);
+  return Number.isInteger(value) && value >= 0 ? Math.min(100, value) : null;
+}
+
+/** Multiplier for background polling intervals, so the app idles more lightly off AC. */
+export function resolvePollIntervalScale(
+  status: Pick<PowerStatus, "onBattery" | "lowPowerMode">,
+): number {
+  if (status.lowPowerMode) {
+    return LOW_POWER_POLL_SCALE;
+  }
+  return status.onBattery ? BATTERY_POLL_SCALE : 1;
+}
+
+async function run(file: string, args: string[]): Promise<string> {
+  const { stdout } = await execFileAsync(file, args, {
+    timeout: POWER_QUERY_TIMEOUT_MS,
+    windowsHide: true,
+  });
+  return stdout;
+}
+
+async function readLinuxBatteryPercent(): Promise<number | null> {
+  const supplies = await readdir(LINUX_POWER_SUPPLY_DIR);
+  const battery = supplies.find((name) => name.startsWith("BAT"));
+  if (!battery) {
+    return null;
+  }
+  return parseBatteryPercentLine(
+    await readFile(path.join(LINUX_POWER_SUPPLY_DIR, battery, "capacity"), "utf8"),
+  );
+}
+
+async function readPlatformPowerDetails(
+  platform: NodeJS.Platform,
+): Promise<Pick<PowerStatus, "batteryPercent" | "lowPowerMode">> {
+  if (platform === "darwin") {
+    const [battery, settings] = await Promise.all([
+      run("pmset", ["-g", "batt"]),
+      run("pmset", ["-g"]),
+    ]);
+    return {
+      batteryPercent: parsePmsetBatteryPercent(battery),
+      lowPowerMode: parsePmsetLowPowerMode(settings),
+    };
+  }
+  if (platform === "win32") {
+    const stdout = await run("powershell.exe", [
+      "-NoProfile",
+      "-NonInteractive",
+      "-Command",
+      "(Get-CimInstance Win32_Battery | Select-Object -First 1).EstimatedChargeRemaining",
+    ]);
+    return { batteryPercent: parseBatteryPercentLine(stdout), lowPowerMode: false };
+  }
+  const [batteryPercent, profile] = await Promise.all([
+    readLinuxBatteryPercent().catch(() => null),
+    run("powerprofilesctl", ["get"]).catch(() => ""),
+  ]);
+  return { batteryPercent, lowPowerMode: profile.trim() === "power-saver" };
+}
+
+let lastPowerStatus: PowerStatus | null = null;
+const scaleListeners = new Set<() => void>();
+
+export async function getPowerStatus(): Promise<PowerStatus> {
+  const onBattery = powerMonitor.isOnBatteryPower();
+  let details: Pick<PowerStatus, "batteryPercent" | "lowPowerMode">;
+  try {
+    details = await readPlatformPowerDetails(process.platform);
+  } catch (error) {
+    log.warn("[power] failed to read battery details", error);
+    details = { batteryPercent: null, lowPowerMode: false };
+  }
+  lastPowerStatus = { onBattery, ...details };
+  return lastPowerStatus;
+}
+
+/** Scales a base polling interval by the last known power status. */
+export function scalePollInterval(baseMs: number): number {
+  const status = lastPowerStatus ?? {
+    onBattery: powerMonitor.isOnBatteryPower(),
+    lowPowerMode: false,
+  };
+  return baseMs * resolvePollIntervalScale(status);
+}
+
+/** Notifies pollers when `scalePollInterval` would return something different. */
+export function onPollIntervalScaleChanged(listener: () => void): () => void {
+  scaleListeners.add(listener);
+  return () => scaleListeners.delete(listener);
+}
+
+export function registerPowerStatusEvents(): void {
+  let lastSnapshot: string | null = null;
+  let lastScale = resolvePollIntervalScale({
+    onBattery: powerMonitor.isOnBatteryPower(),
+    lowPowerMode: false,
+  });
+
+  const refresh = () => {
+    void getPowerStatus().then((status) => {
+      const snapshot = JSON.stringify(status);
+      if (snapshot !== lastSnapshot) {
+        lastSnapshot = snapshot;
+        emitDesktopEvent(POWER_STATUS_CHANGED_EVENT, status);
+      }
+      const scale = resolvePollIntervalScale(status);
+      if (scale !== lastScale) {
+        lastScale = scale;
+        for (const listener of scaleListeners) {
+          listener();
+        }
+      }
+    });
+  };
+
+  refresh();
+  powerMonitor.on("on-battery", refresh);
+  powerMonitor.on("on-ac", refresh);
+  powerMonitor.on("resume", refresh);
+  // Low Power Mode has no event; users usually toggle it from outside the app.
+  powerMonitor.on("unlock-screen", refresh);
+}
@@ -0,0 +1,82 @@
+import { powerSaveBlocker } from "electron";
+import log from "electron-log/main";
+import { getDesktopSettingsStore } from "../settings/desktop-settings-electron.js";
+import { emitDesktopEvent } from "../window/desktop-events.js";
+
+const PRESENTATION_MODE_CHANGED_EVENT = "presentation-mode-changed";
+
+export interface PresentationModeState {
+  enabled: boolean;
+  startedAt: string | null;
+  /** When the display may sleep again on its own. */
+  expiresAt: string | null;
+}
+
+interface ActivePresentation {
+  blockerId: number;
+  startedAt: Date;
+  expiresAt: Date;
+  releaseTimer: NodeJS.Timeout;
+}
+
+let active: ActivePresentation | null = null;
+
+export function getPresentationMode(): PresentationModeState {
+  if (!active) {
+    return { enabled: false, startedAt: null, expiresAt: null };
+  }
+  return {
+    enabled: true,
+    startedAt: active.startedAt.toISOString(),
+    expiresAt: active.expiresAt.toISOString(),
+  };
+}
+
+function release(): void {
+  if (!active) {
+    return;
+  }
+  clearTimeout(active.releaseTimer);
+  if (powerSaveBlocker.isStarted(active.blockerId)) {
+    powerSaveBlocker.stop(active.blockerId);
+  }
+  active = null;
+}
+
+/**
+ * Keeps the display awake while the user reviews or screen-shares, without
+ * holding the system awake the way a running agent would. Enabling again
+ * restarts the countdown to the configured maximum.
+ */
+export async function setPresentationMode(
+  args: Record<string, unknown> | undefined,
+): Promise<PresentationModeState> {
+  if (typeof args?.enabled !== "boolean") {
+    throw new Error("enabled must be a boolean.");
+  }
+  if (!args.enabled) {
+    release();
+    return getPresentationMode();
+  }
+
+  const { maxDurationMinutes } = (await getDesktopSettingsStore().get()).presentation;
+  const maxDurationMs = maxDurationMinutes * 60_000;
+  const startedAt = active?.startedAt ?? new Date();
+  const blockerId = active?.blockerId ?? powerSaveBlocker.start("prevent-display-sleep");
+  if (active) {
+    clearTimeout(active.releaseTimer);
+  }
+  const releaseTimer = setTimeout(() => {
+    log.info(`[presentation] display sleep allowed again after ${maxDurationMinutes} minutes`);
+    release();
+    emitDesktopEvent(PRESENTATION_MODE_CHANGED_EVENT, { ...getPresentationMode(), expired: true });
+  }, maxDurationMs);
+  releaseTimer.unref();
+  active = {
+    blockerId,
+    startedAt,
+    expiresAt: new Date(Date.now() + maxDurationMs),
+    releaseTimer,
+  };
+  return getPresentationMode();
+}
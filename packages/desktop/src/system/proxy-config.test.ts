@@ -0,0 +1,94 @@
+import { describe, expect, it } from "vitest";
+import {
+  parseScutilProxyOutput,
+  parseWindowsInternetSettings,
+  readProxyEnv,
+  toElectronProxyConfig,
+} from "./proxy-config";
+
+describe("parseScutilProxyOutput", () => {
+  it("reads enabled proxies, PAC URL, and exceptions", () => {
+    const output = [
+      "<dictionary> {",
+      "  ExceptionsList : <array> {",
+      "    0 : *.local",
+      "    1 : 169.254/16",
+      "  }",
+      "  HTTPEnable : 1",
+      "  HTTPPort : 8080",
+      "  HTTPProxy : proxy.corp",
+      "  HTTPSEnable : 0",
+      "  HTTPSProxy : ignored.corp",
+      "  ProxyAutoConfigEnable : 1",
+      "  ProxyAutoConfigURLString : http://wpad.corp/wpad.dat",
+      "}",
+    ].join("\n");
+
+    expect(parseScutilProxyOutput(output)).toEqual({
+      httpProxy: "proxy.corp:8080",
+      httpsProxy: null,
+      pacUrl: "http://wpad.corp/wpad.dat",
+      bypass: ["*.local", "169.254/16"],
+    });
+  });
+});
+
+describe("parseWindowsInternetSettings", () => {
+  it("reads the WinINET proxy server, PAC URL, and overrides", () => {
+    const output = [
+      "HKEY_CURRENT_USER\\Software\\Microsoft\\Windows\\CurrentVersion\\Internet Settings",
+      "    ProxyEnable    REG_DWORD    0x1",
+      "    ProxyServer    REG_SZ    proxy.corp:3128",
+      "    ProxyOverride    REG_SZ    <local>;*.corp",
+      "    AutoConfigURL    REG_SZ    http://wpad.corp/proxy.pac",
+    ].join("\r\n");
+
+    expect(parseWindowsInternetSettings(output)).toEqual({
+      httpProxy: "proxy.corp:3128",
+      httpsProxy: "proxy.corp:3128",
+      pacUrl: "http://wpad.corp/proxy.pac",
+      bypass: ["<local>", "*.corp"],
+    });
+  });
+
+  it("ignores the proxy server when the proxy is disabled", () => {
+    const output = "    ProxyEnable    REG_DWORD    0x0\n    ProxyServer    REG_SZ    proxy:1";
+
+    expect(parseWindowsInternetSettings(output).httpProxy).toBeNull();
+  });
+});
+
+describe("readProxyEnv", () => {
+  it("accepts upper and lower case proxy variables", () => {
+    expect(
+      readProxyEnv({
+        https_proxy: "http://proxy.corp:8080",
+        NO_PROXY: "localhost, 127.0.0.1",
+      }),
+    ).toEqual({
+      httpProxy: null,
+      httpsProxy: "http://proxy.corp:8080",
+      pacUrl: null,
+      bypass: ["localhost", "127.0.0.1"],
+    });
+  });
+});
+
+describe("toElectronProxyConfig", () => {
+  it("maps overrides onto Chromium proxy modes", () => {
+    expect(toElectronProxyConfig({ mode: "direct", url: null })).toEqual({ mode: "direct" });
+    expect(toElectronProxyConfig({ mode: "fixed", url: "proxy.corp:8080" })).toEqual({
+      mode: "fixed_servers",
+      proxyRules: "proxy.corp:8080",
+    });
+    expect(toElectronProxyConfig({ mode: "pac", url: "http://wpad/wpad.dat" })).toEqual({
+      mode: "pac_script",
+      pacScript: "http://wpad/wpad.dat",
+    });
+  });
+
+  it("falls back to system settings when an override has no url", () => {
+    expect(toElectronProxyConfig({ mode: "fixed", url: null })).toEqual({ mode: "system" });
+    expect(toElectronProxyConfig({ mode: "pac", url: null })).toEqual({ mode: "system" });
+  });
+});
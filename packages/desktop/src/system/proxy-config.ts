@@ -0,0 +1,208 @@
+import { execFile } from "node:child_process";
+import { promisify } from "node:util";
+import { session } from "electron";
+import log from "electron-log/main";
+import type { DesktopSettings } from "../settings/desktop-settings.js";
+
+const execFileAsync = promisify(execFile);
+const SYSTEM_PROXY_PROBE_TIMEOUT_MS = 2000;
+const WINDOWS_INTERNET_SETTINGS_KEY =
+  "HKCU\\Software\\Microsoft\\Windows\\CurrentVersion\\Internet Settings";
+
+export interface SystemProxyInfo {
+  httpProxy: string | null;
+  httpsProxy: string | null;
+  pacUrl: string | null;
+  bypass: string[];
+}
+
+export interface ProxyConfigInfo {
+  source: "system" | "override";
+  mode: DesktopSettings["proxy"]["mode"];
+  proxyRules: string | null;
+  pacUrl: string | null;
+  system: SystemProxyInfo;
+  /** Chromium's resolution for `url`, e.g. `PROXY proxy.corp:8080; DIRECT`. */
+  resolved: { url: string; proxy: string } | null;
+}
+
+let appliedProxyConfigKey: string | null = null;
+
+const EMPTY_SYSTEM_PROXY: SystemProxyInfo = {
+  httpProxy: null,
+  httpsProxy: null,
+  pacUrl: null,
+  bypass: [],
+};
+
+function toTrimmedString(value: unknown): string | null {
+  if (typeof value !== "string") {
+    return null;
+  }
+  const trimmed = value.trim();
+  return trimmed.length > 0 ? trimmed : null;
+}
+
+function splitBypassList(value: string | null, separator: RegExp): string[] {
+  if (!value) {
+    return [];
+  }
+  return value
+    .split(separator)
+    .map((entry) => entry.trim())
+    .filter((entry) => entry.length > 0);
+}
+
+export function readProxyEnv(env: NodeJS.ProcessEnv): SystemProxyInfo {
+  return {
+    httpProxy: toTrimmedString(env.HTTP_PROXY ?? env.http_proxy),
+    httpsProxy: toTrimmedString(env.HTTPS_PROXY ?? env.https_proxy),
+    pacUrl: null,
+    bypass: splitBypassList(toTrimmedString(env.NO_PROXY ?? env.no_proxy), /,/),
+  };
+}
+
+/** Parses `scutil --proxy`, which prints the active SystemConfiguration proxy dictionary. */
+export function parseScutilProxyOutput(output: string): SystemProxyInfo {
+  const values = new Map<string, string>();
+  const bypass: string[] = [];
+  let inExceptions = false;
+
+  for (const rawLine of output.split("\n")) {
+    const line = rawLine.trim();
+    if (inExceptions) {
+      if (line === "}") {
+        inExceptions = false;
+        continue;
+      }
+      const match = /^\d+\s*:\s*(.+)$/.exec(line);
+      if (match) bypass.push(match[1]);
+      continue;
+    }
+    if (line.startsWith("ExceptionsList")) {
+      inExceptions = true;
+      continue;
+    }
+    const match = /^(\w+)\s*:\s*(.+)$/.exec(line);
+    if (match) values.set(match[1], match[2].trim());
+  }
+
+  const hostPort = (prefix: "HTTP" | "HTTPS"): string | null => {
+    if (values.get(`${prefix}Enable`) !== "1") return null;
+    const host = values.get(`${prefix}Proxy`);
+    if (!host) return null;
+    const port = values.get(`${prefix}Port`);
+    return port ? `${host}:${port}` : host;
+  };
+
+  return {
+    httpProxy: hostPort("HTTP"),
+    httpsProxy: hostPort("HTTPS"),
+    pacUrl:
+      values.get("ProxyAutoConfigEnable") === "1"
+        ? (values.get("ProxyAutoConfigURLString") ?? null)
+        : null,
+    bypass,
+  };
+}
+
+/** Parses `reg query` output for the per-user WinINET Internet Settings key. */
+export function parseWindowsInternetSettings(output: string): SystemProxyInfo {
+  const values = new Map<string, string>();
+  for (const rawLine of output.split(/\r?\n/)) {
+    const match = /^\s*(\w+)\s+REG_\w+\s+(.*)$/.exec(rawLine);
+    if (match) values.set(match[1], match[2].trim());
+  }
+
+  const enabled = Number.parseInt(values.get("ProxyEnable") ?? "0", 16) === 1;
+  const server = enabled ? toTrimmedString(values.get("ProxyServer")) : null;
+
+  return {
+    httpProxy: server,
+    httpsProxy: server,
+    pacUrl: toTrimmedString(values.get("AutoConfigURL")),
+    bypass: splitBypassList(toTrimmedString(values.get("ProxyOverride")), /;/),
+  };
+}
+
+async function probePlatformProxy(platform: NodeJS.Platform): Promise<SystemProxyInfo | null> {
+  try {
+    if (platform === "darwin") {
+      const { stdout } = await execFileAsync("scutil", ["--proxy"], {
+        timeout: SYSTEM_PROXY_PROBE_TIMEOUT_MS,
+      });
+      return parseScutilProxyOutput(stdout);
+    }
+    if (platform === "win32") {
+      const { stdout } = await execFileAsync("reg", ["query", WINDOWS_INTERNET_SETTINGS_KEY], {
+        timeout: SYSTEM_PROXY_PROBE_TIMEOUT_MS,
+      });
+      return parseWindowsInternetSettings(stdout);
+    }
+  } catch (error) {
+    log.warn("[proxy] failed to read system proxy settings", error);
+  }
+  return null;
+}
+
+export async function detectSystemProxy(): Promise<SystemProxyInfo> {
+  const fromEnv = readProxyEnv(process.env);
+  const fromPlatform = (await probePlatformProxy(process.platform)) ?? EMPTY_SYSTEM_PROXY;
+  return {
+    httpProxy: fromPlatform.httpProxy ?? fromEnv.httpProxy,
+    httpsProxy: fromPlatform.httpsProxy ?? fromEnv.httpsProxy,
+    pacUrl: fromPlatform.pacUrl,
+    bypass: [...new Set([...fromPlatform.bypass, ...fromEnv.bypass])],
+  };
+}
+
+/**
+ * Maps the desktop proxy override onto Chromium's proxy config. An override
+ * without a URL falls back to the system settings instead of going direct.
+ */
+export function toElectronProxyConfig(proxy: DesktopSettings["proxy"]): Electron.ProxyConfig {
+  if (proxy.mode === "direct") {
+    return { mode: "direct" };
+  }
+  if (proxy.mode === "fixed" && proxy.url) {
+    return { mode: "fixed_servers", proxyRules: proxy.url };
+  }
+  if (proxy.mode === "pac" && proxy.url) {
+    return { mode: "pac_script", pacScript: proxy.url };
+  }
+  return { mode: "system" };
+}
+
+/**
+ * The renderer's daemon WebSocket, the auto-updater, and downloads all go
+ * through the default session, so one `setProxy` covers every client.
+ */
+export async function applyProxySettings(proxy: DesktopSettings["proxy"]): Promise<void> {
+  const config = toElectronProxyConfig(proxy);
+  const configKey = JSON.stringify(config);
+  if (configKey === appliedProxyConfigKey) {
+    return;
+  }
+  appliedProxyConfigKey = configKey;
+  await session.defaultSession.setProxy(config);
+  await session.defaultSession.closeAllConnections();
+  log.info("[proxy] applied proxy config", { mode: config.mode });
+}
+
+export async function getProxyConfig(input: {
+  proxy: DesktopSettings["proxy"];
+  url?: unknown;
+}): Promise<ProxyConfigInfo> {
+  const config = toElectronProxyConfig(input.proxy);
+  const system = await detectSystemProxy();
+  const url = toTrimmedString(input.url);
+
+  return {
+    source: config.mode === "system" ? "system" : "override",
+    mode: input.proxy.mode,
+    proxyRules: config.proxyRules ?? null,
+    pacUrl: config.mode === "system" ? system.pacUrl : (config.pacScript ?? null),
+    system,
+    resolved: url ? { url, proxy: await session.defaultSession.resolveProxy(url) } : null,
+  };
+}
@@ -0,0 +1,63 @@
+import { describe, expect, it } from "vitest";
+import {
+  mergeSystemFonts,
+  parseFamilyLines,
+  parseFcListOutput,
+  parseSystemProfilerFonts,
+} from "./system-fonts";
+
+describe("system font listing", () => {
+  it("uses fontconfig spacing to classify monospace families", () => {
+    const stdout = "DejaVu Sans\t0\nDejaVu Sans Mono\t100\nNoto Sans CJK\t90\n\n";
+    expect(parseFcListOutput(stdout)).toEqual([
+      { family: "DejaVu Sans", monospace: false },
+      { family: "DejaVu Sans Mono", monospace: true },
+      { family: "Noto Sans CJK", monospace: true },
+    ]);
+  });
+
+  it("falls back to the family name when fontconfig omits spacing", () => {
+    expect(parseFcListOutput("Fira Code\t\nCantarell\t")).toEqual([
+      { family: "Fira Code", monospace: true },
+      { family: "Cantarell", monospace: false },
+    ]);
+  });
+
+  it("reads typeface families from system_profiler", () => {
+    const stdout = JSON.stringify({
+      SPFontsDataType: [
+        { typefaces: [{ family: "Menlo" }, { family: "Helvetica" }] },
+        { typefaces: [{ name: "no family" }] },
+        {},
+      ],
+    });
+    expect(parseSystemProfilerFonts(stdout)).toEqual([
+      { family: "Menlo", monospace: true },
+      { family: "Helvetica", monospace: false },
+    ]);
+  });
+
+  it("reads one family per line", () => {
+    expect(parseFamilyLines("Consolas\r\nSegoe UI\r\n\r\n")).toEqual([
+      { family: "Consolas", monospace: true },
+      { family: "Segoe UI", monospace: false },
+    ]);
+  });
+
+  it("dedupes, drops private families, and sorts case-insensitively", () => {
+    expect(
+      mergeSystemFonts([
+        { family: "menlo", monospace: true },
+        { family: "Arial", monospace: false },
+        { family: ".SF NS", monospace: false },
+        { family: "Arial", monospace: false },
+        { family: "Zed Mono", monospace: false },
+        { family: "Zed Mono", monospace: true },
+      ]),
+    ).toEqual([
+      { family: "Arial", monospace: false },
+      { family: "menlo", monospace: true },
+      { family: "Zed Mono", monospace: true },
+    ]);
+  });
+});
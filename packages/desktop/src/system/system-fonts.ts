@@ -0,0 +1,140 @@
+import { execFile } from "node:child_process";
+import { promisify } from "node:util";
+import log from "electron-log/main";
+import { isRecord } from "../utils/is-record.js";
+
+const execFileAsync = promisify(execFile);
+
+const FONT_ENUMERATION_TIMEOUT_MS = 15_000;
+// system_profiler's font report is several MB on a typical Mac.
+const FONT_ENUMERATION_MAX_BUFFER = 64 * 1024 * 1024;
+// Used where the platform listing has no spacing metadata (macOS, Windows).
+const MONOSPACE_NAME_PATTERN =
+  /mono|code|consol|courier|menlo|monaco|terminal|fixed|hack|iosevka|inconsolata|cascadia/i;
+const FC_SPACING_MONO = 100;
+const FC_SPACING_DUAL = 90;
+
+export interface SystemFont {
+  family: string;
+  monospace: boolean;
+}
+
+export interface SystemFontList {
+  fonts: SystemFont[];
+  /** False when the platform listing failed; the UI should keep its built-in fallbacks. */
+  enumerated: boolean;
+}
+
+export function looksMonospace(family: string): boolean {
+  return MONOSPACE_NAME_PATTERN.test(family);
+}
+
+/** Sorted, de-duplicated by family; a family is monospace if any listing said so. */
+export function mergeSystemFonts(fonts: SystemFont[]): SystemFont[] {
+  const byFamily = new Map<string, SystemFont>();
+  for (const font of fonts) {
+    const family = font.family.trim();
+    if (!family || family.startsWith(".")) {
+      // Dot-prefixed families are private macOS UI fonts.
+      continue;
+    }
+    const existing = byFamily.get(family);
+    byFamily.set(family, { family, monospace: font.monospace || existing?.monospace === true });
+  }
+  return [...byFamily.values()].sort((left, right) =>
+    left.family.localeCompare(right.family, undefined, { sensitivity: "base" }),
+  );
+}
+
+/** Output of `fc-list --format '%{family[0]}\t%{spacing}\n'`. */
+export function parseFcListOutput(stdout: string): SystemFont[] {
+  return stdout.split("\n").flatMap((line): SystemFont[] => {
+    const [family = "", spacing = ""] = line.split("\t");
+    if (!family.trim()) {
+      return [];
+    }
+    const spacingValue = Number.parseInt(spacing, 10);
+    const monospace = Number.isFinite(spacingValue)
+      ? spacingValue === FC_SPACING_MONO || spacingValue === FC_SPACING_DUAL
+      : looksMonospace(family);
+    return [{ family: family.trim(), monospace }];
+  });
+}
+
+/** Output of `system_profiler SPFontsDataType -json`. */
+export function parseSystemProfilerFonts(stdout: string): SystemFont[] {
+  const parsed: unknown = JSON.parse(stdout);
+  const files =
+    isRecord(parsed) && Array.isArray(parsed.SPFontsDataType) ? parsed.SPFontsDataType : [];
+  return files.flatMap((file: unknown): SystemFont[] => {
+    if (!isRecord(file) || !Array.isArray(file.typefaces)) {
+      return [];
+    }
+    return file.typefaces.flatMap((typeface: unknown): SystemFont[] => {
+      if (!isRecord(typeface) || typeof typeface.family !== "string") {
+        return [];
+      }
+      return [{ family: typeface.family, monospace: looksMonospace(typeface.family) }];
+    });
+  });
+}
+
+/** One family name per line, as printed by the PowerShell listing below. */
+export function parseFamilyLines(stdout: string): SystemFont[] {
+  return stdout
+    .split(/\r?\n/)
+    .map((line) => line.trim())
+    .filter((line) => line.length > 0)
+    .map((family) => ({ family, monospace: looksMonospace(family) }));
+}
+
+async function enumerateFonts(platform: NodeJS.Platform): Promise<SystemFont[]> {
+  const options = {
+    timeout: FONT_ENUMERATION_TIMEOUT_MS,
+    maxBuffer: FONT_ENUMERATION_MAX_BUFFER,
+    windowsHide: true,
+  };
+  if (platform === "darwin") {
+    const { stdout } = await execFileAsync(
+      "system_profiler",
+      ["SPFontsDataType", "-json", "-detailLevel", "mini"],
+      options,
+    );
+    return parseSystemProfilerFonts(stdout);
+  }
+  if (platform === "win32") {
+    const script =
+      "Add-Type -AssemblyName System.Drawing; " +
+      "(New-Object System.Drawing.Text.InstalledFontCollection).Families | " +
+      "ForEach-Object { $_.Name }";
+    const { stdout } = await execFileAsync(
+      "powershell.exe",
+      ["-NoProfile", "-NonInteractive", "-Command", script],
+      options,
+    );
+    return parseFamilyLines(stdout);
+  }
+  const { stdout } = await execFileAsync(
+    "fc-list",
+    ["--format", "%{family[0]}\\t%{spacing}\\n"],
+    options,
+  );
+  return parseFcListOutput(stdout);
+}
+
+let cachedFonts: Promise<SystemFontList> | null = null;
+
+export async function listSystemFonts(input: { monospaceOnly: boolean }): Promise<SystemFontList> {
+  cachedFonts ??= enumerateFonts(process.platform)
+    .then((fonts) => ({ fonts: mergeSystemFonts(fonts), enumerated: true }))
+    .catch((error) => {
+      log.warn("[fonts] failed to enumerate system fonts", error);
+      // Retry on the next call rather than caching the failure.
+      cachedFonts = null;
+      return { fonts: [], enumerated: false };
+    });
+  const list = await cachedFonts;
+  return input.monospaceOnly
+    ? { ...list, fonts: list.fonts.filter((font) => font.monospace) }
+    : list;
+}
@@ -0,0 +1,93 @@
+import { describe, expect, it, vi } from "vitest";
+import { detectForcedClockFormat, resolveSystemLocaleInfo } from "./system-locale";
+
+describe("resolveSystemLocaleInfo", () => {
+  it("derives clock format and week start from the system region", () => {
+    expect(
+      resolveSystemLocaleInfo({
+        systemLocale: "en-US",
+        preferredLanguages: ["en-US", "fr-FR"],
+        forcedClockFormat: null,
+      }),
+    ).toEqual({
+      locale: "en-US",
+      preferredLanguages: ["en-US", "fr-FR"],
+      clockFormat: "12h",
+      firstDayOfWeek: 0,
+    });
+
+    expect(
+      resolveSystemLocaleInfo({
+        systemLocale: "en_GB",
+        preferredLanguages: ["en-GB"],
+        forcedClockFormat: null,
+      }),
+    ).toMatchObject({
+      locale: "en-GB",
+      clockFormat: "24h",
+      firstDayOfWeek: 1,
+    });
+  });
+
+  it("prefers an explicit OS clock override over the region default", () => {
+    expect(
+      resolveSystemLocaleInfo({
+        systemLocale: "en-US",
+        preferredLanguages: [],
+        forcedClockFormat: "24h",
+      }).clockFormat,
+    ).toBe("24h");
+  });
+
+  it("falls back to the preferred languages, then en-US, for unusable locales", () => {
+    expect(
+      resolveSystemLocaleInfo({
+        systemLocale: "",
+        preferredLanguages: ["de-DE"],
+        forcedClockFormat: null,
+      }).locale,
+    ).toBe("de-DE");
+
+    expect(
+      resolveSystemLocaleInfo({
+        systemLocale: "not a locale",
+        preferredLanguages: [],
+        forcedClockFormat: null,
+      }),
+    ).toMatchObject({
+      locale: "en-US",
+      preferredLanguages: ["en-US"],
+    });
+  });
+});
+
+describe("detectForcedClockFormat", () => {
+  it("only probes macOS defaults on darwin", async () => {
+    const readDefaults = vi.fn();
+
+    expect(await detectForcedClockFormat({ platform: "win32", readDefaults })).toBeNull();
+    expect(readDefaults).not.toHaveBeenCalled();
+  });
+
+  it("prefers the 24-hour override over the 12-hour one", async () => {
+    const readDefaults = vi.fn(async () => "1\n");
+
+    expect(await detectForcedClockFormat({ platform: "darwin", readDefaults })).toBe("24h");
+  });
+
+  it("reads the 12-hour override", async () => {
+    const readDefaults = vi.fn(async (key: string) =>
+      key === "AppleICUForce12HourTime" ? "1\n" : "0\n",
+    );
+
+    expect(await detectForcedClockFormat({ platform: "darwin", readDefaults })).toBe("12h");
+  });
+
+  it("treats missing defaults keys as no override", async () => {
+    const readDefaults = vi.fn(async () => {
+      throw new Error("does not exist");
+    });
+
+    expect(await detectForcedClockFormat({ platform: "darwin", readDefaults })).toBeNull();
+  });
+});
@@ -0,0 +1,179 @@
+import { execFile } from "node:child_process";
+import { promisify } from "node:util";
+import { app, powerMonitor } from "electron";
+import { emitDesktopEvent } from "../window/desktop-events.js";
+
+const SYSTEM_LOCALE_CHANGED_EVENT = "system-locale-changed";
+const FALLBACK_LOCALE = "en-US";
+
+// Regions whose week starts on Sunday or Saturday per CLDR; everything else starts on Monday.
+const SUNDAY_FIRST_REGIONS = new Set(
+  (
+    "AG AS BD BR BS BT BW BZ CA CN CO DM DO ET GT GU HK HN ID IL IN JM JP KE KH KR LA MH " +
+    "MM MO MT MX MZ NI NP PA PE PH PK PR PT PY SA SG SV TH TT TW UM US VE VI WS YE ZA ZW"
+  ).split(" "),
+);
+const SATURDAY_FIRST_REGIONS = new Set("AE AF BH DJ DZ EG IQ IR JO KW LY OM QA SD SY".split(" "));
+
+export type ClockFormat = "12h" | "24h";
+
+export interface SystemLocaleInfo {
+  locale: string;
+  preferredLanguages: string[];
+  clockFormat: ClockFormat;
+  /** 0 = Sunday … 6 = Saturday, matching `Date.prototype.getDay()`. */
+  firstDayOfWeek: number;
+}
+
+export interface ResolveSystemLocaleInfoInput {
+  systemLocale: string;
+  preferredLanguages: string[];
+  forcedClockFormat: ClockFormat | null;
+}
+
+interface WeekInfoLocale extends Intl.Locale {
+  getWeekInfo?: () => { firstDay: number };
+  weekInfo?: { firstDay: number };
+}
+
+export function canonicalizeLocale(value: string): string | null {
+  const trimmed = value.trim().replace(/_/g, "-");
+  if (trimmed.length === 0) {
+    return null;
+  }
+  try {
+    return Intl.getCanonicalLocales(trimmed)[0] ?? null;
+  } catch {
+    return null;
+  }
+}
+
+function resolveClockFormat(locale: string): ClockFormat {
+  const { hourCycle } = new Intl.DateTimeFormat(locale, { hour: "numeric" }).resolvedOptions();
+  return hourCycle === "h11" || hourCycle === "h12" ? "12h" : "24h";
+}
+
+function resolveFirstDayOfWeek(locale: string): number {
+  const intlLocale = new Intl.Locale(locale) as WeekInfoLocale;
+  const weekInfo = intlLocale.getWeekInfo?.() ?? intlLocale.weekInfo;
+  if (weekInfo && Number.isInteger(weekInfo.firstDay)) {
+    // Intl reports 1 = Monday … 7 = Sunday.
+    return weekInfo.firstDay % 7;
+  }
+
+  const region = intlLocale.maximize().region ?? "";
+  if (SUNDAY_FIRST_REGIONS.has(region)) {
+    return 0;
+  }
+  if (SATURDAY_FIRST_REGIONS.has(region)) {
+    return 6;
+  }
+  return 1;
+}
+
+export function resolveSystemLocaleInfo(input: ResolveSystemLocaleInfoInput): SystemLocaleInfo {
+  const preferredLanguages = input.preferredLanguages
+    .map(canonicalizeLocale)
+    .filter((value): value is string => value !== null);
+  const locale =
+    canonicalizeLocale(input.systemLocale) ?? preferredLanguages[0] ?? FALLBACK_LOCALE;
+
+  return {
+    locale,
+    preferredLanguages: preferredLanguages.length > 0 ? preferredLanguages : [locale],
+    clockFormat: input.forcedClockFormat ?? resolveClockFormat(locale),
+    firstDayOfWeek: resolveFirstDayOfWeek(locale),
+  };
+}
+
+type ReadDefaults = (key: string) => Promise<string>;
+
+const execFileAsync = promisify(execFile);
+
+async function readMacDefaults(key: string): Promise<string> {
+  const { stdout } = await execFileAsync("defaults", ["read", "-g", key], {
+    encoding: "utf-8",
+    timeout: 1000,
+  });
+  return stdout;
+}
+
+async function readMacDefaultsFlag(key: string, readDefaults: ReadDefaults): Promise<boolean> {
+  try {
+    return (await readDefaults(key)).trim() === "1";
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * macOS lets users override the region's clock in System Settings without
+ * changing the locale, so Intl alone reports the wrong hour cycle there.
+ */
+export async function detectForcedClockFormat(input: {
+  platform: NodeJS.Platform;
+  readDefaults?: ReadDefaults;
+}): Promise<ClockFormat | null> {
+  if (input.platform !== "darwin") {
+    return null;
+  }
+
+  const readDefaults = input.readDefaults ?? readMacDefaults;
+  const [force24, force12] = await Promise.all([
+    readMacDefaultsFlag("AppleICUForce24HourTime", readDefaults),
+    readMacDefaultsFlag("AppleICUForce12HourTime", readDefaults),
+  ]);
+  if (force24) {
+    return "24h";
+  }
+  return force12 ? "12h" : null;
+}
+
+// Last detected macOS clock override; `defaults` is too slow to run on every call.
+let cachedForcedClockFormat: ClockFormat | null = null;
+
+/** Locale info from the last refresh, without spawning anything. */
+export function getSystemLocaleInfo(): SystemLocaleInfo {
+  return resolveSystemLocaleInfo({
+    systemLocale: app.getSystemLocale(),
+    preferredLanguages: app.getPreferredSystemLanguages(),
+    forcedClockFormat: cachedForcedClockFormat,
+  });
+}
+
+async function refreshSystemLocaleInfo(): Promise<SystemLocaleInfo> {
+  cachedForcedClockFormat = await detectForcedClockFormat({ platform: process.platform });
+  return getSystemLocaleInfo();
+}
+
+/**
+ * Electron has no locale-changed event, so re-read the region settings whenever
+ * the user is likely to have just visited System Settings.
+ */
+export function registerSystemLocaleEvents(): void {
+  let lastSnapshot = JSON.stringify(getSystemLocaleInfo());
+  let refreshing = false;
+
+  const refresh = () => {
+    if (refreshing) {
+      return;
+    }
+    refreshing = true;
+    void refreshSystemLocaleInfo()
+      .then((info) => {
+        const snapshot = JSON.stringify(info);
+        if (snapshot === lastSnapshot) {
+          return;
+        }
+        lastSnapshot = snapshot;
+        emitDesktopEvent(SYSTEM_LOCALE_CHANGED_EVENT, info);
+      })
+      .finally(() => {
+        refreshing = false;
+      });
+  };
+
+  refresh();
+  app.on("browser-window-focus", refresh);
+  powerMonitor.on("resume", refresh);
+}
@@ -0,0 +1,17 @@
+import { describe, expect, it } from "vitest";
+import { parseGnomeTextScalingFactor, parseWindowsTextScaleFactor } from "./text-scale";
+
+describe("system text scale parsing", () => {
+  it("reads the Windows text size percentage", () => {
+    const stdout =
+      "\r\nHKEY_CURRENT_USER\\Software\\Microsoft\\Accessibility\r\n" +
+      "    TextScaleFactor    REG_DWORD    0x7d\r\n";
+    expect(parseWindowsTextScaleFactor(stdout)).toBe(1.25);
+    expect(parseWindowsTextScaleFactor("ERROR: The system was unable to find")).toBeNull();
+  });
+
+  it("reads the GNOME text scaling factor", () => {
+    expect(parseGnomeTextScalingFactor("1.5\n")).toBe(1.5);
+    expect(parseGnomeTextScalingFactor("")).toBeNull();
+  });
+});
@@ -0,0 +1,71 @@
+import { execFile } from "node:child_process";
+import { promisify } from "node:util";
+import { app } from "electron";
+import log from "electron-log/main";
+
+const execFileAsync = promisify(execFile);
+
+const TEXT_SCALE_QUERY_TIMEOUT_MS = 5_000;
+
+/** `reg query ... /v TextScaleFactor` prints the percentage as hex, e.g. `REG_DWORD    0x7d`. */
+export function parseWindowsTextScaleFactor(stdout: string): number | null {
+  const match = /TextScaleFactor\s+REG_DWORD\s+0x([0-9a-f]+)/i.exec(stdout);
+  if (!match) {
+    return null;
+  }
+  const percent = Number.parseInt(match[1]!, 16);
+  return percent >= 100 ? percent / 100 : null;
+}
+
+/** `gsettings get org.gnome.desktop.interface text-scaling-factor`, e.g. `1.25`. */
+export function parseGnomeTextScalingFactor(stdout: string): number | null {
+  const value = Number.parseFloat(stdout.trim());
+  return Number.isFinite(value) && value > 0 ? value : null;
+}
+
+/**
+ * macOS only exposes text size per app, so it is left to the user zoom there.
+ */
+export async function readSystemTextScale(platform: NodeJS.Platform): Promise<number> {
+  try {
+    if (platform === "win32") {
+      const { stdout } = await execFileAsync(
+        "reg",
+        ["query", "HKCU\\Software\\Microsoft\\Accessibility", "/v", "TextScaleFactor"],
+        { timeout: TEXT_SCALE_QUERY_TIMEOUT_MS, windowsHide: true },
+      );
+      return parseWindowsTextScaleFactor(stdout) ?? 1;
+    }
+    if (platform === "linux") {
+      const { stdout } = await execFileAsync(
+        "gsettings",
+        ["get", "org.gnome.desktop.interface", "text-scaling-factor"],
+        { timeout: TEXT_SCALE_QUERY_TIMEOUT_MS },
+      );
+      return parseGnomeTextScalingFactor(stdout) ?? 1;
+    }
+  } catch (error) {
+    // Missing values are normal: Windows omits the key until text size is changed.
+    log.debug("[text-scale] no system text scale", error);
+  }
+  return 1;
+}
+
+/** Re-reads the preference whenever the user returns to the app, as for locale changes. */
+export function registerSystemTextScaleEvents(input: {
+  onChange: (scale: number) => void;
+}): void {
+  let lastScale: number | null = null;
+
+  const refresh = () => {
+    void readSystemTextScale(process.platform).then((scale) => {
+      if (scale !== lastScale) {
+        lastScale = scale;
+        input.onChange(scale);
+      }
+    });
+  };
+
+  refresh();
+  app.on("browser-window-focus", refresh);
+}
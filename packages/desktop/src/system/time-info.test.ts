@@ -0,0 +1,29 @@
+import { describe, expect, it } from "vitest";
+import { formatUtcOffset, resolveTimeZoneInfo, resolveUtcOffsetMinutes } from "./time-info";
+
+describe("resolveUtcOffsetMinutes", () => {
+  it("follows daylight saving for the given instant", () => {
+    expect(resolveUtcOffsetMinutes("Europe/Paris", new Date("2026-01-15T12:00:00Z"))).toBe(60);
+    expect(resolveUtcOffsetMinutes("Europe/Paris", new Date("2026-07-15T12:00:00Z"))).toBe(120);
+    expect(resolveUtcOffsetMinutes("America/St_Johns", new Date("2026-01-15T12:00:00Z"))).toBe(
+      -210,
+    );
+    expect(resolveUtcOffsetMinutes("UTC", new Date("2026-01-15T12:00:00Z"))).toBe(0);
+  });
+});
+
+describe("formatUtcOffset", () => {
+  it("pads hours and minutes with an explicit sign", () => {
+    expect(formatUtcOffset(0)).toBe("+00:00");
+    expect(formatUtcOffset(330)).toBe("+05:30");
+    expect(formatUtcOffset(-210)).toBe("-03:30");
+  });
+});
+
+describe("resolveTimeZoneInfo", () => {
+  it("combines the zone with its current offset", () => {
+    expect(
+      resolveTimeZoneInfo({ timeZone: "Asia/Kolkata", at: new Date("2026-03-01T00:00:00Z") }),
+    ).toEqual({ timeZone: "Asia/Kolkata", utcOffsetMinutes: 330, utcOffset: "+05:30" });
+  });
+});
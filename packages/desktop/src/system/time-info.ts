@@ -0,0 +1,137 @@
+import { randomUUID } from "node:crypto";
+import { app, powerMonitor } from "electron";
+import log from "electron-log/main";
+import {
+  buildPingFrame,
+  computeClockSkew,
+  parsePongReply,
+  type DaemonClockSample,
+} from "../daemon/daemon-clock.js";
+import {
+  findOpenLocalTransportSession,
+  sendLocalDaemonRequest,
+} from "../daemon/local-transport.js";
+import { emitDesktopEvent } from "../window/desktop-events.js";
+
+const TIME_ZONE_CHANGED_EVENT = "time-zone-changed";
+const TIME_ZONE_POLL_INTERVAL_MS = 60_000;
+// Skew drifts slowly; re-measuring on every call would add a daemon round trip each time.
+const CLOCK_SAMPLE_MAX_AGE_MS = 5 * 60_000;
+const LONG_OFFSET_PATTERN = /^GMT(?:([+-])(\d{1,2})(?::(\d{2}))?)?$/;
+
+export interface TimeZoneInfo {
+  /** IANA zone, e.g. `Europe/Paris`. */
+  timeZone: string;
+  utcOffsetMinutes: number;
+  /** `+02:00` style, for display. */
+  utcOffset: string;
+}
+
+export interface TimeInfo extends TimeZoneInfo {
+  now: string;
+  daemonClock: {
+    /** Daemon clock minus local clock; positive when the daemon runs ahead. */
+    skewMs: number;
+    roundTripMs: number;
+    measuredAt: string;
+  } | null;
+}
+
+/**
+ * Offset of `timeZone` at `at`, from ICU rather than Date#getTimezoneOffset,
+ * which V8 caches for the zone the process started in.
+ */
+export function resolveUtcOffsetMinutes(timeZone: string, at: Date): number {
+  const part = new Intl.DateTimeFormat("en-US", { timeZone, timeZoneName: "longOffset" })
+    .formatToParts(at)
+    .find((entry) => entry.type === "timeZoneName")?.value;
+  const match = part ? LONG_OFFSET_PATTERN.exec(part) : null;
+  if (!match || !match[1]) {
+    return 0;
+  }
+  const minutes = Number(match[2]) * 60 + Number(match[3] ?? 0);
+  return match[1] === "-" ? -minutes : minutes;
+}
+
+export function formatUtcOffset(minutes: number): string {
+  const sign = minutes < 0 ? "-" : "+";
+  const absolute = Math.abs(minutes);
+  const hours = String(Math.floor(absolute / 60)).padStart(2, "0");
+  return `${sign}${hours}:${String(absolute % 60).padStart(2, "0")}`;
+}
+
+export function resolveTimeZoneInfo(input: { timeZone: string; at: Date }): TimeZoneInfo {
+  const utcOffsetMinutes = resolveUtcOffsetMinutes(input.timeZone, input.at);
+  return {
+    timeZone: input.timeZone,
+    utcOffsetMinutes,
+    utcOffset: formatUtcOffset(utcOffsetMinutes),
+  };
+}
+
+function getTimeZoneInfo(): TimeZoneInfo {
+  return resolveTimeZoneInfo({
+    timeZone: Intl.DateTimeFormat().resolvedOptions().timeZone,
+    at: new Date(),
+  });
+}
+
+let lastClockSample: TimeInfo["daemonClock"] = null;
+
+/** Clock of the daemon behind the first open session; `null` when none is connected. */
+async function measureLocalDaemonClock(): Promise<DaemonClockSample | null> {
+  const session = findOpenLocalTransportSession();
+  if (!session) {
+    return null;
+  }
+  const requestId = `desktop-ping-${randomUUID()}`;
+  const clientSentAt = Date.now();
+  const pong = await sendLocalDaemonRequest(session.sessionId, {
+    requestId,
+    frame: buildPingFrame({ requestId, clientSentAt }),
+    parseReply: parsePongReply,
+  });
+  return computeClockSkew({ clientSentAt, clientReceivedAt: Date.now(), ...pong });
+}
+
+async function sampleDaemonClock(): Promise<TimeInfo["daemonClock"]> {
+  const age = lastClockSample ? Date.now() - Date.parse(lastClockSample.measuredAt) : Infinity;
+  if (age < CLOCK_SAMPLE_MAX_AGE_MS) {
+    return lastClockSample;
+  }
+  try {
+    const sample = await measureLocalDaemonClock();
+    lastClockSample = sample ? { ...sample, measuredAt: new Date().toISOString() } : null;
+  } catch (error) {
+    log.warn("[time-info] failed to measure daemon clock", error);
+  }
+  return lastClockSample;
+}
+
+export async function getTimeInfo(): Promise<TimeInfo> {
+  const daemonClock = await sampleDaemonClock();
+  return { ...getTimeZoneInfo(), now: new Date().toISOString(), daemonClock };
+}
+
+/**
+ * Electron has no time zone event. Chromium moves ICU's default zone when the
+ * OS zone changes, so polling it catches travel and DST transitions alike.
+ */
+export function registerTimeZoneEvents(): void {
+  let lastSnapshot = JSON.stringify(getTimeZoneInfo());
+
+  const refresh = () => {
+    const info = getTimeZoneInfo();
+    const snapshot = JSON.stringify(info);
+    if (snapshot === lastSnapshot) {
+      return;
+    }
+    lastSnapshot = snapshot;
+    lastClockSample = null;
+    emitDesktopEvent(TIME_ZONE_CHANGED_EVENT, info);
+  };
+
+  app.on("browser-window-focus", refresh);
+  powerMonitor.on("resume", refresh);
+  setInterval(refresh, TIME_ZONE_POLL_INTERVAL_MS).unref();
+}
@@ -0,0 +1,51 @@
+import { describe, expect, it } from "vitest";
+import {
+  extractGlyphCoverage,
+  formatBadgeLabel,
+  renderBadgeBitmap,
+  renderTintedGlyph,
+  resolveTrayIconVariant,
+} from "./tray-icons";
+
+function pixelAt(bitmap: Buffer, size: number, x: number, y: number): number[] {
+  const offset = (y * size + x) * 4;
+  return [...bitmap.subarray(offset, offset + 4)];
+}
+
+describe("resolveTrayIconVariant", () => {
+  it("uses template images on macOS and contrasts with the taskbar elsewhere", () => {
+    expect(resolveTrayIconVariant({ platform: "darwin", darkTaskbar: false })).toBe("template");
+    expect(resolveTrayIconVariant({ platform: "win32", darkTaskbar: true })).toBe("light");
+    expect(resolveTrayIconVariant({ platform: "linux", darkTaskbar: false })).toBe("dark");
+  });
+});
+
+describe("glyph tinting", () => {
+  it("keeps the light glyph and drops the dark background", () => {
+    // BGRA: opaque white glyph pixel, opaque black background pixel, transparent pixel.
+    const source = Buffer.from([255, 255, 255, 255, 0, 0, 0, 255, 0, 0, 0, 0]);
+
+    const coverage = extractGlyphCoverage(source);
+    expect([...coverage]).toEqual([255, 0, 0]);
+
+    const tinted = renderTintedGlyph({ coverage, color: { r: 0x10, g: 0x20, b: 0x30 } });
+    expect([...tinted]).toEqual([0x30, 0x20, 0x10, 255, 0, 0, 0, 0, 0, 0, 0, 0]);
+  });
+});
+
+describe("badge rendering", () => {
+  it("caps the label at 9+", () => {
+    expect(formatBadgeLabel(0)).toBeNull();
+    expect(formatBadgeLabel(3)).toBe("3");
+    expect(formatBadgeLabel(42)).toBe("9+");
+  });
+
+  it("draws a red circle with a white count", () => {
+    const bitmap = renderBadgeBitmap({ label: "1", size: 16 });
+
+    expect(bitmap.length).toBe(16 * 16 * 4);
+    expect(pixelAt(bitmap, 16, 0, 0)[3]).toBe(0);
+    expect(pixelAt(bitmap, 16, 3, 8)).toEqual([0x4d, 0x48, 0xe5, 255]);
+    expect(pixelAt(bitmap, 16, 7, 3)).toEqual([255, 255, 255, 255]);
+  });
+});
@@ -0,0 +1,144 @@
+// Bitmaps here are Electron's native layout: BGRA, premultiplied alpha, row-major.
+
+export type TrayIconVariant = "template" | "light" | "dark";
+
+export interface RgbColor {
+  r: number;
+  g: number;
+  b: number;
+}
+
+const LIGHT_GLYPH_COLOR: RgbColor = { r: 0xf4, g: 0xf4, b: 0xf5 };
+const DARK_GLYPH_COLOR: RgbColor = { r: 0x18, g: 0x1b, b: 0x1a };
+const TEMPLATE_GLYPH_COLOR: RgbColor = { r: 0, g: 0, b: 0 };
+const BADGE_BACKGROUND_COLOR: RgbColor = { r: 0xe5, g: 0x48, b: 0x4d };
+const BADGE_TEXT_COLOR: RgbColor = { r: 0xff, g: 0xff, b: 0xff };
+
+// 3×5 pixel font; enough for badge counts.
+const BADGE_FONT: Record<string, string[]> = {
+  "0": ["111", "101", "101", "101", "111"],
+  "1": ["010", "110", "010", "010", "111"],
+  "2": ["111", "001", "111", "100", "111"],
+  "3": ["111", "001", "111", "001", "111"],
+  "4": ["101", "101", "111", "001", "001"],
+  "5": ["111", "100", "111", "001", "111"],
+  "6": ["111", "100", "111", "101", "111"],
+  "7": ["111", "001", "001", "001", "001"],
+  "8": ["111", "101", "111", "101", "111"],
+  "9": ["111", "101", "111", "001", "111"],
+  "+": ["000", "010", "111", "010", "000"],
+};
+
+/**
+ * macOS menu bar icons are template images the system tints itself. Elsewhere
+ * the glyph is drawn light on a dark taskbar/panel and dark on a light one.
+ */
+export function resolveTrayIconVariant(input: {
+  platform: NodeJS.Platform;
+  darkTaskbar: boolean;
+}): TrayIconVariant {
+  if (input.platform === "darwin") {
+    return "template";
+  }
+  return input.darkTaskbar ? "light" : "dark";
+}
+
+export function getTrayGlyphColor(variant: TrayIconVariant): RgbColor {
+  if (variant === "template") {
+    return TEMPLATE_GLYPH_COLOR;
+  }
+  return variant === "light" ? LIGHT_GLYPH_COLOR : DARK_GLYPH_COLOR;
+}
+
+/**
+ * Coverage of the app icon's light glyph, dropping its dark rounded-square
+ * background so the mark reads as a single-color menu bar icon.
+ */
+export function extractGlyphCoverage(bitmap: Buffer): Uint8Array {
+  const coverage = new Uint8Array(bitmap.length / 4);
+  for (let pixel = 0; pixel < coverage.length; pixel += 1) {
+    const offset = pixel * 4;
+    // Premultiplied channels already fold alpha into brightness.
+    const brightness = bitmap[offset]! + bitmap[offset + 1]! + bitmap[offset + 2]!;
+    coverage[pixel] = Math.round(brightness / 3);
+  }
+  return coverage;
+}
+
+function writePixel(bitmap: Buffer, pixel: number, color: RgbColor, alpha: number): void {
+  const offset = pixel * 4;
+  bitmap[offset] = Math.round((color.b * alpha) / 255);
+  bitmap[offset + 1] = Math.round((color.g * alpha) / 255);
+  bitmap[offset + 2] = Math.round((color.r * alpha) / 255);
+  bitmap[offset + 3] = alpha;
+}
+
+export function renderTintedGlyph(input: { coverage: Uint8Array; color: RgbColor }): Buffer {
+  const bitmap = Buffer.alloc(input.coverage.length * 4);
+  input.coverage.forEach((alpha, pixel) => writePixel(bitmap, pixel, input.color, alpha));
+  return bitmap;
+}
+
+export function formatBadgeLabel(count: number): string | null {
+  if (!Number.isSafeInteger(count) || count <= 0) {
+    return null;
+  }
+  return count > 9 ? "9+" : String(count);
+}
+
+/**
+ * Count badge for the Windows taskbar overlay, which has no native badge API.
+ * Red unless a `background` is given.
+ */
+export function renderBadgeBitmap(input: {
+  label: string;
+  size: number;
+  background?: RgbColor;
+}): Buffer {
+  const { size } = input;
+  const bitmap = Buffer.alloc(size * size * 4);
+  const radius = size / 2;
+
+  for (let y = 0; y < size; y += 1) {
+    for (let x = 0; x < size; x += 1) {
+      const distance = Math.hypot(x + 0.5 - radius, y + 0.5 - radius);
+      // One pixel of falloff keeps the circle edge from looking jagged.
+      const coverage = Math.max(0, Math.min(1, radius - distance + 0.5));
+      if (coverage > 0) {
+        const background = input.background ?? BADGE_BACKGROUND_COLOR;
+        writePixel(bitmap, y * size + x, background, Math.round(coverage * 255));
+      }
+    }
+  }
+
+  const glyphs = [...input.label].flatMap((char) => {
+    const glyph = BADGE_FONT[char];
+    return glyph ? [glyph] : [];
+  });
+  const scale = Math.max(1, Math.floor(size / (glyphs.length > 1 ? 16 : 8)));
+  const textWidth = (glyphs.length * 4 - 1) * scale;
+  const textHeight = 5 * scale;
+  const originX = Math.floor((size - textWidth) / 2);
+  const originY = Math.floor((size - textHeight) / 2);
+
+  glyphs.forEach((glyph, index) => {
+    glyph.forEach((row, rowIndex) => {
+      [...row].forEach((bit, columnIndex) => {
+        if (bit !== "1") {
+          return;
+        }
+        for (let dy = 0; dy < scale; dy += 1) {
+          for (let dx = 0; dx < scale; dx += 1) {
+            const x = originX + (index * 4 + columnIndex) * scale + dx;
+            const y = originY + rowIndex * scale + dy;
+            if (x >= 0 && x < size && y >= 0 && y < size) {
+              writePixel(bitmap, y * size + x, BADGE_TEXT_COLOR, 255);
+            }
+          }
+        }
+      });
+    });
+  });
+
+  return bitmap;
+}
@@ -0,0 +1,84 @@
+import { execFile } from "node:child_process";
+import { readFile } from "node:fs/promises";
+import { promisify } from "node:util";
+import { desktopCapturer, shell, systemPreferences } from "electron";
+import {
+  MAC_OPEN_WINDOWS_SCRIPT,
+  type OpenWindow,
+  parseOpenWindowsJson,
+  parseWmctrlWindows,
+  WINDOWS_OPEN_WINDOWS_SCRIPT,
+} from "./window-list.js";
+
+const execFileAsync = promisify(execFile);
+
+const LIST_TIMEOUT_MS = 5_000;
+const SCREEN_RECORDING_SETTINGS_URL =
+  "x-apple.systempreferences:com.apple.preference.security?Privacy_ScreenCapture";
+
+/**
+ * Window titles and pixels of other apps need Screen Recording access on
+ * macOS. Asking desktopCapturer for sources is what shows the system prompt;
+ * once denied, System Settings opens on the right pane instead.
+ */
+export async function ensureScreenRecordingAccess(): Promise<void> {
+  if (process.platform !== "darwin") {
+    return;
+  }
+  const status = systemPreferences.getMediaAccessStatus("screen");
+  if (status === "granted") {
+    return;
+  }
+  if (status === "not-determined") {
+    await desktopCapturer.getSources({ types: ["screen"], thumbnailSize: { width: 0, height: 0 } });
+    if (systemPreferences.getMediaAccessStatus("screen") === "granted") {
+      return;
+    }
+    throw new Error("Allow Screen Recording for Paseo in the system prompt, then try again.");
+  }
+  void shell.openExternal(SCREEN_RECORDING_SETTINGS_URL);
+  throw new Error("Screen recording permission is required to see other app windows.");
+}
+
+async function readProcessName(pid: number): Promise<string> {
+  try {
+    return (await readFile(`/proc/${pid}/comm`, "utf8")).trim();
+  } catch {
+    return "";
+  }
+}
+
+async function listLinuxWindows(): Promise<OpenWindow[]> {
+  let stdout: string;
+  try {
+    ({ stdout } = await execFileAsync("wmctrl", ["-lGp"], { timeout: LIST_TIMEOUT_MS }));
+  } catch {
+    throw new Error("Listing windows on Linux requires wmctrl on an X11 session.");
+  }
+  const windows = parseWmctrlWindows(stdout);
+  return await Promise.all(
+    windows.map(async (win) => ({ ...win, app: await readProcessName(win.pid) })),
+  );
+}
+
+/** On-screen top-level windows of every app, front to back where the platform reports it. */
+export async function listOpenWindows(): Promise<OpenWindow[]> {
+  await ensureScreenRecordingAccess();
+  if (process.platform === "darwin") {
+    const { stdout } = await execFileAsync(
+      "osascript",
+      ["-l", "JavaScript", "-e", MAC_OPEN_WINDOWS_SCRIPT],
+      { timeout: LIST_TIMEOUT_MS },
+    );
+    return parseOpenWindowsJson(stdout);
+  }
+  if (process.platform === "win32") {
+    const { stdout } = await execFileAsync(
+      "powershell.exe",
+      ["-NoProfile", "-NonInteractive", "-Command", WINDOWS_OPEN_WINDOWS_SCRIPT],
+      { timeout: LIST_TIMEOUT_MS, windowsHide: true },
+    );
+    return parseOpenWindowsJson(stdout);
+  }
+  return await listLinuxWindows();
+}
@@ -0,0 +1,71 @@
+import { describe, expect, it } from "vitest";
+import { parseOpenWindowsJson, parseWmctrlWindows } from "./window-list";
+
+describe("parseOpenWindowsJson", () => {
+  it("reads the script output and drops zero-sized or malformed windows", () => {
+    const stdout = JSON.stringify([
+      {
+        id: 81,
+        title: "README.md",
+        app: "Code",
+        bundleId: "com.microsoft.VSCode",
+        pid: 400,
+        x: 0,
+        y: 25,
+        width: 1440,
+        height: 875,
+      },
+      { id: 82, title: "", app: "Dock", bundleId: "", pid: 90, x: 0, y: 0, width: 0, height: 0 },
+      { id: "83", pid: 1 },
+    ]);
+
+    expect(parseOpenWindowsJson(stdout)).toEqual([
+      {
+        id: 81,
+        title: "README.md",
+        app: "Code",
+        bundleId: "com.microsoft.VSCode",
+        pid: 400,
+        bounds: { x: 0, y: 25, width: 1440, height: 875 },
+      },
+    ]);
+  });
+
+  it("accepts PowerShell's bare object for a single window and empty output", () => {
+    const single =
+      '{"id":132456,"title":"Inbox","app":"OUTLOOK","bundleId":"","pid":7,' +
+      '"x":-8,"y":-8,"width":1936,"height":1056}';
+    expect(parseOpenWindowsJson(single)).toHaveLength(1);
+    expect(parseOpenWindowsJson(single)[0]?.bundleId).toBeNull();
+    expect(parseOpenWindowsJson("")).toEqual([]);
+  });
+});
+
+describe("parseWmctrlWindows", () => {
+  it("parses geometry, pid and titles with spaces", () => {
+    const stdout = [
+      "0x03a00007  0 4242   10   20   800  600  devbox Terminal - ~/src",
+      "0x04200003 -1 1337   0    0    1920 32   devbox ",
+      "garbage",
+    ].join("\n");
+
+    expect(parseWmctrlWindows(stdout)).toEqual([
+      {
+        id: 0x03a00007,
+        title: "Terminal - ~/src",
+        app: "",
+        bundleId: null,
+        pid: 4242,
+        bounds: { x: 10, y: 20, width: 800, height: 600 },
+      },
+      {
+        id: 0x04200003,
+        title: "",
+        app: "",
+        bundleId: null,
+        pid: 1337,
+        bounds: { x: 0, y: 0, width: 1920, height: 32 },
+      },
+    ]);
+  });
+});
@@ -0,0 +1,131 @@
+import { isRecord } from "../utils/is-record.js";
+
+const WMCTRL_LINE_PATTERN =
+  /^(0x[0-9a-f]+)\s+-?\d+\s+(\d+)\s+(-?\d+)\s+(-?\d+)\s+(\d+)\s+(\d+)\s+\S+\s?(.*)$/i;
+
+export interface OpenWindow {
+  /** Native handle: CGWindowID on macOS, HWND on Windows, X11 window id on Linux. */
+  id: number;
+  title: string;
+  app: string;
+  bundleId: string | null;
+  pid: number;
+  bounds: { x: number; y: number; width: number; height: number };
+}
+
+function coerceNumber(value: unknown): number | null {
+  return typeof value === "number" && Number.isFinite(value) ? value : null;
+}
+
+function coerceOpenWindow(value: unknown): OpenWindow | null {
+  if (!isRecord(value)) {
+    return null;
+  }
+  const id = coerceNumber(value.id);
+  const pid = coerceNumber(value.pid);
+  const [x, y, width, height] = [value.x, value.y, value.width, value.height].map(coerceNumber);
+  if (id === null || pid === null || x == null || y == null || width == null || height == null) {
+    return null;
+  }
+  return {
+    id,
+    title: typeof value.title === "string" ? value.title : "",
+    app: typeof value.app === "string" ? value.app : "",
+    bundleId: typeof value.bundleId === "string" && value.bundleId ? value.bundleId : null,
+    pid,
+    bounds: { x, y, width, height },
+  };
+}
+
+/** Output of the macOS and Windows scripts below; PowerShell emits a bare object for one window. */
+export function parseOpenWindowsJson(stdout: string): OpenWindow[] {
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(stdout.trim() || "[]");
+  } catch {
+    return [];
+  }
+  const entries = Array.isArray(parsed) ? parsed : [parsed];
+  return entries
+    .map(coerceOpenWindow)
+    .filter((entry): entry is OpenWindow => entry !== null && entry.bounds.width > 0);
+}
+
+/**
+ * `wmctrl -lGp`: `<id> <desktop> <pid> <x> <y> <w> <h> <host> <title...>`.
+ * The owning app is filled in from the pid afterwards.
+ */
+export function parseWmctrlWindows(stdout: string): OpenWindow[] {
+  const windows: OpenWindow[] = [];
+  for (const line of stdout.split("\n")) {
+    const match = WMCTRL_LINE_PATTERN.exec(line.trim());
+    if (!match) {
+      continue;
+    }
+    windows.push({
+      id: Number.parseInt(match[1]!, 16),
+      title: match[7] ?? "",
+      app: "",
+      bundleId: null,
+      pid: Number(match[2]),
+      bounds: {
+        x: Number(match[3]),
+        y: Number(match[4]),
+        width: Number(match[5]),
+        height: Number(match[6]),
+      },
+    });
+  }
+  return windows;
+}
+
+/** JXA over CGWindowListCopyWindowInfo; titles are only present with Screen Recording access. */
+export const MAC_OPEN_WINDOWS_SCRIPT = [
+  'ObjC.import("AppKit");',
+  'ObjC.import("CoreGraphics");',
+  "const windows = ObjC.deepUnwrap(ObjC.castRefToObject($.CGWindowListCopyWindowInfo(",
+  "  $.kCGWindowListOptionOnScreenOnly | $.kCGWindowListExcludeDesktopElements,",
+  "  $.kCGNullWindowID",
+  "))) || [];",
+  "JSON.stringify(windows.filter((w) => w.kCGWindowLayer === 0).map((w) => {",
+  "  const owner = $.NSRunningApplication.runningApplicationWithProcessIdentifier(",
+  "    w.kCGWindowOwnerPID,",
+  "  );",
+  "  return {",
+  "    id: w.kCGWindowNumber,",
+  '    title: w.kCGWindowName || "",',
+  '    app: w.kCGWindowOwnerName || "",',
+  '    bundleId: owner.isNil() ? "" : ObjC.unwrap(owner.bundleIdentifier) || "",',
+  "    pid: w.kCGWindowOwnerPID,",
+  "    x: w.kCGWindowBounds.X,",
+  "    y: w.kCGWindowBounds.Y,",
+  "    width: w.kCGWindowBounds.Width,",
+  "    height: w.kCGWindowBounds.Height,",
+  "  };",
+  "}));",
+].join("\n");
+
+/** Top-level windows of processes that own one, with GetWindowRect geometry. */
+export const WINDOWS_OPEN_WINDOWS_SCRIPT = [
+  'Add-Type @"',
+  "using System;",
+  "using System.Runtime.InteropServices;",
+  "public struct PaseoRect {",
+  "  public int Left; public int Top; public int Right; public int Bottom;",
+  "}",
+  "public static class PaseoWindows {",
+  '  [DllImport("user32.dll")]',
+  "  public static extern bool GetWindowRect(IntPtr hWnd, out PaseoRect rect);",
+  "}",
+  '"@',
+  "Get-Process | Where-Object { $_.MainWindowHandle -ne 0 -and $_.MainWindowTitle } |",
+  "  ForEach-Object {",
+  "    $rect = New-Object PaseoRect",
+  "    [void][PaseoWindows]::GetWindowRect($_.MainWindowHandle, [ref]$rect)",
+  "    [pscustomobject]@{",
+  "      id = [int64]$_.MainWindowHandle; title = $_.MainWindowTitle; app = $_.ProcessName",
+  "      bundleId = ''; pid = $_.Id; x = $rect.Left; y = $rect.Top",
+  "      width = $rect.Right - $rect.Left; height = $rect.Bottom - $rect.Top",
+  "    }",
+  "  } | ConvertTo-Json -Compress",
+].join("\n");
@@ -0,0 +1,57 @@
+import { describe, expect, it } from "vitest";
+import {
+  fromFileSystemPath,
+  hasTrailingDotOrSpace,
+  isWindowsReservedName,
+  toFileSystemPath,
+} from "./windows-paths";
+
+describe("isWindowsReservedName", () => {
+  it("matches device names with or without an extension", () => {
+    expect(isWindowsReservedName("CON")).toBe(true);
+    expect(isWindowsReservedName("nul.txt")).toBe(true);
+    expect(isWindowsReservedName("com1.tar.gz")).toBe(true);
+    expect(isWindowsReservedName("aux .md")).toBe(true);
+    expect(isWindowsReservedName("console.log")).toBe(false);
+    expect(isWindowsReservedName("com10")).toBe(false);
+  });
+});
+
+describe("hasTrailingDotOrSpace", () => {
+  it("flags names Win32 would rewrite", () => {
+    expect(hasTrailingDotOrSpace("notes.")).toBe(true);
+    expect(hasTrailingDotOrSpace("draft ")).toBe(true);
+    expect(hasTrailingDotOrSpace("notes.md")).toBe(false);
+    expect(hasTrailingDotOrSpace("..")).toBe(false);
+  });
+});
+
+describe("toFileSystemPath", () => {
+  it("passes paths through on other platforms", () => {
+    expect(toFileSystemPath("/tmp/CON.txt", "darwin")).toBe("/tmp/CON.txt");
+  });
+
+  it("leaves ordinary Windows paths alone", () => {
+    expect(toFileSystemPath("C:\\Users\\me\\a.png", "win32")).toBe("C:\\Users\\me\\a.png");
+  });
+
+  it("prefixes reserved names, trailing dots and long paths", () => {
+    expect(toFileSystemPath("C:\\work\\CON.txt", "win32")).toBe("\\\\?\\C:\\work\\CON.txt");
+    expect(toFileSystemPath("C:\\work\\notes.", "win32")).toBe("\\\\?\\C:\\work\\notes.");
+    const long = `C:\\work\\${"a".repeat(300)}.png`;
+    expect(toFileSystemPath(long, "win32")).toBe(`\\\\?\\${long}`);
+    expect(toFileSystemPath("\\\\server\\share\\nul", "win32")).toBe(
+      "\\\\?\\UNC\\server\\share\\nul",
+    );
+  });
+
+  it("resolves before prefixing, since the prefix disables normalization", () => {
+    expect(toFileSystemPath("C:\\work\\sub\\..\\aux", "win32")).toBe("\\\\?\\C:\\work\\aux");
+  });
+
+  it("round-trips through fromFileSystemPath", () => {
+    for (const input of ["C:\\work\\CON.txt", "\\\\server\\share\\nul"]) {
+      expect(fromFileSystemPath(toFileSystemPath(input, "win32"), "win32")).toBe(input);
+    }
+  });
+});
@@ -0,0 +1,67 @@
+import path from "node:path";
+
+const EXTENDED_PREFIX = "\\\\?\\";
+const EXTENDED_UNC_PREFIX = "\\\\?\\UNC\\";
+// Win32 APIs reject longer paths unless they carry the extended-length prefix.
+const MAX_PATH = 260;
+const RESERVED_BASENAME = /^(con|prn|aux|nul|com[0-9¹²³]|lpt[0-9¹²³])$/i;
+
+/** `CON`, `nul.txt`, `com1.tar.gz`: names Win32 maps to devices, whatever the extension. */
+export function isWindowsReservedName(name: string): boolean {
+  const stem = name.split(".")[0]?.replace(/ +$/, "") ?? "";
+  return RESERVED_BASENAME.test(stem);
+}
+
+/** Names Win32 silently rewrites by dropping trailing dots and spaces. */
+export function hasTrailingDotOrSpace(name: string): boolean {
+  return /[. ]$/.test(name) && name !== "." && name !== "..";
+}
+
+function needsExtendedPath(resolved: string): boolean {
+  if (resolved.length >= MAX_PATH) {
+    return true;
+  }
+  const segments = resolved.split("\\").slice(1);
+  return segments.some(
+    (segment) => isWindowsReservedName(segment) || hasTrailingDotOrSpace(segment),
+  );
+}
+
+/**
+ * The path to hand to `fs` calls. On Windows, long paths and names such as
+ * `CON.txt` or `notes.` only work through the `\\?\` form, which skips Win32
+ * normalization, so the path is resolved first. Other platforms pass through.
+ */
+export function toFileSystemPath(
+  filePath: string,
+  platform: NodeJS.Platform = process.platform,
+): string {
+  if (platform !== "win32" || filePath.startsWith(EXTENDED_PREFIX)) {
+    return filePath;
+  }
+  const resolved = path.win32.resolve(filePath);
+  if (!needsExtendedPath(resolved)) {
+    return resolved;
+  }
+  if (resolved.startsWith("\\\\")) {
+    return EXTENDED_UNC_PREFIX + resolved.slice(2);
+  }
+  return EXTENDED_PREFIX + resolved;
+}
+
+/** Undoes toFileSystemPath, so prefixed input still passes path containment checks. */
+export function fromFileSystemPath(
+  filePath: string,
+  platform: NodeJS.Platform = process.platform,
+): string {
+  if (platform !== "win32") {
+    return filePath;
+  }
+  if (filePath.startsWith(EXTENDED_UNC_PREFIX)) {
+    return `\\\\${filePath.slice(EXTENDED_UNC_PREFIX.length)}`;
+  }
+  if (filePath.startsWith(EXTENDED_PREFIX)) {
+    return filePath.slice(EXTENDED_PREFIX.length);
+  }
+  return filePath;
+}
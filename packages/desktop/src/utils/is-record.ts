@@ -0,0 +1,4 @@
+/** Plain JSON object check for values parsed from disk, IPC or the daemon. */
+export function isRecord(value: unknown): value is Record<string, unknown> {
+  return typeof value === "object" && value !== null && !Array.isArray(value);
+}
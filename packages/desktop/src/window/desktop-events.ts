@@ -0,0 +1,10 @@
+import { BrowserWindow } from "electron";
+
+export function emitDesktopEvent(event: string, payload: unknown): void {
+  for (const win of BrowserWindow.getAllWindows()) {
+    if (win.isDestroyed()) {
+      continue;
+    }
+    win.webContents.send(`paseo:event:${event}`, payload);
+  }
+}
@@ -0,0 +1,23 @@
+import { describe, expect, it } from "vitest";
+import { createInitStateRegistry } from "./init-state";
+
+describe("createInitStateRegistry", () => {
+  it("builds per-window state and skips providers that fail or have nothing", () => {
+    const registry = createInitStateRegistry<{ id: number }>();
+    registry.register("zoom", (win) => ({ factor: win.id === 1 ? 1.25 : 1 }));
+    registry.register("theme", () => "dark");
+    registry.register("broken", () => {
+      throw new Error("boom");
+    });
+    registry.register("empty", () => undefined);
+
+    expect(registry.build({ id: 1 })).toEqual({ zoom: { factor: 1.25 }, theme: "dark" });
+    expect(registry.build({ id: 2 })).toEqual({ zoom: { factor: 1 }, theme: "dark" });
+  });
+
+  it("refuses a second provider for the same key", () => {
+    const registry = createInitStateRegistry<object>();
+    registry.register("theme", () => "dark");
+    expect(() => registry.register("theme", () => "light")).toThrow(/already provided/);
+  });
+});
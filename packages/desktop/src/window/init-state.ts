@@ -0,0 +1,58 @@
+import { BrowserWindow, ipcMain } from "electron";
+import log from "electron-log/main";
+
+export type InitStateProvider<TWindow = BrowserWindow> = (win: TWindow) => unknown;
+
+export interface InitStateRegistry<TWindow = BrowserWindow> {
+  register(key: string, provider: InitStateProvider<TWindow>): void;
+  /** One entry per provider; a provider that throws is left out rather than failing the load. */
+  build(win: TWindow): Record<string, unknown>;
+}
+
+export function createInitStateRegistry<TWindow>(): InitStateRegistry<TWindow> {
+  const providers = new Map<string, InitStateProvider<TWindow>>();
+  return {
+    register(key, provider) {
+      if (providers.has(key)) {
+        throw new Error(`Init state "${key}" is already provided.`);
+      }
+      providers.set(key, provider);
+    },
+
+    build(win) {
+      const state: Record<string, unknown> = {};
+      for (const [key, provider] of providers) {
+        try {
+          const value = provider(win);
+          if (value !== undefined) {
+            state[key] = value;
+          }
+        } catch (error) {
+          log.warn("[init-state] provider failed", key, error);
+        }
+      }
+      return state;
+    },
+  };
+}
+
+const registry = createInitStateRegistry<BrowserWindow>();
+
+/**
+ * Adds a value the renderer can read synchronously before its first paint,
+ * instead of asking for it over IPC after mounting.
+ */
+export function registerInitStateProvider(key: string, provider: InitStateProvider): void {
+  registry.register(key, provider);
+}
+
+/**
+ * The preload reads this once per page load with a synchronous IPC call, so
+ * reloads see current values rather than the ones from window creation.
+ */
+export function registerInitStateHandler(): void {
+  ipcMain.on("paseo:window:getInitState", (event) => {
+    const win = BrowserWindow.fromWebContents(event.sender);
+    event.returnValue = win ? registry.build(win) : {};
+  });
+}
@@ -0,0 +1,117 @@
+import { mkdtemp, rm } from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+import {
+  clampToWorkAreas,
+  createWindowLayoutStore,
+  pickEditorWindow,
+  resolveFollowEditorBounds,
+  resolvePresetBounds,
+} from "./layout-model";
+
+const workArea = { x: 0, y: 25, width: 1441, height: 875 };
+
+describe("resolvePresetBounds", () => {
+  it("splits the work area into halves and quadrants without gaps", () => {
+    expect(resolvePresetBounds({ preset: "left-half", workArea })).toEqual({
+      x: 0,
+      y: 25,
+      width: 720,
+      height: 875,
+    });
+    expect(resolvePresetBounds({ preset: "right-half", workArea })).toEqual({
+      x: 720,
+      y: 25,
+      width: 721,
+      height: 875,
+    });
+    expect(resolvePresetBounds({ preset: "quadrant", quadrant: "bottom-right", workArea })).toEqual(
+      { x: 720, y: 462, width: 721, height: 438 },
+    );
+  });
+});
+
+describe("resolveFollowEditorBounds", () => {
+  it("fills the larger gap beside the editor", () => {
+    const editor = { x: 0, y: 25, width: 900, height: 875 };
+    expect(resolveFollowEditorBounds({ editor, workArea })).toEqual({
+      x: 900,
+      y: 25,
+      width: 541,
+      height: 875,
+    });
+  });
+
+  it("takes the opposite half when the editor fills the display", () => {
+    const editor = { x: 100, y: 25, width: 1300, height: 875 };
+    expect(resolveFollowEditorBounds({ editor, workArea })).toEqual(
+      resolvePresetBounds({ preset: "left-half", workArea }),
+    );
+  });
+});
+
+describe("pickEditorWindow", () => {
+  const windows = [
+    { app: "Paseo", bundleId: "sh.paseo.desktop", pid: 1 },
+    { app: "Safari", bundleId: "com.apple.Safari", pid: 2 },
+    { app: "Code", bundleId: "com.microsoft.VSCode", pid: 3 },
+    { app: "Cursor", bundleId: "com.todesktop.230313mzl4w4u92", pid: 4 },
+  ];
+
+  it("skips our own windows and non-editors, or matches an explicit app", () => {
+    expect(pickEditorWindow(windows, { ownPid: 1 })?.pid).toBe(3);
+    expect(pickEditorWindow(windows, { ownPid: 1, appName: "cursor" })?.pid).toBe(4);
+    expect(pickEditorWindow(windows.slice(0, 2), { ownPid: 1 })).toBeNull();
+  });
+});
+
+describe("clampToWorkAreas", () => {
+  it("moves bounds from an unplugged monitor onto a remaining display", () => {
+    const external = { x: 2000, y: 100, width: 1200, height: 800 };
+    expect(clampToWorkAreas(external, [workArea])).toEqual({
+      x: 241,
+      y: 100,
+      width: 1200,
+      height: 800,
+    });
+  });
+});
+
+describe("createWindowLayoutStore", () => {
+  let userDataPath: string;
+
+  beforeEach(async () => {
+    userDataPath = await mkdtemp(path.join(os.tmpdir(), "paseo-window-layouts-"));
+  });
+
+  afterEach(async () => {
+    await rm(userDataPath, { recursive: true, force: true });
+  });
+
+  it("replaces layouts saved under the same name", async () => {
+    const store = createWindowLayoutStore({ userDataPath });
+    const placement = { label: "main", bounds: { x: 0, y: 0, width: 800, height: 600 } };
+    await store.save({
+      name: "Review",
+      savedAt: "2026-05-01T00:00:00Z",
+      windows: [{ ...placement, maximized: false }],
+    });
+    await store.save({
+      name: "Review",
+      savedAt: "2026-05-02T00:00:00Z",
+      windows: [{ ...placement, maximized: true }],
+    });
+
+    const reopened = createWindowLayoutStore({ userDataPath });
+    expect(await reopened.list()).toEqual([
+      {
+        name: "Review",
+        savedAt: "2026-05-02T00:00:00Z",
+        windows: [{ ...placement, maximized: true }],
+      },
+    ]);
+    expect(await reopened.remove("Review")).toBe(true);
+    expect(await reopened.get("Review")).toBeNull();
+  });
+});
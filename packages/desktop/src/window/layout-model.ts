@@ -0,0 +1,316 @@
+import { readFile } from "node:fs/promises";
+import path from "node:path";
+import { updateJsonConfigFile } from "../settings/config-file.js";
+import { isRecord } from "../utils/is-record.js";
+
+const WINDOW_LAYOUTS_FILENAME = "window-layouts.json";
+const LAYOUT_NAME_MAX_LENGTH = 64;
+// Narrower than this beside the editor and the window is not worth using; split the screen instead.
+const MIN_FOLLOW_WIDTH = 420;
+
+export const WINDOW_LAYOUT_PRESETS = [
+  "left-half",
+  "right-half",
+  "quadrant",
+  "follow-editor",
+] as const;
+export type WindowLayoutPreset = (typeof WINDOW_LAYOUT_PRESETS)[number];
+
+export const WINDOW_QUADRANTS = ["top-left", "top-right", "bottom-left", "bottom-right"] as const;
+export type WindowQuadrant = (typeof WINDOW_QUADRANTS)[number];
+
+export interface Rect {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
+export interface SavedWindowPlacement {
+  label: string;
+  bounds: Rect;
+  maximized: boolean;
+}
+
+export interface SavedWindowLayout {
+  name: string;
+  savedAt: string;
+  windows: SavedWindowPlacement[];
+}
+
+interface PersistedWindowLayoutsDocument {
+  version: 1;
+  layouts: SavedWindowLayout[];
+}
+
+export interface WindowLayoutStore {
+  list(): Promise<SavedWindowLayout[]>;
+  get(name: string): Promise<SavedWindowLayout | null>;
+  /** Saving under an existing name replaces it. */
+  save(layout: SavedWindowLayout): Promise<SavedWindowLayout>;
+  remove(name: string): Promise<boolean>;
+}
+
+/** Editors recognised by `follow-editor`, by bundle id prefix or process name. */
+const EDITOR_BUNDLE_ID_PREFIXES = [
+  "com.microsoft.vscode",
+  "com.todesktop.230313mzl4w4u92",
+  "com.exafunction.windsurf",
+  "dev.zed.zed",
+  "com.sublimetext.",
+  "com.jetbrains.",
+  "com.apple.dt.xcode",
+  "com.neovide.",
+];
+const EDITOR_APP_NAMES = new Set([
+  "code",
+  "code - insiders",
+  "code.exe",
+  "cursor",
+  "cursor.exe",
+  "windsurf",
+  "windsurf.exe",
+  "zed",
+  "zed-editor",
+  "sublime_text",
+  "sublime_text.exe",
+  "xcode",
+  "idea",
+  "idea64.exe",
+  "webstorm",
+  "webstorm64.exe",
+  "pycharm",
+  "pycharm64.exe",
+  "goland",
+  "goland64.exe",
+  "rider",
+  "rider64.exe",
+  "clion",
+  "clion64.exe",
+  "neovide",
+  "emacs",
+]);
+
+export function isWindowLayoutPreset(value: unknown): value is WindowLayoutPreset {
+  return (WINDOW_LAYOUT_PRESETS as readonly unknown[]).includes(value);
+}
+
+export function coerceWindowQuadrant(value: unknown): WindowQuadrant {
+  if (value === undefined || value === null) {
+    return "top-left";
+  }
+  if (!(WINDOW_QUADRANTS as readonly unknown[]).includes(value)) {
+    throw new Error(`Unknown quadrant: ${String(value)}`);
+  }
+  return value as WindowQuadrant;
+}
+
+export function coerceLayoutName(value: unknown): string {
+  const name = typeof value === "string" ? value.trim() : "";
+  if (name.length === 0 || name.length > LAYOUT_NAME_MAX_LENGTH) {
+    throw new Error(`Layout names are 1–${LAYOUT_NAME_MAX_LENGTH} characters.`);
+  }
+  return name;
+}
+
+export function resolvePresetBounds(input: {
+  preset: "left-half" | "right-half" | "quadrant";
+  quadrant?: WindowQuadrant;
+  workArea: Rect;
+}): Rect {
+  const { workArea } = input;
+  const leftWidth = Math.floor(workArea.width / 2);
+  const topHeight = Math.floor(workArea.height / 2);
+  if (input.preset === "left-half") {
+    return { x: workArea.x, y: workArea.y, width: leftWidth, height: workArea.height };
+  }
+  if (input.preset === "right-half") {
+    return {
+      x: workArea.x + leftWidth,
+      y: workArea.y,
+      width: workArea.width - leftWidth,
+      height: workArea.height,
+    };
+  }
+  const quadrant = input.quadrant ?? "top-left";
+  const right = quadrant.endsWith("right");
+  const bottom = quadrant.startsWith("bottom");
+  return {
+    x: right ? workArea.x + leftWidth : workArea.x,
+    y: bottom ? workArea.y + topHeight : workArea.y,
+    width: right ? workArea.width - leftWidth : leftWidth,
+    height: bottom ? workArea.height - topHeight : topHeight,
+  };
+}
+
+/**
+ * Fills the larger gap beside the editor on its display. When neither gap is
+ * usable the window takes the half of the display the editor is not centred on.
+ */
+export function resolveFollowEditorBounds(input: { editor: Rect; workArea: Rect }): Rect {
+  const { editor, workArea } = input;
+  const leftGap = Math.max(0, editor.x - workArea.x);
+  const rightGap = Math.max(0, workArea.x + workArea.width - (editor.x + editor.width));
+  if (Math.max(leftGap, rightGap) >= MIN_FOLLOW_WIDTH) {
+    return rightGap >= leftGap
+      ? { x: editor.x + editor.width, y: workArea.y, width: rightGap, height: workArea.height }
+      : { x: workArea.x, y: workArea.y, width: leftGap, height: workArea.height };
+  }
+  const editorCenter = editor.x + editor.width / 2;
+  const onLeft = editorCenter < workArea.x + workArea.width / 2;
+  return resolvePresetBounds({ preset: onLeft ? "right-half" : "left-half", workArea });
+}
+
+/** Front-most editor window that is not ours; lists arrive front to back. */
+export function pickEditorWindow<
+  T extends { app: string; bundleId: string | null; pid: number },
+>(windows: T[], input: { ownPid: number; appName?: string }): T | null {
+  const wanted = input.appName?.trim().toLowerCase();
+  return (
+    windows.find((win) => {
+      if (win.pid === input.ownPid) {
+        return false;
+      }
+      const appName = win.app.toLowerCase();
+      if (wanted) {
+        return appName.includes(wanted) || win.bundleId?.toLowerCase() === wanted;
+      }
+      const bundleId = win.bundleId?.toLowerCase() ?? "";
+      return (
+        EDITOR_APP_NAMES.has(appName) ||
+        (bundleId !== "" && EDITOR_BUNDLE_ID_PREFIXES.some((prefix) => bundleId.startsWith(prefix)))
+      );
+    }) ?? null
+  );
+}
+
+function intersectionArea(a: Rect, b: Rect): number {
+  const width = Math.min(a.x + a.width, b.x + b.width) - Math.max(a.x, b.x);
+  const height = Math.min(a.y + a.height, b.y + b.height) - Math.max(a.y, b.y);
+  return width > 0 && height > 0 ? width * height : 0;
+}
+
+/**
+ * Keeps restored bounds on a display that still exists: the work area that
+ * overlaps them most, or the first one when a monitor was unplugged.
+ */
+export function clampToWorkAreas(bounds: Rect, workAreas: Rect[]): Rect {
+  if (workAreas.length === 0) {
+    return bounds;
+  }
+  const target = workAreas.reduce((best, area) =>
+    intersectionArea(bounds, area) > intersectionArea(bounds, best) ? area : best,
+  );
+  const width = Math.min(bounds.width, target.width);
+  const height = Math.min(bounds.height, target.height);
+  return {
+    x: Math.min(Math.max(bounds.x, target.x), target.x + target.width - width),
+    y: Math.min(Math.max(bounds.y, target.y), target.y + target.height - height),
+    width,
+    height,
+  };
+}
+
+function coerceRect(value: unknown): Rect | null {
+  if (!isRecord(value)) {
+    return null;
+  }
+  const [x, y, width, height] = [value.x, value.y, value.width, value.height];
+  if (
+    typeof x !== "number" ||
+    typeof y !== "number" ||
+    typeof width !== "number" ||
+    typeof height !== "number" ||
+    ![x, y, width, height].every(Number.isFinite) ||
+    width <= 0 ||
+    height <= 0
+  ) {
+    return null;
+  }
+  return { x, y, width, height };
+}
+
+function coerceLayouts(value: unknown): SavedWindowLayout[] {
+  if (!isRecord(value) || !Array.isArray(value.layouts)) {
+    return [];
+  }
+  return value.layouts.flatMap((entry: unknown): SavedWindowLayout[] => {
+    if (!isRecord(entry) || typeof entry.name !== "string" || !Array.isArray(entry.windows)) {
+      return [];
+    }
+    const windows = entry.windows.flatMap((placement: unknown): SavedWindowPlacement[] => {
+      const bounds = isRecord(placement) ? coerceRect(placement.bounds) : null;
+      if (!isRecord(placement) || typeof placement.label !== "string" || !bounds) {
+        return [];
+      }
+      return [{ label: placement.label, bounds, maximized: placement.maximized === true }];
+    });
+    return [
+      {
+        name: entry.name,
+        savedAt: typeof entry.savedAt === "string" ? entry.savedAt : "",
+        windows,
+      },
+    ];
+  });
+}
+
+export function createWindowLayoutStore(input: { userDataPath: string }): WindowLayoutStore {
+  const filePath = path.join(input.userDataPath, WINDOW_LAYOUTS_FILENAME);
+  let layouts: SavedWindowLayout[] = [];
+  let loaded: Promise<void> | null = null;
+
+  function ensureLoaded(): Promise<void> {
+    loaded ??= readFile(filePath, "utf8")
+      .then((raw) => {
+        layouts = coerceLayouts(JSON.parse(raw));
+      })
+      .catch(() => {
+        layouts = [];
+      });
+    return loaded;
+  }
+
+  /** Saves against the layouts on disk, which another instance may have changed. */
+  async function update(
+    mutate: (current: SavedWindowLayout[]) => SavedWindowLayout[],
+  ): Promise<void> {
+    const document = await updateJsonConfigFile(
+      filePath,
+      (raw): PersistedWindowLayoutsDocument => ({
+        version: 1,
+        layouts: mutate(coerceLayouts(raw)),
+      }),
+    );
+    layouts = document.layouts;
+    loaded = Promise.resolve();
+  }
+
+  return {
+    async list() {
+      await ensureLoaded();
+      return structuredClone(layouts);
+    },
+
+    async get(name) {
+      await ensureLoaded();
+      const layout = layouts.find((entry) => entry.name === name);
+      return layout ? structuredClone(layout) : null;
+    },
+
+    async save(layout) {
+      await update((current) => [...current.filter((entry) => entry.name !== layout.name), layout]);
+      return structuredClone(layout);
+    },
+
+    async remove(name) {
+      let removed = false;
+      await update((current) => {
+        const remaining = current.filter((entry) => entry.name !== name);
+        removed = remaining.length < current.length;
+        return remaining;
+      });
+      return removed;
+    },
+  };
+}
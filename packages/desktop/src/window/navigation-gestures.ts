@@ -0,0 +1,32 @@
+import type { BrowserWindow } from "electron";
+import { emitDesktopEvent } from "./desktop-events.js";
+import { getWindowLabel } from "./window-labels.js";
+
+type NavigationDirection = "back" | "forward";
+
+function emitNavigation(win: BrowserWindow, direction: NavigationDirection): void {
+  emitDesktopEvent(`navigate-${direction}`, { windowLabel: getWindowLabel(win) });
+}
+
+/**
+ * Mouse buttons 4/5 arrive as app commands on Windows and Linux. macOS has no
+ * app command for them, but trackpad swipes (and Magic Mouse swipes, when
+ * enabled in System Settings) arrive as `swipe`.
+ */
+export function setupNavigationGestures(win: BrowserWindow): void {
+  win.on("app-command", (_event, command) => {
+    if (command === "browser-backward") {
+      emitNavigation(win, "back");
+    } else if (command === "browser-forward") {
+      emitNavigation(win, "forward");
+    }
+  });
+  win.on("swipe", (_event, direction) => {
+    // Swiping right reveals what came before, as in Safari.
+    if (direction === "right") {
+      emitNavigation(win, "back");
+    } else if (direction === "left") {
+      emitNavigation(win, "forward");
+    }
+  });
+}
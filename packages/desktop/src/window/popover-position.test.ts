@@ -0,0 +1,36 @@
+import { describe, expect, it } from "vitest";
+import { computePopoverPosition } from "./popover-position";
+
+const size = { width: 360, height: 420 };
+
+describe("computePopoverPosition", () => {
+  it("opens below a menu bar icon, centered on it", () => {
+    expect(
+      computePopoverPosition({
+        anchor: { x: 1000, y: 0, width: 24, height: 24 },
+        size,
+        workArea: { x: 0, y: 25, width: 1440, height: 875 },
+      }),
+    ).toEqual({ x: 832, y: 29 });
+  });
+
+  it("opens above a bottom taskbar icon", () => {
+    expect(
+      computePopoverPosition({
+        anchor: { x: 1700, y: 1040, width: 24, height: 40 },
+        size,
+        workArea: { x: 0, y: 0, width: 1920, height: 1040 },
+      }),
+    ).toEqual({ x: 1532, y: 616 });
+  });
+
+  it("stays inside the work area near the screen edge", () => {
+    expect(
+      computePopoverPosition({
+        anchor: { x: 1420, y: 0, width: 20, height: 24 },
+        size,
+        workArea: { x: 0, y: 25, width: 1440, height: 875 },
+      }),
+    ).toEqual({ x: 1072, y: 29 });
+  });
+});
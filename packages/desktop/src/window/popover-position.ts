@@ -0,0 +1,37 @@
+export interface ScreenRect {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
+const POPOVER_SCREEN_MARGIN = 8;
+
+/**
+ * Anchors a popover to a tray icon: below it for top menu bars (macOS, most
+ * Linux panels), above it for bottom taskbars (Windows), clamped to the work
+ * area of the display the icon is on.
+ */
+export function computePopoverPosition(input: {
+  anchor: ScreenRect;
+  size: { width: number; height: number };
+  workArea: ScreenRect;
+}): { x: number; y: number } {
+  const { anchor, size, workArea } = input;
+  const anchorCenterY = anchor.y + anchor.height / 2;
+  const opensDownward = anchorCenterY < workArea.y + workArea.height / 2;
+
+  const preferredX = Math.round(anchor.x + anchor.width / 2 - size.width / 2);
+  const preferredY = opensDownward
+    ? Math.max(anchor.y + anchor.height, workArea.y) + POPOVER_SCREEN_MARGIN / 2
+    : Math.min(anchor.y, workArea.y + workArea.height) - size.height - POPOVER_SCREEN_MARGIN / 2;
+
+  const minX = workArea.x + POPOVER_SCREEN_MARGIN;
+  const maxX = workArea.x + workArea.width - size.width - POPOVER_SCREEN_MARGIN;
+  const minY = workArea.y;
+  const maxY = workArea.y + workArea.height - size.height;
+  return {
+    x: Math.max(minX, Math.min(preferredX, maxX)),
+    y: Math.round(Math.max(minY, Math.min(preferredY, maxY))),
+  };
+}
@@ -0,0 +1,53 @@
+import { describe, expect, it } from "vitest";
+import { buildRestoreScrollScript, parseReloadSnapshot, routeFromUrl } from "./reload-snapshot";
+
+describe("routeFromUrl", () => {
+  it("keeps path, query and hash of app urls", () => {
+    expect(routeFromUrl("paseo://app/agents/a1?tab=diff#end", "paseo://app/")).toBe(
+      "/agents/a1?tab=diff#end",
+    );
+    expect(routeFromUrl("http://localhost:8081/settings", "http://localhost:8081")).toBe(
+      "/settings",
+    );
+  });
+
+  it("ignores other schemes and hosts", () => {
+    expect(routeFromUrl("paseo://other/agents", "paseo://app/")).toBeNull();
+    expect(routeFromUrl("https://example.com/agents", "paseo://app/")).toBeNull();
+    expect(routeFromUrl("not a url", "paseo://app/")).toBeNull();
+  });
+});
+
+describe("parseReloadSnapshot", () => {
+  it("drops malformed scroll entries", () => {
+    expect(
+      parseReloadSnapshot({
+        route: "/agents/a1",
+        scroll: [
+          { selector: null, top: 120, left: 0 },
+          { selector: "#timeline", top: 900, left: 0 },
+          { selector: 3, top: 1, left: 1 },
+          { selector: "#x", top: Number.NaN, left: 0 },
+        ],
+      }),
+    ).toEqual({
+      route: "/agents/a1",
+      scroll: [
+        { selector: null, top: 120, left: 0 },
+        { selector: "#timeline", top: 900, left: 0 },
+      ],
+    });
+  });
+
+  it("rejects snapshots without an absolute route", () => {
+    expect(parseReloadSnapshot({ route: "agents", scroll: [] })).toBeNull();
+    expect(parseReloadSnapshot(null)).toBeNull();
+  });
+});
+
+describe("buildRestoreScrollScript", () => {
+  it("embeds positions as JSON", () => {
+    const scroll = [{ selector: '[data-testid="a\\"b"]', top: 5, left: 0 }];
+    expect(buildRestoreScrollScript(scroll)).toContain(JSON.stringify(scroll));
+  });
+});
@@ -0,0 +1,108 @@
+import { isRecord } from "../utils/is-record.js";
+
+// Scroll containers only remount once their data arrives, so restoring keeps trying this long.
+const SCROLL_RESTORE_TIMEOUT_MS = 5_000;
+const MAX_SCROLL_ENTRIES = 32;
+
+export interface ScrollPosition {
+  /** `#id` or `[data-testid="…"]`; `null` for the document itself. */
+  selector: string | null;
+  top: number;
+  left: number;
+}
+
+export interface ReloadSnapshot {
+  /** Path, query and hash within the app, e.g. `/agents/abc?tab=diff`. */
+  route: string;
+  scroll: ScrollPosition[];
+}
+
+/** Route of `url` when it belongs to the app at `appOrigin`; other origins are never restored. */
+export function routeFromUrl(url: string, appOrigin: string): string | null {
+  let parsed: URL;
+  let base: URL;
+  try {
+    parsed = new URL(url);
+    base = new URL(appOrigin);
+  } catch {
+    return null;
+  }
+  // Custom schemes have an opaque "null" origin, so compare scheme and host instead.
+  if (parsed.protocol !== base.protocol || parsed.host !== base.host) {
+    return null;
+  }
+  return `${parsed.pathname}${parsed.search}${parsed.hash}`;
+}
+
+/** Runs in the renderer; scrolled elements are only recorded when they can be found again. */
+export const CAPTURE_RELOAD_SNAPSHOT_SCRIPT = `(() => {
+  const scroll = [];
+  const root = document.scrollingElement;
+  if (root && (root.scrollTop > 0 || root.scrollLeft > 0)) {
+    scroll.push({ selector: null, top: root.scrollTop, left: root.scrollLeft });
+  }
+  for (const el of document.querySelectorAll("[id], [data-testid]")) {
+    if (scroll.length >= ${MAX_SCROLL_ENTRIES}) break;
+    if (el.scrollTop <= 0 && el.scrollLeft <= 0) continue;
+    const selector = el.id
+      ? "#" + CSS.escape(el.id)
+      : '[data-testid="' + CSS.escape(el.getAttribute("data-testid")) + '"]';
+    scroll.push({ selector, top: el.scrollTop, left: el.scrollLeft });
+  }
+  return { route: location.pathname + location.search + location.hash, scroll };
+})()`;
+
+function coerceScrollPosition(value: unknown): ScrollPosition | null {
+  if (!isRecord(value)) {
+    return null;
+  }
+  const { selector, top, left } = value;
+  if (selector !== null && typeof selector !== "string") {
+    return null;
+  }
+  if (typeof top !== "number" || typeof left !== "number") {
+    return null;
+  }
+  if (!Number.isFinite(top) || !Number.isFinite(left)) {
+    return null;
+  }
+  return { selector, top, left };
+}
+
+export function parseReloadSnapshot(value: unknown): ReloadSnapshot | null {
+  if (!isRecord(value) || typeof value.route !== "string" || !value.route.startsWith("/")) {
+    return null;
+  }
+  const scroll = Array.isArray(value.scroll)
+    ? value.scroll
+        .map(coerceScrollPosition)
+        .filter((entry): entry is ScrollPosition => entry !== null)
+        .slice(0, MAX_SCROLL_ENTRIES)
+    : [];
+  return { route: value.route, scroll };
+}
+
+/**
+ * Runs in the reloaded renderer. Each position is applied as soon as its
+ * element exists and stops being retried once it sticks.
+ */
+export function buildRestoreScrollScript(scroll: readonly ScrollPosition[]): string {
+  return `(() => {
+  let pending = ${JSON.stringify(scroll)};
+  const deadline = Date.now() + ${SCROLL_RESTORE_TIMEOUT_MS};
+  const step = () => {
+    pending = pending.filter((entry) => {
+      const el = entry.selector
+        ? document.querySelector(entry.selector)
+        : document.scrollingElement;
+      if (!el) return true;
+      el.scrollTo({ top: entry.top, left: entry.left });
+      return Math.abs(el.scrollTop - entry.top) > 1;
+    });
+    if (pending.length > 0 && Date.now() < deadline) {
+      setTimeout(step, 100);
+    }
+  };
+  step();
+})()`;
+}
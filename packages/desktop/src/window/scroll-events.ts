@@ -0,0 +1,43 @@
+import type { BrowserWindow } from "electron";
+import { emitDesktopEvent } from "./desktop-events.js";
+import { getWindowLabel } from "./window-labels.js";
+import {
+  createScrollDeviceClassifier,
+  type ScrollDeviceClassifier,
+  type ScrollProfile,
+} from "./scroll-normalization.js";
+
+const classifiers = new WeakMap<BrowserWindow, ScrollDeviceClassifier>();
+
+export function getScrollProfile(win: BrowserWindow): ScrollProfile {
+  return classifiers.get(win)?.current() ?? { device: "wheel", pixelsPerNotch: null };
+}
+
+/**
+ * Linux only: macOS and Windows report wheel and touchpad deltas consistently.
+ * The renderer's terminal converts wheel deltas with the emitted profile.
+ */
+export function setupScrollNormalization(win: BrowserWindow): void {
+  if (process.platform !== "linux") {
+    return;
+  }
+  const classifier = createScrollDeviceClassifier();
+  classifiers.set(win, classifier);
+  let lastProfile = JSON.stringify(classifier.current());
+
+  win.webContents.on("input-event", (_event, input) => {
+    if (input.type !== "mouseWheel") {
+      return;
+    }
+    const wheel = input as Electron.MouseWheelInputEvent;
+    const profile = classifier.record({
+      deltaY: wheel.deltaY ?? 0,
+      wheelTicksY: wheel.wheelTicksY ?? 0,
+    });
+    const snapshot = JSON.stringify(profile);
+    if (snapshot !== lastProfile) {
+      lastProfile = snapshot;
+      emitDesktopEvent("scroll-profile-changed", { windowLabel: getWindowLabel(win), ...profile });
+    }
+  });
+}
@@ -0,0 +1,43 @@
+import { describe, expect, it } from "vitest";
+import { createScrollDeviceClassifier, normalizeWheelDeltaToLines } from "./scroll-normalization";
+
+describe("scroll device classification", () => {
+  it("recognizes a wheel from whole ticks of a constant size", () => {
+    const classifier = createScrollDeviceClassifier();
+    classifier.record({ deltaY: 53, wheelTicksY: -1 });
+    const profile = classifier.record({ deltaY: 106, wheelTicksY: -2 });
+
+    expect(profile).toEqual({ device: "wheel", pixelsPerNotch: 53 });
+    expect(
+      normalizeWheelDeltaToLines({
+        sample: { deltaY: 106, wheelTicksY: -2 },
+        profile,
+        lineHeightPx: 17,
+      }),
+    ).toBe(6);
+  });
+
+  it("recognizes a touchpad from fractional, uneven deltas", () => {
+    const classifier = createScrollDeviceClassifier();
+    classifier.record({ deltaY: 4.5, wheelTicksY: 0.04 });
+    const profile = classifier.record({ deltaY: 12, wheelTicksY: 0.1 });
+
+    expect(profile).toEqual({ device: "touchpad", pixelsPerNotch: null });
+    expect(
+      normalizeWheelDeltaToLines({
+        sample: { deltaY: 34, wheelTicksY: 0.3 },
+        profile,
+        lineHeightPx: 17,
+      }),
+    ).toBe(2);
+  });
+
+  it("switches back once touchpad samples age out", () => {
+    const classifier = createScrollDeviceClassifier();
+    classifier.record({ deltaY: 3, wheelTicksY: 0.03 });
+    for (let index = 0; index < 6; index += 1) {
+      classifier.record({ deltaY: 120, wheelTicksY: 1 });
+    }
+    expect(classifier.current()).toEqual({ device: "wheel", pixelsPerNotch: 120 });
+  });
+});
@@ -0,0 +1,82 @@
+export type ScrollDeviceKind = "wheel" | "touchpad";
+
+export interface WheelSample {
+  deltaY: number;
+  wheelTicksY: number;
+}
+
+export interface ScrollProfile {
+  device: ScrollDeviceKind;
+  /** Pixels Chromium reports per wheel notch; `null` for touchpads. */
+  pixelsPerNotch: number | null;
+}
+
+const LINES_PER_NOTCH = 3;
+const SAMPLE_WINDOW = 6;
+// Wheel notches are whole ticks; allow for Chromium's float rounding.
+const WHOLE_TICK_TOLERANCE = 0.05;
+
+export interface ScrollDeviceClassifier {
+  record(sample: WheelSample): ScrollProfile;
+  current(): ScrollProfile;
+}
+
+function isWholeTick(ticks: number): boolean {
+  const magnitude = Math.abs(ticks);
+  const offset = Math.abs(magnitude - Math.round(magnitude));
+  return magnitude >= 1 - WHOLE_TICK_TOLERANCE && offset <= WHOLE_TICK_TOLERANCE;
+}
+
+/**
+ * Compositors disagree on `hasPreciseScrollingDeltas` (some report it for
+ * every libinput device), so the device is inferred from the deltas instead:
+ * a wheel sends whole ticks of a constant pixel size, a touchpad does not.
+ */
+export function createScrollDeviceClassifier(): ScrollDeviceClassifier {
+  const samples: WheelSample[] = [];
+
+  function current(): ScrollProfile {
+    if (samples.length === 0) {
+      return { device: "wheel", pixelsPerNotch: null };
+    }
+    const notchSizes = samples.map((sample) =>
+      isWholeTick(sample.wheelTicksY)
+        ? Math.abs(sample.deltaY / Math.round(sample.wheelTicksY))
+        : Number.NaN,
+    );
+    const first = notchSizes[0]!;
+    const consistent =
+      Number.isFinite(first) &&
+      notchSizes.every((size) => Number.isFinite(size) && Math.abs(size - first) < 1);
+    if (consistent) {
+      return { device: "wheel", pixelsPerNotch: Math.round(first) };
+    }
+    return { device: "touchpad", pixelsPerNotch: null };
+  }
+
+  return {
+    record(sample) {
+      if (sample.deltaY !== 0) {
+        samples.push(sample);
+        if (samples.length > SAMPLE_WINDOW) {
+          samples.shift();
+        }
+      }
+      return current();
+    },
+    current,
+  };
+}
+
+/** Terminal lines to scroll for one wheel event; fractional for touchpads. */
+export function normalizeWheelDeltaToLines(input: {
+  sample: WheelSample;
+  profile: ScrollProfile;
+  lineHeightPx: number;
+}): number {
+  const { sample, profile } = input;
+  if (profile.device === "wheel" && profile.pixelsPerNotch) {
+    return (sample.deltaY / profile.pixelsPerNotch) * LINES_PER_NOTCH;
+  }
+  return sample.deltaY / Math.max(1, input.lineHeightPx);
+}
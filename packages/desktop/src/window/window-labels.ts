@@ -0,0 +1,48 @@
+import { BrowserWindow } from "electron";
+
+export const MAIN_WINDOW_LABEL = "main";
+
+const windowsByLabel = new Map<string, BrowserWindow>();
+
+export function registerWindowLabel(input: { win: BrowserWindow; label: string }): void {
+  windowsByLabel.set(input.label, input.win);
+  input.win.once("closed", () => {
+    if (windowsByLabel.get(input.label) === input.win) {
+      windowsByLabel.delete(input.label);
+    }
+  });
+}
+
+export function getWindowLabel(win: BrowserWindow): string | null {
+  for (const [label, candidate] of windowsByLabel) {
+    if (candidate === win) {
+      return label;
+    }
+  }
+  return null;
+}
+
+/**
+ * Window addressed by a command's `windowLabel`. Without a label, commands act
+ * on the focused window, falling back to the main window.
+ */
+export function resolveWindowByLabel(label: unknown): BrowserWindow {
+  if (typeof label === "string" && label.trim().length > 0) {
+    const win = windowsByLabel.get(label.trim());
+    if (!win || win.isDestroyed()) {
+      throw new Error(`Unknown window: ${label}`);
+    }
+    return win;
+  }
+  const win = BrowserWindow.getFocusedWindow() ?? windowsByLabel.get(MAIN_WINDOW_LABEL);
+  if (!win || win.isDestroyed()) {
+    throw new Error("No window is open.");
+  }
+  return win;
+}
+
+export function listLabeledWindows(): Array<{ label: string; win: BrowserWindow }> {
+  return [...windowsByLabel]
+    .filter(([, win]) => !win.isDestroyed())
+    .map(([label, win]) => ({ label, win }));
+}
@@ -0,0 +1,139 @@
+import { app, BrowserWindow, screen } from "electron";
+import { STATUS_POPOVER_WINDOW_LABEL } from "../features/status-popover.js";
+import { listOpenWindows } from "../system/window-list-electron.js";
+import {
+  clampToWorkAreas,
+  coerceLayoutName,
+  coerceWindowQuadrant,
+  createWindowLayoutStore,
+  isWindowLayoutPreset,
+  pickEditorWindow,
+  resolveFollowEditorBounds,
+  resolvePresetBounds,
+  type Rect,
+  type SavedWindowLayout,
+  type WindowLayoutStore,
+} from "./layout-model.js";
+import { getWindowLabel, listLabeledWindows } from "./window-labels.js";
+
+export interface PlacedWindow {
+  windowLabel: string | null;
+  bounds: Rect;
+}
+
+export interface RestoredWindowLayout {
+  restored: string[];
+  /** Labels saved in the layout whose windows are not open right now. */
+  missing: string[];
+}
+
+let windowLayoutStore: WindowLayoutStore | null = null;
+
+function getWindowLayoutStore(): WindowLayoutStore {
+  windowLayoutStore ??= createWindowLayoutStore({ userDataPath: app.getPath("userData") });
+  return windowLayoutStore;
+}
+
+function placeWindow(win: BrowserWindow, bounds: Rect): void {
+  if (win.isFullScreen()) {
+    win.setFullScreen(false);
+  }
+  if (win.isMaximized()) {
+    win.unmaximize();
+  }
+  win.setBounds(bounds);
+}
+
+/** Other apps' frames come from the window list in screen pixels; Electron wants DIPs. */
+async function findEditorBounds(appName: string | undefined): Promise<Rect> {
+  const editor = pickEditorWindow(await listOpenWindows(), { ownPid: process.pid, appName });
+  if (!editor) {
+    throw new Error(
+      appName ? `No open window found for ${appName}.` : "No open editor window was found.",
+    );
+  }
+  return process.platform === "win32"
+    ? screen.screenToDipRect(null, editor.bounds)
+    : editor.bounds;
+}
+
+async function restoreWindowLayout(layout: SavedWindowLayout): Promise<RestoredWindowLayout> {
+  const windows = new Map(listLabeledWindows().map(({ label, win }) => [label, win]));
+  const workAreas = screen.getAllDisplays().map((display) => display.workArea);
+  const restored: string[] = [];
+  const missing: string[] = [];
+  for (const placement of layout.windows) {
+    const win = windows.get(placement.label);
+    if (!win) {
+      missing.push(placement.label);
+      continue;
+    }
+    placeWindow(win, clampToWorkAreas(placement.bounds, workAreas));
+    if (placement.maximized) {
+      win.maximize();
+    }
+    restored.push(placement.label);
+  }
+  return { restored, missing };
+}
+
+/**
+ * `layout` is a preset (`left-half`, `right-half`, `quadrant` with a
+ * `quadrant`, `follow-editor` with an optional `editor` app name) applied to
+ * one window, or the name of a saved layout applied to every window in it.
+ */
+export async function applyWindowLayout(input: {
+  win: BrowserWindow;
+  args: Record<string, unknown> | undefined;
+}): Promise<PlacedWindow | RestoredWindowLayout> {
+  const layout = input.args?.layout;
+  if (!isWindowLayoutPreset(layout)) {
+    const saved = await getWindowLayoutStore().get(coerceLayoutName(layout));
+    if (!saved) {
+      throw new Error(`Unknown window layout: ${String(layout)}`);
+    }
+    return await restoreWindowLayout(saved);
+  }
+
+  let bounds: Rect;
+  if (layout === "follow-editor") {
+    const appName = typeof input.args?.editor === "string" ? input.args.editor : undefined;
+    const editor = await findEditorBounds(appName);
+    bounds = resolveFollowEditorBounds({
+      editor,
+      workArea: screen.getDisplayMatching(editor).workArea,
+    });
+  } else {
+    bounds = resolvePresetBounds({
+      preset: layout,
+      quadrant: coerceWindowQuadrant(input.args?.quadrant),
+      workArea: screen.getDisplayMatching(input.win.getBounds()).workArea,
+    });
+  }
+  placeWindow(input.win, bounds);
+  return { windowLabel: getWindowLabel(input.win), bounds: input.win.getBounds() };
+}
+
+export async function saveWindowLayout(
+  args: Record<string, unknown> | undefined,
+): Promise<SavedWindowLayout> {
+  const name = coerceLayoutName(args?.name);
+  const windows = listLabeledWindows()
+    .filter(({ label }) => label !== STATUS_POPOVER_WINDOW_LABEL)
+    .map(({ label, win }) => ({
+      label,
+      bounds: win.getNormalBounds(),
+      maximized: win.isMaximized(),
+    }));
+  return await getWindowLayoutStore().save({ name, savedAt: new Date().toISOString(), windows });
+}
+
+export async function listWindowLayouts(): Promise<SavedWindowLayout[]> {
+  return await getWindowLayoutStore().list();
+}
+
+export async function deleteWindowLayout(
+  args: Record<string, unknown> | undefined,
+): Promise<boolean> {
+  return await getWindowLayoutStore().remove(coerceLayoutName(args?.name));
+}
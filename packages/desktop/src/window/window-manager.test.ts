@@ -2,6 +2,7 @@ import { describe, expect, it, vi } from "vitest";
 
 import {
   applyWindowControlsOverlayUpdate,
+  coerceWindowBackgroundColor,
   createWindowControlsOverlayState,
   getMainWindowChromeOptions,
   getTitleBarOverlayOptions,
@@ -43,6 +44,20 @@ describe("window-manager", () => {
     });
   });
 
+  describe("coerceWindowBackgroundColor", () => {
+    it("accepts hex colors and null for the system theme", () => {
+      expect(coerceWindowBackgroundColor(" #181B1A ")).toBe("#181B1A");
+      expect(coerceWindowBackgroundColor("#fff")).toBe("#fff");
+      expect(coerceWindowBackgroundColor(null)).toBeNull();
+    });
+
+    it("rejects named colors and malformed hex", () => {
+      expect(() => coerceWindowBackgroundColor("black")).toThrow("hex color");
+      expect(() => coerceWindowBackgroundColor("#12345")).toThrow("hex color");
+      expect(() => coerceWindowBackgroundColor(undefined)).toThrow("hex color");
+    });
+  });
+
   describe("getTitleBarOverlayOptions", () => {
     it("returns light title bar overlay colors", () => {
       expect(getTitleBarOverlayOptions("light")).toEqual({
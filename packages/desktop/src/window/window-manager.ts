@@ -1,4 +1,8 @@
 import { app, BrowserWindow, Menu, ipcMain, nativeTheme } from "electron";
+import { emitDesktopEvent } from "./desktop-events.js";
+import { getWindowLabel, listLabeledWindows } from "./window-labels.js";
+
+const MAC_TRAFFIC_LIGHT_POSITION = { x: 16, y: 14 };
 
 export function readBadgeCount(input: unknown): number {
   if (typeof input !== "number" || !Number.isSafeInteger(input) || input < 0) {
@@ -7,6 +11,10 @@ export function readBadgeCount(input: unknown): number {
 
   return input;
 }
+const HEX_COLOR_PATTERN = /^#(?:[0-9a-f]{3}|[0-9a-f]{4}|[0-9a-f]{6}|[0-9a-f]{8})$/i;
+
+// Colors the renderer picked; windows without one follow the OS theme.
+const windowBackgroundOverrides = new WeakMap<BrowserWindow, string>();
 
 export type WindowTheme = "light" | "dark";
 export interface WindowControlsOverlayUpdate {
@@ -37,6 +45,45 @@ export function getWindowBackgroundColor(theme: WindowTheme): string {
   return theme === "dark" ? "#181B1A" : "#ffffff";
 }
 
+/** `null` hands the background back to the OS theme. */
+export function coerceWindowBackgroundColor(input: unknown): string | null {
+  if (input === null) {
+    return null;
+  }
+  if (typeof input !== "string" || !HEX_COLOR_PATTERN.test(input.trim())) {
+    throw new Error("color must be a hex color such as #181B1A, or null.");
+  }
+  return input.trim();
+}
+
+/**
+ * Sets the native background painted before and between page loads, so a
+ * reload in a dark theme does not flash white.
+ */
+export function setWindowBackground(input: {
+  win: BrowserWindow;
+  color: unknown;
+}): { color: string; followsSystemTheme: boolean } {
+  const color = coerceWindowBackgroundColor(input.color);
+  if (color === null) {
+    windowBackgroundOverrides.delete(input.win);
+  } else {
+    windowBackgroundOverrides.set(input.win, color);
+  }
+  const applied = color ?? getWindowBackgroundColor(resolveSystemWindowTheme());
+  input.win.setBackgroundColor(applied);
+  return { color: applied, followsSystemTheme: color === null };
+}
+
+function syncWindowBackgroundsWithSystemTheme(): void {
+  const color = getWindowBackgroundColor(resolveSystemWindowTheme());
+  for (const { win } of listLabeledWindows()) {
+    if (!windowBackgroundOverrides.has(win)) {
+      win.setBackgroundColor(color);
+    }
+  }
+}
+
 export function createWindowControlsOverlayState(theme: WindowTheme): WindowControlsOverlayState {
   const overlay = getTitleBarOverlayOptions(theme);
   return {
@@ -65,7 +112,7 @@ export function getMainWindowChromeOptions(input: {
     return {
       titleBarStyle: "hidden",
       titleBarOverlay: true,
-      trafficLightPosition: { x: 16, y: 14 },
+      trafficLightPosition: MAC_TRAFFIC_LIGHT_POSITION,
     };
   }
 
@@ -142,9 +189,33 @@ export function applyWindowControlsOverlayUpdate(input: {
   return next;
 }
 
+export const VISIBLE_ON_ALL_WORKSPACES_MENU_ITEM_ID = "visible-on-all-workspaces";
+
+export function syncVisibleOnAllWorkspacesMenuItem(win: BrowserWindow): void {
+  const item = Menu.getApplicationMenu()?.getMenuItemById(VISIBLE_ON_ALL_WORKSPACES_MENU_ITEM_ID);
+  if (item && !win.isDestroyed()) {
+    item.checked = win.isVisibleOnAllWorkspaces();
+  }
+}
+
+/** Pins `win` to every macOS Space / Linux virtual desktop; Windows has no equivalent. */
+export function setWindowVisibleOnAllWorkspaces(input: {
+  win: BrowserWindow;
+  enabled: boolean;
+}): boolean {
+  if (process.platform === "win32") {
+    throw new Error("Showing a window on all virtual desktops is not supported on Windows.");
+  }
+  input.win.setVisibleOnAllWorkspaces(input.enabled);
+  syncVisibleOnAllWorkspacesMenuItem(input.win);
+  return input.win.isVisibleOnAllWorkspaces();
+}
+
 export function registerWindowManager(): void {
   const overlayStateByWindow = new WeakMap<BrowserWindow, WindowControlsOverlayState>();
 
+  nativeTheme.on("updated", syncWindowBackgroundsWithSystemTheme);
+
   ipcMain.handle("paseo:window:toggleMaximize", (event) => {
     const win = BrowserWindow.fromWebContents(event.sender);
     if (!win) return;
@@ -160,21 +231,6 @@ export function registerWindowManager(): void {
     return win?.isFullScreen() ?? false;
   });
 
-  ipcMain.handle("paseo:window:setBadgeCount", (_event, count?: unknown) => {
-    if (process.platform === "darwin" || process.platform === "linux") {
-      const badgeCount = readBadgeCount(count);
-      try {
-        app.setBadgeCount(badgeCount);
-      } catch (error) {
-        console.warn("[window-manager] Failed to update badge count", {
-          count,
-          badgeCount,
-          error,
-        });
-      }
-    }
-  });
-
   ipcMain.handle("paseo:window:updateWindowControls", (event, update?: unknown) => {
     const win = BrowserWindow.fromWebContents(event.sender);
     if (!win) {
@@ -187,6 +243,7 @@ export function registerWindowManager(): void {
     }
 
     if (nextUpdate.backgroundColor) {
+      windowBackgroundOverrides.set(win, nextUpdate.backgroundColor);
       win.setBackgroundColor(nextUpdate.backgroundColor);
     }
 
@@ -205,18 +262,43 @@ export function registerWindowManager(): void {
   });
 }
 
+/** Uses native fullscreen on macOS (its own Space), leaving simple fullscreen first if set. */
+export function toggleWindowFullscreen(win: BrowserWindow): boolean {
+  if (process.platform === "darwin" && win.isSimpleFullScreen()) {
+    win.setSimpleFullScreen(false);
+    return false;
+  }
+  const next = !win.isFullScreen();
+  win.setFullScreen(next);
+  return next;
+}
+
+// macOS drops the traffic-light inset and repaints at the default zoom after a
+// fullscreen transition, so both are re-applied once it settles.
+function restoreWindowChromeAfterFullscreen(win: BrowserWindow, zoomLevel: number): void {
+  if (win.isDestroyed()) {
+    return;
+  }
+  win.webContents.setZoomLevel(zoomLevel);
+  if (process.platform === "darwin" && !win.isFullScreen()) {
+    win.setWindowButtonPosition(MAC_TRAFFIC_LIGHT_POSITION);
+  }
+  win.webContents.invalidate();
+}
+
 export function setupWindowResizeEvents(win: BrowserWindow): void {
   win.on("resize", () => {
     win.webContents.send("paseo:window:resized", {});
   });
 
-  win.on("enter-full-screen", () => {
+  const handleFullscreenChanged = (fullscreen: boolean) => {
+    restoreWindowChromeAfterFullscreen(win, win.webContents.getZoomLevel());
     win.webContents.send("paseo:window:resized", {});
-  });
+    emitDesktopEvent("window-fullscreen-changed", { windowLabel: getWindowLabel(win), fullscreen });
+  };
 
-  win.on("leave-full-screen", () => {
-    win.webContents.send("paseo:window:resized", {});
-  });
+  win.on("enter-full-screen", () => handleFullscreenChanged(true));
+  win.on("leave-full-screen", () => handleFullscreenChanged(false));
 }
 
 function refreshChromiumSurface(win: BrowserWindow): void {
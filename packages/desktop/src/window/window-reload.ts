@@ -0,0 +1,110 @@
+import type { BrowserWindow } from "electron";
+import log from "electron-log/main";
+import {
+  buildRestoreScrollScript,
+  CAPTURE_RELOAD_SNAPSHOT_SCRIPT,
+  parseReloadSnapshot,
+  routeFromUrl,
+  type ReloadSnapshot,
+} from "./reload-snapshot.js";
+
+const CAPTURE_TIMEOUT_MS = 1_000;
+
+const appOrigins = new WeakMap<BrowserWindow, string>();
+const lastRoutes = new WeakMap<BrowserWindow, string>();
+// Refreshed on blur, so a crashed renderer still comes back close to where it was.
+const lastSnapshots = new WeakMap<BrowserWindow, ReloadSnapshot>();
+
+async function captureSnapshot(win: BrowserWindow): Promise<ReloadSnapshot | null> {
+  if (win.isDestroyed() || win.webContents.isCrashed()) {
+    return null;
+  }
+  const timeout = new Promise<null>((resolve) => {
+    setTimeout(() => resolve(null), CAPTURE_TIMEOUT_MS).unref();
+  });
+  try {
+    const raw = await Promise.race([
+      win.webContents.executeJavaScript(CAPTURE_RELOAD_SNAPSHOT_SCRIPT, true),
+      timeout,
+    ]);
+    const snapshot = parseReloadSnapshot(raw);
+    if (snapshot) {
+      lastSnapshots.set(win, snapshot);
+    }
+    return snapshot;
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Reloads an app window onto the route it was showing and scrolls it back.
+ * Zoom needs nothing here: window-zoom re-applies the main-side factor after
+ * every load.
+ */
+export async function reloadWindowPreservingState(input: {
+  win: BrowserWindow;
+  ignoreCache?: boolean;
+}): Promise<ReloadSnapshot | null> {
+  const { win } = input;
+  const appOrigin = appOrigins.get(win);
+  if (!appOrigin) {
+    if (input.ignoreCache) {
+      win.webContents.reloadIgnoringCache();
+    } else {
+      win.webContents.reload();
+    }
+    return null;
+  }
+
+  const route = lastRoutes.get(win);
+  const snapshot =
+    (await captureSnapshot(win)) ??
+    lastSnapshots.get(win) ??
+    (route ? { route, scroll: [] } : null);
+  const url = new URL(snapshot?.route ?? "/", appOrigin).toString();
+
+  if (snapshot && snapshot.scroll.length > 0) {
+    win.webContents.once("did-finish-load", () => {
+      void win.webContents
+        .executeJavaScript(buildRestoreScrollScript(snapshot.scroll), true)
+        .catch(() => undefined);
+    });
+  }
+  try {
+    await win.webContents.loadURL(
+      url,
+      input.ignoreCache ? { extraHeaders: "pragma: no-cache\n" } : undefined,
+    );
+  } catch (error) {
+    log.warn("[window-reload] reload did not finish loading", url, error);
+  }
+  return snapshot;
+}
+
+/** Tracks the route of an app window and brings it back after a renderer crash. */
+export function setupWindowReloadRecovery(win: BrowserWindow, appOrigin: string): void {
+  appOrigins.set(win, appOrigin);
+  const trackRoute = (url: string) => {
+    const route = routeFromUrl(url, appOrigin);
+    if (route) {
+      lastRoutes.set(win, route);
+    }
+  };
+  win.webContents.on("did-navigate", (_event, url) => trackRoute(url));
+  win.webContents.on("did-navigate-in-page", (_event, url, isMainFrame) => {
+    if (isMainFrame) {
+      trackRoute(url);
+    }
+  });
+  win.on("blur", () => {
+    void captureSnapshot(win);
+  });
+  win.webContents.on("render-process-gone", (_event, details) => {
+    if (details.reason === "clean-exit" || win.isDestroyed()) {
+      return;
+    }
+    log.warn("[window-reload] renderer gone, reloading", details.reason);
+    void reloadWindowPreservingState({ win });
+  });
+}
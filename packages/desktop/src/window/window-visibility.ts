@@ -0,0 +1,44 @@
+import { app, BrowserWindow } from "electron";
+
+const listeners = new Set<(visible: boolean) => void>();
+let anyWindowVisible = true;
+
+function computeAnyWindowVisible(): boolean {
+  return BrowserWindow.getAllWindows().some(
+    (win) => !win.isDestroyed() && win.isVisible() && !win.isMinimized(),
+  );
+}
+
+function refresh(): void {
+  const next = computeAnyWindowVisible();
+  if (next === anyWindowVisible) {
+    return;
+  }
+  anyWindowVisible = next;
+  for (const listener of listeners) {
+    listener(next);
+  }
+}
+
+/** False while every window is hidden, minimized or closed. */
+export function isAnyWindowVisible(): boolean {
+  return anyWindowVisible;
+}
+
+export function onWindowVisibilityChanged(listener: (visible: boolean) => void): () => void {
+  listeners.add(listener);
+  return () => {
+    listeners.delete(listener);
+  };
+}
+
+export function registerWindowVisibilityEvents(): void {
+  app.on("browser-window-created", (_event, win) => {
+    win.on("show", refresh);
+    win.on("hide", refresh);
+    win.on("minimize", refresh);
+    win.on("restore", refresh);
+    // The window still counts as open while "closed" runs.
+    win.once("closed", () => setImmediate(refresh));
+  });
+}
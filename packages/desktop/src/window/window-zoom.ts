@@ -0,0 +1,72 @@
+import { BrowserWindow } from "electron";
+import { emitDesktopEvent } from "./desktop-events.js";
+import { getWindowLabel } from "./window-labels.js";
+import { composeZoomState, stepZoomFactor, type WindowZoomState } from "./zoom-model.js";
+
+// Pinch and Ctrl+wheel arrive as a stream of small steps rather than one per gesture.
+const GESTURE_ZOOM_LEVEL_STEP = 0.1;
+
+const userZoomFactors = new WeakMap<BrowserWindow, number>();
+let systemTextScale = 1;
+
+export function getWindowZoomState(win: BrowserWindow): WindowZoomState {
+  return composeZoomState({ userFactor: userZoomFactors.get(win) ?? 1, systemTextScale });
+}
+
+function applyWindowZoom(win: BrowserWindow): WindowZoomState {
+  const state = getWindowZoomState(win);
+  if (!win.isDestroyed()) {
+    win.webContents.setZoomFactor(state.effectiveFactor);
+    emitDesktopEvent("window-zoom-changed", { windowLabel: getWindowLabel(win), ...state });
+  }
+  return state;
+}
+
+export function setWindowZoomFactor(input: {
+  win: BrowserWindow;
+  factor: unknown;
+}): WindowZoomState {
+  if (typeof input.factor !== "number" || !Number.isFinite(input.factor)) {
+    throw new Error("Zoom factor must be a number.");
+  }
+  const { userFactor } = composeZoomState({ userFactor: input.factor, systemTextScale });
+  userZoomFactors.set(input.win, userFactor);
+  return applyWindowZoom(input.win);
+}
+
+/** Menu and shortcut zoom, in Chromium zoom levels. */
+export function adjustWindowZoom(input: { win: BrowserWindow; levels: number }): WindowZoomState {
+  const current = userZoomFactors.get(input.win) ?? 1;
+  userZoomFactors.set(input.win, stepZoomFactor(current, input.levels));
+  return applyWindowZoom(input.win);
+}
+
+export function resetWindowZoom(win: BrowserWindow): WindowZoomState {
+  userZoomFactors.delete(win);
+  return applyWindowZoom(win);
+}
+
+export function setSystemTextScale(scale: number): void {
+  systemTextScale = composeZoomState({ userFactor: 1, systemTextScale: scale }).systemTextScale;
+  for (const win of BrowserWindow.getAllWindows()) {
+    applyWindowZoom(win);
+  }
+}
+
+/**
+ * Re-applies the composed factor after each load, since navigation resets
+ * Chromium's zoom, and routes trackpad pinch and Ctrl+wheel into the same
+ * model as the menu instead of Chromium's visual (page-magnifier) pinch zoom.
+ */
+export function setupWindowZoom(win: BrowserWindow): void {
+  win.webContents.on("did-finish-load", () => {
+    void win.webContents.setVisualZoomLevelLimits(1, 1);
+    applyWindowZoom(win);
+  });
+  win.webContents.on("zoom-changed", (_event, direction) => {
+    adjustWindowZoom({
+      win,
+      levels: direction === "in" ? GESTURE_ZOOM_LEVEL_STEP : -GESTURE_ZOOM_LEVEL_STEP,
+    });
+  });
+}
@@ -0,0 +1,36 @@
+import { describe, expect, it } from "vitest";
+import { clampZoomFactor, composeZoomState, stepZoomFactor } from "./zoom-model";
+
+describe("zoom model", () => {
+  it("clamps factors to 0.5–3", () => {
+    expect(clampZoomFactor(0.1)).toBe(0.5);
+    expect(clampZoomFactor(5)).toBe(3);
+    expect(clampZoomFactor(Number.NaN)).toBe(1);
+  });
+
+  it("composes the user factor with the system text scale", () => {
+    expect(composeZoomState({ userFactor: 1.25, systemTextScale: 1.5 })).toEqual({
+      userFactor: 1.25,
+      systemTextScale: 1.5,
+      effectiveFactor: 1.875,
+    });
+    expect(composeZoomState({ userFactor: 2.5, systemTextScale: 2 }).effectiveFactor).toBe(3);
+    expect(composeZoomState({ userFactor: 1, systemTextScale: 0 }).systemTextScale).toBe(1);
+  });
+
+  it("steps by half a Chromium zoom level and returns to 1", () => {
+    const zoomedIn = stepZoomFactor(1, 0.5);
+    expect(zoomedIn).toBeCloseTo(1.095, 3);
+    expect(stepZoomFactor(zoomedIn, -0.5)).toBe(1);
+    expect(stepZoomFactor(3, 0.5)).toBe(3);
+  });
+
+  it("accumulates small gesture steps without stalling at the clamp", () => {
+    let factor = 1;
+    for (let step = 0; step < 10; step += 1) {
+      factor = stepZoomFactor(factor, 0.1);
+    }
+    expect(factor).toBeCloseTo(1.2, 2);
+    expect(stepZoomFactor(0.5, 0.1)).toBeGreaterThan(0.5);
+  });
+});
@@ -0,0 +1,43 @@
+export const MIN_ZOOM_FACTOR = 0.5;
+export const MAX_ZOOM_FACTOR = 3;
+// Chromium's zoom levels are powers of 1.2; the menu steps by half a level.
+const ZOOM_LEVEL_BASE = 1.2;
+
+export interface WindowZoomState {
+  /** What the user chose with the menu, shortcuts, or `set_zoom_factor`. */
+  userFactor: number;
+  /** OS text-size preference, 1 when unset or unreadable. */
+  systemTextScale: number;
+  /** Factor actually applied to the window. */
+  effectiveFactor: number;
+}
+
+export function clampZoomFactor(factor: number): number {
+  if (!Number.isFinite(factor)) {
+    return 1;
+  }
+  return Math.min(MAX_ZOOM_FACTOR, Math.max(MIN_ZOOM_FACTOR, factor));
+}
+
+export function composeZoomState(input: {
+  userFactor: number;
+  systemTextScale: number;
+}): WindowZoomState {
+  const userFactor = clampZoomFactor(input.userFactor);
+  const systemTextScale =
+    Number.isFinite(input.systemTextScale) && input.systemTextScale > 0
+      ? input.systemTextScale
+      : 1;
+  return {
+    userFactor,
+    systemTextScale,
+    effectiveFactor: clampZoomFactor(userFactor * systemTextScale),
+  };
+}
+
+export function stepZoomFactor(factor: number, levels: number): number {
+  const level = Math.log(clampZoomFactor(factor)) / Math.log(ZOOM_LEVEL_BASE);
+  // Round away float drift so repeated steps land back on exactly 1.
+  const next = Math.round(ZOOM_LEVEL_BASE ** (level + levels) * 1000) / 1000;
+  return clampZoomFactor(next);
+}
@@ -80,6 +80,7 @@ import type {
 } from "../server/agent/agent-sdk-types.js";
 import type { MutableDaemonConfig, MutableDaemonConfigPatch } from "../shared/messages.js";
 import { isRelayClientWebSocketUrl } from "../shared/daemon-endpoints.js";
+import { extractWorkspaceExecutionCwd } from "../shared/workspace-execution.js";
 import {
   asUint8Array,
   decodeFileTransferFrame,
@@ -203,6 +204,11 @@ export interface DaemonClientConfig {
   appVersion?: string;
   runtimeGeneration?: number | null;
   password?: string;
+  /**
+   * Looked up before every connection attempt and used instead of `password`,
+   * so a password replaced or expired elsewhere applies on the next reconnect.
+   */
+  resolvePassword?: () => Promise<string | null>;
   authHeader?: string;
   suppressSendErrors?: boolean;
   transportFactory?: DaemonTransportFactory;
@@ -220,6 +226,20 @@ export interface DaemonClientConfig {
   };
   runtimeMetricsIntervalMs?: number;
   runtimeMetricsWindowMs?: number;
+  /**
+   * Called when the daemon refuses a request because its workspace is not
+   * trusted. Resolving `true` (the user trusted it) sends the request again.
+   */
+  onUntrustedWorkspace?: (workspacePath: string) => Promise<boolean>;
+  /**
+   * Called when the daemon refuses to allow a high-risk command without a
+   * native confirmation. Resolving a signature sends the allow again with it;
+   * `null` (declined or unavailable) leaves the refusal in place.
+   */
+  onNativeApprovalRequired?: (input: {
+    agentId: string;
+    requestId: string;
+  }) => Promise<string | null>;
 }
 
 export interface SendMessageOptions {
@@ -755,6 +775,7 @@ export class DaemonClient {
   private reconnectAttempt = 0;
   private shouldReconnect = true;
   private connectPromise: Promise<void> | null = null;
+  private passwordLookup: Promise<void> | null = null;
   private connectResolve: (() => void) | null = null;
   private connectReject: ((error: Error) => void) | null = null;
   private lastErrorValue: string | null = null;
@@ -852,22 +873,45 @@ export class DaemonClient {
     return this.connectPromise;
   }
 
-  private attemptConnect(): void {
+  private canAttemptConnect(): boolean {
     if (this.connectionState.status === "disposed") {
       this.rejectConnect(new Error("Daemon client is disposed"));
-      return;
+      return false;
     }
     if (!this.shouldReconnect) {
       this.rejectConnect(new Error("Daemon client is closed"));
-      return;
+      return false;
     }
+    return this.connectionState.status !== "connecting";
+  }
 
-    if (this.connectionState.status === "connecting") {
+  private attemptConnect(): void {
+    if (!this.canAttemptConnect()) {
+      return;
+    }
+    const { resolvePassword } = this.config;
+    if (!resolvePassword) {
+      this.openTransport(normalizePassword(this.config.password));
       return;
     }
+    if (this.passwordLookup) {
+      return;
+    }
+    this.passwordLookup = resolvePassword()
+      .catch((error: unknown) => {
+        this.logger.warn({ err: error }, "Failed to look up daemon password");
+        return null;
+      })
+      .then((password) => {
+        this.passwordLookup = null;
+        if (this.canAttemptConnect()) {
+          this.openTransport(normalizePassword(password ?? undefined));
+        }
+      });
+  }
 
+  private openTransport(password: string | null): void {
     const headers: Record<string, string> = {};
-    const password = normalizePassword(this.config.password);
     if (password) {
       headers.Authorization = `Bearer ${password}`;
     } else if (this.config.authHeader) {
@@ -1266,6 +1310,8 @@ export class DaemonClient {
     timeout: number;
     select: (msg: SessionOutboundMessage) => T | null;
     options?: { skipQueue?: boolean };
+    /** Set on the one resend after the user trusted the workspace or confirmed the approval. */
+    retried?: boolean;
   }): Promise<T> {
     const { promise, cancel } = this.waitForWithCancel<RpcWaitResult<T>>(
       (msg) => {
@@ -1301,11 +1347,42 @@ export class DaemonClient {
 
     const result = await promise;
     if (result.kind === "error") {
+      const resend = params.retried
+        ? null
+        : await this.resolveRefusal(params.message, result.error);
+      if (resend) {
+        return this.sendRequest({ ...params, message: resend, retried: true });
+      }
       throw result.error;
     }
     return result.value;
   }
 
+  /** The message to send again once the user resolved the daemon's refusal, if they did. */
+  private async resolveRefusal(
+    message: SessionInboundMessage,
+    error: DaemonRpcError,
+  ): Promise<SessionInboundMessage | null> {
+    if (error.code === "workspace_not_trusted") {
+      const workspacePath = extractWorkspaceExecutionCwd(message);
+      const trusted = workspacePath
+        ? await this.config.onUntrustedWorkspace?.(workspacePath)
+        : false;
+      return trusted ? message : null;
+    }
+    if (
+      error.code === "native_approval_required" &&
+      message.type === "agent_permission_response"
+    ) {
+      const signature = await this.config.onNativeApprovalRequired?.({
+        agentId: message.agentId,
+        requestId: message.requestId,
+      });
+      return signature ? { ...message, nativeApproval: signature } : null;
+    }
+    return null;
+  }
+
   private async sendCorrelatedRequest<
     TResponseType extends CorrelatedResponseType,
     TResult = CorrelatedResponsePayload<TResponseType>,
@@ -3227,6 +3304,11 @@ export class DaemonClient {
     requestId: string,
     response: AgentPermissionResponse,
   ): Promise<void> {
+    if (response.behavior === "allow" && this.config.onNativeApprovalRequired) {
+      // Wait for the outcome so a refusal can be confirmed natively and resent.
+      await this.respondToPermissionAndWait(agentId, requestId, response);
+      return;
+    }
     this.sendSessionMessage({
       type: "agent_permission_response",
       agentId,
@@ -1,6 +1,7 @@
 import express from "express";
 import { createServer as createHTTPServer, type IncomingMessage, type ServerResponse } from "http";
-import { createReadStream, unlinkSync, existsSync } from "fs";
+import { createServer as createHTTPSServer } from "https";
+import { createReadStream, readFileSync, unlinkSync, existsSync } from "fs";
 import { stat } from "fs/promises";
 import { randomUUID } from "node:crypto";
 import { hostname as getHostname } from "node:os";
@@ -17,7 +18,7 @@ export type ListenTarget =
 
 function resolveBoundListenTarget(
   listenTarget: ListenTarget,
-  httpServer: ReturnType<typeof createHTTPServer>,
+  httpServer: ReturnType<typeof createHTTPServer> | ReturnType<typeof createHTTPSServer>,
 ): ListenTarget {
   if (listenTarget.type !== "tcp") {
     return listenTarget;
@@ -86,7 +87,7 @@ function formatListenTarget(listenTarget: ListenTarget | null): string | null {
   return listenTarget.path;
 }
 
-import { VoiceAssistantWebSocketServer } from "./websocket-server.js";
+import { type DaemonCompressionConfig, VoiceAssistantWebSocketServer } from "./websocket-server.js";
 import { createGitHubService } from "../services/github-service.js";
 import { createPaseoWorktree as createRegisteredPaseoWorktree } from "./paseo-worktree-service.js";
 import { createPaseoWorktreeWorkflow } from "./worktree-session.js";
@@ -122,7 +123,11 @@ import { loadOrCreateDaemonKeyPair } from "./daemon-keypair.js";
 import { startRelayTransport, type RelayTransportController } from "./relay-transport.js";
 import { getOrCreateServerId } from "./server-id.js";
 import { resolveDaemonVersion } from "./daemon-version.js";
-import type { AgentClient, AgentProvider } from "./agent/agent-sdk-types.js";
+import type {
+  AgentClient,
+  AgentPermissionRequest,
+  AgentProvider,
+} from "./agent/agent-sdk-types.js";
 import type {
   AgentProviderRuntimeSettingsMap,
   ProviderOverride,
@@ -137,6 +142,8 @@ import { createScriptStatusEmitter } from "./script-status-projection.js";
 import { WorkspaceScriptRuntimeStore } from "./workspace-script-runtime-store.js";
 import { isHostnameAllowed, type HostnamesConfig } from "./hostnames.js";
 import { createRequireBearerMiddleware, type DaemonAuthConfig } from "./auth.js";
+import { createWorkspaceTrustGate } from "./workspace-trust.js";
+import { createNativeApprovalGuard, describeHighRiskPermission } from "./native-approval.js";
 
 type AgentMcpTransportMap = Map<string, StreamableHTTPServerTransport>;
 
@@ -144,13 +151,16 @@ function formatHostForHttpUrl(host: string): string {
   return host.includes(":") && !host.startsWith("[") ? `[${host}]` : host;
 }
 
-function createAgentMcpBaseUrl(listenTarget: ListenTarget | null): string | null {
+function createAgentMcpBaseUrl(
+  listenTarget: ListenTarget | null,
+  protocol: "http" | "https",
+): string | null {
   if (!listenTarget || listenTarget.type !== "tcp") {
     return null;
   }
   return new URL(
     "/mcp/agents",
-    `http://${formatHostForHttpUrl(listenTarget.host)}:${listenTarget.port}`,
+    `${protocol}://${formatHostForHttpUrl(listenTarget.host)}:${listenTarget.port}`,
   ).toString();
 }
 
@@ -175,6 +185,12 @@ export type DaemonLifecycleIntent =
       reason?: string;
     };
 
+/** PEM files for serving HTTPS and WSS on a TCP listen address. */
+export interface DaemonTlsConfig {
+  certPath: string;
+  keyPath: string;
+}
+
 export interface PaseoDaemonConfig {
   listen: string;
   paseoHome: string;
@@ -193,6 +209,14 @@ export interface PaseoDaemonConfig {
   relayPublicEndpoint?: string;
   appBaseUrl?: string;
   auth?: DaemonAuthConfig;
+  /** Off (`null`) unless configured; connections stay uncompressed. */
+  compression?: DaemonCompressionConfig | null;
+  /** Ignored for socket and pipe listeners, which never leave the machine. */
+  tls?: DaemonTlsConfig | null;
+  /** Trust decisions kept by the desktop app; when set, execution in other folders is refused. */
+  workspaceTrustFile?: string | null;
+  /** Key the desktop app signs confirmations with; when set, high-risk approvals need one. */
+  nativeApprovalKeyFile?: string | null;
   openai?: PaseoOpenAIConfig;
   speech?: PaseoSpeechConfig;
   voiceLlmProvider?: AgentProvider | null;
@@ -252,6 +276,8 @@ export async function createPaseoDaemon(
   const downloadTokenStore = new DownloadTokenStore({ ttlMs: downloadTokenTtlMs });
 
   const listenTarget = parseListenString(config.listen);
+  const tls = listenTarget.type === "tcp" ? (config.tls ?? null) : null;
+  const httpProtocol = tls ? "https" : "http";
 
   const app = express();
   let boundListenTarget: ListenTarget | null = null;
@@ -305,9 +331,9 @@ export async function createPaseoDaemon(
     // For TCP, add localhost variants
     ...(listenTarget.type === "tcp"
       ? [
-          `http://${listenTarget.host}:${listenTarget.port}`,
-          `http://localhost:${listenTarget.port}`,
-          `http://127.0.0.1:${listenTarget.port}`,
+          `${httpProtocol}://${listenTarget.host}:${listenTarget.port}`,
+          `${httpProtocol}://localhost:${listenTarget.port}`,
+          `${httpProtocol}://127.0.0.1:${listenTarget.port}`,
         ]
       : []),
   ]);
@@ -410,7 +436,9 @@ export async function createPaseoDaemon(
     void handleFileDownload(req, res);
   });
 
-  const httpServer = createHTTPServer(app);
+  const httpServer = tls
+    ? createHTTPSServer({ cert: readFileSync(tls.certPath), key: readFileSync(tls.keyPath) }, app)
+    : createHTTPServer(app);
 
   // Script proxy WebSocket upgrade handler — must be registered before the
   // VoiceAssistantWebSocketServer attaches its own "upgrade" listener so that
@@ -460,6 +488,25 @@ export async function createPaseoDaemon(
     logger,
   });
 
+  // Lets the desktop app show the pending command itself before it signs an
+  // approval, instead of trusting details relayed by a renderer.
+  app.get("/api/permissions/high-risk", (req, res) => {
+    const agentId = typeof req.query.agentId === "string" ? req.query.agentId : "";
+    const requestId = typeof req.query.requestId === "string" ? req.query.requestId : "";
+    let request: AgentPermissionRequest | undefined;
+    try {
+      request = agentManager.getPendingPermissions(agentId).find((entry) => entry.id === requestId);
+    } catch {
+      request = undefined;
+    }
+    const permission = request ? describeHighRiskPermission(agentId, request) : null;
+    if (!permission) {
+      res.status(404).json({ error: "No pending high-risk permission" });
+      return;
+    }
+    res.json(permission);
+  });
+
   const detachAgentStoragePersistence = attachAgentStoragePersistence(
     logger,
     agentManager,
@@ -763,7 +810,9 @@ export async function createPaseoDaemon(
         httpServer.off("error", onError);
         const logAndResolve = async () => {
           boundListenTarget = resolveBoundListenTarget(listenTarget, httpServer);
-          const mcpBaseUrl = mcpEnabled ? createAgentMcpBaseUrl(boundListenTarget) : null;
+          const mcpBaseUrl = mcpEnabled
+            ? createAgentMcpBaseUrl(boundListenTarget, httpProtocol)
+            : null;
           agentMcpBaseUrl = config.mcpInjectIntoAgents === false ? null : mcpBaseUrl;
           agentManager.setMcpBaseUrl(agentMcpBaseUrl);
           daemonConfigStore.onFieldChange("mcp.injectIntoAgents", (value) => {
@@ -782,7 +831,7 @@ export async function createPaseoDaemon(
                 authRequired: !!config.auth?.password,
                 elapsed: elapsed(),
               },
-              `Server listening on http://${boundListenTarget.host}:${boundListenTarget.port}`,
+              `Server listening on ${httpProtocol}://${formatListenTarget(boundListenTarget)}`,
             );
           } else {
             logger.info(
@@ -808,7 +857,20 @@ export async function createPaseoDaemon(
             config.paseoHome,
             daemonConfigStore,
             mcpBaseUrl,
-            { allowedOrigins, hostnames: configuredHostnames },
+            {
+              allowedOrigins,
+              hostnames: configuredHostnames,
+              compression: config.compression ?? null,
+              workspaceTrust: config.workspaceTrustFile
+                ? createWorkspaceTrustGate({ filePath: config.workspaceTrustFile })
+                : null,
+              nativeApproval: config.nativeApprovalKeyFile
+                ? createNativeApprovalGuard({
+                    keyFile: config.nativeApprovalKeyFile,
+                    getPendingPermissions: (agentId) => agentManager.getPendingPermissions(agentId),
+                  })
+                : null,
+            },
             config.auth,
             speechService,
             terminalManager,
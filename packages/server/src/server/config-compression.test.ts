@@ -0,0 +1,52 @@
+import { mkdir, mkdtemp, writeFile, rm } from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, test } from "vitest";
+
+import { loadConfig } from "./config.js";
+
+const roots: string[] = [];
+
+async function createPaseoHome(config: unknown): Promise<string> {
+  const root = await mkdtemp(path.join(os.tmpdir(), "paseo-config-compression-"));
+  roots.push(root);
+  const paseoHome = path.join(root, ".paseo");
+  await mkdir(paseoHome, { recursive: true });
+  await writeFile(path.join(paseoHome, "config.json"), JSON.stringify(config, null, 2));
+  return paseoHome;
+}
+
+describe("daemon compression config", () => {
+  afterEach(async () => {
+    await Promise.all(roots.splice(0).map((root) => rm(root, { recursive: true, force: true })));
+  });
+
+  test("leaves websocket compression off by default", async () => {
+    const paseoHome = await createPaseoHome({ version: 1 });
+
+    expect(loadConfig(paseoHome, { env: {} }).compression).toBeNull();
+  });
+
+  test("loads the deflate level and threshold from config.json", async () => {
+    const paseoHome = await createPaseoHome({
+      version: 1,
+      daemon: { compression: { enabled: true, level: 4 } },
+    });
+
+    expect(loadConfig(paseoHome, { env: {} }).compression).toEqual({
+      level: 4,
+      threshold: 1024,
+    });
+  });
+
+  test("lets PASEO_WS_COMPRESSION override config.json", async () => {
+    const paseoHome = await createPaseoHome({
+      version: 1,
+      daemon: { compression: { enabled: true } },
+    });
+
+    const config = loadConfig(paseoHome, { env: { PASEO_WS_COMPRESSION: "off" } });
+
+    expect(config.compression).toBeNull();
+  });
+});
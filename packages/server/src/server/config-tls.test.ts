@@ -0,0 +1,68 @@
+import { mkdir, mkdtemp, writeFile, rm } from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, test } from "vitest";
+
+import { loadConfig } from "./config.js";
+
+const roots: string[] = [];
+
+async function createPaseoHome(config: unknown): Promise<string> {
+  const root = await mkdtemp(path.join(os.tmpdir(), "paseo-config-tls-"));
+  roots.push(root);
+  const paseoHome = path.join(root, ".paseo");
+  await mkdir(paseoHome, { recursive: true });
+  await writeFile(path.join(paseoHome, "config.json"), JSON.stringify(config, null, 2));
+  return paseoHome;
+}
+
+describe("daemon tls config", () => {
+  afterEach(async () => {
+    await Promise.all(roots.splice(0).map((root) => rm(root, { recursive: true, force: true })));
+  });
+
+  test("serves plain HTTP by default", async () => {
+    const paseoHome = await createPaseoHome({ version: 1 });
+
+    expect(loadConfig(paseoHome, { env: {} }).tls).toBeNull();
+  });
+
+  test("loads the certificate and key from config.json", async () => {
+    const paseoHome = await createPaseoHome({
+      version: 1,
+      daemon: { tls: { cert: "/tls/server.crt", key: "/tls/server.key" } },
+    });
+
+    expect(loadConfig(paseoHome, { env: {} }).tls).toEqual({
+      certPath: "/tls/server.crt",
+      keyPath: "/tls/server.key",
+    });
+  });
+
+  test("takes the pair from the environment over config.json", async () => {
+    const paseoHome = await createPaseoHome({
+      version: 1,
+      daemon: { tls: { cert: "/tls/server.crt", key: "/tls/server.key" } },
+    });
+
+    const config = loadConfig(paseoHome, {
+      env: { PASEO_TLS_CERT: "/desktop/server.crt", PASEO_TLS_KEY: "/desktop/server.key" },
+    });
+
+    expect(config.tls).toEqual({
+      certPath: "/desktop/server.crt",
+      keyPath: "/desktop/server.key",
+    });
+  });
+
+  test("rejects a certificate without its key", async () => {
+    const paseoHome = await createPaseoHome({
+      version: 1,
+      daemon: { tls: { cert: "/tls/server.crt", key: "/tls/server.key" } },
+    });
+
+    expect(() =>
+      loadConfig(paseoHome, { env: { PASEO_TLS_CERT: "/desktop/server.crt" } }),
+    ).toThrow("PASEO_TLS_CERT and PASEO_TLS_KEY");
+  });
+});
@@ -2,7 +2,8 @@ import path from "node:path";
 import { resolvePaseoNodeEnv } from "./paseo-env.js";
 import { z } from "zod";
 
-import type { PaseoDaemonConfig } from "./bootstrap.js";
+import type { DaemonTlsConfig, PaseoDaemonConfig } from "./bootstrap.js";
+import type { DaemonCompressionConfig } from "./websocket-server.js";
 import { loadPersistedConfig } from "./persisted-config.js";
 import type { AgentProvider } from "./agent/agent-sdk-types.js";
 import type {
@@ -18,6 +19,10 @@ import { mergeHostnames, parseHostnamesEnv, type HostnamesConfig } from "./hostn
 const DEFAULT_PORT = 6767;
 const DEFAULT_RELAY_ENDPOINT = "relay.paseo.sh:443";
 const DEFAULT_APP_BASE_URL = "https://app.paseo.sh";
+// Agent event streams are verbose JSON; deflate only frames large enough to pay off,
+// at the fastest zlib level so busy daemons don't spend their CPU compressing.
+const DEFAULT_COMPRESSION_LEVEL = 1;
+const DEFAULT_COMPRESSION_THRESHOLD = 1024;
 
 function parseBooleanEnv(value: string | undefined): boolean | undefined {
   if (value === undefined) {
@@ -133,6 +138,42 @@ function resolveRelayConfig(input: ResolveRelayInput): ResolvedRelay {
   return { enabled, endpoint, publicEndpoint };
 }
 
+function resolveCompressionConfig(
+  env: NodeJS.ProcessEnv,
+  persisted: ReturnType<typeof loadPersistedConfig>,
+): DaemonCompressionConfig | null {
+  const compression = persisted.daemon?.compression;
+  const enabled = parseBooleanEnv(env.PASEO_WS_COMPRESSION) ?? compression?.enabled ?? false;
+  if (!enabled) {
+    return null;
+  }
+  return {
+    level: compression?.level ?? DEFAULT_COMPRESSION_LEVEL,
+    threshold: compression?.threshold ?? DEFAULT_COMPRESSION_THRESHOLD,
+  };
+}
+
+// The certificate and key must come from the same source; a cert from the
+// environment paired with a key from config.json would never match.
+function resolveTlsConfig(
+  env: NodeJS.ProcessEnv,
+  persisted: ReturnType<typeof loadPersistedConfig>,
+): DaemonTlsConfig | null {
+  const envCert = env.PASEO_TLS_CERT?.trim();
+  const envKey = env.PASEO_TLS_KEY?.trim();
+  const [certPath, keyPath, source] =
+    envCert || envKey
+      ? [envCert, envKey, "PASEO_TLS_CERT and PASEO_TLS_KEY"]
+      : [persisted.daemon?.tls?.cert, persisted.daemon?.tls?.key, "daemon.tls.cert and key"];
+  if (!certPath && !keyPath) {
+    return null;
+  }
+  if (!certPath || !keyPath) {
+    throw new Error(`TLS needs both a certificate and a key: set ${source}.`);
+  }
+  return { certPath, keyPath };
+}
+
 interface ResolvedVoiceLlm {
   provider: AgentProvider | null;
   providerExplicit: boolean;
@@ -264,6 +305,10 @@ export function loadConfig(
     relayPublicEndpoint: relay.publicEndpoint,
     appBaseUrl,
     auth: resolveAuthConfig(env, persisted),
+    compression: resolveCompressionConfig(env, persisted),
+    tls: resolveTlsConfig(env, persisted),
+    workspaceTrustFile: env.PASEO_WORKSPACE_TRUST_FILE?.trim() || null,
+    nativeApprovalKeyFile: env.PASEO_NATIVE_APPROVAL_KEY_FILE?.trim() || null,
     openai,
     speech,
     voiceLlmProvider: voiceLlm.provider,
@@ -1,5 +1,10 @@
 // CLI exports for @getpaseo/server
-export { createPaseoDaemon, type PaseoDaemon, type PaseoDaemonConfig } from "./bootstrap.js";
+export {
+  createPaseoDaemon,
+  parseListenString,
+  type PaseoDaemon,
+  type PaseoDaemonConfig,
+} from "./bootstrap.js";
 export { loadConfig, type CliConfigOverrides } from "./config.js";
 export { resolvePaseoHome } from "./paseo-home.js";
 export { getOrCreateServerId } from "./server-id.js";
@@ -11,6 +16,20 @@ export {
 } from "./persisted-config.js";
 export { hashDaemonPassword, isBearerTokenValid } from "./auth.js";
 export { generateLocalPairingOffer, type LocalPairingOffer } from "./pairing-offer.js";
+export {
+  canonicalizeWorkspacePath,
+  coerceWorkspaceTrustDecisions,
+  findWorkspaceTrustDecision,
+  normalizeWorkspacePath,
+  type WorkspaceTrustDecision,
+} from "./workspace-trust.js";
+export { extractWorkspaceExecutionCwd } from "../shared/workspace-execution.js";
+export { signNativeApproval, type HighRiskPermission } from "./native-approval.js";
+export {
+  classifyApprovalRisk,
+  extractPermissionCommand,
+  type ApprovalRiskCategory,
+} from "../shared/approval-risk.js";
 export {
   ConnectionOfferSchema,
   decodeOfferFragmentPayload,
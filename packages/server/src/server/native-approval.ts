@@ -0,0 +1,113 @@
+import { createHmac, timingSafeEqual } from "node:crypto";
+import { readFile } from "node:fs/promises";
+import {
+  classifyApprovalRisk,
+  extractPermissionCommand,
+  type ApprovalRiskCategory,
+} from "../shared/approval-risk.js";
+import type { AgentPermissionRequest } from "./agent/agent-sdk-types.js";
+
+export interface HighRiskPermission {
+  agentId: string;
+  requestId: string;
+  title: string;
+  command: string;
+  cwd: string | null;
+  riskCategory: ApprovalRiskCategory;
+}
+
+export function describeHighRiskPermission(
+  agentId: string,
+  request: AgentPermissionRequest,
+): HighRiskPermission | null {
+  const shell = extractPermissionCommand(request);
+  const riskCategory = shell ? classifyApprovalRisk(shell.command) : null;
+  if (!shell || !riskCategory) {
+    return null;
+  }
+  return {
+    agentId,
+    requestId: request.id,
+    title: request.title ?? request.name ?? "Command",
+    command: shell.command,
+    cwd: shell.cwd,
+    riskCategory,
+  };
+}
+
+/** What the desktop app attaches to an allow it confirmed natively. */
+export function signNativeApproval(
+  key: string,
+  input: { agentId: string; requestId: string },
+): string {
+  return createHmac("sha256", key)
+    .update(`approve\n${input.agentId}\n${input.requestId}`)
+    .digest("hex");
+}
+
+function isSignatureValid(
+  key: string,
+  input: { agentId: string; requestId: string },
+  signature: string,
+): boolean {
+  const expected = Buffer.from(signNativeApproval(key, input));
+  const actual = Buffer.from(signature);
+  return actual.length === expected.length && timingSafeEqual(actual, expected);
+}
+
+export interface NativeApprovalGuard {
+  /**
+   * The high-risk request an allow in `message` would approve without a
+   * signature from the desktop app, or `null` when it may go through.
+   */
+  findUnconfirmedApproval(message: unknown): Promise<HighRiskPermission | null>;
+}
+
+/**
+ * Holds high-risk approvals to the desktop app's native confirmation, whichever
+ * client sends them. The key file is shared with the app that spawned the
+ * daemon; it is read on each check so a key created later still applies.
+ */
+export function createNativeApprovalGuard(input: {
+  keyFile: string;
+  getPendingPermissions: (agentId: string) => AgentPermissionRequest[];
+}): NativeApprovalGuard {
+  return {
+    async findUnconfirmedApproval(message) {
+      const candidate = message as {
+        type?: unknown;
+        agentId?: unknown;
+        requestId?: unknown;
+        response?: { behavior?: unknown };
+        nativeApproval?: unknown;
+      };
+      if (
+        candidate?.type !== "agent_permission_response" ||
+        typeof candidate.agentId !== "string" ||
+        typeof candidate.requestId !== "string" ||
+        candidate.response?.behavior !== "allow"
+      ) {
+        return null;
+      }
+      const { agentId, requestId } = candidate;
+      let pending: AgentPermissionRequest[];
+      try {
+        pending = input.getPendingPermissions(agentId);
+      } catch {
+        // Unknown agent: the session reports that itself.
+        return null;
+      }
+      const request = pending.find((entry) => entry.id === requestId);
+      const permission = request ? describeHighRiskPermission(agentId, request) : null;
+      if (!permission) {
+        return null;
+      }
+      const key = await readFile(input.keyFile, "utf8")
+        .then((raw) => raw.trim())
+        .catch(() => "");
+      const signature =
+        typeof candidate.nativeApproval === "string" ? candidate.nativeApproval : "";
+      return key && signature && isSignatureValid(key, permission, signature) ? null : permission;
+    },
+  };
+}
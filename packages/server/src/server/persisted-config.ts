@@ -262,6 +262,20 @@ export const PersistedConfigSchema = z
           .strict()
           .optional(),
         auth: DaemonAuthSchema.optional(),
+        compression: z
+          .object({
+            enabled: z.boolean().optional(),
+            level: z.number().int().min(1).max(9).optional(),
+            threshold: z.number().int().min(0).optional(),
+          })
+          .strict()
+          .optional(),        tls: z
+          .object({
+            cert: z.string().optional(),
+            key: z.string().optional(),
+          })
+          .strict()
+          .optional(),
       })
       .strict()
       .transform(({ allowedHosts, ...daemon }) => {
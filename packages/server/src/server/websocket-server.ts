@@ -46,6 +46,8 @@ import type { ScriptRouteStore } from "./script-proxy.js";
 import type { WorkspaceScriptRuntimeStore } from "./workspace-script-runtime-store.js";
 import type { SpeechReadinessSnapshot, SpeechService } from "./speech/speech-runtime.js";
 import type { VoiceCallerContext, VoiceSpeakHandler } from "./voice-types.js";
+import type { WorkspaceTrustGate } from "./workspace-trust.js";
+import type { NativeApprovalGuard } from "./native-approval.js";
 import { computeNotificationPlan, type ClientPresenceState } from "./agent-attention-policy.js";
 import {
   buildAgentAttentionNotificationPayload,
@@ -71,9 +73,18 @@ interface PendingConnection {
   helloTimeout: ReturnType<typeof setTimeout> | null;
 }
 
+/** permessage-deflate the daemon accepts from clients that offer it. */
+export interface DaemonCompressionConfig {
+  level: number;
+  threshold: number;
+}
+
 interface WebSocketServerConfig {
   allowedOrigins: Set<string>;
   hostnames?: HostnamesConfig;
+  compression?: DaemonCompressionConfig | null;
+  workspaceTrust?: WorkspaceTrustGate | null;
+  nativeApproval?: NativeApprovalGuard | null;
 }
 
 type WebSocketRuntimeMetrics = SessionRuntimeMetrics & CheckoutDiffMetrics;
@@ -360,6 +371,8 @@ export class VoiceAssistantWebSocketServer {
   private readonly pushTokenStore: PushTokenStore;
   private readonly pushService: PushService;
   private readonly mcpBaseUrl: string | null;
+  private readonly workspaceTrust: WorkspaceTrustGate | null;
+  private readonly nativeApproval: NativeApprovalGuard | null;
   private speech!: SpeechService | null;
   private terminalManager!: TerminalManager | null;
   private scriptRouteStore!: ScriptRouteStore | null;
@@ -480,6 +493,8 @@ export class VoiceAssistantWebSocketServer {
     this.paseoHome = paseoHome;
     this.daemonConfigStore = daemonConfigStore;
     this.mcpBaseUrl = mcpBaseUrl;
+    this.workspaceTrust = wsConfig.workspaceTrust ?? null;
+    this.nativeApproval = wsConfig.nativeApproval ?? null;
     this.assignOptionalServices({
       speech,
       terminalManager,
@@ -580,11 +595,15 @@ export class VoiceAssistantWebSocketServer {
     wsConfig: WebSocketServerConfig,
     auth: DaemonAuthConfig | undefined,
   ): WebSocketServer {
-    const { allowedOrigins, hostnames } = wsConfig;
+    const { allowedOrigins, hostnames, compression } = wsConfig;
     const password = auth?.password;
     const wss = new WebSocketServer({
       server,
       path: "/ws",
+      // Negotiated per connection: only clients that offer the extension get it.
+      perMessageDeflate: compression
+        ? { threshold: compression.threshold, zlibDeflateOptions: { level: compression.level } }
+        : false,
       handleProtocols: (protocols) => selectWebSocketProtocol(protocols, password),
       verifyClient: ({ req }, callback) => {
         this.verifyWsUpgrade(req, allowedOrigins, hostnames, callback);
@@ -1461,11 +1480,82 @@ export class VoiceAssistantWebSocketServer {
     }
   }
 
+  /**
+   * Refuses requests that would execute in a folder the desktop app has not
+   * trusted. Checked here rather than in one client, so every transport
+   * (direct, socket, relay) is held to the same decisions.
+   */
+  private async rejectUntrustedWorkspace(
+    activeConnection: SessionConnection,
+    message: Extract<WSInboundMessage, { type: "session" }>["message"],
+  ): Promise<boolean> {
+    const workspace = await this.workspaceTrust?.findUntrustedWorkspace(message);
+    if (!workspace) {
+      return false;
+    }
+    activeConnection.connectionLogger.warn(
+      { requestType: message.type, workspace },
+      "Refused request in untrusted workspace",
+    );
+    const requestId = (message as { requestId?: unknown }).requestId;
+    if (typeof requestId === "string") {
+      activeConnection.session.emitServerMessage({
+        type: "rpc_error",
+        payload: {
+          requestId,
+          requestType: message.type,
+          error: `Workspace is not trusted: ${workspace}`,
+          code: "workspace_not_trusted",
+        },
+      });
+    }
+    return true;
+  }
+
+  /**
+   * Refuses allows for high-risk commands that the desktop app has not
+   * confirmed natively. The rpc_error carries the permission id so the client
+   * can ask the app for a confirmation and send the allow again.
+   */
+  private async rejectUnconfirmedApproval(
+    activeConnection: SessionConnection,
+    message: Extract<WSInboundMessage, { type: "session" }>["message"],
+  ): Promise<boolean> {
+    const permission = await this.nativeApproval?.findUnconfirmedApproval(message);
+    if (!permission) {
+      return false;
+    }
+    activeConnection.connectionLogger.warn(
+      {
+        agentId: permission.agentId,
+        requestId: permission.requestId,
+        riskCategory: permission.riskCategory,
+      },
+      "Refused high-risk approval without native confirmation",
+    );
+    activeConnection.session.emitServerMessage({
+      type: "rpc_error",
+      payload: {
+        requestId: permission.requestId,
+        requestType: message.type,
+        error: `Confirm this command in the desktop app to approve it: ${permission.command}`,
+        code: "native_approval_required",
+      },
+    });
+    return true;
+  }
+
   private async dispatchSessionMessage(
     activeConnection: SessionConnection,
     message: Extract<WSInboundMessage, { type: "session" }>,
   ): Promise<void> {
     this.recordInboundSessionRequestType(message.message.type);
+    if (
+      (await this.rejectUntrustedWorkspace(activeConnection, message.message)) ||
+      (await this.rejectUnconfirmedApproval(activeConnection, message.message))
+    ) {
+      return;
+    }
     const startMs = performance.now();
     await activeConnection.session.handleMessage(message.message);
     const durationMs = performance.now() - startMs;
@@ -0,0 +1,74 @@
+import { mkdtemp, rm, writeFile } from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, test } from "vitest";
+
+import { extractWorkspaceExecutionCwd } from "../shared/workspace-execution.js";
+import { createWorkspaceTrustGate, normalizeWorkspacePath } from "./workspace-trust.js";
+
+describe("normalizeWorkspacePath", () => {
+  test("strips trailing separators and folds case on case-insensitive platforms", () => {
+    expect(normalizeWorkspacePath("/Users/me/Repo/", "darwin")).toBe("/users/me/repo");
+    expect(normalizeWorkspacePath("/home/me/Repo/", "linux")).toBe("/home/me/Repo");
+    expect(normalizeWorkspacePath("C:\\Code\\Repo\\", "win32")).toBe("c:\\code\\repo");
+    expect(normalizeWorkspacePath("/", "linux")).toBe("/");
+  });
+});
+
+describe("extractWorkspaceExecutionCwd", () => {
+  test("finds the cwd of requests that run something in a workspace", () => {
+    expect(
+      extractWorkspaceExecutionCwd({
+        type: "create_agent_request",
+        config: { provider: "claude", cwd: "/work/repo" },
+      }),
+    ).toBe("/work/repo");
+    expect(
+      extractWorkspaceExecutionCwd({ type: "create_terminal_request", cwd: "/work/repo" }),
+    ).toBe("/work/repo");
+    expect(
+      extractWorkspaceExecutionCwd({ type: "checkout_status_request", cwd: "/work/repo" }),
+    ).toBeNull();
+  });
+});
+
+describe("createWorkspaceTrustGate", () => {
+  let root: string;
+  let filePath: string;
+
+  beforeEach(async () => {
+    root = await mkdtemp(path.join(os.tmpdir(), "paseo-workspace-trust-"));
+    filePath = path.join(root, "workspace-trust.json");
+  });
+
+  afterEach(async () => {
+    await rm(root, { recursive: true, force: true });
+  });
+
+  test("refuses execution outside trusted folders and follows later decisions", async () => {
+    const gate = createWorkspaceTrustGate({ filePath, platform: "linux" });
+    const request = { type: "create_terminal_request", cwd: "/work/repo/packages/app" };
+
+    expect(await gate.findUntrustedWorkspace(request)).toBe("/work/repo/packages/app");
+
+    await writeFile(
+      filePath,
+      JSON.stringify({
+        version: 1,
+        decisions: [
+          { path: "/work/repo", trusted: true, decidedAt: "" },
+          { path: "/work/repo/vendor", trusted: false, decidedAt: "" },
+        ],
+      }),
+    );
+
+    expect(await gate.findUntrustedWorkspace(request)).toBeNull();
+    expect(
+      await gate.findUntrustedWorkspace({
+        type: "create_terminal_request",
+        cwd: "/work/repo/vendor",
+      }),
+    ).toBe("/work/repo/vendor");
+    expect(await gate.findUntrustedWorkspace({ type: "fetch_agents_request" })).toBeNull();
+  });
+});
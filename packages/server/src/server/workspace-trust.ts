@@ -0,0 +1,124 @@
+import { readFile, realpath } from "node:fs/promises";
+import path from "node:path";
+import { extractWorkspaceExecutionCwd } from "../shared/workspace-execution.js";
+
+export interface WorkspaceTrustDecision {
+  path: string;
+  trusted: boolean;
+  decidedAt: string;
+}
+
+export function normalizeWorkspacePath(
+  workspacePath: string,
+  platform: NodeJS.Platform = process.platform,
+): string {
+  const pathApi = platform === "win32" ? path.win32 : path.posix;
+  const resolved = pathApi.resolve(workspacePath.normalize("NFC"));
+  const root = pathApi.parse(resolved).root;
+  const trimmed = resolved.length > root.length ? resolved.replace(/[\\/]+$/, "") : resolved;
+  // Default macOS and Windows volumes are case-insensitive.
+  return platform === "win32" || platform === "darwin" ? trimmed.toLowerCase() : trimmed;
+}
+
+/**
+ * Resolves symlinks before normalizing, so a link into or out of a trusted
+ * folder is judged by where it points. Trailing segments that do not exist
+ * yet, such as a worktree about to be created, are kept as written.
+ */
+export async function canonicalizeWorkspacePath(
+  workspacePath: string,
+  platform: NodeJS.Platform = process.platform,
+): Promise<string> {
+  const pathApi = platform === "win32" ? path.win32 : path.posix;
+  const resolved = pathApi.resolve(workspacePath.normalize("NFC"));
+  const missing: string[] = [];
+  let current = resolved;
+  for (;;) {
+    try {
+      const real = await realpath(current);
+      return normalizeWorkspacePath(pathApi.join(real, ...missing), platform);
+    } catch {
+      const parent = pathApi.dirname(current);
+      if (parent === current) {
+        return normalizeWorkspacePath(resolved, platform);
+      }
+      missing.unshift(pathApi.basename(current));
+      current = parent;
+    }
+  }
+}
+
+/** Most specific decision covering `normalizedPath`, so a nested revoke/deny wins over a parent. */
+export function findWorkspaceTrustDecision(input: {
+  decisions: WorkspaceTrustDecision[];
+  normalizedPath: string;
+  platform?: NodeJS.Platform;
+}): WorkspaceTrustDecision | null {
+  const separator = (input.platform ?? process.platform) === "win32" ? "\\" : "/";
+  let best: WorkspaceTrustDecision | null = null;
+  for (const decision of input.decisions) {
+    const prefix = decision.path.endsWith(separator) ? decision.path : decision.path + separator;
+    const covers =
+      input.normalizedPath === decision.path || input.normalizedPath.startsWith(prefix);
+    if (covers && (!best || decision.path.length > best.path.length)) {
+      best = decision;
+    }
+  }
+  return best;
+}
+
+function isRecord(value: unknown): value is Record<string, unknown> {
+  return typeof value === "object" && value !== null && !Array.isArray(value);
+}
+
+export function coerceWorkspaceTrustDecisions(value: unknown): WorkspaceTrustDecision[] {
+  if (!isRecord(value) || !Array.isArray(value.decisions)) {
+    return [];
+  }
+  return value.decisions.flatMap((entry: unknown): WorkspaceTrustDecision[] => {
+    if (!isRecord(entry) || typeof entry.path !== "string" || typeof entry.trusted !== "boolean") {
+      return [];
+    }
+    return [
+      {
+        path: entry.path,
+        trusted: entry.trusted,
+        decidedAt: typeof entry.decidedAt === "string" ? entry.decidedAt : "",
+      },
+    ];
+  });
+}
+
+export interface WorkspaceTrustGate {
+  /** The workspace `message` would execute in when it is not trusted, otherwise `null`. */
+  findUntrustedWorkspace(message: unknown): Promise<string | null>;
+}
+
+/**
+ * Enforces the decisions the desktop app records in `filePath`. The file is
+ * read on every execution request, so a decision made or revoked in the app
+ * applies to the next request from any client, including relayed ones.
+ */
+export function createWorkspaceTrustGate(input: {
+  filePath: string;
+  platform?: NodeJS.Platform;
+}): WorkspaceTrustGate {
+  const platform = input.platform ?? process.platform;
+  return {
+    async findUntrustedWorkspace(message) {
+      const cwd = extractWorkspaceExecutionCwd(message);
+      if (!cwd) {
+        return null;
+      }
+      const decisions = await readFile(input.filePath, "utf8")
+        .then((raw) => coerceWorkspaceTrustDecisions(JSON.parse(raw)))
+        .catch(() => []);
+      const decision = findWorkspaceTrustDecision({
+        decisions,
+        normalizedPath: await canonicalizeWorkspacePath(cwd, platform),
+        platform,
+      });
+      return decision?.trusted ? null : cwd;
+    },
+  };
+}
@@ -0,0 +1,39 @@
+function isRecord(value: unknown): value is Record<string, unknown> {
+  return typeof value === "object" && value !== null && !Array.isArray(value);
+}
+
+export type ApprovalRiskCategory = "destructive" | "privilege_escalation" | "remote_code";
+
+const RISK_PATTERNS: Array<{ category: ApprovalRiskCategory; pattern: RegExp }> = [
+  { category: "privilege_escalation", pattern: /(^|[;&|(]\s*)(sudo|doas|su|pkexec)\b/ },
+  { category: "remote_code", pattern: /\b(curl|wget)\b[^|]*\|\s*(sudo\s+)?(ba|z|da)?sh\b/ },
+  { category: "destructive", pattern: /\brm\s+(-\w*r\w*f|-\w*f\w*r|-r\s+-f|-f\s+-r)\b/ },
+  { category: "destructive", pattern: /\bgit\s+push\b.*(--force\b|--force-with-lease\b|\s-f\b)/ },
+  { category: "destructive", pattern: /\bgit\s+(reset\s+--hard|clean\s+-\w*f)/ },
+  { category: "destructive", pattern: /\b(mkfs(\.\w+)?|shred|dd\s+if=)/ },
+  { category: "destructive", pattern: /\bdrop\s+(table|database|schema)\b/i },
+];
+
+export function classifyApprovalRisk(command: string): ApprovalRiskCategory | null {
+  return RISK_PATTERNS.find(({ pattern }) => pattern.test(command))?.category ?? null;
+}
+
+/** The shell command a permission request would run, from its detail or its tool input. */
+export function extractPermissionCommand(
+  request: unknown,
+): { command: string; cwd: string | null } | null {
+  if (!isRecord(request)) {
+    return null;
+  }
+  if (isRecord(request.detail) && request.detail.type === "shell") {
+    const { command, cwd } = request.detail;
+    return typeof command === "string"
+      ? { command, cwd: typeof cwd === "string" ? cwd : null }
+      : null;
+  }
+  if (isRecord(request.input) && typeof request.input.command === "string") {
+    const { command, cwd } = request.input;
+    return { command, cwd: typeof cwd === "string" ? cwd : null };
+  }
+  return null;
+}
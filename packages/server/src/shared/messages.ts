@@ -1183,6 +1183,8 @@ export const AgentPermissionResponseMessageSchema = z.object({
   agentId: z.string(),
   requestId: z.string(),
   response: AgentPermissionResponseSchema,
+  // Desktop-signed confirmation the daemon requires before allowing a high-risk command.
+  nativeApproval: z.string().optional(),
 });
 
 const CheckoutErrorCodeSchema = z.enum([
@@ -0,0 +1,37 @@
+function isRecord(value: unknown): value is Record<string, unknown> {
+  return typeof value === "object" && value !== null && !Array.isArray(value);
+}
+
+// Requests after which the daemon runs something inside `cwd`: an agent, a
+// shell, git hooks or worktree setup scripts.
+const WORKSPACE_EXECUTION_REQUESTS = new Set([
+  "import_agent_request",
+  "create_terminal_request",
+  "create_paseo_worktree_request",
+  "open_project_request",
+  "checkout_commit_request",
+  "checkout_merge_request",
+  "checkout_merge_from_base_request",
+  "checkout_pull_request",
+  "checkout_push_request",
+  "checkout_switch_branch_request",
+  "stash_save_request",
+  "stash_pop_request",
+]);
+
+/**
+ * The workspace a session request would make the daemon execute in, or
+ * `null` when it runs nothing there.
+ */
+export function extractWorkspaceExecutionCwd(message: unknown): string | null {
+  if (!isRecord(message)) {
+    return null;
+  }
+  let cwd: unknown = null;
+  if (message.type === "create_agent_request") {
+    cwd = isRecord(message.config) ? message.config.cwd : null;
+  } else if (typeof message.type === "string" && WORKSPACE_EXECUTION_REQUESTS.has(message.type)) {
+    cwd = message.cwd;
+  }
+  return typeof cwd === "string" && cwd.trim().length > 0 ? cwd : null;
+}